@@ -0,0 +1,31 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use sqlx::postgres::PgRow;
+use sqlx::{Connection, Executor, PgConnection, Row};
+
+// a query with no arguments is sent over the simple query protocol, so every value comes
+// back in the TEXT format
+const QUERY: &str = "SELECT i::int8, i::int4, i::float8 / 7 FROM generate_series(1, 1000000) i";
+
+fn bench_pg_decode_text(c: &mut Criterion) {
+    let mut conn = sqlx_rt::block_on(PgConnection::connect(
+        &dotenv::var("DATABASE_URL").expect("DATABASE_URL must be set to run benchmarks"),
+    ))
+    .expect("failed to connect to PostgreSQL");
+
+    // fetch once up-front so only the decoding is measured
+    let rows: Vec<PgRow> =
+        sqlx_rt::block_on(conn.fetch_all(QUERY)).expect("failed to fetch rows for benchmark");
+
+    c.bench_function("decode 1M text rows", |b| {
+        b.iter(|| {
+            for row in &rows {
+                criterion::black_box(row.get::<i64, _>(0));
+                criterion::black_box(row.get::<i32, _>(1));
+                criterion::black_box(row.get::<f64, _>(2));
+            }
+        })
+    });
+}
+
+criterion_group!(pg_decode_text, bench_pg_decode_text);
+criterion_main!(pg_decode_text);
@@ -0,0 +1,110 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use futures::TryStreamExt;
+use sqlx::{Connection, Executor, PgConnection};
+
+const QUERIES: usize = 100_000;
+
+const ROWS_QUERY: &str = "SELECT i, i::text FROM generate_series(1, 50000) i";
+
+fn bench_pg_executor(c: &mut Criterion) {
+    let mut conn = sqlx_rt::block_on(PgConnection::connect(
+        &dotenv::var("DATABASE_URL").expect("DATABASE_URL must be set to run benchmarks"),
+    ))
+    .expect("failed to connect to PostgreSQL");
+
+    let mut group = c.benchmark_group("bench_pg_executor");
+
+    // each iteration is 100k round-trips, so keep the number of samples down
+    group.sample_size(10);
+
+    group.bench_function("execute (boxed)", |b| {
+        b.iter(|| {
+            sqlx_rt::block_on(async {
+                for _ in 0..QUERIES {
+                    criterion::black_box(
+                        (&mut conn)
+                            .execute_many(sqlx::query("SELECT 1"))
+                            .try_for_each(|_| async { Ok(()) })
+                            .await
+                            .expect("failed to execute query"),
+                    );
+                }
+            })
+        })
+    });
+
+    group.bench_function("execute (concrete)", |b| {
+        b.iter(|| {
+            sqlx_rt::block_on(async {
+                for _ in 0..QUERIES {
+                    criterion::black_box(
+                        conn.execute_concrete(sqlx::query("SELECT 1"))
+                            .await
+                            .expect("failed to execute query"),
+                    );
+                }
+            })
+        })
+    });
+
+    group.bench_function("fetch_optional (boxed)", |b| {
+        b.iter(|| {
+            sqlx_rt::block_on(async {
+                for _ in 0..QUERIES {
+                    criterion::black_box(
+                        (&mut conn)
+                            .fetch(sqlx::query("SELECT 1"))
+                            .try_next()
+                            .await
+                            .expect("failed to fetch row"),
+                    );
+                }
+            })
+        })
+    });
+
+    group.bench_function("fetch_optional (concrete)", |b| {
+        b.iter(|| {
+            sqlx_rt::block_on(async {
+                for _ in 0..QUERIES {
+                    criterion::black_box(
+                        conn.fetch_optional_concrete(sqlx::query("SELECT 1"))
+                            .await
+                            .expect("failed to fetch row"),
+                    );
+                }
+            })
+        })
+    });
+
+    group.bench_function("fetch_all 50k rows (streamed)", |b| {
+        b.iter(|| {
+            sqlx_rt::block_on(async {
+                let rows: Vec<_> = (&mut conn)
+                    .fetch(sqlx::query(ROWS_QUERY))
+                    .try_collect()
+                    .await
+                    .expect("failed to fetch rows");
+
+                criterion::black_box(rows);
+            })
+        })
+    });
+
+    group.bench_function("fetch_all 50k rows (buffered)", |b| {
+        b.iter(|| {
+            sqlx_rt::block_on(async {
+                criterion::black_box(
+                    conn.fetch_all_concrete(sqlx::query(ROWS_QUERY))
+                        .await
+                        .expect("failed to fetch rows"),
+                );
+            })
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(pg_executor, bench_pg_executor);
+criterion_main!(pg_executor);
@@ -0,0 +1,29 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use sqlx::{Connection, PgConnection};
+
+// a single 32MB value, sent in one `DataRow` much larger than the read buffer
+const QUERY: &str = "SELECT decode(repeat('ab', 32 * 1024 * 1024), 'hex')";
+
+fn bench_pg_large_value(c: &mut Criterion) {
+    let mut conn = sqlx_rt::block_on(PgConnection::connect(
+        &dotenv::var("DATABASE_URL").expect("DATABASE_URL must be set to run benchmarks"),
+    ))
+    .expect("failed to connect to PostgreSQL");
+
+    let mut group = c.benchmark_group("large value");
+    group.sample_size(10);
+
+    group.bench_function("fetch a 32MB bytea", |b| {
+        b.iter(|| {
+            let value: Vec<u8> = sqlx_rt::block_on(sqlx::query_scalar(QUERY).fetch_one(&mut conn))
+                .expect("failed to fetch value");
+
+            criterion::black_box(value)
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(pg_large_value, bench_pg_large_value);
+criterion_main!(pg_large_value);
@@ -0,0 +1,79 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use sqlx::postgres::PgRow;
+use sqlx::{Connection, Executor, PgConnection, Row};
+
+// counts allocations, to report how many are made per row
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const ROWS: usize = 10_000;
+
+// 30 columns, of which only the first 2 are read
+fn query() -> String {
+    let columns: Vec<String> = (0..30)
+        .map(|n| format!("i::int8 + {} AS c{}", n, n))
+        .collect();
+
+    format!(
+        "SELECT {} FROM generate_series(1, {}) i",
+        columns.join(", "),
+        ROWS
+    )
+}
+
+fn bench_pg_wide_rows(c: &mut Criterion) {
+    let mut conn = sqlx_rt::block_on(PgConnection::connect(
+        &dotenv::var("DATABASE_URL").expect("DATABASE_URL must be set to run benchmarks"),
+    ))
+    .expect("failed to connect to PostgreSQL");
+
+    let query = query();
+
+    let fetch_and_read = |conn: &mut PgConnection| {
+        let rows: Vec<PgRow> =
+            sqlx_rt::block_on(conn.fetch_all(&*query)).expect("failed to fetch rows");
+
+        for row in &rows {
+            criterion::black_box(row.get::<i64, _>(0));
+            criterion::black_box(row.get::<i64, _>(1));
+        }
+    };
+
+    // warm up the connection, then count the allocations of a single run
+    fetch_and_read(&mut conn);
+
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    fetch_and_read(&mut conn);
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+
+    println!(
+        "fetching {} rows of 30 columns and reading 2: {:.2} allocations per row",
+        ROWS,
+        allocations as f64 / ROWS as f64
+    );
+
+    c.bench_function("fetch 10k rows of 30 columns, read 2", |b| {
+        b.iter(|| fetch_and_read(&mut conn))
+    });
+}
+
+criterion_group!(pg_wide_rows, bench_pg_wide_rows);
+criterion_main!(pg_wide_rows);
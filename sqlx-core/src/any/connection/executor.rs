@@ -160,5 +160,6 @@ where
         parameters,
         nullable: info.nullable,
         columns: info.columns.into_iter().map(Into::into).collect(),
+        returns_rows: info.returns_rows,
     }
 }
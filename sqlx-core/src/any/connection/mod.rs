@@ -1,7 +1,7 @@
 use futures_core::future::BoxFuture;
 
 use crate::any::{Any, AnyConnectOptions, AnyKind};
-use crate::connection::Connection;
+use crate::connection::{Connection, ConnectionStats};
 use crate::error::Error;
 
 #[cfg(feature = "postgres")]
@@ -182,4 +182,32 @@ impl Connection for AnyConnection {
     fn should_flush(&self) -> bool {
         delegate_to!(self.should_flush())
     }
+
+    #[doc(hidden)]
+    fn is_busy(&self) -> bool {
+        delegate_to!(self.is_busy())
+    }
+
+    #[doc(hidden)]
+    fn is_broken(&self) -> bool {
+        delegate_to!(self.is_broken())
+    }
+
+    fn is_alive(&mut self) -> bool {
+        delegate_to_mut!(self.is_alive())
+    }
+
+    #[doc(hidden)]
+    fn shrink_buffers(&mut self) {
+        delegate_to_mut!(self.shrink_buffers())
+    }
+
+    #[doc(hidden)]
+    fn db_system(&self) -> &'static str {
+        delegate_to!(self.db_system())
+    }
+
+    fn stats(&self) -> ConnectionStats {
+        delegate_to!(self.stats())
+    }
 }
@@ -1,4 +1,5 @@
 use crate::error::Error;
+use crate::url_scheme::UrlScheme;
 use std::str::FromStr;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -19,49 +20,23 @@ pub enum AnyKind {
 impl FromStr for AnyKind {
     type Err = Error;
 
-    fn from_str(uri: &str) -> Result<Self, Self::Err> {
-        match uri {
+    fn from_str(url: &str) -> Result<Self, Self::Err> {
+        match UrlScheme::from_url(url)? {
             #[cfg(feature = "postgres")]
-            _ if uri.starts_with("postgres:") || uri.starts_with("postgresql:") => {
-                Ok(AnyKind::Postgres)
-            }
-
-            #[cfg(not(feature = "postgres"))]
-            _ if uri.starts_with("postgres:") || uri.starts_with("postgresql:") => {
-                Err(Error::Configuration("database URL has the scheme of a PostgreSQL database but the `postgres` feature is not enabled".into()))
-            }
+            UrlScheme::Postgres => Ok(AnyKind::Postgres),
 
             #[cfg(feature = "mysql")]
-            _ if uri.starts_with("mysql:") || uri.starts_with("mariadb:") => {
-                Ok(AnyKind::MySql)
-            }
-
-            #[cfg(not(feature = "mysql"))]
-            _ if uri.starts_with("mysql:") || uri.starts_with("mariadb:") => {
-                Err(Error::Configuration("database URL has the scheme of a MySQL database but the `mysql` feature is not enabled".into()))
-            }
+            UrlScheme::MySql => Ok(AnyKind::MySql),
 
             #[cfg(feature = "sqlite")]
-            _ if uri.starts_with("sqlite:") => {
-                Ok(AnyKind::Sqlite)
-            }
-
-            #[cfg(not(feature = "sqlite"))]
-            _ if uri.starts_with("sqlite:") => {
-                Err(Error::Configuration("database URL has the scheme of a SQLite database but the `sqlite` feature is not enabled".into()))
-            }
+            UrlScheme::Sqlite => Ok(AnyKind::Sqlite),
 
             #[cfg(feature = "mssql")]
-            _ if uri.starts_with("mssql:") || uri.starts_with("sqlserver:") => {
-                Ok(AnyKind::Mssql)
-            }
-
-            #[cfg(not(feature = "mssql"))]
-            _ if uri.starts_with("mssql:") || uri.starts_with("sqlserver:") => {
-                Err(Error::Configuration("database URL has the scheme of a MSSQL database but the `mssql` feature is not enabled".into()))
-            }
+            UrlScheme::Mssql => Ok(AnyKind::Mssql),
 
-            _ => Err(Error::Configuration(format!("unrecognized database url: {:?}", uri).into()))
+            // `from_url` fails for these already
+            #[allow(unreachable_patterns)]
+            scheme => Err(scheme.not_enabled()),
         }
     }
 }
@@ -2,7 +2,9 @@ use crate::any::connection::AnyConnectionKind;
 use crate::any::kind::AnyKind;
 use crate::any::{Any, AnyConnection};
 use crate::error::Error;
-use crate::migrate::{AppliedMigration, Migrate, MigrateDatabase, MigrateError, Migration};
+use crate::migrate::{
+    AppliedMigration, Migrate, MigrateDatabase, MigrateError, Migration, MigrationEvent,
+};
 use futures_core::future::BoxFuture;
 use std::str::FromStr;
 use std::time::Duration;
@@ -208,6 +210,29 @@ impl Migrate for AnyConnection {
         }
     }
 
+    fn apply_with_progress<'e: 'm, 'm>(
+        &'e mut self,
+        migration: &'m Migration,
+        progress: &'m mut (dyn FnMut(MigrationEvent) + Send),
+    ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
+        match &mut self.0 {
+            #[cfg(feature = "postgres")]
+            AnyConnectionKind::Postgres(conn) => conn.apply_with_progress(migration, progress),
+
+            #[cfg(feature = "sqlite")]
+            AnyConnectionKind::Sqlite(conn) => conn.apply_with_progress(migration, progress),
+
+            #[cfg(feature = "mysql")]
+            AnyConnectionKind::MySql(conn) => conn.apply_with_progress(migration, progress),
+
+            #[cfg(feature = "mssql")]
+            AnyConnectionKind::Mssql(_conn) => {
+                let _ = (migration, progress);
+                unimplemented!()
+            }
+        }
+    }
+
     fn revert<'e: 'm, 'm>(
         &'e mut self,
         migration: &'m Migration,
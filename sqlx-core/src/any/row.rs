@@ -67,6 +67,54 @@ impl Row for AnyRow {
             AnyRowKind::Mssql(row) => row.try_get_raw(index).map(Into::into),
         }
     }
+
+    fn received_len(&self) -> usize {
+        match &self.kind {
+            #[cfg(feature = "postgres")]
+            AnyRowKind::Postgres(row) => row.received_len(),
+
+            #[cfg(feature = "mysql")]
+            AnyRowKind::MySql(row) => row.received_len(),
+
+            #[cfg(feature = "sqlite")]
+            AnyRowKind::Sqlite(row) => row.received_len(),
+
+            #[cfg(feature = "mssql")]
+            AnyRowKind::Mssql(row) => row.received_len(),
+        }
+    }
+
+    fn text_results(&self) -> bool {
+        match &self.kind {
+            #[cfg(feature = "postgres")]
+            AnyRowKind::Postgres(row) => row.text_results(),
+
+            #[cfg(feature = "mysql")]
+            AnyRowKind::MySql(row) => row.text_results(),
+
+            #[cfg(feature = "sqlite")]
+            AnyRowKind::Sqlite(row) => row.text_results(),
+
+            #[cfg(feature = "mssql")]
+            AnyRowKind::Mssql(row) => row.text_results(),
+        }
+    }
+
+    fn is_ambiguous(&self, name: &str) -> bool {
+        match &self.kind {
+            #[cfg(feature = "postgres")]
+            AnyRowKind::Postgres(row) => row.is_ambiguous(name),
+
+            #[cfg(feature = "mysql")]
+            AnyRowKind::MySql(row) => row.is_ambiguous(name),
+
+            #[cfg(feature = "sqlite")]
+            AnyRowKind::Sqlite(row) => row.is_ambiguous(name),
+
+            #[cfg(feature = "mssql")]
+            AnyRowKind::Mssql(row) => row.is_ambiguous(name),
+        }
+    }
 }
 
 impl<'i> ColumnIndex<AnyRow> for &'i str
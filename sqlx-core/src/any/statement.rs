@@ -1,9 +1,7 @@
 use crate::any::{Any, AnyArguments, AnyColumn, AnyColumnIndex, AnyTypeInfo};
-use crate::column::ColumnIndex;
+use crate::column::{ColumnIndex, ColumnNames};
 use crate::error::Error;
-use crate::ext::ustr::UStr;
 use crate::statement::Statement;
-use crate::HashMap;
 use either::Either;
 use std::borrow::Cow;
 use std::sync::Arc;
@@ -11,7 +9,7 @@ use std::sync::Arc;
 pub struct AnyStatement<'q> {
     pub(crate) sql: Cow<'q, str>,
     pub(crate) parameters: Option<Either<Vec<AnyTypeInfo>, usize>>,
-    pub(crate) column_names: Arc<HashMap<UStr, usize>>,
+    pub(crate) column_names: Arc<ColumnNames>,
     pub(crate) columns: Vec<AnyColumn>,
 }
 
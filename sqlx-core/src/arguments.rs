@@ -1,7 +1,7 @@
 //! Types and traits for passing arguments to SQL queries.
 
 use crate::database::{Database, HasArguments};
-use crate::encode::Encode;
+use crate::encode::{Encode, RawValue};
 use crate::types::Type;
 
 /// A tuple of arguments to be sent to the database.
@@ -16,6 +16,25 @@ pub trait Arguments<'q>: Send + Sized + Default {
     fn add<T>(&mut self, value: T)
     where
         T: 'q + Send + Encode<'q, Self::Database> + Type<Self::Database>;
+
+    /// Add an already encoded value, or `NULL`, to the end of the arguments.
+    ///
+    /// The value is sent as is with the given type information and is not checked in any way.
+    /// See [`RawValue`].
+    fn add_raw(
+        &mut self,
+        type_info: <Self::Database as Database>::TypeInfo,
+        value: Option<&'q [u8]>,
+    ) where
+        RawValue<'q, Self::Database>: Encode<'q, Self::Database> + Type<Self::Database>,
+    {
+        self.add(RawValue::new(type_info, value));
+    }
+
+    #[doc(hidden)]
+    // marks the arguments as those of a query made with `query_named`, so that the names of
+    // its parameters are checked even when no value is bound by name
+    fn expect_named(&mut self) {}
 }
 
 pub trait IntoArguments<'q, DB: HasArguments<'q>>: Sized + Send {
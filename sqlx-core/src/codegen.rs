@@ -0,0 +1,303 @@
+//! Generation of Rust structs from the tables of a database.
+//!
+//! ```rust,ignore
+//! use sqlx::codegen::{self, CodegenOptions};
+//!
+//! let mut conn = PgConnection::connect("postgres://localhost/mydb").await?;
+//! let source = codegen::structs_for_schema(&mut conn, Some("public"), &CodegenOptions::new()).await?;
+//!
+//! std::fs::write("src/models.rs", source)?;
+//! ```
+
+use std::fmt::Write;
+
+use crate::error::Error;
+use crate::introspect::{ColumnInfo, Introspect};
+use crate::type_info::TypeInfo;
+use crate::type_mapping::TypeMapping;
+
+/// How the name of a column is turned into the name of a field.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RenameRule {
+    /// Use the name of the column as is, e.g. `createdAt` stays `createdAt`.
+    Passthrough,
+
+    /// Convert the name of the column to `snake_case`, e.g. `createdAt` becomes `created_at`.
+    SnakeCase,
+}
+
+/// Options for [`structs_for_schema`].
+#[derive(Debug, Clone)]
+pub struct CodegenOptions {
+    field_names: RenameRule,
+    derives: Vec<String>,
+}
+
+impl Default for CodegenOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CodegenOptions {
+    /// Creates the default options: fields named in `snake_case`, and structs that derive
+    /// `Debug`, `Clone` and `sqlx::FromRow`.
+    pub fn new() -> Self {
+        Self {
+            field_names: RenameRule::SnakeCase,
+            derives: vec!["Debug".into(), "Clone".into(), "sqlx::FromRow".into()],
+        }
+    }
+
+    /// Sets how the name of a column is turned into the name of a field.
+    ///
+    /// Either way, characters that cannot appear in an identifier are replaced with `_` and
+    /// keywords are escaped (`type` becomes `r#type`). A field whose name differs from its
+    /// column is given a `#[sqlx(rename = "..")]` attribute.
+    ///
+    /// Default: [`RenameRule::SnakeCase`].
+    pub fn field_names(mut self, rule: RenameRule) -> Self {
+        self.field_names = rule;
+        self
+    }
+
+    /// Adds a trait to derive on every struct, e.g. `serde::Serialize`.
+    pub fn derive(mut self, path: &str) -> Self {
+        self.derives.push(path.to_owned());
+        self
+    }
+}
+
+/// Generates the source of a struct for each table in `schema`, to be decoded from its rows
+/// with [`FromRow`](crate::from_row::FromRow).
+///
+/// If `schema` is `None`, the current schema is used, as in
+/// [`introspect::tables`](crate::introspect::tables).
+///
+/// Each table gets a struct named after it in `UpperCamelCase`, with a field per column whose
+/// type is chosen as the query macros would choose it, wrapped in an `Option` if the column is
+/// nullable. A column of a type that no Rust type is known for (or whose Rust type requires a
+/// feature that is not enabled) is left out, with a comment telling why.
+///
+/// Tables and columns are sorted by name, so the same schema always generates the same source
+/// and the output can be committed and diffed.
+pub async fn structs_for_schema<C>(
+    conn: &mut C,
+    schema: Option<&str>,
+    options: &CodegenOptions,
+) -> Result<String, Error>
+where
+    C: Introspect,
+    C::Database: TypeMapping,
+{
+    let mut tables = conn.tables(schema).await?;
+    tables.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut source =
+        String::from("// Generated by `sqlx::codegen::structs_for_schema`; do not edit by hand.\n");
+
+    for table in tables {
+        let qualified = format!(
+            "{}.{}",
+            C::quote_identifier(&table.schema),
+            C::quote_identifier(&table.name)
+        );
+
+        let mut columns = conn.columns(&qualified).await?;
+        columns.sort_by(|a, b| a.name.cmp(&b.name));
+
+        source.push('\n');
+        write_struct(&mut source, &table.name, &columns, options);
+    }
+
+    Ok(source)
+}
+
+fn write_struct<DB: TypeMapping>(
+    source: &mut String,
+    table: &str,
+    columns: &[ColumnInfo<DB>],
+    options: &CodegenOptions,
+) {
+    let mut fields = String::new();
+    let mut snake_case = true;
+
+    for column in columns {
+        let rust_type = match DB::return_type_for_id(&column.type_info) {
+            Some(rust_type) => rust_type,
+
+            None => {
+                let reason = match DB::get_feature_gate(&column.type_info) {
+                    Some(feature) => format!("requires the `{}` feature", feature),
+                    None => "is not supported".to_owned(),
+                };
+
+                let _ = writeln!(
+                    fields,
+                    "    // column `{}`: type {} {}",
+                    column.name,
+                    column.type_info.name(),
+                    reason
+                );
+
+                continue;
+            }
+        };
+
+        let field = field_name(&column.name, options.field_names);
+
+        if field.trim_start_matches("r#") != column.name {
+            let _ = writeln!(fields, "    #[sqlx(rename = {:?})]", column.name);
+        }
+
+        snake_case &= !field.chars().any(|c| c.is_ascii_uppercase());
+
+        if column.nullable {
+            let _ = writeln!(fields, "    pub {}: Option<{}>,", field, rust_type);
+        } else {
+            let _ = writeln!(fields, "    pub {}: {},", field, rust_type);
+        }
+    }
+
+    let _ = writeln!(source, "#[derive({})]", options.derives.join(", "));
+
+    if !snake_case {
+        source.push_str("#[allow(non_snake_case)]\n");
+    }
+
+    let _ = writeln!(source, "pub struct {} {{", struct_name(table));
+    source.push_str(&fields);
+    source.push_str("}\n");
+}
+
+// keywords that can be used as identifiers in their raw form, e.g. `r#type`
+const RAW_KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in", "let", "loop",
+    "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "static",
+    "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use", "virtual",
+    "where", "while", "yield",
+];
+
+// keywords that cannot be used as identifiers at all
+const RESERVED_KEYWORDS: &[&str] = &["crate", "self", "Self", "super"];
+
+fn field_name(column: &str, rule: RenameRule) -> String {
+    let name = match rule {
+        RenameRule::Passthrough => column.to_owned(),
+        RenameRule::SnakeCase => to_snake_case(column),
+    };
+
+    let mut ident: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+
+    if ident.is_empty() {
+        ident.push('_');
+    }
+
+    if ident == "_" || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert(0, '_');
+    }
+
+    escape_keyword(ident)
+}
+
+fn struct_name(table: &str) -> String {
+    let mut name = String::with_capacity(table.len());
+
+    for word in table.split(|c: char| !c.is_ascii_alphanumeric()) {
+        let mut chars = word.chars();
+
+        if let Some(first) = chars.next() {
+            name.push(first.to_ascii_uppercase());
+            name.extend(chars);
+        }
+    }
+
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert_str(0, "Table");
+    }
+
+    escape_keyword(name)
+}
+
+fn escape_keyword(ident: String) -> String {
+    if RAW_KEYWORDS.contains(&&*ident) {
+        format!("r#{}", ident)
+    } else if RESERVED_KEYWORDS.contains(&&*ident) {
+        ident + "_"
+    } else {
+        ident
+    }
+}
+
+// `createdAt`, `CreatedAt` and `CREATED_AT` all become `created_at`
+fn to_snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut snake = String::with_capacity(name.len() + 4);
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let prev = if i > 0 { chars.get(i - 1) } else { None };
+            let next = chars.get(i + 1);
+
+            // a word starts after a lowercase letter or a digit, or at the last capital of an
+            // acronym followed by a lowercase letter, as in `HTTPStatus`
+            let starts_word = prev.map_or(false, |p| p.is_lowercase() || p.is_ascii_digit())
+                || (prev.map_or(false, |p| p.is_uppercase())
+                    && next.map_or(false, |n| n.is_lowercase()));
+
+            if starts_word && !snake.ends_with('_') {
+                snake.push('_');
+            }
+
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+
+    snake
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_converts_names_to_snake_case() {
+        assert_eq!(to_snake_case("created_at"), "created_at");
+        assert_eq!(to_snake_case("createdAt"), "created_at");
+        assert_eq!(to_snake_case("CreatedAt"), "created_at");
+        assert_eq!(to_snake_case("CREATED_AT"), "created_at");
+        assert_eq!(to_snake_case("HTTPStatus"), "http_status");
+        assert_eq!(to_snake_case("address2Line"), "address2_line");
+    }
+
+    #[test]
+    fn it_escapes_field_names() {
+        assert_eq!(field_name("type", RenameRule::SnakeCase), "r#type");
+        assert_eq!(field_name("self", RenameRule::SnakeCase), "self_");
+        assert_eq!(field_name("user id", RenameRule::SnakeCase), "user_id");
+        assert_eq!(field_name("2fa", RenameRule::SnakeCase), "_2fa");
+        assert_eq!(field_name("", RenameRule::SnakeCase), "__");
+        assert_eq!(field_name("createdAt", RenameRule::SnakeCase), "created_at");
+        assert_eq!(
+            field_name("createdAt", RenameRule::Passthrough),
+            "createdAt"
+        );
+        assert_eq!(field_name("Type", RenameRule::Passthrough), "Type");
+    }
+
+    #[test]
+    fn it_names_structs_after_tables() {
+        assert_eq!(struct_name("users"), "Users");
+        assert_eq!(struct_name("user_profiles"), "UserProfiles");
+        assert_eq!(struct_name("UserProfiles"), "UserProfiles");
+        assert_eq!(struct_name("audit-log"), "AuditLog");
+        assert_eq!(struct_name("2020_events"), "Table2020Events");
+        assert_eq!(struct_name("self"), "Self_");
+    }
+}
@@ -1,6 +1,10 @@
 use crate::database::Database;
 use crate::error::Error;
+use crate::ext::ustr::UStr;
+use crate::HashMap;
+use std::collections::HashSet;
 use std::fmt::Debug;
+use std::ops::Deref;
 
 pub trait Column: private_column::Sealed + 'static + Send + Sync + Debug {
     type Database: Database;
@@ -26,6 +30,58 @@ pub(crate) mod private_column {
     pub trait Sealed {}
 }
 
+/// The ordinals of the columns of a result set or statement by their names, which are
+/// shared by its rows.
+///
+/// A name that more than one column has refers to the first of them, and is ambiguous.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct ColumnNames {
+    ordinals: HashMap<UStr, usize>,
+    // worked out once for the result set, rather than for every row `#[derive(FromRow)]`
+    // decodes
+    ambiguous: HashSet<UStr>,
+}
+
+impl ColumnNames {
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        Self {
+            ordinals: HashMap::with_capacity(capacity),
+            ambiguous: HashSet::new(),
+        }
+    }
+
+    /// Adds the column at `ordinal`, unless a column before it has the same name.
+    pub(crate) fn insert(&mut self, name: UStr, ordinal: usize) {
+        if self.ordinals.contains_key(&*name) {
+            self.ambiguous.insert(name);
+        } else {
+            self.ordinals.insert(name, ordinal);
+        }
+    }
+
+    pub(crate) fn reserve(&mut self, additional: usize) {
+        self.ordinals.reserve(additional);
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.ordinals.clear();
+        self.ambiguous.clear();
+    }
+
+    /// Whether more than one column is named `name`.
+    pub(crate) fn is_ambiguous(&self, name: &str) -> bool {
+        self.ambiguous.contains(name)
+    }
+}
+
+impl Deref for ColumnNames {
+    type Target = HashMap<UStr, usize>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.ordinals
+    }
+}
+
 /// A type that can be used to index into a [`Row`] or [`Statement`].
 ///
 /// The [`get`] and [`try_get`] methods of [`Row`] accept any type that implements `ColumnIndex`.
@@ -95,3 +151,28 @@ mod private_column_index {
     impl Sealed for str {}
     impl<T> Sealed for &'_ T where T: Sealed + ?Sized {}
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ColumnNames;
+
+    #[test]
+    fn test_column_names_ambiguous() {
+        let mut names = ColumnNames::default();
+
+        names.insert("id".into(), 0);
+        names.insert("name".into(), 1);
+        names.insert("id".into(), 2);
+
+        assert_eq!(names.get("id"), Some(&0));
+        assert_eq!(names.get("name"), Some(&1));
+        assert!(names.is_ambiguous("id"));
+        assert!(!names.is_ambiguous("name"));
+        assert!(!names.is_ambiguous("missing"));
+
+        names.clear();
+
+        assert!(names.get("id").is_none());
+        assert!(!names.is_ambiguous("id"));
+    }
+}
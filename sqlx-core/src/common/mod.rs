@@ -1,6 +1,16 @@
+#[cfg(any(feature = "postgres", feature = "mysql"))]
+mod operation;
 mod statement_cache;
 
+#[cfg(test)]
+pub(crate) mod counting_alloc;
+
+#[cfg(any(feature = "postgres", feature = "mysql"))]
+pub(crate) use operation::OperationState;
 pub(crate) use statement_cache::StatementCache;
+
+#[cfg(feature = "postgres")]
+pub(crate) use statement_cache::SharedStatementCache;
 use std::fmt::{Debug, Formatter};
 use std::ops::{Deref, DerefMut};
 
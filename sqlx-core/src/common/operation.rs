@@ -0,0 +1,228 @@
+//! Tracks the operation a connection is running, so that starting another one while it is
+//! unfinished returns [`Error::ConnectionBusy`] instead of interleaving with its messages.
+
+use crate::error::Error;
+use futures_core::Stream;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+// no operation is running
+const IDLE: u8 = 0;
+
+// an operation is being polled; operations started meanwhile are part of it
+const RUNNING: u8 = 1;
+
+// an operation was started and has not finished, and is not being polled; if it was dropped,
+// it never will
+const SUSPENDED: u8 = 2;
+
+/// The state of the operation running on a connection.
+///
+/// Its clones share the state, so that the futures and streams of the operations, which borrow
+/// the connection itself for as long as they run, can keep it up to date.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct OperationState(Arc<AtomicU8>);
+
+impl OperationState {
+    /// Whether an operation was started and has not finished.
+    pub(crate) fn is_unfinished(&self) -> bool {
+        self.0.load(Ordering::Acquire) == SUSPENDED
+    }
+
+    /// Returns [`Error::ConnectionBusy`] if an operation was started and has not finished.
+    pub(crate) fn ensure_finished(&self) -> Result<(), Error> {
+        if self.is_unfinished() {
+            return Err(Error::ConnectionBusy);
+        }
+
+        Ok(())
+    }
+
+    /// Runs `future` as an operation, which finishes once it completes.
+    pub(crate) fn future<F>(self, future: F) -> Operation<F> {
+        Operation::new(self.0, future)
+    }
+
+    /// Runs `stream` as an operation, which finishes once it ends or returns an error.
+    pub(crate) fn stream<S>(self, stream: S) -> Operation<S> {
+        Operation::new(self.0, stream)
+    }
+}
+
+pub(crate) struct Operation<T> {
+    state: Arc<AtomicU8>,
+    inner: T,
+
+    // whether the operation was started within another one, which is tracked instead
+    nested: Option<bool>,
+    finished: bool,
+}
+
+impl<T> Operation<T> {
+    fn new(state: Arc<AtomicU8>, inner: T) -> Self {
+        Self {
+            state,
+            inner,
+            nested: None,
+            finished: false,
+        }
+    }
+
+    // starts the operation when it is first polled, so that one which is never polled is not
+    // left unfinished
+    fn enter(&mut self) -> Result<bool, Error> {
+        let nested = match self.nested {
+            Some(nested) => nested,
+
+            None => {
+                let nested = match self.state.load(Ordering::Acquire) {
+                    SUSPENDED => return Err(Error::ConnectionBusy),
+                    state => state == RUNNING,
+                };
+
+                self.nested = Some(nested);
+                nested
+            }
+        };
+
+        if !nested {
+            self.state.store(RUNNING, Ordering::Release);
+        }
+
+        Ok(nested)
+    }
+
+    fn leave(&mut self, finished: bool) {
+        self.finished |= finished;
+
+        let state = if self.finished { IDLE } else { SUSPENDED };
+        self.state.store(state, Ordering::Release);
+    }
+}
+
+impl<F, T> Future for Operation<F>
+where
+    F: Future<Output = Result<T, Error>> + Unpin,
+{
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+
+        if this.enter()? {
+            return Pin::new(&mut this.inner).poll(cx);
+        }
+
+        let output = Pin::new(&mut this.inner).poll(cx);
+        this.leave(output.is_ready());
+
+        output
+    }
+}
+
+impl<S, T> Stream for Operation<S>
+where
+    S: Stream<Item = Result<T, Error>> + Unpin,
+{
+    type Item = S::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+
+        match this.enter() {
+            Ok(true) => return Pin::new(&mut this.inner).poll_next(cx),
+            Ok(false) => {}
+
+            Err(error) => {
+                // the stream ends after the error, without touching the other operation
+                if this.finished {
+                    return Poll::Ready(None);
+                }
+
+                this.finished = true;
+                return Poll::Ready(Some(Err(error)));
+            }
+        }
+
+        let item = Pin::new(&mut this.inner).poll_next(cx);
+        this.leave(matches!(
+            item,
+            Poll::Ready(None) | Poll::Ready(Some(Err(_)))
+        ));
+
+        item
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OperationState;
+    use crate::error::Error;
+    use futures_util::{future, stream, FutureExt, StreamExt};
+
+    #[test]
+    fn test_finished_operation() {
+        let state = OperationState::default();
+
+        let output = state
+            .clone()
+            .future(future::ready(Ok::<_, Error>(1)))
+            .now_or_never();
+        assert!(matches!(output, Some(Ok(1))));
+        assert!(!state.is_unfinished());
+
+        let items = stream::iter(vec![Ok::<_, Error>(1), Ok(2)]);
+        let items: Vec<_> = state
+            .clone()
+            .stream(items)
+            .collect()
+            .now_or_never()
+            .unwrap();
+        assert_eq!(items.len(), 2);
+        assert!(!state.is_unfinished());
+    }
+
+    #[test]
+    fn test_abandoned_operation() {
+        let state = OperationState::default();
+
+        // dropped after the first item, before the stream ended
+        let mut items = state
+            .clone()
+            .stream(stream::iter(vec![Ok::<_, Error>(1), Ok(2)]));
+        assert!(matches!(items.next().now_or_never(), Some(Some(Ok(1)))));
+        drop(items);
+
+        assert!(state.is_unfinished());
+
+        let output = state
+            .clone()
+            .future(future::ready(Ok::<_, Error>(1)))
+            .now_or_never();
+        assert!(matches!(output, Some(Err(Error::ConnectionBusy))));
+
+        let mut items = state.clone().stream(stream::iter(vec![Ok::<_, Error>(1)]));
+        assert!(matches!(
+            items.next().now_or_never(),
+            Some(Some(Err(Error::ConnectionBusy)))
+        ));
+        assert!(matches!(items.next().now_or_never(), Some(None)));
+    }
+
+    #[test]
+    fn test_nested_operation() {
+        let state = OperationState::default();
+
+        // an operation started while another one is polled is part of it
+        let inner = state.clone().future(future::ready(Ok::<_, Error>(1)));
+        let outer = state
+            .clone()
+            .future(inner.map(|inner| inner.map(|value| value + 1)));
+
+        assert!(matches!(outer.now_or_never(), Some(Ok(2))));
+        assert!(!state.is_unfinished());
+    }
+}
@@ -1,10 +1,15 @@
 use hashlink::lru_cache::LruCache;
 
+#[cfg(feature = "postgres")]
+use std::sync::Mutex;
+
 /// A cache for prepared statements. When full, the least recently used
 /// statement gets removed.
 #[derive(Debug)]
 pub struct StatementCache<T> {
     inner: LruCache<String, T>,
+    hits: u64,
+    misses: u64,
 }
 
 impl<T> StatementCache<T> {
@@ -12,12 +17,29 @@ impl<T> StatementCache<T> {
     pub fn new(capacity: usize) -> Self {
         Self {
             inner: LruCache::new(capacity),
+            hits: 0,
+            misses: 0,
         }
     }
 
     /// Returns a mutable reference to the value corresponding to the given key
-    /// in the cache, if any.
+    /// in the cache, if any, counting the lookup as a hit or a miss.
     pub fn get_mut(&mut self, k: &str) -> Option<&mut T> {
+        let value = self.inner.get_mut(k);
+
+        if value.is_some() {
+            self.hits += 1;
+        } else {
+            self.misses += 1;
+        }
+
+        value
+    }
+
+    /// Returns a mutable reference to the value corresponding to the given key in the cache, if
+    /// any, without counting the lookup.
+    #[cfg(feature = "mysql")]
+    pub fn peek_mut(&mut self, k: &str) -> Option<&mut T> {
         self.inner.get_mut(k)
     }
 
@@ -38,11 +60,29 @@ impl<T> StatementCache<T> {
         lru_item
     }
 
+    /// Removes the statement of the given key from the cache.
+    #[cfg(any(feature = "postgres", feature = "mysql"))]
+    pub fn remove(&mut self, k: &str) -> Option<T> {
+        self.inner.remove(k)
+    }
+
     /// The number of statements in the cache.
     pub fn len(&self) -> usize {
         self.inner.len()
     }
 
+    /// The number of lookups that found a statement.
+    #[allow(dead_code)] // Only used for some `cfg`s
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// The number of lookups that found no statement.
+    #[allow(dead_code)] // Only used for some `cfg`s
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
     /// Removes the least recently used item from the cache.
     pub fn remove_lru(&mut self) -> Option<T> {
         self.inner.remove_lru().map(|(_, v)| v)
@@ -70,3 +110,77 @@ impl<T> StatementCache<T> {
         self.capacity() > 0
     }
 }
+
+/// A [`StatementCache`] shared by the connections opened with the same options, such as those of
+/// a pool, for what one connection learned about a statement that another can reuse.
+#[cfg(feature = "postgres")]
+#[derive(Debug)]
+pub struct SharedStatementCache<T> {
+    inner: Mutex<StatementCache<T>>,
+}
+
+#[cfg(feature = "postgres")]
+impl<T: Clone> SharedStatementCache<T> {
+    /// Create a new cache with the given capacity.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Mutex::new(StatementCache::new(capacity)),
+        }
+    }
+
+    /// Returns a copy of the value corresponding to the given key in the cache, if any.
+    pub fn get(&self, k: &str) -> Option<T> {
+        self.inner.lock().unwrap().get_mut(k).cloned()
+    }
+
+    /// Inserts a value to the cache, replacing the value of the same key or, if the cache is
+    /// full, the least recently used one.
+    pub fn insert(&self, k: &str, v: T) {
+        let mut inner = self.inner.lock().unwrap();
+
+        if inner.is_enabled() {
+            inner.insert(k, v);
+        }
+    }
+
+    /// Removes the value of the given key from the cache.
+    pub fn remove(&self, k: &str) {
+        self.inner.lock().unwrap().remove(k);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_evicts_the_least_recently_used_statement() {
+        let mut cache = StatementCache::new(2);
+
+        assert!(cache.insert("SELECT 1", 1).is_none());
+        assert!(cache.insert("SELECT 2", 2).is_none());
+
+        // using the first statement makes the second the least recently used
+        assert_eq!(cache.get_mut("SELECT 1"), Some(&mut 1));
+        assert_eq!(cache.insert("SELECT 3", 3), Some(2));
+
+        assert_eq!(cache.get_mut("SELECT 2"), None);
+        assert_eq!(cache.get_mut("SELECT 3"), Some(&mut 3));
+
+        // replacing a statement returns the replaced one without evicting another
+        assert_eq!(cache.insert("SELECT 1", 10), Some(1));
+        assert_eq!(cache.len(), 2);
+
+        assert_eq!(cache.hits(), 2);
+        assert_eq!(cache.misses(), 1);
+    }
+
+    #[test]
+    fn it_caches_nothing_without_capacity() {
+        let mut cache = StatementCache::new(0);
+
+        assert!(!cache.is_enabled());
+        assert_eq!(cache.get_mut("SELECT 1"), None);
+        assert_eq!(cache.misses(), 1);
+    }
+}
@@ -3,9 +3,13 @@ use crate::error::Error;
 use crate::transaction::Transaction;
 use futures_core::future::BoxFuture;
 use log::LevelFilter;
-use std::fmt::Debug;
+use std::fmt::{self, Debug, Display, Formatter};
 use std::str::FromStr;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+
+// how long `Connection::close` reads the results of queries that were not read to the end
+#[cfg(any(feature = "postgres", feature = "mysql"))]
+pub(crate) const CLOSE_DRAIN_TIMEOUT: Duration = Duration::from_secs(1);
 
 /// Represents a single database connection.
 pub trait Connection: Send {
@@ -18,6 +22,23 @@ pub trait Connection: Send {
     /// This method is **not required** for safe and consistent operation. However, it is
     /// recommended to call it instead of letting a connection `drop` as the database backend
     /// will be faster at cleaning up resources.
+    ///
+    /// For a connection to a database server, work that is still pending on the connection is
+    /// dealt with in this order:
+    ///
+    ///  1. Work that was deferred and not sent yet, such as the `ROLLBACK` of a dropped
+    ///     [`Transaction`] or the closing of a prepared statement, is discarded. The database
+    ///     rolls back the transaction and frees the statements as the connection goes away.
+    ///  2. The results of queries that were sent but not read to the end are read and
+    ///     discarded, for up to a second, so that the database is not left writing them to a
+    ///     connection that is gone. This is best-effort; the connection is closed either way.
+    ///  3. The database is told that the connection is closing. This is written within the
+    ///     socket write timeout of the connection, if it has one, and is skipped if a previous
+    ///     operation was abandoned partway through a message or the connection is broken.
+    ///  4. The socket is shut down.
+    ///
+    /// An error is returned if telling the database or shutting the socket down fails; the
+    /// connection is closed regardless.
     fn close(self) -> BoxFuture<'static, Result<(), Error>>;
 
     /// Checks if a connection to the database is still valid.
@@ -102,6 +123,59 @@ pub trait Connection: Send {
     #[doc(hidden)]
     fn should_flush(&self) -> bool;
 
+    /// Returns `true` if a previous operation on this connection was abandoned before it
+    /// finished, leaving the connection unusable.
+    #[doc(hidden)]
+    fn is_busy(&self) -> bool {
+        false
+    }
+
+    /// Returns `true` if the database has closed this connection, e.g. because the server is
+    /// shutting down, or if reading from or writing to it failed, leaving the connection
+    /// unusable.
+    #[doc(hidden)]
+    fn is_broken(&self) -> bool {
+        false
+    }
+
+    /// Returns `false` if this connection can no longer be used, because the database has
+    /// closed it or a previous operation on it failed with an I/O error.
+    ///
+    /// This does not wait for or send anything to the database: besides what previous
+    /// operations found out, it peeks at the TCP socket of the connection to see whether the
+    /// database has closed it since. A connection that is not alive fails any operation right
+    /// away with [`Error::ConnectionClosed`], and is discarded by a pool instead of being
+    /// handed out.
+    ///
+    /// A connection that is alive may still turn out to be unusable, e.g. if the network
+    /// between the client and the database is down; use [`ping`](Self::ping) to be sure.
+    fn is_alive(&mut self) -> bool {
+        !self.is_broken()
+    }
+
+    /// Releases memory held by buffers that grew to fit a large message, if they are no longer
+    /// in use. Called when the connection is returned to a pool.
+    #[doc(hidden)]
+    fn shrink_buffers(&mut self) {}
+
+    /// The name of the database in the `db.system` field of the spans of the `tracing` feature,
+    /// which can depend on the server the connection is to.
+    #[doc(hidden)]
+    fn db_system(&self) -> &'static str {
+        <Self::Database as Database>::DB_SYSTEM
+    }
+
+    /// Returns statistics about the use of this connection since it was established.
+    ///
+    /// These are tracked by the PostgreSQL and MySQL drivers; other drivers return empty
+    /// statistics.
+    ///
+    /// Connections of a [`Pool`](crate::pool::Pool) are also reported by
+    /// [`Pool::connection_stats`](crate::pool::Pool::connection_stats).
+    fn stats(&self) -> ConnectionStats {
+        ConnectionStats::default()
+    }
+
     /// Establish a new database connection.
     ///
     /// A value of [`Options`][Self::Options] is parsed from the provided connection string. This parsing
@@ -125,6 +199,167 @@ pub trait Connection: Send {
     }
 }
 
+/// Statistics about the use of a connection since it was established, as returned by
+/// [`Connection::stats`].
+#[derive(Debug, Clone, Default)]
+pub struct ConnectionStats {
+    pub(crate) backend_id: Option<u32>,
+    pub(crate) queries: u64,
+    pub(crate) rows: u64,
+    pub(crate) bytes_read: u64,
+    pub(crate) bytes_written: u64,
+    pub(crate) busy_time: Duration,
+    pub(crate) last_used: Option<Instant>,
+    pub(crate) statement_cache_hits: u64,
+    pub(crate) statement_cache_misses: u64,
+    pub(crate) shared_statement_hits: u64,
+    pub(crate) shared_statement_misses: u64,
+
+    // when the connection started waiting on the server, if it is doing so
+    pub(crate) busy_since: Option<Instant>,
+}
+
+impl ConnectionStats {
+    /// The id the server identifies the connection by: the process id of its backend in
+    /// PostgreSQL (`pg_backend_pid()`) or its connection id in MySQL (`CONNECTION_ID()`).
+    pub fn backend_id(&self) -> Option<u32> {
+        self.backend_id
+    }
+
+    /// The number of queries sent on the connection.
+    pub fn queries(&self) -> u64 {
+        self.queries
+    }
+
+    /// The number of rows fetched on the connection.
+    pub fn rows(&self) -> u64 {
+        self.rows
+    }
+
+    /// The number of bytes read from the server.
+    pub fn bytes_read(&self) -> u64 {
+        self.bytes_read
+    }
+
+    /// The number of bytes written to the server.
+    pub fn bytes_written(&self) -> u64 {
+        self.bytes_written
+    }
+
+    /// The total time spent between sending queries and receiving the last of their results.
+    pub fn busy_time(&self) -> Duration {
+        self.busy_time
+    }
+
+    /// When a query was last sent or completed on the connection.
+    pub fn last_used(&self) -> Option<Instant> {
+        self.last_used
+    }
+
+    /// The number of queries that ran a statement the connection had prepared before.
+    pub fn statement_cache_hits(&self) -> u64 {
+        self.statement_cache_hits
+    }
+
+    /// The number of queries whose statement the connection had not prepared before.
+    pub fn statement_cache_misses(&self) -> u64 {
+        self.statement_cache_misses
+    }
+
+    /// The number of statements prepared on the connection whose parameters and columns were
+    /// known from another connection opened with the same options, e.g. of the same pool, so
+    /// that their types did not have to be looked up again.
+    ///
+    /// Currently only counted by the Postgres driver.
+    pub fn shared_statement_hits(&self) -> u64 {
+        self.shared_statement_hits
+    }
+
+    /// The number of statements prepared on the connection that no other connection opened with
+    /// the same options had described the same way.
+    ///
+    /// Currently only counted by the Postgres driver.
+    pub fn shared_statement_misses(&self) -> u64 {
+        self.shared_statement_misses
+    }
+
+    pub(crate) fn start_query(&mut self) {
+        let now = Instant::now();
+
+        self.queries += 1;
+        self.last_used = Some(now);
+        self.busy_since.get_or_insert(now);
+    }
+
+    pub(crate) fn finish_queries(&mut self) {
+        if let Some(since) = self.busy_since.take() {
+            let now = Instant::now();
+
+            self.busy_time += now - since;
+            self.last_used = Some(now);
+        }
+    }
+}
+
+/// The product and version of the database server a connection is established to, as returned
+/// by `server_version()` on PostgreSQL and MySQL connections.
+///
+/// This is what the server reports on connection; parts of the version it did not report are 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ServerVersion {
+    pub(crate) vendor: ServerVendor,
+    pub(crate) major: u16,
+    pub(crate) minor: u16,
+    pub(crate) patch: u16,
+}
+
+/// The product a database server is, as far as its protocol and SQL are concerned.
+///
+/// Servers compatible with one of these, e.g. Percona Server or Amazon Aurora, are reported as
+/// the product they are compatible with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ServerVendor {
+    Postgres,
+    MySql,
+    MariaDb,
+}
+
+impl ServerVersion {
+    pub fn vendor(&self) -> ServerVendor {
+        self.vendor
+    }
+
+    pub fn major(&self) -> u16 {
+        self.major
+    }
+
+    pub fn minor(&self) -> u16 {
+        self.minor
+    }
+
+    pub fn patch(&self) -> u16 {
+        self.patch
+    }
+
+    /// Whether this is `vendor` at version `major.minor.patch` or later.
+    pub fn is_at_least(&self, vendor: ServerVendor, major: u16, minor: u16, patch: u16) -> bool {
+        self.vendor == vendor && (self.major, self.minor, self.patch) >= (major, minor, patch)
+    }
+}
+
+impl Display for ServerVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let vendor = match self.vendor {
+            ServerVendor::Postgres => "PostgreSQL",
+            ServerVendor::MySql => "MySQL",
+            ServerVendor::MariaDb => "MariaDB",
+        };
+
+        write!(f, "{} {}.{}.{}", vendor, self.major, self.minor, self.patch)
+    }
+}
+
 #[derive(Clone, Debug)]
 pub(crate) struct LogSettings {
     pub(crate) statements_level: LevelFilter,
@@ -98,6 +98,11 @@ pub trait Database:
     /// The concrete type used to hold an owned copy of the not-yet-decoded value that was
     /// received from the database.
     type Value: Value<Database = Self> + 'static;
+
+    /// The name of this database in the `db.system` field of the spans of the `tracing`
+    /// feature, as listed by the OpenTelemetry semantic conventions.
+    #[doc(hidden)]
+    const DB_SYSTEM: &'static str = "other_sql";
 }
 
 /// Associate [`Database`] with a [`ValueRef`](crate::value::ValueRef) of a generic lifetime.
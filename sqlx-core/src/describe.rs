@@ -22,6 +22,14 @@ pub struct Describe<DB: Database> {
     pub(crate) columns: Vec<DB::Column>,
     pub(crate) parameters: Option<Either<Vec<DB::TypeInfo>, usize>>,
     pub(crate) nullable: Vec<Option<bool>>,
+    // query data saved before this field existed was never checked, so assume it returns rows
+    #[cfg_attr(feature = "offline", serde(default = "default_returns_rows"))]
+    pub(crate) returns_rows: bool,
+}
+
+#[cfg(feature = "offline")]
+fn default_returns_rows() -> bool {
+    true
 }
 
 impl<DB: Database> Describe<DB> {
@@ -49,6 +57,15 @@ impl<DB: Database> Describe<DB> {
         })
     }
 
+    /// Gets whether this statement returns a result set.
+    ///
+    /// This is `false` for statements like `INSERT`, `UPDATE` or `DELETE` without a `RETURNING`
+    /// clause. Note that a statement may return a result set with zero columns (e.g.,
+    /// `SELECT FROM tbl` on **PostgreSQL**), which is still a result set of rows.
+    pub fn returns_rows(&self) -> bool {
+        self.returns_rows
+    }
+
     /// Gets whether a column may be `NULL`, if this information is available.
     pub fn nullable(&self, column: usize) -> Option<bool> {
         self.nullable.get(column).copied().and_then(identity)
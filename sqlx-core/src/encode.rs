@@ -1,6 +1,8 @@
 //! Provides [`Encode`] for encoding values for the database.
 
 use std::mem;
+use std::rc::Rc;
+use std::sync::Arc;
 
 use crate::database::{Database, HasArguments};
 
@@ -16,6 +18,11 @@ pub enum IsNull {
 }
 
 /// Encode a single value to be sent to the database.
+#[diagnostic::on_unimplemented(
+    message = "the type `{Self}` cannot be used as a query parameter for {DB}",
+    label = "missing `Encode<{DB}>`",
+    note = "the type `{Self}` cannot be used as a query parameter for {DB} (missing Encode<{DB}>)"
+)]
 pub trait Encode<'q, DB: Database> {
     /// Writes the value of `self` into `buf` in the expected format for the database.
     #[must_use]
@@ -70,6 +77,60 @@ where
     }
 }
 
+// smart pointers encode as the value they point to, by way of a reference so that unsized
+// values such as `Box<str>` or `Arc<[u8]>` are covered as well
+macro_rules! impl_encode_for_smart_pointer {
+    ($($ty:ty),*) => {$(
+        impl<'q, T, DB: Database> Encode<'q, DB> for $ty
+        where
+            T: ?Sized,
+            for<'a> &'a T: Encode<'q, DB>,
+        {
+            #[inline]
+            fn encode_by_ref(
+                &self,
+                buf: &mut <DB as HasArguments<'q>>::ArgumentBuffer,
+            ) -> IsNull {
+                <&T as Encode<DB>>::encode(&**self, buf)
+            }
+
+            #[inline]
+            fn produces(&self) -> Option<DB::TypeInfo> {
+                <&T as Encode<DB>>::produces(&&**self)
+            }
+
+            #[inline]
+            fn size_hint(&self) -> usize {
+                <&T as Encode<DB>>::size_hint(&&**self)
+            }
+        }
+    )*};
+}
+
+impl_encode_for_smart_pointer!(Box<T>, Arc<T>, Rc<T>);
+
+/// A value that is already encoded in the format the database expects, sent with the given
+/// type information.
+///
+/// The bytes and the type are not checked in any way. It is up to the caller to provide bytes
+/// in the binary wire format of the type, as the database would otherwise reject the value or,
+/// worse, read it as something else. `None` is sent as `NULL`.
+///
+/// For MySQL, the bytes of a variable-length type include its length-encoded prefix.
+///
+/// See [`Query::bind_raw`](crate::query::Query::bind_raw).
+#[derive(Debug, Clone)]
+pub struct RawValue<'q, DB: Database> {
+    pub(crate) type_info: DB::TypeInfo,
+    pub(crate) value: Option<&'q [u8]>,
+}
+
+impl<'q, DB: Database> RawValue<'q, DB> {
+    pub fn new(type_info: DB::TypeInfo, value: Option<&'q [u8]>) -> Self {
+        Self { type_info, value }
+    }
+}
+
 #[allow(unused_macros)]
 macro_rules! impl_encode_for_option {
     ($DB:ident) => {
@@ -46,6 +46,14 @@ pub enum Error {
     #[error("error occurred while attempting to establish a TLS connection: {0}")]
     Tls(#[source] BoxDynError),
 
+    /// The server requested an authentication method that is not supported, such as GSSAPI
+    /// or SSPI.
+    ///
+    /// Allow a supported method for the user on the server, such as `scram-sha-256` or `md5`
+    /// in the `pg_hba.conf` of Postgres.
+    #[error("the server requested {method} authentication, which is not supported")]
+    AuthNotSupported { method: String },
+
     /// Unexpected or invalid data encountered while communicating with the database.
     ///
     /// This should indicate there is a programming error in a SQLx driver or there
@@ -69,6 +77,43 @@ pub enum Error {
     #[error("no column found for name: {0}")]
     ColumnNotFound(String),
 
+    /// More than one column has the given name, so a field of a [`FromRow`] derive mapped to
+    /// it could be decoded from either.
+    ///
+    /// Give the columns distinct aliases in the query, or map the field to a column by its
+    /// position with `#[sqlx(ordinal = ..)]`.
+    ///
+    /// [`FromRow`]: crate::from_row::FromRow
+    #[error("more than one column is named {0:?}; alias them in the query")]
+    AmbiguousColumn(String),
+
+    /// The number of values bound to a query differs from the number of its parameters.
+    ///
+    /// Nothing was sent to execute the query.
+    #[error("query has {expected} parameters but {got} values were bound")]
+    ParameterCountMismatch { expected: usize, got: usize },
+
+    /// The names of the values bound to a query made with
+    /// [`query_named`](crate::query::query_named) differ from the names of its parameters.
+    ///
+    /// `missing` are the names of parameters that no value was bound to, and `extra` the names
+    /// of values that no parameter uses or that were bound more than once. Nothing was sent to
+    /// execute the query.
+    #[error(
+        "named parameters do not match the values bound: missing {missing:?}, extra {extra:?}"
+    )]
+    NamedParameterMismatch {
+        missing: Vec<String>,
+        extra: Vec<String>,
+    },
+
+    /// The values bound to a query cannot be sent with it, e.g. because some of them were
+    /// bound by name and others by position.
+    ///
+    /// Nothing was sent to execute the query.
+    #[error("invalid arguments: {0}")]
+    InvalidArguments(String),
+
     /// Error occurred while decoding a value from a specific column.
     #[error("error occurred while decoding column {index}: {source}")]
     ColumnDecode {
@@ -82,6 +127,13 @@ pub enum Error {
     #[error("error occurred while decoding: {0}")]
     Decode(#[source] BoxDynError),
 
+    /// A query, or an option of a connection, cannot be sent to the database as it is, e.g.
+    /// because it contains a NUL byte where the protocol ends a string with one.
+    ///
+    /// Nothing was sent to the database.
+    #[error("error occurred while encoding: {0}")]
+    Encode(#[source] BoxDynError),
+
     /// A [`Pool::acquire`] timed out due to connections not becoming available or
     /// because another task encountered too many errors while trying to open a new connection.
     ///
@@ -96,6 +148,48 @@ pub enum Error {
     #[error("attempted to acquire a connection on a closed pool")]
     PoolClosed,
 
+    /// A [`Pool::acquire`] timed out while the database refused new connections of the pool
+    /// because it has as many as it allows, e.g. `max_connections` in PostgreSQL or MySQL.
+    ///
+    /// The pool does not grow any further until its connections are released or the database
+    /// accepts a new one again, which it tries with an increasing delay. `limit` is the number
+    /// of connections the database allows, if its `message` names it.
+    ///
+    /// [`Pool::acquire`]: crate::pool::Pool::acquire
+    #[error("pool timed out while the database refused new connections: {message}")]
+    ServerConnectionLimit { limit: Option<u32>, message: String },
+
+    /// The connection is already in use by another operation.
+    ///
+    /// This is returned when an operation is started on a connection while its previous
+    /// operation has not finished, e.g. because that operation's future or stream was dropped
+    /// before it completed, or is still held by another task. The connection can no longer be
+    /// used and should be closed.
+    #[error("connection is already in use by another operation")]
+    ConnectionBusy,
+
+    /// The database closed the connection, e.g. because the server is shutting down or the
+    /// session was killed.
+    ///
+    /// This is also returned right away by any operation on a connection that was closed or
+    /// failed with an I/O error before, without sending anything to the database.
+    ///
+    /// The connection can no longer be used and is discarded by a pool; running the operation
+    /// again on a new connection may well succeed. If the server sent an error before closing
+    /// the connection, it is returned by [`as_database_error`](Error::as_database_error).
+    #[error("connection closed by the database: {0}")]
+    ConnectionClosed(#[source] BoxDynError),
+
+    /// An operation was attempted on a connection in a state that does not allow it, e.g. a query
+    /// on a connection that was closed by the database.
+    ///
+    /// Nothing was sent to the database.
+    #[error("cannot {attempted} while the connection is {current}")]
+    InvalidConnectionState {
+        current: &'static str,
+        attempted: &'static str,
+    },
+
     /// A background worker has crashed.
     #[error("attempted to communicate with a crashed background worker")]
     WorkerCrashed,
@@ -111,6 +205,10 @@ impl Error {
     pub fn into_database_error(self) -> Option<Box<dyn DatabaseError + 'static>> {
         match self {
             Error::Database(err) => Some(err),
+            Error::ConnectionClosed(err) => err
+                .downcast::<Box<dyn DatabaseError>>()
+                .ok()
+                .map(|err| *err),
             _ => None,
         }
     }
@@ -118,10 +216,50 @@ impl Error {
     pub fn as_database_error(&self) -> Option<&(dyn DatabaseError + 'static)> {
         match self {
             Error::Database(err) => Some(&**err),
+            Error::ConnectionClosed(err) => err
+                .downcast_ref::<Box<dyn DatabaseError>>()
+                .map(|err| &**err),
             _ => None,
         }
     }
 
+    /// Returns `true` if the database closed the connection.
+    ///
+    /// See [`Error::ConnectionClosed`].
+    pub fn is_connection_closed(&self) -> bool {
+        matches!(self, Error::ConnectionClosed(_))
+    }
+
+    // classifies the I/O errors that mean the database has closed the connection
+    #[inline]
+    pub(crate) fn io(err: io::Error) -> Self {
+        match err.kind() {
+            io::ErrorKind::UnexpectedEof
+            | io::ErrorKind::ConnectionReset
+            | io::ErrorKind::ConnectionAborted
+            | io::ErrorKind::BrokenPipe => Error::ConnectionClosed(err.into()),
+
+            _ => Error::Io(err),
+        }
+    }
+
+    // the connection was closed by the database or failed with an I/O error before
+    #[inline]
+    pub(crate) fn broken() -> Self {
+        Error::ConnectionClosed(
+            "the connection was closed or failed in a previous operation".into(),
+        )
+    }
+
+    // the database sent an error and closed the connection
+    #[allow(dead_code)]
+    #[inline]
+    pub(crate) fn closed_with(err: impl DatabaseError) -> Self {
+        let err: Box<dyn DatabaseError> = Box::new(err);
+
+        Error::ConnectionClosed(Box::new(err))
+    }
+
     #[allow(dead_code)]
     #[inline]
     pub(crate) fn protocol(err: impl Display) -> Self {
@@ -173,6 +311,15 @@ pub trait DatabaseError: 'static + Send + Sync + StdError {
     fn constraint(&self) -> Option<&str> {
         None
     }
+
+    /// Returns `true` if the database refused a new connection because it has as many
+    /// connections as it allows, either in total or for the user.
+    ///
+    /// ### Note
+    /// Currently only reported by the Postgres and MySQL drivers.
+    fn is_connection_limit(&self) -> bool {
+        false
+    }
 }
 
 impl dyn DatabaseError {
@@ -198,6 +198,26 @@ pub trait Execute<'q, DB: Database>: Send + Sized {
 
     /// Returns `true` if the statement should be cached.
     fn persistent(&self) -> bool;
+
+    /// Returns whether the query was marked as read-only, for a
+    /// [`ReadWriteSplit`](crate::pool::ReadWriteSplit) to route it, or `None` if it was not
+    /// marked either way.
+    fn read_only(&self) -> Option<bool> {
+        None
+    }
+
+    /// Returns `true` if the rows of the query are requested in the text format of the
+    /// database; see [`Query::text_results`](crate::query::Query::text_results).
+    fn text_results(&self) -> bool {
+        false
+    }
+
+    /// Returns a copy of the query to run on a new connection if the connection the query was
+    /// sent on is lost before it returned any result, or `None` if it must not be run again.
+    #[doc(hidden)]
+    fn retry(&self) -> Option<Self> {
+        None
+    }
 }
 
 // NOTE: `Execute` is explicitly not implemented for String and &String to make it slightly more
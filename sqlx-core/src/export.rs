@@ -0,0 +1,302 @@
+//! Export the results of a query to common file formats.
+
+use std::io::Write;
+
+use futures_util::TryStreamExt;
+
+use crate::column::Column;
+use crate::database::Database;
+use crate::decode::Decode;
+use crate::error::Error;
+use crate::executor::{Execute, Executor};
+use crate::row::Row;
+use crate::type_info::TypeInfo;
+use crate::types::Type;
+use crate::value::ValueRef;
+
+/// A database whose values can be written as CSV fields by [`csv`].
+///
+/// This trait is sealed and cannot be implemented for types outside of SQLx.
+pub trait CsvDatabase: Database + private_csv_database::Sealed {
+    #[doc(hidden)]
+    fn write_field(row: &Self::Row, index: usize, out: &mut String) -> Result<(), Error>;
+}
+
+mod private_csv_database {
+    pub trait Sealed {}
+}
+
+/// Streams the results of `query` to `writer` as [RFC 4180](https://tools.ietf.org/html/rfc4180)
+/// CSV.
+///
+/// The first record is a header of the column names of the first row. Each following record
+/// is one row of the result set:
+///
+///  * `NULL` is written as an empty field.
+///  * Binary data is written as standard base64.
+///  * Timestamps are written as RFC 3339; dates and times are written as ISO 8601.
+///
+/// Rows are written as they are received; the result set is never buffered in full. Each
+/// record is written with a single call to `write_all`, so if an error occurs partway through
+/// the result set, the writer is left with every record up to that point and the error is
+/// returned.
+///
+/// An empty result set writes nothing, not even the header, as there is no row to take the
+/// column names from.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # use sqlx_core::export;
+/// # use sqlx_core::error::Error;
+/// # use sqlx_core::postgres::PgConnection;
+/// # use sqlx_core::query::query;
+/// #
+/// # async fn _f(conn: &mut PgConnection) -> Result<(), Error> {
+/// let file = std::fs::File::create("tweets.csv")?;
+///
+/// export::csv(query("SELECT id, text FROM tweet"), conn, file).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn csv<'q, 'c, DB, Q, E, W>(query: Q, executor: E, mut writer: W) -> Result<(), Error>
+where
+    DB: CsvDatabase,
+    Q: Execute<'q, DB> + 'q,
+    E: Executor<'c, Database = DB>,
+    'c: 'q,
+    W: Write,
+{
+    let mut rows = executor.fetch(query);
+    let mut record = String::new();
+    let mut field = String::new();
+    let mut wrote_header = false;
+
+    while let Some(row) = rows.try_next().await? {
+        if !wrote_header {
+            for (i, column) in row.columns().iter().enumerate() {
+                push_field(&mut record, i, column.name());
+            }
+
+            record.push_str("\r\n");
+            wrote_header = true;
+        }
+
+        for i in 0..row.len() {
+            field.clear();
+            DB::write_field(&row, i, &mut field)?;
+            push_field(&mut record, i, &field);
+        }
+
+        record.push_str("\r\n");
+
+        writer.write_all(record.as_bytes())?;
+        record.clear();
+    }
+
+    writer.flush()?;
+
+    Ok(())
+}
+
+// appends a field, quoting it if it contains a delimiter, a quote, or a line break
+fn push_field(record: &mut String, index: usize, field: &str) {
+    if index > 0 {
+        record.push(',');
+    }
+
+    if field.contains(|c| matches!(c, ',' | '"' | '\r' | '\n')) {
+        record.push('"');
+        record.push_str(&field.replace('"', "\"\""));
+        record.push('"');
+    } else {
+        record.push_str(field);
+    }
+}
+
+// the textual representation of a value in a CSV field
+trait CsvField {
+    fn write(self, out: &mut String);
+}
+
+macro_rules! impl_csv_field_display {
+    ($($ty:ty),*) => {
+        $(
+            impl CsvField for $ty {
+                fn write(self, out: &mut String) {
+                    use std::fmt::Write;
+
+                    let _ = write!(out, "{}", self);
+                }
+            }
+        )*
+    };
+}
+
+impl_csv_field_display!(bool, i8, i16, i32, i64, u8, u16, u32, u64, f32, f64, String);
+
+impl CsvField for Vec<u8> {
+    fn write(self, out: &mut String) {
+        base64::encode_config_buf(&self, base64::STANDARD, out);
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl CsvField for chrono::DateTime<chrono::Utc> {
+    fn write(self, out: &mut String) {
+        out.push_str(&self.to_rfc3339());
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl CsvField for chrono::NaiveDateTime {
+    fn write(self, out: &mut String) {
+        use std::fmt::Write;
+
+        let _ = write!(out, "{}", self.format("%Y-%m-%dT%H:%M:%S%.f"));
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl_csv_field_display!(chrono::NaiveDate, chrono::NaiveTime);
+
+#[cfg(feature = "uuid")]
+impl_csv_field_display!(uuid::Uuid);
+
+#[cfg(feature = "bigdecimal")]
+impl_csv_field_display!(bigdecimal::BigDecimal);
+
+#[cfg(feature = "json")]
+impl_csv_field_display!(serde_json::Value);
+
+fn write_as<'r, DB, T>(row: &'r DB::Row, index: usize, out: &mut String) -> Result<(), Error>
+where
+    DB: Database,
+    T: Decode<'r, DB> + CsvField,
+{
+    row.try_get_unchecked::<T, _>(index)?.write(out);
+
+    Ok(())
+}
+
+// candidate types are tried in order; an exact type match takes priority over a compatible one
+// so that, e.g., a SQLite integer is written as a number instead of as a boolean
+macro_rules! impl_csv_database {
+    ($DB:ty { $($(#[$meta:meta])* $ty:ty),* $(,)? }) => {
+        impl private_csv_database::Sealed for $DB {}
+
+        impl CsvDatabase for $DB {
+            fn write_field(row: &Self::Row, index: usize, out: &mut String) -> Result<(), Error> {
+                let value = row.try_get_raw(index)?;
+
+                if value.is_null() {
+                    return Ok(());
+                }
+
+                let ty = value.type_info().into_owned();
+
+                $(
+                    $(#[$meta])*
+                    if ty == <$ty as Type<$DB>>::type_info() {
+                        return write_as::<$DB, $ty>(row, index, out);
+                    }
+                )*
+
+                $(
+                    $(#[$meta])*
+                    if <$ty as Type<$DB>>::compatible(&ty) {
+                        return write_as::<$DB, $ty>(row, index, out);
+                    }
+                )*
+
+                Err(Error::ColumnDecode {
+                    index: format!("{:?}", index),
+                    source: format!("cannot export a value of type `{}` as CSV", ty.name())
+                        .into(),
+                })
+            }
+        }
+    };
+}
+
+#[cfg(feature = "postgres")]
+impl_csv_database!(crate::postgres::Postgres {
+    bool,
+    i16,
+    i32,
+    i64,
+    f32,
+    f64,
+    String,
+    Vec<u8>,
+    #[cfg(feature = "chrono")]
+    chrono::DateTime<chrono::Utc>,
+    #[cfg(feature = "chrono")]
+    chrono::NaiveDateTime,
+    #[cfg(feature = "chrono")]
+    chrono::NaiveDate,
+    #[cfg(feature = "chrono")]
+    chrono::NaiveTime,
+    #[cfg(feature = "uuid")]
+    uuid::Uuid,
+    #[cfg(feature = "bigdecimal")]
+    bigdecimal::BigDecimal,
+    #[cfg(feature = "json")]
+    serde_json::Value,
+});
+
+#[cfg(feature = "mysql")]
+impl_csv_database!(crate::mysql::MySql {
+    bool,
+    i8,
+    i16,
+    i32,
+    i64,
+    u8,
+    u16,
+    u32,
+    u64,
+    f32,
+    f64,
+    String,
+    Vec<u8>,
+    #[cfg(feature = "chrono")]
+    chrono::DateTime<chrono::Utc>,
+    #[cfg(feature = "chrono")]
+    chrono::NaiveDateTime,
+    #[cfg(feature = "chrono")]
+    chrono::NaiveDate,
+    #[cfg(feature = "chrono")]
+    chrono::NaiveTime,
+    #[cfg(feature = "bigdecimal")]
+    bigdecimal::BigDecimal,
+    #[cfg(feature = "json")]
+    serde_json::Value,
+});
+
+#[cfg(feature = "sqlite")]
+impl_csv_database!(crate::sqlite::Sqlite {
+    i64,
+    f64,
+    String,
+    Vec<u8>,
+    bool,
+});
+
+#[cfg(test)]
+mod tests {
+    use super::push_field;
+
+    #[test]
+    fn it_quotes_fields() {
+        let mut record = String::new();
+
+        push_field(&mut record, 0, "plain");
+        push_field(&mut record, 1, "");
+        push_field(&mut record, 2, "a,b");
+        push_field(&mut record, 3, "say \"hi\"");
+        push_field(&mut record, 4, "two\r\nlines");
+
+        assert_eq!(record, "plain,,\"a,b\",\"say \"\"hi\"\"\",\"two\r\nlines\"");
+    }
+}
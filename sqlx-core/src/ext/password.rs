@@ -0,0 +1,42 @@
+use std::fmt::{self, Debug, Formatter};
+use std::ops::Deref;
+
+// a password, which is left out of the `Debug` output of the options that hold it
+#[derive(Clone, PartialEq, Eq)]
+pub struct Password(String);
+
+impl Deref for Password {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for Password {
+    fn from(password: String) -> Self {
+        Password(password)
+    }
+}
+
+impl From<&str> for Password {
+    fn from(password: &str) -> Self {
+        Password(password.to_owned())
+    }
+}
+
+impl Debug for Password {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("REDACTED")
+    }
+}
+
+#[test]
+fn test_password_is_redacted() {
+    let password = Password::from("p@ss:w/rd%");
+
+    assert_eq!(&*password, "p@ss:w/rd%");
+    assert_eq!(format!("{:?}", password), "REDACTED");
+    assert_eq!(format!("{:?}", Some(password)), "Some(REDACTED)");
+}
@@ -0,0 +1,217 @@
+//! The progress of a long fetch, see [`Query::fetch_with_progress`].
+//!
+//! [`Query::fetch_with_progress`]: crate::query::Query::fetch_with_progress
+
+use std::time::{Duration, Instant};
+
+/// How often [`Query::fetch_with_progress`] reports the progress of a fetch.
+///
+/// By default, the progress is reported every 1000 rows or every second, whichever comes first.
+///
+/// [`Query::fetch_with_progress`]: crate::query::Query::fetch_with_progress
+#[derive(Debug, Clone)]
+pub struct ProgressOptions {
+    every_rows: u64,
+    every: Duration,
+    estimated_rows: Option<u64>,
+}
+
+impl ProgressOptions {
+    pub fn new() -> Self {
+        ProgressOptions {
+            every_rows: 1000,
+            every: Duration::from_secs(1),
+            estimated_rows: None,
+        }
+    }
+
+    /// Report the progress after every `rows` rows fetched since the last report, or never by
+    /// the number of rows with 0.
+    pub fn every_rows(mut self, rows: u64) -> Self {
+        self.every_rows = rows;
+        self
+    }
+
+    /// Report the progress with the first row fetched after `interval` passed since the last
+    /// report.
+    pub fn every(mut self, interval: Duration) -> Self {
+        self.every = interval;
+        self
+    }
+
+    /// The number of rows the query is expected to return, so that each report includes the
+    /// [`fraction`](FetchProgress::fraction) of it fetched.
+    pub fn estimated_rows(mut self, rows: u64) -> Self {
+        self.estimated_rows = Some(rows);
+        self
+    }
+}
+
+impl Default for ProgressOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A report of the progress of a fetch, passed to the callback of
+/// [`Query::fetch_with_progress`].
+///
+/// [`Query::fetch_with_progress`]: crate::query::Query::fetch_with_progress
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct FetchProgress {
+    /// The number of rows fetched so far.
+    pub rows: u64,
+
+    /// The number of bytes of the values of the rows fetched so far, as received from the
+    /// database.
+    ///
+    /// Only PostgreSQL and MySQL keep the rows as received; this is always 0 for the other
+    /// databases.
+    pub bytes: u64,
+
+    /// The time since the query was started.
+    pub elapsed: Duration,
+
+    /// The number of rows the query was expected to return, if it was given or counted.
+    pub estimated_rows: Option<u64>,
+
+    /// Whether the query completed, in which case this is the last report.
+    pub done: bool,
+}
+
+impl FetchProgress {
+    /// The fraction of the estimated rows fetched so far, from 0 to 1, if the number of rows
+    /// was estimated.
+    ///
+    /// This is 1 once the query completed, and does not exceed 1 if the query returns more
+    /// rows than estimated.
+    pub fn fraction(&self) -> Option<f64> {
+        let estimated_rows = self.estimated_rows?;
+
+        if self.done || self.rows >= estimated_rows {
+            return Some(1.0);
+        }
+
+        Some(self.rows as f64 / estimated_rows as f64)
+    }
+}
+
+pub(crate) struct ProgressTracker<F> {
+    options: ProgressOptions,
+    on_progress: F,
+    progress: FetchProgress,
+    started_at: Instant,
+    reported_rows: u64,
+    reported_at: Instant,
+}
+
+impl<F> ProgressTracker<F>
+where
+    F: FnMut(&FetchProgress),
+{
+    pub(crate) fn new(options: ProgressOptions, on_progress: F) -> Self {
+        let now = Instant::now();
+
+        ProgressTracker {
+            progress: FetchProgress {
+                rows: 0,
+                bytes: 0,
+                elapsed: Duration::from_secs(0),
+                estimated_rows: options.estimated_rows,
+                done: false,
+            },
+            options,
+            on_progress,
+            started_at: now,
+            reported_rows: 0,
+            reported_at: now,
+        }
+    }
+
+    /// Counts a row of `len` bytes, reporting the progress if it is due.
+    pub(crate) fn row(&mut self, len: usize) {
+        self.progress.rows += 1;
+        self.progress.bytes += len as u64;
+
+        let every_rows = self.options.every_rows;
+        let due = (every_rows > 0 && self.progress.rows - self.reported_rows >= every_rows)
+            || self.reported_at.elapsed() >= self.options.every;
+
+        if due {
+            self.report();
+        }
+    }
+
+    /// Reports the progress of the completed query.
+    pub(crate) fn finish(mut self) {
+        self.progress.done = true;
+        self.report();
+    }
+
+    fn report(&mut self) {
+        let now = Instant::now();
+
+        self.progress.elapsed = now - self.started_at;
+        self.reported_rows = self.progress.rows;
+        self.reported_at = now;
+
+        (self.on_progress)(&self.progress);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn track(options: ProgressOptions, rows: u64) -> Vec<FetchProgress> {
+        let mut reports = Vec::new();
+        let mut tracker =
+            ProgressTracker::new(options, |p: &FetchProgress| reports.push(p.clone()));
+
+        for _ in 0..rows {
+            tracker.row(10);
+        }
+
+        tracker.finish();
+        reports
+    }
+
+    #[test]
+    fn it_reports_every_n_rows_and_when_done() {
+        let options = ProgressOptions::new()
+            .every_rows(100)
+            .every(Duration::from_secs(3600));
+
+        let reports = track(options, 250);
+        let rows: Vec<u64> = reports.iter().map(|p| p.rows).collect();
+
+        assert_eq!(rows, [100, 200, 250]);
+        assert_eq!(reports[1].bytes, 2000);
+        assert!(!reports[1].done);
+        assert!(reports[2].done);
+        assert_eq!(reports[2].fraction(), None);
+    }
+
+    #[test]
+    fn it_reports_the_fraction_of_the_estimated_rows() {
+        let options = ProgressOptions::new()
+            .every_rows(50)
+            .every(Duration::from_secs(3600))
+            .estimated_rows(200);
+
+        let fractions: Vec<_> = track(options, 150)
+            .iter()
+            .map(|p| p.fraction().unwrap())
+            .collect();
+
+        assert_eq!(fractions, [0.25, 0.5, 0.75, 1.0]);
+
+        let options = ProgressOptions::new()
+            .every_rows(100)
+            .every(Duration::from_secs(3600))
+            .estimated_rows(100);
+
+        assert_eq!(track(options, 200)[1].fraction(), Some(1.0));
+    }
+}
@@ -0,0 +1,331 @@
+//! Spans around connecting, acquiring a connection from a pool, and preparing and executing
+//! queries, with the `db.*` fields of the OpenTelemetry semantic conventions.
+//!
+//! Without the `tracing` feature, a [`Span`] is empty and every function here does nothing, so
+//! that no field is captured or formatted.
+
+// not every driver opens every kind of span
+#![allow(dead_code)]
+
+use std::future::Future;
+
+use either::Either;
+use futures_core::Stream;
+
+use crate::error::Error;
+
+#[cfg(feature = "tracing")]
+use std::fmt::{self, Display, Formatter};
+#[cfg(feature = "tracing")]
+use std::pin::Pin;
+#[cfg(feature = "tracing")]
+use std::task::{Context, Poll};
+#[cfg(feature = "tracing")]
+use std::time::Instant;
+
+// longer SQL is truncated in the `db.statement` field
+#[cfg(feature = "tracing")]
+const MAX_STATEMENT_LEN: usize = 2048;
+
+#[derive(Clone)]
+pub(crate) struct Span {
+    #[cfg(feature = "tracing")]
+    inner: tracing::Span,
+
+    // when the span was created, if it is enabled and records how long its operation waited
+    #[cfg(feature = "tracing")]
+    start: Option<Instant>,
+}
+
+// spans of the statements a connection runs
+#[cfg(feature = "tracing")]
+macro_rules! statement_span {
+    ($name:literal, $system:expr, $sql:expr) => {
+        tracing::info_span!(
+            target: "sqlx::query",
+            $name,
+            db.system = $system,
+            db.statement = %Statement($sql),
+            db.rows_affected = tracing::field::Empty
+        )
+    };
+}
+
+#[allow(unused_variables)]
+impl Span {
+    /// Establishing a connection to `host`.
+    #[inline]
+    pub(crate) fn connect(
+        system: &'static str,
+        host: &str,
+        port: u16,
+        database: Option<&str>,
+    ) -> Self {
+        Self {
+            #[cfg(feature = "tracing")]
+            inner: tracing::info_span!(
+                target: "sqlx::connect",
+                "sqlx.connect",
+                db.system = system,
+                db.name = database,
+                net.peer.name = host,
+                net.peer.port = port
+            ),
+            #[cfg(feature = "tracing")]
+            start: None,
+        }
+    }
+
+    /// Acquiring a connection from a pool, which records how long it waited for one with
+    /// [`record_wait_time`](Span::record_wait_time).
+    #[inline]
+    pub(crate) fn acquire(system: &'static str) -> Self {
+        #[cfg(feature = "tracing")]
+        let inner = tracing::info_span!(
+            target: "sqlx::pool",
+            "sqlx.acquire",
+            db.system = system,
+            db.client.connections.wait_time = tracing::field::Empty
+        );
+
+        Self {
+            #[cfg(feature = "tracing")]
+            start: (!inner.is_disabled()).then(Instant::now),
+            #[cfg(feature = "tracing")]
+            inner,
+        }
+    }
+
+    /// Preparing the statement `sql`.
+    #[inline]
+    pub(crate) fn prepare(system: &'static str, sql: &str) -> Self {
+        Self {
+            #[cfg(feature = "tracing")]
+            inner: statement_span!("sqlx.prepare", system, sql),
+            #[cfg(feature = "tracing")]
+            start: None,
+        }
+    }
+
+    /// Executing `sql` for the number of rows it affected.
+    #[inline]
+    pub(crate) fn execute(system: &'static str, sql: &str) -> Self {
+        Self {
+            #[cfg(feature = "tracing")]
+            inner: statement_span!("sqlx.execute", system, sql),
+            #[cfg(feature = "tracing")]
+            start: None,
+        }
+    }
+
+    /// Executing `sql` for the rows it returns.
+    #[inline]
+    pub(crate) fn fetch(system: &'static str, sql: &str) -> Self {
+        Self {
+            #[cfg(feature = "tracing")]
+            inner: statement_span!("sqlx.fetch", system, sql),
+            #[cfg(feature = "tracing")]
+            start: None,
+        }
+    }
+
+    /// Records the `db.system` of the server, once a connection to it tells which one it is.
+    #[inline]
+    pub(crate) fn record_system(&self, system: &'static str) {
+        #[cfg(feature = "tracing")]
+        self.inner.record("db.system", &system);
+    }
+
+    /// Records the time since the span was created, in milliseconds.
+    #[inline]
+    pub(crate) fn record_wait_time(&self) {
+        #[cfg(feature = "tracing")]
+        {
+            if let Some(start) = self.start {
+                self.inner.record(
+                    "db.client.connections.wait_time",
+                    &(start.elapsed().as_secs_f64() * 1000.0),
+                );
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "tracing"))]
+impl Span {
+    /// Runs `future` in this span.
+    #[inline]
+    pub(crate) fn instrument<F: Future>(self, future: F) -> F {
+        future
+    }
+
+    /// Runs `future` in this span, recording the rows affected by the result it returns.
+    #[inline]
+    pub(crate) fn instrument_result<F, T>(self, future: F, _rows_affected: fn(&T) -> u64) -> F
+    where
+        F: Future<Output = Result<T, Error>>,
+    {
+        future
+    }
+
+    /// Polls `stream` in this span, recording the total of the rows affected by the results
+    /// among its items.
+    #[inline]
+    pub(crate) fn instrument_stream<S, Q, R>(self, stream: S, _rows_affected: fn(&Q) -> u64) -> S
+    where
+        S: Stream<Item = Result<Either<Q, R>, Error>> + Unpin,
+    {
+        stream
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl Span {
+    #[inline]
+    pub(crate) fn instrument<F: Future>(self, future: F) -> impl Future<Output = F::Output> {
+        tracing::Instrument::instrument(future, self.inner)
+    }
+
+    #[inline]
+    pub(crate) fn instrument_result<F, T>(
+        self,
+        future: F,
+        rows_affected: fn(&T) -> u64,
+    ) -> impl Future<Output = F::Output>
+    where
+        F: Future<Output = Result<T, Error>>,
+    {
+        let span = self.inner.clone();
+
+        let future = async move {
+            let result = future.await;
+
+            if let Ok(result) = &result {
+                span.record("db.rows_affected", &rows_affected(result));
+            }
+
+            result
+        };
+
+        tracing::Instrument::instrument(future, self.inner)
+    }
+
+    #[inline]
+    pub(crate) fn instrument_stream<S, Q, R>(
+        self,
+        stream: S,
+        rows_affected: fn(&Q) -> u64,
+    ) -> impl Stream<Item = Result<Either<Q, R>, Error>>
+    where
+        S: Stream<Item = Result<Either<Q, R>, Error>> + Unpin,
+    {
+        Instrumented {
+            span: self.inner,
+            stream,
+            rows_affected,
+            total: 0,
+        }
+    }
+}
+
+/// An attempt to open a connection for a pool failed with `error`, and is retried.
+#[allow(unused_variables)]
+#[inline]
+pub(crate) fn retry(error: &dyn std::fmt::Display) {
+    #[cfg(feature = "tracing")]
+    tracing::info!(
+        target: "sqlx::pool",
+        error = %error,
+        "retrying to open a connection"
+    );
+}
+
+/// An idle connection of a pool could not be used and is replaced by a new one.
+#[allow(unused_variables)]
+#[inline]
+pub(crate) fn reconnect(reason: &'static str) {
+    #[cfg(feature = "tracing")]
+    tracing::info!(target: "sqlx::pool", reason, "replacing an idle connection");
+}
+
+/// Connecting to a host failed with `error`, and the next host is tried.
+#[allow(unused_variables)]
+#[inline]
+pub(crate) fn fall_back(host: &str, port: u16, error: &Error) {
+    #[cfg(feature = "tracing")]
+    tracing::info!(
+        target: "sqlx::connect",
+        net.peer.name = host,
+        net.peer.port = port,
+        error = %error,
+        "failed to connect; trying the next host"
+    );
+}
+
+// the SQL of a statement, truncated to `MAX_STATEMENT_LEN` bytes
+#[cfg(feature = "tracing")]
+struct Statement<'a>(&'a str);
+
+#[cfg(feature = "tracing")]
+impl Display for Statement<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.0.len() <= MAX_STATEMENT_LEN {
+            return f.write_str(self.0);
+        }
+
+        let mut end = MAX_STATEMENT_LEN;
+
+        while !self.0.is_char_boundary(end) {
+            end -= 1;
+        }
+
+        write!(f, "{} …", &self.0[..end])
+    }
+}
+
+#[cfg(feature = "tracing")]
+struct Instrumented<S, Q> {
+    span: tracing::Span,
+    stream: S,
+    rows_affected: fn(&Q) -> u64,
+    total: u64,
+}
+
+#[cfg(feature = "tracing")]
+impl<S, Q, R> Stream for Instrumented<S, Q>
+where
+    S: Stream<Item = Result<Either<Q, R>, Error>> + Unpin,
+{
+    type Item = S::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        let _entered = this.span.enter();
+
+        let item = Pin::new(&mut this.stream).poll_next(cx);
+
+        if let Poll::Ready(Some(Ok(Either::Left(result)))) = &item {
+            this.total += (this.rows_affected)(result);
+            this.span.record("db.rows_affected", &this.total);
+        }
+
+        item
+    }
+}
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_truncates_long_statements() {
+        assert_eq!(Statement("SELECT 1").to_string(), "SELECT 1");
+
+        let sql = format!("SELECT '{}'", "é".repeat(MAX_STATEMENT_LEN));
+        let truncated = Statement(&sql).to_string();
+
+        assert!(truncated.ends_with(" …"));
+        assert!(truncated.len() <= MAX_STATEMENT_LEN + " …".len());
+        assert!(sql.starts_with(truncated.trim_end_matches(" …")));
+    }
+}
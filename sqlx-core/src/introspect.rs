@@ -0,0 +1,104 @@
+//! Introspection of the tables and columns in a database.
+
+use futures_core::future::BoxFuture;
+
+use crate::connection::Connection;
+use crate::database::Database;
+use crate::error::Error;
+
+/// A table, as returned by [`tables`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableInfo {
+    /// The schema (or, in **MySQL**, the database) the table belongs to.
+    pub schema: String,
+
+    /// The name of the table, as stored in the catalog.
+    pub name: String,
+}
+
+/// A column of a table, as returned by [`columns`].
+#[derive(Debug)]
+pub struct ColumnInfo<DB: Database> {
+    /// The name of the column, as stored in the catalog.
+    pub name: String,
+
+    /// The type of the column.
+    pub type_info: DB::TypeInfo,
+
+    /// `true` if the column may be `NULL`.
+    pub nullable: bool,
+
+    /// The default expression of the column, if it has one.
+    pub default: Option<String>,
+
+    /// `true` if the column is part of the primary key of the table.
+    pub is_primary_key: bool,
+}
+
+// not derived, as that would require `DB: Clone`
+impl<DB: Database> Clone for ColumnInfo<DB> {
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            type_info: self.type_info.clone(),
+            nullable: self.nullable,
+            default: self.default.clone(),
+            is_primary_key: self.is_primary_key,
+        }
+    }
+}
+
+/// A connection whose database can be introspected by [`tables`] and [`columns`].
+///
+/// This trait is sealed and cannot be implemented for types outside of SQLx.
+pub trait Introspect: Connection + private_introspect::Sealed {
+    #[doc(hidden)]
+    fn tables<'e>(
+        &'e mut self,
+        schema: Option<&'e str>,
+    ) -> BoxFuture<'e, Result<Vec<TableInfo>, Error>>;
+
+    #[doc(hidden)]
+    fn columns<'e>(
+        &'e mut self,
+        table: &'e str,
+    ) -> BoxFuture<'e, Result<Vec<ColumnInfo<Self::Database>>, Error>>;
+
+    // quotes a name so that it is taken as is by `columns`
+    #[doc(hidden)]
+    fn quote_identifier(name: &str) -> String;
+}
+
+pub(crate) mod private_introspect {
+    pub trait Sealed {}
+}
+
+/// Lists the tables in `schema`, ordered by name.
+///
+/// If `schema` is `None`, the current schema is used (the first schema in the `search_path`
+/// on **PostgreSQL**, the current database on **MySQL**).
+///
+/// The schema name follows the rules of an identifier in SQL. On **PostgreSQL**, it is folded
+/// to lowercase unless it is surrounded by double quotes. On **MySQL**, it is compared as the
+/// server would compare a database name, which is case-sensitive depending on the platform.
+pub async fn tables<C>(conn: &mut C, schema: Option<&str>) -> Result<Vec<TableInfo>, Error>
+where
+    C: Introspect,
+{
+    conn.tables(schema).await
+}
+
+/// Lists the columns of `table`, in the order they were declared.
+///
+/// The table may be qualified with its schema (`schema.table`), and its name follows the rules
+/// of an identifier in SQL: on **PostgreSQL**, it is folded to lowercase unless it is surrounded
+/// by double quotes; on **MySQL**, it may be surrounded by backticks and is compared as the
+/// server would compare a table name, which is case-sensitive depending on the platform.
+///
+/// An empty list is returned if there is no such table.
+pub async fn columns<C>(conn: &mut C, table: &str) -> Result<Vec<ColumnInfo<C::Database>>, Error>
+where
+    C: Introspect,
+{
+    conn.columns(table).await
+}
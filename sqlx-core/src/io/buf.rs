@@ -47,6 +47,14 @@ impl BufExt for Bytes {
     }
 
     fn get_str(&mut self, len: usize) -> Result<String, Error> {
+        if self.len() < len {
+            return Err(err_protocol!(
+                "expected a string of {} bytes but found {} bytes",
+                len,
+                self.len()
+            ));
+        }
+
         let v = from_utf8(&self[..len])
             .map_err(|err| err_protocol!("{}", err))
             .map(ToOwned::to_owned)?;
@@ -1,5 +1,7 @@
 use bytes::BufMut;
 
+use crate::error::Error;
+
 pub trait BufMutExt: BufMut {
     fn put_str_nul(&mut self, s: &str);
 }
@@ -10,3 +12,29 @@ impl BufMutExt for Vec<u8> {
         self.push(0);
     }
 }
+
+/// Checks that `s` can be sent with [`put_str_nul`](BufMutExt::put_str_nul); the receiver would
+/// otherwise end the string at its first NUL byte and read the rest as what comes after it.
+pub fn check_str_nul(what: &str, s: &str) -> Result<(), Error> {
+    if s.as_bytes().contains(&0) {
+        return Err(Error::Encode(
+            format!("string contains NUL byte: {}", what).into(),
+        ));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_check_str_nul() {
+    assert!(check_str_nul("username", "postgres").is_ok());
+    assert!(check_str_nul("username", "").is_ok());
+
+    let error = check_str_nul("username", "postgres\0admin").unwrap_err();
+
+    assert!(matches!(error, Error::Encode(_)));
+    assert_eq!(
+        error.to_string(),
+        "error occurred while encoding: string contains NUL byte: username"
+    );
+}
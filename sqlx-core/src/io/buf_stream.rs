@@ -10,6 +10,11 @@ use crate::error::Error;
 use crate::io::write_and_flush::WriteAndFlush;
 use crate::io::{decode::Decode, encode::Encode};
 use std::io::Cursor;
+use std::mem;
+use std::time::Duration;
+
+// the initial capacity of the read buffer, which it is shrunk back down to
+const RBUF_CAPACITY: usize = 4096;
 
 pub struct BufStream<S>
 where
@@ -23,6 +28,28 @@ where
 
     // we read into the read buffer using 100% safe code
     rbuf: BytesMut,
+
+    // the largest read into the read buffer since `shrink_read_buffer` was last called
+    rbuf_peak: usize,
+
+    // the largest read into the read buffer since it was last shrunk; the buffer may keep (or
+    // reclaim, once the bytes read are dropped) an allocation at least this large
+    rbuf_high_water: usize,
+
+    // set while a message has been partially read from or written to the underlying stream;
+    // if the operation is dropped in this state, the stream is no longer in sync with the server
+    pub(crate) busy: bool,
+
+    // set once the database has closed the connection or reading from or writing to it failed;
+    // it cannot be used anymore
+    pub(crate) broken: bool,
+
+    // the number of bytes read from and written to the underlying stream
+    pub(crate) bytes_read: u64,
+    pub(crate) bytes_written: u64,
+
+    // how long a flush may wait on the underlying stream before the connection is given up on
+    pub(crate) write_timeout: Option<Duration>,
 }
 
 impl<S> BufStream<S>
@@ -33,7 +60,14 @@ where
         Self {
             stream,
             wbuf: Vec::with_capacity(512),
-            rbuf: BytesMut::with_capacity(4096),
+            rbuf: BytesMut::with_capacity(RBUF_CAPACITY),
+            rbuf_peak: 0,
+            rbuf_high_water: 0,
+            busy: false,
+            broken: false,
+            bytes_read: 0,
+            bytes_written: 0,
+            write_timeout: None,
         }
     }
 
@@ -51,13 +85,50 @@ where
         value.encode_with(&mut self.wbuf, context);
     }
 
-    pub fn flush(&mut self) -> WriteAndFlush<'_, S> {
-        WriteAndFlush {
+    /// Writes the buffer to the stream and flushes it.
+    ///
+    /// If the write timeout elapses first, the connection is marked as broken, as part of a
+    /// message may have been sent already.
+    pub async fn flush(&mut self) -> Result<(), Error> {
+        let write_timeout = self.write_timeout;
+
+        let flush = WriteAndFlush {
             stream: &mut self.stream,
             buf: Cursor::new(&mut self.wbuf),
+            busy: &mut self.busy,
+            broken: &mut self.broken,
+            bytes_written: &mut self.bytes_written,
+        };
+
+        let timeout = match write_timeout {
+            Some(timeout) => timeout,
+            None => return flush.await,
+        };
+
+        match sqlx_rt::timeout(timeout, flush).await {
+            Ok(result) => result,
+
+            Err(_) => {
+                self.broken = true;
+
+                Err(Error::Io(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    "timed out writing to the database",
+                )))
+            }
         }
     }
 
+    /// Returns [`Error::ConnectionBusy`] if a previous operation was abandoned partway
+    /// through a message.
+    pub fn ensure_not_busy(&self) -> Result<(), Error> {
+        if self.busy {
+            return Err(Error::ConnectionBusy);
+        }
+
+        Ok(())
+    }
+
     pub async fn read<'de, T>(&mut self, cnt: usize) -> Result<T, Error>
     where
         T: Decode<'de, ()>,
@@ -72,15 +143,71 @@ where
         T::decode_with(self.read_raw(cnt).await?.freeze(), context)
     }
 
+    /// Returns [`Error::ConnectionClosed`] if the connection was closed by the database or
+    /// failed with an I/O error before.
+    pub fn ensure_not_broken(&self) -> Result<(), Error> {
+        if self.broken {
+            return Err(Error::broken());
+        }
+
+        Ok(())
+    }
+
     pub async fn read_raw(&mut self, cnt: usize) -> Result<BytesMut, Error> {
-        read_raw_into(&mut self.stream, &mut self.rbuf, cnt).await?;
+        self.ensure_not_broken()?;
+
+        self.rbuf_peak = self.rbuf_peak.max(cnt);
+        self.rbuf_high_water = self.rbuf_high_water.max(cnt);
+
+        if let Err(error) = read_raw_into(&mut self.stream, &mut self.rbuf, cnt).await {
+            self.broken = true;
+
+            return Err(error);
+        }
+
+        self.bytes_read += cnt as u64;
+
         let buf = self.rbuf.split_to(cnt);
 
         Ok(buf)
     }
 
+    /// Returns the capacity the read buffer may hold on to.
+    pub fn read_buffer_capacity(&self) -> usize {
+        self.rbuf.capacity().max(self.rbuf_high_water)
+    }
+
+    /// Replaces the read buffer with one of the initial capacity if it has grown larger than
+    /// `threshold`, unless a read since the last call needed more than a quarter of that.
+    ///
+    /// This keeps a single large message from holding on to its memory for the lifetime of the
+    /// connection, while not reallocating the buffer over and over for a connection that keeps
+    /// receiving large messages.
+    pub fn shrink_read_buffer(&mut self, threshold: usize) {
+        let peak = mem::take(&mut self.rbuf_peak);
+
+        if self.read_buffer_capacity() <= threshold || peak > threshold / 4 {
+            return;
+        }
+
+        let mut rbuf = BytesMut::with_capacity(RBUF_CAPACITY.max(self.rbuf.len()));
+        rbuf.extend_from_slice(&self.rbuf);
+
+        self.rbuf = rbuf;
+        self.rbuf_high_water = 0;
+    }
+
     pub async fn read_raw_into(&mut self, buf: &mut BytesMut, cnt: usize) -> Result<(), Error> {
-        read_raw_into(&mut self.stream, buf, cnt).await
+        self.ensure_not_broken()?;
+
+        let result = read_raw_into(&mut self.stream, buf, cnt).await;
+
+        match &result {
+            Ok(()) => self.bytes_read += cnt as u64,
+            Err(_) => self.broken = true,
+        }
+
+        result
     }
 }
 
@@ -121,7 +248,10 @@ impl<'a> BufTruncator<'a> {
         self.buf.resize(self.filled_len + space, 0);
     }
     async fn read<S: AsyncRead + Unpin>(&mut self, stream: &mut S) -> Result<usize, Error> {
-        let n = stream.read(&mut self.buf[self.filled_len..]).await?;
+        let n = stream
+            .read(&mut self.buf[self.filled_len..])
+            .await
+            .map_err(Error::io)?;
         self.filled_len += n;
         Ok(n)
     }
@@ -153,9 +283,89 @@ async fn read_raw_into<S: AsyncRead + Unpin>(
 
             // and an unexpected EOF means the server told us to go away
 
-            return Err(io::Error::from(io::ErrorKind::ConnectionAborted).into());
+            return Err(Error::io(io::ErrorKind::UnexpectedEof.into()));
         }
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::net::TcpListener;
+    use std::time::Instant;
+
+    use super::*;
+    use crate::net::Socket;
+
+    #[test]
+    fn it_times_out_writing_to_a_stalled_peer() {
+        // accepts connections but never reads from them
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        sqlx_rt::block_on(async {
+            let socket = sqlx_rt::TcpStream::connect(addr).await.unwrap();
+            let (_peer, _) = listener.accept().unwrap();
+
+            let mut stream = BufStream::new(socket);
+            stream.write_timeout = Some(Duration::from_millis(500));
+
+            // far more than the socket buffers of both ends can take in
+            stream.wbuf.resize(64 * 1024 * 1024, 0);
+
+            let started = Instant::now();
+            let error = stream.flush().await.unwrap_err();
+
+            assert!(
+                matches!(&error, Error::Io(e) if e.kind() == io::ErrorKind::TimedOut),
+                "{}",
+                error
+            );
+            assert!(started.elapsed() < Duration::from_secs(5));
+
+            // part of the buffer was sent, so the connection cannot be used anymore
+            assert!(stream.busy);
+            assert!(stream.broken);
+            assert!(stream.wbuf.is_empty());
+        });
+    }
+
+    #[test]
+    fn it_fails_fast_once_the_peer_closed_the_connection() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        sqlx_rt::block_on(async {
+            let socket = sqlx_rt::TcpStream::connect(addr).await.unwrap();
+            let (peer, _) = listener.accept().unwrap();
+
+            let mut stream = BufStream::new(Socket::Tcp(socket));
+            assert!(!stream.is_closed());
+
+            drop(peer);
+
+            // the reactor may take a moment to notice
+            let started = Instant::now();
+
+            while !stream.is_closed() {
+                assert!(started.elapsed() < Duration::from_secs(5));
+                sqlx_rt::sleep(Duration::from_millis(10)).await;
+            }
+
+            let error = stream.read_raw(1).await.unwrap_err();
+
+            assert!(error.is_connection_closed(), "{}", error);
+            assert!(stream.broken);
+
+            // nothing is written into the closed connection anymore
+            stream.wbuf.extend_from_slice(b"Q");
+
+            let error = stream.flush().await.unwrap_err();
+
+            assert!(error.is_connection_closed(), "{}", error);
+            assert_eq!(stream.bytes_written, 0);
+            assert!(stream.wbuf.is_empty());
+        });
+    }
+}
@@ -5,8 +5,11 @@ mod decode;
 mod encode;
 mod write_and_flush;
 
+#[cfg(test)]
+pub(crate) mod scripted;
+
 pub use buf::BufExt;
-pub use buf_mut::BufMutExt;
+pub use buf_mut::{check_str_nul, BufMutExt};
 pub use buf_stream::BufStream;
 pub use decode::Decode;
 pub use encode::Encode;
@@ -0,0 +1,101 @@
+use std::collections::VecDeque;
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use sqlx_rt::{AsyncRead, AsyncWrite};
+
+/// A stream that returns the chunks it was created with, one per read, and then EOF.
+///
+/// Used to test how a protocol is read when the server's messages arrive split at arbitrary
+/// byte boundaries. Whatever is written to the stream is kept in `written`.
+#[derive(Debug)]
+pub(crate) struct ScriptedStream {
+    chunks: VecDeque<Vec<u8>>,
+    pub(crate) written: Vec<u8>,
+}
+
+impl ScriptedStream {
+    pub(crate) fn new<I, C>(chunks: I) -> Self
+    where
+        I: IntoIterator<Item = C>,
+        C: Into<Vec<u8>>,
+    {
+        Self {
+            chunks: chunks.into_iter().map(Into::into).collect(),
+            written: Vec::new(),
+        }
+    }
+
+    // splits `data` into chunks of `size` bytes
+    pub(crate) fn chunked(data: &[u8], size: usize) -> Self {
+        Self::new(data.chunks(size).map(<[u8]>::to_vec))
+    }
+
+    // copies as much of the next chunk as fits into `buf`
+    fn read_chunk(&mut self, buf: &mut [u8]) -> usize {
+        let chunk = match self.chunks.front_mut() {
+            Some(chunk) => chunk,
+            None => return 0,
+        };
+
+        let n = chunk.len().min(buf.len());
+        buf[..n].copy_from_slice(&chunk[..n]);
+        chunk.drain(..n);
+
+        if chunk.is_empty() {
+            self.chunks.pop_front();
+        }
+
+        n
+    }
+}
+
+impl AsyncRead for ScriptedStream {
+    #[cfg(any(feature = "_rt-actix", feature = "_rt-tokio"))]
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &mut sqlx_rt::ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let n = self.read_chunk(buf.initialize_unfilled());
+        buf.advance(n);
+
+        Poll::Ready(Ok(()))
+    }
+
+    #[cfg(feature = "_rt-async-std")]
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        Poll::Ready(Ok(self.read_chunk(buf)))
+    }
+}
+
+impl AsyncWrite for ScriptedStream {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.written.extend_from_slice(buf);
+
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    #[cfg(any(feature = "_rt-actix", feature = "_rt-tokio"))]
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    #[cfg(feature = "_rt-async-std")]
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
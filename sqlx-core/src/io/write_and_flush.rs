@@ -2,15 +2,22 @@ use crate::error::Error;
 use futures_core::Future;
 use futures_util::ready;
 use sqlx_rt::AsyncWrite;
-use std::io::{BufRead, Cursor};
+use std::io::{self, BufRead, Cursor};
 use std::pin::Pin;
 use std::task::{Context, Poll};
 
+// the number of bytes written before yielding to the executor, so that a task writing a large
+// buffer to a stream that keeps accepting writes can still be cancelled or time out
+const WRITE_SEGMENT: usize = 64 * 1024;
+
 // Atomic operation that writes the full buffer to the stream, flushes the stream, and then
 // clears the buffer (even if either of the two previous operations failed).
 pub struct WriteAndFlush<'a, S> {
     pub(super) stream: &'a mut S,
     pub(super) buf: Cursor<&'a mut Vec<u8>>,
+    pub(super) busy: &'a mut bool,
+    pub(super) broken: &'a mut bool,
+    pub(super) bytes_written: &'a mut u64,
 }
 
 impl<S: AsyncWrite + Unpin> Future for WriteAndFlush<'_, S> {
@@ -20,23 +27,67 @@ impl<S: AsyncWrite + Unpin> Future for WriteAndFlush<'_, S> {
         let Self {
             ref mut stream,
             ref mut buf,
+            ref mut busy,
+            ref mut broken,
+            ref mut bytes_written,
         } = *self;
 
-        loop {
-            let read = buf.fill_buf()?;
+        // the connection cannot be used anymore; there is no point in writing to it
+        if **broken {
+            return Poll::Ready(Err(Error::broken()));
+        }
 
-            if !read.is_empty() {
-                let written = ready!(Pin::new(&mut *stream).poll_write(cx, read)?);
-                buf.consume(written);
-            } else {
-                break;
-            }
+        // a previous operation was dropped in the middle of a message; writing anything
+        // more would only interleave with the remains of that message
+        if **busy && buf.position() == 0 {
+            return Poll::Ready(Err(Error::ConnectionBusy));
         }
 
-        Pin::new(stream).poll_flush(cx).map_err(Error::Io)
+        let result =
+            ready!(poll_write_and_flush(stream, buf, busy, bytes_written, cx)).map_err(Error::io);
+
+        // whatever went wrong, part of a message may have been sent
+        **broken |= result.is_err();
+
+        Poll::Ready(result)
     }
 }
 
+fn poll_write_and_flush<S: AsyncWrite + Unpin>(
+    stream: &mut S,
+    buf: &mut Cursor<&mut Vec<u8>>,
+    busy: &mut bool,
+    bytes_written: &mut u64,
+    cx: &mut Context<'_>,
+) -> Poll<io::Result<()>> {
+    let mut segment = 0;
+
+    loop {
+        let read = buf.fill_buf()?;
+
+        if !read.is_empty() {
+            if segment >= WRITE_SEGMENT {
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+
+            let read = &read[..read.len().min(WRITE_SEGMENT - segment)];
+            let written = ready!(Pin::new(&mut *stream).poll_write(cx, read)?);
+            segment += written;
+            buf.consume(written);
+            *bytes_written += written as u64;
+
+            // if we are dropped before the rest is written, the server will see a
+            // truncated message
+            *busy = (buf.position() as usize) < buf.get_ref().len();
+        } else {
+            break;
+        }
+    }
+
+    Pin::new(stream).poll_flush(cx)
+}
+
 impl<'a, S> Drop for WriteAndFlush<'a, S> {
     fn drop(&mut self) {
         // clear the buffer regardless of whether the flush succeeded or not
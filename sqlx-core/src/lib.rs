@@ -57,24 +57,49 @@ pub mod column;
 #[macro_use]
 pub mod statement;
 
+#[macro_use]
+#[doc(hidden)]
+pub mod type_mapping;
+
 mod common;
 pub use either::Either;
 pub mod database;
 pub mod describe;
 pub mod executor;
+pub mod fetch_progress;
 pub mod from_row;
+mod instrument;
 mod io;
 mod logger;
+#[cfg(any(feature = "postgres", feature = "mysql"))]
+mod named;
 mod net;
+pub mod owned_row;
 pub mod query_as;
 pub mod query_scalar;
 pub mod row;
 pub mod type_info;
 pub mod value;
+pub mod wire;
+
+#[doc(hidden)]
+pub mod url_scheme;
 
 #[cfg(feature = "migrate")]
 pub mod migrate;
 
+#[cfg(feature = "export")]
+#[cfg_attr(docsrs, doc(cfg(feature = "export")))]
+pub mod export;
+
+#[cfg(feature = "introspect")]
+#[cfg_attr(docsrs, doc(cfg(feature = "introspect")))]
+pub mod introspect;
+
+#[cfg(feature = "introspect")]
+#[cfg_attr(docsrs, doc(cfg(feature = "introspect")))]
+pub mod codegen;
+
 #[cfg(all(
     any(
         feature = "postgres",
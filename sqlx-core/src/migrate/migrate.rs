@@ -1,5 +1,5 @@
 use crate::error::Error;
-use crate::migrate::{AppliedMigration, MigrateError, Migration};
+use crate::migrate::{AppliedMigration, MigrateError, Migration, MigrationEvent};
 use futures_core::future::BoxFuture;
 use std::time::Duration;
 
@@ -62,6 +62,30 @@ pub trait Migrate {
         migration: &'m Migration,
     ) -> BoxFuture<'m, Result<Duration, MigrateError>>;
 
+    // same as [apply], but executes the statements of the migration one at a time
+    // and reports each of them to `progress`
+    // by default, applies the migration with [apply] and reports it as a single statement
+    fn apply_with_progress<'e: 'm, 'm>(
+        &'e mut self,
+        migration: &'m Migration,
+        progress: &'m mut (dyn FnMut(MigrationEvent) + Send),
+    ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
+        let apply = self.apply(migration);
+
+        Box::pin(async move {
+            let elapsed = apply.await?;
+
+            // the number of rows the migration affected is not known
+            progress(MigrationEvent::StatementCompleted {
+                index: 0,
+                elapsed,
+                rows_affected: 0,
+            });
+
+            Ok(elapsed)
+        })
+    }
+
     // run a revert SQL from migration in a DDL transaction
     // deletes the row in [_migrations] table with specified migration version on completion (success or failure)
     // returns the time taking to run the migration SQL
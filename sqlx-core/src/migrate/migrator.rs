@@ -1,5 +1,7 @@
 use crate::acquire::Acquire;
-use crate::migrate::{AppliedMigration, Migrate, MigrateError, Migration, MigrationSource};
+use crate::migrate::{
+    AppliedMigration, Migrate, MigrateError, Migration, MigrationEvent, MigrationSource,
+};
 use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
 use std::ops::Deref;
@@ -88,6 +90,59 @@ impl Migrator {
     /// # }
     /// ```
     pub async fn run<'a, A>(&self, migrator: A) -> Result<(), MigrateError>
+    where
+        A: Acquire<'a>,
+        <A::Connection as Deref>::Target: Migrate,
+    {
+        self.run_inner(migrator, None).await
+    }
+
+    /// Run any pending migrations like [`run`][Self::run], reporting the progress of each to
+    /// `progress`.
+    ///
+    /// The statements of each migration are executed one at a time, so that `progress` is told
+    /// as each of them completes. A migration that cannot be split into its statements with
+    /// confidence is executed as a whole, and reported as a single statement.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use sqlx_core::migrate::MigrateError;
+    /// # #[cfg(feature = "sqlite")]
+    /// # fn main() -> Result<(), MigrateError> {
+    /// #     sqlx_rt::block_on(async move {
+    /// # use sqlx_core::migrate::Migrator;
+    /// use sqlx_core::migrate::MigrationEvent;
+    ///
+    /// let m = Migrator::new(std::path::Path::new("./migrations")).await?;
+    /// let pool = sqlx_core::sqlite::SqlitePoolOptions::new().connect("sqlite::memory:").await?;
+    /// m.run_with_progress(&pool, |event| {
+    ///     if let MigrationEvent::MigrationCompleted { version, elapsed } = event {
+    ///         println!("applied migration {} in {:?}", version, elapsed);
+    ///     }
+    /// })
+    /// .await
+    /// #     })
+    /// # }
+    /// ```
+    pub async fn run_with_progress<'a, A, F>(
+        &self,
+        migrator: A,
+        mut progress: F,
+    ) -> Result<(), MigrateError>
+    where
+        A: Acquire<'a>,
+        <A::Connection as Deref>::Target: Migrate,
+        F: FnMut(MigrationEvent) + Send,
+    {
+        self.run_inner(migrator, Some(&mut progress)).await
+    }
+
+    async fn run_inner<'a, A>(
+        &self,
+        migrator: A,
+        mut progress: Option<&mut (dyn FnMut(MigrationEvent) + Send)>,
+    ) -> Result<(), MigrateError>
     where
         A: Acquire<'a>,
         <A::Connection as Deref>::Target: Migrate,
@@ -125,9 +180,19 @@ impl Migrator {
                         return Err(MigrateError::VersionMismatch(migration.version));
                     }
                 }
-                None => {
-                    conn.apply(migration).await?;
-                }
+                None => match progress.as_deref_mut() {
+                    Some(progress) => {
+                        let version = migration.version;
+
+                        progress(MigrationEvent::MigrationStarted { version });
+                        let elapsed = conn.apply_with_progress(migration, &mut *progress).await?;
+                        progress(MigrationEvent::MigrationCompleted { version, elapsed });
+                    }
+
+                    None => {
+                        conn.apply(migration).await?;
+                    }
+                },
             }
         }
 
@@ -4,11 +4,16 @@ mod migrate;
 mod migration;
 mod migration_type;
 mod migrator;
+mod progress;
 mod source;
+// only used by the drivers
+#[allow(dead_code)]
+pub(crate) mod split;
 
 pub use error::MigrateError;
 pub use migrate::{Migrate, MigrateDatabase};
 pub use migration::{AppliedMigration, Migration};
 pub use migration_type::MigrationType;
 pub use migrator::Migrator;
+pub use progress::MigrationEvent;
 pub use source::MigrationSource;
@@ -0,0 +1,22 @@
+use std::time::Duration;
+
+/// The progress of [`Migrator::run_with_progress`][crate::migrate::Migrator::run_with_progress].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum MigrationEvent {
+    /// A pending migration is about to be applied.
+    MigrationStarted { version: i64 },
+
+    /// A statement of the migration being applied was executed.
+    ///
+    /// `index` counts the statements of the migration from 0. A migration that cannot be split
+    /// into its statements with confidence is executed as a single one.
+    StatementCompleted {
+        index: usize,
+        elapsed: Duration,
+        rows_affected: u64,
+    },
+
+    /// The migration was applied, taking `elapsed` to execute its statements.
+    MigrationCompleted { version: i64, elapsed: Duration },
+}
@@ -0,0 +1,395 @@
+// Splits the SQL of a migration into its statements, so that each can be executed and reported
+// on its own. This only has to tell where statements end, not understand them: it skips over
+// quoted strings and identifiers, dollar-quoted strings and comments, and keeps track of the
+// `BEGIN .. END` bodies of compound statements, which contain semicolons of their own.
+//
+// Whenever it is not sure, e.g. because a quote or a body is not closed, it gives up and the
+// migration is executed as a whole, as it would be without reporting progress.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Dialect {
+    Postgres,
+    MySql,
+    Sqlite,
+}
+
+// the words that can follow `BEGIN` when it starts a transaction rather than a compound statement
+const TRANSACTION_MODES: &[&str] = &[
+    "WORK",
+    "TRANSACTION",
+    "ISOLATION",
+    "READ",
+    "DEFERRABLE",
+    "DEFERRED",
+    "IMMEDIATE",
+    "EXCLUSIVE",
+];
+
+// the words that can follow `END` when it ends a statement that did not start with `BEGIN`
+const END_OF_LOOPS: &[&str] = &["IF", "LOOP", "WHILE", "REPEAT", "FOR"];
+
+/// Splits `sql` into its statements, without the semicolons between them and leaving out those
+/// that are empty or only comments.
+///
+/// Returns `None` if it cannot be split with confidence.
+pub(crate) fn split_statements(sql: &str, dialect: Dialect) -> Option<Vec<&str>> {
+    let bytes = sql.as_bytes();
+    let mut statements = Vec::new();
+
+    let mut start = 0;
+    let mut i = 0;
+
+    // the number of `BEGIN` or `CASE` that were not ended yet
+    let mut depth = 0_usize;
+
+    // whether the current statement has anything but whitespace and comments
+    let mut significant = false;
+
+    while i < bytes.len() {
+        let b = bytes[i];
+
+        match b {
+            b'\'' => {
+                i = skip_quoted(bytes, i, b'\'', dialect == Dialect::MySql)?;
+                significant = true;
+                continue;
+            }
+
+            b'"' => {
+                i = skip_quoted(bytes, i, b'"', dialect == Dialect::MySql)?;
+                significant = true;
+                continue;
+            }
+
+            b'`' if dialect != Dialect::Postgres => {
+                i = skip_quoted(bytes, i, b'`', false)?;
+                significant = true;
+                continue;
+            }
+
+            b'[' if dialect == Dialect::Sqlite => {
+                i += find(&bytes[i..], b"]")? + 1;
+                significant = true;
+                continue;
+            }
+
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                i = skip_line(bytes, i);
+                continue;
+            }
+
+            b'#' if dialect == Dialect::MySql => {
+                i = skip_line(bytes, i);
+                continue;
+            }
+
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                // MySQL executes the contents of `/*! .. */`, and optimizer hints are not
+                // statements on their own either
+                if matches!(bytes.get(i + 2), Some(b'!') | Some(b'+')) {
+                    significant = true;
+                }
+
+                i = skip_block_comment(bytes, i, dialect == Dialect::Postgres)?;
+                continue;
+            }
+
+            b'$' if dialect == Dialect::Postgres => {
+                if let Some(tag) = dollar_quote_tag(bytes, i) {
+                    let body = i + tag.len();
+                    i = body + find(&bytes[body..], tag)? + tag.len();
+                    significant = true;
+                    continue;
+                }
+            }
+
+            b';' if depth == 0 => {
+                if significant {
+                    statements.push(sql[start..i].trim());
+                }
+
+                start = i + 1;
+                significant = false;
+            }
+
+            b if is_word_start(b) => {
+                let word = read_word(bytes, i);
+                i += word.len();
+
+                // `E'..'` is a string with backslash escapes
+                if dialect == Dialect::Postgres
+                    && word.eq_ignore_ascii_case(b"E")
+                    && bytes.get(i) == Some(&b'\'')
+                {
+                    i = skip_quoted(bytes, i, b'\'', true)?;
+                } else if !significant && word.eq_ignore_ascii_case(b"DELIMITER") {
+                    // a command of the `mysql` client rather than a statement
+                    return None;
+                } else if word.eq_ignore_ascii_case(b"BEGIN") {
+                    if starts_block(bytes, i) {
+                        depth += 1;
+                    }
+                } else if word.eq_ignore_ascii_case(b"CASE") {
+                    depth += 1;
+                } else if word.eq_ignore_ascii_case(b"END") {
+                    let (next, end) = next_word(bytes, i);
+
+                    if END_OF_LOOPS
+                        .iter()
+                        .any(|w| next.eq_ignore_ascii_case(w.as_bytes()))
+                    {
+                        i = end;
+                    } else {
+                        if next.eq_ignore_ascii_case(b"CASE") {
+                            i = end;
+                        }
+
+                        // e.g. `END` as a synonym of `COMMIT` in Postgres
+                        depth = depth.checked_sub(1)?;
+                    }
+                }
+
+                significant = true;
+                continue;
+            }
+
+            b if !b.is_ascii_whitespace() => significant = true,
+
+            _ => {}
+        }
+
+        i += 1;
+    }
+
+    if depth > 0 {
+        return None;
+    }
+
+    if significant {
+        statements.push(sql[start..].trim());
+    }
+
+    Some(statements)
+}
+
+// returns the index after the closing quote of the string or identifier starting at `i`
+fn skip_quoted(bytes: &[u8], i: usize, quote: u8, backslash_escapes: bool) -> Option<usize> {
+    let mut j = i + 1;
+
+    loop {
+        match *bytes.get(j)? {
+            b'\\' if backslash_escapes => j += 2,
+
+            // a quote is escaped by doubling it
+            b if b == quote && bytes.get(j + 1) == Some(&quote) => j += 2,
+
+            b if b == quote => return Some(j + 1),
+
+            _ => j += 1,
+        }
+    }
+}
+
+fn skip_line(bytes: &[u8], i: usize) -> usize {
+    find(&bytes[i..], b"\n").map_or(bytes.len(), |end| i + end + 1)
+}
+
+// block comments nest in Postgres
+fn skip_block_comment(bytes: &[u8], i: usize, nested: bool) -> Option<usize> {
+    let mut depth = 0;
+    let mut j = i;
+
+    loop {
+        if bytes.get(j..j + 2)? == b"/*" && (nested || depth == 0) {
+            depth += 1;
+            j += 2;
+        } else if &bytes[j..j + 2] == b"*/" {
+            depth -= 1;
+            j += 2;
+
+            if depth == 0 {
+                return Some(j);
+            }
+        } else {
+            j += 1;
+        }
+    }
+}
+
+// the delimiter of the dollar-quoted string starting at `i`, e.g. `$$` or `$body$`; not `$1`,
+// which is a parameter
+fn dollar_quote_tag(bytes: &[u8], i: usize) -> Option<&[u8]> {
+    let tag = match bytes.get(i + 1)? {
+        b'$' => 0,
+        &b if is_word_start(b) => read_word(bytes, i + 1).len(),
+        _ => return None,
+    };
+
+    // a word may contain dollar signs as well, but the tag ends at the first
+    let tag = bytes[i + 1..i + 1 + tag]
+        .iter()
+        .position(|&b| b == b'$')
+        .unwrap_or(tag);
+
+    if bytes.get(i + 1 + tag) == Some(&b'$') {
+        Some(&bytes[i..i + tag + 2])
+    } else {
+        None
+    }
+}
+
+// whether the `BEGIN` ending at `i` starts a compound statement, rather than a transaction
+fn starts_block(bytes: &[u8], i: usize) -> bool {
+    let (next, end) = next_word(bytes, i);
+
+    if next.is_empty() {
+        // `BEGIN;`
+        return false;
+    }
+
+    if next.eq_ignore_ascii_case(b"NOT") {
+        // `BEGIN NOT ATOMIC` in MariaDB, but `BEGIN NOT DEFERRABLE` in Postgres
+        return next_word(bytes, end).0.eq_ignore_ascii_case(b"ATOMIC");
+    }
+
+    !TRANSACTION_MODES
+        .iter()
+        .any(|mode| next.eq_ignore_ascii_case(mode.as_bytes()))
+}
+
+// the word following whitespace after `i`, if any, and the index after it
+fn next_word(bytes: &[u8], i: usize) -> (&[u8], usize) {
+    let start = bytes[i..]
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .map_or(bytes.len(), |n| i + n);
+
+    match bytes.get(start) {
+        Some(&b) if is_word_start(b) => {
+            let word = read_word(bytes, start);
+            (word, start + word.len())
+        }
+
+        _ => (&[], i),
+    }
+}
+
+fn is_word_start(b: u8) -> bool {
+    b.is_ascii_alphabetic() || b == b'_' || !b.is_ascii()
+}
+
+fn read_word(bytes: &[u8], i: usize) -> &[u8] {
+    let len = bytes[i..]
+        .iter()
+        .position(|&b| !(is_word_start(b) || b.is_ascii_digit() || b == b'$'))
+        .unwrap_or(bytes.len() - i);
+
+    &bytes[i..i + len]
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_splits_statements() {
+        let sql = "CREATE TABLE a (id INT);\n\nINSERT INTO a VALUES (1);  \n-- done\n";
+
+        assert_eq!(
+            split_statements(sql, Dialect::Postgres).unwrap(),
+            ["CREATE TABLE a (id INT)", "INSERT INTO a VALUES (1)"]
+        );
+    }
+
+    #[test]
+    fn it_skips_quotes_and_comments() {
+        let sql = "INSERT INTO a VALUES ('a;b', 'it''s;', \"c;d\"); -- e;f\n\
+                   /* g; /* h; */ i; */ SELECT E'\\';'";
+
+        assert_eq!(
+            split_statements(sql, Dialect::Postgres).unwrap(),
+            [
+                "INSERT INTO a VALUES ('a;b', 'it''s;', \"c;d\")",
+                "-- e;f\n/* g; /* h; */ i; */ SELECT E'\\';'"
+            ]
+        );
+
+        let sql = "INSERT INTO `a;` VALUES ('\\';'); # b;\nSELECT 1";
+
+        assert_eq!(
+            split_statements(sql, Dialect::MySql).unwrap(),
+            ["INSERT INTO `a;` VALUES ('\\';')", "# b;\nSELECT 1"]
+        );
+    }
+
+    #[test]
+    fn it_keeps_dollar_quoted_function_bodies_together() {
+        let function = "CREATE FUNCTION add_one(n INTEGER) RETURNS INTEGER AS $body$\n\
+                        BEGIN\n    RETURN n + 1;\nEND;\n$body$ LANGUAGE plpgsql";
+        let sql = format!(
+            "{};\nDO $$ BEGIN PERFORM $1; END $$;\nSELECT add_one(1);",
+            function
+        );
+
+        assert_eq!(
+            split_statements(&sql, Dialect::Postgres).unwrap(),
+            [
+                function,
+                "DO $$ BEGIN PERFORM $1; END $$",
+                "SELECT add_one(1)"
+            ]
+        );
+    }
+
+    #[test]
+    fn it_keeps_compound_statements_together() {
+        let procedure = "CREATE PROCEDURE p()\nBEGIN\n    DECLARE n INT DEFAULT 0;\n    \
+                         WHILE n < 3 DO\n        SET n = n + 1;\n    END WHILE;\n    \
+                         IF n = 3 THEN\n        SELECT CASE n WHEN 3 THEN 'x;' ELSE 'y' END;\n    \
+                         END IF;\nEND";
+        let sql = format!("{};\nBEGIN;\nCALL p();\nCOMMIT;", procedure);
+
+        assert_eq!(
+            split_statements(&sql, Dialect::MySql).unwrap(),
+            [procedure, "BEGIN", "CALL p()", "COMMIT"]
+        );
+
+        let trigger = "CREATE TRIGGER t AFTER INSERT ON a BEGIN UPDATE b SET n = n + 1; END";
+        let sql = format!("{};\nBEGIN IMMEDIATE;", trigger);
+
+        assert_eq!(
+            split_statements(&sql, Dialect::Sqlite).unwrap(),
+            [trigger, "BEGIN IMMEDIATE"]
+        );
+    }
+
+    #[test]
+    fn it_gives_up_when_unsure() {
+        // unterminated quotes and comments
+        assert_eq!(split_statements("SELECT 'a;", Dialect::Postgres), None);
+        assert_eq!(split_statements("SELECT $$a;", Dialect::Postgres), None);
+        assert_eq!(split_statements("SELECT 1; /* a;", Dialect::Postgres), None);
+
+        // unbalanced bodies
+        assert_eq!(
+            split_statements("BEGIN ATOMIC SELECT 1;", Dialect::Postgres),
+            None
+        );
+        assert_eq!(
+            split_statements("BEGIN; SELECT 1; END;", Dialect::Postgres),
+            None
+        );
+
+        // client commands
+        assert_eq!(
+            split_statements("DELIMITER //\nSELECT 1//", Dialect::MySql),
+            None
+        );
+    }
+}
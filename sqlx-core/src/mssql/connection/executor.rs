@@ -188,6 +188,7 @@ impl<'c> Executor<'c> for &'c mut MssqlConnection {
                 nullable,
                 columns: (metadata.columns).clone(),
                 parameters: None,
+                returns_rows: !metadata.columns.is_empty(),
             })
         })
     }
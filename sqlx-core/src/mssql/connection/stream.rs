@@ -3,8 +3,8 @@ use std::ops::{Deref, DerefMut};
 use bytes::{Bytes, BytesMut};
 use sqlx_rt::TcpStream;
 
+use crate::column::ColumnNames;
 use crate::error::Error;
-use crate::ext::ustr::UStr;
 use crate::io::{BufStream, Encode};
 use crate::mssql::protocol::col_meta_data::ColMetaData;
 use crate::mssql::protocol::done::{Done, Status as DoneStatus};
@@ -20,7 +20,6 @@ use crate::mssql::protocol::return_value::ReturnValue;
 use crate::mssql::protocol::row::Row;
 use crate::mssql::{MssqlColumn, MssqlConnectOptions, MssqlDatabaseError};
 use crate::net::MaybeTlsStream;
-use crate::HashMap;
 use std::sync::Arc;
 
 pub(crate) struct MssqlStream {
@@ -40,7 +39,7 @@ pub(crate) struct MssqlStream {
     // most recent column data from ColMetaData
     // we need to store this as its needed when decoding <Row>
     pub(crate) columns: Arc<Vec<MssqlColumn>>,
-    pub(crate) column_names: Arc<HashMap<UStr, usize>>,
+    pub(crate) column_names: Arc<ColumnNames>,
 }
 
 impl MssqlStream {
@@ -22,6 +22,8 @@ impl Database for Mssql {
     type TypeInfo = MssqlTypeInfo;
 
     type Value = MssqlValue;
+
+    const DB_SYSTEM: &'static str = "mssql";
 }
 
 impl<'r> HasValueRef<'r> for Mssql {
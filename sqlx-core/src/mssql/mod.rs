@@ -15,6 +15,7 @@ mod row;
 mod statement;
 mod transaction;
 mod type_info;
+mod type_mapping;
 pub mod types;
 mod value;
 
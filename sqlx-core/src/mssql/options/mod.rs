@@ -1,4 +1,5 @@
 use crate::connection::LogSettings;
+use crate::ext::password::Password;
 
 mod connect;
 mod parse;
@@ -9,7 +10,7 @@ pub struct MssqlConnectOptions {
     pub(crate) port: u16,
     pub(crate) username: String,
     pub(crate) database: String,
-    pub(crate) password: Option<String>,
+    pub(crate) password: Option<Password>,
     pub(crate) log_settings: LogSettings,
 }
 
@@ -47,7 +48,7 @@ impl MssqlConnectOptions {
     }
 
     pub fn password(mut self, password: &str) -> Self {
-        self.password = Some(password.to_owned());
+        self.password = Some(password.into());
         self
     }
 
@@ -1,18 +1,17 @@
 use crate::error::Error;
 use crate::mssql::MssqlConnectOptions;
-use percent_encoding::percent_decode_str;
+use crate::net::{decode_url_part, parse_url, url_host};
 use std::str::FromStr;
-use url::Url;
 
 impl FromStr for MssqlConnectOptions {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let url: Url = s.parse().map_err(Error::config)?;
+        let url = parse_url(s)?;
         let mut options = Self::new();
 
-        if let Some(host) = url.host_str() {
-            options = options.host(host);
+        if let Some(host) = url_host(&url)? {
+            options = options.host(&host);
         }
 
         if let Some(port) = url.port() {
@@ -21,24 +20,16 @@ impl FromStr for MssqlConnectOptions {
 
         let username = url.username();
         if !username.is_empty() {
-            options = options.username(
-                &*percent_decode_str(username)
-                    .decode_utf8()
-                    .map_err(Error::config)?,
-            );
+            options = options.username(&decode_url_part(username, "username")?);
         }
 
         if let Some(password) = url.password() {
-            options = options.password(
-                &*percent_decode_str(password)
-                    .decode_utf8()
-                    .map_err(Error::config)?,
-            );
+            options = options.password(&decode_url_part(password, "password")?);
         }
 
         let path = url.path().trim_start_matches('/');
         if !path.is_empty() {
-            options = options.database(path);
+            options = options.database(&decode_url_part(path, "database name")?);
         }
 
         Ok(options)
@@ -60,3 +51,19 @@ fn it_parses_password_with_non_ascii_chars_correctly() {
 
     assert_eq!(Some("p@ssw0rd".into()), opts.password);
 }
+
+#[test]
+fn it_decodes_a_password_with_every_reserved_character_once() {
+    use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
+
+    let password = ":/?#[]@!$&'()*+,;=% pässwörd€😀";
+    let uri = format!(
+        "mssql://sa:{}@localhost/my%20database",
+        utf8_percent_encode(password, NON_ALPHANUMERIC)
+    );
+    let opts = MssqlConnectOptions::from_str(&uri).unwrap();
+
+    assert_eq!(Some(password.into()), opts.password);
+    assert_eq!("localhost", opts.host);
+    assert_eq!("my database", opts.database);
+}
@@ -1,12 +1,11 @@
 use bitflags::bitflags;
 use bytes::{Buf, Bytes};
 
+use crate::column::ColumnNames;
 use crate::error::Error;
-use crate::ext::ustr::UStr;
 use crate::mssql::io::MssqlBufExt;
 use crate::mssql::protocol::type_info::TypeInfo;
 use crate::mssql::MssqlColumn;
-use crate::HashMap;
 
 #[derive(Debug)]
 pub(crate) struct ColMetaData;
@@ -80,7 +79,7 @@ impl ColMetaData {
     pub(crate) fn get(
         buf: &mut Bytes,
         columns: &mut Vec<MssqlColumn>,
-        column_names: &mut HashMap<UStr, usize>,
+        column_names: &mut ColumnNames,
     ) -> Result<(), Error> {
         columns.clear();
         column_names.clear();
@@ -100,6 +99,7 @@ impl ColMetaData {
         while count > 0 {
             let col = MssqlColumn::new(ColumnData::get(buf)?, ordinal);
 
+            // a name shared by several columns refers to the first of them
             column_names.insert(col.name.clone(), ordinal);
             columns.push(col);
 
@@ -1,16 +1,14 @@
-use crate::column::ColumnIndex;
+use crate::column::{ColumnIndex, ColumnNames};
 use crate::error::Error;
-use crate::ext::ustr::UStr;
 use crate::mssql::protocol::row::Row as ProtocolRow;
 use crate::mssql::{Mssql, MssqlColumn, MssqlValueRef};
 use crate::row::Row;
-use crate::HashMap;
 use std::sync::Arc;
 
 pub struct MssqlRow {
     pub(crate) row: ProtocolRow,
     pub(crate) columns: Arc<Vec<MssqlColumn>>,
-    pub(crate) column_names: Arc<HashMap<UStr, usize>>,
+    pub(crate) column_names: Arc<ColumnNames>,
 }
 
 impl crate::row::private_row::Sealed for MssqlRow {}
@@ -34,6 +32,10 @@ impl Row for MssqlRow {
 
         Ok(value)
     }
+
+    fn is_ambiguous(&self, name: &str) -> bool {
+        self.column_names.is_ambiguous(name)
+    }
 }
 
 impl ColumnIndex<MssqlRow> for &'_ str {
@@ -1,9 +1,7 @@
-use crate::column::ColumnIndex;
+use crate::column::{ColumnIndex, ColumnNames};
 use crate::error::Error;
-use crate::ext::ustr::UStr;
 use crate::mssql::{Mssql, MssqlArguments, MssqlColumn, MssqlTypeInfo};
 use crate::statement::Statement;
-use crate::HashMap;
 use either::Either;
 use std::borrow::Cow;
 use std::sync::Arc;
@@ -17,7 +15,7 @@ pub struct MssqlStatement<'q> {
 #[derive(Debug, Default, Clone)]
 pub(crate) struct MssqlStatementMetadata {
     pub(crate) columns: Vec<MssqlColumn>,
-    pub(crate) column_names: HashMap<UStr, usize>,
+    pub(crate) column_names: ColumnNames,
 }
 
 impl<'q> Statement<'q> for MssqlStatement<'q> {
@@ -0,0 +1,15 @@
+use crate as sqlx;
+
+impl_type_mapping! {
+    sqlx::mssql::Mssql {
+        bool,
+        i8,
+        i16,
+        i32,
+        i64,
+        f32,
+        f64,
+        String,
+    },
+    feature-types: _info => None
+}
@@ -1,14 +1,39 @@
 use crate::arguments::Arguments;
-use crate::encode::{Encode, IsNull};
+use crate::encode::{Encode, IsNull, RawValue};
+use crate::mysql::protocol::text::ColumnType;
 use crate::mysql::{MySql, MySqlTypeInfo};
 use crate::types::Type;
+use std::ops::Range;
+use std::time::Duration;
 
 /// Implementation of [`Arguments`] for MySQL.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct MySqlArguments {
     pub(crate) values: Vec<u8>,
     pub(crate) types: Vec<MySqlTypeInfo>,
     pub(crate) null_bitmap: Vec<u8>,
+
+    // lists of values that each replace a single `?` of the query, in order of their placeholder
+    pub(crate) lists: Vec<ListArgument>,
+
+    // see `Query::server_timeout`
+    pub(crate) server_timeout: Option<Duration>,
+
+    // the names of the values of a query made with `query_named`, in the order they were bound,
+    // with the range of the encoded bytes of each value in `values`
+    pub(crate) named: Option<Vec<(String, Range<usize>)>>,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ListArgument {
+    // the index of the `?` in the query the list is bound to
+    pub(crate) placeholder: usize,
+
+    // the number of values in the list
+    pub(crate) len: usize,
+
+    // the SQL that replaces the `?` when the list is empty
+    pub(crate) if_empty: &'static str,
 }
 
 impl MySqlArguments {
@@ -26,14 +51,73 @@ impl MySqlArguments {
             self.null_bitmap[index / 8] |= (1 << (index % 8)) as u8;
         }
     }
+
+    pub(crate) fn add_list<'q, I>(&mut self, values: I, if_empty: &'static str)
+    where
+        I: IntoIterator,
+        I::Item: Encode<'q, MySql> + Type<MySql>,
+    {
+        let placeholder = self.placeholders();
+        let start = self.types.len();
+
+        for value in values {
+            self.add(value);
+        }
+
+        self.lists.push(ListArgument {
+            placeholder,
+            len: self.types.len() - start,
+            if_empty,
+        });
+    }
+
+    // adds a value for the `:name` parameters of a query made with `query_named`
+    pub(crate) fn add_named<'q, T>(&mut self, name: &str, value: T)
+    where
+        T: Encode<'q, MySql> + Type<MySql>,
+    {
+        let start = self.values.len();
+
+        self.add(value);
+
+        let range = start..self.values.len();
+
+        self.named
+            .get_or_insert_with(Vec::new)
+            .push((name.to_owned(), range));
+    }
+
+    // adds the value at `index` of `other` again, whose encoded bytes are `range` of its values
+    pub(crate) fn add_copy(&mut self, other: &MySqlArguments, index: usize, range: Range<usize>) {
+        let copy = self.types.len();
+
+        self.types.push(other.types[index].clone());
+        self.null_bitmap.resize((copy / 8) + 1, 0);
+        self.values.extend_from_slice(&other.values[range]);
+
+        if other.null_bitmap[index / 8] & (1 << (index % 8)) as u8 != 0 {
+            self.null_bitmap[copy / 8] |= (1 << (copy % 8)) as u8;
+        }
+    }
+
+    // the number of `?` placeholders of the query the bound arguments account for
+    fn placeholders(&self) -> usize {
+        let listed: usize = self.lists.iter().map(|list| list.len).sum();
+
+        self.types.len() - listed + self.lists.len()
+    }
 }
 
 impl<'q> Arguments<'q> for MySqlArguments {
     type Database = MySql;
 
     fn reserve(&mut self, len: usize, size: usize) {
+        let null_bitmap = (self.types.len() + len + 7) / 8;
+
         self.types.reserve(len);
         self.values.reserve(size);
+        self.null_bitmap
+            .reserve(null_bitmap.saturating_sub(self.null_bitmap.len()));
     }
 
     fn add<T>(&mut self, value: T)
@@ -42,4 +126,70 @@ impl<'q> Arguments<'q> for MySqlArguments {
     {
         self.add(value)
     }
+
+    fn expect_named(&mut self) {
+        self.named.get_or_insert_with(Vec::new);
+    }
+}
+
+impl Type<MySql> for RawValue<'_, MySql> {
+    fn type_info() -> MySqlTypeInfo {
+        // the type of the value is given by `produces`
+        MySqlTypeInfo::binary(ColumnType::Blob)
+    }
+}
+
+impl Encode<'_, MySql> for RawValue<'_, MySql> {
+    fn produces(&self) -> Option<MySqlTypeInfo> {
+        Some(self.type_info.clone())
+    }
+
+    fn encode_by_ref(&self, buf: &mut Vec<u8>) -> IsNull {
+        match self.value {
+            Some(value) => {
+                buf.extend_from_slice(value);
+
+                IsNull::No
+            }
+
+            None => IsNull::Yes,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::counting_alloc::allocations;
+
+    #[test]
+    fn it_encodes_reserved_integers_without_allocating() {
+        let mut arguments = MySqlArguments::default();
+        // the four pairs of an `i32` and an `i64`, and a `bool`; a NULL takes no space
+        arguments.reserve(10, 4 * (4 + 8) + 1);
+
+        let ((), allocated) = allocations(|| {
+            for i in 0..4 {
+                arguments.add(i as i32);
+                arguments.add(-(i as i64));
+            }
+
+            arguments.add(true);
+            arguments.add(Option::<i32>::None);
+        });
+
+        assert_eq!(allocated, 0);
+
+        let mut expected = Vec::new();
+
+        for i in 0..4 {
+            expected.extend_from_slice(&(i as i32).to_le_bytes());
+            expected.extend_from_slice(&(-(i as i64)).to_le_bytes());
+        }
+
+        expected.push(1);
+
+        assert_eq!(arguments.values, expected);
+        assert_eq!(arguments.null_bitmap, [0, 0b10]);
+    }
 }
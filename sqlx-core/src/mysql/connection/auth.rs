@@ -8,6 +8,7 @@ use sha1::Sha1;
 use sha2::Sha256;
 
 use crate::error::Error;
+use crate::io::check_str_nul;
 use crate::mysql::connection::stream::MySqlStream;
 use crate::mysql::protocol::auth::AuthPlugin;
 use crate::mysql::protocol::Packet;
@@ -27,6 +28,30 @@ impl AuthPlugin {
 
             // https://mariadb.com/kb/en/sha256_password-plugin/
             AuthPlugin::Sha256Password => encrypt_rsa(stream, 0x01, password, nonce).await,
+
+            // https://dev.mysql.com/doc/refman/8.0/en/cleartext-pluggable-authentication.html
+            AuthPlugin::MySqlClearPassword => {
+                if !stream.is_tls() {
+                    return Err(Error::Tls(
+                        "server requested authentication with 'mysql_clear_password', \
+                         which is only allowed over TLS"
+                            .into(),
+                    ));
+                }
+
+                to_asciz(password)
+            }
+
+            // https://mariadb.com/kb/en/authentication-plugin-ed25519/
+            #[cfg(feature = "mysql-ed25519")]
+            AuthPlugin::ClientEd25519 => Ok(sign_ed25519(password, nonce).to_vec()),
+
+            #[cfg(not(feature = "mysql-ed25519"))]
+            AuthPlugin::ClientEd25519 => Err(Error::Configuration(
+                "server requested authentication with 'client_ed25519', \
+                 which requires the `mysql-ed25519` feature"
+                    .into(),
+            )),
         }
     }
 
@@ -38,7 +63,7 @@ impl AuthPlugin {
         nonce: &Chain<Bytes, Bytes>,
     ) -> Result<bool, Error> {
         match self {
-            AuthPlugin::CachingSha2Password if packet[0] == 0x01 => {
+            AuthPlugin::CachingSha2Password if packet.len() > 1 && packet[0] == 0x01 => {
                 match packet[1] {
                     // AUTH_OK
                     0x03 => Ok(true),
@@ -123,6 +148,32 @@ fn scramble_sha256(
     pw_hash
 }
 
+#[cfg(feature = "mysql-ed25519")]
+fn sign_ed25519(password: &str, nonce: &Chain<Bytes, Bytes>) -> [u8; 64] {
+    // MariaDB signs the nonce with an ed25519 key that is expanded from the password itself,
+    // where the key would usually be expanded from a 32-byte seed
+    // https://github.com/MariaDB/server/blob/10.4/plugin/auth_ed25519/ref10/sign.c
+    use ed25519_dalek::{ExpandedSecretKey, PublicKey};
+    use sha2::Sha512;
+
+    let mut key = Sha512::digest(password.as_bytes());
+
+    key[0] &= 248;
+    key[31] &= 63;
+    key[31] |= 64;
+
+    // the key is always 64 bytes
+    let secret = ExpandedSecretKey::from_bytes(&key).unwrap();
+    let public = PublicKey::from(&secret);
+
+    let (a, b) = (nonce.first_ref(), nonce.last_ref());
+    let mut message = Vec::with_capacity(a.len() + b.len());
+    message.extend_from_slice(&*a);
+    message.extend_from_slice(&*b);
+
+    secret.sign(&message, &public).to_bytes()
+}
+
 async fn encrypt_rsa<'s>(
     stream: &'s mut MySqlStream,
     public_key_request_id: u8,
@@ -133,7 +184,7 @@ async fn encrypt_rsa<'s>(
 
     if stream.is_tls() {
         // If in a TLS stream, send the password directly in clear text
-        return Ok(to_asciz(password));
+        return to_asciz(password);
     }
 
     // client sends a public key request
@@ -145,7 +196,7 @@ async fn encrypt_rsa<'s>(
     let rsa_pub_key = &packet[1..];
 
     // xor the password with the given nonce
-    let mut pass = to_asciz(password);
+    let mut pass = to_asciz(password)?;
 
     let (a, b) = (nonce.first_ref(), nonce.last_ref());
     let mut nonce = Vec::with_capacity(a.len() + b.len());
@@ -171,12 +222,15 @@ fn xor_eq(x: &mut [u8], y: &[u8]) {
     }
 }
 
-fn to_asciz(s: &str) -> Vec<u8> {
+// the password as a NUL-terminated string
+fn to_asciz(s: &str) -> Result<Vec<u8>, Error> {
+    check_str_nul("password", s)?;
+
     let mut z = String::with_capacity(s.len() + 1);
     z.push_str(s);
     z.push('\0');
 
-    z.into_bytes()
+    Ok(z.into_bytes())
 }
 
 // https://docs.rs/rsa/0.3.0/rsa/struct.RSAPublicKey.html?search=#example-1
@@ -189,3 +243,21 @@ fn parse_rsa_pub_key(key: &[u8]) -> Result<RsaPublicKey, Error> {
 
     RsaPublicKey::from_public_key_pem(&pem).map_err(Error::protocol)
 }
+
+#[cfg(feature = "mysql-ed25519")]
+#[test]
+fn test_sign_ed25519() {
+    use bytes::Buf;
+
+    // the public key of "secret" is `ZIgUREUg5PVgQ6LskhXmO+eZLS0nC8be6HPjYWR4YJY`
+    // https://mariadb.com/kb/en/authentication-plugin-ed25519/
+    let nonce = Bytes::from((0..32).collect::<Vec<u8>>()).chain(Bytes::new());
+
+    assert_eq!(
+        &sign_ed25519("secret", &nonce)[..],
+        &b"\x54\x6c\x3e\x93\x11\xd1\x9e\xe3\x89\xd1\xae\x5d\x94\x58\x21\xbf\
+           \xe4\x59\x42\x39\x10\x13\x6f\x16\xb3\xbd\x2a\xe8\x9f\xd7\xd6\x3b\
+           \x44\x9e\x68\x9f\x4b\xe8\x12\x89\x1a\x85\xb8\xf9\x99\x07\x64\x46\
+           \xd9\x2a\xdb\x83\xd0\x70\x72\x15\xaa\xa6\x0b\x5a\x23\x22\x09\x07"[..]
+    );
+}
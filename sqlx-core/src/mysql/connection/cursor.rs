@@ -0,0 +1,210 @@
+use std::sync::Arc;
+
+use crate::arguments::IntoArguments;
+use crate::column::ColumnNames;
+use crate::error::Error;
+use crate::executor::Execute;
+use crate::mysql::connection::executor::recv_next_result_column;
+use crate::mysql::connection::Waiting;
+use crate::mysql::io::MySqlBufExt;
+use crate::mysql::protocol::response::Status;
+use crate::mysql::protocol::statement::{
+    BinaryRow, CursorType, Execute as StatementExecute, StmtClose, StmtFetch, StmtReset,
+};
+use crate::mysql::{MySql, MySqlColumn, MySqlConnection, MySqlRow, MySqlValueFormat};
+use crate::query::Query;
+
+/// A server-side cursor over the result set of a query.
+///
+/// Returned by [`Query::cursor`]. Rather than streaming the whole result set at once, the
+/// server keeps the rows and sends them in batches as they are requested with
+/// [`next_batch`](MySqlCursor::next_batch).
+///
+/// The cursor borrows the connection for as long as it is alive. Dropping it before all rows
+/// have been fetched closes the cursor on the server as part of the next operation on the
+/// connection.
+pub struct MySqlCursor<'c> {
+    conn: &'c mut MySqlConnection,
+    statement: u32,
+
+    // the statement is kept in the statement cache; reset it instead of closing it
+    cached: bool,
+
+    columns: Arc<Vec<MySqlColumn>>,
+    column_names: Arc<ColumnNames>,
+
+    // the server has sent the last row
+    done: bool,
+}
+
+impl<'q, A> Query<'q, MySql, A>
+where
+    A: 'q + Send + IntoArguments<'q, MySql>,
+{
+    /// Execute the query with a read-only server-side cursor, to fetch the rows in batches.
+    ///
+    /// The query is always executed as a prepared statement.
+    pub async fn cursor(mut self, conn: &mut MySqlConnection) -> Result<MySqlCursor<'_>, Error> {
+        let sql = self.sql();
+        let arguments = self.take_arguments().unwrap_or_default();
+        let persistent = Execute::persistent(&self);
+
+        conn.stream.wait_until_ready().await?;
+
+        let (statement, _) = conn.get_or_prepare(sql, persistent).await?;
+        let cached = conn.cache_statement.contains_key(sql);
+
+        // the statement is reset when the cursor is dropped, so its types are always sent
+        conn.bound_types.remove(&statement);
+
+        let mut cursor = MySqlCursor {
+            conn,
+            statement,
+            cached,
+            columns: Arc::default(),
+            column_names: Arc::default(),
+            // nothing to fetch or to close until the server opens the cursor
+            done: true,
+        };
+
+        let stream = &mut cursor.conn.stream;
+
+        stream
+            .send_packet(StatementExecute {
+                statement,
+                cursor: CursorType::READ_ONLY,
+                arguments: &arguments,
+                send_types: true,
+            })
+            .await?;
+
+        stream.waiting.push_back(Waiting::Result);
+        stream.stats.start_query();
+
+        let mut packet = stream.recv_packet().await?;
+
+        if packet[0] == 0x00 {
+            // the statement does not return a result set; there is nothing to fetch
+            stream.pop_waiting();
+
+            return Ok(cursor);
+        }
+
+        *stream.waiting.front_mut().unwrap() = Waiting::Row;
+
+        let num_columns = packet.get_uint_lenenc()? as usize;
+
+        let mut columns = Vec::with_capacity(num_columns);
+        let mut column_names = ColumnNames::with_capacity(num_columns);
+
+        for ordinal in 0..num_columns {
+            let column = recv_next_result_column(&stream.recv().await?, ordinal)?;
+
+            column_names.insert(column.name.clone(), ordinal);
+            columns.push(column);
+        }
+
+        // the column definitions are always followed by an EOF when a cursor is opened,
+        // as it carries the cursor status
+        let packet = stream.recv_packet().await?;
+
+        if packet[0] != 0xfe || packet.len() >= 9 {
+            // the rows are following; let the next operation skip over them
+            return Err(err_protocol!(
+                "server did not open a cursor for the statement"
+            ));
+        }
+
+        let eof = stream.decode_end_of_rows(packet)?;
+
+        stream.pop_waiting();
+
+        cursor.columns = Arc::new(columns);
+        cursor.column_names = Arc::new(column_names);
+        cursor.done = !eof.status.contains(Status::SERVER_STATUS_CURSOR_EXISTS);
+
+        Ok(cursor)
+    }
+}
+
+impl MySqlCursor<'_> {
+    /// Fetch the next batch of at most `rows` rows.
+    ///
+    /// An empty batch is returned once all rows have been fetched.
+    pub async fn next_batch(&mut self, rows: u32) -> Result<Vec<MySqlRow>, Error> {
+        if self.done || rows == 0 {
+            return Ok(Vec::new());
+        }
+
+        let stream = &mut self.conn.stream;
+
+        stream.wait_until_ready().await?;
+
+        stream
+            .send_packet(StmtFetch {
+                statement: self.statement,
+                rows,
+            })
+            .await?;
+
+        stream.waiting.push_back(Waiting::Row);
+
+        let mut batch = Vec::with_capacity(rows.min(1024) as usize);
+
+        loop {
+            let packet = stream.recv_packet().await?;
+
+            if packet.is_eof(stream.capabilities) {
+                let eof = stream.decode_end_of_rows(packet)?;
+
+                stream.pop_waiting();
+
+                // the server closes the cursor on its own after the last row
+                self.done = eof.status.contains(Status::SERVER_STATUS_LAST_ROW_SENT)
+                    || !eof.status.contains(Status::SERVER_STATUS_CURSOR_EXISTS);
+
+                stream.stats.rows += batch.len() as u64;
+
+                return Ok(batch);
+            }
+
+            let row = packet.decode_with::<BinaryRow, _>(&self.columns)?.0;
+
+            batch.push(MySqlRow {
+                row,
+                format: MySqlValueFormat::Binary,
+                columns: Arc::clone(&self.columns),
+                column_names: Arc::clone(&self.column_names),
+                text_results: false,
+            });
+        }
+    }
+
+    /// Returns `true` if all rows have been fetched.
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+}
+
+impl Drop for MySqlCursor<'_> {
+    fn drop(&mut self) {
+        let stream = &mut self.conn.stream;
+
+        // we cannot wait for the server here; the packet is sent ahead of the next operation
+        // on the connection, which also reads the response
+        stream.sequence_id = 0;
+
+        if !self.cached {
+            // closing a statement also closes its cursor, and has no response
+            stream.write_packet(StmtClose {
+                statement: self.statement,
+            });
+        } else if !self.done {
+            stream.write_packet(StmtReset {
+                statement: self.statement,
+            });
+
+            stream.waiting.push_back(Waiting::Result);
+        }
+    }
+}
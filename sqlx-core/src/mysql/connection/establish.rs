@@ -1,58 +1,71 @@
 use bytes::buf::Buf;
 use bytes::Bytes;
 
-use crate::common::StatementCache;
+use crate::common::{OperationState, StatementCache};
+use crate::connection::ServerVendor;
 use crate::error::Error;
 use crate::mysql::connection::{tls, MySqlStream, MAX_PACKET_SIZE};
 use crate::mysql::protocol::connect::{
     AuthSwitchRequest, AuthSwitchResponse, Handshake, HandshakeResponse,
 };
 use crate::mysql::protocol::Capabilities;
-use crate::mysql::{MySqlConnectOptions, MySqlConnection, MySqlSslMode};
+use crate::mysql::server_version::{
+    default_auth_plugin, parse_server_version, supports_deprecate_eof,
+};
+use crate::mysql::{MySqlConnectOptions, MySqlConnection, MySqlQueryResult, MySqlSslMode};
+use crate::net::connect_any;
+use crate::HashMap;
 
 impl MySqlConnection {
     pub(crate) async fn establish(options: &MySqlConnectOptions) -> Result<Self, Error> {
-        let mut stream: MySqlStream = MySqlStream::connect(options).await?;
+        let hosts = options.hosts();
+
+        connect_any(&hosts, |host, port| {
+            let options = options.clone().host(host).port(port);
+
+            async move { Self::establish_host(&options).await }
+        })
+        .await
+    }
+
+    // connects to the host of `options`, ignoring its fallback hosts
+    async fn establish_host(options: &MySqlConnectOptions) -> Result<Self, Error> {
+        let stream = MySqlStream::connect(options).await?;
+
+        Self::establish_with(stream, options).await
+    }
 
+    // goes through the handshake and authentication on a stream that was just connected
+    async fn establish_with(
+        mut stream: MySqlStream,
+        options: &MySqlConnectOptions,
+    ) -> Result<Self, Error> {
         // https://dev.mysql.com/doc/dev/mysql-server/8.0.12/page_protocol_connection_phase.html
         // https://mariadb.com/kb/en/connection/
 
         let handshake: Handshake = stream.recv_packet().await?.decode()?;
 
-        let mut plugin = handshake.auth_plugin;
-        let mut nonce = handshake.auth_plugin_data;
+        stream.server_version = parse_server_version(&handshake.server_version);
+        stream.is_mariadb = stream.server_version.vendor() == ServerVendor::MariaDb;
 
-        // FIXME: server version parse is a bit ugly
-        // expecting MAJOR.MINOR.PATCH
-
-        let mut server_version = handshake.server_version.split('.');
-
-        let server_version_major: u16 = server_version
-            .next()
-            .unwrap_or_default()
-            .parse()
-            .unwrap_or(0);
-
-        let server_version_minor: u16 = server_version
-            .next()
-            .unwrap_or_default()
-            .parse()
-            .unwrap_or(0);
-
-        let server_version_patch: u16 = server_version
-            .next()
-            .unwrap_or_default()
-            .parse()
-            .unwrap_or(0);
-
-        stream.server_version = (
-            server_version_major,
-            server_version_minor,
-            server_version_patch,
+        // a server that does not say which authentication plugin to use expects its default
+        let mut plugin = Some(
+            handshake
+                .auth_plugin
+                .unwrap_or_else(|| default_auth_plugin(&stream.server_version)),
         );
+        let mut nonce = handshake.auth_plugin_data;
 
-        stream.capabilities &= handshake.server_capabilities;
-        stream.capabilities |= Capabilities::PROTOCOL_41;
+        stream.stats.backend_id = Some(handshake.connection_id);
+
+        stream.capabilities = stream
+            .capabilities
+            .negotiate(handshake.server_capabilities)?;
+
+        if !supports_deprecate_eof(&stream.server_version) {
+            // not every server that offers it sends OK packets instead of EOF packets
+            stream.capabilities.remove(Capabilities::DEPRECATE_EOF);
+        }
 
         if matches!(options.ssl_mode, MySqlSslMode::Disabled) {
             // remove the SSL capability if SSL has been explicitly disabled
@@ -68,14 +81,17 @@ impl MySqlConnection {
             None
         };
 
-        stream.write_packet(HandshakeResponse {
+        let response = HandshakeResponse {
             collation: stream.collation as u8,
             max_packet_size: MAX_PACKET_SIZE,
             username: &options.username,
             database: options.database.as_deref(),
             auth_plugin: plugin,
             auth_response: auth_response.as_deref(),
-        });
+        };
+
+        response.check()?;
+        stream.write_packet(response);
 
         stream.flush().await?;
 
@@ -83,7 +99,7 @@ impl MySqlConnection {
             let packet = stream.recv_packet().await?;
             match packet[0] {
                 0x00 => {
-                    let _ok = packet.ok()?;
+                    stream.decode_ok(packet)?;
 
                     break;
                 }
@@ -125,11 +141,337 @@ impl MySqlConnection {
             }
         }
 
+        stream.redact_sent = false;
+
         Ok(Self {
             stream,
             transaction_depth: 0,
+            queries_at_begin: 0,
+            isolation_level: None,
+            read_only: None,
             cache_statement: StatementCache::new(options.statement_cache_capacity),
+            bound_types: HashMap::new(),
             log_settings: options.log_settings.clone(),
+            last_query_result: MySqlQueryResult::default(),
+            read_buffer_shrink_threshold: options.read_buffer_shrink_threshold,
+            operation: OperationState::default(),
         })
     }
+
+    // a connection over a scripted stream; see `MySqlStream::scripted`
+    #[cfg(test)]
+    pub(super) fn scripted(stream: MySqlStream) -> Self {
+        let options = MySqlConnectOptions::new();
+
+        Self {
+            stream,
+            transaction_depth: 0,
+            queries_at_begin: 0,
+            isolation_level: None,
+            read_only: None,
+            cache_statement: StatementCache::new(options.statement_cache_capacity),
+            bound_types: HashMap::new(),
+            log_settings: options.log_settings,
+            last_query_result: MySqlQueryResult::default(),
+            read_buffer_shrink_threshold: options.read_buffer_shrink_threshold,
+            operation: OperationState::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::net::Socket;
+
+    // the two parts of the nonce of the handshake, 8 and 12 bytes
+    const NONCE: &[u8; 20] = b"abcdefghijklmnopqrst";
+
+    // the OK packet that ends authentication
+    const OK: &[u8] = b"\0\0\0\x02\0\0\0";
+
+    fn packet(sequence_id: u8, payload: &[u8]) -> Vec<u8> {
+        let mut packet = (payload.len() as u32).to_le_bytes()[..3].to_vec();
+        packet.push(sequence_id);
+        packet.extend_from_slice(payload);
+        packet
+    }
+
+    // the initial handshake of a server of `version` that offers `capabilities` and asks for
+    // `mysql_native_password`
+    fn handshake(version: &str, capabilities: Capabilities) -> Vec<u8> {
+        let capabilities = capabilities.bits();
+
+        let mut handshake = vec![0x0a];
+        handshake.extend_from_slice(version.as_bytes());
+        handshake.push(0);
+        handshake.extend_from_slice(&1_u32.to_le_bytes()); // connection id
+        handshake.extend_from_slice(&NONCE[..8]);
+        handshake.push(0);
+        handshake.extend_from_slice(&(capabilities as u16).to_le_bytes());
+        handshake.push(45); // utf8mb4_general_ci
+        handshake.extend_from_slice(&2_u16.to_le_bytes()); // SERVER_STATUS_AUTOCOMMIT
+        handshake.extend_from_slice(&((capabilities >> 16) as u16).to_le_bytes());
+        handshake.push(21); // the length of the nonce, with its NUL-terminator
+        handshake.extend_from_slice(&[0; 6]);
+        handshake.extend_from_slice(&0_u32.to_le_bytes()); // MariaDB capabilities
+        handshake.extend_from_slice(&NONCE[8..]);
+        handshake.push(0);
+        handshake.extend_from_slice(b"mysql_native_password\0");
+        handshake
+    }
+
+    // an `AuthSwitchRequest` to `plugin`, with `data` as is
+    fn auth_switch(plugin: &str, data: &[u8]) -> Vec<u8> {
+        let mut switch = vec![0xfe];
+        switch.extend_from_slice(plugin.as_bytes());
+        switch.push(0);
+        switch.extend_from_slice(data);
+        switch
+    }
+
+    // establishes a connection with a MariaDB server that replays `packets` after its handshake
+    fn establish(packets: Vec<Vec<u8>>) -> Result<MySqlConnection, Error> {
+        let handshake = handshake(
+            "5.5.5-10.6.5-MariaDB",
+            Capabilities::all() - Capabilities::MYSQL,
+        );
+
+        establish_after(handshake, packets)
+    }
+
+    // establishes a connection with a server that replays `packets` after `handshake`
+    fn establish_after(
+        handshake: Vec<u8>,
+        packets: Vec<Vec<u8>>,
+    ) -> Result<MySqlConnection, Error> {
+        let options = MySqlConnectOptions::new()
+            .username("root")
+            .password("secret")
+            .ssl_mode(MySqlSslMode::Disabled);
+
+        let mut script = vec![packet(0, &handshake)];
+        script.extend(packets);
+
+        let stream = MySqlStream::scripted_connect(&options, script);
+
+        sqlx_rt::block_on(MySqlConnection::establish_with(stream, &options))
+    }
+
+    // the payloads of the packets sent over the connection
+    fn sent(conn: &MySqlConnection) -> Vec<&[u8]> {
+        let socket: &Socket = &conn.stream;
+        let mut written = match socket {
+            Socket::Scripted(stream) => &stream.written[..],
+            socket => panic!("not a scripted socket: {:?}", socket),
+        };
+
+        let mut packets = Vec::new();
+
+        while !written.is_empty() {
+            let len = u32::from_le_bytes([written[0], written[1], written[2], 0]) as usize;
+
+            packets.push(&written[4..4 + len]);
+            written = &written[4 + len..];
+        }
+
+        packets
+    }
+
+    // the response to `SELECT 1` through the text protocol, with or without EOF packets
+    fn select_one(deprecate_eof: bool) -> Vec<Vec<u8>> {
+        // the definition of a BIGINT column named `1`
+        let column = b"\x03def\0\0\0\x011\x011\x0c\x3f\0\x01\0\0\0\x08\x81\0\0\0\0";
+
+        let mut packets = vec![packet(1, b"\x01"), packet(2, column)];
+
+        if !deprecate_eof {
+            packets.push(packet(3, b"\xfe\0\0\x02\0"));
+        }
+
+        packets.push(packet(4, b"\x011"));
+
+        // status: SERVER_STATUS_AUTOCOMMIT
+        if deprecate_eof {
+            packets.push(packet(5, b"\xfe\0\0\x02\0\0\0"));
+        } else {
+            packets.push(packet(5, b"\xfe\0\0\x02\0"));
+        }
+
+        packets
+    }
+
+    // runs `SELECT 1` over a connection established after `handshake`, which must read the
+    // response up to its last packet
+    fn negotiated(handshake: Vec<u8>, deprecate_eof: bool) -> MySqlConnection {
+        use crate::executor::Executor;
+        use crate::row::Row;
+
+        let mut packets = vec![packet(2, OK)];
+        packets.extend(select_one(deprecate_eof));
+
+        let mut conn = establish_after(handshake, packets).unwrap();
+
+        let rows = sqlx_rt::block_on(conn.fetch_all("SELECT 1")).unwrap();
+
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].try_get::<i64, _>(0).unwrap(), 1);
+
+        // nothing more is expected from the server
+        let error = sqlx_rt::block_on(conn.stream.recv_packet()).unwrap_err();
+
+        assert!(error.is_connection_closed(), "{}", error);
+        assert!(conn.stream.waiting.is_empty());
+
+        // the capabilities that were asked for in the handshake response
+        let sent = sent(&conn);
+        let bits = u32::from_le_bytes([sent[0][0], sent[0][1], sent[0][2], sent[0][3]]);
+
+        assert_eq!(u64::from(bits), conn.capabilities().bits());
+
+        conn
+    }
+
+    #[test]
+    fn it_negotiates_what_it_implements_with_a_server_that_offers_more() {
+        let conn = negotiated(handshake("8.0.28", Capabilities::all()), true);
+
+        assert_eq!(
+            conn.capabilities(),
+            Capabilities::CLIENT_SUPPORTED - Capabilities::CONNECT_WITH_DB - Capabilities::SSL
+        );
+    }
+
+    #[test]
+    fn it_negotiates_down_to_a_server_that_offers_less() {
+        let server = Capabilities::MYSQL
+            | Capabilities::PROTOCOL_41
+            | Capabilities::SECURE_CONNECTION
+            | Capabilities::PLUGIN_AUTH
+            | Capabilities::TRANSACTIONS;
+
+        let conn = negotiated(handshake("8.0.28", server), false);
+
+        assert_eq!(conn.capabilities(), server - Capabilities::MYSQL);
+    }
+
+    #[test]
+    fn it_drops_deprecate_eof_for_a_server_that_offers_it_but_sends_eof() {
+        let conn = negotiated(handshake("5.7.4", Capabilities::all()), false);
+
+        assert!(!conn.capabilities().contains(Capabilities::DEPRECATE_EOF));
+        assert!(conn.capabilities().contains(Capabilities::MULTI_RESULTS));
+    }
+
+    #[test]
+    fn it_refuses_a_server_without_the_4_1_protocol() {
+        let server = Capabilities::all() - Capabilities::PROTOCOL_41;
+
+        let error = establish_after(handshake("8.0.28", server), vec![packet(2, OK)]).unwrap_err();
+
+        assert!(error.to_string().contains("4.1 protocol"), "{}", error);
+    }
+
+    #[test]
+    fn it_switches_to_mysql_native_password() {
+        use sha1::{Digest, Sha1};
+
+        // a new nonce, NUL-terminated
+        let nonce = b"ABCDEFGHIJKLMNOPQRST";
+        let mut data = nonce.to_vec();
+        data.push(0);
+
+        let conn = establish(vec![
+            packet(2, &auth_switch("mysql_native_password", &data)),
+            packet(4, OK),
+        ])
+        .unwrap();
+
+        // SHA1(password) ^ SHA1(nonce + SHA1(SHA1(password)))
+        let hash = Sha1::digest(b"secret");
+        let salted = Sha1::new()
+            .chain(&nonce[..])
+            .chain(Sha1::digest(&hash))
+            .finalize();
+        let expected: Vec<u8> = hash.iter().zip(salted).map(|(a, b)| a ^ b).collect();
+
+        let sent = sent(&conn);
+
+        assert_eq!(sent.len(), 2);
+        assert_eq!(sent[1], &expected[..]);
+    }
+
+    #[test]
+    fn it_refuses_mysql_clear_password_without_tls() {
+        let error = establish(vec![
+            packet(2, &auth_switch("mysql_clear_password", b"")),
+            packet(4, OK),
+        ])
+        .unwrap_err();
+
+        assert!(matches!(error, Error::Tls(_)), "{}", error);
+        assert!(
+            error.to_string().contains("'mysql_clear_password'"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn it_switches_to_client_ed25519() {
+        // 32 random bytes, which are not NUL-terminated
+        let nonce: Vec<u8> = (0..32).collect();
+
+        let result = establish(vec![
+            packet(2, &auth_switch("client_ed25519", &nonce)),
+            packet(4, OK),
+        ]);
+
+        #[cfg(feature = "mysql-ed25519")]
+        {
+            let conn = result.unwrap();
+            let sent = sent(&conn);
+
+            // the signature of the nonce by the key of "secret", as in `test_sign_ed25519`
+            assert_eq!(sent.len(), 2);
+            assert_eq!(
+                sent[1],
+                &b"\x54\x6c\x3e\x93\x11\xd1\x9e\xe3\x89\xd1\xae\x5d\x94\x58\x21\xbf\
+                   \xe4\x59\x42\x39\x10\x13\x6f\x16\xb3\xbd\x2a\xe8\x9f\xd7\xd6\x3b\
+                   \x44\x9e\x68\x9f\x4b\xe8\x12\x89\x1a\x85\xb8\xf9\x99\x07\x64\x46\
+                   \xd9\x2a\xdb\x83\xd0\x70\x72\x15\xaa\xa6\x0b\x5a\x23\x22\x09\x07"[..]
+            );
+        }
+
+        #[cfg(not(feature = "mysql-ed25519"))]
+        {
+            let error = result.unwrap_err();
+
+            assert!(error.to_string().contains("`mysql-ed25519`"), "{}", error);
+        }
+    }
+
+    #[test]
+    fn it_names_an_unknown_plugin_it_is_switched_to() {
+        let error = establish(vec![
+            packet(2, &auth_switch("auth_gssapi_client", b"abc\0")),
+            packet(4, OK),
+        ])
+        .unwrap_err();
+
+        assert!(
+            error.to_string().contains("'auth_gssapi_client'"),
+            "{}",
+            error
+        );
+    }
+
+    #[test]
+    fn it_tells_mariadb_apart_in_its_spans() {
+        use crate::connection::Connection;
+
+        let conn = establish(vec![packet(2, OK)]).unwrap();
+
+        assert_eq!(conn.db_system(), "mariadb");
+    }
 }
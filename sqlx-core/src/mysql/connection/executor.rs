@@ -1,22 +1,32 @@
 use super::MySqlStream;
+use crate::column::ColumnNames;
+use crate::connection::Connection;
+use crate::database::Database;
 use crate::describe::Describe;
 use crate::error::Error;
 use crate::executor::{Execute, Executor};
 use crate::ext::ustr::UStr;
+use crate::instrument::Span;
 use crate::logger::QueryLogger;
 use crate::mysql::connection::stream::Waiting;
+use crate::mysql::error::MySqlDatabaseError;
+use crate::mysql::in_list::expand_lists;
 use crate::mysql::io::MySqlBufExt;
+use crate::mysql::named::replace_names;
 use crate::mysql::protocol::response::Status;
 use crate::mysql::protocol::statement::{
-    BinaryRow, Execute as StatementExecute, Prepare, PrepareOk, StmtClose,
+    encode_types, BinaryRow, CursorType, Execute as StatementExecute, Prepare, PrepareOk, StmtClose,
 };
 use crate::mysql::protocol::text::{ColumnDefinition, ColumnFlags, Query, TextRow};
+use crate::mysql::protocol::{Capabilities, Packet};
+use crate::mysql::server_timeout::with_server_timeout;
 use crate::mysql::statement::{MySqlStatement, MySqlStatementMetadata};
 use crate::mysql::{
     MySql, MySqlArguments, MySqlColumn, MySqlConnection, MySqlQueryResult, MySqlRow, MySqlTypeInfo,
     MySqlValueFormat,
 };
-use crate::HashMap;
+use crate::wire::RawFrame;
+use bytes::Bytes;
 use either::Either;
 use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
@@ -24,8 +34,17 @@ use futures_core::Stream;
 use futures_util::{pin_mut, TryStreamExt};
 use std::{borrow::Cow, sync::Arc};
 
+// `ER_NEED_REPREPARE`: the server refuses to execute a prepared statement whose tables changed
+// since it was prepared, e.g. by an `ALTER TABLE`, if it cannot prepare it again by itself
+fn is_stale_statement(error: &Error) -> bool {
+    error
+        .as_database_error()
+        .and_then(|e| e.try_downcast_ref::<MySqlDatabaseError>())
+        .map_or(false, |e| e.number() == 1615)
+}
+
 impl MySqlConnection {
-    async fn get_or_prepare<'c>(
+    pub(super) async fn get_or_prepare<'c>(
         &mut self,
         sql: &str,
         persistent: bool,
@@ -35,6 +54,36 @@ impl MySqlConnection {
             return Ok((*statement).clone());
         }
 
+        let (id, metadata) = Span::prepare(self.db_system(), sql)
+            .instrument(self.prepare_statement(sql))
+            .await?;
+
+        if persistent && self.cache_statement.is_enabled() {
+            // in case of the cache being full, close the least recently used statement
+            if let Some((id, _)) = self.cache_statement.insert(sql, (id, metadata.clone())) {
+                self.bound_types.remove(&id);
+                self.stream.send_packet(StmtClose { statement: id }).await?;
+            }
+        }
+
+        Ok((id, metadata))
+    }
+
+    // forgets a statement that can no longer be executed and closes it, so that it is prepared
+    // again the next time
+    async fn evict_statement(&mut self, sql: &str) -> Result<(), Error> {
+        if let Some((id, _)) = self.cache_statement.remove(sql) {
+            self.bound_types.remove(&id);
+            self.stream.send_packet(StmtClose { statement: id }).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn prepare_statement(
+        &mut self,
+        sql: &str,
+    ) -> Result<(u32, MySqlStatementMetadata), Error> {
         // https://dev.mysql.com/doc/internals/en/com-stmt-prepare.html
         // https://dev.mysql.com/doc/internals/en/com-stmt-prepare-response.html#packet-COM_STMT_PREPARE_OK
 
@@ -42,44 +91,171 @@ impl MySqlConnection {
 
         let ok: PrepareOk = self.stream.recv().await?;
 
-        // the parameter definitions are very unreliable so we skip over them
-        // as we have little use
-
-        if ok.params > 0 {
-            for _ in 0..ok.params {
-                let _def: ColumnDefinition = self.stream.recv().await?;
-            }
+        // the parameter definitions are kept with the statement, though the server reports
+        // little more than a placeholder type for most of them
 
-            self.stream.maybe_recv_eof().await?;
-        }
+        let parameters = self
+            .stream
+            .recv_column_defs(ok.params as usize)
+            .await?
+            .iter()
+            .map(MySqlTypeInfo::from_column)
+            .collect();
 
         // the column definitions are berefit the type information from the
         // to-be-bound parameters; we will receive the output column definitions
         // once more on execute so we wait for that
 
         let mut columns = Vec::new();
-
-        let column_names = if ok.columns > 0 {
-            recv_result_metadata(&mut self.stream, ok.columns as usize, &mut columns).await?
-        } else {
-            Default::default()
-        };
+        let column_names =
+            recv_result_metadata(&mut self.stream, ok.columns as usize, &mut columns).await?;
 
         let id = ok.statement_id;
         let metadata = MySqlStatementMetadata {
-            parameters: ok.params as usize,
+            parameters: Arc::new(parameters),
             columns: Arc::new(columns),
             column_names: Arc::new(column_names),
         };
 
-        if persistent && self.cache_statement.is_enabled() {
-            // in case of the cache being full, close the least recently used statement
-            if let Some((id, _)) = self.cache_statement.insert(sql, (id, metadata.clone())) {
-                self.stream.send_packet(StmtClose { statement: id }).await?;
+        Ok((id, metadata))
+    }
+
+    // sends the query, returning the metadata of the statement it was prepared as, the format of
+    // its rows and whether its result sets come with their own column metadata
+    async fn send_query(
+        &mut self,
+        sql: &str,
+        // borrowed, so the values can be bound again if the statement has to be prepared again
+        arguments: Option<&MySqlArguments>,
+        persistent: bool,
+    ) -> Result<(MySqlStatementMetadata, MySqlValueFormat, bool), Error> {
+        if let Some(arguments) = arguments {
+            let (id, metadata) = self.get_or_prepare(sql, persistent).await?;
+
+            // check the values bound against the parameters the server reported for the
+            // statement, rather than leave it to reject the packet with a less helpful error;
+            // nothing is sent, so there is no result to wait for
+            if metadata.parameters.len() != arguments.types.len() {
+                self.stream.pop_waiting();
+
+                return Err(Error::ParameterCountMismatch {
+                    expected: metadata.parameters.len(),
+                    got: arguments.types.len(),
+                });
             }
+
+            // the types of the parameters only need to be sent again if they changed since the
+            // last execution of a cached statement; other statements are executed only once
+            let send_types = if self.cache_statement.contains_key(sql) {
+                let mut types = Vec::new();
+                encode_types(&arguments.types, &mut types);
+
+                self.bound_types.insert(id, types.clone()) != Some(types)
+            } else {
+                true
+            };
+
+            // https://dev.mysql.com/doc/internals/en/com-stmt-execute.html
+            self.stream
+                .send_packet(StatementExecute {
+                    statement: id,
+                    cursor: CursorType::NO_CURSOR,
+                    arguments,
+                    send_types,
+                })
+                .await?;
+
+            Ok((metadata, MySqlValueFormat::Binary, false))
+        } else {
+            // https://dev.mysql.com/doc/internals/en/com-query.html
+            self.stream.send_packet(Query(sql)).await?;
+
+            Ok((
+                MySqlStatementMetadata::default(),
+                MySqlValueFormat::Text,
+                true,
+            ))
         }
+    }
 
-        Ok((id, metadata))
+    // handles the error a query failed with before any of its results: a statement the server
+    // refused because it went stale is closed and the query sent again, to prepare it anew; any
+    // other error is returned
+    async fn resend_stale_query(
+        &mut self,
+        error: Error,
+        sql: &str,
+        arguments: Option<&MySqlArguments>,
+        persistent: bool,
+    ) -> Result<(MySqlStatementMetadata, MySqlValueFormat, bool), Error> {
+        if arguments.is_none() || !is_stale_statement(&error) {
+            return Err(error);
+        }
+
+        self.evict_statement(sql).await?;
+
+        log::debug!(
+            "preparing a statement again after the schema it depends on changed: {}",
+            sql
+        );
+
+        // the error ended the wait for the results of the query
+        self.stream.waiting.push_back(Waiting::Result);
+
+        self.send_query(sql, arguments, persistent).await
+    }
+
+    // decodes the packet that ends the rows of a result set
+    fn result_set_end(
+        &mut self,
+        packet: Packet<Bytes>,
+    ) -> Result<(MySqlQueryResult, Status), Error> {
+        // when EOF is deprecated, the result set ends with an OK packet that
+        // carries the same information as one that ends a statement without rows
+        let end = self.stream.decode_end_of_rows(packet)?;
+        let done = MySqlQueryResult {
+            rows_affected: end.affected_rows,
+            last_insert_id: end.last_insert_id,
+            warnings: end.warnings,
+        };
+
+        Ok((done, end.status))
+    }
+
+    // the server may send other columns when it executes a statement than it prepared it with,
+    // MariaDB in particular, e.g. after a column of a table it selects from changed type; the
+    // rows are decoded with the columns it sends, which replace those of the statement in the
+    // cache, and the names of which are returned
+    fn update_prepared_columns(
+        &mut self,
+        sql: &str,
+        prepared: &[MySqlColumn],
+        columns: &[MySqlColumn],
+    ) -> Option<Arc<ColumnNames>> {
+        let unchanged = prepared.len() == columns.len()
+            && prepared.iter().zip(columns).all(|(prepared, column)| {
+                prepared.name == column.name
+                    && prepared.type_info.r#type == column.type_info.r#type
+                    && prepared.type_info.flags == column.type_info.flags
+            });
+
+        if unchanged {
+            return None;
+        }
+
+        log::debug!(
+            "the columns of a statement changed since it was prepared: {}",
+            sql
+        );
+
+        let column_names = Arc::new(column_names(columns));
+
+        if let Some((_, metadata)) = self.cache_statement.peek_mut(sql) {
+            metadata.columns = Arc::new(columns.to_vec());
+            metadata.column_names = Arc::clone(&column_names);
+        }
+
+        Some(column_names)
     }
 
     #[allow(clippy::needless_lifetimes)]
@@ -88,12 +264,16 @@ impl MySqlConnection {
         sql: &'q str,
         arguments: Option<MySqlArguments>,
         persistent: bool,
+        text_results: bool,
     ) -> Result<impl Stream<Item = Result<Either<MySqlQueryResult, MySqlRow>, Error>> + 'e, Error>
     {
         let mut logger = QueryLogger::new(sql, self.log_settings.clone());
 
         self.stream.wait_until_ready().await?;
         self.stream.waiting.push_back(Waiting::Result);
+        self.stream.stats.start_query();
+
+        self.last_query_result = MySqlQueryResult::default();
 
         Ok(Box::pin(try_stream! {
             // make a slot for the shared column data
@@ -101,110 +281,494 @@ impl MySqlConnection {
             // to re-use this memory freely between result sets
             let mut columns = Arc::new(Vec::new());
 
-            let (mut column_names, format, mut needs_metadata) = if let Some(arguments) = arguments {
-                let (id, metadata) = self.get_or_prepare(
-                    sql,
-                    persistent,
-                )
-                .await?;
+            let mut response = QueryResponse::send(self, sql, arguments, persistent).await?;
 
-                // https://dev.mysql.com/doc/internals/en/com-stmt-execute.html
-                self.stream
-                    .send_packet(StatementExecute {
-                        statement: id,
-                        arguments: &arguments,
-                    })
-                    .await?;
+            while let Some(part) = response.next(self).await? {
+                match part {
+                    ResponsePart::Done(done) => r#yield!(Either::Left(done)),
 
-                (metadata.column_names, MySqlValueFormat::Binary, false)
-            } else {
-                // https://dev.mysql.com/doc/internals/en/com-query.html
-                self.stream.send_packet(Query(sql)).await?;
+                    ResponsePart::ResultSet(packet) => {
+                        response.recv_columns(self, packet, Arc::make_mut(&mut columns)).await?;
+                    }
 
-                (Arc::default(), MySqlValueFormat::Text, true)
-            };
+                    ResponsePart::Row(packet) => {
+                        let row = decode_row(packet, response.format, &columns)?;
+
+                        let v = Either::Right(MySqlRow {
+                            row,
+                            format: response.format,
+                            columns: Arc::clone(&columns),
+                            column_names: Arc::clone(&response.column_names),
+                            text_results,
+                        });
+
+                        logger.increment_rows();
+                        self.stream.stats.rows += 1;
+
+                        r#yield!(v);
+                    }
+                }
+            }
+
+            Ok(())
+        }))
+    }
+}
+
+impl MySqlConnection {
+    /// Execute the query and return the total number of rows affected.
+    ///
+    /// The rows of a statement that returns a result set are read off the connection without
+    /// being decoded; such a statement counts with the rows affected that the server reports at
+    /// the end of its result set, not with the number of rows it returned. The `Executor`
+    /// implementation of `execute` delegates to this method.
+    pub async fn execute_concrete<'q, E>(&mut self, mut query: E) -> Result<MySqlQueryResult, Error>
+    where
+        E: Execute<'q, MySql>,
+    {
+        self.operation.ensure_finished()?;
+
+        let sql = query.sql();
+        let mut arguments = query.take_arguments();
+        let persistent = query.persistent();
+
+        let sql = query_sql(sql, &mut arguments, self.stream.is_mariadb)?;
+        let mut logger = QueryLogger::new(&sql, self.log_settings.clone());
+
+        self.stream.wait_until_ready().await?;
+        self.stream.waiting.push_back(Waiting::Result);
+        self.stream.stats.start_query();
+
+        self.last_query_result = MySqlQueryResult::default();
+
+        let mut response = QueryResponse::send(self, &sql, arguments, persistent).await?;
+
+        while let Some(part) = response.next(self).await? {
+            match part {
+                ResponsePart::ResultSet(packet) => {
+                    self.stream.skip_result_metadata(packet).await?;
+                }
+
+                ResponsePart::Row(_) => {
+                    logger.increment_rows();
+                    self.stream.stats.rows += 1;
+                }
+
+                ResponsePart::Done(_) => {}
+            }
+        }
+
+        Ok(self.last_query_result.clone())
+    }
+
+    /// Execute the query and return all the rows.
+    ///
+    /// Each result set is read off the connection up to its last row before any of its rows are
+    /// decoded, so rows are not handed out one at a time through a stream. This is faster when
+    /// the rows fit in memory anyway; prefer [`Executor::fetch`] for result sets too large to
+    /// hold at once. The `Executor` implementation of `fetch_all` delegates to this method.
+    pub async fn fetch_all_concrete<'q, E>(&mut self, mut query: E) -> Result<Vec<MySqlRow>, Error>
+    where
+        E: Execute<'q, MySql>,
+    {
+        self.operation.ensure_finished()?;
+
+        let sql = query.sql();
+        let mut arguments = query.take_arguments();
+        let persistent = query.persistent();
+
+        let sql = query_sql(sql, &mut arguments, self.stream.is_mariadb)?;
+        let text_results = text_protocol(query.text_results(), &mut arguments);
+        let mut logger = QueryLogger::new(&sql, self.log_settings.clone());
+
+        self.stream.wait_until_ready().await?;
+        self.stream.waiting.push_back(Waiting::Result);
+        self.stream.stats.start_query();
+
+        self.last_query_result = MySqlQueryResult::default();
+
+        let mut response = QueryResponse::send(self, &sql, arguments, persistent).await?;
+
+        let mut rows = Vec::new();
+        let mut packets = Vec::new();
+        let mut columns = Arc::new(Vec::new());
+
+        // an error in the middle of the rows is returned from here, dropping the rows before it
+        while let Some(part) = response.next(self).await? {
+            match part {
+                ResponsePart::ResultSet(packet) => {
+                    // the rows of every result set keep its columns, so they are not re-used
+                    let mut columns_ = Vec::new();
+
+                    response.recv_columns(self, packet, &mut columns_).await?;
+
+                    columns = Arc::new(columns_);
+                }
+
+                ResponsePart::Row(packet) => packets.push(packet),
+
+                ResponsePart::Done(_) => {
+                    rows.reserve(packets.len());
+
+                    for packet in packets.drain(..) {
+                        let row = decode_row(packet, response.format, &columns)?;
+
+                        logger.increment_rows();
+                        self.stream.stats.rows += 1;
+
+                        rows.push(MySqlRow {
+                            row,
+                            format: response.format,
+                            columns: Arc::clone(&columns),
+                            column_names: Arc::clone(&response.column_names),
+                            text_results,
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(rows)
+    }
+
+    /// Execute the query and return the packets of its result sets as they were received,
+    /// without decoding them.
+    ///
+    /// Each result set is returned as its column count packet, its column definition packets
+    /// (followed by an EOF packet on servers that still send one), its row packets and then the
+    /// result of its statement.
+    ///
+    /// # Warning
+    ///
+    /// The packets are in the format of the client/server protocol of MySQL, which couples
+    /// their consumer to it, and to the capabilities that were negotiated for this connection.
+    /// The values in the rows of a query with bound values are in the binary protocol, and
+    /// those of a query without in the text protocol. A query with no values bound is sent
+    /// through the text protocol as well with [`text_results`].
+    ///
+    /// See [`RawFrame`].
+    ///
+    /// [`text_results`]: crate::query::Query::text_results
+    pub fn fetch_raw_frames<'e, 'q: 'e, E: 'q>(
+        &'e mut self,
+        mut query: E,
+    ) -> BoxStream<'e, Result<RawFrame<MySql>, Error>>
+    where
+        E: Execute<'q, MySql>,
+    {
+        let sql = query.sql();
+        let mut arguments = query.take_arguments();
+        let persistent = query.persistent();
+        let text_results = query.text_results();
+
+        Box::pin(try_stream! {
+            let sql = query_sql(sql, &mut arguments, self.stream.is_mariadb)?;
+            text_protocol(text_results, &mut arguments);
+            let mut logger = QueryLogger::new(&sql, self.log_settings.clone());
+
+            self.stream.wait_until_ready().await?;
+            self.stream.waiting.push_back(Waiting::Result);
+            self.stream.stats.start_query();
+
+            self.last_query_result = MySqlQueryResult::default();
+
+            self.send_query(&sql, arguments.as_ref(), persistent).await?;
+
+            let mut first = true;
 
             loop {
-                // query response is a meta-packet which may be one of:
-                //  Ok, Err, ResultSet, or (unhandled) LocalInfileRequest
-                let mut packet = self.stream.recv_packet().await?;
+                let (header, mut packet) = match self.stream.recv_frame().await {
+                    Ok(frame) => frame,
+
+                    Err(error) if first => {
+                        first = false;
+
+                        self.resend_stale_query(error, &sql, arguments.as_ref(), persistent)
+                            .await?;
+
+                        continue;
+                    }
+
+                    Err(error) => return Err(error),
+                };
 
-                if packet[0] == 0x00 || packet[0] == 0xff {
-                    // first packet in a query response is OK or ERR
-                    // this indicates either a successful query with no rows at all or a failed query
-                    let ok = packet.ok()?;
+                first = false;
+
+                if packet[0] == 0xfb {
+                    self.stream.send_local_infile(packet).await?;
+                    continue;
+                }
+
+                let (done, status) = if packet[0] == 0x00 || packet[0] == 0xff {
+                    // a statement without rows
+                    let ok = self.stream.decode_ok(packet)?;
 
                     let done = MySqlQueryResult {
                         rows_affected: ok.affected_rows,
                         last_insert_id: ok.last_insert_id,
+                        warnings: ok.warnings,
                     };
 
-                    r#yield!(Either::Left(done));
+                    (done, ok.status)
+                } else {
+                    *self.stream.waiting.front_mut().unwrap() = Waiting::Row;
 
-                    if ok.status.contains(Status::SERVER_MORE_RESULTS_EXISTS) {
-                        // more result sets exist, continue to the next one
-                        continue;
+                    let mut metadata = vec![frame(&header, &packet)];
+                    let num_columns = packet.get_uint_lenenc()? as usize;
+
+                    // the server sends no EOF after an empty list of definitions
+                    let mut definitions = num_columns;
+
+                    if num_columns > 0
+                        && !self.stream.capabilities.contains(Capabilities::DEPRECATE_EOF)
+                    {
+                        definitions += 1;
                     }
 
-                    self.stream.waiting.pop_front();
-                    return Ok(());
-                }
+                    // the definitions are all received before any is passed on, as the stream
+                    // may be dropped after any frame, and the rest of a result set can only be
+                    // skipped from its rows on
+                    for _ in 0..definitions {
+                        let (header, packet) = self.stream.recv_frame().await?;
 
-                // otherwise, this first packet is the start of the result-set metadata,
-                *self.stream.waiting.front_mut().unwrap() = Waiting::Row;
+                        metadata.push(frame(&header, &packet));
+                    }
 
-                let num_columns = packet.get_uint_lenenc() as usize; // column count
+                    for bytes in metadata {
+                        r#yield!(RawFrame::Metadata(bytes));
+                    }
 
-                if needs_metadata {
-                    column_names = Arc::new(recv_result_metadata(&mut self.stream, num_columns, Arc::make_mut(&mut columns)).await?);
-                } else {
-                    // next time we hit here, it'll be a new result set and we'll need the
-                    // full metadata
-                    needs_metadata = true;
+                    loop {
+                        let (header, packet) = self.stream.recv_frame().await?;
+
+                        if packet.is_eof(self.stream.capabilities) {
+                            break self.result_set_end(packet)?;
+                        }
+
+                        logger.increment_rows();
+                        self.stream.stats.rows += 1;
 
-                    recv_result_columns(&mut self.stream, num_columns, Arc::make_mut(&mut columns)).await?;
+                        r#yield!(RawFrame::Row(frame(&header, &packet)));
+                    }
+                };
+
+                self.last_query_result.extend(Some(done.clone()));
+                r#yield!(RawFrame::Done(done));
+
+                if status.contains(Status::SERVER_MORE_RESULTS_EXISTS) {
+                    *self.stream.waiting.front_mut().unwrap() = Waiting::Result;
+                    continue;
                 }
 
-                // finally, there will be none or many result-rows
-                loop {
-                    let packet = self.stream.recv_packet().await?;
+                self.stream.pop_waiting();
+                self.stream
+                    .shrink_read_buffer(self.read_buffer_shrink_threshold);
 
-                    if packet[0] == 0xfe && packet.len() < 9 {
-                        let eof = packet.eof(self.stream.capabilities)?;
+                return Ok(());
+            }
+        })
+    }
+}
 
-                        r#yield!(Either::Left(MySqlQueryResult {
-                            rows_affected: 0,
-                            last_insert_id: 0,
-                        }));
+// puts the header of a packet back in front of its payload, both as they were received
+fn frame(header: &[u8], payload: &[u8]) -> Bytes {
+    let mut frame = Vec::with_capacity(header.len() + payload.len());
 
-                        if eof.status.contains(Status::SERVER_MORE_RESULTS_EXISTS) {
-                            // more result sets exist, continue to the next one
-                            *self.stream.waiting.front_mut().unwrap() = Waiting::Result;
-                            break;
-                        }
+    frame.extend_from_slice(header);
+    frame.extend_from_slice(payload);
+
+    frame.into()
+}
+
+// the response of the server to a query, read one part at a time; the results of a query are
+// read the same way whether its rows are streamed, collected or skipped
+struct QueryResponse<'q> {
+    sql: &'q str,
+    arguments: Option<MySqlArguments>,
+    persistent: bool,
+    prepared: MySqlStatementMetadata,
+    format: MySqlValueFormat,
+    // whether the next result set is read with its own column metadata
+    needs_metadata: bool,
+    column_names: Arc<ColumnNames>,
+    // whether the query may still be sent again, which is before the server responded to it
+    first: bool,
+    state: ResponseState,
+}
+
+enum ResponseState {
+    // waiting for the result of the next statement
+    Result,
+    // in the middle of the rows of a result set
+    Rows,
+    // the last result was received
+    Done,
+}
 
-                        self.stream.waiting.pop_front();
-                        return Ok(());
+// a part of the response to a query
+enum ResponsePart {
+    // the result of a statement, which ends its result set if it has one
+    Done(MySqlQueryResult),
+    // the column count packet that starts a result set; its column definitions follow
+    ResultSet(Packet<Bytes>),
+    Row(Packet<Bytes>),
+}
+
+impl<'q> QueryResponse<'q> {
+    async fn send(
+        conn: &mut MySqlConnection,
+        sql: &'q str,
+        arguments: Option<MySqlArguments>,
+        persistent: bool,
+    ) -> Result<QueryResponse<'q>, Error> {
+        let (prepared, format, needs_metadata) =
+            conn.send_query(sql, arguments.as_ref(), persistent).await?;
+
+        Ok(Self {
+            sql,
+            arguments,
+            persistent,
+            column_names: Arc::clone(&prepared.column_names),
+            prepared,
+            format,
+            needs_metadata,
+            first: true,
+            state: ResponseState::Result,
+        })
+    }
+
+    // reads the next part of the response, or none once its last result was read
+    async fn next(&mut self, conn: &mut MySqlConnection) -> Result<Option<ResponsePart>, Error> {
+        loop {
+            match self.state {
+                ResponseState::Done => return Ok(None),
+
+                ResponseState::Rows => {
+                    // there will be none or many result-rows
+                    let packet = conn.stream.recv_packet().await?;
+
+                    if packet.is_eof(conn.stream.capabilities) {
+                        let (done, status) = conn.result_set_end(packet)?;
+
+                        return Ok(Some(self.end(conn, done, status)));
                     }
 
-                    let row = match format {
-                        MySqlValueFormat::Binary => packet.decode_with::<BinaryRow, _>(&columns)?.0,
-                        MySqlValueFormat::Text => packet.decode_with::<TextRow, _>(&columns)?.0,
+                    return Ok(Some(ResponsePart::Row(packet)));
+                }
+
+                ResponseState::Result => {
+                    // query response is a meta-packet which may be one of:
+                    //  Ok, Err, ResultSet, or LocalInfileRequest
+                    let packet = match conn.stream.recv_packet().await {
+                        Ok(packet) => packet,
+
+                        Err(error) if self.first => {
+                            self.first = false;
+
+                            let (prepared, format, needs_metadata) = conn
+                                .resend_stale_query(
+                                    error,
+                                    self.sql,
+                                    self.arguments.as_ref(),
+                                    self.persistent,
+                                )
+                                .await?;
+
+                            self.column_names = Arc::clone(&prepared.column_names);
+                            self.prepared = prepared;
+                            self.format = format;
+                            self.needs_metadata = needs_metadata;
+
+                            continue;
+                        }
+
+                        Err(error) => return Err(error),
                     };
 
-                    let v = Either::Right(MySqlRow {
-                        row,
-                        format,
-                        columns: Arc::clone(&columns),
-                        column_names: Arc::clone(&column_names),
-                    });
+                    self.first = false;
 
-                    logger.increment_rows();
+                    if packet[0] == 0xfb {
+                        // the result of the statement follows the contents of the file
+                        conn.stream.send_local_infile(packet).await?;
+                        continue;
+                    }
+
+                    if packet[0] == 0x00 || packet[0] == 0xff {
+                        // first packet in a query response is OK or ERR
+                        // this indicates either a successful query with no rows at all or a
+                        // failed query
+                        let ok = conn.stream.decode_ok(packet)?;
+
+                        let done = MySqlQueryResult {
+                            rows_affected: ok.affected_rows,
+                            last_insert_id: ok.last_insert_id,
+                            warnings: ok.warnings,
+                        };
+
+                        return Ok(Some(self.end(conn, done, ok.status)));
+                    }
+
+                    // otherwise, this first packet is the start of the result-set metadata
+                    *conn.stream.waiting.front_mut().unwrap() = Waiting::Row;
+                    self.state = ResponseState::Rows;
 
-                    r#yield!(v);
+                    return Ok(Some(ResponsePart::ResultSet(packet)));
                 }
             }
-        }))
+        }
+    }
+
+    // reads the column definitions of the result set that starts with `packet` into `columns`
+    async fn recv_columns(
+        &mut self,
+        conn: &mut MySqlConnection,
+        mut packet: Packet<Bytes>,
+        columns: &mut Vec<MySqlColumn>,
+    ) -> Result<(), Error> {
+        let num_columns = packet.get_uint_lenenc()? as usize; // column count
+
+        if self.needs_metadata {
+            self.column_names =
+                Arc::new(recv_result_metadata(&mut conn.stream, num_columns, columns).await?);
+        } else {
+            // next time we hit here, it'll be a new result set and we'll need the
+            // full metadata
+            self.needs_metadata = true;
+
+            recv_result_columns(&mut conn.stream, num_columns, columns).await?;
+
+            // the server prepares a statement again by itself after the tables it selects
+            // from changed, so its columns may no longer be the ones it was prepared with
+            if let Some(column_names) =
+                conn.update_prepared_columns(self.sql, &self.prepared.columns, columns)
+            {
+                self.column_names = column_names;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn end(
+        &mut self,
+        conn: &mut MySqlConnection,
+        done: MySqlQueryResult,
+        status: Status,
+    ) -> ResponsePart {
+        conn.last_query_result.extend(Some(done.clone()));
+
+        if status.contains(Status::SERVER_MORE_RESULTS_EXISTS) {
+            // more result sets exist, continue to the next one
+            *conn.stream.waiting.front_mut().unwrap() = Waiting::Result;
+            self.state = ResponseState::Result;
+        } else {
+            conn.stream.pop_waiting();
+            conn.stream
+                .shrink_read_buffer(conn.read_buffer_shrink_threshold);
+            self.state = ResponseState::Done;
+        }
+
+        ResponsePart::Done(done)
     }
 }
 
@@ -220,11 +784,17 @@ impl<'c> Executor<'c> for &'c mut MySqlConnection {
         E: Execute<'q, Self::Database>,
     {
         let sql = query.sql();
-        let arguments = query.take_arguments();
+        let mut arguments = query.take_arguments();
         let persistent = query.persistent();
+        let text_results = query.text_results();
+        let system = self.db_system();
+        let operation = self.operation.clone();
 
-        Box::pin(try_stream! {
-            let s = self.run(sql, arguments, persistent).await?;
+        let stream = try_stream! {
+            let sql = query_sql(sql, &mut arguments, self.stream.is_mariadb)?;
+            let text_results = text_protocol(text_results, &mut arguments);
+
+            let s = self.run(&sql, arguments, persistent, text_results).await?;
             pin_mut!(s);
 
             while let Some(v) = s.try_next().await? {
@@ -232,7 +802,26 @@ impl<'c> Executor<'c> for &'c mut MySqlConnection {
             }
 
             Ok(())
-        })
+        };
+
+        Box::pin(
+            Span::fetch(system, sql)
+                .instrument_stream(operation.stream(stream), MySqlQueryResult::rows_affected),
+        )
+    }
+
+    fn execute<'e, 'q: 'e, E: 'q>(self, query: E) -> BoxFuture<'e, Result<MySqlQueryResult, Error>>
+    where
+        'c: 'e,
+        E: Execute<'q, Self::Database>,
+    {
+        let span = Span::execute(self.db_system(), query.sql());
+        let operation = self.operation.clone();
+
+        Box::pin(span.instrument_result(
+            operation.future(Box::pin(self.execute_concrete(query))),
+            MySqlQueryResult::rows_affected,
+        ))
     }
 
     fn fetch_optional<'e, 'q: 'e, E: 'q>(
@@ -243,9 +832,12 @@ impl<'c> Executor<'c> for &'c mut MySqlConnection {
         'c: 'e,
         E: Execute<'q, Self::Database>,
     {
+        let operation = self.operation.clone();
+
+        // the stream is part of this operation, which finishes without reading the rest of it
         let mut s = self.fetch_many(query);
 
-        Box::pin(async move {
+        Box::pin(operation.future(Box::pin(async move {
             while let Some(v) = s.try_next().await? {
                 if let Either::Right(r) = v {
                     return Ok(Some(r));
@@ -253,7 +845,18 @@ impl<'c> Executor<'c> for &'c mut MySqlConnection {
             }
 
             Ok(None)
-        })
+        })))
+    }
+
+    fn fetch_all<'e, 'q: 'e, E: 'q>(self, query: E) -> BoxFuture<'e, Result<Vec<MySqlRow>, Error>>
+    where
+        'c: 'e,
+        E: Execute<'q, Self::Database>,
+    {
+        let span = Span::fetch(self.db_system(), query.sql());
+        let operation = self.operation.clone();
+
+        Box::pin(span.instrument(operation.future(Box::pin(self.fetch_all_concrete(query)))))
     }
 
     fn prepare_with<'e, 'q: 'e>(
@@ -264,7 +867,9 @@ impl<'c> Executor<'c> for &'c mut MySqlConnection {
     where
         'c: 'e,
     {
-        Box::pin(async move {
+        let operation = self.operation.clone();
+
+        Box::pin(operation.future(Box::pin(async move {
             self.stream.wait_until_ready().await?;
 
             let (_, metadata) = self.get_or_prepare(sql, true).await?;
@@ -274,7 +879,7 @@ impl<'c> Executor<'c> for &'c mut MySqlConnection {
                 // metadata has internal Arcs for expensive data structures
                 metadata: metadata.clone(),
             })
-        })
+        })))
     }
 
     #[doc(hidden)]
@@ -282,7 +887,9 @@ impl<'c> Executor<'c> for &'c mut MySqlConnection {
     where
         'c: 'e,
     {
-        Box::pin(async move {
+        let operation = self.operation.clone();
+
+        Box::pin(operation.future(Box::pin(async move {
             self.stream.wait_until_ready().await?;
 
             let (_, metadata) = self.get_or_prepare(sql, false).await?;
@@ -298,12 +905,69 @@ impl<'c> Executor<'c> for &'c mut MySqlConnection {
                 .collect();
 
             Ok(Describe {
-                parameters: Some(Either::Right(metadata.parameters)),
+                parameters: Some(Either::Right(metadata.parameters.len())),
+                // MySQL does not allow a SELECT without columns
+                returns_rows: !columns.is_empty(),
                 columns,
                 nullable,
             })
-        })
+        })))
+    }
+}
+
+// the SQL to send for a query, with its named parameters replaced or its lists expanded, and
+// its server timeout applied
+fn query_sql<'q>(
+    sql: &'q str,
+    arguments: &mut Option<MySqlArguments>,
+    is_mariadb: bool,
+) -> Result<Cow<'q, str>, Error> {
+    match arguments {
+        Some(arguments) => {
+            let sql = if arguments.named.is_some() {
+                replace_names(sql, arguments)?
+            } else {
+                expand_lists(sql, arguments)?
+            };
+
+            with_server_timeout(sql, arguments, is_mariadb)
+        }
+        None => Ok(Cow::Borrowed(sql)),
+    }
+}
+
+// the rows of a query are only sent in text format through the text protocol, which does not
+// take bound values, so a query whose rows are requested in text format is sent through it
+// if it has none; see `Query::text_results`
+fn text_protocol(text_results: bool, arguments: &mut Option<MySqlArguments>) -> bool {
+    if !text_results {
+        return false;
+    }
+
+    if let Some(arguments) = arguments {
+        if !arguments.types.is_empty() {
+            return false;
+        }
     }
+
+    *arguments = None;
+    true
+}
+
+fn decode_row(
+    packet: Packet<Bytes>,
+    format: MySqlValueFormat,
+    columns: &[MySqlColumn],
+) -> Result<crate::mysql::protocol::Row, Error> {
+    #[cfg(test)]
+    tests::ROWS_DECODED.with(|decoded| decoded.set(decoded.get() + 1));
+
+    let row = match format {
+        MySqlValueFormat::Binary => packet.decode_with::<BinaryRow, _>(columns)?.0,
+        MySqlValueFormat::Text => packet.decode_with::<TextRow, _>(columns)?.0,
+    };
+
+    Ok(row)
 }
 
 async fn recv_result_columns(
@@ -314,18 +978,19 @@ async fn recv_result_columns(
     columns.clear();
     columns.reserve(num_columns);
 
-    for ordinal in 0..num_columns {
-        columns.push(recv_next_result_column(&stream.recv().await?, ordinal)?);
-    }
+    let defs = stream.recv_column_defs(num_columns).await?;
 
-    if num_columns > 0 {
-        stream.maybe_recv_eof().await?;
+    for (ordinal, def) in defs.iter().enumerate() {
+        columns.push(recv_next_result_column(def, ordinal)?);
     }
 
     Ok(())
 }
 
-fn recv_next_result_column(def: &ColumnDefinition, ordinal: usize) -> Result<MySqlColumn, Error> {
+pub(super) fn recv_next_result_column(
+    def: &ColumnDefinition,
+    ordinal: usize,
+) -> Result<MySqlColumn, Error> {
     // if the alias is empty, use the alias
     // only then use the name
     let name = match (def.name()?, def.alias()?) {
@@ -347,25 +1012,734 @@ async fn recv_result_metadata(
     stream: &mut MySqlStream,
     num_columns: usize,
     columns: &mut Vec<MySqlColumn>,
-) -> Result<HashMap<UStr, usize>, Error> {
+) -> Result<ColumnNames, Error> {
     // the result-set metadata is primarily a listing of each output
     // column in the result-set
+    recv_result_columns(stream, num_columns, columns).await?;
 
-    let mut column_names = HashMap::with_capacity(num_columns);
+    Ok(column_names(columns))
+}
 
-    columns.clear();
-    columns.reserve(num_columns);
+fn column_names(columns: &[MySqlColumn]) -> ColumnNames {
+    let mut column_names = ColumnNames::with_capacity(columns.len());
 
-    for ordinal in 0..num_columns {
-        let def: ColumnDefinition = stream.recv().await?;
+    for column in columns {
+        column_names.insert(column.name.clone(), column.ordinal);
+    }
 
-        let column = recv_next_result_column(&def, ordinal)?;
+    column_names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::column::Column;
+    use crate::common::counting_alloc::allocations;
+    use crate::connection::Connection;
+    use crate::io::Decode;
+    use crate::mysql::options::LocalInfileHandler;
+    use crate::net::Socket;
+    use crate::row::Row;
+    use crate::statement::Statement;
+    use crate::transaction::TransactionStatus;
+    use crate::type_info::TypeInfo;
+    use std::cell::Cell;
+
+    thread_local! {
+        // the number of rows decoded on this thread
+        pub(super) static ROWS_DECODED: Cell<usize> = Cell::new(0);
+    }
+
+    const DOUBLE: u8 = 0x05;
+    const LONGLONG: u8 = 0x08;
+    const VAR_STRING: u8 = 0xfd;
+
+    fn packet(sequence_id: u8, payload: &[u8]) -> Vec<u8> {
+        let mut packet = (payload.len() as u32).to_le_bytes()[..3].to_vec();
+        packet.push(sequence_id);
+        packet.extend_from_slice(payload);
+        packet
+    }
+
+    fn column(name: &str, r#type: u8) -> Vec<u8> {
+        let mut def = b"\x03def\0\0\0".to_vec();
+
+        for _ in 0..2 {
+            def.push(name.len() as u8);
+            def.extend_from_slice(name.as_bytes());
+        }
+
+        def.push(0x0c);
+        def.extend_from_slice(&63_u16.to_le_bytes()); // binary
+        def.extend_from_slice(&20_u32.to_le_bytes());
+        def.push(r#type);
+        def.extend_from_slice(&[0, 0, 0, 0, 0]); // flags, decimals and filler
+        def
+    }
+
+    // negotiated with a server that offers everything, or everything but `DEPRECATE_EOF`
+    fn capabilities(deprecate_eof: bool) -> Capabilities {
+        let server = if deprecate_eof {
+            Capabilities::all()
+        } else {
+            Capabilities::all() - Capabilities::DEPRECATE_EOF
+        };
 
-        column_names.insert(column.name.clone(), ordinal);
-        columns.push(column);
+        Capabilities::CLIENT_SUPPORTED.negotiate(server).unwrap()
     }
 
-    stream.maybe_recv_eof().await?;
+    // the packet that ends a list of definitions or the rows of a result set
+    fn eof(deprecate_eof: bool) -> &'static [u8] {
+        // status: SERVER_STATUS_AUTOCOMMIT
+        if deprecate_eof {
+            b"\xfe\0\0\x02\0\0\0"
+        } else {
+            b"\xfe\0\0\x02\0"
+        }
+    }
+
+    fn written(conn: &MySqlConnection) -> &[u8] {
+        let socket: &Socket = &conn.stream;
+
+        match socket {
+            Socket::Scripted(stream) => &stream.written,
+            socket => panic!("not a scripted socket: {:?}", socket),
+        }
+    }
+
+    // asserts that every scripted packet was read and nothing more is expected
+    fn assert_read_to_end(conn: &mut MySqlConnection) {
+        let error = sqlx_rt::block_on(conn.stream.recv_packet()).unwrap_err();
+
+        assert!(error.is_connection_closed(), "{}", error);
+        assert!(conn.stream.waiting.is_empty());
+    }
+
+    #[test]
+    fn it_prepares_statements_with_and_without_eof_packets() {
+        for deprecate_eof in [false, true] {
+            // SELECT ?: one parameter, one column
+            let mut packets = vec![packet(1, b"\0\x01\0\0\0\x01\0\x01\0\0\0\0")];
+            packets.push(packet(2, &column("?", VAR_STRING)));
+
+            if !deprecate_eof {
+                packets.push(packet(3, eof(false)));
+            }
+
+            packets.push(packet(4, &column("?", VAR_STRING)));
+
+            if !deprecate_eof {
+                packets.push(packet(5, eof(false)));
+            }
+
+            // DO ?: one parameter and no columns, which are not followed by an EOF at all
+            packets.push(packet(1, b"\0\x02\0\0\0\0\0\x01\0\0\0\0"));
+            packets.push(packet(2, &column("?", VAR_STRING)));
+
+            if !deprecate_eof {
+                packets.push(packet(3, eof(false)));
+            }
+
+            let stream = MySqlStream::scripted(capabilities(deprecate_eof), packets);
+            let mut conn = MySqlConnection::scripted(stream);
+
+            let select = sqlx_rt::block_on(conn.prepare("SELECT ?")).unwrap();
+            let r#do = sqlx_rt::block_on(conn.prepare("DO ?")).unwrap();
+
+            assert_eq!(select.parameters(), Some(Either::Right(1)));
+            assert_eq!(select.columns().len(), 1);
+            assert_eq!(select.columns()[0].name(), "?");
+            assert_eq!(r#do.parameters(), Some(Either::Right(1)));
+            assert!(r#do.columns().is_empty());
+
+            let mut expected = packet(0, b"\x16SELECT ?");
+            expected.extend(packet(0, b"\x16DO ?"));
+
+            assert_eq!(written(&conn), &expected[..]);
+            assert_read_to_end(&mut conn);
+        }
+    }
+
+    #[test]
+    fn it_keeps_the_parameter_and_column_definitions_of_statements() {
+        // SELECT a, b, a + b AS c FROM t WHERE a > ? AND b < ?
+        let packets = vec![
+            packet(1, b"\0\x01\0\0\0\x03\0\x02\0\0\0\0"),
+            packet(2, &column("?", LONGLONG)),
+            packet(3, &column("?", DOUBLE)),
+            packet(4, &column("a", LONGLONG)),
+            packet(5, &column("b", DOUBLE)),
+            packet(6, &column("c", DOUBLE)),
+        ];
+
+        let stream = MySqlStream::scripted(capabilities(true), packets);
+        let mut conn = MySqlConnection::scripted(stream);
+
+        let statement =
+            sqlx_rt::block_on(conn.prepare("SELECT a, b, a + b AS c FROM t WHERE a > ? AND b < ?"))
+                .unwrap();
+
+        let parameters: Vec<_> = statement
+            .parameter_types()
+            .iter()
+            .map(TypeInfo::name)
+            .collect();
+
+        assert_eq!(parameters, ["BIGINT", "DOUBLE"]);
+        assert_eq!(statement.parameters(), Some(Either::Right(2)));
+
+        let columns: Vec<_> = statement
+            .columns()
+            .iter()
+            .map(|column| (column.name(), column.type_info().name()))
+            .collect();
+
+        assert_eq!(columns, [("a", "BIGINT"), ("b", "DOUBLE"), ("c", "DOUBLE")]);
+        assert_eq!(statement.column("c").ordinal(), 2);
+
+        assert_read_to_end(&mut conn);
+    }
+
+    #[test]
+    fn it_decodes_rows_with_the_columns_sent_on_execution() {
+        let packets = vec![
+            // prepared with `a` as a BIGINT
+            packet(1, b"\0\x01\0\0\0\x01\0\0\0\0\0\0"),
+            packet(2, &column("a", LONGLONG)),
+            // executed after it changed to a DOUBLE
+            packet(1, b"\x01"),
+            packet(2, &column("a", DOUBLE)),
+            packet(3, &[&[0, 0][..], &1.5_f64.to_le_bytes()].concat()),
+            packet(4, eof(true)),
+        ];
+
+        let stream = MySqlStream::scripted(capabilities(true), packets);
+        let mut conn = MySqlConnection::scripted(stream);
+
+        let rows =
+            sqlx_rt::block_on(crate::query::query("SELECT a FROM t").fetch_all(&mut conn)).unwrap();
+
+        assert_eq!(rows[0].column("a").type_info().name(), "DOUBLE");
+        assert_eq!(rows[0].get::<f64, _>("a"), 1.5);
+
+        // the cached statement has the columns it was executed with
+        let statement = sqlx_rt::block_on(conn.prepare("SELECT a FROM t")).unwrap();
+
+        assert_eq!(statement.column("a").type_info().name(), "DOUBLE");
+        assert_eq!(conn.cached_statements_size(), 1);
+
+        assert_read_to_end(&mut conn);
+    }
+
+    #[test]
+    fn it_reads_columns_of_unknown_types() {
+        // MYSQL_TYPE_VECTOR, added in MySQL 9.0
+        const VECTOR: u8 = 0xf2;
+
+        let vector = 1.5_f32.to_le_bytes();
+
+        let packets = vec![
+            // the text protocol
+            packet(1, b"\x02"),
+            packet(2, &column("i", LONGLONG)),
+            packet(3, &column("v", VECTOR)),
+            packet(4, &[&b"\x017\x04"[..], &vector].concat()),
+            packet(5, eof(true)),
+            // the binary protocol
+            packet(1, b"\0\x01\0\0\0\x02\0\0\0\0\0\0"),
+            packet(2, &column("i", LONGLONG)),
+            packet(3, &column("v", VECTOR)),
+            packet(1, b"\x02"),
+            packet(2, &column("i", LONGLONG)),
+            packet(3, &column("v", VECTOR)),
+            packet(
+                4,
+                &[&[0, 0][..], &7_i64.to_le_bytes(), &[4], &vector].concat(),
+            ),
+            packet(5, eof(true)),
+        ];
+
+        let stream = MySqlStream::scripted(capabilities(true), packets);
+        let mut conn = MySqlConnection::scripted(stream);
+
+        let text = sqlx_rt::block_on(conn.fetch_one("SELECT i, v FROM t")).unwrap();
+        let binary =
+            sqlx_rt::block_on(crate::query::query("SELECT i, v FROM t").fetch_one(&mut conn))
+                .unwrap();
+
+        for row in [text, binary] {
+            assert_eq!(row.get::<i64, _>("i"), 7);
+
+            // the value can only be read as bytes
+            assert_eq!(row.column("v").type_info().name(), "UNKNOWN");
+            assert_eq!(row.get::<Vec<u8>, _>("v"), vector);
+            assert!(matches!(
+                row.try_get::<String, _>("v"),
+                Err(Error::ColumnDecode { .. })
+            ));
+        }
+
+        assert_read_to_end(&mut conn);
+    }
+
+    #[test]
+    fn it_keeps_the_columns_added_to_a_statement_after_it_was_prepared() {
+        let packets = vec![
+            // prepared with a column `a`
+            packet(1, b"\0\x01\0\0\0\x01\0\0\0\0\0\0"),
+            packet(2, &column("a", LONGLONG)),
+            // executed after a column `b` was added to the table
+            packet(1, b"\x02"),
+            packet(2, &column("a", LONGLONG)),
+            packet(3, &column("b", DOUBLE)),
+            packet(
+                4,
+                &[&[0, 0][..], &7_i64.to_le_bytes(), &1.5_f64.to_le_bytes()].concat(),
+            ),
+            packet(5, eof(true)),
+        ];
+
+        let stream = MySqlStream::scripted(capabilities(true), packets);
+        let mut conn = MySqlConnection::scripted(stream);
+
+        let rows =
+            sqlx_rt::block_on(crate::query::query("SELECT * FROM t").fetch_all(&mut conn)).unwrap();
+
+        assert_eq!(rows[0].get::<i64, _>("a"), 7);
+        assert_eq!(rows[0].get::<f64, _>("b"), 1.5);
+
+        // the cached statement has the columns it was executed with
+        let statement = sqlx_rt::block_on(conn.prepare("SELECT * FROM t")).unwrap();
+
+        assert_eq!(statement.columns().len(), 2);
+        assert_eq!(statement.column("b").ordinal(), 1);
+
+        assert_read_to_end(&mut conn);
+    }
+
+    #[test]
+    fn it_reads_result_sets_with_and_without_eof_packets() {
+        for deprecate_eof in [false, true] {
+            let mut packets = vec![packet(1, b"\x01"), packet(2, &column("i", LONGLONG))];
+
+            if !deprecate_eof {
+                packets.push(packet(3, eof(false)));
+            }
+
+            packets.push(packet(4, b"\x011"));
+            packets.push(packet(5, b"\x012"));
+            packets.push(packet(6, eof(deprecate_eof)));
+
+            let stream = MySqlStream::scripted(capabilities(deprecate_eof), packets);
+            let mut conn = MySqlConnection::scripted(stream);
+
+            let rows = sqlx_rt::block_on(conn.fetch_all("SELECT 1 AS i UNION SELECT 2")).unwrap();
+            let values: Vec<i64> = rows.iter().map(|row| row.get("i")).collect();
+
+            assert_eq!(values, [1, 2]);
+            assert_eq!(
+                written(&conn),
+                &packet(0, b"\x03SELECT 1 AS i UNION SELECT 2")[..]
+            );
+            assert_read_to_end(&mut conn);
+        }
+    }
+
+    #[test]
+    fn it_counts_queries_rows_and_bytes() {
+        let packets = vec![
+            // SELECT 1 AS i UNION SELECT 2
+            packet(1, b"\x01"),
+            packet(2, &column("i", LONGLONG)),
+            packet(3, b"\x011"),
+            packet(4, b"\x012"),
+            packet(5, eof(true)),
+            // DO 1
+            packet(1, b"\0\0\0\x02\0\0\0"),
+        ];
+
+        let bytes_read = packets.iter().map(Vec::len).sum::<usize>() as u64;
+
+        let stream = MySqlStream::scripted(capabilities(true), packets);
+        let mut conn = MySqlConnection::scripted(stream);
+
+        assert_eq!(conn.stats().queries(), 0);
+        assert_eq!(conn.stats().last_used(), None);
+
+        let rows = sqlx_rt::block_on(conn.fetch_all("SELECT 1 AS i UNION SELECT 2")).unwrap();
+        assert_eq!(rows.len(), 2);
+
+        sqlx_rt::block_on(conn.execute("DO 1")).unwrap();
 
-    Ok(column_names)
+        let stats = conn.stats();
+
+        assert_eq!(stats.backend_id(), None);
+        assert_eq!(stats.queries(), 2);
+        assert_eq!(stats.rows(), 2);
+        assert_eq!(stats.bytes_read(), bytes_read);
+        assert_eq!(stats.bytes_written(), written(&conn).len() as u64);
+        assert!(stats.last_used().is_some());
+
+        // neither query is still waiting on the server
+        assert!(conn.stream.stats.busy_since.is_none());
+    }
+
+    #[test]
+    fn it_tracks_whether_a_transaction_is_active() {
+        let packets = vec![
+            // BEGIN; status: SERVER_STATUS_IN_TRANS | SERVER_STATUS_AUTOCOMMIT
+            packet(1, b"\0\0\0\x03\0\0\0"),
+            // SELECT 1
+            packet(1, b"\x01"),
+            packet(2, &column("1", LONGLONG)),
+            packet(3, b"\x011"),
+            packet(4, b"\xfe\0\0\x03\0\0\0"),
+            // COMMIT
+            packet(1, b"\0\0\0\x02\0\0\0"),
+        ];
+
+        let stream = MySqlStream::scripted(capabilities(true), packets);
+        let mut conn = MySqlConnection::scripted(stream);
+
+        assert_eq!(conn.transaction_status(), TransactionStatus::Idle);
+
+        let mut tx = sqlx_rt::block_on(conn.begin()).unwrap();
+        assert_eq!(tx.transaction_status(), TransactionStatus::InTransaction);
+
+        sqlx_rt::block_on(tx.execute("SELECT 1")).unwrap();
+        assert_eq!(tx.transaction_status(), TransactionStatus::InTransaction);
+
+        // too late to change the characteristics of the transaction; nothing is sent
+        let error = sqlx_rt::block_on(tx.set_read_only(true)).unwrap_err();
+        assert!(matches!(error, Error::Configuration(_)), "{}", error);
+
+        sqlx_rt::block_on(tx.commit()).unwrap();
+        assert_eq!(conn.transaction_status(), TransactionStatus::Idle);
+
+        assert_read_to_end(&mut conn);
+    }
+
+    #[test]
+    fn it_executes_without_decoding_rows() {
+        let select = [
+            packet(1, b"\x01"),
+            packet(2, &column("i", LONGLONG)),
+            packet(3, b"\x011"),
+            packet(4, b"\x012"),
+            packet(5, eof(true)),
+        ];
+
+        // UPDATE t SET i = i + 1: 1000 rows affected
+        let mut packets = vec![packet(1, b"\0\xfc\xe8\x03\0\x02\0\0\0")];
+        packets.extend(select.iter().cloned());
+        packets.extend(select.iter().cloned());
+
+        let stream = MySqlStream::scripted(capabilities(true), packets);
+        let mut conn = MySqlConnection::scripted(stream);
+
+        ROWS_DECODED.with(|decoded| decoded.set(0));
+
+        let done = sqlx_rt::block_on(conn.execute("UPDATE t SET i = i + 1")).unwrap();
+        assert_eq!(done.rows_affected(), 1000);
+
+        // the rows of a SELECT are not counted as affected
+        let done = sqlx_rt::block_on(conn.execute("SELECT 1 AS i UNION SELECT 2")).unwrap();
+        assert_eq!(done.rows_affected(), 0);
+
+        assert_eq!(ROWS_DECODED.with(Cell::get), 0);
+        assert_eq!(conn.stats().rows(), 2);
+
+        // the connection is left ready for the next query
+        let rows = sqlx_rt::block_on(conn.fetch_all("SELECT 1 AS i UNION SELECT 2")).unwrap();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(ROWS_DECODED.with(Cell::get), 2);
+        assert_read_to_end(&mut conn);
+    }
+
+    #[test]
+    fn it_executes_prepared_statements_without_parameters() {
+        for deprecate_eof in [false, true] {
+            // SELECT 1: no parameters and one column
+            let mut packets = vec![
+                packet(1, b"\0\x01\0\0\0\x01\0\0\0\0\0\0"),
+                packet(2, &column("1", LONGLONG)),
+            ];
+
+            if !deprecate_eof {
+                packets.push(packet(3, eof(false)));
+            }
+
+            packets.push(packet(1, b"\x01"));
+            packets.push(packet(2, &column("1", LONGLONG)));
+
+            if !deprecate_eof {
+                packets.push(packet(3, eof(false)));
+            }
+
+            packets.push(packet(4, &[&b"\0\0"[..], &1_i64.to_le_bytes()].concat()));
+            packets.push(packet(5, eof(deprecate_eof)));
+
+            // DELETE FROM t: no parameters and no columns; 2 rows affected
+            packets.push(packet(1, b"\0\x02\0\0\0\0\0\0\0\0\0\0"));
+            packets.push(packet(1, b"\0\x02\0\x02\0\0\0"));
+
+            let stream = MySqlStream::scripted(capabilities(deprecate_eof), packets);
+            let mut conn = MySqlConnection::scripted(stream);
+
+            let rows = sqlx_rt::block_on(conn.fetch_all(crate::query::query("SELECT 1"))).unwrap();
+
+            assert_eq!(rows.len(), 1);
+            assert_eq!(rows[0].get::<i64, _>(0), 1);
+
+            let done =
+                sqlx_rt::block_on(conn.execute(crate::query::query("DELETE FROM t"))).unwrap();
+
+            assert_eq!(done.rows_affected(), 2);
+
+            // the execute packets end after the iteration count
+            let mut expected = packet(0, b"\x16SELECT 1");
+            expected.extend(packet(0, b"\x17\x01\0\0\0\0\x01\0\0\0"));
+            expected.extend(packet(0, b"\x16DELETE FROM t"));
+            expected.extend(packet(0, b"\x17\x02\0\0\0\0\x01\0\0\0"));
+
+            assert_eq!(written(&conn), &expected[..]);
+            assert_read_to_end(&mut conn);
+        }
+    }
+
+    #[test]
+    fn it_sends_parameter_types_only_when_they_change() {
+        // DO ?: one parameter and no columns
+        let mut packets = vec![
+            packet(1, b"\0\x01\0\0\0\0\0\x01\0\0\0\0"),
+            packet(2, &column("?", LONGLONG)),
+        ];
+
+        for _ in 0..3 {
+            packets.push(packet(1, b"\0\0\0\x02\0\0\0"));
+        }
+
+        let stream = MySqlStream::scripted(capabilities(true), packets);
+        let mut conn = MySqlConnection::scripted(stream);
+
+        for query in [
+            crate::query::query("DO ?").bind(1_i32),
+            crate::query::query("DO ?").bind(2_i32),
+            crate::query::query("DO ?").bind(3_i64),
+        ] {
+            sqlx_rt::block_on(conn.execute(query)).unwrap();
+        }
+
+        // the types are left out when executing the statement again with the same ones
+        let mut expected = packet(0, b"\x16DO ?");
+        expected.extend(packet(
+            0,
+            b"\x17\x01\0\0\0\0\x01\0\0\0\0\x01\x03\0\x01\0\0\0",
+        ));
+        expected.extend(packet(0, b"\x17\x01\0\0\0\0\x01\0\0\0\0\0\x02\0\0\0"));
+        expected.extend(packet(
+            0,
+            b"\x17\x01\0\0\0\0\x01\0\0\0\0\x01\x08\0\x03\0\0\0\0\0\0\0",
+        ));
+
+        assert_eq!(written(&conn), &expected[..]);
+        assert_read_to_end(&mut conn);
+    }
+
+    #[test]
+    fn it_shares_the_metadata_of_a_result_set_between_its_rows() {
+        // the allocations made to fetch `rows` rows of `columns` text values of 64 bytes in
+        // all, so that only the number of columns differs
+        fn fetch(columns: usize, rows: usize) -> usize {
+            let value = vec![b'x'; 64 / columns - 1];
+            let mut row = Vec::new();
+
+            for _ in 0..columns {
+                row.push(value.len() as u8);
+                row.extend_from_slice(&value);
+            }
+
+            let mut sequence_id = 0_u8;
+            let mut next = || {
+                sequence_id = sequence_id.wrapping_add(1);
+                sequence_id
+            };
+
+            let mut packets = vec![packet(next(), &[columns as u8])];
+
+            for i in 0..columns {
+                packets.push(packet(next(), &column(&format!("c{}", i), VAR_STRING)));
+            }
+
+            for _ in 0..rows {
+                packets.push(packet(next(), &row));
+            }
+
+            packets.push(packet(next(), eof(true)));
+
+            let stream = MySqlStream::scripted(capabilities(true), packets);
+            let mut conn = MySqlConnection::scripted(stream);
+
+            let (fetched, allocations) =
+                allocations(|| sqlx_rt::block_on(conn.fetch_all("SELECT *")).unwrap());
+
+            assert_eq!(fetched.len(), rows);
+            assert!(fetched.iter().all(|row| row.try_get_raw("c0").is_ok()));
+
+            allocations
+        }
+
+        // the average number of allocations made for each row
+        let per_row = |columns| (fetch(columns, 200) - fetch(columns, 100) + 50) / 100;
+
+        // anything allocated once, e.g. by the runtime, is not counted for a row
+        fetch(2, 1);
+
+        assert_eq!(per_row(2), per_row(16));
+    }
+
+    #[test]
+    fn it_refuses_local_files_without_a_handler() {
+        let packets = vec![
+            // LOAD DATA LOCAL INFILE 'data.csv' INTO TABLE t
+            packet(1, b"\xfbdata.csv"),
+            packet(3, b"\0\0\0\x02\0\0\0"),
+            // DO 1
+            packet(1, b"\0\0\0\x02\0\0\0"),
+        ];
+
+        let stream = MySqlStream::scripted(capabilities(true), packets);
+        let mut conn = MySqlConnection::scripted(stream);
+
+        let sql = "LOAD DATA LOCAL INFILE 'data.csv' INTO TABLE t";
+        let error = sqlx_rt::block_on(conn.execute(sql)).unwrap_err();
+
+        assert!(matches!(error, Error::Configuration(_)), "{}", error);
+        assert!(error.to_string().contains("\"data.csv\""), "{}", error);
+
+        // the refusal is answered with the result of the statement, which is discarded
+        sqlx_rt::block_on(conn.execute("DO 1")).unwrap();
+
+        let mut expected = packet(0, &[&b"\x03"[..], sql.as_bytes()].concat());
+        expected.extend(packet(2, b""));
+        expected.extend(packet(0, b"\x03DO 1"));
+
+        assert_eq!(written(&conn), &expected[..]);
+        assert_read_to_end(&mut conn);
+    }
+
+    #[test]
+    fn it_sends_local_files_to_the_server() {
+        let packets = vec![
+            packet(1, b"\xfbdata.csv"),
+            // 2 rows affected
+            packet(4, b"\0\x02\0\x02\0\0\0"),
+        ];
+
+        let stream = MySqlStream::scripted(capabilities(true), packets);
+        let mut conn = MySqlConnection::scripted(stream);
+
+        conn.stream.local_infile = Some(LocalInfileHandler::new(|filename| {
+            assert_eq!(filename, "data.csv");
+
+            async { Ok(&b"1,one\n2,two\n"[..]) }
+        }));
+
+        let sql = "LOAD DATA LOCAL INFILE 'data.csv' INTO TABLE t";
+        let done = sqlx_rt::block_on(conn.execute(sql)).unwrap();
+
+        assert_eq!(done.rows_affected(), 2);
+
+        let mut expected = packet(0, &[&b"\x03"[..], sql.as_bytes()].concat());
+        expected.extend(packet(2, b"1,one\n2,two\n"));
+        expected.extend(packet(3, b""));
+
+        assert_eq!(written(&conn), &expected[..]);
+        assert_read_to_end(&mut conn);
+    }
+
+    #[test]
+    fn it_fetches_raw_frames() {
+        for deprecate_eof in [false, true] {
+            // SELECT 1 AS i UNION SELECT 2, read to the end and then again up to its first row
+            let mut select = vec![packet(1, b"\x01"), packet(2, &column("i", LONGLONG))];
+
+            if !deprecate_eof {
+                select.push(packet(3, eof(false)));
+            }
+
+            select.push(packet(4, b"\x011"));
+            select.push(packet(5, b"\x012"));
+            select.push(packet(6, eof(deprecate_eof)));
+
+            let mut packets = select.clone();
+            packets.extend(select.iter().cloned());
+
+            // DO 1
+            packets.push(packet(1, b"\0\0\0\x02\0\0\0"));
+
+            let stream = MySqlStream::scripted(capabilities(deprecate_eof), packets);
+            let mut conn = MySqlConnection::scripted(stream);
+
+            let frames: Vec<RawFrame<MySql>> = sqlx_rt::block_on(
+                conn.fetch_raw_frames("SELECT 1 AS i UNION SELECT 2")
+                    .try_collect(),
+            )
+            .unwrap();
+
+            // every frame but the end of the rows is passed on as it was received
+            let metadata = if deprecate_eof { 2 } else { 3 };
+
+            assert_eq!(frames.len(), metadata + 3);
+
+            for (frame, expected) in frames.iter().zip(&select) {
+                match frame {
+                    RawFrame::Metadata(frame) | RawFrame::Row(frame) => {
+                        assert_eq!(&frame[..], &expected[..])
+                    }
+
+                    frame => panic!("unexpected frame: {:?}", frame),
+                }
+            }
+
+            // and is decoded as it would have been
+            let def = match &frames[1] {
+                RawFrame::Metadata(frame) => {
+                    ColumnDefinition::decode_with(frame.slice(4..), conn.stream.capabilities)
+                        .unwrap()
+                }
+
+                frame => panic!("unexpected frame: {:?}", frame),
+            };
+
+            let columns = vec![recv_next_result_column(&def, 0).unwrap()];
+
+            assert_eq!(columns[0].name(), "i");
+
+            for (frame, value) in frames[metadata..metadata + 2].iter().zip(&["1", "2"]) {
+                match frame {
+                    RawFrame::Row(frame) => {
+                        let row = TextRow::decode_with(frame.slice(4..), &columns).unwrap().0;
+
+                        assert_eq!(row.get(0), Some(value.as_bytes()));
+                    }
+
+                    frame => panic!("unexpected frame: {:?}", frame),
+                }
+            }
+
+            assert!(matches!(frames.last(), Some(RawFrame::Done(_))));
+
+            // frames that are not read are drained before the next query
+            let first = sqlx_rt::block_on(
+                conn.fetch_raw_frames("SELECT 1 AS i UNION SELECT 2")
+                    .try_next(),
+            );
+            assert!(matches!(first, Ok(Some(RawFrame::Metadata(_)))));
+
+            sqlx_rt::block_on(conn.execute("DO 1")).unwrap();
+            assert_read_to_end(&mut conn);
+        }
+    }
 }
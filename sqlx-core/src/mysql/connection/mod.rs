@@ -1,21 +1,27 @@
-use crate::common::StatementCache;
-use crate::connection::{Connection, LogSettings};
+use crate::common::{OperationState, StatementCache};
+use crate::connection::{
+    Connection, ConnectionStats, LogSettings, ServerVersion, CLOSE_DRAIN_TIMEOUT,
+};
 use crate::error::Error;
+use crate::mysql::protocol::response::Status;
 use crate::mysql::protocol::statement::StmtClose;
 use crate::mysql::protocol::text::{Ping, Quit};
 use crate::mysql::statement::MySqlStatementMetadata;
-use crate::mysql::{MySql, MySqlConnectOptions};
-use crate::transaction::Transaction;
+use crate::mysql::{MySql, MySqlCapabilities, MySqlConnectOptions, MySqlQueryResult};
+use crate::transaction::{IsolationLevel, Transaction, TransactionStatus};
+use crate::HashMap;
 use futures_core::future::BoxFuture;
 use futures_util::FutureExt;
 use std::fmt::{self, Debug, Formatter};
 
 mod auth;
+mod cursor;
 mod establish;
 mod executor;
 mod stream;
 mod tls;
 
+pub use cursor::MySqlCursor;
 pub(crate) use stream::{MySqlStream, Waiting};
 
 const MAX_PACKET_SIZE: u32 = 1024;
@@ -30,10 +36,78 @@ pub struct MySqlConnection {
     // transaction status
     pub(crate) transaction_depth: usize,
 
+    // the number of queries sent once the outermost transaction began, and the characteristics
+    // set on it since, which have to be set again if it is restarted
+    pub(crate) queries_at_begin: u64,
+    pub(crate) isolation_level: Option<IsolationLevel>,
+    pub(crate) read_only: Option<bool>,
+
     // cache by query string to the statement id and metadata
     cache_statement: StatementCache<(u32, MySqlStatementMetadata)>,
 
+    // the encoded parameter types last sent to execute each cached statement, which the server
+    // keeps, so that they are left out when executing it again with the same types
+    bound_types: HashMap<u32, Vec<u8>>,
+
     log_settings: LogSettings,
+
+    // the combined result of the last statement, filled in as its results are received
+    last_query_result: MySqlQueryResult,
+
+    // see `MySqlConnectOptions::read_buffer_shrink_threshold`
+    read_buffer_shrink_threshold: usize,
+
+    // the operation running on this connection; see `Error::ConnectionBusy`
+    pub(crate) operation: OperationState,
+}
+
+impl MySqlConnection {
+    /// Returns the combined result of the last statement executed on this connection.
+    ///
+    /// This is filled in as the results of the statement are received, so that the number of
+    /// rows affected is available after fetching rows as well, e.g. with `fetch_all`. Once the
+    /// rows of a statement have all been fetched, this is equal to what [`execute`] would have
+    /// returned.
+    ///
+    /// [`execute`]: crate::executor::Executor::execute
+    pub fn last_query_result(&self) -> &MySqlQueryResult {
+        &self.last_query_result
+    }
+
+    /// Returns the capabilities negotiated with the server: those that the server offered and
+    /// this driver implements.
+    ///
+    /// These decide how the protocol is spoken on this connection, e.g. whether result sets end
+    /// with an `EOF` or an `OK` packet; this is mostly useful for diagnostics.
+    pub fn capabilities(&self) -> MySqlCapabilities {
+        self.stream.capabilities
+    }
+
+    /// Returns whether this connection is in a transaction, as reported by the server when the
+    /// last statement completed.
+    ///
+    /// A transaction is started with [`begin`][Connection::begin] or by executing `BEGIN`; either
+    /// way, this tells whether a new transaction or a savepoint would be started. MySQL does not
+    /// abort a transaction when a statement in it fails, so this is never
+    /// [`InFailedTransaction`][TransactionStatus::InFailedTransaction].
+    pub fn transaction_status(&self) -> TransactionStatus {
+        if self.stream.status.contains(Status::SERVER_STATUS_IN_TRANS) {
+            TransactionStatus::InTransaction
+        } else {
+            TransactionStatus::Idle
+        }
+    }
+
+    /// Returns the version of the server, as reported by it when the connection was
+    /// established.
+    pub fn server_version(&self) -> ServerVersion {
+        self.stream.server_version
+    }
+
+    #[doc(hidden)]
+    pub fn read_buffer_capacity(&self) -> usize {
+        self.stream.read_buffer_capacity()
+    }
 }
 
 impl Debug for MySqlConnection {
@@ -48,9 +122,40 @@ impl Connection for MySqlConnection {
     type Options = MySqlConnectOptions;
 
     fn close(mut self) -> BoxFuture<'static, Result<(), Error>> {
+        // see `Connection::close` for how pending work is dealt with first
         Box::pin(async move {
-            self.stream.send_packet(Quit).await?;
-            self.stream.shutdown().await?;
+            // a packet may have been written or read partway; nothing more can be sent
+            let in_sync = !self.stream.busy && !self.stream.broken;
+
+            if in_sync {
+                self.stream.discard_queued();
+
+                if !self.stream.waiting.is_empty() {
+                    match sqlx_rt::timeout(CLOSE_DRAIN_TIMEOUT, self.stream.wait_until_ready())
+                        .await
+                    {
+                        Ok(Ok(())) => {}
+                        Ok(Err(e)) => log::debug!(
+                            "error reading the results of a query while closing the connection: {}",
+                            e
+                        ),
+                        Err(_) => log::debug!(
+                            "gave up reading the results of a query while closing the connection"
+                        ),
+                    }
+                }
+            }
+
+            let quit = if in_sync && !self.stream.broken {
+                self.stream.send_packet(Quit).await
+            } else {
+                Ok(())
+            };
+
+            let shutdown = self.stream.shutdown().await;
+
+            quit?;
+            shutdown?;
 
             Ok(())
         })
@@ -78,6 +183,8 @@ impl Connection for MySqlConnection {
     fn clear_cached_statements(&mut self) -> BoxFuture<'_, Result<(), Error>> {
         Box::pin(async move {
             while let Some((statement_id, _)) = self.cache_statement.remove_lru() {
+                self.bound_types.remove(&statement_id);
+
                 self.stream
                     .send_packet(StmtClose {
                         statement: statement_id,
@@ -94,6 +201,49 @@ impl Connection for MySqlConnection {
         !self.stream.wbuf.is_empty()
     }
 
+    #[doc(hidden)]
+    fn is_busy(&self) -> bool {
+        self.stream.busy || self.operation.is_unfinished()
+    }
+
+    #[doc(hidden)]
+    fn is_broken(&self) -> bool {
+        self.stream.broken
+    }
+
+    fn is_alive(&mut self) -> bool {
+        if !self.stream.broken && self.stream.is_closed() {
+            self.stream.broken = true;
+        }
+
+        !self.stream.broken
+    }
+
+    #[doc(hidden)]
+    fn shrink_buffers(&mut self) {
+        self.stream
+            .shrink_read_buffer(self.read_buffer_shrink_threshold);
+    }
+
+    #[doc(hidden)]
+    fn db_system(&self) -> &'static str {
+        if self.stream.is_mariadb {
+            "mariadb"
+        } else {
+            MySql::DB_SYSTEM
+        }
+    }
+
+    fn stats(&self) -> ConnectionStats {
+        ConnectionStats {
+            bytes_read: self.stream.bytes_read,
+            bytes_written: self.stream.bytes_written,
+            statement_cache_hits: self.cache_statement.hits(),
+            statement_cache_misses: self.cache_statement.misses(),
+            ..self.stream.stats.clone()
+        }
+    }
+
     fn begin(&mut self) -> BoxFuture<'_, Result<Transaction<'_, Self::Database>, Error>>
     where
         Self: Sized,
@@ -3,23 +3,47 @@ use std::ops::{Deref, DerefMut};
 
 use bytes::{Buf, Bytes};
 
+use crate::connection::{ConnectionStats, ServerVendor, ServerVersion};
 use crate::error::Error;
 use crate::io::{BufStream, Decode, Encode};
 use crate::mysql::collation::{CharSet, Collation};
 use crate::mysql::io::MySqlBufExt;
-use crate::mysql::protocol::response::{EofPacket, ErrPacket, OkPacket, Status};
+use crate::mysql::options::LocalInfileHandler;
+use crate::mysql::protocol::response::{ErrPacket, LocalInfileRequest, OkPacket, Status};
+use crate::mysql::protocol::text::ColumnDefinition;
 use crate::mysql::protocol::{Capabilities, Packet};
 use crate::mysql::{MySqlConnectOptions, MySqlDatabaseError};
 use crate::net::{MaybeTlsStream, Socket};
+use crate::wire::{Direction, PacketObserver};
+use sqlx_rt::AsyncReadExt;
+
+// the type of the command that closes a prepared statement, which has no response
+const COM_STMT_CLOSE: u8 = 0x19;
+
+// the size of the packets that the contents of a local file are sent in, well below both the
+// largest payload of a packet and the smallest `max_allowed_packet` of a server
+const LOCAL_INFILE_CHUNK_SIZE: usize = 16 * 1024;
 
 pub struct MySqlStream {
     stream: BufStream<MaybeTlsStream<Socket>>,
-    pub(crate) server_version: (u16, u16, u16),
+    pub(crate) server_version: ServerVersion,
+    pub(crate) is_mariadb: bool,
     pub(super) capabilities: Capabilities,
     pub(crate) sequence_id: u8,
     pub(crate) waiting: VecDeque<Waiting>,
     pub(crate) charset: CharSet,
     pub(crate) collation: Collation,
+    observer: Option<PacketObserver>,
+    pub(super) local_infile: Option<LocalInfileHandler>,
+
+    // packets sent during authentication carry credentials and are observed without a payload
+    pub(super) redact_sent: bool,
+
+    // see `Connection::stats`; kept here as the stream tracks which results are outstanding
+    pub(crate) stats: ConnectionStats,
+
+    // the status of the last OK or EOF packet received, which tells whether a transaction is active
+    pub(crate) status: Status,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -33,6 +57,43 @@ pub(crate) enum Waiting {
 
 impl MySqlStream {
     pub(super) async fn connect(options: &MySqlConnectOptions) -> Result<Self, Error> {
+        let socket = match options.socket {
+            Some(ref path) => Socket::connect_uds(path).await?,
+            None => Socket::connect_tcp(&options.host, options.port).await?,
+        };
+
+        Self::with_socket(options, socket)
+    }
+
+    // a stream that replays `packets` and was negotiated with `capabilities`, as if it had
+    // completed the handshake
+    #[cfg(test)]
+    pub(super) fn scripted<I, C>(capabilities: Capabilities, packets: I) -> Self
+    where
+        I: IntoIterator<Item = C>,
+        C: Into<Vec<u8>>,
+    {
+        let socket = Socket::Scripted(crate::io::scripted::ScriptedStream::new(packets));
+        let mut stream = Self::with_socket(&MySqlConnectOptions::new(), socket).unwrap();
+
+        stream.capabilities = capabilities;
+        stream.redact_sent = false;
+        stream
+    }
+
+    // a stream that replays `packets` from the start of the connection, before the handshake
+    #[cfg(test)]
+    pub(super) fn scripted_connect<I, C>(options: &MySqlConnectOptions, packets: I) -> Self
+    where
+        I: IntoIterator<Item = C>,
+        C: Into<Vec<u8>>,
+    {
+        let socket = Socket::Scripted(crate::io::scripted::ScriptedStream::new(packets));
+
+        Self::with_socket(options, socket).unwrap()
+    }
+
+    fn with_socket(options: &MySqlConnectOptions, socket: Socket) -> Result<Self, Error> {
         let charset: CharSet = options.charset.parse()?;
         let collation: Collation = options
             .collation
@@ -41,39 +102,82 @@ impl MySqlStream {
             .transpose()?
             .unwrap_or_else(|| charset.default_collation());
 
-        let socket = match options.socket {
-            Some(ref path) => Socket::connect_uds(path).await?,
-            None => Socket::connect_tcp(&options.host, options.port).await?,
-        };
+        // narrowed down to what the server offers by the handshake
+        let mut capabilities = Capabilities::CLIENT_SUPPORTED;
+
+        if options.database.is_none() {
+            capabilities.remove(Capabilities::CONNECT_WITH_DB);
+        }
 
-        let mut capabilities = Capabilities::PROTOCOL_41
-            | Capabilities::IGNORE_SPACE
-            | Capabilities::DEPRECATE_EOF
-            | Capabilities::FOUND_ROWS
-            | Capabilities::TRANSACTIONS
-            | Capabilities::SECURE_CONNECTION
-            | Capabilities::PLUGIN_AUTH_LENENC_DATA
-            | Capabilities::MULTI_STATEMENTS
-            | Capabilities::MULTI_RESULTS
-            | Capabilities::PLUGIN_AUTH
-            | Capabilities::PS_MULTI_RESULTS
-            | Capabilities::SSL;
-
-        if options.database.is_some() {
-            capabilities |= Capabilities::CONNECT_WITH_DB;
+        // the server only asks for local files when the client offers to send them
+        if options.local_infile_handler.is_some() {
+            capabilities.insert(Capabilities::LOCAL_FILES);
         }
 
+        let mut stream = BufStream::new(MaybeTlsStream::Raw(socket));
+        stream.write_timeout = options.socket_write_timeout;
+
         Ok(Self {
             waiting: VecDeque::new(),
             capabilities,
-            server_version: (0, 0, 0),
+            // until the handshake says otherwise
+            server_version: ServerVersion {
+                vendor: ServerVendor::MySql,
+                major: 0,
+                minor: 0,
+                patch: 0,
+            },
+            is_mariadb: false,
             sequence_id: 0,
             collation,
             charset,
-            stream: BufStream::new(MaybeTlsStream::Raw(socket)),
+            stream,
+            observer: options.packet_observer,
+            local_infile: options.local_infile_handler.clone(),
+            redact_sent: true,
+            stats: ConnectionStats::default(),
+            status: Status::empty(),
         })
     }
 
+    // discards the packets that were queued to be sent ahead of the next command, along with
+    // the results they would have returned; only `COM_STMT_CLOSE` has none
+    pub(crate) fn discard_queued(&mut self) {
+        let mut results = 0;
+        let mut continued = false;
+        let mut queued = &self.stream.wbuf[..];
+
+        while queued.len() >= 4 {
+            let len =
+                usize::from(queued[0]) | usize::from(queued[1]) << 8 | usize::from(queued[2]) << 16;
+
+            // the first byte of a command is its type, unless this continues a larger payload
+            let command = queued.get(4).copied();
+
+            if !continued && command.is_some() && command != Some(COM_STMT_CLOSE) {
+                results += 1;
+            }
+
+            continued = len == 0xFF_FF_FF;
+            queued = &queued[(4 + len).min(queued.len())..];
+        }
+
+        self.stream.wbuf.clear();
+
+        for _ in 0..results {
+            self.waiting.pop_back();
+        }
+    }
+
+    // done with the results at the front of `waiting`
+    pub(crate) fn pop_waiting(&mut self) {
+        self.waiting.pop_front();
+
+        if self.waiting.is_empty() {
+            self.stats.finish_queries();
+        }
+    }
+
     pub(crate) async fn wait_until_ready(&mut self) -> Result<(), Error> {
         if !self.stream.wbuf.is_empty() {
             self.stream.flush().await?;
@@ -83,13 +187,13 @@ impl MySqlStream {
             while self.waiting.front() == Some(&Waiting::Row) {
                 let packet = self.recv_packet().await?;
 
-                if packet[0] == 0xfe && packet.len() < 9 {
-                    let eof = packet.eof(self.capabilities)?;
+                if packet.is_eof(self.capabilities) {
+                    let end = self.decode_end_of_rows(packet)?;
 
-                    if eof.status.contains(Status::SERVER_MORE_RESULTS_EXISTS) {
+                    if end.status.contains(Status::SERVER_MORE_RESULTS_EXISTS) {
                         *self.waiting.front_mut().unwrap() = Waiting::Result;
                     } else {
-                        self.waiting.pop_front();
+                        self.pop_waiting();
                     };
                 }
             }
@@ -98,11 +202,16 @@ impl MySqlStream {
                 let packet = self.recv_packet().await?;
 
                 if packet[0] == 0x00 || packet[0] == 0xff {
-                    let ok = packet.ok()?;
+                    let ok = self.decode_ok(packet)?;
 
                     if !ok.status.contains(Status::SERVER_MORE_RESULTS_EXISTS) {
-                        self.waiting.pop_front();
+                        self.pop_waiting();
                     }
+                } else if packet[0] == 0xfb {
+                    // nothing reads the results of this statement anymore, so its file is not
+                    // sent; the server answers the refusal with the result of the statement
+                    self.write_packet(&[][..]);
+                    self.flush().await?;
                 } else {
                     *self.waiting.front_mut().unwrap() = Waiting::Row;
                     self.skip_result_metadata(packet).await?;
@@ -113,6 +222,60 @@ impl MySqlStream {
         Ok(())
     }
 
+    // answers the LOCAL INFILE request of a `LOAD DATA LOCAL INFILE` statement with the
+    // contents of the file, ended by an empty packet, after which the server sends the result
+    // of the statement; without a handler for local files, only the empty packet is sent
+    pub(crate) async fn send_local_infile(&mut self, packet: Packet<Bytes>) -> Result<(), Error> {
+        let request: LocalInfileRequest = packet.decode()?;
+
+        let handler = match &self.local_infile {
+            Some(handler) => handler.clone(),
+
+            None => {
+                self.write_packet(&[][..]);
+                self.flush().await?;
+
+                return Err(Error::Configuration(
+                    format!(
+                        "the server asked for the local file {:?} of a LOAD DATA LOCAL INFILE \
+                         statement, but no local_infile_handler is set",
+                        request.filename
+                    )
+                    .into(),
+                ));
+            }
+        };
+
+        let sent = self.send_file(&handler, &request.filename).await;
+
+        // the empty packet ends the file, even when it could not be read to its end, so that
+        // the server goes on to the result of the statement
+        self.write_packet(&[][..]);
+        self.flush().await?;
+
+        sent
+    }
+
+    async fn send_file(
+        &mut self,
+        handler: &LocalInfileHandler,
+        filename: &str,
+    ) -> Result<(), Error> {
+        let mut file = handler.open(filename).await?;
+        let mut chunk = vec![0; LOCAL_INFILE_CHUNK_SIZE];
+
+        loop {
+            let read = file.read(&mut chunk).await?;
+
+            if read == 0 {
+                return Ok(());
+            }
+
+            self.write_packet(&chunk[..read]);
+            self.flush().await?;
+        }
+    }
+
     pub(crate) async fn send_packet<'en, T>(&mut self, payload: T) -> Result<(), Error>
     where
         T: Encode<'en, Capabilities>,
@@ -126,39 +289,84 @@ impl MySqlStream {
     where
         T: Encode<'en, Capabilities>,
     {
+        let offset = self.stream.wbuf.len();
+
         self.stream
             .write_with(Packet(payload), (self.capabilities, &mut self.sequence_id));
+
+        if let Some(observer) = &self.observer {
+            let packet = &self.stream.wbuf[offset..];
+
+            if self.redact_sent {
+                observer.observe(Direction::Sent, &packet[..packet.len().min(4)]);
+            } else {
+                observer.observe(Direction::Sent, packet);
+            }
+        }
     }
 
     // receive the next packet from the database server
     // may block (async) on more data from the server
     pub(crate) async fn recv_packet(&mut self) -> Result<Packet<Bytes>, Error> {
+        self.recv_frame().await.map(|(_, packet)| packet)
+    }
+
+    // receives the next packet along with its header, as they were received
+    pub(crate) async fn recv_frame(&mut self) -> Result<(Bytes, Packet<Bytes>), Error> {
         // https://dev.mysql.com/doc/dev/mysql-server/8.0.12/page_protocol_basic_packets.html
         // https://mariadb.com/kb/en/library/0-packet/#standard-packet
 
-        let mut header: Bytes = self.stream.read(4).await?;
+        self.stream.ensure_not_busy()?;
+
+        let header: Bytes = self.stream.read(4).await?;
 
-        let packet_size = header.get_uint_le(3) as usize;
-        let sequence_id = header.get_u8();
+        let packet_size = (&header[..]).get_uint_le(3) as usize;
+        let sequence_id = header[3];
 
         self.sequence_id = sequence_id.wrapping_add(1);
 
+        // the header has been consumed; until the payload is read, dropping this future
+        // would leave the stream positioned in the middle of a packet
+        self.stream.busy = true;
         let payload: Bytes = self.stream.read(packet_size).await?;
+        self.stream.busy = false;
+
+        if let Some(observer) = &self.observer {
+            observer.observe_parts(Direction::Received, &header, &payload);
+        }
+
+        // the payload is split off of the read buffer, not copied out of it, so the values of
+        // the rows decoded from it point into the buffer the packet was read into
 
         // TODO: packet compression
         // TODO: packet joining
 
+        // every packet the server sends starts with a header byte, which is looked at
+        // without checking the length of the payload again
+        if payload.is_empty() {
+            return Err(err_protocol!("received an empty packet"));
+        }
+
         if payload[0] == 0xff {
-            self.waiting.pop_front();
+            self.pop_waiting();
+
+            let error = MySqlDatabaseError(ErrPacket::decode_with(payload, self.capabilities)?);
+
+            // ER_SERVER_SHUTDOWN, ER_CONNECTION_KILLED (MariaDB) and
+            // ER_CLIENT_INTERACTION_TIMEOUT (MySQL) are sent right before the server closes
+            // the connection
+            if matches!(error.number(), 1053 | 1927 | 4031) {
+                self.stream.broken = true;
+
+                return Err(Error::closed_with(error));
+            }
 
             // instead of letting this packet be looked at everywhere, we check here
             // and emit a proper Error
-            return Err(
-                MySqlDatabaseError(ErrPacket::decode_with(payload, self.capabilities)?).into(),
-            );
+            return Err(error.into());
         }
 
-        Ok(Packet(payload))
+        Ok((header, Packet(payload)))
     }
 
     pub(crate) async fn recv<'de, T>(&mut self) -> Result<T, Error>
@@ -169,25 +377,61 @@ impl MySqlStream {
     }
 
     pub(crate) async fn recv_ok(&mut self) -> Result<OkPacket, Error> {
-        self.recv_packet().await?.ok()
+        let packet = self.recv_packet().await?;
+
+        self.decode_ok(packet)
     }
 
-    pub(crate) async fn maybe_recv_eof(&mut self) -> Result<Option<EofPacket>, Error> {
-        if self.capabilities.contains(Capabilities::DEPRECATE_EOF) {
-            Ok(None)
-        } else {
-            self.recv().await.map(Some)
-        }
+    // decodes an OK packet, keeping its status
+    pub(crate) fn decode_ok(&mut self, packet: Packet<Bytes>) -> Result<OkPacket, Error> {
+        let ok = packet.ok()?;
+        self.status = ok.status;
+
+        Ok(ok)
+    }
+
+    // decodes the packet that ends the rows of a result set, keeping its status
+    pub(crate) fn decode_end_of_rows(&mut self, packet: Packet<Bytes>) -> Result<OkPacket, Error> {
+        let end = packet.end_of_rows(self.capabilities)?;
+        self.status = end.status;
+
+        Ok(end)
     }
 
-    async fn skip_result_metadata(&mut self, mut packet: Packet<Bytes>) -> Result<(), Error> {
-        let num_columns: u64 = packet.get_uint_lenenc(); // column count
+    // receives `count` column (or parameter) definitions along with the EOF packet that follows
+    // them, unless EOF packets are deprecated on this connection
+    pub(crate) async fn recv_column_defs(
+        &mut self,
+        count: usize,
+    ) -> Result<Vec<ColumnDefinition>, Error> {
+        let mut defs = Vec::with_capacity(count);
+
+        for _ in 0..count {
+            defs.push(self.recv().await?);
+        }
+
+        // the server sends no EOF after an empty list of definitions
+        if count > 0 && !self.capabilities.contains(Capabilities::DEPRECATE_EOF) {
+            let packet = self.recv_packet().await?;
 
-        for _ in 0..num_columns {
-            let _ = self.recv_packet().await?;
+            if !packet.is_eof(self.capabilities) {
+                return Err(err_protocol!(
+                    "expected an EOF packet after the column definitions but received 0x{:02x}",
+                    packet[0]
+                ));
+            }
         }
 
-        self.maybe_recv_eof().await?;
+        Ok(defs)
+    }
+
+    pub(super) async fn skip_result_metadata(
+        &mut self,
+        mut packet: Packet<Bytes>,
+    ) -> Result<(), Error> {
+        let num_columns: u64 = packet.get_uint_lenenc()?; // column count
+
+        self.recv_column_defs(num_columns as usize).await?;
 
         Ok(())
     }
@@ -206,3 +450,78 @@ impl DerefMut for MySqlStream {
         &mut self.stream
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::counting_alloc::allocations;
+
+    #[test]
+    fn it_reserves_a_large_packet_at_once() {
+        let payload: Vec<u8> = (0..1_000_000_u32).map(|i| i as u8).collect();
+
+        let mut data = (payload.len() as u32).to_le_bytes()[..3].to_vec();
+        data.push(0);
+        data.extend_from_slice(&payload);
+
+        // the header over three reads, then the payload in reads of odd sizes
+        let mut chunks = vec![&data[..1], &data[1..2], &data[2..5]];
+        chunks.extend(data[5..].chunks(4093));
+
+        let mut stream = MySqlStream::scripted(Capabilities::CLIENT_SUPPORTED, chunks);
+
+        let (packet, allocations) =
+            allocations(|| sqlx_rt::block_on(stream.recv_packet()).unwrap());
+
+        assert_eq!(packet.0, payload);
+
+        // the read buffer grows to the declared length once, rather than with every read
+        assert!(allocations < 10, "{} allocations", allocations);
+    }
+
+    #[test]
+    fn it_decodes_packets_in_place_in_the_read_buffer() {
+        let mut data = Vec::new();
+
+        for payload in [&b"\x01first"[..], &b"\x01second"[..]] {
+            data.extend_from_slice(&(payload.len() as u32).to_le_bytes()[..3]);
+            data.push(0);
+            data.extend_from_slice(payload);
+        }
+
+        let mut stream = MySqlStream::scripted(Capabilities::CLIENT_SUPPORTED, vec![data]);
+
+        let first = sqlx_rt::block_on(stream.recv_packet()).unwrap();
+        let second = sqlx_rt::block_on(stream.recv_packet()).unwrap();
+
+        assert_eq!(&first.0[..], b"\x01first");
+        assert_eq!(&second.0[..], b"\x01second");
+
+        // both payloads are views of the read buffer, the second right after the first and the
+        // header of the second
+        let first_end = first.0.as_ptr() as usize + first.0.len();
+
+        assert_eq!(second.0.as_ptr() as usize, first_end + 4);
+    }
+
+    #[test]
+    fn it_starts_outside_of_a_transaction_offering_only_the_supported_capabilities() {
+        let socket = Socket::Scripted(crate::io::scripted::ScriptedStream::new(
+            Vec::<Vec<u8>>::new(),
+        ));
+        let stream = MySqlStream::with_socket(&MySqlConnectOptions::new(), socket).unwrap();
+
+        // no status until the server sends an OK or EOF packet
+        assert!(stream.status.is_empty());
+        assert!(!stream.status.contains(Status::SERVER_STATUS_IN_TRANS));
+
+        // what the client offers in the handshake, without a database to connect to; no flag is
+        // assumed of the server before it is negotiated
+        assert_eq!(
+            stream.capabilities,
+            Capabilities::CLIENT_SUPPORTED - Capabilities::CONNECT_WITH_DB
+        );
+        assert!(!stream.capabilities.contains(Capabilities::MYSQL));
+        assert!(!stream.capabilities.contains(Capabilities::LOCAL_FILES));
+    }
+}
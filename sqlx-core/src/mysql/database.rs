@@ -23,6 +23,8 @@ impl Database for MySql {
     type TypeInfo = MySqlTypeInfo;
 
     type Value = MySqlValue;
+
+    const DB_SYSTEM: &'static str = "mysql";
 }
 
 impl<'r> HasValueRef<'r> for MySql {
@@ -76,4 +76,16 @@ impl DatabaseError for MySqlDatabaseError {
     fn into_error(self: Box<Self>) -> Box<dyn Error + Send + Sync + 'static> {
         self
     }
+
+    fn is_connection_limit(&self) -> bool {
+        match self.number() {
+            // ER_CON_COUNT_ERROR and ER_TOO_MANY_USER_CONNECTIONS
+            1040 | 1203 => true,
+
+            // ER_USER_LIMIT_REACHED, which is also returned for the other resources of a user
+            1226 => self.message().contains("max_user_connections"),
+
+            _ => false,
+        }
+    }
 }
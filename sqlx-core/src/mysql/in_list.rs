@@ -0,0 +1,246 @@
+use std::borrow::Cow;
+
+use crate::encode::Encode;
+use crate::error::Error;
+use crate::mysql::placeholders::placeholders;
+use crate::mysql::{MySql, MySqlArguments};
+use crate::query::Query;
+use crate::query_as::QueryAs;
+use crate::query_scalar::QueryScalar;
+use crate::types::Type;
+
+// the number of parameters of a prepared statement is sent as an unsigned 16-bit integer
+const MAX_PARAMETERS: usize = u16::MAX as usize;
+
+// a subquery without rows; `x IN (..)` is `FALSE` and `x NOT IN (..)` is `TRUE` for it
+const EMPTY_LIST: &str = "SELECT NULL FROM DUAL WHERE FALSE";
+
+impl<'q> Query<'q, MySql, MySqlArguments> {
+    /// Bind a list of values to a single `?` of this SQL query, typically the `?` of
+    /// `IN (?)`.
+    ///
+    /// When the query is executed, the `?` is replaced by one placeholder per value
+    /// (`IN (?, ?, ?)`) and the values are bound to them. Placeholders in string literals,
+    /// quoted identifiers and comments are not counted.
+    ///
+    /// An empty list replaces the `?` by a subquery that returns no rows, so `IN (?)` matches
+    /// no row and `NOT IN (?)` matches every row. See [`bind_list_or`](Query::bind_list_or) to
+    /// replace it by something else.
+    ///
+    /// Every value counts towards the limit of 65535 parameters of a statement. Executing a
+    /// query with more parameters than that returns [`Error::InvalidArguments`].
+    ///
+    /// ```rust,no_run
+    /// # async fn f(conn: &mut sqlx_core::mysql::MySqlConnection) -> Result<(), sqlx_core::error::Error> {
+    /// let ids: Vec<i64> = vec![1, 2, 3];
+    ///
+    /// let rows = sqlx_core::query::query("SELECT name FROM users WHERE id IN (?) AND active = ?")
+    ///     .bind_list(&ids)
+    ///     .bind(true)
+    ///     .fetch_all(conn)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn bind_list<I>(self, values: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: 'q + Send + Encode<'q, MySql> + Type<MySql>,
+    {
+        self.bind_list_or(values, EMPTY_LIST)
+    }
+
+    /// Bind a list of values to a single `?` of this SQL query, replacing the `?` by `if_empty`
+    /// when the list is empty.
+    ///
+    /// See [`bind_list`](Query::bind_list).
+    pub fn bind_list_or<I>(mut self, values: I, if_empty: &'static str) -> Self
+    where
+        I: IntoIterator,
+        I::Item: 'q + Send + Encode<'q, MySql> + Type<MySql>,
+    {
+        if let Some(arguments) = &mut self.arguments {
+            arguments.add_list(values, if_empty);
+        }
+
+        self
+    }
+}
+
+impl<'q, O> QueryAs<'q, MySql, O, MySqlArguments> {
+    /// Bind a list of values to a single `?` of this SQL query.
+    ///
+    /// See [`Query::bind_list`](Query::bind_list).
+    pub fn bind_list<I>(mut self, values: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: 'q + Send + Encode<'q, MySql> + Type<MySql>,
+    {
+        self.inner = self.inner.bind_list(values);
+        self
+    }
+
+    /// Bind a list of values to a single `?` of this SQL query, replacing the `?` by `if_empty`
+    /// when the list is empty.
+    ///
+    /// See [`Query::bind_list_or`](Query::bind_list_or).
+    pub fn bind_list_or<I>(mut self, values: I, if_empty: &'static str) -> Self
+    where
+        I: IntoIterator,
+        I::Item: 'q + Send + Encode<'q, MySql> + Type<MySql>,
+    {
+        self.inner = self.inner.bind_list_or(values, if_empty);
+        self
+    }
+}
+
+impl<'q, O> QueryScalar<'q, MySql, O, MySqlArguments> {
+    /// Bind a list of values to a single `?` of this SQL query.
+    ///
+    /// See [`Query::bind_list`](Query::bind_list).
+    pub fn bind_list<I>(mut self, values: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: 'q + Send + Encode<'q, MySql> + Type<MySql>,
+    {
+        self.inner = self.inner.bind_list(values);
+        self
+    }
+
+    /// Bind a list of values to a single `?` of this SQL query, replacing the `?` by `if_empty`
+    /// when the list is empty.
+    ///
+    /// See [`Query::bind_list_or`](Query::bind_list_or).
+    pub fn bind_list_or<I>(mut self, values: I, if_empty: &'static str) -> Self
+    where
+        I: IntoIterator,
+        I::Item: 'q + Send + Encode<'q, MySql> + Type<MySql>,
+    {
+        self.inner = self.inner.bind_list_or(values, if_empty);
+        self
+    }
+}
+
+// replaces each `?` a list is bound to by a placeholder for every value of the list
+pub(crate) fn expand_lists<'q>(
+    sql: &'q str,
+    arguments: &MySqlArguments,
+) -> Result<Cow<'q, str>, Error> {
+    if arguments.lists.is_empty() {
+        return Ok(Cow::Borrowed(sql));
+    }
+
+    if arguments.types.len() > MAX_PARAMETERS {
+        return Err(Error::InvalidArguments(format!(
+            "query has {} parameters after expanding its lists, but MySQL allows at most {}; \
+             bind the values in smaller batches or insert them into a temporary table and join against it",
+            arguments.types.len(),
+            MAX_PARAMETERS
+        )));
+    }
+
+    let placeholders = placeholders(sql);
+    let mut expanded = String::with_capacity(sql.len() + arguments.types.len() * 3);
+    let mut last = 0;
+
+    for list in &arguments.lists {
+        let offset = *placeholders.get(list.placeholder).ok_or_else(|| {
+            Error::InvalidArguments(format!(
+                "a list was bound to parameter {} but the query only has {} parameters",
+                list.placeholder + 1,
+                placeholders.len()
+            ))
+        })?;
+
+        expanded.push_str(&sql[last..offset]);
+
+        if list.len == 0 {
+            expanded.push_str(list.if_empty);
+        } else {
+            for i in 0..list.len {
+                if i > 0 {
+                    expanded.push_str(", ");
+                }
+
+                expanded.push('?');
+            }
+        }
+
+        last = offset + 1;
+    }
+
+    expanded.push_str(&sql[last..]);
+
+    Ok(Cow::Owned(expanded))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand(sql: &str, lists: &[&[i32]]) -> Result<String, Error> {
+        let mut arguments = MySqlArguments::default();
+
+        for list in lists {
+            arguments.add_list(list.iter().copied(), EMPTY_LIST);
+        }
+
+        expand_lists(sql, &arguments).map(Cow::into_owned)
+    }
+
+    #[test]
+    fn it_expands_lists() {
+        assert_eq!(
+            expand("SELECT * FROM t WHERE id IN (?)", &[&[1]]).unwrap(),
+            "SELECT * FROM t WHERE id IN (?)"
+        );
+
+        assert_eq!(
+            expand(
+                "SELECT * FROM t WHERE id IN (?) AND '?' <> ?",
+                &[&[1, 2, 3]]
+            )
+            .unwrap(),
+            "SELECT * FROM t WHERE id IN (?, ?, ?) AND '?' <> ?"
+        );
+
+        assert_eq!(
+            expand(
+                "SELECT * FROM t WHERE a IN (?) OR b NOT IN (?)",
+                &[&[], &[1, 2]]
+            )
+            .unwrap(),
+            "SELECT * FROM t WHERE a IN (SELECT NULL FROM DUAL WHERE FALSE) OR b NOT IN (?, ?)"
+        );
+    }
+
+    #[test]
+    fn it_counts_lists_after_plain_parameters() {
+        let mut arguments = MySqlArguments::default();
+
+        arguments.add(10_i32);
+        arguments.add_list(vec![1_i32, 2], EMPTY_LIST);
+        arguments.add(20_i32);
+        arguments.add_list(Vec::<i32>::new(), "NULL");
+
+        assert_eq!(
+            expand_lists("SELECT ? + 1 IN (?) AND ? IN (?)", &arguments).unwrap(),
+            "SELECT ? + 1 IN (?, ?) AND ? IN (NULL)"
+        );
+    }
+
+    #[test]
+    fn it_rejects_too_many_parameters() {
+        let values: Vec<i32> = (0..70_000).collect();
+
+        assert!(expand("SELECT * FROM t WHERE id IN (?)", &[&values[..1000]]).is_ok());
+        assert!(matches!(
+            expand("SELECT * FROM t WHERE id IN (?)", &[&values]),
+            Err(Error::InvalidArguments(_))
+        ));
+        assert!(matches!(
+            expand("SELECT * FROM t", &[&[1]]),
+            Err(Error::InvalidArguments(_))
+        ));
+    }
+}
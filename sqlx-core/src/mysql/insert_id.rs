@@ -0,0 +1,38 @@
+use crate::error::Error;
+use crate::executor::Executor;
+use crate::mysql::{MySql, MySqlArguments};
+use crate::query::Query;
+
+impl<'q> Query<'q, MySql, MySqlArguments> {
+    /// Execute this `INSERT` and return the `AUTO_INCREMENT` id of the row it inserted.
+    ///
+    /// This is [`execute`](Query::execute) followed by
+    /// [`MySqlQueryResult::last_insert_id`](crate::mysql::MySqlQueryResult::last_insert_id). For
+    /// an `INSERT` of several rows, the id of the first row is returned, and the others follow
+    /// it in order of insertion. An `INSERT` that did not insert a row returns
+    /// [`Error::RowNotFound`], and one into a table without an `AUTO_INCREMENT` column returns
+    /// `0`.
+    ///
+    /// ```rust,no_run
+    /// # async fn f(conn: &mut sqlx_core::mysql::MySqlConnection) -> Result<(), sqlx_core::error::Error> {
+    /// let id = sqlx_core::query::query("INSERT INTO users (name) VALUES (?)")
+    ///     .bind("Herp Derpinson")
+    ///     .insert_returning_id(conn)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn insert_returning_id<'e, 'c: 'e, E>(self, executor: E) -> Result<u64, Error>
+    where
+        'q: 'e,
+        E: 'e + Executor<'c, Database = MySql>,
+    {
+        let result = self.execute(executor).await?;
+
+        if result.rows_affected() == 0 {
+            return Err(Error::RowNotFound);
+        }
+
+        Ok(result.last_insert_id())
+    }
+}
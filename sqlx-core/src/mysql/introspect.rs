@@ -0,0 +1,145 @@
+use futures_core::future::BoxFuture;
+
+use crate::column::Column;
+use crate::error::Error;
+use crate::executor::Executor;
+use crate::introspect::{private_introspect, ColumnInfo, Introspect, TableInfo};
+use crate::mysql::{MySql, MySqlConnection};
+use crate::query_as::query_as;
+
+impl private_introspect::Sealed for MySqlConnection {}
+
+impl Introspect for MySqlConnection {
+    fn tables<'e>(
+        &'e mut self,
+        schema: Option<&'e str>,
+    ) -> BoxFuture<'e, Result<Vec<TableInfo>, Error>> {
+        Box::pin(async move {
+            let schema = schema.map(unquote);
+
+            let tables: Vec<(String, String)> = query_as(
+                r#"
+SELECT TABLE_SCHEMA, TABLE_NAME
+FROM information_schema.TABLES
+WHERE TABLE_SCHEMA = COALESCE(?, DATABASE())
+  AND TABLE_TYPE = 'BASE TABLE'
+ORDER BY TABLE_NAME
+                "#,
+            )
+            .bind(schema)
+            .fetch_all(&mut *self)
+            .await?;
+
+            Ok(tables
+                .into_iter()
+                .map(|(schema, name)| TableInfo { schema, name })
+                .collect())
+        })
+    }
+
+    fn columns<'e>(
+        &'e mut self,
+        table: &'e str,
+    ) -> BoxFuture<'e, Result<Vec<ColumnInfo<MySql>>, Error>> {
+        Box::pin(async move {
+            let (schema, table) = split_qualified(table);
+
+            let rows: Vec<(String, bool, Option<String>, bool)> = query_as(
+                r#"
+SELECT COLUMN_NAME, IS_NULLABLE = 'YES', COLUMN_DEFAULT, COLUMN_KEY = 'PRI'
+FROM information_schema.COLUMNS
+WHERE TABLE_SCHEMA = COALESCE(?, DATABASE())
+  AND TABLE_NAME = ?
+ORDER BY ORDINAL_POSITION
+                "#,
+            )
+            .bind(schema.as_deref())
+            .bind(table.as_str())
+            .fetch_all(&mut *self)
+            .await?;
+
+            if rows.is_empty() {
+                return Ok(Vec::new());
+            }
+
+            // the catalog only has the name of the type, so have the server describe the
+            // columns for their full type information
+            let sql = match schema {
+                Some(schema) => format!("SELECT * FROM {}.{}", quote(&schema), quote(&table)),
+                None => format!("SELECT * FROM {}", quote(&table)),
+            };
+
+            let describe = (&mut *self).describe(&sql).await?;
+
+            rows.into_iter()
+                .map(|(name, nullable, default, is_primary_key)| {
+                    let type_info = describe
+                        .columns()
+                        .iter()
+                        .find(|column| column.name() == name)
+                        .map(|column| column.type_info().clone())
+                        .ok_or_else(|| Error::ColumnNotFound(name.clone()))?;
+
+                    Ok(ColumnInfo {
+                        name,
+                        type_info,
+                        nullable,
+                        default,
+                        is_primary_key,
+                    })
+                })
+                .collect()
+        })
+    }
+
+    fn quote_identifier(name: &str) -> String {
+        quote(name)
+    }
+}
+
+// splits `schema.table` into its parts, each of which may be quoted with backticks
+fn split_qualified(name: &str) -> (Option<String>, String) {
+    let mut quoted = false;
+
+    for (i, c) in name.char_indices() {
+        match c {
+            '`' => quoted = !quoted,
+            '.' if !quoted => return (Some(unquote(&name[..i])), unquote(&name[i + 1..])),
+            _ => {}
+        }
+    }
+
+    (None, unquote(name))
+}
+
+fn unquote(name: &str) -> String {
+    if name.len() >= 2 && name.starts_with('`') && name.ends_with('`') {
+        name[1..name.len() - 1].replace("``", "`")
+    } else {
+        name.to_owned()
+    }
+}
+
+fn quote(name: &str) -> String {
+    format!("`{}`", name.replace('`', "``"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::split_qualified;
+
+    #[test]
+    fn it_splits_qualified_names() {
+        assert_eq!(split_qualified("tweet"), (None, "tweet".to_owned()));
+
+        assert_eq!(
+            split_qualified("sqlx.tweet"),
+            (Some("sqlx".to_owned()), "tweet".to_owned())
+        );
+
+        assert_eq!(
+            split_qualified("`my.db`.`my``table`"),
+            (Some("my.db".to_owned()), "my`table".to_owned())
+        );
+    }
+}
@@ -3,38 +3,142 @@ use bytes::{Buf, Bytes};
 use crate::error::Error;
 use crate::io::BufExt;
 
+// Every method returns an error rather than panic when the packet ends before the value does, so
+// that a truncated or malformed packet from the server cannot bring down the client.
 pub trait MySqlBufExt: Buf {
     // Read a length-encoded integer.
     // NOTE: 0xfb or NULL is only returned for binary value encoding to indicate NULL.
     // NOTE: 0xff is only returned during a result set to indicate ERR.
     // <https://dev.mysql.com/doc/internals/en/integer.html#packet-Protocol::LengthEncodedInteger>
-    fn get_uint_lenenc(&mut self) -> u64;
+    fn get_uint_lenenc(&mut self) -> Result<u64, Error>;
 
     // Read a length-encoded string.
     fn get_str_lenenc(&mut self) -> Result<String, Error>;
 
     // Read a length-encoded byte sequence.
-    fn get_bytes_lenenc(&mut self) -> Bytes;
+    fn get_bytes_lenenc(&mut self) -> Result<Bytes, Error>;
+
+    // Read a byte sequence of the exact length.
+    fn try_get_bytes(&mut self, len: usize) -> Result<Bytes, Error>;
+
+    // Skip over the exact number of bytes.
+    fn try_advance(&mut self, len: usize) -> Result<(), Error>;
+
+    fn try_get_u8(&mut self) -> Result<u8, Error>;
+
+    fn try_get_u16_le(&mut self) -> Result<u16, Error>;
+
+    fn try_get_u32_le(&mut self) -> Result<u32, Error>;
+
+    fn try_get_u64_le(&mut self) -> Result<u64, Error>;
+
+    fn try_get_uint_le(&mut self, len: usize) -> Result<u64, Error>;
 }
 
 impl MySqlBufExt for Bytes {
-    fn get_uint_lenenc(&mut self) -> u64 {
-        match self.get_u8() {
-            0xfc => u64::from(self.get_u16_le()),
-            0xfd => self.get_uint_le(3),
-            0xfe => self.get_u64_le(),
+    fn get_uint_lenenc(&mut self) -> Result<u64, Error> {
+        Ok(match self.try_get_u8()? {
+            0xfc => u64::from(self.try_get_u16_le()?),
+            0xfd => self.try_get_uint_le(3)?,
+            0xfe => self.try_get_u64_le()?,
 
             v => u64::from(v),
-        }
+        })
     }
 
     fn get_str_lenenc(&mut self) -> Result<String, Error> {
-        let size = self.get_uint_lenenc();
+        let size = self.get_uint_lenenc()?;
+        ensure_remaining(self, size)?;
+
         self.get_str(size as usize)
     }
 
-    fn get_bytes_lenenc(&mut self) -> Bytes {
-        let size = self.get_uint_lenenc();
-        self.split_to(size as usize)
+    fn get_bytes_lenenc(&mut self) -> Result<Bytes, Error> {
+        let size = self.get_uint_lenenc()?;
+        ensure_remaining(self, size)?;
+
+        Ok(self.split_to(size as usize))
+    }
+
+    fn try_get_bytes(&mut self, len: usize) -> Result<Bytes, Error> {
+        ensure_remaining(self, len as u64)?;
+
+        Ok(self.split_to(len))
+    }
+
+    fn try_advance(&mut self, len: usize) -> Result<(), Error> {
+        ensure_remaining(self, len as u64)?;
+
+        self.advance(len);
+
+        Ok(())
+    }
+
+    fn try_get_u8(&mut self) -> Result<u8, Error> {
+        ensure_remaining(self, 1)?;
+
+        Ok(self.get_u8())
+    }
+
+    fn try_get_u16_le(&mut self) -> Result<u16, Error> {
+        ensure_remaining(self, 2)?;
+
+        Ok(self.get_u16_le())
+    }
+
+    fn try_get_u32_le(&mut self) -> Result<u32, Error> {
+        ensure_remaining(self, 4)?;
+
+        Ok(self.get_u32_le())
     }
+
+    fn try_get_u64_le(&mut self) -> Result<u64, Error> {
+        ensure_remaining(self, 8)?;
+
+        Ok(self.get_u64_le())
+    }
+
+    fn try_get_uint_le(&mut self, len: usize) -> Result<u64, Error> {
+        ensure_remaining(self, len as u64)?;
+
+        Ok(self.get_uint_le(len))
+    }
+}
+
+fn ensure_remaining(buf: &Bytes, len: u64) -> Result<(), Error> {
+    if (buf.len() as u64) < len {
+        return Err(err_protocol!(
+            "unexpected end of packet: expected {} more bytes but found {}",
+            len,
+            buf.len()
+        ));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_get_uint_lenenc_truncated() {
+    for data in [
+        &b""[..],
+        b"\xfc\x01",
+        b"\xfd\x01\x02",
+        b"\xfe\x01\x02\x03\x04",
+    ] {
+        assert!(Bytes::from_static(data).get_uint_lenenc().is_err());
+    }
+
+    assert_eq!(
+        Bytes::from_static(b"\xfc\x01\x02")
+            .get_uint_lenenc()
+            .unwrap(),
+        0x0201
+    );
+}
+
+#[test]
+fn test_get_bytes_lenenc_truncated() {
+    let mut buf = Bytes::from_static(b"\x05hel");
+
+    assert!(buf.get_bytes_lenenc().is_err());
 }
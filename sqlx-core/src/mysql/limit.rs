@@ -0,0 +1,74 @@
+use crate::mysql::{MySql, MySqlArguments};
+use crate::query::Query;
+use crate::query_as::QueryAs;
+use crate::query_scalar::QueryScalar;
+
+impl<'q> Query<'q, MySql, MySqlArguments> {
+    /// Bind the number of rows of the `?` of `LIMIT ?` of this SQL query.
+    ///
+    /// `n` is bound as a `BIGINT UNSIGNED`, which MySQL and MariaDB accept for `LIMIT` and
+    /// `OFFSET` as a query with bound values is always run as a prepared statement. Queries that
+    /// only differ in the number of rows they fetch share one prepared statement.
+    ///
+    /// ```rust,no_run
+    /// # async fn f(conn: &mut sqlx_core::mysql::MySqlConnection) -> Result<(), sqlx_core::error::Error> {
+    /// let rows = sqlx_core::query::query("SELECT name FROM users WHERE active = ? LIMIT ? OFFSET ?")
+    ///     .bind(true)
+    ///     .bind_limit(20)
+    ///     .bind_offset(40)
+    ///     .fetch_all(conn)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn bind_limit(mut self, n: u64) -> Self {
+        if let Some(arguments) = &mut self.arguments {
+            arguments.add(n);
+        }
+
+        self
+    }
+
+    /// Bind the number of rows to skip of the `?` of `OFFSET ?` of this SQL query.
+    ///
+    /// See [`bind_limit`](Query::bind_limit).
+    pub fn bind_offset(self, n: u64) -> Self {
+        self.bind_limit(n)
+    }
+}
+
+impl<'q, O> QueryAs<'q, MySql, O, MySqlArguments> {
+    /// Bind the number of rows of the `?` of `LIMIT ?` of this SQL query.
+    ///
+    /// See [`Query::bind_limit`](Query::bind_limit).
+    pub fn bind_limit(mut self, n: u64) -> Self {
+        self.inner = self.inner.bind_limit(n);
+        self
+    }
+
+    /// Bind the number of rows to skip of the `?` of `OFFSET ?` of this SQL query.
+    ///
+    /// See [`Query::bind_offset`](Query::bind_offset).
+    pub fn bind_offset(mut self, n: u64) -> Self {
+        self.inner = self.inner.bind_offset(n);
+        self
+    }
+}
+
+impl<'q, O> QueryScalar<'q, MySql, O, MySqlArguments> {
+    /// Bind the number of rows of the `?` of `LIMIT ?` of this SQL query.
+    ///
+    /// See [`Query::bind_limit`](Query::bind_limit).
+    pub fn bind_limit(mut self, n: u64) -> Self {
+        self.inner = self.inner.bind_limit(n);
+        self
+    }
+
+    /// Bind the number of rows to skip of the `?` of `OFFSET ?` of this SQL query.
+    ///
+    /// See [`Query::bind_offset`](Query::bind_offset).
+    pub fn bind_offset(mut self, n: u64) -> Self {
+        self.inner = self.inner.bind_offset(n);
+        self
+    }
+}
@@ -1,8 +1,9 @@
 use crate::connection::ConnectOptions;
 use crate::error::Error;
 use crate::executor::Executor;
+use crate::migrate::split::{split_statements, Dialect};
 use crate::migrate::MigrateError;
-use crate::migrate::{AppliedMigration, Migration};
+use crate::migrate::{AppliedMigration, Migration, MigrationEvent};
 use crate::migrate::{Migrate, MigrateDatabase};
 use crate::mysql::{MySql, MySqlConnectOptions, MySqlConnection};
 use crate::query::query;
@@ -208,32 +209,15 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
         &'e mut self,
         migration: &'m Migration,
     ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
-        Box::pin(async move {
-            let start = Instant::now();
-
-            let res = self.execute(&*migration.sql).await;
-
-            let elapsed = start.elapsed();
-
-            // language=MySQL
-            let _ = query(
-                r#"
-    INSERT INTO _sqlx_migrations ( version, description, success, checksum, execution_time )
-    VALUES ( ?, ?, ?, ?, ? )
-                "#,
-            )
-            .bind(migration.version)
-            .bind(&*migration.description)
-            .bind(res.is_ok())
-            .bind(&*migration.checksum)
-            .bind(elapsed.as_nanos() as i64)
-            .execute(self)
-            .await?;
-
-            res?;
+        Box::pin(apply(self, migration, None))
+    }
 
-            Ok(elapsed)
-        })
+    fn apply_with_progress<'e: 'm, 'm>(
+        &'e mut self,
+        migration: &'m Migration,
+        progress: &'m mut (dyn FnMut(MigrationEvent) + Send),
+    ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
+        Box::pin(apply(self, migration, Some(progress)))
     }
 
     fn revert<'e: 'm, 'm>(
@@ -272,3 +256,59 @@ fn generate_lock_id(database_name: &str) -> String {
         0x3d32ad9e * (CRC_IEEE.checksum(database_name.as_bytes()) as i64)
     )
 }
+
+async fn apply(
+    conn: &mut MySqlConnection,
+    migration: &Migration,
+    progress: Option<&mut (dyn FnMut(MigrationEvent) + Send)>,
+) -> Result<Duration, MigrateError> {
+    let start = Instant::now();
+
+    let res = match progress {
+        Some(progress) => execute_statements(conn, &migration.sql, progress).await,
+        None => conn.execute(&*migration.sql).await.map(drop),
+    };
+
+    let elapsed = start.elapsed();
+
+    // language=MySQL
+    let _ = query(
+        r#"
+    INSERT INTO _sqlx_migrations ( version, description, success, checksum, execution_time )
+    VALUES ( ?, ?, ?, ?, ? )
+        "#,
+    )
+    .bind(migration.version)
+    .bind(&*migration.description)
+    .bind(res.is_ok())
+    .bind(&*migration.checksum)
+    .bind(elapsed.as_nanos() as i64)
+    .execute(conn)
+    .await?;
+
+    res?;
+
+    Ok(elapsed)
+}
+
+// executes the statements of a migration one at a time, or all at once if it cannot be split
+async fn execute_statements(
+    conn: &mut MySqlConnection,
+    sql: &str,
+    progress: &mut (dyn FnMut(MigrationEvent) + Send),
+) -> Result<(), Error> {
+    let statements = split_statements(sql, Dialect::MySql).unwrap_or_else(|| vec![sql]);
+
+    for (index, statement) in statements.into_iter().enumerate() {
+        let start = Instant::now();
+        let done = conn.execute(statement).await?;
+
+        progress(MigrationEvent::StatementCompleted {
+            index,
+            elapsed: start.elapsed(),
+            rows_affected: done.rows_affected(),
+        });
+    }
+
+    Ok(())
+}
@@ -8,26 +8,38 @@ mod column;
 mod connection;
 mod database;
 mod error;
+mod in_list;
+mod insert_id;
 mod io;
+mod limit;
+mod named;
 mod options;
+mod placeholders;
 mod protocol;
 mod query_result;
 mod row;
+mod server_timeout;
+mod server_version;
 mod statement;
 mod transaction;
 mod type_info;
+mod type_mapping;
 pub mod types;
 mod value;
 
+#[cfg(feature = "introspect")]
+mod introspect;
+
 #[cfg(feature = "migrate")]
 mod migrate;
 
 pub use arguments::MySqlArguments;
 pub use column::MySqlColumn;
-pub use connection::MySqlConnection;
+pub use connection::{MySqlConnection, MySqlCursor};
 pub use database::MySql;
 pub use error::MySqlDatabaseError;
 pub use options::{MySqlConnectOptions, MySqlSslMode};
+pub use protocol::Capabilities as MySqlCapabilities;
 pub use query_result::MySqlQueryResult;
 pub use row::MySqlRow;
 pub use statement::MySqlStatement;
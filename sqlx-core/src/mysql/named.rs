@@ -0,0 +1,159 @@
+use std::borrow::Cow;
+
+use crate::encode::Encode;
+use crate::error::Error;
+use crate::mysql::placeholders::named_parameters;
+use crate::mysql::{MySql, MySqlArguments};
+use crate::named::check_names;
+use crate::query::Query;
+use crate::query_as::QueryAs;
+use crate::query_scalar::QueryScalar;
+use crate::types::Type;
+
+impl<'q> Query<'q, MySql, MySqlArguments> {
+    /// Bind a value to every `:name` parameter of this SQL query made with
+    /// [`query_named`](crate::query::query_named).
+    ///
+    /// The parameters are replaced by `?`, and the value is sent once for every appearance of
+    /// its name.
+    pub fn bind_named<T>(mut self, name: &str, value: T) -> Self
+    where
+        T: 'q + Send + Encode<'q, MySql> + Type<MySql>,
+    {
+        if let Some(arguments) = &mut self.arguments {
+            arguments.add_named(name, value);
+        }
+
+        self
+    }
+}
+
+impl<'q, O> QueryAs<'q, MySql, O, MySqlArguments> {
+    /// Bind a value to every `:name` parameter of this SQL query.
+    ///
+    /// See [`Query::bind_named`](Query::bind_named).
+    pub fn bind_named<T>(mut self, name: &str, value: T) -> Self
+    where
+        T: 'q + Send + Encode<'q, MySql> + Type<MySql>,
+    {
+        self.inner = self.inner.bind_named(name, value);
+        self
+    }
+}
+
+impl<'q, O> QueryScalar<'q, MySql, O, MySqlArguments> {
+    /// Bind a value to every `:name` parameter of this SQL query.
+    ///
+    /// See [`Query::bind_named`](Query::bind_named).
+    pub fn bind_named<T>(mut self, name: &str, value: T) -> Self
+    where
+        T: 'q + Send + Encode<'q, MySql> + Type<MySql>,
+    {
+        self.inner = self.inner.bind_named(name, value);
+        self
+    }
+}
+
+// replaces each `:name` parameter by a `?`, and the values bound by name by one value for each
+// `?`, in order
+pub(crate) fn replace_names<'q>(
+    sql: &'q str,
+    arguments: &mut MySqlArguments,
+) -> Result<Cow<'q, str>, Error> {
+    let named = match arguments.named.take() {
+        Some(named) => named,
+        None => return Ok(Cow::Borrowed(sql)),
+    };
+
+    if named.len() != arguments.types.len() || !arguments.lists.is_empty() {
+        return Err(Error::InvalidArguments(
+            "values cannot be bound both by name and by position to the same query".into(),
+        ));
+    }
+
+    let names: Vec<&str> = named.iter().map(|(name, _)| name.as_str()).collect();
+    let parameters = named_parameters(sql);
+
+    check_names(parameters.iter().map(|name| &sql[name.clone()]), &names)?;
+
+    let mut replaced = String::with_capacity(sql.len());
+    let mut values = MySqlArguments {
+        server_timeout: arguments.server_timeout,
+        ..MySqlArguments::default()
+    };
+
+    let mut last = 0;
+
+    for name in parameters {
+        // every name was checked to be bound above
+        let index = names.iter().position(|&n| n == &sql[name.clone()]).unwrap();
+
+        values.add_copy(arguments, index, named[index].1.clone());
+
+        // the parameter starts at the `:` before its name
+        replaced.push_str(&sql[last..name.start - 1]);
+        replaced.push('?');
+
+        last = name.end;
+    }
+
+    replaced.push_str(&sql[last..]);
+    *arguments = values;
+
+    Ok(Cow::Owned(replaced))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_repeats_values_of_repeated_names() {
+        let mut arguments = MySqlArguments::default();
+
+        arguments.add_named("a", 1_i32);
+        arguments.add_named("b", Option::<i32>::None);
+        arguments.add_named("c", 3_i32);
+
+        let sql = replace_names("SELECT :c, :a, ':a', :b, :a", &mut arguments).unwrap();
+
+        assert_eq!(sql, "SELECT ?, ?, ':a', ?, ?");
+        assert_eq!(arguments.types.len(), 4);
+        assert_eq!(arguments.null_bitmap, vec![0b0100]);
+        assert_eq!(
+            arguments.values,
+            [3_i32, 1, 1]
+                .iter()
+                .flat_map(|v| v.to_le_bytes().to_vec())
+                .collect::<Vec<u8>>()
+        );
+        assert!(arguments.named.is_none());
+    }
+
+    #[test]
+    fn it_rejects_mismatched_names() {
+        let mut arguments = MySqlArguments::default();
+
+        arguments.add_named("a", 1_i32);
+        arguments.add_named("a", 2_i32);
+
+        match replace_names("SELECT :a, :b", &mut arguments) {
+            Err(Error::NamedParameterMismatch { missing, extra }) => {
+                assert_eq!(missing, vec!["b"]);
+                assert_eq!(extra, vec!["a"]);
+            }
+
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        let mut arguments = MySqlArguments::default();
+
+        arguments.add_named("a", 1_i32);
+        arguments.add(2_i32);
+
+        assert!(matches!(
+            replace_names("SELECT :a, ?", &mut arguments),
+            Err(Error::InvalidArguments(_))
+        ));
+    }
+}
@@ -1,7 +1,10 @@
-use crate::connection::ConnectOptions;
+use crate::connection::{ConnectOptions, Connection};
+use crate::database::Database;
 use crate::error::Error;
 use crate::executor::Executor;
-use crate::mysql::{MySqlConnectOptions, MySqlConnection};
+use crate::instrument::Span;
+use crate::mysql::protocol::response::Status;
+use crate::mysql::{MySql, MySqlConnectOptions, MySqlConnection};
 use futures_core::future::BoxFuture;
 use log::LevelFilter;
 use std::time::Duration;
@@ -13,9 +16,19 @@ impl ConnectOptions for MySqlConnectOptions {
     where
         Self::Connection: Sized,
     {
-        Box::pin(async move {
+        let span = Span::connect(
+            MySql::DB_SYSTEM,
+            &self.host,
+            self.port,
+            self.database.as_deref(),
+        );
+
+        Box::pin(span.clone().instrument(async move {
             let mut conn = MySqlConnection::establish(self).await?;
 
+            // MariaDB is only told apart from MySQL by its handshake
+            span.record_system(conn.db_system());
+
             // After the connection is established, we initialize by configuring a few
             // connection parameters
 
@@ -35,15 +48,35 @@ impl ConnectOptions for MySqlConnectOptions {
             // --
 
             // Setting the time zone allows us to assume that the output
-            // from a TIMESTAMP field is UTC
+            // from a TIMESTAMP field is UTC, unless another time zone was requested
 
             // --
 
             // https://mathiasbynens.be/notes/mysql-utf8mb4
 
+            // All of these are applied in a single round trip; an invalid time zone or
+            // SQL mode fails the connection with the error from the server
+
+            // the values are quoted for the `sql_mode` the server has before they are applied
+            let backslash_escapes = !conn
+                .stream
+                .status
+                .contains(Status::SERVER_STATUS_NO_BACKSLASH_ESCAPES);
+
+            let sql_mode = match &self.sql_mode {
+                Some(sql_mode) => quote(sql_mode, backslash_escapes),
+                None => String::from("@@sql_mode"),
+            };
+
             let mut options = String::new();
-            options.push_str(r#"SET sql_mode=(SELECT CONCAT(@@sql_mode, ',PIPES_AS_CONCAT,NO_ENGINE_SUBSTITUTION')),"#);
-            options.push_str(r#"time_zone='+00:00',"#);
+            options.push_str(&format!(
+                r#"SET sql_mode=(SELECT CONCAT({}, ',PIPES_AS_CONCAT,NO_ENGINE_SUBSTITUTION')),"#,
+                sql_mode
+            ));
+            options.push_str(&format!(
+                r#"time_zone={},"#,
+                quote(&self.time_zone, backslash_escapes)
+            ));
             options.push_str(&format!(
                 r#"NAMES {} COLLATE {};"#,
                 conn.stream.charset.as_str(),
@@ -53,7 +86,7 @@ impl ConnectOptions for MySqlConnectOptions {
             conn.execute(&*options).await?;
 
             Ok(conn)
-        })
+        }))
     }
 
     fn log_statements(&mut self, level: LevelFilter) -> &mut Self {
@@ -66,3 +99,26 @@ impl ConnectOptions for MySqlConnectOptions {
         self
     }
 }
+
+// quote a value as a string literal; a backslash is an escape character in one unless the
+// server is in the `NO_BACKSLASH_ESCAPES` SQL mode
+fn quote(value: &str, backslash_escapes: bool) -> String {
+    let value = value.replace('\'', "''");
+
+    if backslash_escapes {
+        format!("'{}'", value.replace('\\', "\\\\"))
+    } else {
+        format!("'{}'", value)
+    }
+}
+
+#[test]
+fn test_quote() {
+    assert_eq!(quote("+00:00", true), "'+00:00'");
+    assert_eq!(quote("it's", true), "'it''s'");
+    assert_eq!(quote("a\\'b", true), "'a\\\\''b'");
+
+    // a backslash is an ordinary character
+    assert_eq!(quote("it's", false), "'it''s'");
+    assert_eq!(quote("a\\'b", false), "'a\\''b'");
+}
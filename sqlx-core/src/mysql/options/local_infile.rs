@@ -0,0 +1,47 @@
+use std::fmt::{self, Debug, Formatter};
+use std::future::Future;
+use std::io;
+use std::sync::Arc;
+
+use futures_core::future::BoxFuture;
+use sqlx_rt::AsyncRead;
+
+type OpenFile =
+    dyn Fn(&str) -> BoxFuture<'static, io::Result<Box<dyn AsyncRead + Send + Unpin>>> + Send + Sync;
+
+// opens the files that `LOAD DATA LOCAL INFILE` statements ask for; see
+// `MySqlConnectOptions::local_infile_handler`
+#[derive(Clone)]
+pub(crate) struct LocalInfileHandler(Arc<OpenFile>);
+
+impl LocalInfileHandler {
+    pub(crate) fn new<F, Fut, R>(open: F) -> Self
+    where
+        F: Fn(&str) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = io::Result<R>> + Send + 'static,
+        R: AsyncRead + Send + Unpin + 'static,
+    {
+        Self(Arc::new(move |filename| {
+            let file = open(filename);
+
+            Box::pin(async move {
+                let file: Box<dyn AsyncRead + Send + Unpin> = Box::new(file.await?);
+
+                Ok(file)
+            })
+        }))
+    }
+
+    pub(crate) fn open(
+        &self,
+        filename: &str,
+    ) -> BoxFuture<'static, io::Result<Box<dyn AsyncRead + Send + Unpin>>> {
+        (self.0)(filename)
+    }
+}
+
+impl Debug for LocalInfileHandler {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalInfileHandler").finish()
+    }
+}
@@ -1,10 +1,18 @@
+use std::future::Future;
+use std::io;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 mod connect;
+mod local_infile;
 mod parse;
 mod ssl_mode;
 
+use crate::ext::password::Password;
+use crate::wire::{Direction, PacketObserver};
 use crate::{connection::LogSettings, net::CertificateInput};
+pub(crate) use local_infile::LocalInfileHandler;
+use sqlx_rt::AsyncRead;
 pub use ssl_mode::MySqlSslMode;
 
 /// Options and flags which can be used to configure a MySQL connection.
@@ -15,15 +23,21 @@ pub use ssl_mode::MySqlSslMode;
 /// The generic format of the connection URL:
 ///
 /// ```text
-/// mysql://[host][/database][?properties]
+/// mysql://[host][,host...][/database][?properties]
 /// ```
 ///
+/// An IPv6 address is written in brackets, as in `mysql://[2001:db8::1]:3306/db`. When several
+/// hosts are listed, they are tried in order until a connection is established (see
+/// [`MySqlConnectOptions::fallback_host`]).
+///
 /// ## Properties
 ///
 /// |Parameter|Default|Description|
 /// |---------|-------|-----------|
 /// | `ssl-mode` | `PREFERRED` | Determines whether or with what priority a secure SSL TCP/IP connection will be negotiated. See [`MySqlSslMode`]. |
 /// | `ssl-ca` | `None` | Sets the name of a file containing a list of trusted SSL Certificate Authorities. |
+/// | `time_zone` | `+00:00` | The time zone of the session. `time-zone` is accepted as well. See [`MySqlConnectOptions::time_zone`]. |
+/// | `sql_mode` | `None` | The SQL mode of the session, in place of the global SQL mode of the server. `sql-mode` is accepted as well. See [`MySqlConnectOptions::sql_mode`]. |
 /// | `statement-cache-capacity` | `100` | The maximum number of prepared statements stored in the cache. Set to `0` to disable. |
 /// | `socket` | `None` | Path to the unix domain socket, which will be used instead of TCP if set. |
 ///
@@ -55,16 +69,23 @@ pub use ssl_mode::MySqlSslMode;
 pub struct MySqlConnectOptions {
     pub(crate) host: String,
     pub(crate) port: u16,
+    pub(crate) fallback_hosts: Vec<(String, u16)>,
     pub(crate) socket: Option<PathBuf>,
     pub(crate) username: String,
-    pub(crate) password: Option<String>,
+    pub(crate) password: Option<Password>,
     pub(crate) database: Option<String>,
     pub(crate) ssl_mode: MySqlSslMode,
     pub(crate) ssl_ca: Option<CertificateInput>,
     pub(crate) statement_cache_capacity: usize,
+    pub(crate) read_buffer_shrink_threshold: usize,
+    pub(crate) socket_write_timeout: Option<Duration>,
     pub(crate) charset: String,
     pub(crate) collation: Option<String>,
+    pub(crate) time_zone: String,
+    pub(crate) sql_mode: Option<String>,
     pub(crate) log_settings: LogSettings,
+    pub(crate) packet_observer: Option<PacketObserver>,
+    pub(crate) local_infile_handler: Option<LocalInfileHandler>,
 }
 
 impl Default for MySqlConnectOptions {
@@ -79,16 +100,23 @@ impl MySqlConnectOptions {
         Self {
             port: 3306,
             host: String::from("localhost"),
+            fallback_hosts: Vec::new(),
             socket: None,
             username: String::from("root"),
             password: None,
             database: None,
             charset: String::from("utf8mb4"),
             collation: None,
+            time_zone: String::from("+00:00"),
+            sql_mode: None,
             ssl_mode: MySqlSslMode::Preferred,
             ssl_ca: None,
             statement_cache_capacity: 100,
+            read_buffer_shrink_threshold: 256 * 1024,
+            socket_write_timeout: None,
             log_settings: Default::default(),
+            packet_observer: None,
+            local_infile_handler: None,
         }
     }
 
@@ -109,6 +137,16 @@ impl MySqlConnectOptions {
         self
     }
 
+    /// Adds a host to try when a connection to the previous hosts could not be established.
+    ///
+    /// The hosts are tried in order, starting with [`host`](Self::host), and the first one that
+    /// can be connected to is used. When none of them can, the error lists why for each of
+    /// them. Fallback hosts are not used when connecting through a Unix socket.
+    pub fn fallback_host(mut self, host: &str, port: u16) -> Self {
+        self.fallback_hosts.push((host.to_owned(), port));
+        self
+    }
+
     /// Pass a path to a Unix socket. This changes the connection stream from
     /// TCP to UDS.
     ///
@@ -125,8 +163,11 @@ impl MySqlConnectOptions {
     }
 
     /// Sets the password to connect with.
+    ///
+    /// Unlike in a connection URL, the password needs no percent-encoding here. It is shown as
+    /// `REDACTED` in the `Debug` output of the options.
     pub fn password(mut self, password: &str) -> Self {
-        self.password = Some(password.to_owned());
+        self.password = Some(password.into());
         self
     }
 
@@ -195,6 +236,28 @@ impl MySqlConnectOptions {
         self
     }
 
+    /// Sets the size above which the buffer that messages are read into is shrunk back down
+    /// once it is no longer needed, i.e. when the connection is returned to a pool or after a
+    /// statement completes, unless the connection has kept receiving large messages.
+    ///
+    /// The default threshold is 256 KiB.
+    pub fn read_buffer_shrink_threshold(mut self, threshold: usize) -> Self {
+        self.read_buffer_shrink_threshold = threshold;
+        self
+    }
+
+    /// Sets how long sending a message to the server may take before giving up on the
+    /// connection, e.g. when the server stops reading from it.
+    ///
+    /// A message that is not sent in time may have been sent in part, so the connection cannot
+    /// be used anymore: the error is returned and the connection is discarded by its pool.
+    ///
+    /// By default, there is no timeout.
+    pub fn socket_write_timeout(mut self, timeout: Duration) -> Self {
+        self.socket_write_timeout = Some(timeout);
+        self
+    }
+
     /// Sets the character set for the connection.
     ///
     /// The default character set is `utf8mb4`. This is supported from MySQL 5.5.3.
@@ -212,4 +275,114 @@ impl MySqlConnectOptions {
         self.collation = Some(collation.to_owned());
         self
     }
+
+    /// Sets the time zone of the session.
+    ///
+    /// The default time zone is `+00:00` (UTC). Note that the `chrono` and `time` types for a
+    /// `TIMESTAMP` assume that the time zone is UTC.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_core::mysql::MySqlConnectOptions;
+    /// let options = MySqlConnectOptions::new()
+    ///     .time_zone("Europe/Berlin");
+    /// ```
+    pub fn time_zone(mut self, time_zone: &str) -> Self {
+        self.time_zone = time_zone.to_owned();
+        self
+    }
+
+    /// Sets the SQL mode of the session, in place of the global SQL mode of the server.
+    ///
+    /// `PIPES_AS_CONCAT` and `NO_ENGINE_SUBSTITUTION` are always added to the SQL mode.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_core::mysql::MySqlConnectOptions;
+    /// let options = MySqlConnectOptions::new()
+    ///     .sql_mode("STRICT_TRANS_TABLES,NO_ZERO_DATE");
+    /// ```
+    pub fn sql_mode(mut self, sql_mode: &str) -> Self {
+        self.sql_mode = Some(sql_mode.to_owned());
+        self
+    }
+
+    /// Sets a function to pass every packet sent to and received from the server, for
+    /// debugging protocol issues.
+    ///
+    /// Each packet is passed with its header and truncated to `limit` bytes. Packets sent
+    /// before authentication completes are passed without their payload. See
+    /// [`wire`](crate::wire) for more.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_core::mysql::MySqlConnectOptions;
+    /// use sqlx_core::wire::{Direction, HexDump};
+    ///
+    /// let options = MySqlConnectOptions::new()
+    ///     .packet_observer(|direction, packet| {
+    ///         eprintln!("{:?}:\n{}", direction, HexDump(packet))
+    ///     }, 256);
+    /// ```
+    pub fn packet_observer(mut self, observer: fn(Direction, &[u8]), limit: usize) -> Self {
+        self.packet_observer = Some(PacketObserver::new(observer, limit));
+        self
+    }
+
+    /// Sets a function to open the files that `LOAD DATA LOCAL INFILE` statements ask for.
+    ///
+    /// The server reads the file of such a statement from the client, by asking for it by the
+    /// name written in the statement; the function is given that name and returns a reader for
+    /// the contents to send. As the name comes from the server, the function decides which
+    /// files may be read, and should refuse any other with an error.
+    ///
+    /// The client only offers to send files to a server when this is set. Without it, a
+    /// request for a file is refused and the statement fails with [`Error::Configuration`].
+    /// If the file cannot be opened or read, the statement fails with the I/O error; the server
+    /// still loads the rows of what was sent up to then.
+    ///
+    /// [`Error::Configuration`]: crate::error::Error::Configuration
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use sqlx_core::mysql::MySqlConnectOptions;
+    /// use std::io;
+    ///
+    /// let options = MySqlConnectOptions::new()
+    ///     .local_infile_handler(|filename| {
+    ///         let allowed = filename == "data.csv";
+    ///
+    ///         async move {
+    ///             if !allowed {
+    ///                 return Err(io::Error::new(io::ErrorKind::PermissionDenied, "not allowed"));
+    ///             }
+    ///
+    ///             Ok(&b"1,one\n2,two\n"[..])
+    ///         }
+    ///     });
+    /// ```
+    pub fn local_infile_handler<F, Fut, R>(mut self, handler: F) -> Self
+    where
+        F: Fn(&str) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = io::Result<R>> + Send + 'static,
+        R: AsyncRead + Send + Unpin + 'static,
+    {
+        self.local_infile_handler = Some(LocalInfileHandler::new(handler));
+        self
+    }
+
+    // the hosts to try in order, starting with the primary one
+    pub(crate) fn hosts(&self) -> Vec<(String, u16)> {
+        let mut hosts = vec![(self.host.clone(), self.port)];
+
+        if self.socket.is_none() {
+            hosts.extend(self.fallback_hosts.iter().cloned());
+        }
+
+        hosts
+    }
 }
@@ -0,0 +1,146 @@
+// finds the `?` placeholders of a query, to expand the lists bound to them, and the `:name`
+// parameters of queries made with `query_named`
+
+use std::ops::Range;
+
+use crate::named::name_at;
+
+/// Returns the byte offsets of the `?` placeholders of `sql`, skipping string literals, quoted
+/// identifiers and comments.
+pub(crate) fn placeholders(sql: &str) -> Vec<usize> {
+    let bytes = sql.as_bytes();
+    let mut offsets = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if let Some(end) = skip_non_code(bytes, i) {
+            i = end;
+        } else {
+            if bytes[i] == b'?' {
+                offsets.push(i);
+            }
+
+            i += 1;
+        }
+    }
+
+    offsets
+}
+
+/// Returns the ranges of the names of the `:name` parameters of `sql`, in order, skipping the
+/// same parts of the query as [`placeholders`].
+pub(crate) fn named_parameters(sql: &str) -> Vec<Range<usize>> {
+    let bytes = sql.as_bytes();
+    let mut names = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if let Some(end) = skip_non_code(bytes, i) {
+            i = end;
+        } else if let Some(name) = name_at(bytes, i) {
+            i = name.end;
+            names.push(name);
+        } else {
+            i += 1;
+        }
+    }
+
+    names
+}
+
+// returns the offset after the string literal, quoted identifier or comment starting at `i`,
+// if one does
+fn skip_non_code(bytes: &[u8], i: usize) -> Option<usize> {
+    match bytes[i] {
+        b'\'' | b'"' | b'`' => Some(skip_quoted(bytes, i)),
+
+        b'#' => Some(skip_line(bytes, i)),
+
+        // `--` only starts a comment when it is followed by whitespace
+        b'-' if bytes.get(i + 1) == Some(&b'-')
+            && bytes.get(i + 2).map_or(true, |b| b.is_ascii_whitespace()) =>
+        {
+            Some(skip_line(bytes, i))
+        }
+
+        // the contents of `/*! .. */` are executed by the server and may have placeholders
+        b'/' if bytes.get(i + 1) == Some(&b'*') && bytes.get(i + 2) != Some(&b'!') => {
+            Some(match find(&bytes[i + 2..], b"*/") {
+                Some(end) => i + 2 + end + 2,
+                None => bytes.len(),
+            })
+        }
+
+        _ => None,
+    }
+}
+
+// returns the offset after the closing quote of the literal or identifier starting at `start`
+fn skip_quoted(bytes: &[u8], start: usize) -> usize {
+    let quote = bytes[start];
+    let mut i = start + 1;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            // quoted identifiers do not have backslash escapes
+            b'\\' if quote != b'`' => i += 2,
+
+            // a doubled quote is read as a closing quote that is followed by another literal
+            b if b == quote => return i + 1,
+
+            _ => i += 1,
+        }
+    }
+
+    bytes.len()
+}
+
+fn skip_line(bytes: &[u8], start: usize) -> usize {
+    match bytes[start..].iter().position(|&b| b == b'\n') {
+        Some(end) => start + end + 1,
+        None => bytes.len(),
+    }
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(sql: &str) -> Vec<&str> {
+        named_parameters(sql)
+            .into_iter()
+            .map(|range| &sql[range])
+            .collect()
+    }
+
+    #[test]
+    fn it_finds_placeholders() {
+        assert_eq!(placeholders("SELECT ?, ?"), vec![7, 10]);
+        assert_eq!(placeholders("SELECT '?', \"?\", `?`, ?"), vec![22]);
+        assert_eq!(placeholders(r"SELECT 'it\'s ?', 'a''?', ?"), vec![26]);
+        assert_eq!(
+            placeholders("SELECT ? # ?\n, ? -- ?\n, ?--?"),
+            vec![7, 15, 24, 27]
+        );
+        assert_eq!(
+            placeholders("SELECT /* ? */ ?, /*!50000 ? */ ?"),
+            vec![15, 27, 32]
+        );
+        assert_eq!(placeholders("SELECT '?"), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn it_finds_named_parameters() {
+        assert_eq!(names("SELECT :a, :b, :a"), vec!["a", "b", "a"]);
+        assert_eq!(names("SELECT ':a', \":b\", `:c`, :d"), vec!["d"]);
+        assert_eq!(names(r"SELECT 'it\'s :a', :b # :c"), vec!["b"]);
+        assert_eq!(names("SELECT @x:=1, :a /* :b */"), vec!["a"]);
+        assert_eq!(names("SELECT /*!50000 :a */ 1"), vec!["a"]);
+    }
+}
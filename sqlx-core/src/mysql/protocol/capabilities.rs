@@ -1,6 +1,11 @@
+use crate::error::Error;
+
 // https://dev.mysql.com/doc/dev/mysql-server/8.0.12/group__group__cs__capabilities__flags.html
 // https://mariadb.com/kb/en/library/connection/#capabilities
 bitflags::bitflags! {
+    /// The capability flags of the MySQL protocol.
+    ///
+    /// See [`MySqlConnection::capabilities`](crate::mysql::MySqlConnection::capabilities).
     pub struct Capabilities: u64 {
         // [MariaDB] MySQL compatibility
         const MYSQL = 1;
@@ -84,3 +89,76 @@ bitflags::bitflags! {
         const REMEMBER_OPTIONS = (1 << 31);
     }
 }
+
+impl Capabilities {
+    /// The capabilities this client implements, and so may ask the server for.
+    ///
+    /// [`CONNECT_WITH_DB`](Self::CONNECT_WITH_DB) and [`SSL`](Self::SSL) are dropped depending
+    /// on the options of a connection, and [`LOCAL_FILES`](Self::LOCAL_FILES) is added when it
+    /// has a handler for local files.
+    pub(crate) const CLIENT_SUPPORTED: Capabilities = Capabilities {
+        bits: Self::PROTOCOL_41.bits
+            | Self::IGNORE_SPACE.bits
+            | Self::DEPRECATE_EOF.bits
+            | Self::FOUND_ROWS.bits
+            | Self::TRANSACTIONS.bits
+            | Self::SECURE_CONNECTION.bits
+            | Self::PLUGIN_AUTH_LENENC_DATA.bits
+            | Self::MULTI_STATEMENTS.bits
+            | Self::MULTI_RESULTS.bits
+            | Self::PLUGIN_AUTH.bits
+            | Self::PS_MULTI_RESULTS.bits
+            | Self::CONNECT_WITH_DB.bits
+            | Self::SSL.bits,
+    };
+
+    /// The capabilities to use with a server that offers `server`: those that were requested
+    /// and are offered.
+    pub(crate) fn negotiate(self, server: Capabilities) -> Result<Capabilities, Error> {
+        let negotiated = self & server;
+
+        if !negotiated.contains(Capabilities::PROTOCOL_41) {
+            return Err(err_protocol!(
+                "the server does not support the 4.1 protocol; capabilities: {:?}",
+                server
+            ));
+        }
+
+        Ok(negotiated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Capabilities;
+
+    #[test]
+    fn it_negotiates_only_what_both_sides_support() {
+        // a server offering everything gets what the client supports
+        assert_eq!(
+            Capabilities::CLIENT_SUPPORTED
+                .negotiate(Capabilities::all())
+                .unwrap(),
+            Capabilities::CLIENT_SUPPORTED
+        );
+
+        // a server without `DEPRECATE_EOF` gets EOF packets
+        let server = Capabilities::all() - Capabilities::DEPRECATE_EOF;
+        let negotiated = Capabilities::CLIENT_SUPPORTED.negotiate(server).unwrap();
+
+        assert!(!negotiated.contains(Capabilities::DEPRECATE_EOF));
+        assert!(negotiated.contains(Capabilities::MULTI_RESULTS));
+
+        // flags the client does not implement are never used
+        let server = Capabilities::PROTOCOL_41 | Capabilities::COMPRESS | Capabilities::LOCAL_FILES;
+        assert_eq!(
+            Capabilities::CLIENT_SUPPORTED.negotiate(server).unwrap(),
+            Capabilities::PROTOCOL_41
+        );
+
+        // nor is a protocol the server does not offer
+        assert!(Capabilities::CLIENT_SUPPORTED
+            .negotiate(Capabilities::all() - Capabilities::PROTOCOL_41)
+            .is_err());
+    }
+}
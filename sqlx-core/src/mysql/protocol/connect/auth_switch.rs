@@ -1,8 +1,9 @@
-use bytes::{Buf, Bytes};
+use bytes::Bytes;
 
 use crate::error::Error;
 use crate::io::Encode;
 use crate::io::{BufExt, Decode};
+use crate::mysql::io::MySqlBufExt;
 use crate::mysql::protocol::auth::AuthPlugin;
 use crate::mysql::protocol::Capabilities;
 
@@ -16,7 +17,7 @@ pub struct AuthSwitchRequest {
 
 impl Decode<'_> for AuthSwitchRequest {
     fn decode_with(mut buf: Bytes, _: ()) -> Result<Self, Error> {
-        let header = buf.get_u8();
+        let header = buf.try_get_u8()?;
         if header != 0xfe {
             return Err(err_protocol!(
                 "expected 0xfe (AUTH_SWITCH) but found 0x{:x}",
@@ -26,17 +27,14 @@ impl Decode<'_> for AuthSwitchRequest {
 
         let plugin = buf.get_str_nul()?.parse()?;
 
-        // See: https://github.com/mysql/mysql-server/blob/ea7d2e2d16ac03afdd9cb72a972a95981107bf51/sql/auth/sha2_password.cc#L942
-        if buf.len() != 21 {
-            return Err(err_protocol!(
-                "expected 21 bytes but found {} bytes",
-                buf.len()
-            ));
+        // the nonce of client_ed25519 is 32 random bytes, any of which may be 0x00
+        // https://github.com/MariaDB/server/blob/10.4/plugin/auth_ed25519/server_ed25519.c
+        if plugin != AuthPlugin::ClientEd25519 && buf.last() == Some(&0) {
+            // See: https://github.com/mysql/mysql-server/blob/ea7d2e2d16ac03afdd9cb72a972a95981107bf51/sql/auth/sha2_password.cc#L942
+            buf.truncate(buf.len() - 1); // NUL-terminator
         }
-        let data = buf.get_bytes(20);
-        buf.advance(1); // NUL-terminator
 
-        Ok(Self { plugin, data })
+        Ok(Self { plugin, data: buf })
     }
 }
 
@@ -48,3 +46,48 @@ impl Encode<'_, Capabilities> for AuthSwitchResponse {
         buf.extend_from_slice(&self.0);
     }
 }
+
+#[test]
+fn test_decode_auth_switch_mysql_native_password() -> Result<(), Error> {
+    const SWITCH: &[u8] = b"\xfemysql_native_password\x00\x1a\x0c,9\x10\x06~\x14\x0bjA\x13\x1cBV\x7f\x01T\x10\x22\x00";
+
+    let switch = AuthSwitchRequest::decode(SWITCH.into())?;
+
+    assert_eq!(switch.plugin, AuthPlugin::MySqlNativePassword);
+    assert_eq!(&switch.data[..], &SWITCH[23..43]);
+
+    Ok(())
+}
+
+#[test]
+fn test_decode_auth_switch_mysql_clear_password() -> Result<(), Error> {
+    let switch = AuthSwitchRequest::decode(Bytes::from_static(b"\xfemysql_clear_password\x00"))?;
+
+    assert_eq!(switch.plugin, AuthPlugin::MySqlClearPassword);
+    assert!(switch.data.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_decode_auth_switch_client_ed25519() -> Result<(), Error> {
+    // the nonce is not NUL-terminated, and may end in 0x00
+    let mut packet = b"\xfeclient_ed25519\x00".to_vec();
+    packet.extend((1..=31).chain(Some(0)));
+
+    let switch = AuthSwitchRequest::decode(packet.into())?;
+
+    assert_eq!(switch.plugin, AuthPlugin::ClientEd25519);
+    assert_eq!(switch.data.len(), 32);
+    assert_eq!(switch.data[31], 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_decode_auth_switch_unknown_plugin() {
+    let err = AuthSwitchRequest::decode(Bytes::from_static(b"\xfeauth_gssapi_client\x00abc\x00"))
+        .unwrap_err();
+
+    assert!(err.to_string().contains("'auth_gssapi_client'"));
+}
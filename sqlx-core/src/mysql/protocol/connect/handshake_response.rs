@@ -1,4 +1,5 @@
-use crate::io::{BufMutExt, Encode};
+use crate::error::Error;
+use crate::io::{check_str_nul, BufMutExt, Encode};
 use crate::mysql::io::MySqlBufMutExt;
 use crate::mysql::protocol::auth::AuthPlugin;
 use crate::mysql::protocol::connect::ssl_request::SslRequest;
@@ -27,6 +28,40 @@ pub struct HandshakeResponse<'a> {
     pub auth_response: Option<&'a [u8]>,
 }
 
+// the longest names that MySQL or MariaDB accept, in characters
+const MAX_USERNAME_LEN: usize = 80;
+const MAX_DATABASE_LEN: usize = 64;
+
+impl HandshakeResponse<'_> {
+    /// Checks that the username and the database can be sent as NUL-terminated strings, and are
+    /// not longer than a server accepts.
+    pub fn check(&self) -> Result<(), Error> {
+        check_str_nul("username", self.username)?;
+
+        if self.username.chars().count() > MAX_USERNAME_LEN {
+            return Err(Error::Encode(
+                format!("username is longer than {} characters", MAX_USERNAME_LEN).into(),
+            ));
+        }
+
+        if let Some(database) = self.database {
+            check_str_nul("database", database)?;
+
+            if database.chars().count() > MAX_DATABASE_LEN {
+                return Err(Error::Encode(
+                    format!(
+                        "database name is longer than {} characters",
+                        MAX_DATABASE_LEN
+                    )
+                    .into(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
 impl Encode<'_, Capabilities> for HandshakeResponse<'_> {
     fn encode_with(&self, buf: &mut Vec<u8>, mut capabilities: Capabilities) {
         if self.auth_plugin.is_none() {
@@ -71,3 +106,25 @@ impl Encode<'_, Capabilities> for HandshakeResponse<'_> {
         }
     }
 }
+
+#[test]
+fn test_check_handshake_response() {
+    let response = |username, database| HandshakeResponse {
+        database,
+        max_packet_size: 1024,
+        collation: 0,
+        username,
+        auth_plugin: None,
+        auth_response: None,
+    };
+
+    assert!(response("root", Some("sqlx")).check().is_ok());
+    assert!(response("root\0", None).check().is_err());
+    assert!(response("root", Some("sqlx\0mysql")).check().is_err());
+
+    let long = "x".repeat(65);
+
+    assert!(response("root", Some(&long[..64])).check().is_ok());
+    assert!(response("root", Some(&long)).check().is_err());
+    assert!(response(&"x".repeat(81), None).check().is_err());
+}
@@ -7,6 +7,109 @@ mod row;
 pub(crate) mod statement;
 pub(crate) mod text;
 
-pub(crate) use capabilities::Capabilities;
+pub use capabilities::Capabilities;
 pub(crate) use packet::Packet;
 pub(crate) use row::Row;
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use super::connect::{AuthSwitchRequest, Handshake};
+    use super::response::{EofPacket, ErrPacket, OkPacket};
+    use super::statement::{BinaryRow, PrepareOk};
+    use super::text::{ColumnDefinition, ColumnType, TextRow};
+    use super::Capabilities;
+    use crate::error::Error;
+    use crate::ext::ustr::UStr;
+    use crate::io::Decode;
+    use crate::mysql::{MySqlColumn, MySqlTypeInfo};
+
+    const HANDSHAKE_MYSQL_8_0_18: &[u8] = b"\n8.0.18\x00\x19\x00\x00\x00\x114aB0c\x06g\x00\xff\xff\xff\x02\x00\xff\xc7\x15\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00tL\x03s\x0f[4\rl4. \x00caching_sha2_password\x00";
+    const HANDSHAKE_MARIA_DB_10_4_7: &[u8] = b"\n5.5.5-10.4.7-MariaDB-1:10.4.7+maria~bionic\x00\x0b\x00\x00\x00t6L\\j\"dS\x00\xfe\xf7\x08\x02\x00\xff\x81\x15\x00\x00\x00\x00\x00\x00\x07\x00\x00\x00U14Oph9\"<H5n\x00mysql_native_password\x00";
+    const AUTH_SWITCH: &[u8] = b"\xfemysql_native_password\x00\x1a\x0c,9\x10\x06~\x14\x0bjA\x13\x1cBV\x7f\x01T\x10\x22\x00";
+    const OK: &[u8] = b"\x00\xfc\xe8\x03\x00\x02\x00\x00\x00";
+    const ERR: &[u8] = b"\xff\x19\x04#42000Unknown database 'unknown'";
+    const EOF: &[u8] = b"\xfe\x00\x00\x02\x00";
+    const PREPARE_OK: &[u8] = b"\x00\x01\x00\x00\x00\x03\x00\x02\x00\x00\x00\x00";
+    const COLUMN_DEFINITION: &[u8] =
+        b"\x03def\x00\x00\x00\x01a\x01a\x0c\x3f\x00\x14\x00\x00\x00\x08\x00\x00\x00\x00\x00";
+    // 1, NULL, 'hello', NULL
+    const TEXT_ROW: &[u8] = b"\x011\xfb\x05hello\xfb";
+    // 1, 'hello', 2020-01-02
+    const BINARY_ROW: &[u8] =
+        b"\x00\x00\x01\x00\x00\x00\x00\x00\x00\x00\x05hello\x04\xe4\x07\x01\x02";
+
+    fn columns(types: &[ColumnType]) -> Vec<MySqlColumn> {
+        types
+            .iter()
+            .enumerate()
+            .map(|(ordinal, &r#type)| MySqlColumn {
+                ordinal,
+                name: UStr::Static("column"),
+                type_info: MySqlTypeInfo::binary(r#type),
+                flags: None,
+            })
+            .collect()
+    }
+
+    // decodes `packet` cut at every possible byte offset, which must return an error rather
+    // than panic; returns the number of cuts that still decoded, as the last field of some
+    // packets runs to the end of the packet
+    fn decode_truncated<T>(packet: &[u8], decode: impl Fn(Bytes) -> Result<T, Error>) -> usize {
+        assert!(decode(Bytes::copy_from_slice(packet)).is_ok());
+
+        (0..packet.len())
+            .filter(|&len| decode(Bytes::copy_from_slice(&packet[..len])).is_ok())
+            .count()
+    }
+
+    #[test]
+    fn it_returns_errors_for_truncated_packets() {
+        let capabilities = Capabilities::PROTOCOL_41;
+
+        for handshake in [HANDSHAKE_MYSQL_8_0_18, HANDSHAKE_MARIA_DB_10_4_7] {
+            assert_eq!(decode_truncated(handshake, Handshake::decode), 0);
+        }
+
+        assert_eq!(decode_truncated(OK, OkPacket::decode), 0);
+        assert_eq!(
+            decode_truncated(EOF, |buf| EofPacket::decode_with(buf, capabilities)),
+            0
+        );
+        assert_eq!(
+            decode_truncated(PREPARE_OK, |buf| PrepareOk::decode_with(buf, capabilities)),
+            0
+        );
+        // the 2 bytes of filler at the end are not read
+        assert_eq!(
+            decode_truncated(COLUMN_DEFINITION, |buf| {
+                ColumnDefinition::decode_with(buf, capabilities)
+            }),
+            2
+        );
+
+        let text_columns = columns(&[ColumnType::VarString; 4]);
+        assert_eq!(
+            decode_truncated(TEXT_ROW, |buf| TextRow::decode_with(buf, &text_columns)),
+            0
+        );
+
+        let binary_columns = columns(&[
+            ColumnType::LongLong,
+            ColumnType::VarString,
+            ColumnType::Date,
+        ]);
+        assert_eq!(
+            decode_truncated(BINARY_ROW, |buf| {
+                BinaryRow::decode_with(buf, &binary_columns)
+            }),
+            0
+        );
+
+        // the message of an error and the data of an authentication switch run to the end of
+        // the packet, so cutting into them leaves a valid packet
+        decode_truncated(ERR, |buf| ErrPacket::decode_with(buf, capabilities));
+        decode_truncated(AUTH_SWITCH, AuthSwitchRequest::decode);
+    }
+}
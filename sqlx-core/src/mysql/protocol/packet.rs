@@ -7,6 +7,9 @@ use crate::io::{Decode, Encode};
 use crate::mysql::protocol::response::{EofPacket, OkPacket};
 use crate::mysql::protocol::Capabilities;
 
+#[cfg(test)]
+use crate::mysql::protocol::response::Status;
+
 #[derive(Debug)]
 pub struct Packet<T>(pub(crate) T);
 
@@ -60,18 +63,40 @@ impl Packet<Bytes> {
         self.decode()
     }
 
-    pub(crate) fn eof(self, capabilities: Capabilities) -> Result<EofPacket, Error> {
-        if capabilities.contains(Capabilities::DEPRECATE_EOF) {
-            let ok = self.ok()?;
+    // returns `true` if this packet ends a result set, rather than being the next row
+    pub(crate) fn is_eof(&self, capabilities: Capabilities) -> bool {
+        if self.0.first() != Some(&0xfe) {
+            return false;
+        }
 
-            Ok(EofPacket {
-                warnings: ok.warnings,
-                status: ok.status,
-            })
+        if capabilities.contains(Capabilities::DEPRECATE_EOF) {
+            // the result set ends with an OK packet, which may carry session state and so
+            // be longer than an EOF packet; a row can only start with 0xfe if its first value
+            // takes more than a single packet
+            self.0.len() < 0xff_ff_ff
         } else {
-            self.decode_with(capabilities)
+            self.0.len() < 9
         }
     }
+
+    // decodes the packet that ends the rows of a result set; this is an OK packet when EOF is
+    // deprecated, or else an EOF packet, which only has the status and the warnings
+    pub(crate) fn end_of_rows(self, capabilities: Capabilities) -> Result<OkPacket, Error> {
+        // an OK packet is at least 7 bytes; a server may still send a real EOF packet (5 bytes)
+        // to end the metadata of a result set read through a cursor
+        if capabilities.contains(Capabilities::DEPRECATE_EOF) && self.0.len() > 5 {
+            return self.ok();
+        }
+
+        let eof: EofPacket = self.decode_with(capabilities)?;
+
+        Ok(OkPacket {
+            affected_rows: 0,
+            last_insert_id: 0,
+            status: eof.status,
+            warnings: eof.warnings,
+        })
+    }
 }
 
 impl Deref for Packet<Bytes> {
@@ -87,3 +112,35 @@ impl DerefMut for Packet<Bytes> {
         &mut self.0
     }
 }
+
+#[test]
+fn test_is_eof() {
+    // EOF packet
+    const EOF: &[u8] = b"\xfe\x00\x00\x02\x00";
+
+    assert!(Packet(Bytes::from_static(EOF)).is_eof(Capabilities::empty()));
+    assert!(Packet(Bytes::from_static(EOF)).is_eof(Capabilities::DEPRECATE_EOF));
+}
+
+#[test]
+fn test_is_eof_deprecate_eof_with_session_state() {
+    // OK packet with the 0xfe header, after `SET autocommit = 1` was run in the same query
+    const OK: &[u8] = b"\xfe\x00\x00\x02\x40\x00\x00\x00\x10\x00\x0e\x0aautocommit\x02ON";
+
+    let packet = Packet(Bytes::from_static(OK));
+
+    assert!(packet.is_eof(Capabilities::DEPRECATE_EOF));
+
+    let ok = packet.end_of_rows(Capabilities::DEPRECATE_EOF).unwrap();
+
+    assert!(ok.status.contains(Status::SERVER_SESSION_STATE_CHANGED));
+}
+
+#[test]
+fn test_is_not_eof() {
+    // a row with the values 1 and NULL
+    const ROW: &[u8] = b"\x011\xfb";
+
+    assert!(!Packet(Bytes::from_static(ROW)).is_eof(Capabilities::empty()));
+    assert!(!Packet(Bytes::from_static(ROW)).is_eof(Capabilities::DEPRECATE_EOF));
+}
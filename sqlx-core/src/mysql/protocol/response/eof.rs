@@ -1,7 +1,8 @@
-use bytes::{Buf, Bytes};
+use bytes::Bytes;
 
 use crate::error::Error;
 use crate::io::Decode;
+use crate::mysql::io::MySqlBufExt;
 use crate::mysql::protocol::response::Status;
 use crate::mysql::protocol::Capabilities;
 
@@ -19,7 +20,7 @@ pub struct EofPacket {
 
 impl Decode<'_, Capabilities> for EofPacket {
     fn decode_with(mut buf: Bytes, _: Capabilities) -> Result<Self, Error> {
-        let header = buf.get_u8();
+        let header = buf.try_get_u8()?;
         if header != 0xfe {
             return Err(err_protocol!(
                 "expected 0xfe (EOF_Packet) but found 0x{:x}",
@@ -27,8 +28,8 @@ impl Decode<'_, Capabilities> for EofPacket {
             ));
         }
 
-        let warnings = buf.get_u16_le();
-        let status = Status::from_bits_truncate(buf.get_u16_le());
+        let warnings = buf.try_get_u16_le()?;
+        let status = Status::from_bits_truncate(buf.try_get_u16_le()?);
 
         Ok(Self { status, warnings })
     }
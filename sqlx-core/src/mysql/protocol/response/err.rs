@@ -2,6 +2,7 @@ use bytes::{Buf, Bytes};
 
 use crate::error::Error;
 use crate::io::{BufExt, Decode};
+use crate::mysql::io::MySqlBufExt;
 use crate::mysql::protocol::Capabilities;
 
 // https://dev.mysql.com/doc/dev/mysql-server/8.0.12/page_protocol_basic_err_packet.html
@@ -17,7 +18,7 @@ pub struct ErrPacket {
 
 impl Decode<'_, Capabilities> for ErrPacket {
     fn decode_with(mut buf: Bytes, capabilities: Capabilities) -> Result<Self, Error> {
-        let header = buf.get_u8();
+        let header = buf.try_get_u8()?;
         if header != 0xff {
             return Err(err_protocol!(
                 "expected 0xff (ERR_Packet) but found 0x{:x}",
@@ -25,7 +26,7 @@ impl Decode<'_, Capabilities> for ErrPacket {
             ));
         }
 
-        let error_code = buf.get_u16_le();
+        let error_code = buf.try_get_u16_le()?;
         let mut sql_state = None;
 
         if capabilities.contains(Capabilities::PROTOCOL_41) {
@@ -0,0 +1,41 @@
+use bytes::{Buf, Bytes};
+
+use crate::error::Error;
+use crate::io::Decode;
+
+/// Sent by the server in place of a result set when a `LOAD DATA LOCAL INFILE` statement asks
+/// for the contents of a file on the client.
+///
+/// <https://dev.mysql.com/doc/dev/mysql-server/latest/page_protocol_com_query_response_local_infile_request.html>
+/// <https://mariadb.com/kb/en/local_infile-packet/>
+#[derive(Debug)]
+pub struct LocalInfileRequest {
+    pub filename: String,
+}
+
+impl Decode<'_> for LocalInfileRequest {
+    fn decode_with(mut buf: Bytes, _: ()) -> Result<Self, Error> {
+        let header = buf.get_u8();
+        if header != 0xfb {
+            return Err(err_protocol!(
+                "expected 0xfb (LOCAL INFILE Request) but found 0x{:x}",
+                header
+            ));
+        }
+
+        // the file name takes the rest of the packet, as written in the statement
+        let filename = String::from_utf8(buf.to_vec())
+            .map_err(|_| err_protocol!("the file name of a LOCAL INFILE request is not UTF-8"))?;
+
+        Ok(Self { filename })
+    }
+}
+
+#[test]
+fn test_decode_local_infile_request() {
+    const DATA: &[u8] = b"\xfb/tmp/data.csv";
+
+    let request = LocalInfileRequest::decode(DATA.into()).unwrap();
+
+    assert_eq!(request.filename, "/tmp/data.csv");
+}
@@ -1,38 +1,170 @@
 use crate::io::Encode;
 use crate::mysql::protocol::text::ColumnFlags;
 use crate::mysql::protocol::Capabilities;
-use crate::mysql::MySqlArguments;
+use crate::mysql::{MySqlArguments, MySqlTypeInfo};
 
 // https://dev.mysql.com/doc/dev/mysql-server/8.0.12/page_protocol_com_stmt_execute.html
 
+bitflags::bitflags! {
+    // https://dev.mysql.com/doc/dev/mysql-server/8.0.12/mysql__com_8h.html#a3e5e9e744ff6f7b989a604fd669977da
+    pub struct CursorType: u8 {
+        const NO_CURSOR = 0;
+        const READ_ONLY = 1;
+        const FOR_UPDATE = 2;
+        const SCROLLABLE = 4;
+    }
+}
+
 #[derive(Debug)]
 pub struct Execute<'q> {
     pub statement: u32,
+    pub cursor: CursorType,
     pub arguments: &'q MySqlArguments,
+
+    // whether the types of the parameters are sent; the server keeps those of the previous
+    // execution of the statement otherwise, so they are only needed when they change
+    pub send_types: bool,
 }
 
 impl<'q> Encode<'_, Capabilities> for Execute<'q> {
     fn encode_with(&self, buf: &mut Vec<u8>, _: Capabilities) {
         buf.push(0x17); // COM_STMT_EXECUTE
         buf.extend(&self.statement.to_le_bytes());
-        buf.push(0); // NO_CURSOR
+        buf.push(self.cursor.bits()); // flags
         buf.extend(&1_u32.to_le_bytes()); // iterations (always 1): int<4>
 
+        // the rest is only sent for a statement with parameters; the packet of a statement
+        // without any ends here, with neither a null bitmap nor a types block
         if !self.arguments.types.is_empty() {
-            buf.extend(&*self.arguments.null_bitmap);
-            buf.push(1); // send type to server
+            // one bit for every parameter
+            let null_bitmap_len = (self.arguments.types.len() + 7) / 8;
 
-            for ty in &self.arguments.types {
-                buf.push(ty.r#type as u8);
+            buf.extend(&self.arguments.null_bitmap[..null_bitmap_len]);
+            buf.push(self.send_types as u8); // new params bound flag
 
-                buf.push(if ty.flags.contains(ColumnFlags::UNSIGNED) {
-                    0x80
-                } else {
-                    0
-                });
+            if self.send_types {
+                encode_types(&self.arguments.types, buf);
             }
 
             buf.extend(&*self.arguments.values);
         }
     }
 }
+
+// the type of every parameter: its field type, and whether it is unsigned
+pub(crate) fn encode_types(types: &[MySqlTypeInfo], buf: &mut Vec<u8>) {
+    for ty in types {
+        buf.push(ty.r#type.id());
+
+        buf.push(if ty.flags.contains(ColumnFlags::UNSIGNED) {
+            0x80
+        } else {
+            0
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mysql::protocol::text::ColumnType;
+
+    #[test]
+    fn it_encodes_execute() {
+        // the example of the documentation: a single VARCHAR parameter bound to "foo"
+        const EXPECTED: &[u8] = b"\x17\x01\0\0\0\0\x01\0\0\0\0\x01\x0f\0\x03foo";
+
+        let arguments = MySqlArguments {
+            values: b"\x03foo".to_vec(),
+            types: vec![MySqlTypeInfo::binary(ColumnType::VarChar)],
+            null_bitmap: vec![0],
+            ..MySqlArguments::default()
+        };
+
+        let mut buf = Vec::new();
+
+        Execute {
+            statement: 1,
+            cursor: CursorType::NO_CURSOR,
+            arguments: &arguments,
+            send_types: true,
+        }
+        .encode_with(&mut buf, Capabilities::empty());
+
+        assert_eq!(buf, EXPECTED);
+    }
+
+    #[test]
+    fn it_encodes_execute_without_parameters() {
+        // nothing follows the iteration count
+        const EXPECTED: &[u8] = b"\x17\x02\0\0\0\0\x01\0\0\0";
+
+        let mut buf = Vec::new();
+
+        Execute {
+            statement: 2,
+            cursor: CursorType::NO_CURSOR,
+            arguments: &MySqlArguments::default(),
+            send_types: true,
+        }
+        .encode_with(&mut buf, Capabilities::empty());
+
+        assert_eq!(buf, EXPECTED);
+    }
+
+    #[test]
+    fn it_encodes_the_null_bitmap_of_nine_parameters() {
+        let mut arguments = MySqlArguments::default();
+
+        for i in 0..9 {
+            arguments.add(if i == 0 || i == 8 { None } else { Some(i) });
+        }
+
+        let mut buf = Vec::new();
+
+        Execute {
+            statement: 1,
+            cursor: CursorType::NO_CURSOR,
+            arguments: &arguments,
+            send_types: true,
+        }
+        .encode_with(&mut buf, Capabilities::empty());
+
+        // two bytes of null bitmap, then the flag that types follow
+        assert_eq!(&buf[10..13], &[0b0000_0001, 0b0000_0001, 1]);
+        assert_eq!(buf.len(), 13 + 9 * 2 + 7 * 4);
+    }
+
+    #[test]
+    fn it_encodes_typed_parameters() {
+        // `SELECT ?, ?, ?, ?` bound to `1_i32`, `u64::MAX`, `"abc"` and a NULL `i32`: the NULL is
+        // only in the bitmap, and the unsigned BIGINT has the 0x80 flag after its type
+        const EXPECTED: &[u8] = b"\x17\x03\0\0\0\0\x01\0\0\0\x08\x01\x03\0\x08\x80\xfd\0\x03\0\
+            \x01\0\0\0\xff\xff\xff\xff\xff\xff\xff\xff\x03abc";
+
+        // the same, executed again with the same types, which are then left out
+        const EXPECTED_AGAIN: &[u8] = b"\x17\x03\0\0\0\0\x01\0\0\0\x08\0\
+            \x01\0\0\0\xff\xff\xff\xff\xff\xff\xff\xff\x03abc";
+
+        let mut arguments = MySqlArguments::default();
+
+        arguments.add(1_i32);
+        arguments.add(u64::MAX);
+        arguments.add("abc");
+        arguments.add(None::<i32>);
+
+        for (send_types, expected) in [(true, EXPECTED), (false, EXPECTED_AGAIN)] {
+            let mut buf = Vec::new();
+
+            Execute {
+                statement: 3,
+                cursor: CursorType::NO_CURSOR,
+                arguments: &arguments,
+                send_types,
+            }
+            .encode_with(&mut buf, Capabilities::empty());
+
+            assert_eq!(buf, expected);
+        }
+    }
+}
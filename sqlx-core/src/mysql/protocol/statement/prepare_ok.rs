@@ -1,7 +1,8 @@
-use bytes::{Buf, Bytes};
+use bytes::Bytes;
 
 use crate::error::Error;
 use crate::io::Decode;
+use crate::mysql::io::MySqlBufExt;
 use crate::mysql::protocol::Capabilities;
 
 // https://dev.mysql.com/doc/internals/en/com-stmt-prepare-response.html#packet-COM_STMT_PREPARE_OK
@@ -16,7 +17,7 @@ pub(crate) struct PrepareOk {
 
 impl Decode<'_, Capabilities> for PrepareOk {
     fn decode_with(mut buf: Bytes, _: Capabilities) -> Result<Self, Error> {
-        let status = buf.get_u8();
+        let status = buf.try_get_u8()?;
         if status != 0x00 {
             return Err(err_protocol!(
                 "expected 0x00 (COM_STMT_PREPARE_OK) but found 0x{:02x}",
@@ -24,13 +25,13 @@ impl Decode<'_, Capabilities> for PrepareOk {
             ));
         }
 
-        let statement_id = buf.get_u32_le();
-        let columns = buf.get_u16_le();
-        let params = buf.get_u16_le();
+        let statement_id = buf.try_get_u32_le()?;
+        let columns = buf.try_get_u16_le()?;
+        let params = buf.try_get_u16_le()?;
 
-        buf.advance(1); // reserved: string<1>
+        buf.try_advance(1)?; // reserved: string<1>
 
-        let warnings = buf.get_u16_le();
+        let warnings = buf.try_get_u16_le()?;
 
         Ok(Self {
             statement_id,
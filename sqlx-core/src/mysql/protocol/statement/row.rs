@@ -1,7 +1,7 @@
-use bytes::{Buf, Bytes};
+use bytes::Bytes;
 
 use crate::error::Error;
-use crate::io::{BufExt, Decode};
+use crate::io::Decode;
 use crate::mysql::io::MySqlBufExt;
 use crate::mysql::protocol::text::ColumnType;
 use crate::mysql::protocol::Row;
@@ -15,7 +15,7 @@ pub(crate) struct BinaryRow(pub(crate) Row);
 
 impl<'de> Decode<'de, &'de [MySqlColumn]> for BinaryRow {
     fn decode_with(mut buf: Bytes, columns: &'de [MySqlColumn]) -> Result<Self, Error> {
-        let header = buf.get_u8();
+        let header = buf.try_get_u8()?;
         if header != 0 {
             return Err(err_protocol!(
                 "exepcted 0x00 (ROW) but found 0x{:02x}",
@@ -27,7 +27,7 @@ impl<'de> Decode<'de, &'de [MySqlColumn]> for BinaryRow {
         let offset = buf.len();
 
         let null_bitmap_len = (columns.len() + 9) / 8;
-        let null_bitmap = buf.get_bytes(null_bitmap_len);
+        let null_bitmap = buf.try_get_bytes(null_bitmap_len)?;
 
         let mut values = Vec::with_capacity(columns.len());
 
@@ -59,7 +59,9 @@ impl<'de> Decode<'de, &'de [MySqlColumn]> for BinaryRow {
                 | ColumnType::Bit
                 | ColumnType::Decimal
                 | ColumnType::Json
-                | ColumnType::NewDecimal => buf.get_uint_lenenc() as usize,
+                | ColumnType::NewDecimal
+                // types added to the protocol since are all sent as length-encoded strings
+                | ColumnType::Unknown(_) => buf.get_uint_lenenc()? as usize,
 
                 ColumnType::LongLong => 8,
                 ColumnType::Long | ColumnType::Int24 => 4,
@@ -73,18 +75,23 @@ impl<'de> Decode<'de, &'de [MySqlColumn]> for BinaryRow {
                 | ColumnType::Date
                 | ColumnType::Datetime => {
                     // The size of this type is important for decoding
-                    buf[0] as usize + 1
+                    match buf.first() {
+                        Some(&len) => len as usize + 1,
+                        None => return Err(err_protocol!("binary row ended before a value")),
+                    }
                 }
 
                 // NOTE: MySQL will never generate NULL types for non-NULL values
-                ColumnType::Null => unreachable!(),
+                ColumnType::Null => {
+                    return Err(err_protocol!("unexpected non-NULL value of type NULL"));
+                }
             };
 
             let offset = offset - buf.len();
 
             values.push(Some(offset..(offset + size)));
 
-            buf.advance(size);
+            buf.try_advance(size)?;
         }
 
         Ok(BinaryRow(Row { values, storage }))
@@ -1,7 +1,7 @@
 use std::str::from_utf8;
 
 use bitflags::bitflags;
-use bytes::{Buf, Bytes};
+use bytes::Bytes;
 
 use crate::error::Error;
 use crate::io::Decode;
@@ -64,35 +64,38 @@ bitflags! {
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 #[cfg_attr(feature = "offline", derive(serde::Serialize, serde::Deserialize))]
-#[repr(u8)]
 pub enum ColumnType {
-    Decimal = 0x00,
-    Tiny = 0x01,
-    Short = 0x02,
-    Long = 0x03,
-    Float = 0x04,
-    Double = 0x05,
-    Null = 0x06,
-    Timestamp = 0x07,
-    LongLong = 0x08,
-    Int24 = 0x09,
-    Date = 0x0a,
-    Time = 0x0b,
-    Datetime = 0x0c,
-    Year = 0x0d,
-    VarChar = 0x0f,
-    Bit = 0x10,
-    Json = 0xf5,
-    NewDecimal = 0xf6,
-    Enum = 0xf7,
-    Set = 0xf8,
-    TinyBlob = 0xf9,
-    MediumBlob = 0xfa,
-    LongBlob = 0xfb,
-    Blob = 0xfc,
-    VarString = 0xfd,
-    String = 0xfe,
-    Geometry = 0xff,
+    Decimal,
+    Tiny,
+    Short,
+    Long,
+    Float,
+    Double,
+    Null,
+    Timestamp,
+    LongLong,
+    Int24,
+    Date,
+    Time,
+    Datetime,
+    Year,
+    VarChar,
+    Bit,
+    Json,
+    NewDecimal,
+    Enum,
+    Set,
+    TinyBlob,
+    MediumBlob,
+    LongBlob,
+    Blob,
+    VarString,
+    String,
+    Geometry,
+
+    // a type added to the protocol after this driver was written, such as `VECTOR` (`0xf2`);
+    // its values can be read as bytes
+    Unknown(u8),
 }
 
 // https://dev.mysql.com/doc/dev/mysql-server/8.0.12/page_protocol_com_query_response_text_resultset_column_definition.html
@@ -129,18 +132,18 @@ impl ColumnDefinition {
 
 impl Decode<'_, Capabilities> for ColumnDefinition {
     fn decode_with(mut buf: Bytes, _: Capabilities) -> Result<Self, Error> {
-        let catalog = buf.get_bytes_lenenc();
-        let schema = buf.get_bytes_lenenc();
-        let table_alias = buf.get_bytes_lenenc();
-        let table = buf.get_bytes_lenenc();
-        let alias = buf.get_bytes_lenenc();
-        let name = buf.get_bytes_lenenc();
-        let _next_len = buf.get_uint_lenenc(); // always 0x0c
-        let char_set = buf.get_u16_le();
-        let max_size = buf.get_u32_le();
-        let type_id = buf.get_u8();
-        let flags = buf.get_u16_le();
-        let decimals = buf.get_u8();
+        let catalog = buf.get_bytes_lenenc()?;
+        let schema = buf.get_bytes_lenenc()?;
+        let table_alias = buf.get_bytes_lenenc()?;
+        let table = buf.get_bytes_lenenc()?;
+        let alias = buf.get_bytes_lenenc()?;
+        let name = buf.get_bytes_lenenc()?;
+        let _next_len = buf.get_uint_lenenc()?; // always 0x0c
+        let char_set = buf.try_get_u16_le()?;
+        let max_size = buf.try_get_u32_le()?;
+        let type_id = buf.try_get_u8()?;
+        let flags = buf.try_get_u16_le()?;
+        let decimals = buf.try_get_u8()?;
 
         Ok(Self {
             catalog,
@@ -151,7 +154,7 @@ impl Decode<'_, Capabilities> for ColumnDefinition {
             name,
             char_set,
             max_size,
-            r#type: ColumnType::try_from_u16(type_id)?,
+            r#type: ColumnType::from_id(type_id),
             flags: ColumnFlags::from_bits_truncate(flags),
             decimals,
         })
@@ -214,11 +217,81 @@ impl ColumnType {
 
             ColumnType::LongBlob if is_binary => "LONGBLOB",
             ColumnType::LongBlob => "LONGTEXT",
+
+            ColumnType::Unknown(_) => "UNKNOWN",
         }
     }
 
-    pub(crate) fn try_from_u16(id: u8) -> Result<Self, Error> {
-        Ok(match id {
+    // the name of the type in the C API of MySQL
+    pub(crate) fn protocol_name(self) -> &'static str {
+        match self {
+            ColumnType::Decimal => "MYSQL_TYPE_DECIMAL",
+            ColumnType::Tiny => "MYSQL_TYPE_TINY",
+            ColumnType::Short => "MYSQL_TYPE_SHORT",
+            ColumnType::Long => "MYSQL_TYPE_LONG",
+            ColumnType::Float => "MYSQL_TYPE_FLOAT",
+            ColumnType::Double => "MYSQL_TYPE_DOUBLE",
+            ColumnType::Null => "MYSQL_TYPE_NULL",
+            ColumnType::Timestamp => "MYSQL_TYPE_TIMESTAMP",
+            ColumnType::LongLong => "MYSQL_TYPE_LONGLONG",
+            ColumnType::Int24 => "MYSQL_TYPE_INT24",
+            ColumnType::Date => "MYSQL_TYPE_DATE",
+            ColumnType::Time => "MYSQL_TYPE_TIME",
+            ColumnType::Datetime => "MYSQL_TYPE_DATETIME",
+            ColumnType::Year => "MYSQL_TYPE_YEAR",
+            ColumnType::VarChar => "MYSQL_TYPE_VARCHAR",
+            ColumnType::Bit => "MYSQL_TYPE_BIT",
+            ColumnType::Json => "MYSQL_TYPE_JSON",
+            ColumnType::NewDecimal => "MYSQL_TYPE_NEWDECIMAL",
+            ColumnType::Enum => "MYSQL_TYPE_ENUM",
+            ColumnType::Set => "MYSQL_TYPE_SET",
+            ColumnType::TinyBlob => "MYSQL_TYPE_TINY_BLOB",
+            ColumnType::MediumBlob => "MYSQL_TYPE_MEDIUM_BLOB",
+            ColumnType::LongBlob => "MYSQL_TYPE_LONG_BLOB",
+            ColumnType::Blob => "MYSQL_TYPE_BLOB",
+            ColumnType::VarString => "MYSQL_TYPE_VAR_STRING",
+            ColumnType::String => "MYSQL_TYPE_STRING",
+            ColumnType::Geometry => "MYSQL_TYPE_GEOMETRY",
+            ColumnType::Unknown(_) => "MYSQL_TYPE_UNKNOWN",
+        }
+    }
+
+    // the id of the type in the protocol
+    pub(crate) fn id(self) -> u8 {
+        match self {
+            ColumnType::Decimal => 0x00,
+            ColumnType::Tiny => 0x01,
+            ColumnType::Short => 0x02,
+            ColumnType::Long => 0x03,
+            ColumnType::Float => 0x04,
+            ColumnType::Double => 0x05,
+            ColumnType::Null => 0x06,
+            ColumnType::Timestamp => 0x07,
+            ColumnType::LongLong => 0x08,
+            ColumnType::Int24 => 0x09,
+            ColumnType::Date => 0x0a,
+            ColumnType::Time => 0x0b,
+            ColumnType::Datetime => 0x0c,
+            ColumnType::Year => 0x0d,
+            ColumnType::VarChar => 0x0f,
+            ColumnType::Bit => 0x10,
+            ColumnType::Json => 0xf5,
+            ColumnType::NewDecimal => 0xf6,
+            ColumnType::Enum => 0xf7,
+            ColumnType::Set => 0xf8,
+            ColumnType::TinyBlob => 0xf9,
+            ColumnType::MediumBlob => 0xfa,
+            ColumnType::LongBlob => 0xfb,
+            ColumnType::Blob => 0xfc,
+            ColumnType::VarString => 0xfd,
+            ColumnType::String => 0xfe,
+            ColumnType::Geometry => 0xff,
+            ColumnType::Unknown(id) => id,
+        }
+    }
+
+    pub(crate) fn from_id(id: u8) -> Self {
+        match id {
             0x00 => ColumnType::Decimal,
             0x01 => ColumnType::Tiny,
             0x02 => ColumnType::Short,
@@ -251,9 +324,14 @@ impl ColumnType {
             0xfe => ColumnType::String,
             0xff => ColumnType::Geometry,
 
-            _ => {
-                return Err(err_protocol!("unknown column type 0x{:02x}", id));
-            }
-        })
+            id => ColumnType::Unknown(id),
+        }
+    }
+
+    pub(crate) fn try_from_u16(id: u8) -> Result<Self, Error> {
+        match ColumnType::from_id(id) {
+            ColumnType::Unknown(id) => Err(err_protocol!("unknown column type 0x{:02x}", id)),
+            ty => Ok(ty),
+        }
     }
 }
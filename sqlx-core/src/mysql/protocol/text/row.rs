@@ -6,6 +6,8 @@ use crate::mysql::io::MySqlBufExt;
 use crate::mysql::protocol::Row;
 use crate::mysql::MySqlColumn;
 
+// https://dev.mysql.com/doc/internals/en/com-query-response.html#packet-ProtocolText::ResultsetRow
+
 #[derive(Debug)]
 pub(crate) struct TextRow(pub(crate) Row);
 
@@ -17,20 +19,134 @@ impl<'de> Decode<'de, &'de [MySqlColumn]> for TextRow {
         let mut values = Vec::with_capacity(columns.len());
 
         for _ in columns {
-            if buf[0] == 0xfb {
+            let header = match buf.first() {
+                Some(&header) => header,
+                None => {
+                    return Err(err_protocol!(
+                        "expected {} values in text row but found {}",
+                        columns.len(),
+                        values.len()
+                    ));
+                }
+            };
+
+            if header == 0xfb {
                 // NULL is sent as 0xfb
                 values.push(None);
                 buf.advance(1);
-            } else {
-                let size = buf.get_uint_lenenc() as usize;
-                let offset = offset - buf.len();
 
-                values.push(Some(offset..(offset + size)));
+                continue;
+            }
+
+            let size_len = match header {
+                0xfc => 3,
+                0xfd => 4,
+                0xfe => 9,
+                0xff => {
+                    return Err(err_protocol!(
+                        "unexpected 0xff at the start of a value in text row"
+                    ));
+                }
+
+                _ => 1,
+            };
 
-                buf.advance(size);
+            if buf.len() < size_len {
+                return Err(err_protocol!("text row ended in the length of a value"));
             }
+
+            let size = buf.get_uint_lenenc()? as usize;
+
+            if buf.len() < size {
+                return Err(err_protocol!(
+                    "text row ended {} bytes into a value of {} bytes",
+                    buf.len(),
+                    size
+                ));
+            }
+
+            let offset = offset - buf.len();
+
+            values.push(Some(offset..(offset + size)));
+
+            buf.advance(size);
+        }
+
+        if !buf.is_empty() {
+            return Err(err_protocol!(
+                "unexpected {} bytes after the last value in text row",
+                buf.len()
+            ));
         }
 
         Ok(TextRow(Row { values, storage }))
     }
 }
+
+#[cfg(test)]
+fn columns(len: usize) -> Vec<MySqlColumn> {
+    use crate::ext::ustr::UStr;
+    use crate::mysql::protocol::text::ColumnType;
+    use crate::mysql::MySqlTypeInfo;
+
+    (0..len)
+        .map(|ordinal| MySqlColumn {
+            ordinal,
+            name: UStr::Static("column"),
+            type_info: MySqlTypeInfo::binary(ColumnType::VarString),
+            flags: None,
+        })
+        .collect()
+}
+
+#[test]
+fn test_decode_text_row_with_nulls() {
+    // SELECT 1, NULL, 'hello', NULL
+    const ROW: &[u8] = b"\x011\xfb\x05hello\xfb";
+
+    let row = TextRow::decode_with(ROW.into(), &columns(4)).unwrap().0;
+
+    assert_eq!(row.get(0), Some(&b"1"[..]));
+    assert_eq!(row.get(1), None);
+    assert_eq!(row.get(2), Some(&b"hello"[..]));
+    assert_eq!(row.get(3), None);
+}
+
+#[test]
+fn test_decode_text_row_with_empty_value() {
+    // SELECT ''
+    const ROW: &[u8] = b"\x00";
+
+    let row = TextRow::decode_with(ROW.into(), &columns(1)).unwrap().0;
+
+    assert_eq!(row.get(0), Some(&b""[..]));
+}
+
+#[test]
+fn test_decode_text_row_missing_values() {
+    const ROW: &[u8] = b"\x011";
+
+    assert!(TextRow::decode_with(ROW.into(), &columns(2)).is_err());
+}
+
+#[test]
+fn test_decode_text_row_truncated_value() {
+    const ROW: &[u8] = b"\x05hel";
+
+    assert!(TextRow::decode_with(ROW.into(), &columns(1)).is_err());
+}
+
+#[test]
+fn test_decode_text_row_truncated_length() {
+    const ROW: &[u8] = b"\xfc\x01";
+
+    assert!(TextRow::decode_with(ROW.into(), &columns(1)).is_err());
+}
+
+#[test]
+fn test_decode_text_row_trailing_bytes() {
+    // an EOF packet mistaken for a row of one column
+    const ROW: &[u8] = b"\x011\x00\x00\x02\x00";
+
+    assert!(TextRow::decode_with(ROW.into(), &columns(1)).is_err());
+}
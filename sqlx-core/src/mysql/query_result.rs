@@ -1,9 +1,10 @@
 use std::iter::{Extend, IntoIterator};
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct MySqlQueryResult {
     pub(super) rows_affected: u64,
     pub(super) last_insert_id: u64,
+    pub(super) warnings: u16,
 }
 
 impl MySqlQueryResult {
@@ -14,6 +15,11 @@ impl MySqlQueryResult {
     pub fn rows_affected(&self) -> u64 {
         self.rows_affected
     }
+
+    /// The number of warnings raised by the statement, as listed by `SHOW WARNINGS`.
+    pub fn warnings(&self) -> u16 {
+        self.warnings
+    }
 }
 
 impl Extend<MySqlQueryResult> for MySqlQueryResult {
@@ -21,6 +27,7 @@ impl Extend<MySqlQueryResult> for MySqlQueryResult {
         for elem in iter {
             self.rows_affected += elem.rows_affected;
             self.last_insert_id = elem.last_insert_id;
+            self.warnings = self.warnings.saturating_add(elem.warnings);
         }
     }
 }
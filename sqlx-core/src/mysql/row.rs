@@ -1,18 +1,24 @@
-use crate::column::ColumnIndex;
+use crate::column::{ColumnIndex, ColumnNames};
 use crate::error::Error;
-use crate::ext::ustr::UStr;
+use crate::from_row::FromRow;
 use crate::mysql::{protocol, MySql, MySqlColumn, MySqlValueFormat, MySqlValueRef};
+use crate::owned_row::{OwnedRow, OwnedValueFormat};
 use crate::row::Row;
-use crate::HashMap;
 use std::sync::Arc;
 
 /// Implementation of [`Row`] for MySQL.
+///
+/// The values of a row are in the text or the binary format, depending on how the query was run,
+/// and are decoded the same way in either. The columns, and the map of their names to their
+/// ordinals, are built once per result set and shared by all of its rows.
 #[derive(Debug)]
 pub struct MySqlRow {
     pub(crate) row: protocol::Row,
     pub(crate) format: MySqlValueFormat,
     pub(crate) columns: Arc<Vec<MySqlColumn>>,
-    pub(crate) column_names: Arc<HashMap<UStr, usize>>,
+    pub(crate) column_names: Arc<ColumnNames>,
+    // see `Query::text_results`
+    pub(crate) text_results: bool,
 }
 
 impl crate::row::private_row::Sealed for MySqlRow {}
@@ -39,6 +45,88 @@ impl Row for MySqlRow {
             value,
         })
     }
+
+    fn received_len(&self) -> usize {
+        self.row.storage.len()
+    }
+
+    fn text_results(&self) -> bool {
+        self.text_results
+    }
+
+    fn is_ambiguous(&self, name: &str) -> bool {
+        self.column_names.is_ambiguous(name)
+    }
+}
+
+impl MySqlRow {
+    /// Returns the format of the values of this row.
+    pub fn format(&self) -> MySqlValueFormat {
+        self.format
+    }
+
+    /// Copies the values of this row into an [`OwnedRow`], which can be kept after the
+    /// connection is gone.
+    pub fn to_owned(&self) -> OwnedRow<MySql> {
+        OwnedRow {
+            columns: Arc::clone(&self.columns),
+            values: (0..self.row.values.len())
+                .map(|index| self.row.get(index).map(<[u8]>::to_vec))
+                .collect(),
+            format: match self.format {
+                MySqlValueFormat::Text => OwnedValueFormat::Text,
+                MySqlValueFormat::Binary => OwnedValueFormat::Binary,
+            },
+            text_results: self.text_results,
+        }
+    }
+}
+
+impl Row for OwnedRow<MySql> {
+    type Database = MySql;
+
+    fn columns(&self) -> &[MySqlColumn] {
+        &self.columns
+    }
+
+    fn try_get_raw<I>(&self, index: I) -> Result<MySqlValueRef<'_>, Error>
+    where
+        I: ColumnIndex<Self>,
+    {
+        let index = index.index(self)?;
+
+        Ok(MySqlValueRef {
+            format: match self.format {
+                OwnedValueFormat::Text => MySqlValueFormat::Text,
+                OwnedValueFormat::Binary => MySqlValueFormat::Binary,
+            },
+            row: None,
+            type_info: self.columns[index].type_info.clone(),
+            value: self.value(index),
+        })
+    }
+
+    fn text_results(&self) -> bool {
+        self.text_results
+    }
+}
+
+impl From<&'_ MySqlRow> for OwnedRow<MySql> {
+    fn from(row: &MySqlRow) -> Self {
+        row.to_owned()
+    }
+}
+
+impl From<MySqlRow> for OwnedRow<MySql> {
+    fn from(row: MySqlRow) -> Self {
+        row.to_owned()
+    }
+}
+
+impl<'r> FromRow<'r, MySqlRow> for OwnedRow<MySql> {
+    fn from_row(row: &'r MySqlRow) -> Result<Self, Error> {
+        Ok(row.to_owned())
+    }
 }
 
 impl ColumnIndex<MySqlRow> for &'_ str {
@@ -0,0 +1,112 @@
+use std::borrow::Cow;
+use std::time::Duration;
+
+use crate::error::Error;
+use crate::mysql::{MySql, MySqlArguments};
+use crate::query::Query;
+use crate::query_as::QueryAs;
+use crate::query_scalar::QueryScalar;
+
+impl<'q> Query<'q, MySql, MySqlArguments> {
+    /// Have the server abort this query once it has run for longer than `timeout`.
+    ///
+    /// The query is sent as `SET STATEMENT max_statement_time = .. FOR <query>`, which bounds
+    /// this statement alone, without round trips to set and reset the session variable. A query
+    /// that runs for too long returns a [database error](Error::Database) with the error number
+    /// 1969 (`ER_STATEMENT_TIMEOUT`), and the connection can be used as before.
+    ///
+    /// This requires MariaDB; executing the query on MySQL returns an error. On MySQL, a
+    /// `SELECT` can be bounded with the `/*+ MAX_EXECUTION_TIME(milliseconds) */` optimizer hint
+    /// written into the query instead.
+    pub fn server_timeout(mut self, timeout: Duration) -> Self {
+        if let Some(arguments) = &mut self.arguments {
+            arguments.server_timeout = Some(timeout);
+        }
+
+        self
+    }
+}
+
+impl<'q, O> QueryAs<'q, MySql, O, MySqlArguments> {
+    /// Have the server abort this query once it has run for longer than `timeout`.
+    ///
+    /// See [`Query::server_timeout`](Query::server_timeout).
+    pub fn server_timeout(mut self, timeout: Duration) -> Self {
+        self.inner = self.inner.server_timeout(timeout);
+        self
+    }
+}
+
+impl<'q, O> QueryScalar<'q, MySql, O, MySqlArguments> {
+    /// Have the server abort this query once it has run for longer than `timeout`.
+    ///
+    /// See [`Query::server_timeout`](Query::server_timeout).
+    pub fn server_timeout(mut self, timeout: Duration) -> Self {
+        self.inner = self.inner.server_timeout(timeout);
+        self
+    }
+}
+
+// prefixes the query with the `SET STATEMENT` that bounds its execution time, if it has one
+pub(crate) fn with_server_timeout<'q>(
+    sql: Cow<'q, str>,
+    arguments: &MySqlArguments,
+    is_mariadb: bool,
+) -> Result<Cow<'q, str>, Error> {
+    let timeout = match arguments.server_timeout {
+        Some(timeout) => timeout,
+        None => return Ok(sql),
+    };
+
+    if !is_mariadb {
+        return Err(Error::Configuration(
+            "`server_timeout` requires MariaDB; on MySQL, use the `MAX_EXECUTION_TIME` optimizer hint"
+                .into(),
+        ));
+    }
+
+    // in seconds, with microsecond precision; zero would mean no limit at all
+    let micros = timeout.as_micros().max(1);
+
+    Ok(Cow::Owned(format!(
+        "SET STATEMENT max_statement_time = {}.{:06} FOR {}",
+        micros / 1_000_000,
+        micros % 1_000_000,
+        sql
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wrap(sql: &str, timeout: Option<Duration>, is_mariadb: bool) -> Result<String, Error> {
+        let arguments = MySqlArguments {
+            server_timeout: timeout,
+            ..MySqlArguments::default()
+        };
+
+        with_server_timeout(Cow::Borrowed(sql), &arguments, is_mariadb).map(Cow::into_owned)
+    }
+
+    #[test]
+    fn it_prefixes_queries_with_a_timeout() {
+        assert_eq!(wrap("SELECT ?", None, true).unwrap(), "SELECT ?");
+        assert_eq!(wrap("SELECT ?", None, false).unwrap(), "SELECT ?");
+
+        assert_eq!(
+            wrap("SELECT ?", Some(Duration::from_millis(1500)), true).unwrap(),
+            "SET STATEMENT max_statement_time = 1.500000 FOR SELECT ?"
+        );
+
+        assert_eq!(
+            wrap("SELECT 1", Some(Duration::from_secs(0)), true).unwrap(),
+            "SET STATEMENT max_statement_time = 0.000001 FOR SELECT 1"
+        );
+
+        assert!(matches!(
+            wrap("SELECT 1", Some(Duration::from_secs(1)), false),
+            Err(Error::Configuration(_))
+        ));
+    }
+}
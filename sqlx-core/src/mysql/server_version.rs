@@ -0,0 +1,146 @@
+//! The version of the server, as reported in the initial handshake, and the behavior that
+//! depends on it.
+
+use crate::connection::{ServerVendor, ServerVersion};
+use crate::mysql::protocol::auth::AuthPlugin;
+
+/// Parses the version of the server from the handshake, e.g. `8.0.28`,
+/// `5.7.mysql_aurora.2.10.2` or `5.5.5-10.6.4-MariaDB-1:10.6.4+maria~focal`.
+pub(crate) fn parse_server_version(s: &str) -> ServerVersion {
+    let vendor = if s.contains("MariaDB") {
+        ServerVendor::MariaDb
+    } else {
+        ServerVendor::MySql
+    };
+
+    // MariaDB before 11.0 reports `5.5.5-` before its actual version, so that clients that
+    // expect MySQL 5 accept it
+    let version = match vendor {
+        ServerVendor::MariaDb => s.strip_prefix("5.5.5-").unwrap_or(s),
+        _ => s,
+    };
+
+    // each part is a number followed by anything, e.g. `37-log`; a part that does not start
+    // with a number, e.g. `mysql_aurora`, is 0
+    let mut parts = version.split('.').map(|part| {
+        let digits = part.bytes().take_while(u8::is_ascii_digit).count();
+        part[..digits].parse::<u16>().ok()
+    });
+
+    let mut next = || parts.next().flatten();
+
+    let major = next().unwrap_or(0);
+    let minor = next().unwrap_or(0);
+    let mut patch = next().unwrap_or(0);
+
+    // Aurora reports its own version in place of the patch level of MySQL; it is compatible
+    // with MySQL 5.7.12 as of Aurora 2, and with MySQL 8.0.23 as of Aurora 3
+    if version.contains(".mysql_aurora.") {
+        patch = match (major, minor) {
+            (5, 7) => 12,
+            (8, 0) => 23,
+            _ => patch,
+        };
+    }
+
+    ServerVersion {
+        vendor,
+        major,
+        minor,
+        patch,
+    }
+}
+
+/// Whether the server can be asked to send OK packets instead of EOF packets.
+///
+/// Some proxies and old servers offer `DEPRECATE_EOF` and still send EOF packets, so it is only
+/// asked of servers that are known to support it: MySQL since 5.7.5 and MariaDB since 10.2.
+pub(crate) fn supports_deprecate_eof(version: &ServerVersion) -> bool {
+    version.is_at_least(ServerVendor::MySql, 5, 7, 5)
+        || version.is_at_least(ServerVendor::MariaDb, 10, 2, 0)
+}
+
+/// The authentication plugin the server uses by default, for servers that do not say which one
+/// to use in the handshake.
+pub(crate) fn default_auth_plugin(version: &ServerVersion) -> AuthPlugin {
+    if version.is_at_least(ServerVendor::MySql, 8, 0, 4) {
+        AuthPlugin::CachingSha2Password
+    } else {
+        AuthPlugin::MySqlNativePassword
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_server_versions() {
+        use ServerVendor::{MariaDb, MySql};
+
+        for (s, vendor, major, minor, patch) in [
+            ("8.0.28", MySql, 8, 0, 28),
+            ("5.7.37-log", MySql, 5, 7, 37),
+            ("8.0.28-0ubuntu0.20.04.3", MySql, 8, 0, 28),
+            // Percona Server
+            ("8.0.28-19", MySql, 8, 0, 28),
+            ("5.7.36-39-log", MySql, 5, 7, 36),
+            // Amazon Aurora
+            ("5.7.mysql_aurora.2.10.2", MySql, 5, 7, 12),
+            ("8.0.mysql_aurora.3.02.0", MySql, 8, 0, 23),
+            ("5.6.10", MySql, 5, 6, 10),
+            // MariaDB
+            ("5.5.5-10.6.4-MariaDB", MariaDb, 10, 6, 4),
+            ("5.5.5-10.3.34-MariaDB-0ubuntu0.20.04.1", MariaDb, 10, 3, 34),
+            ("10.6.4-MariaDB-1:10.6.4+maria~focal", MariaDb, 10, 6, 4),
+            ("11.0.2-MariaDB-1:11.0.2+maria~ubu2204", MariaDb, 11, 0, 2),
+            ("unknown", MySql, 0, 0, 0),
+        ] {
+            assert_eq!(
+                parse_server_version(s),
+                ServerVersion {
+                    vendor,
+                    major,
+                    minor,
+                    patch
+                },
+                "{}",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn it_asks_only_known_servers_to_deprecate_eof() {
+        assert!(supports_deprecate_eof(&parse_server_version("8.0.28")));
+        assert!(supports_deprecate_eof(&parse_server_version("5.7.5")));
+        assert!(supports_deprecate_eof(&parse_server_version(
+            "5.7.mysql_aurora.2.10.2"
+        )));
+        assert!(supports_deprecate_eof(&parse_server_version(
+            "5.5.5-10.2.0-MariaDB"
+        )));
+
+        assert!(!supports_deprecate_eof(&parse_server_version("5.7.4")));
+        assert!(!supports_deprecate_eof(&parse_server_version(
+            "5.5.5-10.1.48-MariaDB"
+        )));
+        assert!(!supports_deprecate_eof(&parse_server_version("unknown")));
+    }
+
+    #[test]
+    fn it_defaults_to_the_auth_plugin_of_the_server() {
+        assert_eq!(
+            default_auth_plugin(&parse_server_version("8.0.28")),
+            AuthPlugin::CachingSha2Password
+        );
+        assert_eq!(
+            default_auth_plugin(&parse_server_version("5.7.37")),
+            AuthPlugin::MySqlNativePassword
+        );
+        assert_eq!(
+            default_auth_plugin(&parse_server_version("5.5.5-10.6.4-MariaDB")),
+            AuthPlugin::MySqlNativePassword
+        );
+    }
+}
@@ -1,10 +1,8 @@
 use super::MySqlColumn;
-use crate::column::ColumnIndex;
+use crate::column::{ColumnIndex, ColumnNames};
 use crate::error::Error;
-use crate::ext::ustr::UStr;
 use crate::mysql::{MySql, MySqlArguments, MySqlTypeInfo};
 use crate::statement::Statement;
-use crate::HashMap;
 use either::Either;
 use std::borrow::Cow;
 use std::sync::Arc;
@@ -18,8 +16,21 @@ pub struct MySqlStatement<'q> {
 #[derive(Debug, Default, Clone)]
 pub(crate) struct MySqlStatementMetadata {
     pub(crate) columns: Arc<Vec<MySqlColumn>>,
-    pub(crate) column_names: Arc<HashMap<UStr, usize>>,
-    pub(crate) parameters: usize,
+    pub(crate) column_names: Arc<ColumnNames>,
+    pub(crate) parameters: Arc<Vec<MySqlTypeInfo>>,
+}
+
+impl MySqlStatement<'_> {
+    /// The types of the parameters of this statement, as reported by the server when it was
+    /// prepared.
+    ///
+    /// MySQL and MariaDB do not infer the type of a parameter from how it is used, so most
+    /// parameters are reported with a placeholder type, such as `VARCHAR` or `NULL`. The types
+    /// of the arguments bound to the statement are sent along with them, so these are only
+    /// informative.
+    pub fn parameter_types(&self) -> &[MySqlTypeInfo] {
+        &self.metadata.parameters
+    }
 }
 
 impl<'q> Statement<'q> for MySqlStatement<'q> {
@@ -37,7 +48,7 @@ impl<'q> Statement<'q> for MySqlStatement<'q> {
     }
 
     fn parameters(&self) -> Option<Either<&[MySqlTypeInfo], usize>> {
-        Some(Either::Right(self.metadata.parameters))
+        Some(Either::Right(self.metadata.parameters.len()))
     }
 
     fn columns(&self) -> &[MySqlColumn] {
@@ -70,7 +81,7 @@ impl<'q> From<MySqlStatement<'q>> for crate::any::AnyStatement<'q> {
                 .map(|col| col.clone().into())
                 .collect(),
             column_names: statement.metadata.column_names,
-            parameters: Some(Either::Right(statement.metadata.parameters)),
+            parameters: Some(Either::Right(statement.metadata.parameters.len())),
             sql: statement.sql,
         }
     }
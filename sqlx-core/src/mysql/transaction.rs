@@ -6,8 +6,8 @@ use crate::mysql::connection::Waiting;
 use crate::mysql::protocol::text::Query;
 use crate::mysql::{MySql, MySqlConnection};
 use crate::transaction::{
-    begin_ansi_transaction_sql, commit_ansi_transaction_sql, rollback_ansi_transaction_sql,
-    TransactionManager,
+    begin_ansi_transaction_sql, check_characteristics_settable, commit_ansi_transaction_sql,
+    rollback_ansi_transaction_sql, IsolationLevel, Transaction, TransactionManager,
 };
 
 /// Implementation of [`TransactionManager`] for MySQL.
@@ -23,6 +23,12 @@ impl TransactionManager for MySqlTransactionManager {
             conn.execute(&*begin_ansi_transaction_sql(depth)).await?;
             conn.transaction_depth = depth + 1;
 
+            if depth == 0 {
+                conn.queries_at_begin = conn.stream.stats.queries;
+                conn.isolation_level = None;
+                conn.read_only = None;
+            }
+
             Ok(())
         })
     }
@@ -66,3 +72,67 @@ impl TransactionManager for MySqlTransactionManager {
         }
     }
 }
+
+impl<'c> Transaction<'c, MySql> {
+    /// Sets the isolation level of this transaction.
+    ///
+    /// This must be called on the outermost transaction, before any other statement is executed
+    /// in it. MySQL only accepts `SET TRANSACTION` for the next transaction, so the transaction
+    /// is rolled back and begun again with the characteristics set on it so far.
+    pub async fn set_isolation_level(&mut self, level: IsolationLevel) -> Result<(), Error> {
+        let conn: &mut MySqlConnection = self;
+
+        check_settable(conn)?;
+        conn.isolation_level = Some(level);
+
+        restart(conn).await
+    }
+
+    /// Sets whether this transaction is read-only.
+    ///
+    /// This must be called on the outermost transaction, before any other statement is executed
+    /// in it. MySQL only accepts `SET TRANSACTION` for the next transaction, so the transaction
+    /// is rolled back and begun again with the characteristics set on it so far.
+    pub async fn set_read_only(&mut self, read_only: bool) -> Result<(), Error> {
+        let conn: &mut MySqlConnection = self;
+
+        check_settable(conn)?;
+        conn.read_only = Some(read_only);
+
+        restart(conn).await
+    }
+}
+
+fn check_settable(conn: &MySqlConnection) -> Result<(), Error> {
+    check_characteristics_settable(
+        conn.transaction_depth,
+        conn.queries_at_begin,
+        conn.stream.stats.queries,
+    )
+}
+
+// nothing was executed in the transaction yet, so it can be begun again in its place
+async fn restart(conn: &mut MySqlConnection) -> Result<(), Error> {
+    let mut characteristics = Vec::new();
+
+    if let Some(level) = conn.isolation_level {
+        characteristics.push(format!("ISOLATION LEVEL {}", level.as_sql()));
+    }
+
+    if let Some(read_only) = conn.read_only {
+        characteristics.push(if read_only { "READ ONLY" } else { "READ WRITE" }.to_owned());
+    }
+
+    conn.execute("ROLLBACK").await?;
+
+    // begin again even if the characteristics were refused, so that the statements that follow
+    // are not executed outside of a transaction
+    let set = conn
+        .execute(&*format!("SET TRANSACTION {}", characteristics.join(", ")))
+        .await;
+
+    conn.execute("BEGIN").await?;
+    conn.queries_at_begin = conn.stream.stats.queries;
+
+    set.map(|_| ())
+}
@@ -1,5 +1,6 @@
 use std::fmt::{self, Display, Formatter};
 
+use crate::error::Error;
 use crate::mysql::protocol::text::{ColumnDefinition, ColumnFlags, ColumnType};
 use crate::type_info::TypeInfo;
 
@@ -26,6 +27,21 @@ impl MySqlTypeInfo {
         }
     }
 
+    /// Create a `MySqlTypeInfo` from the id of a field type of the protocol
+    /// (e.g. `0x03` for `INT`), to bind a value of that type with
+    /// [`Query::bind_raw`](crate::query::Query::bind_raw).
+    ///
+    /// Returns an error if the id is not of a known field type.
+    pub fn with_type_id(id: u8, unsigned: bool) -> Result<Self, Error> {
+        let mut type_info = Self::binary(ColumnType::try_from_u16(id)?);
+
+        if unsigned {
+            type_info.flags |= ColumnFlags::UNSIGNED;
+        }
+
+        Ok(type_info)
+    }
+
     #[doc(hidden)]
     pub const fn __enum() -> Self {
         Self {
@@ -74,11 +90,19 @@ impl TypeInfo for MySqlTypeInfo {
     fn name(&self) -> &str {
         self.r#type.name(self.char_set, self.flags, self.max_size)
     }
+
+    fn protocol_id(&self) -> Option<String> {
+        Some(format!(
+            "{}, id {}",
+            self.r#type.protocol_name(),
+            self.r#type.id()
+        ))
+    }
 }
 
 impl PartialEq<MySqlTypeInfo> for MySqlTypeInfo {
     fn eq(&self, other: &MySqlTypeInfo) -> bool {
-        if self.r#type != other.r#type {
+        if canonical(self.r#type) != canonical(other.r#type) {
             return false;
         }
 
@@ -92,7 +116,8 @@ impl PartialEq<MySqlTypeInfo> for MySqlTypeInfo {
                     == other.flags.contains(ColumnFlags::UNSIGNED);
             }
 
-            // for string types, check that our charset matches
+            // string types hold text or bytes, depending on whether their character set is the
+            // binary one; which text character set is only a matter of conversion
             ColumnType::VarChar
             | ColumnType::Blob
             | ColumnType::TinyBlob
@@ -101,7 +126,7 @@ impl PartialEq<MySqlTypeInfo> for MySqlTypeInfo {
             | ColumnType::String
             | ColumnType::VarString
             | ColumnType::Enum => {
-                return self.char_set == other.char_set;
+                return (self.char_set == BINARY_CHAR_SET) == (other.char_set == BINARY_CHAR_SET);
             }
 
             _ => {}
@@ -111,6 +136,27 @@ impl PartialEq<MySqlTypeInfo> for MySqlTypeInfo {
     }
 }
 
+// the character set of binary strings, `binary`
+const BINARY_CHAR_SET: u16 = 63;
+
+// the type that stands for all the types the server sends interchangeably for the same kind of
+// value: strings and blobs differ only in the lengths they allow, and `DECIMAL` is the old
+// encoding of `NEWDECIMAL`
+fn canonical(ty: ColumnType) -> ColumnType {
+    match ty {
+        ColumnType::VarChar
+        | ColumnType::TinyBlob
+        | ColumnType::MediumBlob
+        | ColumnType::LongBlob
+        | ColumnType::Blob
+        | ColumnType::String => ColumnType::VarString,
+
+        ColumnType::Decimal => ColumnType::NewDecimal,
+
+        ty => ty,
+    }
+}
+
 impl Eq for MySqlTypeInfo {}
 
 #[cfg(feature = "any")]
@@ -120,3 +166,87 @@ impl From<MySqlTypeInfo> for crate::any::AnyTypeInfo {
         crate::any::AnyTypeInfo(crate::any::type_info::AnyTypeInfoKind::MySql(ty))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn type_info(ty: ColumnType, char_set: u16) -> MySqlTypeInfo {
+        MySqlTypeInfo {
+            r#type: ty,
+            flags: ColumnFlags::empty(),
+            char_set,
+            max_size: None,
+        }
+    }
+
+    fn unsigned(ty: ColumnType) -> MySqlTypeInfo {
+        let mut type_info = MySqlTypeInfo::binary(ty);
+        type_info.flags |= ColumnFlags::UNSIGNED;
+        type_info
+    }
+
+    #[test]
+    fn it_equates_types_sent_interchangeably() {
+        let strings = [
+            ColumnType::VarChar,
+            ColumnType::VarString,
+            ColumnType::String,
+            ColumnType::TinyBlob,
+            ColumnType::Blob,
+            ColumnType::MediumBlob,
+            ColumnType::LongBlob,
+        ];
+
+        for &a in &strings {
+            for &b in &strings {
+                // utf8mb4_general_ci and utf8mb4_unicode_ci
+                assert_eq!(type_info(a, 45), type_info(b, 224), "{:?} {:?}", a, b);
+                assert_eq!(type_info(a, 63), type_info(b, 63), "{:?} {:?}", a, b);
+            }
+        }
+
+        assert_eq!(
+            MySqlTypeInfo::binary(ColumnType::Decimal),
+            MySqlTypeInfo::binary(ColumnType::NewDecimal)
+        );
+        assert_eq!(
+            unsigned(ColumnType::LongLong),
+            unsigned(ColumnType::LongLong)
+        );
+    }
+
+    #[test]
+    fn it_distinguishes_other_types() {
+        // text and bytes
+        assert_ne!(
+            type_info(ColumnType::VarString, 224),
+            type_info(ColumnType::Blob, 63)
+        );
+
+        // strings and other types sent as strings
+        assert_ne!(
+            type_info(ColumnType::VarString, 224),
+            type_info(ColumnType::Enum, 224)
+        );
+        assert_ne!(
+            type_info(ColumnType::VarString, 224),
+            type_info(ColumnType::Json, 224)
+        );
+
+        // integers of different sizes or signedness
+        assert_ne!(
+            MySqlTypeInfo::binary(ColumnType::Long),
+            MySqlTypeInfo::binary(ColumnType::LongLong)
+        );
+        assert_ne!(
+            MySqlTypeInfo::binary(ColumnType::LongLong),
+            unsigned(ColumnType::LongLong)
+        );
+
+        assert_ne!(
+            MySqlTypeInfo::binary(ColumnType::Datetime),
+            MySqlTypeInfo::binary(ColumnType::Timestamp)
+        );
+    }
+}
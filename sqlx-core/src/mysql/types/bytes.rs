@@ -5,6 +5,7 @@ use crate::mysql::io::MySqlBufMutExt;
 use crate::mysql::protocol::text::ColumnType;
 use crate::mysql::{MySql, MySqlTypeInfo, MySqlValueRef};
 use crate::types::Type;
+use std::borrow::Cow;
 
 impl Type<MySql> for [u8] {
     fn type_info() -> MySqlTypeInfo {
@@ -22,6 +23,7 @@ impl Type<MySql> for [u8] {
                 | ColumnType::String
                 | ColumnType::VarString
                 | ColumnType::Enum
+                | ColumnType::Unknown(_)
         )
     }
 }
@@ -56,6 +58,28 @@ impl Encode<'_, MySql> for Vec<u8> {
     }
 }
 
+impl<const N: usize> Type<MySql> for [u8; N] {
+    fn type_info() -> MySqlTypeInfo {
+        <[u8] as Type<MySql>>::type_info()
+    }
+
+    fn compatible(ty: &MySqlTypeInfo) -> bool {
+        <&[u8] as Type<MySql>>::compatible(ty)
+    }
+}
+
+impl<const N: usize> Encode<'_, MySql> for [u8; N] {
+    fn encode_by_ref(&self, buf: &mut Vec<u8>) -> IsNull {
+        <&[u8] as Encode<MySql>>::encode(&self[..], buf)
+    }
+}
+
+impl Encode<'_, MySql> for Cow<'_, [u8]> {
+    fn encode_by_ref(&self, buf: &mut Vec<u8>) -> IsNull {
+        <&[u8] as Encode<MySql>>::encode(&**self, buf)
+    }
+}
+
 impl Decode<'_, MySql> for Vec<u8> {
     fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
         <&[u8] as Decode<MySql>>::decode(value).map(ToOwned::to_owned)
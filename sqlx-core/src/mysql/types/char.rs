@@ -0,0 +1,36 @@
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::mysql::io::MySqlBufMutExt;
+use crate::mysql::{MySql, MySqlTypeInfo, MySqlValueRef};
+use crate::types::Type;
+
+impl Type<MySql> for char {
+    fn type_info() -> MySqlTypeInfo {
+        <str as Type<MySql>>::type_info()
+    }
+
+    fn compatible(ty: &MySqlTypeInfo) -> bool {
+        <str as Type<MySql>>::compatible(ty)
+    }
+}
+
+impl Encode<'_, MySql> for char {
+    fn encode_by_ref(&self, buf: &mut Vec<u8>) -> IsNull {
+        buf.put_str_lenenc(self.encode_utf8(&mut [0; 4]));
+
+        IsNull::No
+    }
+}
+
+impl Decode<'_, MySql> for char {
+    fn decode(value: MySqlValueRef<'_>) -> Result<Self, BoxDynError> {
+        let s = value.as_str()?;
+        let mut chars = s.chars();
+
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(format!("expected a single character but found {:?}", s).into()),
+        }
+    }
+}
@@ -1,13 +1,19 @@
 use std::convert::TryFrom;
 
 use bytes::Buf;
-use chrono::{DateTime, Datelike, Local, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc};
+use chrono::{
+    DateTime, Datelike, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc,
+};
 
 use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::{BoxDynError, UnexpectedNullError};
 use crate::mysql::protocol::text::ColumnType;
 use crate::mysql::type_info::MySqlTypeInfo;
+use crate::mysql::types::datetime::{
+    datetime_size, decode_date, decode_datetime, decode_time, encode_date, encode_datetime,
+    encode_time, time_size, RawDate, RawTime, DATE_SIZE,
+};
 use crate::mysql::{MySql, MySqlValueFormat, MySqlValueRef};
 use crate::types::Type;
 
@@ -68,24 +74,77 @@ impl Type<MySql> for NaiveTime {
 }
 
 impl Encode<'_, MySql> for NaiveTime {
+    fn encode_by_ref(&self, buf: &mut Vec<u8>) -> IsNull {
+        encode_time(raw_time(self), buf);
+
+        IsNull::No
+    }
+
+    fn size_hint(&self) -> usize {
+        time_size(raw_time(self))
+    }
+}
+
+impl<'r> Decode<'r, MySql> for NaiveTime {
+    fn decode(value: MySqlValueRef<'r>) -> Result<Self, BoxDynError> {
+        match value.format() {
+            MySqlValueFormat::Binary => from_raw_time(decode_time(value.as_bytes()?)?),
+
+            MySqlValueFormat::Text => {
+                let s = value.as_str()?;
+                NaiveTime::parse_from_str(s, "%H:%M:%S%.f").map_err(Into::into)
+            }
+        }
+    }
+}
+
+/// Encodes a signed duration as a MySQL `TIME`, which ranges from `-838:59:59.000000`
+/// to `838:59:59.000000`.
+impl Type<MySql> for Duration {
+    fn type_info() -> MySqlTypeInfo {
+        MySqlTypeInfo::binary(ColumnType::Time)
+    }
+}
+
+impl Encode<'_, MySql> for Duration {
     fn encode_by_ref(&self, buf: &mut Vec<u8>) -> IsNull {
         let len = Encode::<MySql>::size_hint(self) - 1;
         buf.push(len as u8);
 
-        // NaiveTime is not negative
-        buf.push(0);
+        if len == 0 {
+            return IsNull::No;
+        }
+
+        let is_negative = *self < Duration::zero();
+        let abs = if is_negative { -*self } else { *self };
+
+        // the microseconds are always in range as we split off the seconds first
+        let seconds = abs.num_seconds();
+        let micros = (abs - Duration::seconds(seconds))
+            .num_microseconds()
+            .unwrap_or_default();
 
-        // "date on 4 bytes little-endian format" (?)
-        // https://mariadb.com/kb/en/resultset-row/#teimstamp-binary-encoding
-        buf.extend_from_slice(&[0_u8; 4]);
+        let days = u32::try_from(seconds / 86400)
+            .unwrap_or_else(|_| panic!("Duration out of range for MySql: {}", self));
 
-        encode_time(self, len > 9, buf);
+        buf.push(is_negative as u8);
+        buf.extend_from_slice(&days.to_le_bytes());
+        buf.push(((seconds % 86400) / 3600) as u8);
+        buf.push(((seconds % 3600) / 60) as u8);
+        buf.push((seconds % 60) as u8);
+
+        if len > 8 {
+            buf.extend_from_slice(&(micros as u32).to_le_bytes());
+        }
 
         IsNull::No
     }
 
     fn size_hint(&self) -> usize {
-        if self.nanosecond() == 0 {
+        if self.is_zero() {
+            // if all of the fields are 0, length is 0 and nothing else is sent
+            1
+        } else if (*self - Duration::seconds(self.num_seconds())).num_microseconds() == Some(0) {
             // if micro_seconds is 0, length is 8 and micro_seconds is not sent
             9
         } else {
@@ -95,41 +154,76 @@ impl Encode<'_, MySql> for NaiveTime {
     }
 }
 
-impl<'r> Decode<'r, MySql> for NaiveTime {
+impl<'r> Decode<'r, MySql> for Duration {
     fn decode(value: MySqlValueRef<'r>) -> Result<Self, BoxDynError> {
         match value.format() {
             MySqlValueFormat::Binary => {
                 let mut buf = value.as_bytes()?;
 
-                // data length, expecting 8 or 12 (fractional seconds)
+                // data length, expecting 0, 8 or 12 (fractional seconds)
                 let len = buf.get_u8();
 
-                // MySQL specifies that if all of hours, minutes, seconds, microseconds
-                // are 0 then the length is 0 and no further data is send
-                // https://dev.mysql.com/doc/internals/en/binary-protocol-value.html
                 if len == 0 {
-                    return Ok(NaiveTime::from_hms_micro(0, 0, 0, 0));
+                    return Ok(Duration::zero());
                 }
 
-                // is negative : int<1>
-                let is_negative = buf.get_u8();
-                debug_assert_eq!(is_negative, 0, "Negative dates/times are not supported");
+                let is_negative = buf.get_u8() != 0;
+                let days = buf.get_u32_le();
+                let hours = buf.get_u8();
+                let minutes = buf.get_u8();
+                let seconds = buf.get_u8();
 
-                // "date on 4 bytes little-endian format" (?)
-                // https://mariadb.com/kb/en/resultset-row/#timestamp-binary-encoding
-                buf.advance(4);
+                let micros = if len > 8 { buf.get_u32_le() } else { 0 };
 
-                Ok(decode_time(len - 5, buf))
-            }
+                let duration = Duration::days(days.into())
+                    + Duration::hours(hours.into())
+                    + Duration::minutes(minutes.into())
+                    + Duration::seconds(seconds.into())
+                    + Duration::microseconds(micros.into());
 
-            MySqlValueFormat::Text => {
-                let s = value.as_str()?;
-                NaiveTime::parse_from_str(s, "%H:%M:%S%.f").map_err(Into::into)
+                Ok(if is_negative { -duration } else { duration })
             }
+
+            MySqlValueFormat::Text => parse_time_duration(value.as_str()?),
         }
     }
 }
 
+// parses `[-]HHH:MM:SS[.ffffff]`
+fn parse_time_duration(s: &str) -> Result<Duration, BoxDynError> {
+    let (is_negative, s) = match s.strip_prefix('-') {
+        Some(s) => (true, s),
+        None => (false, s),
+    };
+
+    let (s, micros) = match s.split_once('.') {
+        Some((s, fraction)) => {
+            // right-pad the fraction to microseconds
+            let digits = fraction.len().min(6);
+            let micros: i64 = fraction[..digits].parse()?;
+
+            (s, micros * 10_i64.pow((6 - digits) as u32))
+        }
+
+        None => (s, 0),
+    };
+
+    let mut parts = s.splitn(3, ':');
+    let mut next = || -> Result<i64, BoxDynError> {
+        Ok(parts
+            .next()
+            .ok_or_else(|| format!("invalid TIME value: {:?}", s))?
+            .parse()?)
+    };
+
+    let duration = Duration::hours(next()?)
+        + Duration::minutes(next()?)
+        + Duration::seconds(next()?)
+        + Duration::microseconds(micros);
+
+    Ok(if is_negative { -duration } else { duration })
+}
+
 impl Type<MySql> for NaiveDate {
     fn type_info() -> MySqlTypeInfo {
         MySqlTypeInfo::binary(ColumnType::Date)
@@ -138,15 +232,13 @@ impl Type<MySql> for NaiveDate {
 
 impl Encode<'_, MySql> for NaiveDate {
     fn encode_by_ref(&self, buf: &mut Vec<u8>) -> IsNull {
-        buf.push(4);
-
-        encode_date(self, buf);
+        encode_date(raw_date(self), buf);
 
         IsNull::No
     }
 
     fn size_hint(&self) -> usize {
-        5
+        DATE_SIZE
     }
 }
 
@@ -154,7 +246,7 @@ impl<'r> Decode<'r, MySql> for NaiveDate {
     fn decode(value: MySqlValueRef<'r>) -> Result<Self, BoxDynError> {
         match value.format() {
             MySqlValueFormat::Binary => {
-                decode_date(&value.as_bytes()?[1..]).ok_or_else(|| UnexpectedNullError.into())
+                from_raw_date(decode_date(value.as_bytes()?)?.ok_or(UnexpectedNullError)?)
             }
 
             MySqlValueFormat::Text => {
@@ -173,37 +265,13 @@ impl Type<MySql> for NaiveDateTime {
 
 impl Encode<'_, MySql> for NaiveDateTime {
     fn encode_by_ref(&self, buf: &mut Vec<u8>) -> IsNull {
-        let len = Encode::<MySql>::size_hint(self) - 1;
-        buf.push(len as u8);
-
-        encode_date(&self.date(), buf);
-
-        if len > 4 {
-            encode_time(&self.time(), len > 8, buf);
-        }
+        encode_datetime(raw_date(&self.date()), raw_time(&self.time()), buf);
 
         IsNull::No
     }
 
     fn size_hint(&self) -> usize {
-        // to save space the packet can be compressed:
-        match (
-            self.hour(),
-            self.minute(),
-            self.second(),
-            self.timestamp_subsec_nanos(),
-        ) {
-            // if hour, minutes, seconds and micro_seconds are all 0,
-            // length is 4 and no other field is sent
-            (0, 0, 0, 0) => 5,
-
-            // if micro_seconds is 0, length is 7
-            // and micro_seconds is not sent
-            (_, _, _, 0) => 8,
-
-            // otherwise length is 11
-            (_, _, _, _) => 12,
-        }
+        datetime_size(raw_time(&self.time()))
     }
 }
 
@@ -211,18 +279,10 @@ impl<'r> Decode<'r, MySql> for NaiveDateTime {
     fn decode(value: MySqlValueRef<'r>) -> Result<Self, BoxDynError> {
         match value.format() {
             MySqlValueFormat::Binary => {
-                let buf = value.as_bytes()?;
-
-                let len = buf[0];
-                let date = decode_date(&buf[1..]).ok_or(UnexpectedNullError)?;
+                let (date, time) =
+                    decode_datetime(value.as_bytes()?)?.ok_or(UnexpectedNullError)?;
 
-                let dt = if len > 4 {
-                    date.and_time(decode_time(len - 4, &buf[5..]))
-                } else {
-                    date.and_hms(0, 0, 0)
-                };
-
-                Ok(dt)
+                Ok(from_raw_date(date)?.and_time(from_raw_time(time)?))
             }
 
             MySqlValueFormat::Text => {
@@ -233,51 +293,106 @@ impl<'r> Decode<'r, MySql> for NaiveDateTime {
     }
 }
 
-fn encode_date(date: &NaiveDate, buf: &mut Vec<u8>) {
-    // MySQL supports years from 1000 - 9999
-    let year = u16::try_from(date.year())
-        .unwrap_or_else(|_| panic!("NaiveDateTime out of range for Mysql: {}", date));
+fn raw_date(date: &NaiveDate) -> RawDate {
+    RawDate {
+        year: date.year(),
+        month: date.month() as u8,
+        day: date.day() as u8,
+    }
+}
 
-    buf.extend_from_slice(&year.to_le_bytes());
-    buf.push(date.month() as u8);
-    buf.push(date.day() as u8);
+fn from_raw_date(date: RawDate) -> Result<NaiveDate, BoxDynError> {
+    NaiveDate::from_ymd_opt(date.year, date.month.into(), date.day.into())
+        .ok_or_else(|| format!("date out of range: {:?}", date).into())
 }
 
-fn decode_date(mut buf: &[u8]) -> Option<NaiveDate> {
-    if buf.len() == 0 {
-        // MySQL specifies that if there are no bytes, this is all zeros
-        None
-    } else {
-        let year = buf.get_u16_le();
-        Some(NaiveDate::from_ymd(
-            year as i32,
-            buf[0] as u32,
-            buf[1] as u32,
-        ))
+fn raw_time(time: &NaiveTime) -> RawTime {
+    RawTime {
+        hour: time.hour() as u8,
+        minute: time.minute() as u8,
+        second: time.second() as u8,
+        microsecond: time.nanosecond() / 1000,
     }
 }
 
-fn encode_time(time: &NaiveTime, include_micros: bool, buf: &mut Vec<u8>) {
-    buf.push(time.hour() as u8);
-    buf.push(time.minute() as u8);
-    buf.push(time.second() as u8);
+fn from_raw_time(time: RawTime) -> Result<NaiveTime, BoxDynError> {
+    NaiveTime::from_hms_micro_opt(
+        time.hour.into(),
+        time.minute.into(),
+        time.second.into(),
+        time.microsecond,
+    )
+    .ok_or_else(|| format!("time out of range: {:?}", time).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode<T: for<'q> Encode<'q, MySql>>(value: T) -> Vec<u8> {
+        let mut buf = Vec::new();
+        let _ = value.encode_by_ref(&mut buf);
 
-    if include_micros {
-        buf.extend(&((time.nanosecond() / 1000) as u32).to_le_bytes());
+        assert_eq!(buf.len(), Encode::<MySql>::size_hint(&value));
+
+        buf
     }
-}
 
-fn decode_time(len: u8, mut buf: &[u8]) -> NaiveTime {
-    let hour = buf.get_u8();
-    let minute = buf.get_u8();
-    let seconds = buf.get_u8();
+    #[test]
+    fn it_encodes_time_in_shortest_form() {
+        assert_eq!(encode(NaiveTime::from_hms(0, 0, 0)), [0]);
+        assert_eq!(
+            encode(NaiveTime::from_hms(12, 34, 56)),
+            [8, 0, 0, 0, 0, 0, 12, 34, 56]
+        );
+        assert_eq!(
+            encode(NaiveTime::from_hms_micro(0, 0, 0, 1)),
+            [12, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0]
+        );
+    }
 
-    let micros = if len > 3 {
-        // microseconds : int<EOF>
-        buf.get_uint_le(buf.len())
-    } else {
-        0
-    };
+    #[test]
+    fn it_encodes_durations() {
+        assert_eq!(encode(Duration::zero()), [0]);
+
+        // -838:59:59 is 34 days and 22:59:59
+        assert_eq!(
+            encode(-(Duration::hours(838) + Duration::minutes(59) + Duration::seconds(59))),
+            [8, 1, 34, 0, 0, 0, 22, 59, 59]
+        );
+
+        assert_eq!(
+            encode(Duration::microseconds(999_999)),
+            [12, 0, 0, 0, 0, 0, 0, 0, 0, 0x3f, 0x42, 0x0f, 0]
+        );
+
+        assert_eq!(
+            encode(-Duration::microseconds(1)),
+            [12, 1, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0]
+        );
+    }
 
-    NaiveTime::from_hms_micro(hour as u32, minute as u32, seconds as u32, micros as u32)
+    #[test]
+    fn it_encodes_datetime_in_shortest_form() {
+        let date = NaiveDate::from_ymd(0, 1, 1);
+
+        assert_eq!(encode(date.and_hms(0, 0, 0)), [4, 0, 0, 1, 1]);
+        assert_eq!(encode(date.and_hms(1, 2, 3)), [7, 0, 0, 1, 1, 1, 2, 3]);
+        assert_eq!(
+            encode(date.and_hms_micro(1, 2, 3, 4)),
+            [11, 0, 0, 1, 1, 1, 2, 3, 4, 0, 0, 0]
+        );
+    }
+
+    #[test]
+    fn it_parses_text_durations() {
+        assert_eq!(
+            parse_time_duration("-838:59:59").unwrap(),
+            -(Duration::hours(838) + Duration::minutes(59) + Duration::seconds(59))
+        );
+        assert_eq!(
+            parse_time_duration("00:00:00.5").unwrap(),
+            Duration::microseconds(500_000)
+        );
+    }
 }
@@ -0,0 +1,296 @@
+//! The binary encoding of `DATE`, `TIME`, `DATETIME` and `TIMESTAMP` values, shared by the
+//! `chrono` and `time` integrations so that both encode and decode them the same way.
+//!
+//! <https://dev.mysql.com/doc/internals/en/binary-protocol-value.html>
+
+use std::convert::TryFrom;
+
+use bytes::Buf;
+
+use crate::error::BoxDynError;
+
+/// The fields of a date, as sent by MySQL.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct RawDate {
+    pub(super) year: i32,
+    pub(super) month: u8,
+    pub(super) day: u8,
+}
+
+/// The fields of a time of day, as sent by MySQL.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct RawTime {
+    pub(super) hour: u8,
+    pub(super) minute: u8,
+    pub(super) second: u8,
+    pub(super) microsecond: u32,
+}
+
+impl RawTime {
+    pub(super) const MIDNIGHT: RawTime = RawTime {
+        hour: 0,
+        minute: 0,
+        second: 0,
+        microsecond: 0,
+    };
+}
+
+/// The size of a `DATE`, including its length.
+pub(super) const DATE_SIZE: usize = 5;
+
+pub(super) fn encode_date(date: RawDate, buf: &mut Vec<u8>) {
+    buf.push(4);
+
+    encode_date_fields(date, buf);
+}
+
+/// Decodes a `DATE`, which is `None` for the zero date `0000-00-00`.
+pub(super) fn decode_date(mut buf: &[u8]) -> Result<Option<RawDate>, BoxDynError> {
+    let len = buf.get_u8();
+
+    decode_date_fields(len, buf)
+}
+
+/// The size of a `TIME` that is a time of day, including its length.
+pub(super) fn time_size(time: RawTime) -> usize {
+    if time == RawTime::MIDNIGHT {
+        // if all of the fields are 0, length is 0 and nothing else is sent
+        1
+    } else if time.microsecond == 0 {
+        // if micro_seconds is 0, length is 8 and micro_seconds is not sent
+        9
+    } else {
+        // otherwise length is 12
+        13
+    }
+}
+
+pub(super) fn encode_time(time: RawTime, buf: &mut Vec<u8>) {
+    let len = time_size(time) - 1;
+    buf.push(len as u8);
+
+    if len == 0 {
+        return;
+    }
+
+    // a time of day is not negative
+    buf.push(0);
+
+    // nor longer than a day
+    buf.extend_from_slice(&[0_u8; 4]);
+
+    encode_time_fields(time, len > 8, buf);
+}
+
+/// Decodes a `TIME` that is a time of day, i.e. neither negative nor longer than a day.
+pub(super) fn decode_time(mut buf: &[u8]) -> Result<RawTime, BoxDynError> {
+    // data length, expecting 0, 8 or 12 (fractional seconds)
+    let len = buf.get_u8();
+
+    if len == 0 {
+        return Ok(RawTime::MIDNIGHT);
+    }
+
+    if buf.len() < 5 {
+        return Err(format!(
+            "expected a sign and days of a time but found {} bytes",
+            buf.len()
+        )
+        .into());
+    }
+
+    let is_negative = buf.get_u8() != 0;
+    let days = buf.get_u32_le();
+
+    if is_negative || days != 0 {
+        return Err("TIME value out of range for a time of day".into());
+    }
+
+    decode_time_fields(len - 5, buf)
+}
+
+/// The size of a `DATETIME` or `TIMESTAMP`, including its length.
+pub(super) fn datetime_size(time: RawTime) -> usize {
+    // to save space the packet can be compressed:
+    if time == RawTime::MIDNIGHT {
+        // if hour, minutes, seconds and micro_seconds are all 0,
+        // length is 4 and no other field is sent
+        5
+    } else if time.microsecond == 0 {
+        // if micro_seconds is 0, length is 7
+        // and micro_seconds is not sent
+        8
+    } else {
+        // otherwise length is 11
+        12
+    }
+}
+
+pub(super) fn encode_datetime(date: RawDate, time: RawTime, buf: &mut Vec<u8>) {
+    let len = datetime_size(time) - 1;
+    buf.push(len as u8);
+
+    encode_date_fields(date, buf);
+
+    if len > 4 {
+        encode_time_fields(time, len > 7, buf);
+    }
+}
+
+/// Decodes a `DATETIME` or `TIMESTAMP`, which is `None` for the zero date.
+pub(super) fn decode_datetime(mut buf: &[u8]) -> Result<Option<(RawDate, RawTime)>, BoxDynError> {
+    let len = buf.get_u8();
+
+    let date = match decode_date_fields(len, buf)? {
+        Some(date) => date,
+        None => return Ok(None),
+    };
+
+    let time = if len > 4 {
+        decode_time_fields(len - 4, &buf[4..])?
+    } else {
+        RawTime::MIDNIGHT
+    };
+
+    Ok(Some((date, time)))
+}
+
+fn encode_date_fields(date: RawDate, buf: &mut Vec<u8>) {
+    // MySQL supports years from 1000 - 9999
+    let year = u16::try_from(date.year)
+        .unwrap_or_else(|_| panic!("year {} out of range for MySQL", date.year));
+
+    buf.extend_from_slice(&year.to_le_bytes());
+    buf.push(date.month);
+    buf.push(date.day);
+}
+
+fn decode_date_fields(len: u8, mut buf: &[u8]) -> Result<Option<RawDate>, BoxDynError> {
+    if len == 0 {
+        // MySQL specifies that if there are no bytes, this is all zeros
+        return Ok(None);
+    }
+
+    if buf.len() < 4 {
+        return Err(format!("expected 4 bytes of a date but found {}", buf.len()).into());
+    }
+
+    Ok(Some(RawDate {
+        year: buf.get_u16_le().into(),
+        month: buf.get_u8(),
+        day: buf.get_u8(),
+    }))
+}
+
+fn encode_time_fields(time: RawTime, include_micros: bool, buf: &mut Vec<u8>) {
+    buf.push(time.hour);
+    buf.push(time.minute);
+    buf.push(time.second);
+
+    if include_micros {
+        buf.extend_from_slice(&time.microsecond.to_le_bytes());
+    }
+}
+
+fn decode_time_fields(len: u8, mut buf: &[u8]) -> Result<RawTime, BoxDynError> {
+    if buf.len() < 3 {
+        return Err(format!("expected 3 bytes of a time but found {}", buf.len()).into());
+    }
+
+    let hour = buf.get_u8();
+    let minute = buf.get_u8();
+    let second = buf.get_u8();
+
+    let microsecond = if len > 3 {
+        // microseconds : int<EOF>
+        buf.get_uint_le(buf.len()) as u32
+    } else {
+        0
+    };
+
+    Ok(RawTime {
+        hour,
+        minute,
+        second,
+        microsecond,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATE: RawDate = RawDate {
+        year: 1969,
+        month: 7,
+        day: 20,
+    };
+
+    const TIME: RawTime = RawTime {
+        hour: 23,
+        minute: 59,
+        second: 59,
+        microsecond: 999_999,
+    };
+
+    #[test]
+    fn it_encodes_and_decodes_dates() {
+        let mut buf = Vec::new();
+        encode_date(DATE, &mut buf);
+
+        assert_eq!(buf, [4, 0xb1, 0x07, 7, 20]);
+        assert_eq!(buf.len(), DATE_SIZE);
+        assert_eq!(decode_date(&buf).unwrap(), Some(DATE));
+        assert_eq!(decode_date(&[0]).unwrap(), None);
+    }
+
+    #[test]
+    fn it_encodes_and_decodes_times_in_shortest_form() {
+        let second = RawTime {
+            microsecond: 0,
+            ..TIME
+        };
+
+        for (time, expected) in [
+            (RawTime::MIDNIGHT, &[0][..]),
+            (second, &[8, 0, 0, 0, 0, 0, 23, 59, 59][..]),
+            (
+                TIME,
+                &[12, 0, 0, 0, 0, 0, 23, 59, 59, 0x3f, 0x42, 0x0f, 0][..],
+            ),
+        ] {
+            let mut buf = Vec::new();
+            encode_time(time, &mut buf);
+
+            assert_eq!(buf, expected);
+            assert_eq!(buf.len(), time_size(time));
+            assert_eq!(decode_time(&buf).unwrap(), time);
+        }
+    }
+
+    #[test]
+    fn it_refuses_times_that_are_not_a_time_of_day() {
+        // -00:00:01 and 24:00:00
+        assert!(decode_time(&[8, 1, 0, 0, 0, 0, 0, 0, 1]).is_err());
+        assert!(decode_time(&[8, 0, 1, 0, 0, 0, 0, 0, 0]).is_err());
+    }
+
+    #[test]
+    fn it_encodes_and_decodes_datetimes_in_shortest_form() {
+        let second = RawTime {
+            microsecond: 0,
+            ..TIME
+        };
+
+        for (time, len) in [(RawTime::MIDNIGHT, 4), (second, 7), (TIME, 11)] {
+            let mut buf = Vec::new();
+            encode_datetime(DATE, time, &mut buf);
+
+            assert_eq!(buf[0], len);
+            assert_eq!(buf.len(), datetime_size(time));
+            assert_eq!(decode_datetime(&buf).unwrap(), Some((DATE, time)));
+        }
+
+        assert_eq!(decode_datetime(&[0]).unwrap(), None);
+    }
+}
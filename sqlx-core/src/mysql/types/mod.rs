@@ -16,6 +16,7 @@
 //! | `f32`                                 | FLOAT                                                |
 //! | `f64`                                 | DOUBLE                                               |
 //! | `&str`, [`String`]                    | VARCHAR, CHAR, TEXT                                  |
+//! | `char`                                | CHAR(1), VARCHAR, TEXT                               |
 //! | `&[u8]`, `Vec<u8>`                    | VARBINARY, BINARY, BLOB                              |
 //!
 //! ### [`chrono`](https://crates.io/crates/chrono)
@@ -29,6 +30,7 @@
 //! | `chrono::NaiveDateTime`               | DATETIME                                             |
 //! | `chrono::NaiveDate`                   | DATE                                                 |
 //! | `chrono::NaiveTime`                   | TIME                                                 |
+//! | `chrono::Duration`                    | TIME                                                 |
 //!
 //! ### [`time`](https://crates.io/crates/time)
 //!
@@ -80,6 +82,7 @@
 
 mod bool;
 mod bytes;
+mod char;
 mod float;
 mod int;
 mod str;
@@ -94,6 +97,9 @@ mod decimal;
 #[cfg(feature = "chrono")]
 mod chrono;
 
+#[cfg(any(feature = "chrono", feature = "time"))]
+mod datetime;
+
 #[cfg(feature = "time")]
 mod time;
 
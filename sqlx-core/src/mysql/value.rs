@@ -6,10 +6,15 @@ use bytes::Bytes;
 use std::borrow::Cow;
 use std::str::from_utf8;
 
-#[derive(Debug, Clone, Copy)]
+/// The format of the values of a row, which every [`Decode`](crate::decode::Decode)
+/// implementation for MySQL accepts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
 pub enum MySqlValueFormat {
+    /// Values as strings, in the results of a query without arguments.
     Text,
+
+    /// Values in their binary encoding, in the results of a prepared statement.
     Binary,
 }
 
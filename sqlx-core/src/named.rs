@@ -0,0 +1,112 @@
+//! Named parameters (`:name`) of queries made with [`query_named`](crate::query::query_named),
+//! which the drivers rewrite to their own placeholders before the query is sent.
+
+use std::ops::Range;
+
+use crate::error::Error;
+
+fn is_name_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_' || b >= 0x80
+}
+
+/// Returns the range of the name of the `:name` parameter starting at `i`, if any.
+///
+/// A `:` that follows a name or another `:` does not start a parameter, so neither the
+/// `::` of a cast nor the `:` of an array slice such as `a[1:n]` is mistaken for one. The
+/// caller is expected to have skipped string literals, quoted identifiers and comments.
+pub(crate) fn name_at(bytes: &[u8], i: usize) -> Option<Range<usize>> {
+    if bytes[i] != b':' {
+        return None;
+    }
+
+    if i > 0 && (is_name_byte(bytes[i - 1]) || bytes[i - 1] == b':') {
+        return None;
+    }
+
+    let start = i + 1;
+
+    match bytes.get(start) {
+        Some(&b) if is_name_byte(b) && !b.is_ascii_digit() => {}
+        _ => return None,
+    }
+
+    let len = bytes[start..]
+        .iter()
+        .take_while(|&&b| is_name_byte(b))
+        .count();
+
+    Some(start..start + len)
+}
+
+/// Checks that every name used by the query was bound exactly once, and that every bound name
+/// is used by the query.
+pub(crate) fn check_names<'a>(
+    used: impl IntoIterator<Item = &'a str>,
+    bound: &[&str],
+) -> Result<(), Error> {
+    let mut missing: Vec<String> = Vec::new();
+    let mut seen: Vec<&str> = Vec::new();
+
+    for name in used {
+        if seen.contains(&name) {
+            continue;
+        }
+
+        seen.push(name);
+
+        if !bound.iter().any(|&bound| bound == name) {
+            missing.push(name.to_owned());
+        }
+    }
+
+    // a name bound twice is extra, as only one of its values could be used
+    let extra: Vec<String> = bound
+        .iter()
+        .enumerate()
+        .filter(|&(i, &name)| !seen.iter().any(|&used| used == name) || bound[..i].contains(&name))
+        .map(|(_, &name)| name.to_owned())
+        .collect();
+
+    if missing.is_empty() && extra.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::NamedParameterMismatch { missing, extra })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(sql: &str) -> Vec<&str> {
+        let bytes = sql.as_bytes();
+
+        (0..bytes.len())
+            .filter_map(|i| name_at(bytes, i))
+            .map(|range| &sql[range])
+            .collect()
+    }
+
+    #[test]
+    fn it_finds_names() {
+        assert_eq!(names("SELECT :a, :b_2"), vec!["a", "b_2"]);
+        assert_eq!(names("SELECT :a::text, x::int"), vec!["a"]);
+        assert_eq!(names("SELECT a[1:n], a[:n], :1"), vec!["n"]);
+        assert_eq!(names("SELECT :été"), vec!["été"]);
+    }
+
+    #[test]
+    fn it_checks_names() {
+        assert!(check_names(vec!["a", "b", "a"], &["b", "a"]).is_ok());
+        assert!(check_names(vec![], &[]).is_ok());
+
+        match check_names(vec!["a", "b", "b"], &["a", "c", "a"]) {
+            Err(Error::NamedParameterMismatch { missing, extra }) => {
+                assert_eq!(missing, vec!["b"]);
+                assert_eq!(extra, vec!["c", "a"]);
+            }
+
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+}
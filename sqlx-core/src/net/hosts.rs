@@ -0,0 +1,236 @@
+use std::borrow::Cow;
+use std::future::Future;
+use std::io;
+
+use percent_encoding::percent_decode_str;
+use url::{Host, Url};
+
+use crate::error::Error;
+use crate::instrument;
+use crate::net::decode_url_part;
+
+/// Splits the hosts to fall back to off a connection URL that lists several hosts, as in
+/// `postgres://user@host1:5432,host2:5432/database`, which cannot be parsed as a [`Url`].
+///
+/// Returns the URL with only the first host, along with each of the other hosts and its port,
+/// if it has one.
+pub(crate) fn split_hosts(url: &str) -> Result<(Cow<'_, str>, Vec<(String, Option<u16>)>), Error> {
+    let authority_start = match url.find("://") {
+        Some(index) => index + 3,
+        None => return Ok((Cow::Borrowed(url), Vec::new())),
+    };
+
+    let authority_end = url[authority_start..]
+        .find(|c| matches!(c, '/' | '?' | '#'))
+        .map_or(url.len(), |index| authority_start + index);
+
+    // the hosts follow the credentials, if there are any
+    let hosts_start = url[authority_start..authority_end]
+        .rfind('@')
+        .map_or(authority_start, |index| authority_start + index + 1);
+
+    let hosts = &url[hosts_start..authority_end];
+
+    if !hosts.contains(',') {
+        return Ok((Cow::Borrowed(url), Vec::new()));
+    }
+
+    let mut hosts = hosts.split(',');
+    let first = hosts.next().unwrap_or_default();
+    let fallbacks = hosts.map(parse_host).collect::<Result<_, _>>()?;
+
+    let url = format!("{}{}{}", &url[..hosts_start], first, &url[authority_end..]);
+
+    Ok((Cow::Owned(url), fallbacks))
+}
+
+// parses `host`, `host:port`, `[address]` or `[address]:port`
+fn parse_host(entry: &str) -> Result<(String, Option<u16>), Error> {
+    let (host, port) = if let Some(rest) = entry.strip_prefix('[') {
+        let end = rest.find(']').ok_or_else(|| invalid_host(entry))?;
+
+        let port = match &rest[end + 1..] {
+            "" => None,
+            port => Some(port.strip_prefix(':').ok_or_else(|| invalid_host(entry))?),
+        };
+
+        (&rest[..end], port)
+    } else {
+        match entry.rsplit_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (entry, None),
+        }
+    };
+
+    if host.is_empty() {
+        return Err(invalid_host(entry));
+    }
+
+    let host = percent_decode_str(host)
+        .decode_utf8()
+        .map_err(Error::config)?
+        .into_owned();
+
+    let port = port
+        .map(|port| port.parse().map_err(|_| invalid_host(entry)))
+        .transpose()?;
+
+    Ok((host, port))
+}
+
+fn invalid_host(entry: &str) -> Error {
+    Error::Configuration(format!("invalid host in connection URL: {:?}", entry).into())
+}
+
+/// The host of a URL, without the brackets around an IPv6 address.
+///
+/// The host of a URL whose scheme is not one the `url` crate knows, as are those of SQLx, is
+/// kept as it was written, so it is percent-decoded here.
+pub(crate) fn url_host(url: &Url) -> Result<Option<String>, Error> {
+    match url.host() {
+        Some(Host::Domain(host)) => decode_url_part(host, "host").map(Some),
+        Some(Host::Ipv6(address)) => Ok(Some(address.to_string())),
+        Some(host) => Ok(Some(host.to_string())),
+        None => Ok(None),
+    }
+}
+
+/// Connects to each of `hosts` in turn, until a connection is established.
+///
+/// When there are several hosts and none of them could be connected to, the error lists why for
+/// each of them.
+pub(crate) async fn connect_any<'h, T, F, Fut>(
+    hosts: &'h [(String, u16)],
+    mut connect: F,
+) -> Result<T, Error>
+where
+    F: FnMut(&'h str, u16) -> Fut,
+    Fut: Future<Output = Result<T, Error>>,
+{
+    let mut errors = Vec::with_capacity(hosts.len());
+
+    for (host, port) in hosts {
+        match connect(host, *port).await {
+            Ok(conn) => return Ok(conn),
+            Err(error) if hosts.len() == 1 => return Err(error),
+
+            Err(error) => {
+                instrument::fall_back(host, *port, &error);
+                log::debug!(
+                    "failed to connect to {}: {}",
+                    display_host(host, *port),
+                    error
+                );
+                errors.push(format!("{}: {}", display_host(host, *port), error));
+            }
+        }
+    }
+
+    Err(Error::Io(io::Error::new(
+        io::ErrorKind::Other,
+        format!("failed to connect to any host ({})", errors.join("; ")),
+    )))
+}
+
+fn display_host(host: &str, port: u16) -> String {
+    if host.contains(':') {
+        format!("[{}]:{}", host, port)
+    } else {
+        format!("{}:{}", host, port)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_splits_hosts_off_urls() {
+        let (url, hosts) = split_hosts("postgres://user@localhost:5432/database").unwrap();
+
+        assert_eq!(url, "postgres://user@localhost:5432/database");
+        assert!(hosts.is_empty());
+
+        let (url, hosts) = split_hosts(
+            "postgres://user:p@ss@host1:5432,host2,[2001:db8::1]:5433/database?sslmode=require",
+        )
+        .unwrap();
+
+        assert_eq!(
+            url,
+            "postgres://user:p@ss@host1:5432/database?sslmode=require"
+        );
+        assert_eq!(
+            hosts,
+            [
+                ("host2".to_owned(), None),
+                ("2001:db8::1".to_owned(), Some(5433))
+            ]
+        );
+
+        let (url, hosts) = split_hosts("mysql://host1,host2:3307").unwrap();
+
+        assert_eq!(url, "mysql://host1");
+        assert_eq!(hosts, [("host2".to_owned(), Some(3307))]);
+
+        assert!(split_hosts("postgres://host1,:5432/database").is_err());
+        assert!(split_hosts("postgres://host1,host2:port/database").is_err());
+        assert!(split_hosts("postgres://host1,[2001:db8::1/database").is_err());
+    }
+
+    #[test]
+    fn it_reads_ipv6_hosts_without_brackets() {
+        let url: Url = "postgresql://[2001:db8::1]:5432/database".parse().unwrap();
+
+        assert_eq!(url_host(&url).unwrap().as_deref(), Some("2001:db8::1"));
+        assert_eq!(url.port(), Some(5432));
+
+        let url: Url = "mysql://localhost/database".parse().unwrap();
+
+        assert_eq!(url_host(&url).unwrap().as_deref(), Some("localhost"));
+    }
+
+    #[test]
+    fn it_decodes_the_host_once() {
+        let url: Url = "postgres://%2Fvar%2Frun%2Fpostgresql/database"
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            url_host(&url).unwrap().as_deref(),
+            Some("/var/run/postgresql")
+        );
+
+        let url: Url = "postgres://db%252Dhost/database".parse().unwrap();
+
+        assert_eq!(url_host(&url).unwrap().as_deref(), Some("db%2Dhost"));
+    }
+
+    #[test]
+    fn it_combines_the_errors_of_every_host() {
+        let hosts = vec![("host1".to_owned(), 5432), ("2001:db8::1".to_owned(), 5433)];
+
+        let error = sqlx_rt::block_on(connect_any(&hosts, |host, _| async move {
+            Err::<(), _>(Error::Protocol(format!("{} is down", host)))
+        }))
+        .unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "error communicating with database: failed to connect to any host \
+             (host1:5432: encountered unexpected or invalid data: host1 is down; \
+             [2001:db8::1]:5433: encountered unexpected or invalid data: 2001:db8::1 is down)"
+        );
+
+        // the first host that can be connected to is used
+        let connected = sqlx_rt::block_on(connect_any(&hosts, |host, port| async move {
+            if host == "host1" {
+                Err(Error::Protocol("refused".into()))
+            } else {
+                Ok(port)
+            }
+        }));
+
+        assert_eq!(connected.unwrap(), 5433);
+    }
+}
@@ -1,6 +1,10 @@
+mod connection_url;
+mod hosts;
 mod socket;
 mod tls;
 
+pub(crate) use connection_url::{decode_url_part, parse_url};
+pub(crate) use hosts::{connect_any, split_hosts, url_host};
 pub use socket::Socket;
 pub use tls::{CertificateInput, MaybeTlsStream};
 
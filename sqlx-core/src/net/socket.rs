@@ -13,6 +13,10 @@ pub enum Socket {
 
     #[cfg(unix)]
     Unix(sqlx_rt::UnixStream),
+
+    // replays the packets of a test instead of talking to a server
+    #[cfg(test)]
+    Scripted(crate::io::scripted::ScriptedStream),
 }
 
 impl Socket {
@@ -35,6 +39,23 @@ impl Socket {
         ))
     }
 
+    /// Returns `true` if the peer has closed the socket, without waiting for or consuming any
+    /// data.
+    ///
+    /// Only TCP sockets are looked at; a Unix domain socket is assumed to be open. With
+    /// async-std, a socket that the peer sent data on before closing it is not detected either.
+    pub fn is_closed(&self) -> bool {
+        match self {
+            Socket::Tcp(s) => tcp_peer_closed(s),
+
+            #[cfg(unix)]
+            Socket::Unix(_) => false,
+
+            #[cfg(test)]
+            Socket::Scripted(_) => false,
+        }
+    }
+
     pub async fn shutdown(&mut self) -> io::Result<()> {
         #[cfg(feature = "_rt-async-std")]
         {
@@ -45,6 +66,9 @@ impl Socket {
 
                 #[cfg(unix)]
                 Socket::Unix(s) => s.shutdown(Shutdown::Both),
+
+                #[cfg(test)]
+                Socket::Scripted(_) => Ok(()),
             }
         }
 
@@ -57,11 +81,51 @@ impl Socket {
 
                 #[cfg(unix)]
                 Socket::Unix(s) => s.shutdown().await,
+
+                #[cfg(test)]
+                Socket::Scripted(s) => s.shutdown().await,
             }
         }
     }
 }
 
+// polls the socket once, without waiting, to tell whether the peer has closed it
+fn tcp_peer_closed(stream: &TcpStream) -> bool {
+    use futures_core::Future;
+
+    let mut cx = Context::from_waker(futures_util::task::noop_waker_ref());
+
+    // the reactor keeps track of whether the peer has closed the socket, even if the data it
+    // sent before, e.g. an error telling why, was not read yet
+    #[cfg(any(feature = "_rt-actix", feature = "_rt-tokio"))]
+    {
+        let ready = stream.ready(sqlx_rt::tokio::io::Interest::READABLE);
+        futures_util::pin_mut!(ready);
+
+        match ready.poll(&mut cx) {
+            Poll::Ready(Ok(ready)) => ready.is_read_closed(),
+            Poll::Ready(Err(_)) => true,
+            Poll::Pending => false,
+        }
+    }
+
+    // a closed socket is readable and returns no data, while an open one either has data
+    // waiting or would block
+    #[cfg(feature = "_rt-async-std")]
+    {
+        let mut byte = [0_u8; 1];
+
+        let peek = stream.peek(&mut byte);
+        futures_util::pin_mut!(peek);
+
+        match peek.poll(&mut cx) {
+            Poll::Ready(Ok(n)) => n == 0,
+            Poll::Ready(Err(_)) => true,
+            Poll::Pending => false,
+        }
+    }
+}
+
 impl AsyncRead for Socket {
     fn poll_read(
         mut self: Pin<&mut Self>,
@@ -73,6 +137,9 @@ impl AsyncRead for Socket {
 
             #[cfg(unix)]
             Socket::Unix(s) => Pin::new(s).poll_read(cx, buf),
+
+            #[cfg(test)]
+            Socket::Scripted(s) => Pin::new(s).poll_read(cx, buf),
         }
     }
 }
@@ -88,6 +155,9 @@ impl AsyncWrite for Socket {
 
             #[cfg(unix)]
             Socket::Unix(s) => Pin::new(s).poll_write(cx, buf),
+
+            #[cfg(test)]
+            Socket::Scripted(s) => Pin::new(s).poll_write(cx, buf),
         }
     }
 
@@ -97,6 +167,9 @@ impl AsyncWrite for Socket {
 
             #[cfg(unix)]
             Socket::Unix(s) => Pin::new(s).poll_flush(cx),
+
+            #[cfg(test)]
+            Socket::Scripted(s) => Pin::new(s).poll_flush(cx),
         }
     }
 
@@ -107,6 +180,9 @@ impl AsyncWrite for Socket {
 
             #[cfg(unix)]
             Socket::Unix(s) => Pin::new(s).poll_shutdown(cx),
+
+            #[cfg(test)]
+            Socket::Scripted(s) => Pin::new(s).poll_shutdown(cx),
         }
     }
 
@@ -117,6 +193,9 @@ impl AsyncWrite for Socket {
 
             #[cfg(unix)]
             Socket::Unix(s) => Pin::new(s).poll_close(cx),
+
+            #[cfg(test)]
+            Socket::Scripted(s) => Pin::new(s).poll_close(cx),
         }
     }
 }
@@ -0,0 +1,96 @@
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+
+use crate::column::{Column, ColumnIndex};
+use crate::database::Database;
+use crate::error::Error;
+
+/// A row that owns a copy of its values, independent of the connection it was fetched from.
+///
+/// An `OwnedRow` holds the columns of a row along with a copy of each of its values, so it can
+/// be cloned, sent between threads and kept around, for instance in a cache of query results.
+/// It implements [`Row`](crate::row::Row) for the database it was fetched from: values are
+/// decoded from it and types that implement [`FromRow`](crate::from_row::FromRow) are built from
+/// it in the same way as from the row it was copied from.
+///
+/// An `OwnedRow<Postgres>` or `OwnedRow<MySql>` is copied from a `PgRow` or `MySqlRow` with
+/// their `to_owned` method or [`From`], or is fetched directly with
+/// `query_as::<_, OwnedRow<_>>(..)`.
+///
+/// With the `offline` feature, `OwnedRow` implements `Serialize` and `Deserialize` to be kept
+/// outside of the process.
+#[cfg_attr(feature = "offline", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "offline",
+    serde(bound(
+        serialize = "DB::Column: serde::Serialize",
+        deserialize = "DB::Column: serde::de::DeserializeOwned"
+    ))
+)]
+pub struct OwnedRow<DB: Database> {
+    pub(crate) columns: Arc<Vec<DB::Column>>,
+    pub(crate) values: Vec<Option<Vec<u8>>>,
+    pub(crate) format: OwnedValueFormat,
+    // see `Query::text_results`
+    #[cfg_attr(feature = "offline", serde(default))]
+    pub(crate) text_results: bool,
+}
+
+/// The format every value of an [`OwnedRow`] is encoded in, as sent by the database.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "offline", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) enum OwnedValueFormat {
+    Text,
+    Binary,
+}
+
+impl<DB: Database> OwnedRow<DB> {
+    pub(crate) fn value(&self, index: usize) -> Option<&[u8]> {
+        self.values[index].as_deref()
+    }
+}
+
+impl<DB: Database> crate::row::private_row::Sealed for OwnedRow<DB> {}
+
+impl<DB: Database> Clone for OwnedRow<DB> {
+    fn clone(&self) -> Self {
+        OwnedRow {
+            columns: Arc::clone(&self.columns),
+            values: self.values.clone(),
+            format: self.format,
+            text_results: self.text_results,
+        }
+    }
+}
+
+impl<DB: Database> Debug for OwnedRow<DB> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OwnedRow")
+            .field("columns", &self.columns)
+            .field("values", &self.values)
+            .field("format", &self.format)
+            .field("text_results", &self.text_results)
+            .finish()
+    }
+}
+
+impl<DB: Database> ColumnIndex<OwnedRow<DB>> for usize {
+    fn index(&self, row: &OwnedRow<DB>) -> Result<usize, Error> {
+        let len = row.values.len();
+
+        if *self >= len {
+            return Err(Error::ColumnIndexOutOfBounds { len, index: *self });
+        }
+
+        Ok(*self)
+    }
+}
+
+impl<DB: Database> ColumnIndex<OwnedRow<DB>> for &'_ str {
+    fn index(&self, row: &OwnedRow<DB>) -> Result<usize, Error> {
+        row.columns
+            .iter()
+            .position(|column| column.name() == *self)
+            .ok_or_else(|| Error::ColumnNotFound((*self).into()))
+    }
+}
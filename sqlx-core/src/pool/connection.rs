@@ -1,11 +1,11 @@
 use std::fmt::{self, Debug, Formatter};
 use std::ops::{Deref, DerefMut};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::Instant;
 
 use futures_intrusive::sync::SemaphoreReleaser;
 
-use crate::connection::Connection;
+use crate::connection::{Connection, ConnectionStats};
 use crate::database::Database;
 use crate::error::Error;
 
@@ -14,7 +14,11 @@ use std::future::Future;
 
 /// A connection managed by a [`Pool`][crate::pool::Pool].
 ///
-/// Will be returned to the pool on-drop.
+/// Will be returned to the pool on-drop, or closed if it is dropped while its thread panics.
+///
+/// A connection keeps the pool it was checked out from alive, so it can still be used after
+/// every [`Pool`][crate::pool::Pool] handle is dropped or the pool is closed; it is closed rather
+/// than returned to the pool then.
 pub struct PoolConnection<DB: Database> {
     live: Option<Live<DB>>,
     pub(crate) pool: Arc<SharedPool<DB>>,
@@ -23,6 +27,10 @@ pub struct PoolConnection<DB: Database> {
 pub(super) struct Live<DB: Database> {
     pub(super) raw: DB::Connection,
     pub(super) created: Instant,
+
+    // the statistics of the connection as of when it was last released to the pool,
+    // tracked by the pool for as long as the connection is alive
+    pub(super) stats: Arc<Mutex<ConnectionStats>>,
 }
 
 pub(super) struct Idle<DB: Database> {
@@ -96,16 +104,47 @@ impl<DB: Database> PoolConnection<DB> {
     pub(crate) fn return_to_pool(&mut self) -> impl Future<Output = ()> + Send + 'static {
         // we want these to happen synchronously so the drop handler doesn't try to spawn a task anyway
         // this also makes the returned future `'static`
-        let live = self.live.take();
-        let pool = self.pool.clone();
+        let returning = Returning {
+            live: self.live.take(),
+            pool: self.pool.clone(),
+        };
 
         async move {
-            let mut floating = if let Some(live) = live {
-                live.float(&pool)
+            let mut returning = returning;
+
+            let mut floating = if let Some(live) = returning.live.take() {
+                live.float(&returning.pool)
             } else {
                 return;
             };
 
+            // an operation was abandoned before it finished; every operation started on the
+            // connection would fail, so don't bother testing it
+            if floating.raw.is_busy() {
+                log::warn!("connection was released to the pool in the middle of an operation");
+
+                drop(floating);
+                return;
+            }
+
+            // the database has closed the connection, there is nothing left to test
+            if floating.raw.is_broken() {
+                log::info!("connection was closed by the database; discarding it");
+
+                drop(floating);
+                return;
+            }
+
+            // the pool was closed or dropped, or resized down, while the connection was checked
+            // out
+            if returning.pool.is_closed() || returning.pool.is_oversized() {
+                if let Err(e) = floating.close().await {
+                    log::debug!("error occurred while closing the pool connection: {}", e);
+                }
+
+                return;
+            }
+
             // test the connection on-release to ensure it is still viable
             // if an Executor future/stream is dropped during an `.await` call, the connection
             // is likely to be left in an inconsistent state, in which case it should not be
@@ -122,20 +161,51 @@ impl<DB: Database> PoolConnection<DB> {
                 // trying to close gracefully might cause something weird to happen
                 drop(floating);
             } else {
+                // don't keep memory for a large result around while the connection is idle
+                floating.raw.shrink_buffers();
+
                 // if the connection is still viable, release it to the pool
-                pool.release(floating);
+                returning.pool.release(floating).await;
             }
         }
     }
 }
 
+/// A connection on its way back to the pool in [`PoolConnection::return_to_pool`], which is
+/// closed if the future returning it is dropped before it starts, e.g. along with the runtime.
+struct Returning<DB: Database> {
+    live: Option<Live<DB>>,
+    pool: Arc<SharedPool<DB>>,
+}
+
+impl<DB: Database> Drop for Returning<DB> {
+    fn drop(&mut self) {
+        if let Some(live) = self.live.take() {
+            // the guard frees the place of the connection in the pool
+            drop(live.float(&self.pool));
+        }
+    }
+}
+
 /// Returns the connection to the [`Pool`][crate::pool::Pool] it was checked-out from.
 impl<DB: Database> Drop for PoolConnection<DB> {
     fn drop(&mut self) {
+        // the connection is in an unknown state if the thread panicked while it was checked out
+        if std::thread::panicking() {
+            if let Some(live) = self.live.take() {
+                self.pool.drop_on_panic(live);
+            }
+
+            return;
+        }
+
         if self.live.is_some() {
+            // without a runtime to return the connection, it is closed rather than leaked
             #[cfg(not(feature = "_rt-async-std"))]
             if let Ok(handle) = sqlx_rt::Handle::try_current() {
                 handle.spawn(self.return_to_pool());
+            } else {
+                drop(self.return_to_pool());
             }
 
             #[cfg(feature = "_rt-async-std")]
@@ -176,11 +246,16 @@ impl<DB: Database> DerefMut for Idle<DB> {
 }
 
 impl<'s, DB: Database> Floating<'s, Live<DB>> {
-    pub fn new_live(conn: DB::Connection, guard: DecrementSizeGuard<'s>) -> Self {
+    pub fn new_live(
+        conn: DB::Connection,
+        stats: Arc<Mutex<ConnectionStats>>,
+        guard: DecrementSizeGuard<'s>,
+    ) -> Self {
         Self {
             inner: Live {
                 raw: conn,
                 created: Instant::now(),
+                stats,
             },
             guard,
         }
@@ -7,6 +7,7 @@ use crate::database::{Database, HasStatement};
 use crate::describe::Describe;
 use crate::error::Error;
 use crate::executor::{Execute, Executor};
+use crate::pool::split::starts_with_select;
 use crate::pool::Pool;
 
 impl<'p, DB: Database> Executor<'p> for &'_ Pool<DB>
@@ -45,7 +46,27 @@ where
     {
         let pool = self.clone();
 
-        Box::pin(async move { pool.acquire().await?.fetch_optional(query).await })
+        Box::pin(async move {
+            match retry_of(&query) {
+                Some(retry) => Ok(fetch_retrying(&pool, query, retry, Some(1)).await?.pop()),
+
+                None => pool.acquire().await?.fetch_optional(query).await,
+            }
+        })
+    }
+
+    fn fetch_all<'e, 'q: 'e, E: 'q>(self, query: E) -> BoxFuture<'e, Result<Vec<DB::Row>, Error>>
+    where
+        E: Execute<'q, Self::Database>,
+    {
+        let pool = self.clone();
+
+        Box::pin(async move {
+            match retry_of(&query) {
+                Some(retry) => fetch_retrying(&pool, query, retry, None).await,
+                None => pool.acquire().await?.fetch_all(query).await,
+            }
+        })
     }
 
     fn prepare_with<'e, 'q: 'e>(
@@ -69,6 +90,74 @@ where
     }
 }
 
+// the copy of a query to run again if it is lost with its connection, see `Query::retry_transient`
+fn retry_of<'q, DB: Database, E: Execute<'q, DB>>(query: &E) -> Option<E> {
+    if query.read_only() == Some(false) || !starts_with_select(query.sql()) {
+        return None;
+    }
+
+    query.retry()
+}
+
+// fetches at most `limit` rows, running `retry` on a new connection if the connection is lost
+// before anything was returned for `query`
+async fn fetch_retrying<'q, DB, E>(
+    pool: &Pool<DB>,
+    query: E,
+    retry: E,
+    limit: Option<usize>,
+) -> Result<Vec<DB::Row>, Error>
+where
+    DB: Database,
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+    E: 'q + Execute<'q, DB>,
+{
+    let mut retry = Some(retry);
+    let mut query = query;
+
+    loop {
+        let mut conn = pool.acquire().await?;
+        let mut s = conn.fetch_many(query);
+
+        let mut rows = Vec::new();
+        let mut returned = false;
+
+        let error = loop {
+            match s.try_next().await {
+                Ok(Some(Either::Left(_))) => returned = true,
+
+                Ok(Some(Either::Right(row))) => {
+                    returned = true;
+
+                    // the rows past the limit are still read, so that the query finishes and
+                    // its connection can go back to the pool
+                    if Some(rows.len()) != limit {
+                        rows.push(row);
+                    }
+                }
+
+                Ok(None) => return Ok(rows),
+
+                // as if the query had not been read any further
+                Err(_) if Some(rows.len()) == limit => return Ok(rows),
+
+                Err(error) => break error,
+            }
+        };
+
+        let lost = matches!(error, Error::ConnectionClosed(_) | Error::Io(_));
+
+        match retry.take() {
+            Some(next) if lost && !returned => {
+                log::warn!("retrying query on a new connection: {}", error);
+                query = next;
+            }
+
+            _ => return Err(error),
+        }
+    }
+}
+
 // NOTE: required due to lack of lazy normalization
 #[allow(unused_macros)]
 macro_rules! impl_executor_for_pool_connection {
@@ -106,6 +195,18 @@ macro_rules! impl_executor_for_pool_connection {
                 (**self).fetch_optional(query)
             }
 
+            #[inline]
+            fn fetch_all<'e, 'q: 'e, E: 'q>(
+                self,
+                query: E,
+            ) -> futures_core::future::BoxFuture<'e, Result<Vec<$R>, crate::error::Error>>
+            where
+                'c: 'e,
+                E: crate::executor::Execute<'q, $DB>,
+            {
+                (**self).fetch_all(query)
+            }
+
             #[inline]
             fn prepare_with<'e, 'q: 'e>(
                 self,
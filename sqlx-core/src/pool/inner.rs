@@ -1,18 +1,20 @@
 use super::connection::{Floating, Idle, Live};
 use crate::connection::ConnectOptions;
-use crate::connection::Connection;
+use crate::connection::{Connection, ConnectionStats};
 use crate::database::Database;
 use crate::error::Error;
+use crate::instrument;
 use crate::pool::{deadline_as_timeout, PoolOptions};
-use crossbeam_queue::ArrayQueue;
+use crossbeam_queue::SegQueue;
 
 use futures_intrusive::sync::{Semaphore, SemaphoreReleaser};
 
 use std::cmp;
+use std::future::Future;
 use std::mem;
 use std::ptr;
-use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock, Weak};
 
 use std::time::{Duration, Instant};
 
@@ -22,13 +24,60 @@ use std::time::{Duration, Instant};
 /// potentially overflowing the permits count in the semaphore itself.
 const WAKE_ALL_PERMITS: usize = usize::MAX / 2;
 
+/// How long the pool waits before it opens a connection again after the database refused one
+/// because it has as many as it allows.
+///
+/// This doubles with every refusal, up to [`MAX_REFUSAL_BACKOFF`].
+const REFUSAL_BACKOFF: Duration = Duration::from_millis(100);
+
+const MAX_REFUSAL_BACKOFF: Duration = Duration::from_secs(5);
+
 pub(crate) struct SharedPool<DB: Database> {
-    pub(super) connect_options: <DB::Connection as Connection>::Options,
-    pub(super) idle_conns: ArrayQueue<Idle<DB>>,
+    // replaced as a whole, so that a connection is opened with either the old or the new options
+    connect_options: RwLock<Arc<<DB::Connection as Connection>::Options>>,
+    // not bounded by `max_connections`, which may be raised by `Pool::resize`
+    pub(super) idle_conns: SegQueue<Idle<DB>>,
     pub(super) semaphore: Semaphore,
     pub(super) size: AtomicU32,
+
+    // the current maximum size of the pool; see `Pool::resize`
+    max_connections: AtomicU32,
+
+    // the permits still to be taken out of the semaphore after the pool was resized down,
+    // which are forgotten instead of being released as the connections holding them are closed
+    excess_permits: AtomicUsize,
+
     is_closed: AtomicBool,
     pub(super) options: PoolOptions<DB>,
+
+    // the number of `Pool` handles; the pool is closed along with the last one
+    handles: AtomicUsize,
+
+    // the statistics of every connection of the pool, which are dropped along with it
+    connection_stats: Mutex<Vec<Weak<Mutex<ConnectionStats>>>>,
+
+    // see `Pool::connections_dropped_on_panic`
+    connections_dropped_on_panic: AtomicU64,
+
+    // the new connections the database refused since the pool last opened one
+    refusals: Mutex<Refusals>,
+}
+
+// see `SharedPool::refuse`
+#[derive(Default)]
+struct Refusals {
+    count: u32,
+
+    // the permits kept out of the semaphore, so that the pool does not grow
+    withheld: usize,
+
+    // bumped when the withheld permits are all released at once, so that the ones released
+    // after a backoff are not released twice
+    generation: u64,
+
+    // what the database said the last time; see `Error::ServerConnectionLimit`
+    limit: Option<u32>,
+    message: String,
 }
 
 impl<DB: Database> SharedPool<DB> {
@@ -45,12 +94,19 @@ impl<DB: Database> SharedPool<DB> {
             .expect("max_connections exceeds max capacity of the pool");
 
         let pool = Self {
-            connect_options,
-            idle_conns: ArrayQueue::new(capacity),
+            connect_options: RwLock::new(Arc::new(connect_options)),
+            idle_conns: SegQueue::new(),
             semaphore: Semaphore::new(options.fair, capacity),
             size: AtomicU32::new(0),
+            max_connections: AtomicU32::new(options.max_connections),
+            excess_permits: AtomicUsize::new(0),
             is_closed: AtomicBool::new(false),
             options,
+            // the `Pool` this is created for
+            handles: AtomicUsize::new(1),
+            connection_stats: Mutex::new(Vec::new()),
+            connections_dropped_on_panic: AtomicU64::new(0),
+            refusals: Mutex::new(Refusals::default()),
         };
 
         let pool = Arc::new(pool);
@@ -60,20 +116,144 @@ impl<DB: Database> SharedPool<DB> {
         pool
     }
 
+    pub(super) fn connect_options(&self) -> Arc<<DB::Connection as Connection>::Options> {
+        Arc::clone(&self.connect_options.read().unwrap())
+    }
+
+    pub(super) fn set_connect_options(&self, options: <DB::Connection as Connection>::Options) {
+        *self.connect_options.write().unwrap() = Arc::new(options);
+    }
+
     pub(super) fn size(&self) -> u32 {
         self.size.load(Ordering::Acquire)
     }
 
+    pub(super) fn max_connections(&self) -> u32 {
+        self.max_connections.load(Ordering::Acquire)
+    }
+
+    // whether the pool has more connections than it may keep since it was resized down
+    pub(super) fn is_oversized(&self) -> bool {
+        self.size() > self.max_connections()
+    }
+
+    pub(super) async fn resize(&self, max_connections: u32) {
+        if self.is_closed() {
+            return;
+        }
+
+        let previous = self.max_connections.swap(max_connections, Ordering::AcqRel);
+
+        if max_connections >= previous {
+            let added = (max_connections - previous) as usize;
+
+            // the permits not taken out yet since the pool was resized down are simply kept
+            let kept = take_excess(&self.excess_permits, added);
+
+            // this wakes the tasks waiting for a connection, which may now open one
+            self.semaphore.release(added - kept);
+            return;
+        }
+
+        self.excess_permits
+            .fetch_add((previous - max_connections) as usize, Ordering::AcqRel);
+
+        // idle connections are closed right away; the ones in use as they are returned
+        while self.is_oversized() {
+            match self.try_acquire() {
+                Some(idle) => drop(idle.close().await),
+                None => break,
+            }
+        }
+
+        // the permits not held by any connection are taken out now as well
+        let excess = self.excess_permits.load(Ordering::Acquire);
+        let spare = self
+            .semaphore
+            .permits()
+            .saturating_sub(self.idle_conns.len());
+        let taken = cmp::min(excess, spare);
+
+        if taken == 0 {
+            return;
+        }
+
+        if let Some(mut permits) = self.semaphore.try_acquire(taken) {
+            permits.disarm();
+
+            // some may have been forgotten in the meantime
+            let forgotten = take_excess(&self.excess_permits, taken);
+            self.semaphore.release(taken - forgotten);
+        }
+    }
+
     pub(super) fn num_idle(&self) -> usize {
         // NOTE: This is very expensive
         self.idle_conns.len()
     }
 
+    pub(super) fn connection_stats(&self) -> Vec<ConnectionStats> {
+        self.connection_stats
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(Weak::upgrade)
+            .map(|stats| stats.lock().unwrap().clone())
+            .collect()
+    }
+
+    pub(super) fn connections_dropped_on_panic(&self) -> u64 {
+        self.connections_dropped_on_panic.load(Ordering::Relaxed)
+    }
+
+    // closes a connection that was checked out when its thread panicked, as it may have been
+    // in the middle of an operation; its permit goes back to the pool so that the next
+    // `acquire` opens a new connection
+    pub(super) fn drop_on_panic(&self, live: Live<DB>) {
+        self.connections_dropped_on_panic
+            .fetch_add(1, Ordering::Relaxed);
+
+        log::warn!("connection was checked out when its thread panicked; discarding it");
+
+        drop(live.float(self));
+    }
+
+    // starts tracking the statistics of a new connection
+    fn track_stats(&self, conn: &DB::Connection) -> Arc<Mutex<ConnectionStats>> {
+        let stats = Arc::new(Mutex::new(conn.stats()));
+        let mut tracked = self.connection_stats.lock().unwrap();
+
+        // forget the connections that were closed since
+        tracked.retain(|stats| stats.strong_count() > 0);
+        tracked.push(Arc::downgrade(&stats));
+
+        stats
+    }
+
     pub(super) fn is_closed(&self) -> bool {
         self.is_closed.load(Ordering::Acquire)
     }
 
-    pub(super) async fn close(&self) {
+    // marks the pool as closed, so that every acquire from now on fails with `PoolClosed`,
+    // and returns a future waiting for its connections to be gracefully closed
+    pub(super) fn close(&self) -> impl Future<Output = ()> + '_ {
+        self.mark_closed();
+
+        // the connections over `max_connections` release their permits as well from now on
+        let excess = self.excess_permits.swap(0, Ordering::AcqRel);
+        let permits = WAKE_ALL_PERMITS + self.max_connections() as usize + excess;
+
+        async move {
+            // wait for all permits to be released
+            let _permits = self.semaphore.acquire(permits).await;
+
+            while let Some(idle) = self.idle_conns.pop() {
+                let _ = idle.live.float(self).close().await;
+            }
+        }
+    }
+
+    fn mark_closed(&self) {
         let already_closed = self.is_closed.swap(true, Ordering::AcqRel);
 
         if !already_closed {
@@ -83,14 +263,26 @@ impl<DB: Database> SharedPool<DB> {
             self.semaphore.release(WAKE_ALL_PERMITS);
         }
 
-        // wait for all permits to be released
-        let _permits = self
-            .semaphore
-            .acquire(WAKE_ALL_PERMITS + (self.options.max_connections as usize))
-            .await;
+        // the permits withheld from the pool are not coming back on their own in time
+        self.release_withheld();
+    }
+
+    pub(super) fn clone_handle(&self) {
+        self.handles.fetch_add(1, Ordering::Relaxed);
+    }
+
+    // closes the pool along with the last `Pool` handle; there is no one left to wait for the
+    // connections to be closed gracefully, so the idle ones are closed by dropping them and the
+    // checked-out ones as they are returned
+    pub(super) fn drop_handle(&self) {
+        if self.handles.fetch_sub(1, Ordering::AcqRel) != 1 {
+            return;
+        }
+
+        self.mark_closed();
 
         while let Some(idle) = self.idle_conns.pop() {
-            let _ = idle.live.float(self).close().await;
+            drop(idle.live.float(self));
         }
     }
 
@@ -115,7 +307,14 @@ impl<DB: Database> SharedPool<DB> {
         }
     }
 
-    pub(super) fn release(&self, mut floating: Floating<'_, Live<DB>>) {
+    pub(super) async fn release(&self, mut floating: Floating<'_, Live<DB>>) {
+        *floating.stats.lock().unwrap() = floating.raw.stats();
+
+        if self.is_closed() {
+            // `close()` may no longer be waiting to close idle connections
+            return;
+        }
+
         if let Some(test) = &self.options.after_release {
             if !test(&mut floating.raw) {
                 // drop the connection and do not return it to the pool
@@ -123,12 +322,19 @@ impl<DB: Database> SharedPool<DB> {
             }
         }
 
-        let Floating { inner: idle, guard } = floating.into_idle();
+        if self.is_oversized() {
+            // the pool was resized down while the connection was in use
+            if let Err(e) = floating.close().await {
+                log::debug!("error occurred while closing the pool connection: {}", e);
+            }
 
-        if !self.idle_conns.push(idle).is_ok() {
-            panic!("BUG: connection queue overflow in release()");
+            return;
         }
 
+        let Floating { inner: idle, guard } = floating.into_idle();
+
+        self.idle_conns.push(idle);
+
         // NOTE: we need to make sure we drop the permit *after* we push to the idle queue
         // don't decrease the size
         guard.release_permit();
@@ -145,7 +351,7 @@ impl<DB: Database> SharedPool<DB> {
             .size
             .fetch_update(Ordering::AcqRel, Ordering::Acquire, |size| {
                 size.checked_add(1)
-                    .filter(|size| size <= &self.options.max_connections)
+                    .filter(|size| size <= &self.max_connections())
             }) {
             // we successfully incremented the size
             Ok(_) => Ok(DecrementSizeGuard::from_permit(self, permit)),
@@ -155,15 +361,22 @@ impl<DB: Database> SharedPool<DB> {
     }
 
     #[allow(clippy::needless_lifetimes)]
-    pub(super) async fn acquire<'s>(&'s self) -> Result<Floating<'s, Live<DB>>, Error> {
+    pub(super) async fn acquire<'s>(
+        self: &'s Arc<Self>,
+        timeout: Duration,
+    ) -> Result<Floating<'s, Live<DB>>, Error> {
         if self.is_closed() {
             return Err(Error::PoolClosed);
         }
 
-        let deadline = Instant::now() + self.options.connect_timeout;
+        let deadline = Instant::now() + timeout;
 
-        sqlx_rt::timeout(
-            self.options.connect_timeout,
+        // this future may be dropped at any `.await`, e.g. by a timeout around `acquire()`:
+        // the semaphore removes a waiter from its queue when it is dropped, and whatever was
+        // taken by then, the permit or the `DecrementSizeGuard` of an idle or a new connection,
+        // gives back its place in the pool when it is dropped along with the future
+        let acquired = sqlx_rt::timeout(
+            timeout,
             async {
                 loop {
                     let permit = self.semaphore.acquire(1).await;
@@ -195,16 +408,21 @@ impl<DB: Database> SharedPool<DB> {
                     };
 
                     // Attempt to connect...
-                    return self.connection(deadline, guard).await;
+                    match self.connection(deadline, guard).await {
+                        // the pool does not grow for now; wait for a connection to be released
+                        Err(Error::ServerConnectionLimit { .. }) => continue,
+                        result => return result,
+                    }
                 }
             }
         )
-            .await
-            .map_err(|_| Error::PoolTimedOut)?
+            .await;
+
+        acquired.map_err(|_| self.timed_out())?
     }
 
     pub(super) async fn connection<'s>(
-        &'s self,
+        self: &'s Arc<Self>,
         deadline: Instant,
         guard: DecrementSizeGuard<'s>,
     ) -> Result<Floating<'s, Live<DB>>, Error> {
@@ -215,29 +433,57 @@ impl<DB: Database> SharedPool<DB> {
         let mut backoff = Duration::from_millis(10);
         let max_backoff = deadline_as_timeout::<DB>(deadline)? / 5;
 
+        // whether a connection was already closed by the database while it was established
+        let mut closed = false;
+
         loop {
             let timeout = deadline_as_timeout::<DB>(deadline)?;
 
             // result here is `Result<Result<C, Error>, TimeoutError>`
             // if this block does not return, sleep for the backoff timeout and try again
-            match sqlx_rt::timeout(timeout, self.connect_options.connect()).await {
+            // every attempt uses the options current at the time it is made
+            let connect_options = self.connect_options();
+
+            match sqlx_rt::timeout(timeout, connect_options.connect()).await {
                 // successfully established connection
                 Ok(Ok(mut raw)) => {
                     if let Some(callback) = &self.options.after_connect {
                         callback(&mut raw).await?;
                     }
 
-                    return Ok(Floating::new_live(raw, guard));
+                    if let Some(warm_statements) = &self.options.warm_statements {
+                        warm_statements(&mut raw).await;
+                    }
+
+                    let stats = self.track_stats(&raw);
+
+                    // the database accepts new connections again
+                    self.release_withheld();
+
+                    return Ok(Floating::new_live(raw, stats, guard));
                 }
 
                 // an IO error while connecting is assumed to be the system starting up
-                Ok(Err(Error::Io(e))) if e.kind() == std::io::ErrorKind::ConnectionRefused => (),
+                Ok(Err(Error::Io(e))) if e.kind() == std::io::ErrorKind::ConnectionRefused => {
+                    instrument::retry(&e);
+                }
 
                 // TODO: Handle other database "boot period"s
 
                 // [postgres] the database system is starting up
                 // TODO: Make this check actually check if this is postgres
-                Ok(Err(Error::Database(error))) if error.code().as_deref() == Some("57P03") => (),
+                Ok(Err(e)) if is_starting_up(&e) => instrument::retry(&e),
+
+                // the database closed the connection, e.g. because it is restarting;
+                // try once more with a replacement before giving up
+                Ok(Err(Error::ConnectionClosed(e))) if !closed => {
+                    log::debug!("connection was closed while being established: {}", e);
+                    instrument::retry(&e);
+                    closed = true;
+                }
+
+                // the database has as many connections as it allows
+                Ok(Err(e)) if is_connection_limit(&e) => return Err(self.refuse(guard, &e)),
 
                 // Any other error while connection should immediately
                 // terminate and bubble the error up
@@ -254,6 +500,140 @@ impl<DB: Database> SharedPool<DB> {
             backoff = cmp::min(backoff * 2, max_backoff);
         }
     }
+
+    // the database refused a new connection because it has as many as it allows: rather than
+    // trying again right away, the pool stops growing past its current size by withholding its
+    // spare permits, along with the one of the refused connection. After a backoff that doubles
+    // with every refusal, one permit is released for a single attempt to grow again; once an
+    // attempt succeeds, the rest are released as well
+    fn refuse(self: &Arc<Self>, guard: DecrementSizeGuard<'_>, error: &Error) -> Error {
+        let mut refusals = self.refusals.lock().unwrap();
+        let message = error
+            .as_database_error()
+            .map_or_else(|| error.to_string(), |error| error.message().to_owned());
+
+        refusals.limit = parse_connection_limit(&message);
+        refusals.message = message;
+
+        if self.is_closed() {
+            return refusals.error();
+        }
+
+        log::warn!(
+            "the database refused a new connection of the pool with {} connections: {}",
+            self.size() - 1,
+            refusals.message
+        );
+
+        guard.withhold_permit();
+        refusals.withheld += 1;
+
+        // the permits of idle connections are in the semaphore as well
+        let spare = self
+            .semaphore
+            .permits()
+            .saturating_sub(self.idle_conns.len());
+
+        if let Some(mut permits) = self.semaphore.try_acquire(spare) {
+            permits.disarm();
+            refusals.withheld += spare;
+        }
+
+        let backoff = refusal_backoff(refusals.count);
+        let generation = refusals.generation;
+        let pool = Arc::downgrade(self);
+
+        refusals.count += 1;
+
+        sqlx_rt::spawn(async move {
+            sqlx_rt::sleep(backoff).await;
+
+            if let Some(pool) = pool.upgrade() {
+                let mut refusals = pool.refusals.lock().unwrap();
+
+                if refusals.generation == generation && refusals.withheld > 0 {
+                    refusals.withheld -= 1;
+                    release_permits(&pool.semaphore, &pool.excess_permits, 1);
+                }
+            }
+        });
+
+        refusals.error()
+    }
+
+    // releases every permit withheld after the database refused new connections
+    fn release_withheld(&self) {
+        let mut refusals = self.refusals.lock().unwrap();
+
+        if refusals.count > 0 {
+            let withheld = mem::take(&mut refusals.withheld);
+            release_permits(&self.semaphore, &self.excess_permits, withheld);
+
+            refusals.count = 0;
+            refusals.generation += 1;
+        }
+    }
+
+    // the error returned when a connection could not be acquired in time
+    fn timed_out(&self) -> Error {
+        let refusals = self.refusals.lock().unwrap();
+
+        if refusals.count > 0 {
+            refusals.error()
+        } else {
+            Error::PoolTimedOut
+        }
+    }
+}
+
+impl Refusals {
+    fn error(&self) -> Error {
+        Error::ServerConnectionLimit {
+            limit: self.limit,
+            message: self.message.clone(),
+        }
+    }
+}
+
+// releases `permits` to the semaphore, except for the ones that are in excess since the pool was
+// resized down
+fn release_permits(semaphore: &Semaphore, excess: &AtomicUsize, permits: usize) {
+    let forgotten = take_excess(excess, permits);
+
+    if permits > forgotten {
+        semaphore.release(permits - forgotten);
+    }
+}
+
+// takes up to `permits` from the excess permits, returning how many were taken
+fn take_excess(excess: &AtomicUsize, permits: usize) -> usize {
+    let mut taken = 0;
+
+    let _ = excess.fetch_update(Ordering::AcqRel, Ordering::Acquire, |excess| {
+        taken = cmp::min(excess, permits);
+        Some(excess - taken)
+    });
+
+    taken
+}
+
+fn refusal_backoff(refusals: u32) -> Duration {
+    REFUSAL_BACKOFF
+        .checked_mul(1 << cmp::min(refusals, 16))
+        .map_or(MAX_REFUSAL_BACKOFF, |backoff| {
+            cmp::min(backoff, MAX_REFUSAL_BACKOFF)
+        })
+}
+
+// MySQL names the limit of the connections of a user, e.g. "User 'a' has exceeded the
+// 'max_user_connections' resource (current value: 2)"
+fn parse_connection_limit(message: &str) -> Option<u32> {
+    let (_, value) = message.split_once("current value: ")?;
+    let end = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+
+    value[..end].parse().ok()
 }
 
 // NOTE: Function names here are bizzare. Helpful help would be appreciated.
@@ -272,15 +652,39 @@ fn is_beyond_idle<DB: Database>(idle: &Idle<DB>, options: &PoolOptions<DB>) -> b
         .map_or(false, |timeout| idle.since.elapsed() > timeout)
 }
 
+fn is_connection_limit(error: &Error) -> bool {
+    error
+        .as_database_error()
+        .map_or(false, |error| error.is_connection_limit())
+}
+
+// [postgres] cannot_connect_now, which is also sent as the connection is closed
+fn is_starting_up(error: &Error) -> bool {
+    error
+        .as_database_error()
+        .and_then(|error| error.code())
+        .as_deref()
+        == Some("57P03")
+}
+
 async fn check_conn<'s: 'p, 'p, DB: Database>(
     mut conn: Floating<'s, Idle<DB>>,
     options: &'p PoolOptions<DB>,
 ) -> Result<Floating<'s, Live<DB>>, DecrementSizeGuard<'s>> {
+    // The database closed the connection while it was idle, or it failed before it was
+    // returned; don't bother closing it nicely
+    if !conn.live.raw.is_alive() {
+        log::debug!("discarding idle connection that is no longer alive");
+        instrument::reconnect("the connection was closed");
+        return Err(conn.close().await);
+    }
+
     // If the connection we pulled has expired, close the connection and
     // immediately create a new connection
     if is_beyond_lifetime(&conn, options) {
         // we're closing the connection either way
         // close the connection but don't really care about the result
+        instrument::reconnect("the connection is older than `max_lifetime`");
         return Err(conn.close().await);
     } else if options.test_before_acquire {
         // Check that the connection is still live
@@ -289,6 +693,7 @@ async fn check_conn<'s: 'p, 'p, DB: Database>(
             // either way we're fine to just discard the connection
             // the error itself here isn't necessarily unexpected so WARN is too strong
             log::info!("ping on idle connection returned error: {}", e);
+            instrument::reconnect("the connection did not answer a ping");
             // connection is broken so don't try to close nicely
             return Err(conn.close().await);
         }
@@ -296,11 +701,13 @@ async fn check_conn<'s: 'p, 'p, DB: Database>(
         match test(&mut conn.live.raw).await {
             Ok(false) => {
                 // connection was rejected by user-defined hook
+                instrument::reconnect("the connection was rejected by `before_acquire`");
                 return Err(conn.close().await);
             }
 
             Err(error) => {
                 log::info!("in `before_acquire`: {}", error);
+                instrument::reconnect("`before_acquire` returned an error");
                 return Err(conn.close().await);
             }
 
@@ -348,7 +755,7 @@ async fn do_reap<DB: Database>(pool: &SharedPool<DB>) {
 
     for conn in keep {
         // return valid connections to the pool first
-        pool.release(conn.into_live());
+        pool.release(conn.into_live()).await;
     }
 
     for conn in reap {
@@ -363,6 +770,7 @@ async fn do_reap<DB: Database>(pool: &SharedPool<DB>) {
 pub(in crate::pool) struct DecrementSizeGuard<'a> {
     size: &'a AtomicU32,
     semaphore: &'a Semaphore,
+    excess_permits: &'a AtomicUsize,
     dropped: bool,
 }
 
@@ -372,6 +780,7 @@ impl<'a> DecrementSizeGuard<'a> {
         Self {
             size: &pool.size,
             semaphore: &pool.semaphore,
+            excess_permits: &pool.excess_permits,
             dropped: false,
         }
     }
@@ -390,9 +799,16 @@ impl<'a> DecrementSizeGuard<'a> {
         ptr::eq(self.size, &pool.size)
     }
 
+    /// Decrease the pool size without releasing the semaphore permit, which is withheld from
+    /// the pool until it is released separately.
+    fn withhold_permit(self) {
+        self.size.fetch_sub(1, Ordering::SeqCst);
+        self.cancel();
+    }
+
     /// Release the semaphore permit without decreasing the pool size.
     fn release_permit(self) {
-        self.semaphore.release(1);
+        release_permits(self.semaphore, self.excess_permits, 1);
         self.cancel();
     }
 
@@ -408,6 +824,46 @@ impl Drop for DecrementSizeGuard<'_> {
         self.size.fetch_sub(1, Ordering::SeqCst);
 
         // and here we release the permit we got on construction
-        self.semaphore.release(1);
+        release_permits(self.semaphore, self.excess_permits, 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_backs_off_exponentially_after_refusals() {
+        assert_eq!(refusal_backoff(0), Duration::from_millis(100));
+        assert_eq!(refusal_backoff(1), Duration::from_millis(200));
+        assert_eq!(refusal_backoff(6), MAX_REFUSAL_BACKOFF);
+        assert_eq!(refusal_backoff(u32::MAX), MAX_REFUSAL_BACKOFF);
+
+        // after the first refusal, at most 3 more attempts are made in the first second
+        let mut elapsed = Duration::from_secs(0);
+        let attempts = (0..)
+            .take_while(|&refusals| {
+                elapsed += refusal_backoff(refusals);
+                elapsed <= Duration::from_secs(1)
+            })
+            .count();
+
+        assert_eq!(attempts, 3);
+    }
+
+    #[test]
+    fn it_parses_the_connection_limit() {
+        assert_eq!(
+            parse_connection_limit(
+                "User 'a' has exceeded the 'max_user_connections' resource (current value: 2)"
+            ),
+            Some(2)
+        );
+
+        assert_eq!(
+            parse_connection_limit("sorry, too many clients already"),
+            None
+        );
+        assert_eq!(parse_connection_limit("Too many connections"), None);
     }
 }
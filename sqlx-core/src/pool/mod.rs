@@ -57,9 +57,10 @@
 use self::inner::SharedPool;
 #[cfg(feature = "any")]
 use crate::any::{Any, AnyKind};
-use crate::connection::Connection;
+use crate::connection::{Connection, ConnectionStats};
 use crate::database::Database;
 use crate::error::Error;
+use crate::instrument::Span;
 use crate::transaction::Transaction;
 use std::fmt;
 use std::future::Future;
@@ -75,10 +76,12 @@ mod maybe;
 mod connection;
 mod inner;
 mod options;
+mod split;
 
 pub use self::connection::PoolConnection;
 pub(crate) use self::maybe::MaybePoolConnection;
 pub use self::options::PoolOptions;
+pub use self::split::ReadWriteSplit;
 
 /// An asynchronous pool of SQLx database connections.
 ///
@@ -106,10 +109,11 @@ pub use self::options::PoolOptions;
 /// In Actix-Web, for example, you can share a single pool with all request handlers using [web::Data].
 ///
 /// Cloning `Pool` is cheap as it is simply a reference-counted handle to the inner pool state.
-/// When the last remaining handle to the pool is dropped, the connections owned by the pool are
-/// immediately closed (also by dropping). `PoolConnection` returned by [Pool::acquire] and
-/// `Transaction` returned by [Pool::begin] both implicitly hold a reference to the pool for
-/// their lifetimes.
+/// When the last remaining handle to the pool is dropped, the pool is closed and the idle
+/// connections owned by it are immediately closed (also by dropping). `PoolConnection` returned
+/// by [Pool::acquire] and `Transaction` returned by [Pool::begin] both implicitly hold a
+/// reference to the pool for their lifetimes, so they remain usable; they are closed instead of
+/// being returned to the pool when they are dropped.
 ///
 /// If you prefer to explicitly shutdown the pool and gracefully close its connections (which
 /// depending on the database type, may include sending a message to the database server that the
@@ -262,13 +266,42 @@ impl<DB: Database> Pool<DB> {
     ///
     /// Waits for at most the configured connection timeout before returning an error.
     pub fn acquire(&self) -> impl Future<Output = Result<PoolConnection<DB>, Error>> + 'static {
+        self.acquire_timeout(self.0.options.connect_timeout)
+    }
+
+    /// Retrieves a connection from the pool, waiting for at most `timeout` instead of the
+    /// configured connection timeout.
+    ///
+    /// Returns [`Error::PoolTimedOut`] if no connection could be retrieved or opened in time, or
+    /// [`Error::ServerConnectionLimit`] if the database refused to open more in the meantime.
+    /// A short timeout lets e.g. a request handler fail fast when the pool is exhausted, while
+    /// other tasks keep waiting for as long as the pool allows.
+    pub fn acquire_timeout(
+        &self,
+        timeout: Duration,
+    ) -> impl Future<Output = Result<PoolConnection<DB>, Error>> + 'static {
         let shared = self.0.clone();
-        async move { shared.acquire().await.map(|conn| conn.attach(&shared)) }
+        let span = Span::acquire(DB::DB_SYSTEM);
+
+        async move {
+            let conn = span.clone().instrument(shared.acquire(timeout)).await;
+            span.record_wait_time();
+
+            let conn = conn.map(|conn| conn.attach(&shared));
+
+            if let Ok(conn) = &conn {
+                span.record_system(conn.db_system());
+            }
+
+            conn
+        }
     }
 
     /// Attempts to retrieve a connection from the pool if there is one available.
     ///
-    /// Returns `None` immediately if there are no idle connections available in the pool.
+    /// Returns `None` immediately if there are no idle connections available in the pool. This
+    /// never opens a new connection, and does not take an idle connection while other tasks are
+    /// waiting in [`acquire`](Pool::acquire) for one.
     pub fn try_acquire(&self) -> Option<PoolConnection<DB>> {
         self.0
             .try_acquire()
@@ -282,7 +315,13 @@ impl<DB: Database> Pool<DB> {
 
     /// Attempts to retrieve a new connection and immediately begins a new transaction if there
     /// is one available.
+    ///
+    /// Returns [Error::PoolClosed] if the pool was closed.
     pub async fn try_begin(&self) -> Result<Option<Transaction<'static, DB>>, Error> {
+        if self.is_closed() {
+            return Err(Error::PoolClosed);
+        }
+
         match self.try_acquire() {
             Some(conn) => Transaction::begin(MaybePoolConnection::PoolConnection(conn))
                 .await
@@ -294,29 +333,21 @@ impl<DB: Database> Pool<DB> {
 
     /// Shut down the connection pool, waiting for all connections to be gracefully closed.
     ///
-    /// Upon `.await`ing this call, any currently waiting or subsequent calls to [Pool::acquire] and
-    /// the like will immediately return [Error::PoolClosed] and no new connections will be opened.
+    /// As soon as this is called, any currently waiting or subsequent calls to [Pool::acquire]
+    /// and the like will immediately return [Error::PoolClosed] and no new connections will be
+    /// opened. [Pool::try_acquire] returns `None`.
     ///
-    /// Any connections currently idle in the pool will be immediately closed, including sending
-    /// a graceful shutdown message to the database server, if applicable.
+    /// Any connections currently idle in the pool will be closed, including sending a graceful
+    /// shutdown message to the database server, if applicable.
     ///
     /// Checked-out connections are unaffected, but will be closed in the same manner when they are
     /// returned to the pool.
     ///
-    /// Does not resolve until all connections are returned to the pool and gracefully closed.
-    ///
-    /// ### Note: `async fn`
-    /// Because this is an `async fn`, the pool will *not* be marked as closed unless the
-    /// returned future is polled at least once.
-    ///
-    /// If you want to close the pool but don't want to wait for all connections to be gracefully
-    /// closed, you can do `pool.close().now_or_never()`, which polls the future exactly once
-    /// with a no-op waker.
-    // TODO: I don't want to change the signature right now in case it turns out to be a
-    // breaking change, but this probably should eagerly mark the pool as closed and then the
-    // returned future only needs to be awaited to gracefully close the connections.
-    pub async fn close(&self) {
-        self.0.close().await;
+    /// The returned future does not resolve until all connections are returned to the pool and
+    /// gracefully closed. If you want to close the pool but don't want to wait for that, you can
+    /// drop it without awaiting it.
+    pub fn close(&self) -> impl Future<Output = ()> + '_ {
+        self.0.close()
     }
 
     /// Returns `true` if [`.close()`][Pool::close] has been called on the pool, `false` otherwise.
@@ -337,6 +368,86 @@ impl<DB: Database> Pool<DB> {
     pub fn num_idle(&self) -> usize {
         self.0.num_idle()
     }
+
+    /// Returns the statistics of every connection of the pool, idle or in use.
+    ///
+    /// These are taken when a connection is released to the pool, so the statistics of a
+    /// connection that is in use do not include what it has done since it was acquired. The
+    /// statistics of a connection are kept from one checkout to the next, until it is closed.
+    ///
+    /// See [`Connection::stats`].
+    pub fn connection_stats(&self) -> Vec<ConnectionStats> {
+        self.0.connection_stats()
+    }
+
+    /// Returns the number of connections that were closed instead of being returned to the
+    /// pool, because the thread that had them checked out panicked.
+    ///
+    /// A panic can interrupt a connection in the middle of an operation, leaving it in a state
+    /// the next user could not rely on, so it is closed as it is dropped during the unwind. The
+    /// pool opens a new connection in its place when one is next acquired.
+    pub fn connections_dropped_on_panic(&self) -> u64 {
+        self.0.connections_dropped_on_panic()
+    }
+
+    /// Returns the options that new connections of the pool are opened with.
+    pub fn connect_options(&self) -> Arc<<DB::Connection as Connection>::Options> {
+        self.0.connect_options()
+    }
+
+    /// Replaces the options that new connections of the pool are opened with, e.g. to use a
+    /// password that was rotated.
+    ///
+    /// Connections that are idle or in use are not affected, and keep being used until they are
+    /// closed by [`idle_timeout`] or [`max_lifetime`]. A connection that is being opened when the
+    /// options are replaced uses either the old or the new options, never a mix of both.
+    ///
+    /// ```rust,no_run
+    /// # #[cfg(feature = "postgres")]
+    /// # async fn f(pool: sqlx::PgPool, password: &str) {
+    /// let options = (*pool.connect_options()).clone().password(password);
+    ///
+    /// pool.set_connect_options(options);
+    /// # }
+    /// ```
+    ///
+    /// [`idle_timeout`]: PoolOptions::idle_timeout
+    /// [`max_lifetime`]: PoolOptions::max_lifetime
+    pub fn set_connect_options(&self, options: <DB::Connection as Connection>::Options) {
+        self.0.set_connect_options(options);
+    }
+
+    /// Returns the maximum number of connections of the pool, as set by
+    /// [`PoolOptions::max_connections`] or the last [`resize`](Pool::resize).
+    pub fn max_connections(&self) -> u32 {
+        self.0.max_connections()
+    }
+
+    /// Changes the maximum number of connections of the pool, e.g. to drain it before the
+    /// application is taken out of rotation.
+    ///
+    /// When the pool is resized down, its idle connections are closed right away until it is
+    /// no larger than `max_connections`, which the returned future waits for. The connections
+    /// in use are never interrupted: they are closed as they are returned to the pool, until it
+    /// is small enough. When the pool is resized up, the tasks waiting in
+    /// [`acquire`](Pool::acquire) because it was full may open new connections right away.
+    ///
+    /// Resizing the pool to 0 and waiting for [`size`](Pool::size) to reach 0 drains it, while
+    /// it can still be resized up later, unlike with [`close`](Pool::close).
+    ///
+    /// ```rust,no_run
+    /// # #[cfg(feature = "postgres")]
+    /// # async fn f(pool: sqlx::PgPool) {
+    /// pool.resize(0).await;
+    ///
+    /// while pool.size() > 0 {
+    ///     sqlx_rt::sleep(std::time::Duration::from_millis(100)).await;
+    /// }
+    /// # }
+    /// ```
+    pub async fn resize(&self, max_connections: u32) {
+        self.0.resize(max_connections).await
+    }
 }
 
 #[cfg(feature = "any")]
@@ -346,17 +457,25 @@ impl Pool<Any> {
     /// Determined by the connection URI.
     #[cfg(feature = "any")]
     pub fn any_kind(&self) -> AnyKind {
-        self.0.connect_options.kind()
+        self.0.connect_options().kind()
     }
 }
 
 /// Returns a new [Pool] tied to the same shared connection pool.
 impl<DB: Database> Clone for Pool<DB> {
     fn clone(&self) -> Self {
+        self.0.clone_handle();
         Self(Arc::clone(&self.0))
     }
 }
 
+/// Closes the pool when the last handle to it is dropped.
+impl<DB: Database> Drop for Pool<DB> {
+    fn drop(&mut self) {
+        self.0.drop_handle();
+    }
+}
+
 impl<DB: Database> fmt::Debug for Pool<DB> {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt.debug_struct("Pool")
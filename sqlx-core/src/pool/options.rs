@@ -1,6 +1,7 @@
 use crate::connection::Connection;
 use crate::database::Database;
 use crate::error::Error;
+use crate::executor::Executor;
 use crate::pool::inner::SharedPool;
 use crate::pool::Pool;
 use futures_core::future::BoxFuture;
@@ -17,6 +18,8 @@ pub struct PoolOptions<DB: Database> {
             dyn Fn(&mut DB::Connection) -> BoxFuture<'_, Result<(), Error>> + 'static + Send + Sync,
         >,
     >,
+    pub(crate) warm_statements:
+        Option<Box<dyn Fn(&mut DB::Connection) -> BoxFuture<'_, ()> + 'static + Send + Sync>>,
     pub(crate) before_acquire: Option<
         Box<
             dyn Fn(&mut DB::Connection) -> BoxFuture<'_, Result<bool, Error>>
@@ -45,6 +48,7 @@ impl<DB: Database> PoolOptions<DB> {
     pub fn new() -> Self {
         Self {
             after_connect: None,
+            warm_statements: None,
             test_before_acquire: true,
             before_acquire: None,
             after_release: None,
@@ -170,6 +174,54 @@ impl<DB: Database> PoolOptions<DB> {
         self
     }
 
+    /// Prepare the given statements on every new connection, so they are already in its
+    /// statement cache when the connection is first handed out.
+    ///
+    /// The statements are prepared after the [`after_connect`](Self::after_connect) callback
+    /// has run. A statement that fails to prepare is logged and skipped; it does not fail the
+    /// connection.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn f() -> Result<(), Box<dyn std::error::Error>> {
+    /// use sqlx_core::postgres::PgPoolOptions;
+    /// // PostgreSQL
+    /// let pool = PgPoolOptions::new()
+    ///     .warm_statements(&[
+    ///         "SELECT * FROM users WHERE id = $1",
+    ///         "UPDATE users SET last_seen = NOW() WHERE id = $1",
+    ///     ])
+    ///     .connect("postgres:// …").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn warm_statements<I>(mut self, statements: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+        for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+    {
+        let statements: Arc<[String]> = statements
+            .into_iter()
+            .map(|sql| sql.as_ref().to_owned())
+            .collect();
+
+        self.warm_statements = Some(Box::new(move |conn| {
+            let statements = Arc::clone(&statements);
+
+            Box::pin(async move {
+                for sql in &*statements {
+                    if let Err(error) = conn.prepare(sql).await {
+                        log::warn!("failed to prepare statement {:?}: {}", sql, error);
+                    }
+                }
+            })
+        }));
+
+        self
+    }
+
     pub fn before_acquire<F>(mut self, callback: F) -> Self
     where
         for<'c> F: Fn(&'c mut DB::Connection) -> BoxFuture<'c, Result<bool, Error>>
@@ -199,11 +251,12 @@ impl<DB: Database> PoolOptions<DB> {
         self,
         options: <DB::Connection as Connection>::Options,
     ) -> Result<Pool<DB>, Error> {
-        let shared = SharedPool::new_arc(self, options);
+        // the pool is closed if it cannot be connected to
+        let pool = Pool(SharedPool::new_arc(self, options));
 
-        init_min_connections(&shared).await?;
+        init_min_connections(&pool.0).await?;
 
-        Ok(Pool(shared))
+        Ok(pool)
     }
 
     /// Creates a new pool from this configuration and will establish a connections as the pool
@@ -228,7 +281,7 @@ impl<DB: Database> PoolOptions<DB> {
     }
 }
 
-async fn init_min_connections<DB: Database>(pool: &SharedPool<DB>) -> Result<(), Error> {
+async fn init_min_connections<DB: Database>(pool: &Arc<SharedPool<DB>>) -> Result<(), Error> {
     for _ in 0..cmp::max(pool.options.min_connections, 1) {
         let deadline = Instant::now() + pool.options.connect_timeout;
         let permit = pool.semaphore.acquire(1).await;
@@ -237,7 +290,7 @@ async fn init_min_connections<DB: Database>(pool: &SharedPool<DB>) -> Result<(),
         if let Ok(guard) = pool.try_increment_size(permit) {
             // [connect] will raise an error when past deadline
             let conn = pool.connection(deadline, guard).await?;
-            pool.release(conn);
+            pool.release(conn).await;
         }
     }
 
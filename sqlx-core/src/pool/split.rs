@@ -0,0 +1,333 @@
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use either::Either;
+use futures_core::future::BoxFuture;
+use futures_core::stream::BoxStream;
+use futures_util::TryStreamExt;
+
+use crate::connection::Connection;
+use crate::database::{Database, HasStatement};
+use crate::describe::Describe;
+use crate::error::Error;
+use crate::executor::{Execute, Executor};
+use crate::pool::{Pool, PoolConnection};
+use crate::transaction::Transaction;
+
+/// A primary [`Pool`] along with pools of its read replicas, which routes each query to one of
+/// them.
+///
+/// A query runs on a replica when it was marked with [`read_only(true)`][Query::read_only],
+/// and on the primary otherwise:
+///
+/// ```rust,ignore
+/// let db = ReadWriteSplit::new(primary, vec![replica_1, replica_2]);
+///
+/// // runs on a replica
+/// let users = sqlx::query_as::<_, User>("SELECT * FROM users")
+///     .read_only(true)
+///     .fetch_all(&db)
+///     .await?;
+///
+/// // runs on the primary
+/// sqlx::query("DELETE FROM sessions").execute(&db).await?;
+/// ```
+///
+/// The SQL of a query is not looked at, unless the naive detection of reads is enabled with
+/// [`auto_detect`](ReadWriteSplit::auto_detect). Keep in mind that a replica may lag behind the
+/// primary, so a read that must see a previous write should run on the primary.
+///
+/// ### Transactions
+///
+/// A transaction is started with [`begin`](ReadWriteSplit::begin) on a connection of the
+/// primary, and every query of the transaction runs on that connection, whether it was marked
+/// as read-only or not.
+///
+/// ### Replicas
+///
+/// Replicas are used in turn. A replica that a connection could not be acquired from is
+/// skipped for a while (see [`replica_retry_after`](ReadWriteSplit::replica_retry_after)), and
+/// read-only queries run on the primary while no replica is available. As
+/// [`Pool::acquire`] keeps trying to connect until its
+/// [`connect_timeout`](crate::pool::PoolOptions::connect_timeout), the pools of the replicas
+/// should have a short one for a replica that went down to be skipped quickly.
+///
+/// [Query::read_only]: crate::query::Query::read_only
+pub struct ReadWriteSplit<DB: Database> {
+    primary: Pool<DB>,
+    replicas: Arc<[Replica<DB>]>,
+    next: Arc<AtomicUsize>,
+    auto_detect: bool,
+    retry_after: Duration,
+}
+
+struct Replica<DB: Database> {
+    pool: Pool<DB>,
+
+    // when acquiring a connection from the replica last failed, if it has not worked since
+    down_since: Mutex<Option<Instant>>,
+}
+
+impl<DB: Database> ReadWriteSplit<DB> {
+    /// Route queries between the `primary` pool and the pools of its `replicas`.
+    ///
+    /// Without replicas, every query runs on the primary.
+    pub fn new(primary: Pool<DB>, replicas: impl IntoIterator<Item = Pool<DB>>) -> Self {
+        let replicas = replicas
+            .into_iter()
+            .map(|pool| Replica {
+                pool,
+                down_since: Mutex::new(None),
+            })
+            .collect();
+
+        Self {
+            primary,
+            replicas,
+            next: Arc::new(AtomicUsize::new(0)),
+            auto_detect: false,
+            retry_after: Duration::from_secs(30),
+        }
+    }
+
+    /// Also run queries that start with `SELECT` on a replica, unless they were marked with
+    /// `read_only(false)`.
+    ///
+    /// This only looks at the first word of the query, so the following still run on the
+    /// primary unless they are marked as read-only: queries that start with a comment, with
+    /// `WITH` or with a parenthesis. On the other hand, it routes these writes to a replica
+    /// unless they are marked with `read_only(false)`: a `SELECT .. FOR UPDATE`, a
+    /// `SELECT .. INTO` and a `SELECT` that calls a function with side effects, such as
+    /// `nextval()` or `pg_advisory_lock()`.
+    ///
+    /// Default: `false`.
+    pub fn auto_detect(mut self, enabled: bool) -> Self {
+        self.auto_detect = enabled;
+        self
+    }
+
+    /// How long to skip a replica after a connection could not be acquired from it.
+    ///
+    /// Default: 30 seconds.
+    pub fn replica_retry_after(mut self, delay: Duration) -> Self {
+        self.retry_after = delay;
+        self
+    }
+
+    /// The pool of the primary.
+    pub fn primary(&self) -> &Pool<DB> {
+        &self.primary
+    }
+
+    /// Retrieves a connection from the primary and immediately begins a new transaction.
+    pub async fn begin(&self) -> Result<Transaction<'static, DB>, Error> {
+        self.primary.begin().await
+    }
+
+    /// Retrieves a connection from the next available replica, or from the primary if no
+    /// replica is available.
+    pub async fn acquire_replica(&self) -> Result<PoolConnection<DB>, Error> {
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+
+        for i in 0..self.replicas.len() {
+            let index = (start + i) % self.replicas.len();
+            let replica = &self.replicas[index];
+
+            if replica.is_down(self.retry_after) {
+                continue;
+            }
+
+            match replica.pool.acquire().await {
+                Ok(conn) => {
+                    replica.set_down(false);
+                    return Ok(conn);
+                }
+
+                Err(error) => {
+                    log::warn!("skipping replica #{} of the pool: {}", index, error);
+                    replica.set_down(true);
+                }
+            }
+        }
+
+        self.primary.acquire().await
+    }
+
+    /// Pings every replica and returns the number of replicas that are available.
+    ///
+    /// A replica that does not respond is skipped from then on, like when a connection could
+    /// not be acquired from it, and a replica that responds is used again right away.
+    pub async fn check_replicas(&self) -> usize {
+        let mut available = 0;
+
+        for (index, replica) in self.replicas.iter().enumerate() {
+            let result = match replica.pool.acquire().await {
+                Ok(mut conn) => conn.ping().await,
+                Err(error) => Err(error),
+            };
+
+            if let Err(error) = &result {
+                log::warn!("replica #{} of the pool is not available: {}", index, error);
+            } else {
+                available += 1;
+            }
+
+            replica.set_down(result.is_err());
+        }
+
+        available
+    }
+
+    // whether the query runs on a replica rather than on the primary
+    fn is_read<'q, E>(&self, query: &E) -> bool
+    where
+        E: Execute<'q, DB>,
+    {
+        let read_only = query
+            .read_only()
+            .unwrap_or_else(|| self.auto_detect && starts_with_select(query.sql()));
+
+        read_only && !self.replicas.is_empty()
+    }
+
+    async fn acquire_for(&self, read: bool) -> Result<PoolConnection<DB>, Error> {
+        if read {
+            self.acquire_replica().await
+        } else {
+            self.primary.acquire().await
+        }
+    }
+}
+
+impl<DB: Database> Replica<DB> {
+    fn is_down(&self, retry_after: Duration) -> bool {
+        match *self.down_since.lock().unwrap() {
+            Some(since) => since.elapsed() < retry_after,
+            None => false,
+        }
+    }
+
+    fn set_down(&self, down: bool) {
+        *self.down_since.lock().unwrap() = if down { Some(Instant::now()) } else { None };
+    }
+}
+
+pub(super) fn starts_with_select(sql: &str) -> bool {
+    sql.trim_start()
+        .split(|c: char| !c.is_ascii_alphabetic())
+        .next()
+        .map_or(false, |word| word.eq_ignore_ascii_case("select"))
+}
+
+impl<DB: Database> Clone for ReadWriteSplit<DB> {
+    fn clone(&self) -> Self {
+        Self {
+            primary: self.primary.clone(),
+            replicas: Arc::clone(&self.replicas),
+            next: Arc::clone(&self.next),
+            auto_detect: self.auto_detect,
+            retry_after: self.retry_after,
+        }
+    }
+}
+
+impl<DB: Database> fmt::Debug for ReadWriteSplit<DB> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReadWriteSplit")
+            .field("primary", &self.primary)
+            .field(
+                "replicas",
+                &self.replicas.iter().map(|r| &r.pool).collect::<Vec<_>>(),
+            )
+            .field("auto_detect", &self.auto_detect)
+            .field("retry_after", &self.retry_after)
+            .finish()
+    }
+}
+
+impl<'p, DB: Database> Executor<'p> for &'_ ReadWriteSplit<DB>
+where
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+{
+    type Database = DB;
+
+    fn fetch_many<'e, 'q: 'e, E: 'q>(
+        self,
+        query: E,
+    ) -> BoxStream<'e, Result<Either<DB::QueryResult, DB::Row>, Error>>
+    where
+        E: Execute<'q, Self::Database>,
+    {
+        let read = self.is_read(&query);
+        let split = self.clone();
+
+        Box::pin(try_stream! {
+            let mut conn = split.acquire_for(read).await?;
+            let mut s = conn.fetch_many(query);
+
+            while let Some(v) = s.try_next().await? {
+                r#yield!(v);
+            }
+
+            Ok(())
+        })
+    }
+
+    fn fetch_optional<'e, 'q: 'e, E: 'q>(
+        self,
+        query: E,
+    ) -> BoxFuture<'e, Result<Option<DB::Row>, Error>>
+    where
+        E: Execute<'q, Self::Database>,
+    {
+        let read = self.is_read(&query);
+        let split = self.clone();
+
+        Box::pin(async move { split.acquire_for(read).await?.fetch_optional(query).await })
+    }
+
+    fn fetch_all<'e, 'q: 'e, E: 'q>(self, query: E) -> BoxFuture<'e, Result<Vec<DB::Row>, Error>>
+    where
+        E: Execute<'q, Self::Database>,
+    {
+        let read = self.is_read(&query);
+        let split = self.clone();
+
+        Box::pin(async move { split.acquire_for(read).await?.fetch_all(query).await })
+    }
+
+    fn prepare_with<'e, 'q: 'e>(
+        self,
+        sql: &'q str,
+        parameters: &'e [<Self::Database as Database>::TypeInfo],
+    ) -> BoxFuture<'e, Result<<Self::Database as HasStatement<'q>>::Statement, Error>> {
+        (&self.primary).prepare_with(sql, parameters)
+    }
+
+    #[doc(hidden)]
+    fn describe<'e, 'q: 'e>(
+        self,
+        sql: &'q str,
+    ) -> BoxFuture<'e, Result<Describe<Self::Database>, Error>> {
+        (&self.primary).describe(sql)
+    }
+}
+
+#[test]
+fn test_starts_with_select() {
+    assert!(starts_with_select("SELECT 1"));
+    assert!(starts_with_select("  \n\tselect * FROM users"));
+    assert!(starts_with_select("Select\n1"));
+    assert!(starts_with_select("SELECT"));
+
+    assert!(!starts_with_select("SELECTED"));
+    assert!(!starts_with_select("WITH t AS (SELECT 1) SELECT * FROM t"));
+    assert!(!starts_with_select("(SELECT 1)"));
+    assert!(!starts_with_select("/* read */ SELECT 1"));
+    assert!(!starts_with_select(
+        "INSERT INTO users SELECT * FROM staged_users"
+    ));
+    assert!(!starts_with_select(""));
+}
@@ -1,7 +1,9 @@
 use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
+use std::time::Duration;
 
 use crate::arguments::Arguments;
-use crate::encode::{Encode, IsNull};
+use crate::encode::{Encode, IsNull, RawValue};
 use crate::error::Error;
 use crate::ext::ustr::UStr;
 use crate::postgres::{PgConnection, PgTypeInfo, Postgres};
@@ -17,7 +19,7 @@ use crate::types::Type;
 //            that has a patch, we then apply the patch which should write to &mut Vec<u8>,
 //            backtrack and update the prefixed-len, then write until the next patch offset
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct PgArgumentBuffer {
     buffer: Vec<u8>,
 
@@ -33,7 +35,7 @@ pub struct PgArgumentBuffer {
     patches: Vec<(
         usize, // offset
         usize, // argument index
-        Box<dyn Fn(&mut [u8], &PgTypeInfo) + 'static + Send + Sync>,
+        Arc<dyn Fn(&mut [u8], &PgTypeInfo) + 'static + Send + Sync>,
     )>,
 
     // Whenever an `Encode` impl encounters a `PgTypeInfo` object that does not have an OID
@@ -47,13 +49,19 @@ pub struct PgArgumentBuffer {
 }
 
 /// Implementation of [`Arguments`] for PostgreSQL.
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct PgArguments {
     // Types of each bind parameter
     pub(crate) types: Vec<PgTypeInfo>,
 
     // Buffer of encoded bind parameters
     pub(crate) buffer: PgArgumentBuffer,
+
+    // see `Query::server_timeout`
+    pub(crate) server_timeout: Option<Duration>,
+
+    // the names of the values of a query made with `query_named`, in the order they were bound
+    pub(crate) names: Option<Vec<String>>,
 }
 
 impl PgArguments {
@@ -72,6 +80,27 @@ impl PgArguments {
         self.buffer.count += 1;
     }
 
+    // adds a value whose parameter is declared as `ty` rather than the type of the value
+    pub(crate) fn add_typed<'q, T>(&mut self, value: T, ty: PgTypeInfo)
+    where
+        T: Encode<'q, Postgres>,
+    {
+        self.types.push(ty);
+        self.buffer.encode(value);
+        self.buffer.count += 1;
+    }
+
+    // adds a value for the `:name` parameters of a query made with `query_named`
+    pub(crate) fn add_named<'q, T>(&mut self, name: &str, value: T)
+    where
+        T: Encode<'q, Postgres> + Type<Postgres>,
+    {
+        self.names
+            .get_or_insert_with(Vec::new)
+            .push(name.to_owned());
+        self.add(value);
+    }
+
     // Apply patches
     // This should only go out and ask postgres if we have not seen the type name yet
     pub(crate) async fn apply_patches(
@@ -79,9 +108,42 @@ impl PgArguments {
         conn: &mut PgConnection,
         parameters: &[PgTypeInfo],
     ) -> Result<(), Error> {
+        self.apply_callbacks(parameters);
+
+        for (offset, name) in &self.buffer.type_holes {
+            let oid = conn.fetch_type_id_by_name(&*name).await?;
+            self.buffer.buffer[*offset..(*offset + 4)].copy_from_slice(&oid.to_be_bytes());
+        }
+
+        Ok(())
+    }
+
+    // Apply patches without asking postgres, for when the connection cannot run a query, e.g.
+    // during a `COPY`; fails for a type name we have not seen yet
+    pub(crate) fn apply_patches_cached(
+        &mut self,
+        conn: &PgConnection,
+        parameters: &[PgTypeInfo],
+    ) -> Result<(), Error> {
+        self.apply_callbacks(parameters);
+
+        for (offset, name) in &self.buffer.type_holes {
+            let oid = conn
+                .cache_type_oid
+                .get(name)
+                .ok_or_else(|| Error::TypeNotFound {
+                    type_name: name.to_string(),
+                })?;
+
+            self.buffer.buffer[*offset..(*offset + 4)].copy_from_slice(&oid.to_be_bytes());
+        }
+
+        Ok(())
+    }
+
+    fn apply_callbacks(&mut self, parameters: &[PgTypeInfo]) {
         let PgArgumentBuffer {
             ref patches,
-            ref type_holes,
             ref mut buffer,
             ..
         } = self.buffer;
@@ -92,13 +154,6 @@ impl PgArguments {
 
             callback(buf, ty);
         }
-
-        for (offset, name) in type_holes {
-            let oid = conn.fetch_type_id_by_name(&*name).await?;
-            buffer[*offset..(*offset + 4)].copy_from_slice(&oid.to_be_bytes());
-        }
-
-        Ok(())
     }
 }
 
@@ -116,6 +171,10 @@ impl<'q> Arguments<'q> for PgArguments {
     {
         self.add(value)
     }
+
+    fn expect_named(&mut self) {
+        self.names.get_or_insert_with(Vec::new);
+    }
 }
 
 impl PgArgumentBuffer {
@@ -150,7 +209,7 @@ impl PgArgumentBuffer {
         let offset = self.len();
         let index = self.count;
 
-        self.patches.push((offset, index, Box::new(callback)));
+        self.patches.push((offset, index, Arc::new(callback)));
     }
 
     // Extends the inner buffer by enough space to have an OID
@@ -178,3 +237,68 @@ impl DerefMut for PgArgumentBuffer {
         &mut self.buffer
     }
 }
+
+impl Type<Postgres> for RawValue<'_, Postgres> {
+    fn type_info() -> PgTypeInfo {
+        // the type of the value is given by `produces`
+        PgTypeInfo::UNKNOWN
+    }
+}
+
+impl Encode<'_, Postgres> for RawValue<'_, Postgres> {
+    fn produces(&self) -> Option<PgTypeInfo> {
+        Some(self.type_info.clone())
+    }
+
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+        match self.value {
+            Some(value) => {
+                buf.extend_from_slice(value);
+
+                IsNull::No
+            }
+
+            None => IsNull::Yes,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::counting_alloc::allocations;
+
+    #[test]
+    fn it_encodes_reserved_integers_without_allocating() {
+        let mut arguments = PgArguments::default();
+        arguments.reserve(10, 10 * (4 + 8));
+
+        let ((), allocated) = allocations(|| {
+            for i in 0..4 {
+                arguments.add(i as i32);
+                arguments.add(-(i as i64));
+            }
+
+            arguments.add(true);
+            arguments.add(Option::<i32>::None);
+        });
+
+        assert_eq!(allocated, 0);
+
+        let mut expected = Vec::new();
+
+        for i in 0..4 {
+            expected.extend_from_slice(&4_i32.to_be_bytes());
+            expected.extend_from_slice(&(i as i32).to_be_bytes());
+            expected.extend_from_slice(&8_i32.to_be_bytes());
+            expected.extend_from_slice(&(-(i as i64)).to_be_bytes());
+        }
+
+        expected.extend_from_slice(&1_i32.to_be_bytes());
+        expected.push(1);
+        expected.extend_from_slice(&(-1_i32).to_be_bytes());
+
+        assert_eq!(*arguments.buffer, expected);
+        assert_eq!(arguments.buffer.count, 10);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::column::ColumnNames;
 use crate::error::Error;
 use crate::ext::ustr::UStr;
 use crate::postgres::message::{ParameterDescription, RowDescription};
@@ -7,12 +8,14 @@ use crate::postgres::{PgArguments, PgColumn, PgConnection, PgTypeInfo};
 use crate::query_as::query_as;
 use crate::query_scalar::{query_scalar, query_scalar_with};
 use crate::types::Json;
-use crate::HashMap;
 use futures_core::future::BoxFuture;
 use std::convert::TryFrom;
 use std::fmt::Write;
 use std::sync::Arc;
 
+// the most domains a domain can be defined over in turn before its type is rejected
+const MAX_DOMAIN_DEPTH: usize = 16;
+
 /// Describes the type of the `pg_type.typtype` column
 ///
 /// See <https://www.postgresql.org/docs/13/catalog-pg-type.html>
@@ -96,9 +99,9 @@ impl PgConnection {
         &mut self,
         desc: Option<RowDescription>,
         should_fetch: bool,
-    ) -> Result<(Vec<PgColumn>, HashMap<UStr, usize>), Error> {
+    ) -> Result<(Vec<PgColumn>, ColumnNames), Error> {
         let mut columns = Vec::new();
-        let mut column_names = HashMap::new();
+        let mut column_names = ColumnNames::default();
 
         let desc = if let Some(desc) = desc {
             desc
@@ -139,13 +142,18 @@ impl PgConnection {
         let mut params = Vec::with_capacity(desc.types.len());
 
         for ty in desc.types {
-            params.push(self.maybe_fetch_type_info_by_oid(ty, true).await?);
+            // the server leaves the type of a parameter it could not infer unspecified
+            params.push(if ty == 0 {
+                PgTypeInfo::UNKNOWN
+            } else {
+                self.maybe_fetch_type_info_by_oid(ty, true).await?
+            });
         }
 
         Ok(params)
     }
 
-    async fn maybe_fetch_type_info_by_oid(
+    pub(crate) async fn maybe_fetch_type_info_by_oid(
         &mut self,
         oid: u32,
         should_fetch: bool,
@@ -301,13 +309,55 @@ ORDER BY attnum
         name: String,
     ) -> BoxFuture<'_, Result<PgTypeInfo, Error>> {
         Box::pin(async move {
-            let base_type = self.maybe_fetch_type_info_by_oid(base_type, true).await?;
+            // the domain, and the domains it is defined over in turn, from the outermost
+            let mut domains = vec![(oid, name)];
+            let mut base_type = base_type;
+
+            loop {
+                if PgTypeInfo::try_from_oid(base_type).is_some()
+                    || self.cache_type_info.contains_key(&base_type)
+                {
+                    break;
+                }
 
-            Ok(PgTypeInfo(PgType::Custom(Arc::new(PgCustomType {
-                oid,
-                name: name.into(),
-                kind: PgTypeKind::Domain(base_type),
-            }))))
+                let (name, typ_type, next): (String, i8, u32) = query_as(
+                    "SELECT typname, typtype, typbasetype FROM pg_catalog.pg_type WHERE oid = $1",
+                )
+                .bind(base_type)
+                .fetch_one(&mut *self)
+                .await?;
+
+                if TypType::try_from(typ_type as u8) != Ok(TypType::Domain) {
+                    break;
+                }
+
+                if domains.len() >= MAX_DOMAIN_DEPTH {
+                    return Err(err_protocol!(
+                        "domain {:?} is defined over more than {} nested domains",
+                        domains[0].1,
+                        MAX_DOMAIN_DEPTH
+                    ));
+                }
+
+                domains.push((base_type, name));
+                base_type = next;
+            }
+
+            let mut ty = self.maybe_fetch_type_info_by_oid(base_type, true).await?;
+
+            for (oid, name) in domains.into_iter().rev() {
+                ty = PgTypeInfo(PgType::Custom(Arc::new(PgCustomType {
+                    oid,
+                    name: name.into(),
+                    kind: PgTypeKind::Domain(ty),
+                })));
+
+                self.cache_type_info.insert(oid, ty.clone());
+                self.cache_type_oid
+                    .insert(ty.0.name().to_string().into(), oid);
+            }
+
+            Ok(ty)
         })
     }
 
@@ -362,7 +412,7 @@ SELECT oid FROM pg_catalog.pg_type WHERE typname ILIKE $1
 
     pub(crate) async fn get_nullable_for_columns(
         &mut self,
-        stmt_id: u32,
+        stmt_id: Option<u32>,
         meta: &PgStatementMetadata,
     ) -> Result<Vec<Option<bool>>, Error> {
         if meta.columns.is_empty() {
@@ -404,7 +454,11 @@ SELECT oid FROM pg_catalog.pg_type WHERE typname ILIKE $1
             .await?;
 
         // if it's cockroachdb skip this step #1248
-        if !self.stream.parameter_statuses.contains_key("crdb_version") {
+        // the unnamed statement cannot be referenced from EXPLAIN EXECUTE
+        if let (Some(stmt_id), false) = (
+            stmt_id,
+            self.stream.parameter_statuses.contains_key("crdb_version"),
+        ) {
             // patch up our null inference with data from EXPLAIN
             let nullable_patch = self
                 .nullables_from_explain(stmt_id, meta.parameters.len())
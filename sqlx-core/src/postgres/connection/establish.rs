@@ -1,24 +1,80 @@
 use crate::HashMap;
 
-use crate::common::StatementCache;
+use crate::common::{OperationState, StatementCache};
+use crate::connection::{Connection, ConnectionStats};
 use crate::error::Error;
-use crate::io::Decode;
+use crate::executor::Executor;
+use crate::io::{check_str_nul, Decode};
+use crate::net::connect_any;
 use crate::postgres::connection::{sasl, stream::PgStream, tls};
 use crate::postgres::message::{
     Authentication, BackendKeyData, MessageFormat, Password, ReadyForQuery, Startup,
 };
-use crate::postgres::{PgConnectOptions, PgConnection};
+use crate::postgres::{
+    PgConnectOptions, PgConnection, PgGssEncMode, PgQueryResult, PgTargetSessionAttrs,
+};
+use crate::row::Row;
+use std::io;
+use std::sync::Arc;
 
 // https://www.postgresql.org/docs/current/protocol-flow.html#id-1.10.5.7.3
 // https://www.postgresql.org/docs/current/protocol-flow.html#id-1.10.5.7.11
 
 impl PgConnection {
     pub(crate) async fn establish(options: &PgConnectOptions) -> Result<Self, Error> {
+        // GSSAPI encryption is not supported; without it, the connection goes on over SSL or
+        // in plain text, so no `GSSENCRequest` is sent that the server would have to decline
+        if options.gss_enc_mode == PgGssEncMode::Require {
+            return Err(Error::Configuration(
+                "`gssencmode=require` was requested, but GSSAPI encryption is not supported".into(),
+            ));
+        }
+
+        let hosts = options.hosts();
+
+        connect_any(&hosts, |host, port| {
+            let options = options.clone().host(host).port(port);
+
+            async move {
+                let mut conn = Self::establish_host(&options).await?;
+
+                if options.target_session_attrs == PgTargetSessionAttrs::ReadWrite {
+                    let read_only: String = conn
+                        .fetch_one("SHOW transaction_read_only")
+                        .await?
+                        .try_get(0)?;
+
+                    if read_only == "on" {
+                        let _ = conn.close().await;
+
+                        return Err(Error::Io(io::Error::new(
+                            io::ErrorKind::Other,
+                            "the server is read-only, but `target_session_attrs=read-write` was requested",
+                        )));
+                    }
+                }
+
+                Ok(conn)
+            }
+        })
+        .await
+    }
+
+    // connects to the host of `options`, ignoring its fallback hosts
+    async fn establish_host(options: &PgConnectOptions) -> Result<Self, Error> {
         let mut stream = PgStream::connect(options).await?;
 
         // Upgrade to TLS if we were asked to and the server supports it
         tls::maybe_upgrade(&mut stream, options).await?;
 
+        Self::startup(stream, options).await
+    }
+
+    // starts a session over `stream`, authenticating as the user of `options`
+    pub(super) async fn startup(
+        mut stream: PgStream,
+        options: &PgConnectOptions,
+    ) -> Result<Self, Error> {
         // To begin a session, a frontend opens a connection to the server
         // and sends a startup message.
 
@@ -30,7 +86,7 @@ impl PgConnection {
             // <https://www.postgresql.org/docs/devel/multibyte.html#MULTIBYTE-CHARSET-SUPPORTED>
             ("client_encoding", "UTF8"),
             // Sets the time zone for displaying and interpreting time stamps.
-            ("TimeZone", "UTC"),
+            ("TimeZone", options.timezone.as_str()),
             // Adjust postgres to return precise values for floats
             // NOTE: This is default in postgres 12+
             ("extra_float_digits", "3"),
@@ -44,13 +100,20 @@ impl PgConnection {
             params.push(("options", options));
         }
 
-        stream
-            .send(Startup {
-                username: Some(&options.username),
-                database: options.database.as_deref(),
-                params: &params,
-            })
-            .await?;
+        let startup = Startup {
+            username: Some(&options.username),
+            database: options.database.as_deref(),
+            params: &params,
+        };
+
+        startup.check()?;
+
+        // sent as a NUL-terminated string with cleartext and MD5 authentication
+        if let Some(password) = &options.password {
+            check_str_nul("password", password)?;
+        }
+
+        stream.send(startup).await?;
 
         // The server then uses this information and the contents of
         // its configuration files (such as pg_hba.conf) to determine whether the connection is
@@ -101,8 +164,14 @@ impl PgConnection {
                     }
 
                     method => {
+                        if let Some(method) = method.unsupported_method() {
+                            return Err(Error::AuthNotSupported {
+                                method: method.to_owned(),
+                            });
+                        }
+
                         return Err(err_protocol!(
-                            "unsupported authentication method: {:?}",
+                            "unexpected authentication request: {:?}",
                             method
                         ));
                     }
@@ -141,12 +210,97 @@ impl PgConnection {
             secret_key,
             transaction_status,
             transaction_depth: 0,
+            queries_at_begin: 0,
+            copy: None,
             pending_ready_for_query_count: 0,
             next_statement_id: 1,
             cache_statement: StatementCache::new(options.statement_cache_capacity),
+            shared_statements: Arc::clone(&options.shared_statements),
+            prefer_simple_protocol: options.prefer_simple_protocol,
             cache_type_oid: HashMap::new(),
             cache_type_info: HashMap::new(),
             log_settings: options.log_settings.clone(),
+            last_query_result: PgQueryResult::default(),
+            internal_results: 0,
+            text_results: false,
+            read_buffer_shrink_threshold: options.read_buffer_shrink_threshold,
+            stats: ConnectionStats::default(),
+            operation: OperationState::default(),
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bytes::BufMut;
+
+    use super::*;
+
+    fn message(format: u8, contents: &[u8]) -> Vec<u8> {
+        let mut message = vec![format];
+        message.put_u32(contents.len() as u32 + 4);
+        message.extend_from_slice(contents);
+        message
+    }
+
+    fn authentication(method: u32, data: &[u8]) -> Vec<u8> {
+        let mut contents = method.to_be_bytes().to_vec();
+        contents.extend_from_slice(data);
+
+        message(b'R', &contents)
+    }
+
+    // starts a session with a server that sends `messages`
+    fn startup(messages: Vec<Vec<u8>>) -> Result<(), Error> {
+        let options = PgConnectOptions::new_without_pgpass()
+            .username("postgres")
+            .password("password");
+
+        let stream = PgStream::scripted(&options, messages);
+
+        sqlx_rt::block_on(PgConnection::startup(stream, &options)).map(drop)
+    }
+
+    #[test]
+    fn it_authenticates_with_supported_methods() {
+        let ok = authentication(0, b"");
+        let ready = message(b'Z', b"I");
+
+        assert!(startup(vec![ok.clone(), ready.clone()]).is_ok());
+        assert!(startup(vec![authentication(3, b""), ok.clone(), ready.clone()]).is_ok());
+        assert!(startup(vec![authentication(5, b"salt"), ok, ready]).is_ok());
+    }
+
+    #[test]
+    fn it_rejects_unsupported_methods() {
+        let requests = vec![
+            (2, "", "Kerberos V5"),
+            (6, "", "SCM credential"),
+            (7, "", "GSSAPI"),
+            (8, "token", "GSSAPI"),
+            (9, "", "SSPI"),
+        ];
+
+        for (request, data, expected) in requests {
+            match startup(vec![authentication(request, data.as_bytes())]) {
+                Err(Error::AuthNotSupported { method }) => assert_eq!(method, expected),
+                other => panic!("unexpected result for request {}: {:?}", request, other),
+            }
+        }
+
+        assert!(matches!(
+            startup(vec![authentication(42, b"")]),
+            Err(Error::Protocol(_))
+        ));
+    }
+
+    #[test]
+    fn it_refuses_to_require_gss_encryption() {
+        let options = PgConnectOptions::new_without_pgpass().gss_enc_mode(PgGssEncMode::Require);
+
+        assert!(matches!(
+            sqlx_rt::block_on(PgConnection::establish(&options)),
+            Err(Error::Configuration(_))
+        ));
+    }
+}
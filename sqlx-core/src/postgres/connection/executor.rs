@@ -1,32 +1,107 @@
+use crate::database::Database;
 use crate::describe::Describe;
 use crate::error::Error;
 use crate::executor::{Execute, Executor};
+use crate::instrument::Span;
+use crate::io::check_str_nul;
 use crate::logger::QueryLogger;
+use crate::postgres::error::PgDatabaseError;
 use crate::postgres::message::{
-    self, Bind, Close, CommandComplete, DataRow, MessageFormat, ParameterDescription, Parse, Query,
-    RowDescription,
+    self, Bind, Close, CommandComplete, DataRow, Message, MessageFormat, ParameterDescription,
+    Parse, Query, RowDescription, TransactionStatus,
 };
+use crate::postgres::named::replace_names;
+use crate::postgres::placeholders::count_placeholders;
 use crate::postgres::statement::PgStatementMetadata;
 use crate::postgres::type_info::PgType;
 use crate::postgres::{
     statement::PgStatement, PgArguments, PgConnection, PgQueryResult, PgRow, PgTypeInfo,
     PgValueFormat, Postgres,
 };
+use crate::types::Type;
+use crate::wire::RawFrame;
+use bytes::Bytes;
 use either::Either;
 use futures_core::future::BoxFuture;
 use futures_core::stream::BoxStream;
 use futures_core::Stream;
 use futures_util::{pin_mut, TryStreamExt};
+use std::time::Duration;
 use std::{borrow::Cow, sync::Arc};
 
+// the server decodes each value as the type it declared for its parameter, which differs from
+// the type of the value bound to it when the statement was prepared ahead of time or with other
+// values; textual types share their encoding and are not reported
+fn warn_on_mismatched_parameters(query: &str, bound: &[PgTypeInfo], declared: &[PgTypeInfo]) {
+    for (index, (bound, declared)) in bound.iter().zip(declared).enumerate() {
+        let (bound_oid, declared_oid) = match (bound.0.try_oid(), declared.0.try_oid()) {
+            (Some(bound_oid), Some(declared_oid)) => (bound_oid, declared_oid),
+            _ => continue,
+        };
+
+        if bound_oid == declared_oid
+            || (<str as Type<Postgres>>::compatible(bound)
+                && <str as Type<Postgres>>::compatible(declared))
+        {
+            continue;
+        }
+
+        log::warn!(
+            "parameter ${} of query is declared as {} but a value of type {} was bound to it: {}",
+            index + 1,
+            declared,
+            bound,
+            query
+        );
+    }
+}
+
+// the format of each of the values of `types`: binary, unless some are of a type we only send
+// and receive in text format, which then is given for every value
+fn value_formats<'a, I>(types: I) -> Cow<'static, [PgValueFormat]>
+where
+    I: Iterator<Item = &'a PgTypeInfo> + Clone,
+{
+    let binary = |ty: &PgTypeInfo| ty.value_format(PgValueFormat::Binary) == PgValueFormat::Binary;
+
+    if types.clone().all(binary) {
+        Cow::Borrowed(&[PgValueFormat::Binary])
+    } else {
+        Cow::Owned(
+            types
+                .map(|ty| ty.value_format(PgValueFormat::Binary))
+                .collect(),
+        )
+    }
+}
+
+// attach the query to an error raised by the server, so it can point at the failing position
+fn with_query(mut error: Error, query: &str) -> Error {
+    if let Error::Database(ref mut e) = error {
+        if let Some(e) = e.as_error_mut().downcast_mut::<PgDatabaseError>() {
+            e.set_query(query);
+        }
+    }
+
+    error
+}
+
 async fn prepare(
     conn: &mut PgConnection,
     sql: &str,
     parameters: &[PgTypeInfo],
     metadata: Option<Arc<PgStatementMetadata>>,
-) -> Result<(u32, Arc<PgStatementMetadata>), Error> {
-    let id = conn.next_statement_id;
-    conn.next_statement_id = conn.next_statement_id.wrapping_add(1);
+    // should the statement be prepared under its own name, or as the unnamed statement
+    named: bool,
+) -> Result<(Option<u32>, Arc<PgStatementMetadata>), Error> {
+    let id = if named {
+        let id = conn.next_statement_id;
+        conn.next_statement_id = conn.next_statement_id.wrapping_add(1);
+
+        Some(id)
+    } else {
+        None
+    };
 
     // build a list of type OIDs to send to the database in the PARSE command
     // we have not yet started the query sequence, so we are *safe* to cleanly make
@@ -42,6 +117,8 @@ async fn prepare(
         });
     }
 
+    check_str_nul("query", sql)?;
+
     // flush and wait until we are re-ready
     conn.wait_until_ready().await?;
 
@@ -54,7 +131,10 @@ async fn prepare(
 
     if metadata.is_none() {
         // get the statement columns and parameters
-        conn.stream.write(message::Describe::Statement(id));
+        conn.stream.write(match id {
+            Some(id) => message::Describe::Statement(id),
+            None => message::Describe::UnnamedStatement,
+        });
     }
 
     // we ask for the server to immediately send us the result of the PARSE command
@@ -77,28 +157,58 @@ async fn prepare(
         let parameters = recv_desc_params(conn).await?;
 
         let rows = recv_desc_rows(conn).await?;
+        let returns_rows = rows.is_some();
 
         // each SYNC produces one READY FOR QUERY
         conn.recv_ready_for_query().await?;
 
-        let parameters = conn.handle_parameter_description(parameters).await?;
+        // another connection may have described the statement already; its metadata is only
+        // reused if the server describes it the same way here, e.g. not after the schema changed
+        match conn.shared_statements.get(sql) {
+            Some(metadata) if metadata.describes(&parameters, rows.as_ref()) => {
+                conn.stats.shared_statement_hits += 1;
+
+                metadata
+            }
 
-        let (columns, column_names) = conn.handle_row_description(rows, true).await?;
+            _ => {
+                conn.stats.shared_statement_misses += 1;
 
-        // ensure that if we did fetch custom data, we wait until we are fully ready before
-        // continuing
-        conn.wait_until_ready().await?;
+                let parameters = conn.handle_parameter_description(parameters).await?;
 
-        Arc::new(PgStatementMetadata {
-            parameters,
-            columns,
-            column_names,
-        })
+                let (columns, column_names) = conn.handle_row_description(rows, true).await?;
+
+                // ensure that if we did fetch custom data, we wait until we are fully ready
+                // before continuing
+                conn.wait_until_ready().await?;
+
+                let metadata = Arc::new(PgStatementMetadata {
+                    parameters,
+                    columns,
+                    column_names,
+                    returns_rows,
+                });
+
+                conn.shared_statements.insert(sql, Arc::clone(&metadata));
+
+                metadata
+            }
+        }
     };
 
     Ok((id, metadata))
 }
 
+// the server refuses to execute a prepared statement whose result columns changed since it was
+// prepared, e.g. by an `ALTER TABLE` of a table it selects from
+fn is_stale_statement(error: &Error) -> bool {
+    error.as_database_error().map_or(false, |e| {
+        e.code().as_deref() == Some("0A000")
+            && e.message()
+                .starts_with("cached plan must not change result type")
+    })
+}
+
 async fn recv_desc_params(conn: &mut PgConnection) -> Result<ParameterDescription, Error> {
     conn.stream
         .recv_expect(MessageFormat::ParameterDescription)
@@ -169,15 +279,31 @@ impl PgConnection {
         // optional metadata that was provided by the user, this means they are reusing
         // a statement object
         metadata: Option<Arc<PgStatementMetadata>>,
-    ) -> Result<(u32, Arc<PgStatementMetadata>), Error> {
+    ) -> Result<(Option<u32>, Arc<PgStatementMetadata>), Error> {
         if let Some(statement) = self.cache_statement.get_mut(sql) {
             return Ok((*statement).clone());
         }
 
-        let statement = prepare(self, sql, parameters, metadata).await?;
+        let store_to_cache = store_to_cache && self.cache_statement.is_enabled();
+
+        // a statement that is not cached would never be closed, and with the simple protocol
+        // preferred we must not leave named statements on the server at all; in both cases we
+        // use the unnamed statement, which is re-parsed on every execution
+        let named = store_to_cache && !self.prefer_simple_protocol;
+
+        if !named {
+            if let Some(metadata) = metadata {
+                // nothing to ask the server that we don't already know
+                return Ok((None, metadata));
+            }
+        }
+
+        let statement = Span::prepare(Postgres::DB_SYSTEM, sql)
+            .instrument(prepare(self, sql, parameters, metadata, named))
+            .await?;
 
-        if store_to_cache && self.cache_statement.is_enabled() {
-            if let Some((id, _)) = self.cache_statement.insert(sql, statement.clone()) {
+        if store_to_cache {
+            if let Some((Some(id), _)) = self.cache_statement.insert(sql, statement.clone()) {
                 self.stream.write(Close::Statement(id));
                 self.write_sync();
 
@@ -194,27 +320,183 @@ impl PgConnection {
     async fn run<'e, 'c: 'e, 'q: 'e>(
         &'c mut self,
         query: &'q str,
-        arguments: Option<PgArguments>,
+        mut arguments: Option<PgArguments>,
         limit: u8,
         persistent: bool,
+        text_results: bool,
         metadata_opt: Option<Arc<PgStatementMetadata>>,
     ) -> Result<impl Stream<Item = Result<Either<PgQueryResult, PgRow>, Error>> + 'e, Error> {
         let mut logger = QueryLogger::new(query, self.log_settings.clone());
 
+        let (mut format, mut metadata) = self
+            .send_query(
+                query,
+                arguments.as_mut(),
+                limit,
+                persistent,
+                text_results,
+                metadata_opt,
+                false,
+            )
+            .await
+            .map_err(|e| with_query(e, query))?;
+
+        Ok(try_stream! {
+            // whether the query may still be sent again, which is before any of its results
+            let mut first = true;
+
+            loop {
+                match self.recv_query_result(&mut logger, format, &mut metadata).await {
+                    Ok(Some(v)) => {
+                        first = false;
+                        r#yield!(v);
+                    }
+
+                    Ok(None) => break,
+
+                    Err(error) if first => {
+                        first = false;
+
+                        let (format_, metadata_) = self
+                            .resend_stale_query(
+                                error,
+                                query,
+                                arguments.as_mut(),
+                                limit,
+                                persistent,
+                                text_results,
+                                false,
+                            )
+                            .await?;
+
+                        format = format_;
+                        metadata = metadata_;
+                    }
+
+                    Err(error) => return Err(with_query(error, query)),
+                }
+            }
+
+            Ok(())
+        })
+    }
+
+    // forgets a statement that can no longer be executed, so that it is prepared again the next
+    // time; the statement is closed once the current query is done
+    fn evict_statement(&mut self, sql: &str) {
+        self.shared_statements.remove(sql);
+
+        if let Some((Some(id), _)) = self.cache_statement.remove(sql) {
+            self.stream.write(Close::Statement(id));
+            self.write_sync();
+        }
+    }
+
+    // handles the error a query failed with before any of its results: a statement the server
+    // refused because it went stale is evicted and, unless that aborted a transaction, the query
+    // is sent again to prepare it anew; any other error is returned
+    async fn resend_stale_query(
+        &mut self,
+        error: Error,
+        query: &str,
+        arguments: Option<&mut PgArguments>,
+        limit: u8,
+        persistent: bool,
+        text_results: bool,
+        describe: bool,
+    ) -> Result<(PgValueFormat, Arc<PgStatementMetadata>), Error> {
+        if !is_stale_statement(&error) {
+            return Err(with_query(error, query));
+        }
+
+        self.evict_statement(query);
+
+        // the status from before the query, which was in no transaction but its own
+        if !matches!(self.transaction_status, TransactionStatus::Idle) {
+            return Err(with_query(error, query));
+        }
+
+        log::debug!(
+            "preparing a statement again after the schema it depends on changed: {}",
+            query
+        );
+
+        self.send_query(
+            query,
+            arguments,
+            limit,
+            persistent,
+            text_results,
+            None,
+            describe,
+        )
+        .await
+        .map_err(|e| with_query(e, query))
+    }
+
+    // sends the query to the server, returning the format and metadata to decode its rows with;
+    // with `describe`, the server describes the rows ahead of them even for a prepared statement,
+    // and with `text_results`, it sends them in text format
+    async fn send_query(
+        &mut self,
+        query: &str,
+        // borrowed, so the values can be bound again if the statement has to be prepared again
+        arguments: Option<&mut PgArguments>,
+        limit: u8,
+        persistent: bool,
+        text_results: bool,
+        metadata_opt: Option<Arc<PgStatementMetadata>>,
+        describe: bool,
+    ) -> Result<(PgValueFormat, Arc<PgStatementMetadata>), Error> {
+        self.mode().check("execute a query")?;
+        check_str_nul("query", query)?;
+
         // before we continue, wait until we are "ready" to accept more queries
         self.wait_until_ready().await?;
 
-        let mut metadata: Arc<PgStatementMetadata>;
+        self.last_query_result = PgQueryResult::default();
+        self.internal_results = 0;
+
+        let metadata: Arc<PgStatementMetadata>;
+
+        // a query without parameters can go through the simple query protocol instead
+        let arguments = arguments.filter(|arguments| {
+            !(self.prefer_simple_protocol
+                && metadata_opt.is_none()
+                && arguments.types.is_empty()
+                && arguments.server_timeout.is_none())
+        });
+
+        let format = if let Some(arguments) = arguments {
+            // the server takes a type given for a parameter the query does not refer to as one
+            // more parameter, so a statement is never prepared (and cached) with the types of
+            // more values than the highest placeholder of the query
+            let declared = arguments.types.len().min(count_placeholders(query));
 
-        let format = if let Some(mut arguments) = arguments {
             // prepare the statement if this our first time executing it
             // always return the statement ID here
             let (statement, metadata_) = self
-                .get_or_prepare(query, &arguments.types, persistent, metadata_opt)
+                .get_or_prepare(
+                    query,
+                    &arguments.types[..declared],
+                    persistent,
+                    metadata_opt,
+                )
                 .await?;
 
             metadata = metadata_;
 
+            // check the values bound against the parameters the server described for the
+            // statement, which counts a placeholder used twice once and one skipped as well
+            if metadata.parameters.len() != arguments.types.len() {
+                return Err(Error::ParameterCountMismatch {
+                    expected: metadata.parameters.len(),
+                    got: arguments.types.len(),
+                });
+            }
+
+            warn_on_mismatched_parameters(query, &arguments.types, &metadata.parameters);
+
             // patch holes created during encoding
             arguments.apply_patches(self, &metadata.parameters).await?;
 
@@ -222,16 +504,47 @@ impl PgConnection {
             // consume messages til `ReadyForQuery` before bind and execute
             self.wait_until_ready().await?;
 
+            if let Some(timeout) = arguments.server_timeout {
+                self.write_statement_timeout(timeout);
+            }
+
+            if statement.is_none() {
+                // the unnamed statement may have been replaced since we described it (or it may
+                // live on another server connection entirely, behind a transaction pooler),
+                // so parse it again as part of this same pipeline
+                let param_types: Vec<u32> =
+                    metadata.parameters.iter().map(|ty| ty.0.oid()).collect();
+
+                self.stream.write(Parse {
+                    param_types: &param_types,
+                    query,
+                    statement: None,
+                });
+            }
+
+            let formats = value_formats(arguments.types.iter());
+            let result_formats = if text_results {
+                Cow::Borrowed(&[PgValueFormat::Text][..])
+            } else {
+                value_formats(metadata.columns.iter().map(|c| &c.type_info))
+            };
+
             // bind to attach the arguments to the statement and create a portal
             self.stream.write(Bind {
                 portal: None,
                 statement,
-                formats: &[PgValueFormat::Binary],
+                formats: &formats,
                 num_params: arguments.types.len() as i16,
                 params: &*arguments.buffer,
-                result_formats: &[PgValueFormat::Binary],
+                result_formats: &result_formats,
             });
 
+            if statement.is_none() || describe {
+                // the columns of a re-parsed statement are not guaranteed to be the ones we
+                // described, so have the server describe them again ahead of the rows
+                self.stream.write(message::Describe::UnnamedPortal);
+            }
+
             // executes the portal up to the passed limit
             // the protocol-level limit acts nearly identically to the `LIMIT` in SQL
             self.stream.write(message::Execute {
@@ -246,8 +559,12 @@ impl PgConnection {
             // termed batching might suit this.
             self.write_sync();
 
-            // prepared statements are binary
-            PgValueFormat::Binary
+            // prepared statements are binary, unless asked otherwise
+            if text_results {
+                PgValueFormat::Text
+            } else {
+                PgValueFormat::Binary
+            }
         } else {
             // Query will trigger a ReadyForQuery
             self.stream.write(Query(query));
@@ -260,71 +577,499 @@ impl PgConnection {
             PgValueFormat::Text
         };
 
+        // the queries made to prepare this one are done with by now
+        self.text_results = text_results;
+
+        self.stats.start_query();
         self.stream.flush().await?;
 
-        Ok(try_stream! {
-            loop {
-                let message = self.stream.recv().await?;
+        Ok((format, metadata))
+    }
 
-                match message.format {
-                    MessageFormat::BindComplete
-                    | MessageFormat::ParseComplete
-                    | MessageFormat::ParameterDescription
-                    | MessageFormat::NoData => {
-                        // harmless messages to ignore
-                    }
+    // sets `statement_timeout` until the end of the transaction, ahead of the query being written
+    fn write_statement_timeout(&mut self, timeout: Duration) {
+        // `set_config(.., true)` lasts until the end of the transaction like `SET LOCAL`, but does
+        // not warn outside of a transaction block, where the messages up to the next Sync are a
+        // transaction of their own; a timeout of zero would disable it
+        let query = format!(
+            "SELECT 1 WHERE set_config('statement_timeout', '{}', true) IS NULL",
+            timeout.as_millis().max(1)
+        );
+
+        // the unnamed statement is re-parsed for the query if it uses it
+        self.stream.write(Parse {
+            param_types: &[],
+            query: &query,
+            statement: None,
+        });
 
-                    MessageFormat::CommandComplete => {
-                        // a SQL command completed normally
-                        let cc: CommandComplete = message.decode()?;
+        self.stream.write(Bind {
+            portal: None,
+            statement: None,
+            formats: &[],
+            num_params: 0,
+            params: &[],
+            result_formats: &[],
+        });
 
-                        r#yield!(Either::Left(PgQueryResult {
-                            rows_affected: cc.rows_affected(),
-                        }));
-                    }
+        self.stream.write(message::Execute {
+            portal: None,
+            limit: 0,
+        });
 
-                    MessageFormat::EmptyQueryResponse => {
-                        // empty query string passed to an unprepared execute
-                    }
+        self.internal_results += 1;
+    }
 
-                    MessageFormat::RowDescription => {
-                        // indicates that a *new* set of rows are about to be returned
-                        let (columns, column_names) = self
-                            .handle_row_description(Some(message.decode()?), false)
-                            .await?;
+    // receives the next result or row of the query sent by `send_query`, or `None` once the
+    // server has finished processing it
+    async fn recv_query_result(
+        &mut self,
+        logger: &mut QueryLogger<'_>,
+        format: PgValueFormat,
+        metadata: &mut Arc<PgStatementMetadata>,
+    ) -> Result<Option<Either<PgQueryResult, PgRow>>, Error> {
+        loop {
+            let message = self.stream.recv().await?;
+
+            if message.format == MessageFormat::ReadyForQuery {
+                // processing of the query string is complete
+                self.handle_ready_for_query(message)?;
+
+                return Ok(None);
+            }
+
+            if let Some(step) = self
+                .handle_query_message(message, logger, format, metadata)
+                .await?
+            {
+                return Ok(Some(step));
+            }
+        }
+    }
+
+    // receives every message of the response to the query sent by `send_query`, up to and
+    // including its ReadyForQuery
+    async fn recv_query_response(&mut self) -> Result<Vec<Message>, Error> {
+        let mut messages = Vec::new();
+
+        loop {
+            let message = self.stream.recv().await?;
+
+            if message.format == MessageFormat::ReadyForQuery {
+                self.handle_ready_for_query(message)?;
+
+                return Ok(messages);
+            }
+
+            messages.push(message);
+        }
+    }
+
+    // turns a message of the response to a query into its result or row, if it is one
+    async fn handle_query_message(
+        &mut self,
+        message: Message,
+        logger: &mut QueryLogger<'_>,
+        format: PgValueFormat,
+        metadata: &mut Arc<PgStatementMetadata>,
+    ) -> Result<Option<Either<PgQueryResult, PgRow>>, Error> {
+        match message.format {
+            MessageFormat::BindComplete
+            | MessageFormat::ParseComplete
+            | MessageFormat::ParameterDescription
+            | MessageFormat::NoData => {
+                // harmless messages to ignore
+            }
+
+            MessageFormat::CommandComplete if self.internal_results > 0 => {
+                self.internal_results -= 1;
+            }
+
+            MessageFormat::CommandComplete => {
+                // a SQL command completed normally
+                let cc: CommandComplete = message.decode()?;
+                let done = PgQueryResult {
+                    rows_affected: cc.rows_affected(),
+                };
+
+                self.last_query_result.extend(Some(done.clone()));
+
+                return Ok(Some(Either::Left(done)));
+            }
+
+            MessageFormat::EmptyQueryResponse => {
+                // empty query string passed to an unprepared execute
+            }
+
+            MessageFormat::RowDescription => {
+                // indicates that a *new* set of rows are about to be returned
+                let (columns, column_names) = self
+                    .handle_row_description(Some(message.decode()?), false)
+                    .await?;
+
+                *metadata = Arc::new(PgStatementMetadata {
+                    column_names,
+                    columns,
+                    parameters: Vec::default(),
+                    returns_rows: true,
+                });
+            }
+
+            MessageFormat::DataRow => {
+                logger.increment_rows();
+                self.stats.rows += 1;
 
-                        metadata = Arc::new(PgStatementMetadata {
-                            column_names,
-                            columns,
-                            parameters: Vec::default(),
-                        });
+                // one of the set of rows returned by a SELECT, FETCH, etc query
+                let data: DataRow = message.decode()?;
+                let row = PgRow {
+                    data,
+                    format,
+                    metadata: Arc::clone(metadata),
+                    text_results: self.text_results,
+                };
+
+                return Ok(Some(Either::Right(row)));
+            }
+
+            _ => {
+                return Err(err_protocol!(
+                    "execute: unexpected message: {:?}",
+                    message.format
+                ));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+impl PgConnection {
+    /// Execute the query and return the total number of rows affected.
+    ///
+    /// This is [`Executor::execute`] without boxing the returned future, for hot paths where
+    /// the allocation matters. The `Executor` implementation delegates to this method.
+    pub async fn execute_concrete<'q, E>(&mut self, mut query: E) -> Result<PgQueryResult, Error>
+    where
+        E: Execute<'q, Postgres>,
+    {
+        self.operation.ensure_finished()?;
+
+        let sql = query.sql();
+        let metadata = query.statement().map(|s| Arc::clone(&s.metadata));
+        let mut arguments = query.take_arguments();
+        let persistent = query.persistent();
+        let text_results = query.text_results();
+
+        let sql = replace_names(sql, &arguments)?;
+        let mut logger = QueryLogger::new(&sql, self.log_settings.clone());
+
+        let (mut format, mut metadata) = self
+            .send_query(
+                &sql,
+                arguments.as_mut(),
+                0,
+                persistent,
+                text_results,
+                metadata,
+                false,
+            )
+            .await
+            .map_err(|e| with_query(e, &sql))?;
+
+        let mut result = PgQueryResult::default();
+        let mut first = true;
+
+        loop {
+            match self
+                .recv_query_result(&mut logger, format, &mut metadata)
+                .await
+            {
+                Ok(Some(step)) => {
+                    first = false;
+
+                    if let Either::Left(done) = step {
+                        result.extend(Some(done));
                     }
+                }
 
-                    MessageFormat::DataRow => {
-                        logger.increment_rows();
+                Ok(None) => return Ok(result),
+
+                Err(error) if first => {
+                    first = false;
+
+                    let (format_, metadata_) = self
+                        .resend_stale_query(
+                            error,
+                            &sql,
+                            arguments.as_mut(),
+                            0,
+                            persistent,
+                            text_results,
+                            false,
+                        )
+                        .await?;
+
+                    format = format_;
+                    metadata = metadata_;
+                }
 
-                        // one of the set of rows returned by a SELECT, FETCH, etc query
-                        let data: DataRow = message.decode()?;
-                        let row = PgRow {
-                            data,
-                            format,
-                            metadata: Arc::clone(&metadata),
-                        };
+                Err(error) => return Err(with_query(error, &sql)),
+            }
+        }
+    }
 
-                        r#yield!(Either::Right(row));
+    /// Execute the query and return at most one row.
+    ///
+    /// This is [`Executor::fetch_optional`] without boxing the returned future, for hot paths
+    /// where the allocation matters. No stream is constructed; the row is read directly off the
+    /// connection. The `Executor` implementation delegates to this method.
+    pub async fn fetch_optional_concrete<'q, E>(
+        &mut self,
+        mut query: E,
+    ) -> Result<Option<PgRow>, Error>
+    where
+        E: Execute<'q, Postgres>,
+    {
+        self.operation.ensure_finished()?;
+
+        let sql = query.sql();
+        let metadata = query.statement().map(|s| Arc::clone(&s.metadata));
+        let mut arguments = query.take_arguments();
+        let persistent = query.persistent();
+        let text_results = query.text_results();
+
+        let sql = replace_names(sql, &arguments)?;
+        let mut logger = QueryLogger::new(&sql, self.log_settings.clone());
+
+        let (mut format, mut metadata) = self
+            .send_query(
+                &sql,
+                arguments.as_mut(),
+                1,
+                persistent,
+                text_results,
+                metadata,
+                false,
+            )
+            .await
+            .map_err(|e| with_query(e, &sql))?;
+
+        let mut first = true;
+
+        loop {
+            match self
+                .recv_query_result(&mut logger, format, &mut metadata)
+                .await
+            {
+                Ok(Some(Either::Right(row))) => return Ok(Some(row)),
+
+                Ok(Some(Either::Left(_))) => first = false,
+
+                Ok(None) => return Ok(None),
+
+                Err(error) if first => {
+                    first = false;
+
+                    let (format_, metadata_) = self
+                        .resend_stale_query(
+                            error,
+                            &sql,
+                            arguments.as_mut(),
+                            1,
+                            persistent,
+                            text_results,
+                            false,
+                        )
+                        .await?;
+
+                    format = format_;
+                    metadata = metadata_;
+                }
+
+                Err(error) => return Err(with_query(error, &sql)),
+            }
+        }
+    }
+
+    /// Execute the query and return all the rows.
+    ///
+    /// The whole response is read off the connection before any of it is decoded, so rows are
+    /// not handed out one at a time through a stream. This is faster when the rows fit in memory
+    /// anyway; prefer [`Executor::fetch`] for result sets too large to hold at once. The
+    /// `Executor` implementation of `fetch_all` delegates to this method.
+    pub async fn fetch_all_concrete<'q, E>(&mut self, mut query: E) -> Result<Vec<PgRow>, Error>
+    where
+        E: Execute<'q, Postgres>,
+    {
+        self.operation.ensure_finished()?;
+
+        let sql = query.sql();
+        let metadata = query.statement().map(|s| Arc::clone(&s.metadata));
+        let mut arguments = query.take_arguments();
+        let persistent = query.persistent();
+        let text_results = query.text_results();
+
+        let sql = replace_names(sql, &arguments)?;
+        let mut logger = QueryLogger::new(&sql, self.log_settings.clone());
+
+        let (mut format, mut metadata) = self
+            .send_query(
+                &sql,
+                arguments.as_mut(),
+                0,
+                persistent,
+                text_results,
+                metadata,
+                false,
+            )
+            .await
+            .map_err(|e| with_query(e, &sql))?;
+
+        // an error in the middle of the response is returned from here, dropping the rows before
+        // it; no rows were handed out yet, so the query can be sent again for a stale statement
+        let messages = match self.recv_query_response().await {
+            Ok(messages) => messages,
+
+            Err(error) => {
+                let (format_, metadata_) = self
+                    .resend_stale_query(
+                        error,
+                        &sql,
+                        arguments.as_mut(),
+                        0,
+                        persistent,
+                        text_results,
+                        false,
+                    )
+                    .await?;
+
+                format = format_;
+                metadata = metadata_;
+
+                self.recv_query_response()
+                    .await
+                    .map_err(|e| with_query(e, &sql))?
+            }
+        };
+
+        let mut rows = Vec::with_capacity(messages.len());
+
+        for message in messages {
+            if let Some(Either::Right(row)) = self
+                .handle_query_message(message, &mut logger, format, &mut metadata)
+                .await?
+            {
+                rows.push(row);
+            }
+        }
+
+        Ok(rows)
+    }
+
+    /// Execute the query and return the frames of its result sets as they were received,
+    /// without decoding them.
+    ///
+    /// Each result set is returned as its `RowDescription` message, its `DataRow` messages and
+    /// then the result of its statement. The server is asked to describe the rows of every
+    /// query, including those of prepared statements, so the `RowDescription` is always there.
+    ///
+    /// # Warning
+    ///
+    /// The frames are in the format of the frontend/backend protocol of Postgres, which couples
+    /// their consumer to it. The values in the rows of a query with bound values are in the
+    /// binary format of their types, save for `TSVECTOR`, `TSQUERY` and `XML` values in text
+    /// format, and those of a query without, or with [`text_results`], in the text format.
+    ///
+    /// See [`RawFrame`].
+    ///
+    /// [`text_results`]: crate::query::Query::text_results
+    pub fn fetch_raw_frames<'e, 'q: 'e, E: 'q>(
+        &'e mut self,
+        mut query: E,
+    ) -> BoxStream<'e, Result<RawFrame<Postgres>, Error>>
+    where
+        E: Execute<'q, Postgres>,
+    {
+        let sql = query.sql();
+        let metadata = query.statement().map(|s| Arc::clone(&s.metadata));
+        let mut arguments = query.take_arguments();
+        let persistent = query.persistent();
+        let text_results = query.text_results();
+
+        Box::pin(try_stream! {
+            let sql = replace_names(sql, &arguments)?;
+            let mut logger = QueryLogger::new(&sql, self.log_settings.clone());
+
+            let (mut format, mut metadata) = self
+                .send_query(
+                    &sql,
+                    arguments.as_mut(),
+                    0,
+                    persistent,
+                    text_results,
+                    metadata,
+                    true,
+                )
+                .await
+                .map_err(|e| with_query(e, &sql))?;
+
+            // whether the query may still be sent again, which is before the server responded to it
+            let mut first = true;
+
+            loop {
+                let message = match self.stream.recv().await {
+                    Ok(message) => message,
+
+                    Err(error) if first => {
+                        first = false;
+
+                        let (format_, metadata_) = self
+                            .resend_stale_query(
+                                error,
+                                &sql,
+                                arguments.as_mut(),
+                                0,
+                                persistent,
+                                text_results,
+                                true,
+                            )
+                            .await?;
+
+                        format = format_;
+                        metadata = metadata_;
+
+                        continue;
                     }
 
+                    Err(error) => return Err(with_query(error, &sql)),
+                };
+
+                first = false;
+
+                match message.format {
                     MessageFormat::ReadyForQuery => {
-                        // processing of the query string is complete
                         self.handle_ready_for_query(message)?;
                         break;
                     }
 
+                    MessageFormat::RowDescription => {
+                        r#yield!(RawFrame::Metadata(frame(b'T', &message.contents)));
+                    }
+
+                    MessageFormat::DataRow => {
+                        logger.increment_rows();
+                        self.stats.rows += 1;
+
+                        r#yield!(RawFrame::Row(frame(b'D', &message.contents)));
+                    }
+
                     _ => {
-                        return Err(err_protocol!(
-                            "execute: unexpected message: {:?}",
-                            message.format
-                        ));
+                        if let Some(Either::Left(done)) = self
+                            .handle_query_message(message, &mut logger, format, &mut metadata)
+                            .await?
+                        {
+                            r#yield!(RawFrame::Done(done));
+                        }
                     }
                 }
             }
@@ -334,6 +1079,17 @@ impl PgConnection {
     }
 }
 
+// puts the header of a message back in front of its contents
+fn frame(format: u8, contents: &[u8]) -> Bytes {
+    let mut frame = Vec::with_capacity(5 + contents.len());
+
+    frame.push(format);
+    frame.extend_from_slice(&(contents.len() as i32 + 4).to_be_bytes());
+    frame.extend_from_slice(contents);
+
+    frame.into()
+}
+
 impl<'c> Executor<'c> for &'c mut PgConnection {
     type Database = Postgres;
 
@@ -349,9 +1105,15 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
         let metadata = query.statement().map(|s| Arc::clone(&s.metadata));
         let arguments = query.take_arguments();
         let persistent = query.persistent();
+        let text_results = query.text_results();
+        let operation = self.operation.clone();
 
-        Box::pin(try_stream! {
-            let s = self.run(sql, arguments, 0, persistent, metadata).await?;
+        let stream = try_stream! {
+            let sql = replace_names(sql, &arguments)?;
+
+            let s = self
+                .run(&sql, arguments, 0, persistent, text_results, metadata)
+                .await?;
             pin_mut!(s);
 
             while let Some(v) = s.try_next().await? {
@@ -359,34 +1121,51 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
             }
 
             Ok(())
-        })
+        };
+
+        Box::pin(
+            Span::fetch(Postgres::DB_SYSTEM, sql)
+                .instrument_stream(operation.stream(stream), PgQueryResult::rows_affected),
+        )
+    }
+
+    fn execute<'e, 'q: 'e, E: 'q>(self, query: E) -> BoxFuture<'e, Result<PgQueryResult, Error>>
+    where
+        'c: 'e,
+        E: Execute<'q, Self::Database>,
+    {
+        let span = Span::execute(Postgres::DB_SYSTEM, query.sql());
+        let operation = self.operation.clone();
+
+        Box::pin(span.instrument_result(
+            operation.future(Box::pin(self.execute_concrete(query))),
+            PgQueryResult::rows_affected,
+        ))
     }
 
     fn fetch_optional<'e, 'q: 'e, E: 'q>(
         self,
-        mut query: E,
+        query: E,
     ) -> BoxFuture<'e, Result<Option<PgRow>, Error>>
     where
         'c: 'e,
         E: Execute<'q, Self::Database>,
     {
-        let sql = query.sql();
-        let metadata = query.statement().map(|s| Arc::clone(&s.metadata));
-        let arguments = query.take_arguments();
-        let persistent = query.persistent();
+        let span = Span::fetch(Postgres::DB_SYSTEM, query.sql());
+        let operation = self.operation.clone();
 
-        Box::pin(async move {
-            let s = self.run(sql, arguments, 1, persistent, metadata).await?;
-            pin_mut!(s);
+        Box::pin(span.instrument(operation.future(Box::pin(self.fetch_optional_concrete(query)))))
+    }
 
-            while let Some(s) = s.try_next().await? {
-                if let Either::Right(r) = s {
-                    return Ok(Some(r));
-                }
-            }
+    fn fetch_all<'e, 'q: 'e, E: 'q>(self, query: E) -> BoxFuture<'e, Result<Vec<PgRow>, Error>>
+    where
+        'c: 'e,
+        E: Execute<'q, Self::Database>,
+    {
+        let span = Span::fetch(Postgres::DB_SYSTEM, query.sql());
+        let operation = self.operation.clone();
 
-            Ok(None)
-        })
+        Box::pin(span.instrument(operation.future(Box::pin(self.fetch_all_concrete(query)))))
     }
 
     fn prepare_with<'e, 'q: 'e>(
@@ -397,16 +1176,22 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
     where
         'c: 'e,
     {
-        Box::pin(async move {
+        let operation = self.operation.clone();
+
+        Box::pin(operation.future(Box::pin(async move {
+            self.mode().check("prepare a statement")?;
             self.wait_until_ready().await?;
 
-            let (_, metadata) = self.get_or_prepare(sql, parameters, true, None).await?;
+            let (_, metadata) = self
+                .get_or_prepare(sql, parameters, true, None)
+                .await
+                .map_err(|e| with_query(e, sql))?;
 
             Ok(PgStatement {
                 sql: Cow::Borrowed(sql),
                 metadata,
             })
-        })
+        })))
     }
 
     fn describe<'e, 'q: 'e>(
@@ -416,10 +1201,16 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
     where
         'c: 'e,
     {
-        Box::pin(async move {
+        let operation = self.operation.clone();
+
+        Box::pin(operation.future(Box::pin(async move {
+            self.mode().check("describe a statement")?;
             self.wait_until_ready().await?;
 
-            let (stmt_id, metadata) = self.get_or_prepare(sql, &[], true, None).await?;
+            let (stmt_id, metadata) = self
+                .get_or_prepare(sql, &[], true, None)
+                .await
+                .map_err(|e| with_query(e, sql))?;
 
             let nullable = self.get_nullable_for_columns(stmt_id, &metadata).await?;
 
@@ -427,7 +1218,424 @@ impl<'c> Executor<'c> for &'c mut PgConnection {
                 columns: metadata.columns.clone(),
                 nullable,
                 parameters: Some(Either::Left(metadata.parameters.clone())),
+                returns_rows: metadata.returns_rows,
             })
-        })
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::BufMut;
+
+    use super::*;
+    use crate::common::counting_alloc::allocations;
+    use crate::io::Decode;
+    use crate::net::Socket;
+    use crate::postgres::connection::PgConnectionMode;
+    use crate::postgres::{PgConnectOptions, PgStream};
+    use crate::row::Row;
+
+    fn message(format: u8, contents: &[u8]) -> Vec<u8> {
+        let mut message = vec![format];
+        message.put_u32(contents.len() as u32 + 4);
+        message.extend_from_slice(contents);
+        message
+    }
+
+    // the description of a single int4 column in the text format
+    fn row_description(name: &str) -> Vec<u8> {
+        let mut contents = Vec::new();
+
+        contents.put_i16(1);
+        contents.extend_from_slice(name.as_bytes());
+        contents.put_u8(0);
+        contents.put_i32(0); // table
+        contents.put_i16(0); // attribute
+        contents.put_u32(23); // int4
+        contents.put_i16(4);
+        contents.put_i32(-1);
+        contents.put_i16(0); // text
+
+        message(b'T', &contents)
+    }
+
+    fn data_row(value: &[u8]) -> Vec<u8> {
+        let mut contents = Vec::new();
+
+        contents.put_i16(1);
+        contents.put_i32(value.len() as i32);
+        contents.extend_from_slice(value);
+
+        message(b'D', &contents)
+    }
+
+    fn connect(messages: Vec<Vec<u8>>) -> PgConnection {
+        let mut script = vec![message(b'R', &0_i32.to_be_bytes()), message(b'Z', b"I")];
+        script.extend(messages);
+
+        let options = PgConnectOptions::new_without_pgpass().username("postgres");
+        let stream = PgStream::scripted(&options, script);
+
+        sqlx_rt::block_on(PgConnection::startup(stream, &options)).unwrap()
+    }
+
+    fn written(conn: &PgConnection) -> &[u8] {
+        let socket: &Socket = &conn.stream;
+
+        match socket {
+            Socket::Scripted(stream) => &stream.written,
+            socket => panic!("not a scripted socket: {:?}", socket),
+        }
+    }
+
+    #[test]
+    fn it_shares_the_metadata_of_a_result_set_between_its_rows() {
+        // the allocations made to fetch `rows` rows of `columns` text values of 128 bytes in
+        // all, with their lengths, so that only the number of columns differs
+        fn fetch(columns: usize, rows: usize) -> usize {
+            let value = vec![b'x'; 128 / columns - 4];
+            let mut description = Vec::new();
+            let mut row = Vec::new();
+
+            description.put_i16(columns as i16);
+            row.put_i16(columns as i16);
+
+            for i in 0..columns {
+                description.extend_from_slice(format!("c{}\0", i).as_bytes());
+                description.put_i32(0); // table
+                description.put_i16(0); // attribute
+                description.put_u32(25); // text
+                description.put_i16(-1);
+                description.put_i32(-1);
+                description.put_i16(0); // text
+
+                row.put_i32(value.len() as i32);
+                row.extend_from_slice(&value);
+            }
+
+            let mut messages = vec![message(b'T', &description)];
+            messages.extend((0..rows).map(|_| message(b'D', &row)));
+            messages.push(message(b'C', b"SELECT 0\0"));
+            messages.push(message(b'Z', b"I"));
+
+            let mut conn = connect(messages);
+
+            let (fetched, allocations) =
+                allocations(|| sqlx_rt::block_on(conn.fetch_all("SELECT *")).unwrap());
+
+            assert_eq!(fetched.len(), rows);
+            assert!(fetched.iter().all(|row| row.try_get_raw("c0").is_ok()));
+
+            allocations
+        }
+
+        // the average number of allocations made for each row
+        let per_row = |columns| (fetch(columns, 200) - fetch(columns, 100) + 50) / 100;
+
+        // anything allocated once, e.g. by the runtime, is not counted for a row
+        fetch(2, 1);
+
+        assert_eq!(per_row(2), per_row(16));
+    }
+
+    #[test]
+    fn it_executes_prepared_statements_without_parameters() {
+        let ready = message(b'Z', b"I");
+
+        let mut conn = connect(vec![
+            // SELECT 1: described without parameters and with one column
+            message(b'1', b""),
+            message(b't', b"\0\0"),
+            row_description("?column?"),
+            ready.clone(),
+            message(b'2', b""),
+            data_row(&1_i32.to_be_bytes()),
+            message(b'C', b"SELECT 1\0"),
+            ready.clone(),
+            // DELETE FROM t: described without parameters and without columns
+            message(b'1', b""),
+            message(b't', b"\0\0"),
+            message(b'n', b""),
+            ready.clone(),
+            message(b'2', b""),
+            message(b'C', b"DELETE 2\0"),
+            ready,
+        ]);
+
+        let rows = sqlx_rt::block_on(conn.fetch_all(crate::query::query("SELECT 1"))).unwrap();
+
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].get::<i32, _>(0), 1);
+
+        let done = sqlx_rt::block_on(conn.execute(crate::query::query("DELETE FROM t"))).unwrap();
+
+        assert_eq!(done.rows_affected(), 2);
+        assert_eq!(conn.pending_ready_for_query_count, 0);
+
+        // both statements are bound without format codes or values for their parameters
+        for bind in [
+            &b"B\0\0\0\x16\0sqlx_s_1\0\0\0\0\0\0\x01\0\x01"[..],
+            &b"B\0\0\0\x16\0sqlx_s_2\0\0\0\0\0\0\x01\0\x01"[..],
+        ] {
+            assert!(written(&conn)
+                .windows(bind.len())
+                .any(|window| window == bind));
+        }
+    }
+
+    #[test]
+    fn it_fetches_raw_frames() {
+        let description = row_description("i");
+        let rows = vec![data_row(b"1"), data_row(b"2")];
+
+        let mut messages = Vec::new();
+
+        // SELECT i FROM t, read to the end and then again up to its first row
+        for _ in 0..2 {
+            messages.push(description.clone());
+            messages.extend(rows.iter().cloned());
+            messages.push(message(b'C', b"SELECT 2\0"));
+            messages.push(message(b'Z', b"I"));
+        }
+
+        // DELETE FROM t
+        messages.push(message(b'C', b"DELETE 2\0"));
+        messages.push(message(b'Z', b"I"));
+
+        let mut conn = connect(messages);
+
+        let frames: Vec<RawFrame<Postgres>> =
+            sqlx_rt::block_on(conn.fetch_raw_frames("SELECT i FROM t").try_collect()).unwrap();
+
+        assert_eq!(frames.len(), 4);
+
+        // every frame is passed on as it was received, and is decoded as it would have been
+        match &frames[0] {
+            RawFrame::Metadata(frame) => {
+                assert_eq!(&frame[..], &description[..]);
+
+                let decoded = RowDescription::decode(frame.slice(5..)).unwrap();
+
+                assert_eq!(decoded.fields.len(), 1);
+                assert_eq!(decoded.fields[0].name, "i");
+                assert_eq!(decoded.fields[0].data_type_id, 23);
+            }
+
+            frame => panic!("unexpected frame: {:?}", frame),
+        }
+
+        for (frame, (expected, value)) in frames[1..3].iter().zip(rows.iter().zip(&["1", "2"])) {
+            match frame {
+                RawFrame::Row(frame) => {
+                    assert_eq!(&frame[..], &expected[..]);
+
+                    let decoded = DataRow::decode(frame.slice(5..)).unwrap();
+
+                    assert_eq!(decoded.len(), 1);
+                    assert_eq!(decoded.get(0), Some(value.as_bytes()));
+                }
+
+                frame => panic!("unexpected frame: {:?}", frame),
+            }
+        }
+
+        assert!(matches!(
+            frames[3],
+            RawFrame::Done(PgQueryResult { rows_affected: 2 })
+        ));
+
+        // frames that are not read are drained before the next query
+        let first = sqlx_rt::block_on(conn.fetch_raw_frames("SELECT i FROM t").try_next());
+        assert!(matches!(first, Ok(Some(RawFrame::Metadata(_)))));
+
+        let done = sqlx_rt::block_on(conn.execute("DELETE FROM t")).unwrap();
+        assert_eq!(done.rows_affected(), 2);
+        assert_eq!(conn.pending_ready_for_query_count, 0);
+    }
+
+    fn copy_response(format: u8) -> Vec<u8> {
+        // text, one column in the text format
+        message(format, b"\0\0\x01\0\0")
+    }
+
+    fn error_response(severity: &str, code: &str) -> Vec<u8> {
+        let contents = format!(
+            "S{0}\0V{0}\0C{1}\0Msomething went wrong\0\0",
+            severity, code
+        );
+
+        message(b'E', contents.as_bytes())
+    }
+
+    // asserts that queries and COPYs are rejected in `mode`, without anything being written
+    fn assert_rejected(conn: &mut PgConnection, mode: PgConnectionMode, current: &str) {
+        assert_eq!(conn.mode(), mode);
+
+        let written_before = written(conn).len();
+
+        match sqlx_rt::block_on(conn.execute("SELECT 1")) {
+            Err(Error::InvalidConnectionState {
+                current: current_,
+                attempted,
+            }) => {
+                assert_eq!(current_, current);
+                assert_eq!(attempted, "execute a query");
+            }
+
+            result => panic!("{:?} allowed a query: {:?}", mode, result),
+        }
+
+        assert!(matches!(
+            sqlx_rt::block_on(conn.copy_in_raw("COPY t (id) FROM STDIN")),
+            Err(Error::InvalidConnectionState { .. })
+        ));
+
+        assert_eq!(written(conn).len(), written_before);
+    }
+
+    #[test]
+    fn it_allows_queries_in_and_out_of_a_transaction() {
+        let mut conn = connect(vec![
+            message(b'C', b"BEGIN\0"),
+            message(b'Z', b"T"),
+            message(b'C', b"DELETE 1\0"),
+            message(b'Z', b"T"),
+            message(b'C', b"COMMIT\0"),
+            message(b'Z', b"I"),
+        ]);
+
+        assert_eq!(conn.mode(), PgConnectionMode::Idle);
+
+        sqlx_rt::block_on(conn.execute("BEGIN")).unwrap();
+        assert_eq!(conn.mode(), PgConnectionMode::InTransaction);
+
+        let done = sqlx_rt::block_on(conn.execute("DELETE FROM t")).unwrap();
+        assert_eq!(done.rows_affected(), 1);
+
+        sqlx_rt::block_on(conn.execute("COMMIT")).unwrap();
+        assert_eq!(conn.mode(), PgConnectionMode::Idle);
+    }
+
+    #[test]
+    fn it_rejects_queries_during_a_copy_in() {
+        let mut conn = connect(vec![
+            copy_response(b'G'),
+            message(b'C', b"COPY 1\0"),
+            message(b'Z', b"I"),
+            copy_response(b'G'),
+        ]);
+
+        // a finished COPY leaves the connection ready for queries
+        let mut copy = sqlx_rt::block_on(conn.copy_in_raw("COPY t (id) FROM STDIN")).unwrap();
+        sqlx_rt::block_on(copy.send(&b"1\n"[..])).unwrap();
+        assert_eq!(sqlx_rt::block_on(copy.finish()).unwrap(), 1);
+
+        assert_eq!(conn.mode(), PgConnectionMode::Idle);
+
+        // as does an unfinished one, as long as it is dropped; a COPY that was neither
+        // finished, aborted nor dropped, e.g. because finishing it was canceled, is not
+        let copy = sqlx_rt::block_on(conn.copy_in_raw("COPY t (id) FROM STDIN")).unwrap();
+        std::mem::forget(copy);
+
+        assert_rejected(
+            &mut conn,
+            PgConnectionMode::CopyIn,
+            "copying data in (COPY FROM STDIN)",
+        );
+    }
+
+    #[test]
+    fn it_recovers_from_an_aborted_copy_in() {
+        let mut conn = connect(vec![
+            copy_response(b'G'),
+            error_response("ERROR", "57014"),
+            message(b'Z', b"I"),
+            message(b'C', b"DELETE 1\0"),
+            message(b'Z', b"I"),
+        ]);
+
+        let copy = sqlx_rt::block_on(conn.copy_in_raw("COPY t (id) FROM STDIN")).unwrap();
+        sqlx_rt::block_on(copy.abort("changed my mind")).unwrap();
+
+        assert_eq!(conn.mode(), PgConnectionMode::Idle);
+
+        let done = sqlx_rt::block_on(conn.execute("DELETE FROM t")).unwrap();
+        assert_eq!(done.rows_affected(), 1);
+    }
+
+    #[test]
+    fn it_discards_the_rest_of_a_copy_out_before_the_next_query() {
+        let mut conn = connect(vec![
+            copy_response(b'H'),
+            message(b'd', b"1\n"),
+            message(b'd', b"2\n"),
+            message(b'c', b""),
+            message(b'C', b"COPY 2\0"),
+            message(b'Z', b"I"),
+            message(b'C', b"DELETE 1\0"),
+            message(b'Z', b"I"),
+        ]);
+
+        {
+            let mut copy = sqlx_rt::block_on(conn.copy_out_raw("COPY t (id) TO STDOUT")).unwrap();
+
+            let first = sqlx_rt::block_on(copy.try_next()).unwrap();
+            assert_eq!(first.as_deref(), Some(&b"1\n"[..]));
+        }
+
+        assert_eq!(conn.mode(), PgConnectionMode::CopyOut);
+
+        let done = sqlx_rt::block_on(conn.execute("DELETE FROM t")).unwrap();
+        assert_eq!(done.rows_affected(), 1);
+
+        assert_eq!(conn.mode(), PgConnectionMode::Idle);
+    }
+
+    #[test]
+    fn it_rejects_queries_once_its_listener_is_gone() {
+        let mut notification = 42_u32.to_be_bytes().to_vec();
+        notification.extend_from_slice(b"jobs\0new\0");
+
+        let mut conn = connect(vec![
+            message(b'A', &notification),
+            message(b'C', b"LISTEN\0"),
+            message(b'Z', b"I"),
+        ]);
+
+        let (sender, mut receiver) = futures_channel::mpsc::unbounded();
+        conn.stream.notifications = Some(sender);
+
+        // the listener runs its queries on the connection while it is there to receive its
+        // notifications
+        sqlx_rt::block_on(conn.execute("LISTEN jobs")).unwrap();
+
+        let received = receiver.try_next().unwrap().unwrap();
+        assert_eq!(&received.channel[..], b"jobs");
+        assert_eq!(&received.payload[..], b"new");
+
+        assert_eq!(conn.mode(), PgConnectionMode::Idle);
+
+        drop(receiver);
+
+        assert_rejected(
+            &mut conn,
+            PgConnectionMode::Listening,
+            "listening for notifications",
+        );
+    }
+
+    #[test]
+    fn it_rejects_queries_once_closed_by_the_server() {
+        let mut conn = connect(vec![error_response("FATAL", "57P01")]);
+
+        let error = sqlx_rt::block_on(conn.execute("SELECT 1")).unwrap_err();
+        assert!(error.is_connection_closed(), "{}", error);
+
+        assert_rejected(
+            &mut conn,
+            PgConnectionMode::Broken,
+            "closed by the database",
+        );
     }
 }
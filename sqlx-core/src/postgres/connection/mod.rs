@@ -5,24 +5,27 @@ use crate::HashMap;
 use futures_core::future::BoxFuture;
 use futures_util::{FutureExt, TryFutureExt};
 
-use crate::common::StatementCache;
-use crate::connection::{Connection, LogSettings};
+use crate::common::{OperationState, SharedStatementCache, StatementCache};
+use crate::connection::{Connection, ConnectionStats, LogSettings, CLOSE_DRAIN_TIMEOUT};
 use crate::error::Error;
 use crate::executor::Executor;
 use crate::ext::ustr::UStr;
 use crate::io::Decode;
+use crate::postgres::copy::CopyDirection;
 use crate::postgres::message::{
     Close, Message, MessageFormat, ReadyForQuery, Terminate, TransactionStatus,
 };
 use crate::postgres::statement::PgStatementMetadata;
-use crate::postgres::{PgConnectOptions, PgTypeInfo, Postgres};
+use crate::postgres::{PgConnectOptions, PgQueryResult, PgTypeInfo, Postgres};
 use crate::transaction::Transaction;
 
+pub(crate) use self::mode::PgConnectionMode;
 pub use self::stream::PgStream;
 
 pub(crate) mod describe;
 mod establish;
 mod executor;
+mod mode;
 mod sasl;
 mod stream;
 mod tls;
@@ -36,7 +39,6 @@ pub struct PgConnection {
 
     // process id of this backend
     // used to send cancel requests
-    #[allow(dead_code)]
     process_id: u32,
 
     // secret key of this backend
@@ -49,11 +51,18 @@ pub struct PgConnection {
     next_statement_id: u32,
 
     // cache statement by query string to the id and columns
-    cache_statement: StatementCache<(u32, Arc<PgStatementMetadata>)>,
+    // a statement without an id is kept as the unnamed statement and re-parsed on each use
+    cache_statement: StatementCache<(Option<u32>, Arc<PgStatementMetadata>)>,
+
+    // the metadata of statements, shared with the connections opened with the same options
+    shared_statements: Arc<SharedStatementCache<Arc<PgStatementMetadata>>>,
+
+    // avoid leaving named statements on the server; see `PgConnectOptions::prefer_simple_protocol`
+    prefer_simple_protocol: bool,
 
     // cache user-defined types by id <-> info
     cache_type_info: HashMap<u32, PgTypeInfo>,
-    cache_type_oid: HashMap<UStr, u32>,
+    pub(crate) cache_type_oid: HashMap<UStr, u32>,
 
     // number of ReadyForQuery messages that we are currently expecting
     pub(crate) pending_ready_for_query_count: usize,
@@ -62,16 +71,109 @@ pub struct PgConnection {
     transaction_status: TransactionStatus,
     pub(crate) transaction_depth: usize,
 
+    // the number of queries sent once the outermost transaction began, which tells whether any
+    // statement was executed in it since
+    pub(crate) queries_at_begin: u64,
+
+    // set while a `COPY` started on this connection has not been completed
+    pub(crate) copy: Option<CopyDirection>,
+
     log_settings: LogSettings,
+
+    // the combined result of the last statement, filled in as its results are received
+    last_query_result: PgQueryResult,
+
+    // the number of statements sent ahead of the current query whose results are not reported,
+    // e.g. the one that sets its `statement_timeout`
+    internal_results: usize,
+
+    // whether the rows of the current query were requested in text format; see
+    // `Query::text_results`
+    text_results: bool,
+
+    // see `PgConnectOptions::read_buffer_shrink_threshold`
+    read_buffer_shrink_threshold: usize,
+
+    // see `Connection::stats`
+    pub(crate) stats: ConnectionStats,
+
+    // the operation running on this connection; see `Error::ConnectionBusy`
+    pub(crate) operation: OperationState,
 }
 
 impl PgConnection {
+    /// Returns the combined result of the last statement executed on this connection.
+    ///
+    /// This is filled in from the `CommandComplete` messages of the statement as they are
+    /// received, so that the number of rows affected is available after fetching rows as well,
+    /// e.g. with `fetch_all`. Once the rows of a statement have all been fetched, this is equal
+    /// to what [`execute`] would have returned.
+    ///
+    /// [`execute`]: crate::executor::Executor::execute
+    pub fn last_query_result(&self) -> &PgQueryResult {
+        &self.last_query_result
+    }
+
+    /// Returns whether this connection is in a transaction, as reported by the server when the
+    /// last statement completed.
+    ///
+    /// A transaction is started with [`begin`][Connection::begin] or by executing `BEGIN`; either
+    /// way, this tells whether a new transaction or a savepoint would be started.
+    pub fn transaction_status(&self) -> crate::transaction::TransactionStatus {
+        use crate::transaction::TransactionStatus as Status;
+
+        match self.transaction_status {
+            TransactionStatus::Idle => Status::Idle,
+            TransactionStatus::Transaction => Status::InTransaction,
+            TransactionStatus::Error => Status::InFailedTransaction,
+        }
+    }
+
+    /// Returns the version of the server, as reported by it when the connection was
+    /// established, or `None` if it did not report it.
+    pub fn server_version(&self) -> Option<crate::connection::ServerVersion> {
+        self.stream.server_version_num.map(stream::server_version)
+    }
+
+    #[doc(hidden)]
+    pub fn read_buffer_capacity(&self) -> usize {
+        self.stream.read_buffer_capacity()
+    }
+
+    pub(crate) fn mode(&self) -> PgConnectionMode {
+        if self.stream.broken {
+            PgConnectionMode::Broken
+        } else if let Some(copy) = self.copy {
+            match copy {
+                CopyDirection::In => PgConnectionMode::CopyIn,
+                CopyDirection::Out => PgConnectionMode::CopyOut,
+            }
+        } else if self
+            .stream
+            .notifications
+            .as_ref()
+            .map_or(false, |notifications| notifications.is_closed())
+        {
+            // the listener the notifications were for is gone
+            PgConnectionMode::Listening
+        } else if let TransactionStatus::Idle = self.transaction_status {
+            PgConnectionMode::Idle
+        } else {
+            PgConnectionMode::InTransaction
+        }
+    }
+
     // will return when the connection is ready for another query
     pub(in crate::postgres) async fn wait_until_ready(&mut self) -> Result<(), Error> {
         if !self.stream.wbuf.is_empty() {
             self.stream.flush().await?;
         }
 
+        // a `COPY TO STDOUT` that was not read to the end; the rest of its data is discarded below
+        if self.copy == Some(CopyDirection::Out) {
+            self.copy = None;
+        }
+
         while self.pending_ready_for_query_count > 0 {
             let message = self.stream.recv().await?;
 
@@ -83,7 +185,7 @@ impl PgConnection {
         Ok(())
     }
 
-    async fn recv_ready_for_query(&mut self) -> Result<(), Error> {
+    pub(in crate::postgres) async fn recv_ready_for_query(&mut self) -> Result<(), Error> {
         let r: ReadyForQuery = self
             .stream
             .recv_expect(MessageFormat::ReadyForQuery)
@@ -91,13 +193,53 @@ impl PgConnection {
 
         self.pending_ready_for_query_count -= 1;
         self.transaction_status = r.transaction_status;
+        self.stream
+            .shrink_read_buffer(self.read_buffer_shrink_threshold);
+
+        if self.pending_ready_for_query_count == 0 {
+            self.stats.finish_queries();
+        }
 
         Ok(())
     }
 
+    // discards the messages that were queued to be sent ahead of the next query, along with the
+    // `ReadyForQuery` that each `Sync` or simple `Query` among them would have returned; only a
+    // `CopyFail` is kept, without which the server would still be waiting for data to copy
+    fn discard_queued(&mut self) {
+        let mut kept = Vec::new();
+        let mut queued = &self.stream.wbuf[..];
+
+        while queued.len() >= 5 {
+            let len = u32::from_be_bytes([queued[1], queued[2], queued[3], queued[4]]) as usize;
+            let (message, rest) = queued.split_at((1 + len).min(queued.len()));
+
+            match message[0] {
+                b'f' => kept.extend_from_slice(message),
+
+                b'S' | b'Q' => {
+                    self.pending_ready_for_query_count =
+                        self.pending_ready_for_query_count.saturating_sub(1);
+                }
+
+                _ => {}
+            }
+
+            queued = rest;
+        }
+
+        self.stream.wbuf = kept;
+    }
+
     fn handle_ready_for_query(&mut self, message: Message) -> Result<(), Error> {
         self.pending_ready_for_query_count -= 1;
         self.transaction_status = ReadyForQuery::decode(message.contents)?.transaction_status;
+        self.stream
+            .shrink_read_buffer(self.read_buffer_shrink_threshold);
+
+        if self.pending_ready_for_query_count == 0 {
+            self.stats.finish_queries();
+        }
 
         Ok(())
     }
@@ -121,9 +263,39 @@ impl Connection for PgConnection {
         // On receipt of this message, the backend closes the
         // connection and terminates.
 
+        // See `Connection::close` for how pending work is dealt with first.
+
         Box::pin(async move {
-            self.stream.send(Terminate).await?;
-            self.stream.shutdown().await?;
+            // a message may have been written or read partway; nothing more can be sent
+            let in_sync = !self.stream.busy && !self.stream.broken;
+
+            if in_sync {
+                self.discard_queued();
+
+                if self.pending_ready_for_query_count > 0 {
+                    match sqlx_rt::timeout(CLOSE_DRAIN_TIMEOUT, self.wait_until_ready()).await {
+                        Ok(Ok(())) => {}
+                        Ok(Err(e)) => log::debug!(
+                            "error reading the results of a query while closing the connection: {}",
+                            e
+                        ),
+                        Err(_) => log::debug!(
+                            "gave up reading the results of a query while closing the connection"
+                        ),
+                    }
+                }
+            }
+
+            let terminated = if in_sync && !self.stream.broken {
+                self.stream.send(Terminate).await
+            } else {
+                Ok(())
+            };
+
+            let shutdown = self.stream.shutdown().await;
+
+            terminated?;
+            shutdown?;
 
             Ok(())
         })
@@ -152,8 +324,10 @@ impl Connection for PgConnection {
             self.wait_until_ready().await?;
 
             while let Some((id, _)) = self.cache_statement.remove_lru() {
-                self.stream.write(Close::Statement(id));
-                cleared += 1;
+                if let Some(id) = id {
+                    self.stream.write(Close::Statement(id));
+                    cleared += 1;
+                }
             }
 
             if cleared > 0 {
@@ -177,6 +351,41 @@ impl Connection for PgConnection {
     fn should_flush(&self) -> bool {
         !self.stream.wbuf.is_empty()
     }
+
+    #[doc(hidden)]
+    fn is_busy(&self) -> bool {
+        self.stream.busy || self.operation.is_unfinished()
+    }
+
+    #[doc(hidden)]
+    fn is_broken(&self) -> bool {
+        self.stream.broken
+    }
+
+    fn is_alive(&mut self) -> bool {
+        if !self.stream.broken && self.stream.is_closed() {
+            self.stream.broken = true;
+        }
+
+        !self.stream.broken
+    }
+
+    #[doc(hidden)]
+    fn shrink_buffers(&mut self) {
+        self.stream
+            .shrink_read_buffer(self.read_buffer_shrink_threshold);
+    }
+
+    fn stats(&self) -> ConnectionStats {
+        ConnectionStats {
+            backend_id: Some(self.process_id),
+            bytes_read: self.stream.bytes_read,
+            bytes_written: self.stream.bytes_written,
+            statement_cache_hits: self.cache_statement.hits(),
+            statement_cache_misses: self.cache_statement.misses(),
+            ..self.stats.clone()
+        }
+    }
 }
 
 pub trait PgConnectionInfo {
@@ -0,0 +1,95 @@
+use crate::error::Error;
+
+/// What a connection is doing, as far as it limits what can be sent on it next.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum PgConnectionMode {
+    /// Ready for queries, outside of a transaction.
+    Idle,
+
+    /// Ready for queries, inside of a transaction.
+    InTransaction,
+
+    /// Sending the data of a `COPY .. FROM STDIN`.
+    CopyIn,
+
+    /// Receiving the data of a `COPY .. TO STDOUT`.
+    ///
+    /// Whatever was not read of it is discarded before the next query is sent.
+    CopyOut,
+
+    /// Still subscribed to the channels of a [`PgListener`](crate::postgres::PgListener) that
+    /// was dropped before it could unsubscribe.
+    ///
+    /// The connection is dedicated to the listener; its notifications are not received by
+    /// anyone anymore.
+    Listening,
+
+    /// Closed by the database or failed with an I/O error; nothing can be sent on the
+    /// connection anymore.
+    Broken,
+}
+
+impl PgConnectionMode {
+    // returns an error instead of letting `attempted` write into a connection in this mode
+    pub(crate) fn check(self, attempted: &'static str) -> Result<(), Error> {
+        match self {
+            PgConnectionMode::CopyIn => Err(Error::InvalidConnectionState {
+                current: self.describe(),
+                attempted,
+            }),
+
+            PgConnectionMode::Broken => Err(Error::broken()),
+
+            _ => Ok(()),
+        }
+    }
+
+    fn describe(self) -> &'static str {
+        match self {
+            PgConnectionMode::Idle => "idle",
+            PgConnectionMode::InTransaction => "in a transaction",
+            PgConnectionMode::CopyIn => "copying data in (COPY FROM STDIN)",
+            PgConnectionMode::CopyOut => "copying data out (COPY TO STDOUT)",
+            PgConnectionMode::Listening => "listening for notifications",
+            PgConnectionMode::Broken => "closed",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PgConnectionMode::*;
+    use super::*;
+
+    #[test]
+    fn it_allows_queries_when_ready() {
+        for mode in [Idle, InTransaction, CopyOut] {
+            assert!(mode.check("execute a query").is_ok(), "{:?}", mode);
+        }
+    }
+
+    #[test]
+    fn it_rejects_writes_during_copy_in_and_after_close() {
+        for attempted in ["execute a query", "prepare a statement", "start a COPY"] {
+            match CopyIn.check(attempted) {
+                Err(Error::InvalidConnectionState {
+                    current,
+                    attempted: attempted_,
+                }) => {
+                    assert_eq!(current, CopyIn.describe());
+                    assert_eq!(attempted_, attempted);
+                }
+
+                result => panic!("CopyIn allowed {:?}: {:?}", attempted, result),
+            }
+
+            assert!(Broken.check(attempted).unwrap_err().is_connection_closed());
+        }
+
+        assert_eq!(
+            Broken.check("execute a query").unwrap_err().to_string(),
+            "connection closed by the database: \
+             the connection was closed or failed in a previous operation"
+        );
+    }
+}
@@ -6,12 +6,15 @@ use bytes::{Buf, Bytes};
 use futures_channel::mpsc::UnboundedSender;
 use futures_util::SinkExt;
 use log::Level;
+use sqlx_rt::{AsyncRead, AsyncWrite};
 
+use crate::connection::{ServerVendor, ServerVersion};
 use crate::error::Error;
 use crate::io::{BufStream, Decode, Encode};
 use crate::net::{MaybeTlsStream, Socket};
 use crate::postgres::message::{Message, MessageFormat, Notice, Notification, ParameterStatus};
 use crate::postgres::{PgConnectOptions, PgDatabaseError, PgSeverity};
+use crate::wire::{Direction, PacketObserver};
 
 // the stream is a separate type from the connection to uphold the invariant where an instantiated
 // [PgConnection] is a **valid** connection to postgres
@@ -33,6 +36,8 @@ pub struct PgStream {
     pub(crate) parameter_statuses: BTreeMap<String, String>,
 
     pub(crate) server_version_num: Option<u32>,
+
+    observer: Option<PacketObserver>,
 }
 
 impl PgStream {
@@ -42,14 +47,53 @@ impl PgStream {
             None => Socket::connect_tcp(&options.host, options.port).await?,
         };
 
-        let inner = BufStream::new(MaybeTlsStream::Raw(socket));
+        Ok(Self::with_socket(options, socket))
+    }
+
+    // a stream that replays `messages` instead of talking to a server
+    #[cfg(test)]
+    pub(super) fn scripted<I, C>(options: &PgConnectOptions, messages: I) -> Self
+    where
+        I: IntoIterator<Item = C>,
+        C: Into<Vec<u8>>,
+    {
+        let socket = Socket::Scripted(crate::io::scripted::ScriptedStream::new(messages));
+
+        Self::with_socket(options, socket)
+    }
+
+    fn with_socket(options: &PgConnectOptions, socket: Socket) -> Self {
+        let mut inner = BufStream::new(MaybeTlsStream::Raw(socket));
+        inner.write_timeout = options.socket_write_timeout;
 
-        Ok(Self {
+        Self {
             inner,
             notifications: None,
             parameter_statuses: BTreeMap::default(),
             server_version_num: None,
-        })
+            observer: options.packet_observer,
+        }
+    }
+
+    // writes a message to the buffer, to be sent with the next flush
+    pub(crate) fn write<'en, T>(&mut self, message: T)
+    where
+        T: Encode<'en>,
+    {
+        let offset = self.inner.wbuf.len();
+
+        self.inner.write(message);
+
+        if let Some(observer) = &self.observer {
+            let message = &self.inner.wbuf[offset..];
+
+            if message.first() == Some(&b'p') {
+                // password and SASL messages are redacted down to their header
+                observer.observe(Direction::Sent, &message[..message.len().min(5)]);
+            } else {
+                observer.observe(Direction::Sent, message);
+            }
+        }
     }
 
     pub(crate) async fn send<'en, T>(&mut self, message: T) -> Result<(), Error>
@@ -79,14 +123,13 @@ impl PgStream {
     }
 
     pub(crate) async fn recv_unchecked(&mut self) -> Result<Message, Error> {
-        // all packets in postgres start with a 5-byte header
-        // this header contains the message type and the total length of the message
-        let mut header: Bytes = self.inner.read(5).await?;
+        let (header, contents) = read_message(&mut self.inner).await?;
 
-        let format = MessageFormat::try_from_u8(header.get_u8())?;
-        let size = (header.get_u32() - 4) as usize;
+        if let Some(observer) = &self.observer {
+            observer.observe_parts(Direction::Received, &header, &contents);
+        }
 
-        let contents = self.inner.read(size).await?;
+        let format = MessageFormat::try_from_u8(header[0])?;
 
         Ok(Message { format, contents })
     }
@@ -100,7 +143,17 @@ impl PgStream {
             match message.format {
                 MessageFormat::ErrorResponse => {
                     // An error returned from the database server.
-                    return Err(PgDatabaseError(message.decode()?).into());
+                    let error = PgDatabaseError::new(message.decode()?);
+
+                    // admin_shutdown, crash_shutdown and cannot_connect_now are sent right
+                    // before the server closes the connection
+                    if matches!(error.code(), "57P01" | "57P02" | "57P03") {
+                        self.inner.broken = true;
+
+                        return Err(Error::closed_with(error));
+                    }
+
+                    return Err(error.into());
                 }
 
                 MessageFormat::NotificationResponse => {
@@ -186,6 +239,37 @@ impl DerefMut for PgStream {
     }
 }
 
+// reads the header and the contents of the next message
+async fn read_message<S>(stream: &mut BufStream<S>) -> Result<(Bytes, Bytes), Error>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    stream.ensure_not_busy()?;
+
+    // all packets in postgres start with a 5-byte header
+    // this header contains the message type and the total length of the message
+    let header: Bytes = stream.read(5).await?;
+
+    // the length includes itself
+    let size = match (&header[1..]).get_u32().checked_sub(4) {
+        Some(size) => size as usize,
+        None => {
+            return Err(err_protocol!(
+                "message {:?} has an invalid length",
+                char::from(header[0])
+            ))
+        }
+    };
+
+    // the header has been consumed; until the contents are read, dropping this future
+    // would leave the stream positioned in the middle of a message
+    stream.busy = true;
+    let contents: Bytes = stream.read(size).await?;
+    stream.busy = false;
+
+    Ok((header, contents))
+}
+
 // reference:
 // https://github.com/postgres/postgres/blob/6feebcb6b44631c3dc435e971bd80c2dd218a5ab/src/interfaces/libpq/fe-exec.c#L1030-L1065
 fn parse_server_version(s: &str) -> Option<u32> {
@@ -231,9 +315,131 @@ fn parse_server_version(s: &str) -> Option<u32> {
     Some(version_num)
 }
 
+// the version of the server from its `libpq` version number, which has no patch version since
+// PostgreSQL 10: 9.6.24 is 90624 and 14.2 is 140002
+pub(super) fn server_version(version_num: u32) -> ServerVersion {
+    let major = version_num / 10000;
+
+    let (minor, patch) = if major >= 10 {
+        (version_num % 10000, 0)
+    } else {
+        (version_num / 100 % 100, version_num % 100)
+    };
+
+    ServerVersion {
+        vendor: ServerVendor::Postgres,
+        major: major as u16,
+        minor: minor as u16,
+        patch: patch as u16,
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::parse_server_version;
+    use bytes::BufMut;
+
+    use super::*;
+    use crate::common::counting_alloc::allocations;
+    use crate::io::scripted::ScriptedStream;
+
+    fn message(format: u8, contents: &[u8]) -> Vec<u8> {
+        let mut message = vec![format];
+        message.put_u32(contents.len() as u32 + 4);
+        message.extend_from_slice(contents);
+        message
+    }
+
+    fn read_all(stream: ScriptedStream) -> Result<Vec<(u8, Bytes)>, Error> {
+        let mut stream = BufStream::new(stream);
+
+        sqlx_rt::block_on(async {
+            let mut messages = Vec::new();
+
+            loop {
+                match read_message(&mut stream).await {
+                    Ok((header, contents)) => messages.push((header[0], contents)),
+                    Err(error) if error.is_connection_closed() && !stream.busy => {
+                        return Ok(messages)
+                    }
+                    Err(error) => return Err(error),
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn it_reads_messages_split_at_any_byte() {
+        let mut data = message(b'1', b"");
+        data.extend(message(b'C', b"SELECT 1\0"));
+        data.extend(message(b'Z', b"I"));
+
+        for size in 1..=data.len() {
+            let messages = read_all(ScriptedStream::chunked(&data, size)).unwrap();
+
+            assert_eq!(
+                messages,
+                vec![
+                    (b'1', Bytes::new()),
+                    (b'C', Bytes::from_static(b"SELECT 1\0")),
+                    (b'Z', Bytes::from_static(b"I")),
+                ],
+                "chunks of {} bytes",
+                size
+            );
+        }
+    }
+
+    #[test]
+    fn it_reads_a_message_spanning_many_reads() {
+        let contents: Vec<u8> = (0..1_000_000_u32).map(|i| i as u8).collect();
+
+        let mut data = message(b'D', &contents);
+        data.extend(message(b'Z', b"I"));
+
+        let messages = read_all(ScriptedStream::chunked(&data, 1021)).unwrap();
+
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].1, contents);
+        assert_eq!(messages[1].1, &b"I"[..]);
+    }
+
+    #[test]
+    fn it_reserves_a_large_message_at_once() {
+        let contents: Vec<u8> = (0..1_000_000_u32).map(|i| i as u8).collect();
+        let data = message(b'D', &contents);
+
+        // the header over three reads, then the contents in reads of odd sizes
+        let mut chunks = vec![&data[..1], &data[1..3], &data[3..6]];
+        chunks.extend(data[6..].chunks(4093));
+
+        let mut stream = BufStream::new(ScriptedStream::new(chunks));
+
+        let (message, allocations) =
+            allocations(|| sqlx_rt::block_on(read_message(&mut stream)).unwrap());
+
+        assert_eq!(message.1, contents);
+
+        // the read buffer grows to the declared length once, rather than with every read
+        assert!(allocations < 10, "{} allocations", allocations);
+    }
+
+    #[test]
+    fn it_reports_a_message_cut_off_by_eof() {
+        let data = message(b'D', b"\0\x01\0\0\0\x011");
+        let mut stream = BufStream::new(ScriptedStream::new(vec![&data[..9]]));
+
+        let error = sqlx_rt::block_on(read_message(&mut stream)).unwrap_err();
+
+        assert!(error.is_connection_closed(), "{}", error);
+        assert!(stream.broken);
+    }
+
+    #[test]
+    fn it_rejects_an_invalid_length() {
+        let error = read_all(ScriptedStream::new(vec![&b"Z\0\0\0\x03"[..]])).unwrap_err();
+
+        assert!(matches!(error, Error::Protocol(_)), "{}", error);
+    }
 
     #[test]
     fn test_parse_server_version_num() {
@@ -249,4 +455,28 @@ mod tests {
         // unknown
         assert_eq!(parse_server_version("unknown"), None);
     }
+
+    #[test]
+    fn it_parses_server_versions() {
+        for (s, major, minor, patch) in [
+            ("9.6.24", 9, 6, 24),
+            ("14.2", 14, 2, 0),
+            ("14.2 (Debian 14.2-1.pgdg110+1)", 14, 2, 0),
+            ("12.11 (Ubuntu 12.11-0ubuntu0.20.04.1)", 12, 11, 0),
+            ("15beta1", 15, 0, 0),
+            // Amazon Aurora and CockroachDB
+            ("13.7", 13, 7, 0),
+            ("13.0.0", 13, 0, 0),
+        ] {
+            let version = server_version(parse_server_version(s).unwrap());
+
+            assert_eq!(version.vendor(), ServerVendor::Postgres);
+            assert_eq!(
+                (version.major(), version.minor(), version.patch()),
+                (major, minor, patch),
+                "{}",
+                s
+            );
+        }
+    }
 }
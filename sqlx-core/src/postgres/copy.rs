@@ -1,3 +1,4 @@
+use crate::encode::Encode;
 use crate::error::{Error, Result};
 use crate::ext::async_stream::TryAsyncStream;
 use crate::pool::{Pool, PoolConnection};
@@ -5,14 +6,29 @@ use crate::postgres::connection::PgConnection;
 use crate::postgres::message::{
     CommandComplete, CopyData, CopyDone, CopyFail, CopyResponse, MessageFormat, Query,
 };
-use crate::postgres::Postgres;
+use crate::postgres::{PgArguments, Postgres};
+use crate::types::Type;
 use bytes::{BufMut, Bytes};
 use futures_core::stream::BoxStream;
 use smallvec::alloc::borrow::Cow;
 use sqlx_rt::{AsyncRead, AsyncReadExt, AsyncWriteExt};
 use std::convert::TryFrom;
+use std::mem;
 use std::ops::{Deref, DerefMut};
 
+// the signature, flags and header extension length that start the data of a binary COPY
+const BINARY_HEADER: &[u8] = b"PGCOPY\n\xff\r\n\0\0\0\0\0\0\0\0\0";
+
+// the field count that ends the data of a binary COPY
+const BINARY_TRAILER: i16 = -1;
+
+// the direction of a `COPY` in progress on a connection
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum CopyDirection {
+    In,
+    Out,
+}
+
 impl PgConnection {
     /// Issue a `COPY FROM STDIN` statement and transition the connection to streaming data
     /// to Postgres. This is a more efficient way to import data into Postgres as compared to
@@ -31,6 +47,22 @@ impl PgConnection {
         PgCopyIn::begin(self, statement).await
     }
 
+    /// Issue a `COPY FROM STDIN WITH (FORMAT binary)` statement and transition the connection to
+    /// writing rows of typed values to Postgres.
+    ///
+    /// Each value is encoded as it would be bound to a query, which saves Postgres from parsing
+    /// it as text. See [PgCopyInBinary].
+    ///
+    /// If `statement` is anything other than a `COPY ... FROM STDIN` command in the binary
+    /// format, an error is returned.
+    ///
+    /// ### Note
+    /// [PgCopyInBinary::finish] or [PgCopyInBinary::abort] *must* be called when finished or
+    /// the connection will return an error the next time it is used.
+    pub async fn copy_in_binary(&mut self, statement: &str) -> Result<PgCopyInBinary<&mut Self>> {
+        PgCopyInBinary::begin(self, statement).await
+    }
+
     /// Issue a `COPY TO STDOUT` statement and transition the connection to streaming data
     /// from Postgres. This is a more efficient way to export data from Postgres but
     /// arrives in chunks of one of a few data formats (text/CSV/binary).
@@ -79,6 +111,19 @@ impl Pool<Postgres> {
         PgCopyIn::begin(self.acquire().await?, statement).await
     }
 
+    /// Issue a `COPY FROM STDIN WITH (FORMAT binary)` statement and begin writing rows of typed
+    /// values to Postgres.
+    ///
+    /// A single connection will be checked out for the duration.
+    ///
+    /// See [PgConnection::copy_in_binary].
+    pub async fn copy_in_binary(
+        &self,
+        statement: &str,
+    ) -> Result<PgCopyInBinary<PoolConnection<Postgres>>> {
+        PgCopyInBinary::begin(self.acquire().await?, statement).await
+    }
+
     /// Issue a `COPY TO STDOUT` statement and begin streaming data
     /// from Postgres. This is a more efficient way to export data from Postgres but
     /// arrives in chunks of one of a few data formats (text/CSV/binary).
@@ -118,13 +163,29 @@ pub struct PgCopyIn<C: DerefMut<Target = PgConnection>> {
 
 impl<C: DerefMut<Target = PgConnection>> PgCopyIn<C> {
     async fn begin(mut conn: C, statement: &str) -> Result<Self> {
+        conn.mode().check("start a COPY")?;
         conn.wait_until_ready().await?;
+
+        // the server may be waiting for data from here on, until the COPY is finished or aborted,
+        // even if this is canceled
+        conn.copy = Some(CopyDirection::In);
         conn.stream.send(Query(statement)).await?;
 
-        let response: CopyResponse = conn
-            .stream
-            .recv_expect(MessageFormat::CopyInResponse)
-            .await?;
+        // if the statement fails, its ReadyForQuery is received by the next `wait_until_ready`
+        conn.pending_ready_for_query_count += 1;
+        conn.stats.start_query();
+
+        let response: CopyResponse =
+            match conn.stream.recv_expect(MessageFormat::CopyInResponse).await {
+                Ok(response) => response,
+
+                Err(error) => {
+                    // the statement failed, or was not a `COPY .. FROM STDIN`
+                    conn.copy = None;
+
+                    return Err(error);
+                }
+            };
 
         Ok(PgCopyIn {
             conn: Some(conn),
@@ -241,12 +302,18 @@ impl<C: DerefMut<Target = PgConnection>> PgCopyIn<C> {
     ///
     /// The server is expected to respond with an error, so only _unexpected_ errors are returned.
     pub async fn abort(mut self, msg: impl Into<String>) -> Result<()> {
+        self.fail(msg.into()).await
+    }
+
+    // aborts the `COPY`, leaving this without a connection
+    async fn fail(&mut self, msg: String) -> Result<()> {
         let mut conn = self
             .conn
             .take()
             .expect("PgCopyIn::fail_with: conn taken illegally");
 
         conn.stream.send(CopyFail::new(msg)).await?;
+        conn.copy = None;
 
         match conn.stream.recv().await {
             Ok(msg) => Err(err_protocol!(
@@ -257,9 +324,7 @@ impl<C: DerefMut<Target = PgConnection>> PgCopyIn<C> {
                 match e.code() {
                     Some(Cow::Borrowed("57014")) => {
                         // postgres abort received error code
-                        conn.stream
-                            .recv_expect(MessageFormat::ReadyForQuery)
-                            .await?;
+                        conn.recv_ready_for_query().await?;
                         Ok(())
                     }
                     _ => Err(Error::Database(e)),
@@ -279,14 +344,13 @@ impl<C: DerefMut<Target = PgConnection>> PgCopyIn<C> {
             .expect("CopyWriter::finish: conn taken illegally");
 
         conn.stream.send(CopyDone).await?;
+        conn.copy = None;
         let cc: CommandComplete = conn
             .stream
             .recv_expect(MessageFormat::CommandComplete)
             .await?;
 
-        conn.stream
-            .recv_expect(MessageFormat::ReadyForQuery)
-            .await?;
+        conn.recv_ready_for_query().await?;
 
         Ok(cc.rows_affected())
     }
@@ -295,6 +359,8 @@ impl<C: DerefMut<Target = PgConnection>> PgCopyIn<C> {
 impl<C: DerefMut<Target = PgConnection>> Drop for PgCopyIn<C> {
     fn drop(&mut self) {
         if let Some(mut conn) = self.conn.take() {
+            // the error the server responds with is returned by the next use of the connection
+            conn.copy = None;
             conn.stream.write(CopyFail::new(
                 "PgCopyIn dropped without calling finish() or fail()",
             ));
@@ -302,18 +368,401 @@ impl<C: DerefMut<Target = PgConnection>> Drop for PgCopyIn<C> {
     }
 }
 
+/// A connection in binary `COPY FROM STDIN` mode, writing rows of typed values.
+///
+/// Created by [PgConnection::copy_in_binary] or [Pool::copy_in_binary]. The rows are buffered
+/// and sent in chunks of [`chunk_size`][Self::chunk_size] bytes.
+///
+/// ```rust,no_run
+/// # async fn example(conn: &mut sqlx::postgres::PgConnection) -> sqlx::Result<()> {
+/// let mut copy = conn
+///     .copy_in_binary("COPY users (id, name) FROM STDIN WITH (FORMAT binary)")
+///     .await?;
+///
+/// copy.write_row((1_i64, "alice")).await?;
+/// copy.write_row((2_i64, "bob")).await?;
+///
+/// let rows = copy.finish().await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// ### Note
+/// [PgCopyInBinary::finish] or [PgCopyInBinary::abort] *must* be called when finished or the
+/// connection will return an error the next time it is used.
+///
+/// ### Column types
+/// Postgres does not say the types of the columns of a `COPY`, so the types of the values are
+/// not checked against them. The server reads each value in the binary format of its column, so
+/// each value must have the type of its column exactly, e.g. `i64` for an `INT8` column, not
+/// `i32`. A value of another type is rejected by the server at best, but may also be stored as a
+/// different value when its encoding happens to be valid for the column, e.g. an `f64` for an
+/// `INT8` or a `TIMESTAMP` column.
+#[must_use = "connection will error on next use if `.finish()` or `.abort()` is not called"]
+pub struct PgCopyInBinary<C: DerefMut<Target = PgConnection>> {
+    copy: PgCopyIn<C>,
+    buf: Vec<u8>,
+    chunk_size: usize,
+}
+
+impl<C: DerefMut<Target = PgConnection>> PgCopyInBinary<C> {
+    async fn begin(conn: C, statement: &str) -> Result<Self> {
+        let copy = PgCopyIn::begin(conn, statement).await?;
+
+        if copy.is_textual() {
+            copy.abort("a binary COPY was expected").await?;
+
+            return Err(Error::Encode(
+                "the COPY is not in the binary format; add `WITH (FORMAT binary)`".into(),
+            ));
+        }
+
+        let mut buf = Vec::with_capacity(DEFAULT_CHUNK_SIZE);
+        buf.extend_from_slice(BINARY_HEADER);
+
+        Ok(PgCopyInBinary {
+            copy,
+            buf,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+        })
+    }
+
+    /// Sets the number of bytes of rows that are buffered before they are sent.
+    ///
+    /// Default: 64 KiB.
+    pub fn chunk_size(&mut self, bytes: usize) -> &mut Self {
+        self.chunk_size = bytes.max(1);
+        self
+    }
+
+    /// Returns the number of columns expected in each row.
+    pub fn num_columns(&self) -> usize {
+        self.copy.num_columns()
+    }
+
+    /// Encode a row and send it, along with the rows before it, once a chunk is full.
+    ///
+    /// If the row does not have as many values as the `COPY` has columns, or a value cannot be
+    /// encoded, the `COPY` is aborted and an error is returned; the connection is left usable,
+    /// but any further rows are rejected.
+    ///
+    /// The types of the values are not checked against the types of the columns; see
+    /// [Column types](PgCopyInBinary#column-types).
+    pub async fn write_row<'q, R: PgCopyRow<'q>>(&mut self, row: R) -> Result<&mut Self> {
+        if self.copy.conn.is_none() {
+            return Err(aborted("write a row"));
+        }
+
+        let num_columns = self.copy.num_columns();
+
+        if row.num_columns() != num_columns {
+            let error = format!(
+                "expected {} values for the columns of the COPY but the row has {}",
+                num_columns,
+                row.num_columns()
+            );
+
+            self.copy.fail(error.clone()).await?;
+
+            return Err(Error::Encode(error.into()));
+        }
+
+        let mut arguments = PgArguments::default();
+        row.add_to(&mut arguments);
+
+        let types = mem::take(&mut arguments.types);
+
+        let conn = self.copy.conn.as_deref().expect("conn taken");
+
+        if let Err(error) = arguments.apply_patches_cached(conn, &types) {
+            self.copy.fail(error.to_string()).await?;
+
+            return Err(Error::Encode(error.into()));
+        }
+
+        self.buf
+            .extend_from_slice(&(num_columns as i16).to_be_bytes());
+        self.buf.extend_from_slice(&arguments.buffer);
+
+        if self.buf.len() >= self.chunk_size {
+            self.copy.send(&*self.buf).await?;
+            self.buf.clear();
+        }
+
+        Ok(self)
+    }
+
+    /// Signal that the `COPY` process should be aborted and any data sent should be discarded.
+    ///
+    /// See [PgCopyIn::abort].
+    pub async fn abort(self, msg: impl Into<String>) -> Result<()> {
+        if self.copy.conn.is_none() {
+            return Ok(());
+        }
+
+        self.copy.abort(msg).await
+    }
+
+    /// Send the remaining rows and signal that the `COPY` process is complete.
+    ///
+    /// The number of rows affected is returned.
+    pub async fn finish(mut self) -> Result<u64> {
+        if self.copy.conn.is_none() {
+            return Err(aborted("finish it"));
+        }
+
+        self.buf.extend_from_slice(&BINARY_TRAILER.to_be_bytes());
+
+        self.copy.send(&*self.buf).await?;
+        self.copy.finish().await
+    }
+}
+
+// the default number of bytes of rows to send at once in a binary COPY
+const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+fn aborted(attempted: &'static str) -> Error {
+    Error::InvalidConnectionState {
+        current: "no longer copying, as the COPY was aborted",
+        attempted,
+    }
+}
+
+/// A row of a binary `COPY`: a tuple of values that are encoded as they would be bound to a
+/// query.
+///
+/// Implemented for tuples of up to 16 values.
+pub trait PgCopyRow<'q>: Send {
+    #[doc(hidden)]
+    fn num_columns(&self) -> usize;
+
+    #[doc(hidden)]
+    fn add_to(self, arguments: &mut PgArguments);
+}
+
+macro_rules! impl_copy_row_for_tuple {
+    ($( ($idx:tt) -> $T:ident );+;) => {
+        impl<'q, $($T,)+> PgCopyRow<'q> for ($($T,)+)
+        where
+            $($T: 'q + Send + Encode<'q, Postgres> + Type<Postgres>,)+
+        {
+            #[inline]
+            fn num_columns(&self) -> usize {
+                [$($idx),+].len()
+            }
+
+            #[inline]
+            fn add_to(self, arguments: &mut PgArguments) {
+                $(arguments.add(self.$idx);)+
+            }
+        }
+    };
+}
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+);
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+);
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+);
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+);
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+);
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+);
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+);
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+    (7) -> T8;
+);
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+    (7) -> T8;
+    (8) -> T9;
+);
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+    (7) -> T8;
+    (8) -> T9;
+    (9) -> T10;
+);
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+    (7) -> T8;
+    (8) -> T9;
+    (9) -> T10;
+    (10) -> T11;
+);
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+    (7) -> T8;
+    (8) -> T9;
+    (9) -> T10;
+    (10) -> T11;
+    (11) -> T12;
+);
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+    (7) -> T8;
+    (8) -> T9;
+    (9) -> T10;
+    (10) -> T11;
+    (11) -> T12;
+    (12) -> T13;
+);
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+    (7) -> T8;
+    (8) -> T9;
+    (9) -> T10;
+    (10) -> T11;
+    (11) -> T12;
+    (12) -> T13;
+    (13) -> T14;
+);
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+    (7) -> T8;
+    (8) -> T9;
+    (9) -> T10;
+    (10) -> T11;
+    (11) -> T12;
+    (12) -> T13;
+    (13) -> T14;
+    (14) -> T15;
+);
+
+impl_copy_row_for_tuple!(
+    (0) -> T1;
+    (1) -> T2;
+    (2) -> T3;
+    (3) -> T4;
+    (4) -> T5;
+    (5) -> T6;
+    (6) -> T7;
+    (7) -> T8;
+    (8) -> T9;
+    (9) -> T10;
+    (10) -> T11;
+    (11) -> T12;
+    (12) -> T13;
+    (13) -> T14;
+    (14) -> T15;
+    (15) -> T16;
+);
+
 async fn pg_begin_copy_out<'c, C: DerefMut<Target = PgConnection> + Send + 'c>(
     mut conn: C,
     statement: &str,
 ) -> Result<BoxStream<'c, Result<Bytes>>> {
+    conn.mode().check("start a COPY")?;
     conn.wait_until_ready().await?;
     conn.stream.send(Query(statement)).await?;
 
+    // if the stream is not read to the end, the rest of it is discarded up to this ReadyForQuery
+    // by the next `wait_until_ready`
+    conn.pending_ready_for_query_count += 1;
+    conn.stats.start_query();
+
     let _: CopyResponse = conn
         .stream
         .recv_expect(MessageFormat::CopyOutResponse)
         .await?;
 
+    conn.copy = Some(CopyDirection::Out);
+
     let stream: TryAsyncStream<'c, Bytes> = try_stream! {
         loop {
             let msg = conn.stream.recv().await?;
@@ -322,7 +771,8 @@ async fn pg_begin_copy_out<'c, C: DerefMut<Target = PgConnection> + Send + 'c>(
                 MessageFormat::CopyDone => {
                     let _ = msg.decode::<CopyDone>()?;
                     conn.stream.recv_expect(MessageFormat::CommandComplete).await?;
-                    conn.stream.recv_expect(MessageFormat::ReadyForQuery).await?;
+                    conn.copy = None;
+                    conn.recv_ready_for_query().await?;
                     return Ok(())
                 },
                 _ => return Err(err_protocol!("unexpected message format during copy out: {:?}", msg.format))
@@ -24,6 +24,8 @@ impl Database for Postgres {
     type TypeInfo = PgTypeInfo;
 
     type Value = PgValue;
+
+    const DB_SYSTEM: &'static str = "postgresql";
 }
 
 impl<'r> HasValueRef<'r> for Postgres {
@@ -8,36 +8,55 @@ use crate::error::DatabaseError;
 use crate::postgres::message::{Notice, PgSeverity};
 
 /// An error returned from the PostgreSQL database.
-pub struct PgDatabaseError(pub(crate) Notice);
+pub struct PgDatabaseError {
+    notice: Notice,
+
+    // the query this error was returned for, kept only if the error points at a position in it
+    query: Option<Box<str>>,
+}
 
 // Error message fields are documented:
 // https://www.postgresql.org/docs/current/protocol-error-fields.html
 
 impl PgDatabaseError {
+    pub(crate) fn new(notice: Notice) -> Self {
+        Self {
+            notice,
+            query: None,
+        }
+    }
+
+    // keeps the query to point out the position of the error when displayed
+    pub(crate) fn set_query(&mut self, query: &str) {
+        if let Some(PgErrorPosition::Original(_)) = self.position() {
+            self.query = Some(query.into());
+        }
+    }
+
     #[inline]
     pub fn severity(&self) -> PgSeverity {
-        self.0.severity()
+        self.notice.severity()
     }
 
     /// The [SQLSTATE](https://www.postgresql.org/docs/current/errcodes-appendix.html) code for
     /// this error.
     #[inline]
     pub fn code(&self) -> &str {
-        self.0.code()
+        self.notice.code()
     }
 
     /// The primary human-readable error message. This should be accurate but
     /// terse (typically one line).
     #[inline]
     pub fn message(&self) -> &str {
-        self.0.message()
+        self.notice.message()
     }
 
     /// An optional secondary error message carrying more detail about the problem.
     /// Might run to multiple lines.
     #[inline]
     pub fn detail(&self) -> Option<&str> {
-        self.0.get(b'D')
+        self.notice.get(b'D')
     }
 
     /// An optional suggestion what to do about the problem. This is intended to differ from
@@ -45,20 +64,20 @@ impl PgDatabaseError {
     /// Might run to multiple lines.
     #[inline]
     pub fn hint(&self) -> Option<&str> {
-        self.0.get(b'H')
+        self.notice.get(b'H')
     }
 
     /// Indicates an error cursor position as an index into the original query string; or,
     /// a position into an internally generated query.
     #[inline]
     pub fn position(&self) -> Option<PgErrorPosition<'_>> {
-        self.0
+        self.notice
             .get_raw(b'P')
             .and_then(atoi)
             .map(PgErrorPosition::Original)
             .or_else(|| {
-                let position = self.0.get_raw(b'p').and_then(atoi)?;
-                let query = self.0.get(b'q')?;
+                let position = self.notice.get_raw(b'p').and_then(atoi)?;
+                let query = self.notice.get(b'q')?;
 
                 Some(PgErrorPosition::Internal { position, query })
             })
@@ -68,50 +87,50 @@ impl PgDatabaseError {
     /// stack traceback of active procedural language functions and internally-generated queries.
     /// The trace is one entry per line, most recent first.
     pub fn r#where(&self) -> Option<&str> {
-        self.0.get(b'W')
+        self.notice.get(b'W')
     }
 
     /// If this error is with a specific database object, the
     /// name of the schema containing that object, if any.
     pub fn schema(&self) -> Option<&str> {
-        self.0.get(b's')
+        self.notice.get(b's')
     }
 
     /// If this error is with a specific table, the name of the table.
     pub fn table(&self) -> Option<&str> {
-        self.0.get(b't')
+        self.notice.get(b't')
     }
 
     /// If the error is with a specific table column, the name of the column.
     pub fn column(&self) -> Option<&str> {
-        self.0.get(b'c')
+        self.notice.get(b'c')
     }
 
     /// If the error is with a specific data type, the name of the data type.
     pub fn data_type(&self) -> Option<&str> {
-        self.0.get(b'd')
+        self.notice.get(b'd')
     }
 
     /// If the error is with a specific constraint, the name of the constraint.
     /// For this purpose, indexes are constraints, even if they weren't created
     /// with constraint syntax.
     pub fn constraint(&self) -> Option<&str> {
-        self.0.get(b'n')
+        self.notice.get(b'n')
     }
 
     /// The file name of the source-code location where this error was reported.
     pub fn file(&self) -> Option<&str> {
-        self.0.get(b'F')
+        self.notice.get(b'F')
     }
 
     /// The line number of the source-code location where this error was reported.
     pub fn line(&self) -> Option<usize> {
-        self.0.get_raw(b'L').and_then(atoi)
+        self.notice.get_raw(b'L').and_then(atoi)
     }
 
     /// The name of the source-code routine reporting this error.
     pub fn routine(&self) -> Option<&str> {
-        self.0.get(b'R')
+        self.notice.get(b'R')
     }
 }
 
@@ -153,9 +172,55 @@ impl Debug for PgDatabaseError {
     }
 }
 
+/// If the error points at a position in the query that was executed, the line of the query
+/// is shown with the position marked, followed by the detail and hint of the error, if any:
+///
+/// ```text
+/// syntax error at or near "FORM"
+/// LINE 1: SELECT * FORM tweet
+///                  ^
+/// ```
 impl Display for PgDatabaseError {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        f.write_str(self.message())
+        f.write_str(self.message())?;
+
+        if let (Some(query), Some(PgErrorPosition::Original(position))) =
+            (&self.query, self.position())
+        {
+            // the position is 1-based, and in characters rather than bytes
+            let offset = query
+                .char_indices()
+                .nth(position.saturating_sub(1))
+                .map_or(query.len(), |(offset, _)| offset);
+
+            let line_start = query[..offset].rfind('\n').map_or(0, |i| i + 1);
+            let line_end = query[offset..]
+                .find('\n')
+                .map_or(query.len(), |i| offset + i);
+            let line_number = query[..offset].matches('\n').count() + 1;
+
+            let prefix = format!("LINE {}: ", line_number);
+            let column = query[line_start..offset].chars().count();
+
+            write!(
+                f,
+                "\n{}{}\n{:width$}^",
+                prefix,
+                query[line_start..line_end].trim_end_matches('\r'),
+                "",
+                width = prefix.len() + column
+            )?;
+        }
+
+        if let Some(detail) = self.detail() {
+            write!(f, "\nDETAIL: {}", detail)?;
+        }
+
+        if let Some(hint) = self.hint() {
+            write!(f, "\nHINT: {}", hint)?;
+        }
+
+        Ok(())
     }
 }
 
@@ -188,4 +253,101 @@ impl DatabaseError for PgDatabaseError {
     fn constraint(&self) -> Option<&str> {
         self.constraint()
     }
+
+    // too_many_connections, for the server, a database or a role
+    fn is_connection_limit(&self) -> bool {
+        self.code() == "53300"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use super::PgDatabaseError;
+    use crate::io::Decode;
+    use crate::postgres::message::Notice;
+
+    fn error(fields: &'static [u8], query: &str) -> PgDatabaseError {
+        let mut error = PgDatabaseError::new(Notice::decode(Bytes::from_static(fields)).unwrap());
+        error.set_query(query);
+        error
+    }
+
+    #[test]
+    fn it_displays_the_position_of_an_error() {
+        let error = error(
+            b"SERROR\0VERROR\0C42601\0Msyntax error at or near \"FORM\"\0P10\0\0",
+            "SELECT * FORM tweet",
+        );
+
+        assert_eq!(
+            error.to_string(),
+            concat!(
+                "syntax error at or near \"FORM\"\n",
+                "LINE 1: SELECT * FORM tweet\n",
+                "                 ^",
+            )
+        );
+    }
+
+    #[test]
+    fn it_displays_the_position_of_an_error_after_multibyte_characters() {
+        let error = error(
+            b"SERROR\0VERROR\0C42601\0Msyntax error at or near \"FORM\"\0P22\0\0",
+            "SELECT '\u{1f980}',\n  'caf\u{e9}' FORM tweet",
+        );
+
+        assert_eq!(
+            error.to_string(),
+            concat!(
+                "syntax error at or near \"FORM\"\n",
+                "LINE 2:   'caf\u{e9}' FORM tweet\n",
+                "                 ^",
+            )
+        );
+    }
+
+    #[test]
+    fn it_displays_the_detail_and_hint_of_an_error() {
+        let error = error(
+            b"SERROR\0VERROR\0C42883\0Mfunction nope(integer) does not exist\0\
+              HNo function matches the given name and argument types.\0P8\0\0",
+            "SELECT nope(1)",
+        );
+
+        assert_eq!(
+            error.to_string(),
+            concat!(
+                "function nope(integer) does not exist\n",
+                "LINE 1: SELECT nope(1)\n",
+                "               ^\n",
+                "HINT: No function matches the given name and argument types.",
+            )
+        );
+
+        // the fields themselves are untouched
+        assert_eq!(error.message(), "function nope(integer) does not exist");
+        assert_eq!(
+            error.hint(),
+            Some("No function matches the given name and argument types.")
+        );
+    }
+
+    #[test]
+    fn it_displays_the_detail_of_an_error_without_a_position() {
+        let error = error(
+            b"SERROR\0VERROR\0C23505\0Mduplicate key value violates unique constraint \"tweet_pkey\"\0\
+              DKey (id)=(1) already exists.\0\0",
+            "INSERT INTO tweet (id) VALUES (1)",
+        );
+
+        assert_eq!(
+            error.to_string(),
+            concat!(
+                "duplicate key value violates unique constraint \"tweet_pkey\"\n",
+                "DETAIL: Key (id)=(1) already exists.",
+            )
+        );
+    }
 }
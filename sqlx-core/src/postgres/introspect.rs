@@ -0,0 +1,85 @@
+use futures_core::future::BoxFuture;
+
+use crate::error::Error;
+use crate::introspect::{private_introspect, ColumnInfo, Introspect, TableInfo};
+use crate::postgres::{PgConnection, Postgres};
+use crate::query_as::query_as;
+
+impl private_introspect::Sealed for PgConnection {}
+
+impl Introspect for PgConnection {
+    fn tables<'e>(
+        &'e mut self,
+        schema: Option<&'e str>,
+    ) -> BoxFuture<'e, Result<Vec<TableInfo>, Error>> {
+        Box::pin(async move {
+            // `parse_ident` applies the rules of an identifier; an unquoted name is folded
+            // to lowercase
+            let tables: Vec<(String, String)> = query_as(
+                r#"
+SELECT n.nspname, c.relname
+FROM pg_catalog.pg_class c
+JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+WHERE c.relkind IN ('r', 'p')
+  AND n.nspname = COALESCE((parse_ident($1))[1], current_schema())
+ORDER BY c.relname
+                "#,
+            )
+            .bind(schema)
+            .fetch_all(&mut *self)
+            .await?;
+
+            Ok(tables
+                .into_iter()
+                .map(|(schema, name)| TableInfo { schema, name })
+                .collect())
+        })
+    }
+
+    fn columns<'e>(
+        &'e mut self,
+        table: &'e str,
+    ) -> BoxFuture<'e, Result<Vec<ColumnInfo<Postgres>>, Error>> {
+        Box::pin(async move {
+            // `to_regclass` resolves the name as a query would, against the `search_path` if it
+            // is not qualified; it returns NULL, and so no rows, if there is no such table
+            let rows: Vec<(String, u32, bool, Option<String>, bool)> = query_as(
+                r#"
+SELECT a.attname,
+       a.atttypid,
+       NOT a.attnotnull,
+       pg_catalog.pg_get_expr(d.adbin, d.adrelid),
+       COALESCE(a.attnum = ANY (i.indkey), false)
+FROM pg_catalog.pg_attribute a
+LEFT JOIN pg_catalog.pg_attrdef d ON d.adrelid = a.attrelid AND d.adnum = a.attnum
+LEFT JOIN pg_catalog.pg_index i ON i.indrelid = a.attrelid AND i.indisprimary
+WHERE a.attrelid = to_regclass($1)
+  AND a.attnum > 0
+  AND NOT a.attisdropped
+ORDER BY a.attnum
+                "#,
+            )
+            .bind(table)
+            .fetch_all(&mut *self)
+            .await?;
+
+            let mut columns = Vec::with_capacity(rows.len());
+
+            for (name, type_id, nullable, default, is_primary_key) in rows {
+                columns.push(ColumnInfo {
+                    name,
+                    type_info: self.maybe_fetch_type_info_by_oid(type_id, true).await?,
+                    nullable,
+                    default,
+                    is_primary_key,
+                });
+            }
+
+            Ok(columns)
+        })
+    }
+
+    fn quote_identifier(name: &str) -> String {
+        format!("\"{}\"", name.replace('"', "\"\""))
+    }
+}
@@ -3,7 +3,7 @@ pub trait PgBufMutExt {
     where
         F: FnOnce(&mut Vec<u8>);
 
-    fn put_statement_name(&mut self, id: u32);
+    fn put_statement_name(&mut self, id: Option<u32>);
 
     fn put_portal_name(&mut self, id: Option<u32>);
 }
@@ -29,11 +29,13 @@ impl PgBufMutExt for Vec<u8> {
 
     // writes a statement name by ID
     #[inline]
-    fn put_statement_name(&mut self, id: u32) {
+    fn put_statement_name(&mut self, id: Option<u32>) {
         // N.B. if you change this don't forget to update it in ../describe.rs
-        self.extend(b"sqlx_s_");
+        if let Some(id) = id {
+            self.extend(b"sqlx_s_");
 
-        self.extend(itoa::Buffer::new().format(id).as_bytes());
+            self.extend(itoa::Buffer::new().format(id).as_bytes());
+        }
 
         self.push(0);
     }
@@ -0,0 +1,72 @@
+use std::convert::TryFrom;
+
+use crate::postgres::{PgArguments, Postgres};
+use crate::query::Query;
+use crate::query_as::QueryAs;
+use crate::query_scalar::QueryScalar;
+
+impl<'q> Query<'q, Postgres, PgArguments> {
+    /// Bind the number of rows of the parameter of `LIMIT $N` of this SQL query.
+    ///
+    /// Postgres requires the parameter of `LIMIT` and `OFFSET` to be a `BIGINT`, so `n` is
+    /// bound as an `INT8`. A number above [`i64::MAX`] is bound as `i64::MAX`, which no table
+    /// can have more rows than.
+    ///
+    /// ```rust,no_run
+    /// # async fn f(conn: &mut sqlx_core::postgres::PgConnection) -> Result<(), sqlx_core::error::Error> {
+    /// let rows = sqlx_core::query::query("SELECT name FROM users WHERE active = $1 LIMIT $2 OFFSET $3")
+    ///     .bind(true)
+    ///     .bind_limit(20)
+    ///     .bind_offset(40)
+    ///     .fetch_all(conn)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn bind_limit(self, n: u64) -> Self {
+        self.bind(i64::try_from(n).unwrap_or(i64::MAX))
+    }
+
+    /// Bind the number of rows to skip of the parameter of `OFFSET $N` of this SQL query.
+    ///
+    /// See [`bind_limit`](Query::bind_limit).
+    pub fn bind_offset(self, n: u64) -> Self {
+        self.bind_limit(n)
+    }
+}
+
+impl<'q, O> QueryAs<'q, Postgres, O, PgArguments> {
+    /// Bind the number of rows of the parameter of `LIMIT $N` of this SQL query.
+    ///
+    /// See [`Query::bind_limit`](Query::bind_limit).
+    pub fn bind_limit(mut self, n: u64) -> Self {
+        self.inner = self.inner.bind_limit(n);
+        self
+    }
+
+    /// Bind the number of rows to skip of the parameter of `OFFSET $N` of this SQL query.
+    ///
+    /// See [`Query::bind_offset`](Query::bind_offset).
+    pub fn bind_offset(mut self, n: u64) -> Self {
+        self.inner = self.inner.bind_offset(n);
+        self
+    }
+}
+
+impl<'q, O> QueryScalar<'q, Postgres, O, PgArguments> {
+    /// Bind the number of rows of the parameter of `LIMIT $N` of this SQL query.
+    ///
+    /// See [`Query::bind_limit`](Query::bind_limit).
+    pub fn bind_limit(mut self, n: u64) -> Self {
+        self.inner = self.inner.bind_limit(n);
+        self
+    }
+
+    /// Bind the number of rows to skip of the parameter of `OFFSET $N` of this SQL query.
+    ///
+    /// See [`Query::bind_offset`](Query::bind_offset).
+    pub fn bind_offset(mut self, n: u64) -> Self {
+        self.inner = self.inner.bind_offset(n);
+        self
+    }
+}
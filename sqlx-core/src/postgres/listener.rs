@@ -10,7 +10,6 @@ use futures_channel::mpsc;
 use futures_core::future::BoxFuture;
 use futures_core::stream::{BoxStream, Stream};
 use std::fmt::{self, Debug};
-use std::io;
 use std::str::from_utf8;
 
 /// A stream of asynchronous notifications from Postgres.
@@ -25,11 +24,16 @@ pub struct PgListener {
     buffer_rx: mpsc::UnboundedReceiver<Notification>,
     buffer_tx: Option<mpsc::UnboundedSender<Notification>>,
     channels: Vec<String>,
+    on_reconnect: Option<Box<dyn FnMut() + Send + Sync + 'static>>,
 }
 
 /// An asynchronous notification from Postgres.
 pub struct PgNotification(Notification);
 
+/// The size, in bytes, that a notification payload must stay below in the default server
+/// configuration.
+pub const MAX_NOTIFY_PAYLOAD_LEN: usize = 8000;
+
 impl PgListener {
     pub async fn connect(uri: &str) -> Result<Self, Error> {
         // Create a pool of 1 without timeouts (as they don't apply here)
@@ -58,9 +62,20 @@ impl PgListener {
             buffer_rx: receiver,
             buffer_tx: None,
             channels: Vec::new(),
+            on_reconnect: None,
         })
     }
 
+    /// Sets a callback to be invoked whenever the listener reconnects after losing its
+    /// connection.
+    ///
+    /// The callback is invoked after all channels have been re-subscribed and before any
+    /// further notifications are returned. Notifications sent while the connection was lost
+    /// are not delivered, so this is the place to re-scan any state they would have signalled.
+    pub fn on_reconnect(&mut self, callback: impl FnMut() + Send + Sync + 'static) {
+        self.on_reconnect = Some(Box::new(callback));
+    }
+
     /// Starts listening for notifications on a channel.
     /// The channel name is quoted here to ensure case sensitivity.
     pub async fn listen(&mut self, channel: &str) -> Result<(), Error> {
@@ -68,7 +83,9 @@ impl PgListener {
             .execute(&*format!(r#"LISTEN "{}""#, ident(channel)))
             .await?;
 
-        self.channels.push(channel.to_owned());
+        if !self.channels.iter().any(|s| s == channel) {
+            self.channels.push(channel.to_owned());
+        }
 
         Ok(())
     }
@@ -79,7 +96,12 @@ impl PgListener {
         channels: impl IntoIterator<Item = &str>,
     ) -> Result<(), Error> {
         let beg = self.channels.len();
-        self.channels.extend(channels.into_iter().map(|s| s.into()));
+
+        for channel in channels {
+            if !self.channels.iter().any(|s| s == channel) {
+                self.channels.push(channel.into());
+            }
+        }
 
         self.connection
             .as_mut()
@@ -97,9 +119,7 @@ impl PgListener {
             .execute(&*format!(r#"UNLISTEN "{}""#, ident(channel)))
             .await?;
 
-        if let Some(pos) = self.channels.iter().position(|s| s == channel) {
-            self.channels.remove(pos);
-        }
+        self.channels.retain(|s| s != channel);
 
         Ok(())
     }
@@ -124,11 +144,22 @@ impl PgListener {
                 .await?;
 
             self.connection = Some(connection);
+
+            if let Some(on_reconnect) = &mut self.on_reconnect {
+                on_reconnect();
+            }
         }
 
         Ok(())
     }
 
+    /// Returns the channels this listener is currently subscribed to.
+    ///
+    /// These are the channels that are re-subscribed after a reconnect.
+    pub fn channels(&self) -> impl Iterator<Item = &str> + '_ {
+        self.channels.iter().map(String::as_str)
+    }
+
     #[inline]
     fn connection(&mut self) -> &mut PgConnection {
         self.connection.as_mut().unwrap()
@@ -211,7 +242,7 @@ impl PgListener {
 
                 // The connection is dead, ensure that it is dropped,
                 // update self state, and loop to try again.
-                Err(Error::Io(err)) if err.kind() == io::ErrorKind::ConnectionAborted => {
+                Err(Error::ConnectionClosed(_)) => {
                     self.buffer_tx = self.connection().stream.notifications.take();
                     self.connection = None;
 
@@ -257,11 +288,48 @@ impl PgListener {
     }
 }
 
+impl PgConnection {
+    /// Sends a notification with the given payload on a channel.
+    ///
+    /// The channel and payload are sent as bind parameters so neither needs to be quoted.
+    /// The payload must be shorter than [`MAX_NOTIFY_PAYLOAD_LEN`] bytes; this is checked here
+    /// so the error is reported without a round-trip to the server.
+    pub async fn notify(&mut self, channel: &str, payload: &str) -> Result<(), Error> {
+        if payload.len() >= MAX_NOTIFY_PAYLOAD_LEN {
+            return Err(Error::Configuration(
+                format!(
+                    "notification payload must be shorter than {} bytes, got {}",
+                    MAX_NOTIFY_PAYLOAD_LEN,
+                    payload.len()
+                )
+                .into(),
+            ));
+        }
+
+        crate::query::query("SELECT pg_notify($1, $2)")
+            .bind(channel)
+            .bind(payload)
+            .execute(self)
+            .await?;
+
+        Ok(())
+    }
+}
+
 impl Drop for PgListener {
     fn drop(&mut self) {
         if let Some(mut conn) = self.connection.take() {
+            // notifications that arrive before `UNLISTEN *` is processed are discarded here; if
+            // the connection is returned to the pool still subscribed, it is rejected for queries
+            let (sender, receiver) = mpsc::unbounded();
+            conn.stream.notifications = Some(sender);
+
             let fut = async move {
-                let _ = conn.execute("UNLISTEN *").await;
+                let _receiver = receiver;
+
+                if conn.execute("UNLISTEN *").await.is_ok() {
+                    conn.stream.notifications = None;
+                }
 
                 // inline the drop handler from `PoolConnection` so it doesn't try to spawn another task
                 // otherwise, it may trigger a panic if this task is dropped because the runtime is going away:
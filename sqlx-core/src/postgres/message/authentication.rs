@@ -25,6 +25,10 @@ pub enum Authentication {
     /// The authentication exchange is successfully completed.
     Ok,
 
+    /// The frontend must now take part in a Kerberos V5 authentication dialog. Only servers
+    /// older than Postgres 9.4 request this.
+    KerberosV5,
+
     /// The frontend must now send a [PasswordMessage] containing the
     /// password in clear-text form.
     CleartextPassword,
@@ -34,6 +38,20 @@ pub enum Authentication {
     /// again using the 4-byte random salt.
     Md5Password(AuthenticationMd5Password),
 
+    /// The frontend must now send an SCM credentials message, over a Unix-domain socket.
+    /// Only servers older than Postgres 9.1 request this.
+    ScmCredential,
+
+    /// The frontend must now initiate a GSSAPI negotiation.
+    Gss,
+
+    /// This message contains the response data from the previous step of a GSSAPI or SSPI
+    /// negotiation.
+    GssContinue(Bytes),
+
+    /// The frontend must now initiate an SSPI negotiation.
+    Sspi,
+
     /// The frontend must now initiate a SASL negotiation,
     /// using one of the SASL mechanisms listed in the message.
     ///
@@ -63,6 +81,8 @@ impl Decode<'_> for Authentication {
         Ok(match buf.get_u32() {
             0 => Authentication::Ok,
 
+            2 => Authentication::KerberosV5,
+
             3 => Authentication::CleartextPassword,
 
             5 => {
@@ -72,6 +92,11 @@ impl Decode<'_> for Authentication {
                 Authentication::Md5Password(AuthenticationMd5Password { salt })
             }
 
+            6 => Authentication::ScmCredential,
+            7 => Authentication::Gss,
+            8 => Authentication::GssContinue(buf),
+            9 => Authentication::Sspi,
+
             10 => Authentication::Sasl(AuthenticationSasl(buf)),
             11 => Authentication::SaslContinue(AuthenticationSaslContinue::decode(buf)?),
             12 => Authentication::SaslFinal(AuthenticationSaslFinal::decode(buf)?),
@@ -83,6 +108,20 @@ impl Decode<'_> for Authentication {
     }
 }
 
+impl Authentication {
+    /// The name of the authentication method of this request, if it is one that is not
+    /// supported.
+    pub(crate) fn unsupported_method(&self) -> Option<&'static str> {
+        match self {
+            Authentication::KerberosV5 => Some("Kerberos V5"),
+            Authentication::ScmCredential => Some("SCM credential"),
+            Authentication::Gss | Authentication::GssContinue(_) => Some("GSSAPI"),
+            Authentication::Sspi => Some("SSPI"),
+            _ => None,
+        }
+    }
+}
+
 /// Body of [Authentication::Md5Password].
 #[derive(Debug)]
 pub struct AuthenticationMd5Password {
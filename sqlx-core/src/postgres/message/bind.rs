@@ -7,8 +7,8 @@ pub struct Bind<'a> {
     /// The ID of the destination portal (`None` selects the unnamed portal).
     pub portal: Option<u32>,
 
-    /// The id of the source prepared statement.
-    pub statement: u32,
+    /// The id of the source prepared statement (`None` selects the unnamed statement).
+    pub statement: Option<u32>,
 
     /// The parameter format codes. Each must presently be zero (text) or one (binary).
     ///
@@ -41,9 +41,17 @@ impl Encode<'_> for Bind<'_> {
 
             buf.put_statement_name(self.statement);
 
-            buf.extend(&(self.formats.len() as i16).to_be_bytes());
+            // without parameters, there is nothing for a format to apply to; no format codes
+            // are sent rather than one for every parameter
+            let formats: &[PgValueFormat] = if self.num_params == 0 {
+                &[]
+            } else {
+                self.formats
+            };
 
-            for &format in self.formats {
+            buf.extend(&(formats.len() as i16).to_be_bytes());
+
+            for &format in formats {
                 buf.extend(&(format as i16).to_be_bytes());
             }
 
@@ -60,5 +68,42 @@ impl Encode<'_> for Bind<'_> {
     }
 }
 
-// TODO: Unit Test Bind
-// TODO: Benchmark Bind
+#[test]
+fn test_encode_bind() {
+    const EXPECTED: &[u8] =
+        b"B\0\0\0\x28sqlx_p_2\0sqlx_s_1\0\0\x01\0\x01\0\x01\0\0\0\x04\0\0\0\x2a\0\x01\0\x01";
+
+    let mut buf = Vec::new();
+    let m = Bind {
+        portal: Some(2),
+        statement: Some(1),
+        formats: &[PgValueFormat::Binary],
+        num_params: 1,
+        params: b"\0\0\0\x04\0\0\0\x2a",
+        result_formats: &[PgValueFormat::Binary],
+    };
+
+    m.encode(&mut buf);
+
+    assert_eq!(buf, EXPECTED);
+}
+
+#[test]
+fn test_encode_bind_without_parameters() {
+    // no format codes, no parameters and a single format code for all result columns
+    const EXPECTED: &[u8] = b"B\0\0\0\x0e\0\0\0\0\0\0\0\x01\0\x01";
+
+    let mut buf = Vec::new();
+    let m = Bind {
+        portal: None,
+        statement: None,
+        formats: &[PgValueFormat::Binary],
+        num_params: 0,
+        params: &[],
+        result_formats: &[PgValueFormat::Binary],
+    };
+
+    m.encode(&mut buf);
+
+    assert_eq!(buf, EXPECTED);
+}
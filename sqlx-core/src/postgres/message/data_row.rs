@@ -1,5 +1,3 @@
-use std::ops::Range;
-
 use byteorder::{BigEndian, ByteOrder};
 use bytes::Bytes;
 
@@ -7,22 +5,38 @@ use crate::error::Error;
 use crate::io::Decode;
 
 /// A row of data from the database.
+///
+/// The values are not split off the message up front; a value is only sliced out of `storage`
+/// when its column is read.
 #[derive(Debug)]
 pub struct DataRow {
     pub(crate) storage: Bytes,
 
-    /// Ranges into the stored row data.
-    /// This uses `u32` instead of usize to reduce the size of this type. Values cannot be larger
-    /// than `i32` in postgres.
-    pub(crate) values: Vec<Option<Range<u32>>>,
+    /// The offset into the stored row data and the length of each value, as found by a single
+    /// scan of the message. A length of `-1` marks a NULL value.
+    /// This uses `u32` and `i32` instead of `usize` to reduce the size of this type. Values
+    /// cannot be larger than `i32` in postgres.
+    pub(crate) values: Vec<(u32, i32)>,
 }
 
 impl DataRow {
     #[inline]
     pub(crate) fn get(&self, index: usize) -> Option<&'_ [u8]> {
-        self.values[index]
-            .as_ref()
-            .map(|col| &self.storage[(col.start as usize)..(col.end as usize)])
+        let (offset, length) = self.values[index];
+
+        if length < 0 {
+            return None;
+        }
+
+        let start = offset as usize;
+
+        Some(&self.storage[start..(start + length as usize)])
+    }
+
+    /// The number of values in the row.
+    #[inline]
+    pub(crate) fn len(&self) -> usize {
+        self.values.len()
     }
 }
 
@@ -40,10 +54,9 @@ impl Decode<'_> for DataRow {
             let length = BigEndian::read_i32(&buf[(offset as usize)..]);
             offset += 4;
 
-            if length < 0 {
-                values.push(None);
-            } else {
-                values.push(Some(offset..(offset + length as u32)));
+            values.push((offset, length));
+
+            if length > 0 {
                 offset += length as u32;
             }
         }
@@ -61,7 +74,7 @@ fn test_decode_data_row() {
 
     let row = DataRow::decode(DATA.into()).unwrap();
 
-    assert_eq!(row.values.len(), 8);
+    assert_eq!(row.len(), 8);
 
     assert!(row.get(0).is_none());
     assert_eq!(row.get(1).unwrap(), &[0_u8, 0, 0, 10][..]);
@@ -73,6 +86,22 @@ fn test_decode_data_row() {
     assert_eq!(row.get(7).unwrap(), &[0_u8, 0, 0, 80][..]);
 }
 
+#[test]
+fn test_decode_data_row_with_nulls_and_empty_values() {
+    // NULL, '', 'ab', NULL, ''
+    const DATA: &[u8] = b"\x00\x05\xff\xff\xff\xff\x00\x00\x00\x00\x00\x00\x00\x02ab\xff\xff\xff\xff\x00\x00\x00\x00";
+
+    let row = DataRow::decode(DATA.into()).unwrap();
+
+    assert_eq!(row.len(), 5);
+
+    assert!(row.get(0).is_none());
+    assert_eq!(row.get(1).unwrap(), b"");
+    assert_eq!(row.get(2).unwrap(), b"ab");
+    assert!(row.get(3).is_none());
+    assert_eq!(row.get(4).unwrap(), b"");
+}
+
 #[cfg(all(test, not(debug_assertions)))]
 #[bench]
 fn bench_data_row_get(b: &mut test::Bencher) {
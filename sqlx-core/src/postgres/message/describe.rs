@@ -27,7 +27,7 @@ impl Encode<'_> for Describe {
                 // #[likely]
                 Describe::Statement(id) => {
                     buf.push(DESCRIBE_STATEMENT);
-                    buf.put_statement_name(*id);
+                    buf.put_statement_name(Some(*id));
                 }
 
                 Describe::UnnamedPortal => {
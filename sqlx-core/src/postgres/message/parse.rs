@@ -5,8 +5,8 @@ use crate::postgres::io::PgBufMutExt;
 
 #[derive(Debug)]
 pub struct Parse<'a> {
-    /// The ID of the destination prepared statement.
-    pub statement: u32,
+    /// The ID of the destination prepared statement (`None` selects the unnamed statement).
+    pub statement: Option<u32>,
 
     /// The query string to be parsed.
     pub query: &'a str,
@@ -44,7 +44,23 @@ fn test_encode_parse() {
 
     let mut buf = Vec::new();
     let m = Parse {
-        statement: 1,
+        statement: Some(1),
+        query: "SELECT $1",
+        param_types: &[25],
+    };
+
+    m.encode(&mut buf);
+
+    assert_eq!(buf, EXPECTED);
+}
+
+#[test]
+fn test_encode_parse_unnamed() {
+    const EXPECTED: &[u8] = b"P\0\0\0\x15\0SELECT $1\0\0\x01\0\0\0\x19";
+
+    let mut buf = Vec::new();
+    let m = Parse {
+        statement: None,
         query: "SELECT $1",
         param_types: &[25],
     };
@@ -1,4 +1,5 @@
-use crate::io::{BufMutExt, Encode};
+use crate::error::Error;
+use crate::io::{check_str_nul, BufMutExt, Encode};
 use crate::postgres::io::PgBufMutExt;
 
 // To begin a session, a frontend opens a connection to the server and sends a startup message.
@@ -19,6 +20,26 @@ pub struct Startup<'a> {
     pub params: &'a [(&'a str, &'a str)],
 }
 
+impl Startup<'_> {
+    /// Checks that every name and value can be sent as a NUL-terminated string.
+    pub fn check(&self) -> Result<(), Error> {
+        if let Some(username) = self.username {
+            check_str_nul("user", username)?;
+        }
+
+        if let Some(database) = self.database {
+            check_str_nul("database", database)?;
+        }
+
+        for (name, value) in self.params {
+            check_str_nul("name of startup parameter", name)?;
+            check_str_nul(name, value)?;
+        }
+
+        Ok(())
+    }
+}
+
 impl Encode<'_> for Startup<'_> {
     fn encode_with(&self, buf: &mut Vec<u8>, _: ()) {
         buf.reserve(120);
@@ -73,6 +94,22 @@ fn test_encode_startup() {
     assert_eq!(buf, EXPECTED);
 }
 
+#[test]
+fn test_check_startup() {
+    let params = [("application_name", "app\0")];
+
+    let startup = |username, database, params| Startup {
+        username: Some(username),
+        database: Some(database),
+        params,
+    };
+
+    assert!(startup("postgres", "postgres", &[]).check().is_ok());
+    assert!(startup("postgres\0", "postgres", &[]).check().is_err());
+    assert!(startup("postgres", "postgres\0other", &[]).check().is_err());
+    assert!(startup("postgres", "postgres", &params).check().is_err());
+}
+
 #[cfg(all(test, not(debug_assertions)))]
 #[bench]
 fn bench_encode_startup(b: &mut test::Bencher) {
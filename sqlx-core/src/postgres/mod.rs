@@ -9,29 +9,38 @@ mod copy;
 mod database;
 mod error;
 mod io;
+mod limit;
 mod listener;
 mod message;
+mod named;
 mod options;
+mod placeholders;
 mod query_result;
 mod row;
+mod server_timeout;
 mod statement;
 mod transaction;
 mod type_info;
+mod type_mapping;
+mod typed_bind;
 pub mod types;
 mod value;
 
+#[cfg(feature = "introspect")]
+mod introspect;
+
 #[cfg(feature = "migrate")]
 mod migrate;
 
 pub use arguments::{PgArgumentBuffer, PgArguments};
 pub use column::PgColumn;
 pub use connection::{PgConnection, PgConnectionInfo};
-pub use copy::PgCopyIn;
+pub use copy::{PgCopyIn, PgCopyInBinary, PgCopyRow};
 pub use database::Postgres;
 pub use error::{PgDatabaseError, PgErrorPosition};
-pub use listener::{PgListener, PgNotification};
+pub use listener::{PgListener, PgNotification, MAX_NOTIFY_PAYLOAD_LEN};
 pub use message::PgSeverity;
-pub use options::{PgConnectOptions, PgSslMode};
+pub use options::{PgConnectOptions, PgGssEncMode, PgSslMode, PgTargetSessionAttrs};
 pub use query_result::PgQueryResult;
 pub use row::PgRow;
 pub use statement::PgStatement;
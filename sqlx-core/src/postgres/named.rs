@@ -0,0 +1,157 @@
+use std::borrow::Cow;
+use std::fmt::Write;
+
+use crate::encode::Encode;
+use crate::error::Error;
+use crate::named::check_names;
+use crate::postgres::placeholders::named_parameters;
+use crate::postgres::{PgArguments, Postgres};
+use crate::query::Query;
+use crate::query_as::QueryAs;
+use crate::query_scalar::QueryScalar;
+use crate::types::Type;
+
+impl<'q> Query<'q, Postgres, PgArguments> {
+    /// Bind a value to every `:name` parameter of this SQL query made with
+    /// [`query_named`](crate::query::query_named).
+    ///
+    /// The parameters are replaced by `$N`, the same `$N` for every appearance of a name, so
+    /// the value is sent once.
+    pub fn bind_named<T>(mut self, name: &str, value: T) -> Self
+    where
+        T: 'q + Send + Encode<'q, Postgres> + Type<Postgres>,
+    {
+        if let Some(arguments) = &mut self.arguments {
+            arguments.add_named(name, value);
+        }
+
+        self
+    }
+}
+
+impl<'q, O> QueryAs<'q, Postgres, O, PgArguments> {
+    /// Bind a value to every `:name` parameter of this SQL query.
+    ///
+    /// See [`Query::bind_named`](Query::bind_named).
+    pub fn bind_named<T>(mut self, name: &str, value: T) -> Self
+    where
+        T: 'q + Send + Encode<'q, Postgres> + Type<Postgres>,
+    {
+        self.inner = self.inner.bind_named(name, value);
+        self
+    }
+}
+
+impl<'q, O> QueryScalar<'q, Postgres, O, PgArguments> {
+    /// Bind a value to every `:name` parameter of this SQL query.
+    ///
+    /// See [`Query::bind_named`](Query::bind_named).
+    pub fn bind_named<T>(mut self, name: &str, value: T) -> Self
+    where
+        T: 'q + Send + Encode<'q, Postgres> + Type<Postgres>,
+    {
+        self.inner = self.inner.bind_named(name, value);
+        self
+    }
+}
+
+// replaces each `:name` parameter by the `$N` of the value bound to the name
+pub(crate) fn replace_names<'q>(
+    sql: &'q str,
+    arguments: &Option<PgArguments>,
+) -> Result<Cow<'q, str>, Error> {
+    let (names, bound) = match arguments {
+        Some(PgArguments {
+            names: Some(names),
+            types,
+            ..
+        }) => (names, types.len()),
+
+        _ => return Ok(Cow::Borrowed(sql)),
+    };
+
+    if names.len() != bound {
+        return Err(Error::InvalidArguments(
+            "values cannot be bound both by name and by position to the same query".into(),
+        ));
+    }
+
+    let names: Vec<&str> = names.iter().map(String::as_str).collect();
+    let parameters = named_parameters(sql);
+
+    check_names(parameters.iter().map(|name| &sql[name.clone()]), &names)?;
+
+    let mut replaced = String::with_capacity(sql.len());
+    let mut last = 0;
+
+    for name in parameters {
+        // every name was checked to be bound above
+        let index = names.iter().position(|&n| n == &sql[name.clone()]).unwrap();
+
+        // the parameter starts at the `:` before its name
+        replaced.push_str(&sql[last..name.start - 1]);
+        let _ = write!(replaced, "${}", index + 1);
+
+        last = name.end;
+    }
+
+    replaced.push_str(&sql[last..]);
+
+    Ok(Cow::Owned(replaced))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn replace(sql: &str, names: &[&str]) -> Result<String, Error> {
+        let mut arguments = PgArguments {
+            names: Some(Vec::new()),
+            ..PgArguments::default()
+        };
+
+        for name in names {
+            arguments.add_named(name, 1_i32);
+        }
+
+        replace_names(sql, &Some(arguments)).map(Cow::into_owned)
+    }
+
+    #[test]
+    fn it_replaces_names() {
+        assert_eq!(
+            replace("SELECT :a, :b, :a", &["b", "a"]).unwrap(),
+            "SELECT $2, $1, $2"
+        );
+
+        assert_eq!(
+            replace("SELECT :a::text, ':b', $$ :b $$ -- :b", &["a"]).unwrap(),
+            "SELECT $1::text, ':b', $$ :b $$ -- :b"
+        );
+
+        assert_eq!(replace("SELECT 1", &[]).unwrap(), "SELECT 1");
+    }
+
+    #[test]
+    fn it_rejects_mismatched_names() {
+        assert!(matches!(
+            replace("SELECT :a, :b", &["a", "c"]),
+            Err(Error::NamedParameterMismatch { .. })
+        ));
+
+        assert!(matches!(
+            replace("SELECT :a", &[]),
+            Err(Error::NamedParameterMismatch { .. })
+        ));
+
+        let mut arguments = PgArguments::default();
+
+        arguments.add_named("a", 1_i32);
+        arguments.add(2_i32);
+
+        assert!(matches!(
+            replace_names("SELECT :a, $2", &Some(arguments)),
+            Err(Error::InvalidArguments(_))
+        ));
+    }
+}
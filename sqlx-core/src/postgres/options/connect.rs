@@ -1,6 +1,8 @@
 use crate::connection::ConnectOptions;
+use crate::database::Database;
 use crate::error::Error;
-use crate::postgres::{PgConnectOptions, PgConnection};
+use crate::instrument::Span;
+use crate::postgres::{PgConnectOptions, PgConnection, Postgres};
 use futures_core::future::BoxFuture;
 use log::LevelFilter;
 use std::time::Duration;
@@ -12,7 +14,14 @@ impl ConnectOptions for PgConnectOptions {
     where
         Self::Connection: Sized,
     {
-        Box::pin(PgConnection::establish(self))
+        let span = Span::connect(
+            Postgres::DB_SYSTEM,
+            &self.host,
+            self.port,
+            self.database.as_deref(),
+        );
+
+        Box::pin(span.instrument(PgConnection::establish(self)))
     }
 
     fn log_statements(&mut self, level: LevelFilter) -> &mut Self {
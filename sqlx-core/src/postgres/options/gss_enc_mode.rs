@@ -0,0 +1,46 @@
+use crate::error::Error;
+use std::str::FromStr;
+
+/// Whether a GSSAPI-encrypted connection is negotiated with the server.
+///
+/// GSSAPI encryption is not supported, so no connection ever negotiates it: with `Disable` and
+/// `Prefer` the connection goes on without it, over SSL or in plain text as the
+/// [`ssl_mode`](super::PgConnectOptions::ssl_mode) allows, and with `Require` connecting
+/// fails.
+///
+/// It is used by the [`gss_enc_mode`](super::PgConnectOptions::gss_enc_mode) method.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PgGssEncMode {
+    /// Never negotiate GSSAPI encryption.
+    Disable,
+
+    /// Negotiate GSSAPI encryption if possible, and go on without it otherwise.
+    Prefer,
+
+    /// Only connect with GSSAPI encryption.
+    Require,
+}
+
+impl Default for PgGssEncMode {
+    fn default() -> Self {
+        PgGssEncMode::Prefer
+    }
+}
+
+impl FromStr for PgGssEncMode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        Ok(match &*s.to_ascii_lowercase() {
+            "disable" => PgGssEncMode::Disable,
+            "prefer" => PgGssEncMode::Prefer,
+            "require" => PgGssEncMode::Require,
+
+            _ => {
+                return Err(Error::Configuration(
+                    format!("unknown value {:?} for `gssencmode`", s).into(),
+                ));
+            }
+        })
+    }
+}
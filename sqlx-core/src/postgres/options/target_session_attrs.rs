@@ -0,0 +1,39 @@
+use crate::error::Error;
+use std::str::FromStr;
+
+/// Which kind of server a connection is accepted from, when several hosts are tried in turn.
+///
+/// It is used by the [`target_session_attrs`](super::PgConnectOptions::target_session_attrs)
+/// method.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PgTargetSessionAttrs {
+    /// Any server that can be connected to is accepted.
+    Any,
+
+    /// Only a server that accepts writes is accepted, i.e. one where
+    /// `SHOW transaction_read_only` returns `off`, such as the primary of a cluster.
+    ReadWrite,
+}
+
+impl Default for PgTargetSessionAttrs {
+    fn default() -> Self {
+        PgTargetSessionAttrs::Any
+    }
+}
+
+impl FromStr for PgTargetSessionAttrs {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        Ok(match &*s.to_ascii_lowercase() {
+            "any" => PgTargetSessionAttrs::Any,
+            "read-write" => PgTargetSessionAttrs::ReadWrite,
+
+            _ => {
+                return Err(Error::Configuration(
+                    format!("unknown value {:?} for `target_session_attrs`", s).into(),
+                ));
+            }
+        })
+    }
+}
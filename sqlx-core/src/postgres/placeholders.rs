@@ -0,0 +1,215 @@
+// finds the `$N` placeholders of a query, to keep the types of values bound beyond them out of
+// the statement prepared for it, and the `:name` parameters of queries made with `query_named`
+
+use std::ops::Range;
+
+use crate::named::name_at;
+
+/// Returns the highest `$N` placeholder of `sql`, skipping string literals, dollar-quoted
+/// strings, quoted identifiers and comments. A query without placeholders returns `0`.
+pub(crate) fn count_placeholders(sql: &str) -> usize {
+    let bytes = sql.as_bytes();
+    let mut highest = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if let Some(end) = skip_non_code(bytes, i) {
+            i = end;
+            continue;
+        }
+
+        // a `$` inside an identifier, as in `foo$1`, is part of the identifier
+        if bytes[i] == b'$' && (i == 0 || !is_ident_byte(bytes[i - 1])) {
+            let digits = bytes[i + 1..]
+                .iter()
+                .take_while(|b| b.is_ascii_digit())
+                .count();
+
+            let n = sql[i + 1..i + 1 + digits].parse().unwrap_or(usize::MAX);
+
+            highest = highest.max(n);
+            i += 1 + digits;
+        } else {
+            i += 1;
+        }
+    }
+
+    highest
+}
+
+/// Returns the ranges of the names of the `:name` parameters of `sql`, in order, skipping the
+/// same parts of the query as [`count_placeholders`].
+pub(crate) fn named_parameters(sql: &str) -> Vec<Range<usize>> {
+    let bytes = sql.as_bytes();
+    let mut names = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if let Some(end) = skip_non_code(bytes, i) {
+            i = end;
+        } else if let Some(name) = name_at(bytes, i) {
+            i = name.end;
+            names.push(name);
+        } else {
+            i += 1;
+        }
+    }
+
+    names
+}
+
+// returns the index after the string literal, quoted identifier, comment or dollar-quoted
+// string starting at `i`, if one does
+fn skip_non_code(bytes: &[u8], i: usize) -> Option<usize> {
+    match bytes[i] {
+        b'\'' => {
+            // `E'..'` strings escape a quote with a backslash
+            let escapes = i > 0
+                && matches!(bytes[i - 1], b'e' | b'E')
+                && (i < 2 || !is_ident_byte(bytes[i - 2]));
+
+            Some(skip_quoted(bytes, i, b'\'', escapes))
+        }
+
+        b'"' => Some(skip_quoted(bytes, i, b'"', false)),
+
+        b'-' if bytes.get(i + 1) == Some(&b'-') => Some(
+            bytes[i..]
+                .iter()
+                .position(|&b| b == b'\n')
+                .map_or(bytes.len(), |end| i + end + 1),
+        ),
+
+        b'/' if bytes.get(i + 1) == Some(&b'*') => Some(skip_block_comment(bytes, i)),
+
+        // neither `$1` nor the `$` of `foo$1` opens a dollar-quoted string
+        b'$' if (i == 0 || !is_ident_byte(bytes[i - 1]))
+            && !bytes.get(i + 1).map_or(false, u8::is_ascii_digit) =>
+        {
+            Some(skip_dollar_quoted(bytes, i))
+        }
+
+        _ => None,
+    }
+}
+
+fn is_ident_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b == b'_' || b == b'$' || b >= 0x80
+}
+
+// returns the index after the closing quote, a doubled quote being part of the string
+fn skip_quoted(bytes: &[u8], start: usize, quote: u8, escapes: bool) -> usize {
+    let mut i = start + 1;
+
+    while i < bytes.len() {
+        if escapes && bytes[i] == b'\\' {
+            i += 2;
+        } else if bytes[i] == quote {
+            if bytes.get(i + 1) == Some(&quote) {
+                i += 2;
+            } else {
+                return i + 1;
+            }
+        } else {
+            i += 1;
+        }
+    }
+
+    bytes.len()
+}
+
+// block comments nest in Postgres
+fn skip_block_comment(bytes: &[u8], start: usize) -> usize {
+    let mut depth = 0;
+    let mut i = start;
+
+    while i < bytes.len() {
+        if bytes[i..].starts_with(b"/*") {
+            depth += 1;
+            i += 2;
+        } else if bytes[i..].starts_with(b"*/") {
+            depth -= 1;
+            i += 2;
+
+            if depth == 0 {
+                return i;
+            }
+        } else {
+            i += 1;
+        }
+    }
+
+    bytes.len()
+}
+
+// `$$..$$` or `$tag$..$tag$`; a `$` that does not open one is skipped on its own
+fn skip_dollar_quoted(bytes: &[u8], start: usize) -> usize {
+    let tag_len = bytes[start + 1..]
+        .iter()
+        .take_while(|&&b| b.is_ascii_alphanumeric() || b == b'_' || b >= 0x80)
+        .count();
+
+    let end = start + 1 + tag_len;
+
+    if bytes.get(end) != Some(&b'$') {
+        return start + 1;
+    }
+
+    let delimiter = &bytes[start..=end];
+    let body = end + 1;
+
+    bytes[body..]
+        .windows(delimiter.len())
+        .position(|window| window == delimiter)
+        .map_or(bytes.len(), |pos| body + pos + delimiter.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{count_placeholders, named_parameters};
+
+    fn names(sql: &str) -> Vec<&str> {
+        named_parameters(sql)
+            .into_iter()
+            .map(|range| &sql[range])
+            .collect()
+    }
+
+    #[test]
+    fn it_counts_placeholders() {
+        assert_eq!(count_placeholders("SELECT 1"), 0);
+        assert_eq!(count_placeholders("SELECT $1"), 1);
+        assert_eq!(count_placeholders("SELECT $1, $2, $1"), 2);
+        assert_eq!(count_placeholders("SELECT $3"), 3);
+        assert_eq!(count_placeholders("SELECT $10::int4"), 10);
+    }
+
+    #[test]
+    fn it_skips_placeholders_in_literals_and_comments() {
+        assert_eq!(count_placeholders("SELECT '$1', $1"), 1);
+        assert_eq!(count_placeholders("SELECT 'it''s $2', $1"), 1);
+        assert_eq!(count_placeholders(r"SELECT E'\'$2', $1"), 1);
+        assert_eq!(count_placeholders(r"SELECT '\', $1"), 1);
+        assert_eq!(count_placeholders(r#"SELECT "$2" FROM t WHERE a = $1"#), 1);
+        assert_eq!(count_placeholders("SELECT $1 -- and $2\n"), 1);
+        assert_eq!(count_placeholders("SELECT /* $2 /* $3 */ $4 */ $1"), 1);
+        assert_eq!(count_placeholders("SELECT $$ $2 $$, $1"), 1);
+        assert_eq!(count_placeholders("SELECT $fn$ $$ $2 $fn$, $1"), 1);
+        assert_eq!(count_placeholders("SELECT foo$2 FROM t WHERE a = $1"), 1);
+    }
+
+    #[test]
+    fn it_finds_named_parameters() {
+        assert_eq!(names("SELECT :a, :b, :a"), vec!["a", "b", "a"]);
+        assert_eq!(names("SELECT :a::text, (:b)::int4[]"), vec!["a", "b"]);
+        assert_eq!(names("SELECT ':a', \":b\", E'\\':c', :d"), vec!["d"]);
+        assert_eq!(
+            names("SELECT $$ :a $$, $fn$ :b $fn$, :c -- :d\n"),
+            vec!["c"]
+        );
+        assert_eq!(
+            names("SELECT /* :a /* :b */ */ arr[1:2], arr[:n]"),
+            vec!["n"]
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use std::iter::{Extend, IntoIterator};
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct PgQueryResult {
     pub(super) rows_affected: u64,
 }
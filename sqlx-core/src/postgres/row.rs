@@ -1,5 +1,7 @@
 use crate::column::ColumnIndex;
 use crate::error::Error;
+use crate::from_row::FromRow;
+use crate::owned_row::{OwnedRow, OwnedValueFormat};
 use crate::postgres::message::DataRow;
 use crate::postgres::statement::PgStatementMetadata;
 use crate::postgres::value::PgValueFormat;
@@ -8,10 +10,15 @@ use crate::row::Row;
 use std::sync::Arc;
 
 /// Implementation of [`Row`] for PostgreSQL.
+///
+/// The columns, and the map of their names to their ordinals, are built once per result set and
+/// shared by all of its rows.
 pub struct PgRow {
     pub(crate) data: DataRow,
     pub(crate) format: PgValueFormat,
     pub(crate) metadata: Arc<PgStatementMetadata>,
+    // see `Query::text_results`
+    pub(crate) text_results: bool,
 }
 
 impl crate::row::private_row::Sealed for PgRow {}
@@ -32,12 +39,92 @@ impl Row for PgRow {
         let value = self.data.get(index);
 
         Ok(PgValueRef {
-            format: self.format,
+            format: column.type_info.value_format(self.format),
             row: Some(&self.data.storage),
             type_info: column.type_info.clone(),
             value,
         })
     }
+
+    fn received_len(&self) -> usize {
+        self.data.storage.len()
+    }
+
+    fn text_results(&self) -> bool {
+        self.text_results
+    }
+
+    fn is_ambiguous(&self, name: &str) -> bool {
+        self.metadata.column_names.is_ambiguous(name)
+    }
+}
+
+impl PgRow {
+    /// Copies the values of this row into an [`OwnedRow`], which can be kept after the
+    /// connection is gone.
+    pub fn to_owned(&self) -> OwnedRow<Postgres> {
+        OwnedRow {
+            columns: Arc::new(self.metadata.columns.clone()),
+            values: (0..self.data.len())
+                .map(|index| self.data.get(index).map(<[u8]>::to_vec))
+                .collect(),
+            format: match self.format {
+                PgValueFormat::Text => OwnedValueFormat::Text,
+                PgValueFormat::Binary => OwnedValueFormat::Binary,
+            },
+            text_results: self.text_results,
+        }
+    }
+}
+
+impl Row for OwnedRow<Postgres> {
+    type Database = Postgres;
+
+    fn columns(&self) -> &[PgColumn] {
+        &self.columns
+    }
+
+    fn try_get_raw<I>(&self, index: I) -> Result<PgValueRef<'_>, Error>
+    where
+        I: ColumnIndex<Self>,
+    {
+        let index = index.index(self)?;
+        let column = &self.columns[index];
+
+        let format = match self.format {
+            OwnedValueFormat::Text => PgValueFormat::Text,
+            OwnedValueFormat::Binary => PgValueFormat::Binary,
+        };
+
+        Ok(PgValueRef {
+            format: column.type_info.value_format(format),
+            row: None,
+            type_info: column.type_info.clone(),
+            value: self.value(index),
+        })
+    }
+
+    fn text_results(&self) -> bool {
+        self.text_results
+    }
+}
+
+impl From<&'_ PgRow> for OwnedRow<Postgres> {
+    fn from(row: &PgRow) -> Self {
+        row.to_owned()
+    }
+}
+
+impl From<PgRow> for OwnedRow<Postgres> {
+    fn from(row: PgRow) -> Self {
+        row.to_owned()
+    }
+}
+
+impl<'r> FromRow<'r, PgRow> for OwnedRow<Postgres> {
+    fn from_row(row: &'r PgRow) -> Result<Self, Error> {
+        Ok(row.to_owned())
+    }
 }
 
 impl ColumnIndex<PgRow> for &'_ str {
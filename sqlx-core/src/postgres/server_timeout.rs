@@ -0,0 +1,46 @@
+use std::time::Duration;
+
+use crate::postgres::{PgArguments, Postgres};
+use crate::query::Query;
+use crate::query_as::QueryAs;
+use crate::query_scalar::QueryScalar;
+
+impl<'q> Query<'q, Postgres, PgArguments> {
+    /// Have the server cancel this query once it has run for longer than `timeout`.
+    ///
+    /// The `statement_timeout` of the query is set by a statement sent right before it, in the
+    /// same round trip. A query that runs for too long returns a
+    /// [database error](crate::error::Error::Database) with the code `57014` (`query_canceled`),
+    /// and the connection can be used as before.
+    ///
+    /// Like with `SET LOCAL`, the setting lasts until the end of the current transaction. Outside
+    /// of a transaction that is the end of this query; inside of one, it also applies to the
+    /// queries that follow until the transaction is committed or rolled back.
+    pub fn server_timeout(mut self, timeout: Duration) -> Self {
+        if let Some(arguments) = &mut self.arguments {
+            arguments.server_timeout = Some(timeout);
+        }
+
+        self
+    }
+}
+
+impl<'q, O> QueryAs<'q, Postgres, O, PgArguments> {
+    /// Have the server cancel this query once it has run for longer than `timeout`.
+    ///
+    /// See [`Query::server_timeout`](Query::server_timeout).
+    pub fn server_timeout(mut self, timeout: Duration) -> Self {
+        self.inner = self.inner.server_timeout(timeout);
+        self
+    }
+}
+
+impl<'q, O> QueryScalar<'q, Postgres, O, PgArguments> {
+    /// Have the server cancel this query once it has run for longer than `timeout`.
+    ///
+    /// See [`Query::server_timeout`](Query::server_timeout).
+    pub fn server_timeout(mut self, timeout: Duration) -> Self {
+        self.inner = self.inner.server_timeout(timeout);
+        self
+    }
+}
@@ -1,10 +1,9 @@
 use super::{PgColumn, PgTypeInfo};
-use crate::column::ColumnIndex;
+use crate::column::{ColumnIndex, ColumnNames};
 use crate::error::Error;
-use crate::ext::ustr::UStr;
+use crate::postgres::message::{ParameterDescription, RowDescription};
 use crate::postgres::{PgArguments, Postgres};
 use crate::statement::Statement;
-use crate::HashMap;
 use either::Either;
 use std::borrow::Cow;
 use std::sync::Arc;
@@ -18,8 +17,51 @@ pub struct PgStatement<'q> {
 #[derive(Debug, Default)]
 pub(crate) struct PgStatementMetadata {
     pub(crate) columns: Vec<PgColumn>,
-    pub(crate) column_names: HashMap<UStr, usize>,
+    pub(crate) column_names: ColumnNames,
     pub(crate) parameters: Vec<PgTypeInfo>,
+    // `false` if the server responded with `NoData`, meaning the statement never returns rows
+    pub(crate) returns_rows: bool,
+}
+
+impl PgStatementMetadata {
+    // whether this is the metadata of a statement the server described as given
+    pub(crate) fn describes(
+        &self,
+        parameters: &ParameterDescription,
+        rows: Option<&RowDescription>,
+    ) -> bool {
+        let parameters_match = self.parameters.len() == parameters.types.len()
+            && self
+                .parameters
+                .iter()
+                .zip(&parameters.types)
+                .all(|(ty, &oid)| match oid {
+                    // the type of a parameter the server could not infer
+                    0 => *ty == PgTypeInfo::UNKNOWN,
+                    oid => ty.0.try_oid() == Some(oid),
+                });
+
+        let columns_match = match rows {
+            Some(rows) => {
+                self.returns_rows
+                    && self.columns.len() == rows.fields.len()
+                    && self
+                        .columns
+                        .iter()
+                        .zip(&rows.fields)
+                        .all(|(column, field)| {
+                            *column.name == field.name
+                                && column.type_info.0.try_oid() == Some(field.data_type_id)
+                                && column.relation_id == field.relation_id
+                                && column.relation_attribute_no == field.relation_attribute_no
+                        })
+            }
+
+            None => !self.returns_rows,
+        };
+
+        parameters_match && columns_match
+    }
 }
 
 impl<'q> Statement<'q> for PgStatement<'q> {
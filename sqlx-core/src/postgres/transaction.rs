@@ -5,10 +5,14 @@ use crate::executor::Executor;
 use crate::postgres::message::Query;
 use crate::postgres::{PgConnection, Postgres};
 use crate::transaction::{
-    begin_ansi_transaction_sql, commit_ansi_transaction_sql, rollback_ansi_transaction_sql,
-    TransactionManager,
+    begin_ansi_transaction_sql, check_characteristics_settable, commit_ansi_transaction_sql,
+    rollback_ansi_transaction_sql, IsolationLevel, Transaction, TransactionManager,
 };
 
+// the maximum length of a global transaction identifier, which must be shorter than the 200
+// bytes Postgres keeps for it along with its terminating NUL
+const MAX_GID_LEN: usize = 199;
+
 /// Implementation of [`TransactionManager`] for PostgreSQL.
 pub struct PgTransactionManager;
 
@@ -22,6 +26,10 @@ impl TransactionManager for PgTransactionManager {
 
             conn.transaction_depth += 1;
 
+            if conn.transaction_depth == 1 {
+                conn.queries_at_begin = conn.stats.queries;
+            }
+
             Ok(())
         })
     }
@@ -63,3 +71,135 @@ impl TransactionManager for PgTransactionManager {
         }
     }
 }
+
+impl<'c> Transaction<'c, Postgres> {
+    /// Prepares this transaction for a two-phase commit under the global identifier `gid`.
+    ///
+    /// After this succeeds, the transaction is no longer associated with this connection and
+    /// must be finished with [`PgConnection::commit_prepared`] or
+    /// [`PgConnection::rollback_prepared`], which may be called from any connection.
+    /// Dropping this transaction afterwards does not roll it back.
+    ///
+    /// Savepoints cannot be prepared; this must be called on the outermost transaction.
+    pub async fn prepare_two_phase(mut self, gid: &str) -> Result<(), Error> {
+        let sql = format!("PREPARE TRANSACTION {}", quote_gid(gid)?);
+        let conn: &mut PgConnection = &mut self;
+
+        if conn.transaction_depth != 1 {
+            return Err(Error::Configuration(
+                "PREPARE TRANSACTION can only be issued from the outermost transaction".into(),
+            ));
+        }
+
+        conn.execute(&*sql).await?;
+
+        // the session is no longer in a transaction; make sure that dropping this guard
+        // does not queue a ROLLBACK
+        conn.transaction_depth -= 1;
+        self.open = false;
+
+        Ok(())
+    }
+
+    /// Sets the isolation level of this transaction with `SET TRANSACTION ISOLATION LEVEL`.
+    ///
+    /// This must be called on the outermost transaction, before any other statement is executed
+    /// in it.
+    pub async fn set_isolation_level(&mut self, level: IsolationLevel) -> Result<(), Error> {
+        set_characteristic(self, &format!("ISOLATION LEVEL {}", level.as_sql())).await
+    }
+
+    /// Sets whether this transaction is read-only with `SET TRANSACTION READ ONLY` or
+    /// `SET TRANSACTION READ WRITE`.
+    ///
+    /// This must be called on the outermost transaction, before any other statement is executed
+    /// in it.
+    pub async fn set_read_only(&mut self, read_only: bool) -> Result<(), Error> {
+        set_characteristic(self, if read_only { "READ ONLY" } else { "READ WRITE" }).await
+    }
+}
+
+async fn set_characteristic(conn: &mut PgConnection, characteristic: &str) -> Result<(), Error> {
+    check_characteristics_settable(
+        conn.transaction_depth,
+        conn.queries_at_begin,
+        conn.stats.queries,
+    )?;
+
+    conn.execute(&*format!("SET TRANSACTION {}", characteristic))
+        .await?;
+
+    // setting one characteristic does not prevent setting another
+    conn.queries_at_begin = conn.stats.queries;
+
+    Ok(())
+}
+
+impl PgConnection {
+    /// Commits the transaction previously prepared under the global identifier `gid`.
+    ///
+    /// This cannot be called from inside a transaction.
+    pub async fn commit_prepared(&mut self, gid: &str) -> Result<(), Error> {
+        self.execute(&*format!("COMMIT PREPARED {}", quote_gid(gid)?))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Rolls back the transaction previously prepared under the global identifier `gid`.
+    ///
+    /// This cannot be called from inside a transaction.
+    pub async fn rollback_prepared(&mut self, gid: &str) -> Result<(), Error> {
+        self.execute(&*format!("ROLLBACK PREPARED {}", quote_gid(gid)?))
+            .await?;
+
+        Ok(())
+    }
+}
+
+// the statements that take a GID only accept a string literal and not a bind parameter
+fn quote_gid(gid: &str) -> Result<String, Error> {
+    if gid.is_empty() || gid.len() > MAX_GID_LEN {
+        return Err(Error::Configuration(
+            format!(
+                "transaction identifier must be between 1 and {} bytes long, got {}",
+                MAX_GID_LEN,
+                gid.len()
+            )
+            .into(),
+        ));
+    }
+
+    if gid.contains('\0') {
+        return Err(Error::Configuration(
+            "transaction identifier must not contain NUL bytes".into(),
+        ));
+    }
+
+    // an escape string literal is interpreted the same regardless of
+    // the `standard_conforming_strings` setting
+    Ok(format!(
+        "E'{}'",
+        gid.replace('\\', "\\\\").replace('\'', "''")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::quote_gid;
+
+    #[test]
+    fn it_quotes_gid() {
+        assert_eq!(quote_gid("tx-1").unwrap(), "E'tx-1'");
+        assert_eq!(quote_gid("it's").unwrap(), "E'it''s'");
+        assert_eq!(quote_gid("a\\b").unwrap(), "E'a\\\\b'");
+    }
+
+    #[test]
+    fn it_rejects_invalid_gid() {
+        assert!(quote_gid("").is_err());
+        assert!(quote_gid(&"x".repeat(200)).is_err());
+        assert!(quote_gid("a\0b").is_err());
+        assert!(quote_gid(&"x".repeat(199)).is_ok());
+    }
+}
@@ -6,6 +6,7 @@ use std::ops::Deref;
 use std::sync::Arc;
 
 use crate::ext::ustr::UStr;
+use crate::postgres::PgValueFormat;
 use crate::type_info::TypeInfo;
 
 /// Type information for a PostgreSQL type.
@@ -116,6 +117,16 @@ pub enum PgType {
     JsonpathArray,
     Money,
     MoneyArray,
+    Regclass,
+    RegclassArray,
+    Regtype,
+    RegtypeArray,
+    TsVector,
+    TsVectorArray,
+    TsQuery,
+    TsQueryArray,
+    Xml,
+    XmlArray,
 
     // https://www.postgresql.org/docs/9.3/datatype-pseudo.html
     Void,
@@ -164,6 +175,19 @@ impl PgTypeInfo {
         self.0.kind()
     }
 
+    // the format of a value of this type among values in `format`; the text search types are
+    // always in text format, as we do not implement their binary format, and so is `XML`
+    pub(crate) fn value_format(&self, format: PgValueFormat) -> PgValueFormat {
+        if *self == PgTypeInfo::TS_VECTOR
+            || *self == PgTypeInfo::TS_QUERY
+            || *self == PgTypeInfo::XML
+        {
+            PgValueFormat::Text
+        } else {
+            format
+        }
+    }
+
     #[doc(hidden)]
     pub fn __type_feature_gate(&self) -> Option<&'static str> {
         if [
@@ -203,6 +227,8 @@ impl PgTypeInfo {
             Some("mac_address")
         } else if [PgTypeInfo::NUMERIC, PgTypeInfo::NUMERIC_ARRAY].contains(self) {
             Some("bigdecimal")
+        } else if self.name() == "hstore" {
+            Some("hstore")
         } else {
             None
         }
@@ -246,6 +272,8 @@ impl PgType {
             25 => PgType::Text,
             26 => PgType::Oid,
             114 => PgType::Json,
+            142 => PgType::Xml,
+            143 => PgType::XmlArray,
             199 => PgType::JsonArray,
             600 => PgType::Point,
             601 => PgType::Lseg,
@@ -307,11 +335,19 @@ impl PgType {
             1562 => PgType::Varbit,
             1563 => PgType::VarbitArray,
             1700 => PgType::Numeric,
+            2205 => PgType::Regclass,
+            2206 => PgType::Regtype,
+            2210 => PgType::RegclassArray,
+            2211 => PgType::RegtypeArray,
             2278 => PgType::Void,
             2249 => PgType::Record,
             2287 => PgType::RecordArray,
             2950 => PgType::Uuid,
             2951 => PgType::UuidArray,
+            3614 => PgType::TsVector,
+            3615 => PgType::TsQuery,
+            3643 => PgType::TsVectorArray,
+            3645 => PgType::TsQueryArray,
             3802 => PgType::Jsonb,
             3807 => PgType::JsonbArray,
             3904 => PgType::Int4Range,
@@ -373,6 +409,16 @@ impl PgType {
             PgType::Macaddr8Array => 775,
             PgType::Money => 790,
             PgType::MoneyArray => 791,
+            PgType::Regclass => 2205,
+            PgType::RegclassArray => 2210,
+            PgType::Regtype => 2206,
+            PgType::RegtypeArray => 2211,
+            PgType::TsVector => 3614,
+            PgType::TsVectorArray => 3643,
+            PgType::TsQuery => 3615,
+            PgType::TsQueryArray => 3645,
+            PgType::Xml => 142,
+            PgType::XmlArray => 143,
             PgType::Macaddr => 829,
             PgType::Inet => 869,
             PgType::BoolArray => 1000,
@@ -538,6 +584,16 @@ impl PgType {
             PgType::JsonpathArray => "JSONPATH[]",
             PgType::Money => "MONEY",
             PgType::MoneyArray => "MONEY[]",
+            PgType::Regclass => "REGCLASS",
+            PgType::RegclassArray => "REGCLASS[]",
+            PgType::Regtype => "REGTYPE",
+            PgType::RegtypeArray => "REGTYPE[]",
+            PgType::TsVector => "TSVECTOR",
+            PgType::TsVectorArray => "TSVECTOR[]",
+            PgType::TsQuery => "TSQUERY",
+            PgType::TsQueryArray => "TSQUERY[]",
+            PgType::Xml => "XML",
+            PgType::XmlArray => "XML[]",
             PgType::Void => "VOID",
             PgType::Custom(ty) => &*ty.name,
             PgType::DeclareWithOid(_) => "?",
@@ -638,6 +694,16 @@ impl PgType {
             PgType::JsonpathArray => "_jsonpath",
             PgType::Money => "money",
             PgType::MoneyArray => "_money",
+            PgType::Regclass => "regclass",
+            PgType::RegclassArray => "_regclass",
+            PgType::Regtype => "regtype",
+            PgType::RegtypeArray => "_regtype",
+            PgType::TsVector => "tsvector",
+            PgType::TsVectorArray => "_tsvector",
+            PgType::TsQuery => "tsquery",
+            PgType::TsQueryArray => "_tsquery",
+            PgType::Xml => "xml",
+            PgType::XmlArray => "_xml",
             PgType::Void => "void",
             PgType::Custom(ty) => &*ty.name,
             PgType::DeclareWithOid(_) => "?",
@@ -738,14 +804,24 @@ impl PgType {
             PgType::JsonpathArray => &PgTypeKind::Array(PgTypeInfo(PgType::Jsonpath)),
             PgType::Money => &PgTypeKind::Simple,
             PgType::MoneyArray => &PgTypeKind::Array(PgTypeInfo(PgType::Money)),
+            PgType::Regclass => &PgTypeKind::Simple,
+            PgType::RegclassArray => &PgTypeKind::Array(PgTypeInfo(PgType::Regclass)),
+            PgType::Regtype => &PgTypeKind::Simple,
+            PgType::RegtypeArray => &PgTypeKind::Array(PgTypeInfo(PgType::Regtype)),
+            PgType::TsVector => &PgTypeKind::Simple,
+            PgType::TsVectorArray => &PgTypeKind::Array(PgTypeInfo(PgType::TsVector)),
+            PgType::TsQuery => &PgTypeKind::Simple,
+            PgType::TsQueryArray => &PgTypeKind::Array(PgTypeInfo(PgType::TsQuery)),
+            PgType::Xml => &PgTypeKind::Simple,
+            PgType::XmlArray => &PgTypeKind::Array(PgTypeInfo(PgType::Xml)),
 
             PgType::Void => &PgTypeKind::Pseudo,
 
             PgType::Custom(ty) => &ty.kind,
 
-            PgType::DeclareWithOid(oid) => {
-                unreachable!("(bug) use of unresolved type declaration [oid={}]", oid);
-            }
+            // a column of a query that was not prepared has only the oid of its type, unless it
+            // is a builtin type, so nothing is known of its kind
+            PgType::DeclareWithOid(_) => &PgTypeKind::Simple,
             PgType::DeclareWithName(name) => {
                 unreachable!("(bug) use of unresolved type declaration [name={}]", name);
             }
@@ -755,7 +831,7 @@ impl PgType {
     /// If `self` is an array type, return the type info for its element.
     ///
     /// This method should only be called on resolved types: calling it on
-    /// a type that is merely declared (DeclareWithOid/Name) is a bug.
+    /// a type that is merely declared by name (DeclareWithName) is a bug.
     pub(crate) fn try_array_element(&self) -> Option<Cow<'_, PgTypeInfo>> {
         // We explicitly match on all the `None` cases to ensure an exhaustive match.
         match self {
@@ -803,6 +879,16 @@ impl PgType {
             PgType::Macaddr8Array => Some(Cow::Owned(PgTypeInfo(PgType::Macaddr8))),
             PgType::Money => None,
             PgType::MoneyArray => Some(Cow::Owned(PgTypeInfo(PgType::Money))),
+            PgType::Regclass => None,
+            PgType::RegclassArray => Some(Cow::Owned(PgTypeInfo(PgType::Regclass))),
+            PgType::Regtype => None,
+            PgType::RegtypeArray => Some(Cow::Owned(PgTypeInfo(PgType::Regtype))),
+            PgType::TsVector => None,
+            PgType::TsVectorArray => Some(Cow::Owned(PgTypeInfo(PgType::TsVector))),
+            PgType::TsQuery => None,
+            PgType::TsQueryArray => Some(Cow::Owned(PgTypeInfo(PgType::TsQuery))),
+            PgType::Xml => None,
+            PgType::XmlArray => Some(Cow::Owned(PgTypeInfo(PgType::Xml))),
             PgType::Macaddr => None,
             PgType::MacaddrArray => Some(Cow::Owned(PgTypeInfo(PgType::Macaddr))),
             PgType::Inet => None,
@@ -862,9 +948,7 @@ impl PgType {
                 PgTypeKind::Enum(_) => None,
                 PgTypeKind::Range(_) => None,
             },
-            PgType::DeclareWithOid(oid) => {
-                unreachable!("(bug) use of unresolved type declaration [oid={}]", oid);
-            }
+            PgType::DeclareWithOid(_) => None,
             PgType::DeclareWithName(name) => {
                 unreachable!("(bug) use of unresolved type declaration [name={}]", name);
             }
@@ -884,6 +968,14 @@ impl TypeInfo for PgTypeInfo {
     fn is_void(&self) -> bool {
         matches!(self.0, PgType::Void)
     }
+
+    fn is_unknown(&self) -> bool {
+        matches!(self.0, PgType::Unknown)
+    }
+
+    fn protocol_id(&self) -> Option<String> {
+        self.0.try_oid().map(|oid| format!("oid {}", oid))
+    }
 }
 
 impl PartialEq<PgCustomType> for PgCustomType {
@@ -1005,6 +1097,31 @@ impl PgTypeInfo {
     pub(crate) const MONEY: Self = Self(PgType::Money);
     pub(crate) const MONEY_ARRAY: Self = Self(PgType::MoneyArray);
 
+    // object identifiers, sent as their OID in binary format and as their name in text format
+    pub(crate) const REGCLASS: Self = Self(PgType::Regclass);
+    pub(crate) const REGCLASS_ARRAY: Self = Self(PgType::RegclassArray);
+    pub(crate) const REGTYPE: Self = Self(PgType::Regtype);
+    pub(crate) const REGTYPE_ARRAY: Self = Self(PgType::RegtypeArray);
+
+    //
+    // text search types, which SQLx sends and receives in text format only
+    // https://www.postgresql.org/docs/current/datatype-textsearch.html
+    //
+
+    pub(crate) const TS_VECTOR: Self = Self(PgType::TsVector);
+    pub(crate) const TS_VECTOR_ARRAY: Self = Self(PgType::TsVectorArray);
+
+    pub(crate) const TS_QUERY: Self = Self(PgType::TsQuery);
+    pub(crate) const TS_QUERY_ARRAY: Self = Self(PgType::TsQueryArray);
+
+    //
+    // xml type, which SQLx sends and receives in text format only
+    // https://www.postgresql.org/docs/current/datatype-xml.html
+    //
+
+    pub(crate) const XML: Self = Self(PgType::Xml);
+    pub(crate) const XML_ARRAY: Self = Self(PgType::XmlArray);
+
     //
     // date/time types
     // https://www.postgresql.org/docs/current/datatype-datetime.html
@@ -1115,8 +1232,22 @@ impl Display for PgTypeInfo {
     }
 }
 
-impl PartialEq<PgType> for PgType {
-    fn eq(&self, other: &PgType) -> bool {
+impl PgType {
+    // the type a domain is defined over, following domains of domains, or else this type
+    fn base_type(&self) -> &PgType {
+        let mut ty = self;
+
+        while let PgType::Custom(custom) = ty {
+            match &custom.kind {
+                PgTypeKind::Domain(base) => ty = &base.0,
+                _ => break,
+            }
+        }
+
+        ty
+    }
+
+    fn eq_exact(&self, other: &PgType) -> bool {
         if let (Some(a), Some(b)) = (self.try_oid(), other.try_oid()) {
             // If there are OIDs available, use OIDs to perform a direct match
             a == b
@@ -1136,6 +1267,20 @@ impl PartialEq<PgType> for PgType {
     }
 }
 
+impl PartialEq<PgType> for PgType {
+    fn eq(&self, other: &PgType) -> bool {
+        if self.eq_exact(other) {
+            return true;
+        }
+
+        // a domain is treated as the type it is defined over, so that a value of a domain over
+        // `TEXT` is encoded and decoded as any other `TEXT`
+        let (base, other_base) = (self.base_type(), other.base_type());
+
+        (!std::ptr::eq(base, self) || !std::ptr::eq(other_base, other)) && base.eq_exact(other_base)
+    }
+}
+
 #[cfg(feature = "any")]
 impl From<PgTypeInfo> for crate::any::AnyTypeInfo {
     #[inline]
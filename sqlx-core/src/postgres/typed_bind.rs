@@ -0,0 +1,66 @@
+use crate::encode::Encode;
+use crate::postgres::{PgArguments, PgTypeInfo, Postgres};
+use crate::query::Query;
+use crate::query_as::QueryAs;
+use crate::query_scalar::QueryScalar;
+
+impl<'q> Query<'q, Postgres, PgArguments> {
+    /// Bind a value for use with this SQL query, declaring the type of its parameter as `ty`
+    /// instead of the type of the value.
+    ///
+    /// The type of each parameter is sent when the query is prepared, so this decides how the
+    /// server resolves a parameter whose type it could not infer from the query alone, such as
+    /// `$1` in `SELECT $1 IS NULL`. A type that is not built-in can be given by name with
+    /// [`PgTypeInfo::with_name`].
+    ///
+    /// The value is still sent as its [`Encode`] implementation encodes it, so `ty` must be a
+    /// type that the value can be decoded as by the server, or the query returns an error.
+    ///
+    /// ```rust,no_run
+    /// # async fn f(conn: &mut sqlx_core::postgres::PgConnection) -> Result<(), sqlx_core::error::Error> {
+    /// use sqlx_core::postgres::PgTypeInfo;
+    ///
+    /// let row = sqlx_core::query::query("SELECT $1 IS NULL")
+    ///     .bind_typed(Option::<i64>::None, PgTypeInfo::with_name("int8"))
+    ///     .fetch_one(conn)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn bind_typed<T>(mut self, value: T, ty: PgTypeInfo) -> Self
+    where
+        T: 'q + Send + Encode<'q, Postgres>,
+    {
+        if let Some(arguments) = &mut self.arguments {
+            arguments.add_typed(value, ty);
+        }
+
+        self
+    }
+}
+
+impl<'q, O> QueryAs<'q, Postgres, O, PgArguments> {
+    /// Bind a value for use with this SQL query, declaring the type of its parameter as `ty`.
+    ///
+    /// See [`Query::bind_typed`](Query::bind_typed).
+    pub fn bind_typed<T>(mut self, value: T, ty: PgTypeInfo) -> Self
+    where
+        T: 'q + Send + Encode<'q, Postgres>,
+    {
+        self.inner = self.inner.bind_typed(value, ty);
+        self
+    }
+}
+
+impl<'q, O> QueryScalar<'q, Postgres, O, PgArguments> {
+    /// Bind a value for use with this SQL query, declaring the type of its parameter as `ty`.
+    ///
+    /// See [`Query::bind_typed`](Query::bind_typed).
+    pub fn bind_typed<T>(mut self, value: T, ty: PgTypeInfo) -> Self
+    where
+        T: 'q + Send + Encode<'q, Postgres>,
+    {
+        self.inner = self.inner.bind_typed(value, ty);
+        self
+    }
+}
@@ -5,6 +5,7 @@ use crate::postgres::{
     PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres,
 };
 use crate::types::Type;
+use std::borrow::Cow;
 
 impl PgHasArrayType for u8 {
     fn array_type_info() -> PgTypeInfo {
@@ -38,6 +39,18 @@ impl Encode<'_, Postgres> for Vec<u8> {
     }
 }
 
+impl<const N: usize> Encode<'_, Postgres> for [u8; N] {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+        <&[u8] as Encode<Postgres>>::encode(&self[..], buf)
+    }
+}
+
+impl Encode<'_, Postgres> for Cow<'_, [u8]> {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+        <&[u8] as Encode<Postgres>>::encode(&**self, buf)
+    }
+}
+
 impl<'r> Decode<'r, Postgres> for &'r [u8] {
     fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
         match value.format() {
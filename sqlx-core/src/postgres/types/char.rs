@@ -0,0 +1,63 @@
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::postgres::{PgArgumentBuffer, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+use crate::types::Type;
+
+impl Type<Postgres> for char {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::CHAR
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        [
+            PgTypeInfo::CHAR,
+            PgTypeInfo::BPCHAR,
+            PgTypeInfo::VARCHAR,
+            PgTypeInfo::TEXT,
+            PgTypeInfo::UNKNOWN,
+        ]
+        .contains(ty)
+    }
+}
+
+impl Encode<'_, Postgres> for char {
+    fn produces(&self) -> Option<PgTypeInfo> {
+        // "char" holds a single byte; anything else is sent as text
+        if self.is_ascii() {
+            None
+        } else {
+            Some(PgTypeInfo::TEXT)
+        }
+    }
+
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+        buf.extend(self.encode_utf8(&mut [0; 4]).as_bytes());
+
+        IsNull::No
+    }
+
+    fn size_hint(&self) -> usize {
+        self.len_utf8()
+    }
+}
+
+impl Decode<'_, Postgres> for char {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        if value.format() == PgValueFormat::Binary && value.type_info == PgTypeInfo::CHAR {
+            // "char" is sent as the byte itself
+            return match value.as_bytes()? {
+                [byte] if byte.is_ascii() => Ok(char::from(*byte)),
+                bytes => Err(format!("expected an ASCII \"char\" but found {:?}", bytes).into()),
+            };
+        }
+
+        let s = value.as_str()?;
+        let mut chars = s.chars();
+
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(format!("expected a single character but found {:?}", s).into()),
+        }
+    }
+}
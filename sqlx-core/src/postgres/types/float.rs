@@ -1,3 +1,5 @@
+use std::str::FromStr;
+
 use byteorder::{BigEndian, ByteOrder};
 
 use crate::decode::Decode;
@@ -32,7 +34,7 @@ impl Decode<'_, Postgres> for f32 {
     fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
         Ok(match value.format() {
             PgValueFormat::Binary => BigEndian::read_f32(value.as_bytes()?),
-            PgValueFormat::Text => value.as_str()?.parse()?,
+            PgValueFormat::Text => float_decode_text(value.as_str()?)?,
         })
     }
 }
@@ -61,7 +63,50 @@ impl Decode<'_, Postgres> for f64 {
     fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
         Ok(match value.format() {
             PgValueFormat::Binary => BigEndian::read_f64(value.as_bytes()?),
-            PgValueFormat::Text => value.as_str()?.parse()?,
+            PgValueFormat::Text => float_decode_text(value.as_str()?)?,
         })
     }
 }
+
+// parses a float in the TEXT format, which spells out the special values
+#[inline]
+fn float_decode_text<T>(value: &str) -> Result<T, BoxDynError>
+where
+    T: FromStr + From<f32>,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    Ok(match value {
+        "NaN" => T::from(f32::NAN),
+        "Infinity" => T::from(f32::INFINITY),
+        "-Infinity" => T::from(f32::NEG_INFINITY),
+        _ => value.parse()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::float_decode_text;
+
+    #[test]
+    fn it_decodes_text_floats() {
+        assert_eq!(float_decode_text::<f64>("1e+308").unwrap(), 1e308);
+        assert_eq!(float_decode_text::<f64>("0.5").unwrap(), 0.5);
+        assert_eq!(float_decode_text::<f64>("Infinity").unwrap(), f64::INFINITY);
+        assert_eq!(
+            float_decode_text::<f64>("-Infinity").unwrap(),
+            f64::NEG_INFINITY
+        );
+        assert!(float_decode_text::<f64>("NaN").unwrap().is_nan());
+        assert!(float_decode_text::<f32>("NaN").unwrap().is_nan());
+
+        let negative_zero = float_decode_text::<f64>("-0").unwrap();
+        assert_eq!(negative_zero, 0.0);
+        assert!(negative_zero.is_sign_negative());
+    }
+
+    #[test]
+    fn it_rejects_invalid_text_floats() {
+        assert!(float_decode_text::<f64>("").is_err());
+        assert!(float_decode_text::<f64>("1.0.0").is_err());
+    }
+}
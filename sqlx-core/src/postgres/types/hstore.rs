@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::mem;
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::postgres::{
+    PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres,
+};
+use crate::types::Type;
+
+// the type of the `hstore` extension has no fixed OID, so it is looked up by name on use
+const HSTORE: PgTypeInfo = PgTypeInfo::with_name("hstore");
+const HSTORE_ARRAY: PgTypeInfo = PgTypeInfo::with_name("_hstore");
+
+impl Type<Postgres> for HashMap<String, Option<String>> {
+    fn type_info() -> PgTypeInfo {
+        HSTORE
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        *ty == HSTORE
+    }
+}
+
+impl PgHasArrayType for HashMap<String, Option<String>> {
+    fn array_type_info() -> PgTypeInfo {
+        HSTORE_ARRAY
+    }
+}
+
+impl Encode<'_, Postgres> for HashMap<String, Option<String>> {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+        buf.extend(&(self.len() as i32).to_be_bytes());
+
+        for (key, value) in self {
+            buf.extend(&(key.len() as i32).to_be_bytes());
+            buf.extend(key.as_bytes());
+
+            match value {
+                Some(value) => {
+                    buf.extend(&(value.len() as i32).to_be_bytes());
+                    buf.extend(value.as_bytes());
+                }
+
+                // a NULL value has a length of -1
+                None => buf.extend(&(-1_i32).to_be_bytes()),
+            }
+        }
+
+        IsNull::No
+    }
+
+    fn size_hint(&self) -> usize {
+        self.iter()
+            .fold(mem::size_of::<i32>(), |size, (key, value)| {
+                size + 2 * mem::size_of::<i32>() + key.len() + value.as_ref().map_or(0, String::len)
+            })
+    }
+}
+
+impl Decode<'_, Postgres> for HashMap<String, Option<String>> {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        let mut buf = match value.format() {
+            PgValueFormat::Binary => value.as_bytes()?,
+            PgValueFormat::Text => {
+                return Err("reading a HSTORE value in text format is not supported".into());
+            }
+        };
+
+        let count = usize::try_from(read_i32(&mut buf)?)
+            .map_err(|_| "negative number of entries in HSTORE")?;
+
+        let mut map = HashMap::with_capacity(count);
+
+        for _ in 0..count {
+            let key = read_str(&mut buf)?.ok_or("NULL key in HSTORE")?;
+            let value = read_str(&mut buf)?;
+
+            map.insert(key, value);
+        }
+
+        if !buf.is_empty() {
+            return Err("trailing data after the entries of HSTORE".into());
+        }
+
+        Ok(map)
+    }
+}
+
+fn read_i32(buf: &mut &[u8]) -> Result<i32, BoxDynError> {
+    if buf.len() < mem::size_of::<i32>() {
+        return Err("unexpected end of HSTORE".into());
+    }
+
+    let (bytes, rest) = buf.split_at(mem::size_of::<i32>());
+    *buf = rest;
+
+    Ok(i32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+// a length-prefixed string, or `None` for a length of -1
+fn read_str(buf: &mut &[u8]) -> Result<Option<String>, BoxDynError> {
+    let len = read_i32(buf)?;
+
+    if len < 0 {
+        return Ok(None);
+    }
+
+    let len = len as usize;
+
+    if buf.len() < len {
+        return Err("unexpected end of HSTORE".into());
+    }
+
+    let (bytes, rest) = buf.split_at(len);
+    *buf = rest;
+
+    Ok(Some(String::from_utf8(bytes.to_vec())?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_encodes_and_decodes_hstore() {
+        let mut map = HashMap::new();
+        map.insert("a=>b".to_owned(), Some("\"quoted\"".to_owned()));
+        map.insert("null".to_owned(), None);
+
+        let mut buf = PgArgumentBuffer::default();
+        let _ = map.encode_by_ref(&mut buf);
+
+        assert_eq!(buf.len(), map.size_hint());
+
+        let decoded = HashMap::<String, Option<String>>::decode(PgValueRef {
+            value: Some(&buf),
+            row: None,
+            type_info: HSTORE,
+            format: PgValueFormat::Binary,
+        })
+        .unwrap();
+
+        assert_eq!(decoded, map);
+    }
+}
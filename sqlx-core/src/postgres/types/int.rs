@@ -1,3 +1,4 @@
+use atoi::FromRadix10SignedChecked;
 use byteorder::{BigEndian, ByteOrder};
 
 use crate::decode::Decode;
@@ -59,7 +60,7 @@ impl Decode<'_, Postgres> for i16 {
     fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
         Ok(match value.format() {
             PgValueFormat::Binary => BigEndian::read_i16(value.as_bytes()?),
-            PgValueFormat::Text => value.as_str()?.parse()?,
+            PgValueFormat::Text => int_decode_text(value.as_bytes()?)?,
         })
     }
 }
@@ -88,7 +89,7 @@ impl Decode<'_, Postgres> for u32 {
     fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
         Ok(match value.format() {
             PgValueFormat::Binary => BigEndian::read_u32(value.as_bytes()?),
-            PgValueFormat::Text => value.as_str()?.parse()?,
+            PgValueFormat::Text => int_decode_text(value.as_bytes()?)?,
         })
     }
 }
@@ -117,7 +118,7 @@ impl Decode<'_, Postgres> for i32 {
     fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
         Ok(match value.format() {
             PgValueFormat::Binary => BigEndian::read_i32(value.as_bytes()?),
-            PgValueFormat::Text => value.as_str()?.parse()?,
+            PgValueFormat::Text => int_decode_text(value.as_bytes()?)?,
         })
     }
 }
@@ -146,7 +147,68 @@ impl Decode<'_, Postgres> for i64 {
     fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
         Ok(match value.format() {
             PgValueFormat::Binary => BigEndian::read_i64(value.as_bytes()?),
-            PgValueFormat::Text => value.as_str()?.parse()?,
+            PgValueFormat::Text => int_decode_text(value.as_bytes()?)?,
         })
     }
 }
+
+// parses an integer in the TEXT format directly from the bytes of the value, skipping
+// the UTF-8 validation that going through `str::parse` would require
+#[inline]
+pub(crate) fn int_decode_text<T>(value: &[u8]) -> Result<T, BoxDynError>
+where
+    T: FromRadix10SignedChecked + From<u8>,
+{
+    // the most common case by far in small tables and counts
+    if let [digit @ b'0'..=b'9'] = value {
+        return Ok(T::from(digit - b'0'));
+    }
+
+    if value.is_empty() {
+        return Err("invalid integer: empty value".into());
+    }
+
+    match T::from_radix_10_signed_checked(value) {
+        // there must be at least one digit, and nothing after the digits
+        (Some(n), len) if len == value.len() && value[len - 1].is_ascii_digit() => Ok(n),
+
+        (None, _) => {
+            Err(format!("integer out of range: {:?}", String::from_utf8_lossy(value)).into())
+        }
+
+        _ => Err(format!("invalid integer: {:?}", String::from_utf8_lossy(value)).into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::int_decode_text;
+
+    #[test]
+    fn it_decodes_text_integers() {
+        assert_eq!(int_decode_text::<i64>(b"0").unwrap(), 0);
+        assert_eq!(int_decode_text::<i64>(b"7").unwrap(), 7);
+        assert_eq!(int_decode_text::<i64>(b"-42").unwrap(), -42);
+        assert_eq!(int_decode_text::<i64>(b"+42").unwrap(), 42);
+        assert_eq!(
+            int_decode_text::<i64>(b"-9223372036854775808").unwrap(),
+            i64::MIN
+        );
+        assert_eq!(
+            int_decode_text::<i64>(b"9223372036854775807").unwrap(),
+            i64::MAX
+        );
+        assert_eq!(int_decode_text::<u32>(b"4294967295").unwrap(), u32::MAX);
+    }
+
+    #[test]
+    fn it_rejects_invalid_text_integers() {
+        assert!(int_decode_text::<i64>(b"").is_err());
+        assert!(int_decode_text::<i64>(b"-").is_err());
+        assert!(int_decode_text::<i64>(b"12a").is_err());
+        assert!(int_decode_text::<i64>(b" 12").is_err());
+        assert!(int_decode_text::<i64>(b"9223372036854775808").is_err());
+        assert!(int_decode_text::<i16>(b"32768").is_err());
+        assert!(int_decode_text::<u32>(b"-1").is_err());
+    }
+}
@@ -6,16 +6,22 @@
 //! |---------------------------------------|------------------------------------------------------|
 //! | `bool`                                | BOOL                                                 |
 //! | `i8`                                  | "CHAR"                                               |
+//! | `char`                                | "CHAR", CHAR(1), VARCHAR, TEXT                       |
 //! | `i16`                                 | SMALLINT, SMALLSERIAL, INT2                          |
 //! | `i32`                                 | INT, SERIAL, INT4                                    |
 //! | `i64`                                 | BIGINT, BIGSERIAL, INT8                              |
 //! | `f32`                                 | REAL, FLOAT4                                         |
 //! | `f64`                                 | DOUBLE PRECISION, FLOAT8                             |
-//! | `&str`, [`String`]                    | VARCHAR, CHAR(N), TEXT, NAME                         |
+//! | `&str`, [`String`]                    | VARCHAR, CHAR(N), TEXT, NAME, REGCLASS, REGTYPE      |
 //! | `&[u8]`, `Vec<u8>`                    | BYTEA                                                |
 //! | [`PgInterval`]                        | INTERVAL                                             |
 //! | [`PgRange<T>`](PgRange)               | INT8RANGE, INT4RANGE, TSRANGE, TSTZTRANGE, DATERANGE, NUMRANGE |
 //! | [`PgMoney`]                           | MONEY                                                |
+//! | `u32`                                 | OID                                                  |
+//! | [`PgOid`]                             | OID, REGCLASS, REGTYPE                               |
+//! | [`PgTsVector`]                        | TSVECTOR                                             |
+//! | [`PgTsQuery`]                         | TSQUERY                                              |
+//! | [`PgXml`]                             | XML                                                  |
 //!
 //!
 //! ### [`bigdecimal`](https://crates.io/crates/bigdecimal)
@@ -89,6 +95,17 @@
 //! |---------------------------------------|------------------------------------------------------|
 //! | `bit_vec::BitVec`                     | BIT, VARBIT                                          |
 //!
+//! ### [`hstore`](https://www.postgresql.org/docs/current/hstore.html)
+//!
+//! Requires the `hstore` Cargo feature flag.
+//!
+//! | Rust type                             | Postgres type(s)                                     |
+//! |---------------------------------------|------------------------------------------------------|
+//! | `HashMap<String, Option<String>>`     | HSTORE                                               |
+//!
+//! The OID of `hstore` is looked up by its name, as it differs between databases that installed
+//! the extension. Values are only read in the binary format.
+//!
 //! ### [`json`](https://crates.io/crates/serde_json)
 //!
 //! Requires the `json` Cargo feature flag.
@@ -129,6 +146,14 @@
 //! Anonymous composite types are represented as tuples. Note that anonymous composites may only
 //! be returned and not sent to Postgres (this is a limitation of postgres).
 //!
+//! A tuple also decodes a value of any composite type, field by field, and is sent as one when
+//! it is bound with the type of the composite, the fields matching its types exactly:
+//!
+//! ```rust,ignore
+//! sqlx::query("INSERT INTO inventory (item) VALUES ($1)")
+//!     .bind_typed(("fuzzy dice", 42_i32, None::<BigDecimal>), PgTypeInfo::with_name("inventory_item"))
+//! ```
+//!
 //! # Arrays
 //!
 //! One-dimensional arrays are supported as `Vec<T>` or `&[T]` where `T` implements `Type`.
@@ -165,15 +190,21 @@ use crate::types::Type;
 mod array;
 mod bool;
 mod bytes;
+mod char;
 mod float;
 mod int;
 mod interval;
 mod money;
+mod oid;
 mod range;
 mod record;
 mod str;
+// declares `impl_text_type!`, used by `xml`
+#[macro_use]
+mod text_search;
 mod tuple;
 mod void;
+mod xml;
 
 #[cfg(any(feature = "chrono", feature = "time"))]
 mod time_tz;
@@ -208,10 +239,16 @@ mod mac_address;
 #[cfg(feature = "bit-vec")]
 mod bit_vec;
 
+#[cfg(feature = "hstore")]
+mod hstore;
+
 pub use array::PgHasArrayType;
 pub use interval::PgInterval;
 pub use money::PgMoney;
+pub use oid::PgOid;
 pub use range::PgRange;
+pub use text_search::{PgTsQuery, PgTsVector};
+pub use xml::PgXml;
 
 #[cfg(any(feature = "chrono", feature = "time"))]
 pub use time_tz::PgTimeTz;
@@ -0,0 +1,70 @@
+use byteorder::{BigEndian, ByteOrder};
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::postgres::types::array_compatible;
+use crate::postgres::types::int::int_decode_text;
+use crate::postgres::{
+    PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres,
+};
+use crate::types::Type;
+
+/// The PostgreSQL [`OID`] type, which identifies the rows of the system catalogs.
+///
+/// Besides `OID` itself, this decodes the OID of a `REGCLASS` or `REGTYPE` value in binary
+/// format, which is how the results of a prepared statement are sent. In text format these are
+/// sent as the name of the table or type instead, which can be decoded as a [`String`].
+///
+/// [`OID`]: https://www.postgresql.org/docs/current/datatype-oid.html
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct PgOid(pub u32);
+
+impl Type<Postgres> for PgOid {
+    fn type_info() -> PgTypeInfo {
+        PgTypeInfo::OID
+    }
+
+    fn compatible(ty: &PgTypeInfo) -> bool {
+        [PgTypeInfo::OID, PgTypeInfo::REGCLASS, PgTypeInfo::REGTYPE].contains(ty)
+    }
+}
+
+impl PgHasArrayType for PgOid {
+    fn array_type_info() -> PgTypeInfo {
+        PgTypeInfo::OID_ARRAY
+    }
+
+    fn array_compatible(ty: &PgTypeInfo) -> bool {
+        array_compatible::<PgOid>(ty)
+    }
+}
+
+impl Encode<'_, Postgres> for PgOid {
+    fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+        buf.extend(&self.0.to_be_bytes());
+
+        IsNull::No
+    }
+}
+
+impl Decode<'_, Postgres> for PgOid {
+    fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+        Ok(PgOid(match value.format() {
+            PgValueFormat::Binary => BigEndian::read_u32(value.as_bytes()?),
+            PgValueFormat::Text => int_decode_text(value.as_bytes()?)?,
+        }))
+    }
+}
+
+impl From<u32> for PgOid {
+    fn from(oid: u32) -> Self {
+        PgOid(oid)
+    }
+}
+
+impl From<PgOid> for u32 {
+    fn from(oid: PgOid) -> Self {
+        oid.0
+    }
+}
@@ -102,26 +102,32 @@ impl<'r> PgRecordDecoder<'r> {
         match self.fmt {
             PgValueFormat::Binary => {
                 let element_type_oid = self.buf.get_u32();
-                let element_type_opt = match self.typ.0.kind() {
-                    PgTypeKind::Simple if self.typ.0 == PgType::Record => {
+                let element_type_opt = match &self.typ.0 {
+                    // an anonymous record, or a composite nested in one, which is only known by
+                    // its OID; the type of each field is known from its OID as well
+                    PgType::Record | PgType::DeclareWithOid(_) => {
                         PgTypeInfo::try_from_oid(element_type_oid)
                     }
 
-                    PgTypeKind::Composite(fields) => {
-                        let ty = fields[self.ind].1.clone();
-                        if ty.0.oid() != element_type_oid {
-                            return Err("unexpected mismatch of composite type information".into());
-                        }
+                    ty => match ty.kind() {
+                        PgTypeKind::Composite(fields) => {
+                            let ty = fields[self.ind].1.clone();
+                            if ty.0.oid() != element_type_oid {
+                                return Err(
+                                    "unexpected mismatch of composite type information".into()
+                                );
+                            }
 
-                        Some(ty)
-                    }
+                            Some(ty)
+                        }
 
-                    _ => {
-                        return Err(
-                            "unexpected non-composite type being decoded as a composite type"
-                                .into(),
-                        );
-                    }
+                        _ => {
+                            return Err(
+                                "unexpected non-composite type being decoded as a composite type"
+                                    .into(),
+                            );
+                        }
+                    },
                 };
 
                 self.ind += 1;
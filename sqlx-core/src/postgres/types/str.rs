@@ -2,7 +2,9 @@ use crate::decode::Decode;
 use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
 use crate::postgres::types::array_compatible;
-use crate::postgres::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueRef, Postgres};
+use crate::postgres::{
+    PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueFormat, PgValueRef, Postgres,
+};
 use crate::types::Type;
 use std::borrow::Cow;
 
@@ -18,21 +20,13 @@ impl Type<Postgres> for str {
             PgTypeInfo::BPCHAR,
             PgTypeInfo::VARCHAR,
             PgTypeInfo::UNKNOWN,
+            PgTypeInfo::REGCLASS,
+            PgTypeInfo::REGTYPE,
         ]
         .contains(ty)
     }
 }
 
-impl Type<Postgres> for Cow<'_, str> {
-    fn type_info() -> PgTypeInfo {
-        <&str as Type<Postgres>>::type_info()
-    }
-
-    fn compatible(ty: &PgTypeInfo) -> bool {
-        <&str as Type<Postgres>>::compatible(ty)
-    }
-}
-
 impl Type<Postgres> for String {
     fn type_info() -> PgTypeInfo {
         <&str as Type<Postgres>>::type_info()
@@ -98,18 +92,33 @@ impl Encode<'_, Postgres> for String {
 
 impl<'r> Decode<'r, Postgres> for &'r str {
     fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
-        Ok(value.as_str()?)
+        Ok(as_str(&value)?)
     }
 }
 
 impl<'r> Decode<'r, Postgres> for Cow<'r, str> {
     fn decode(value: PgValueRef<'r>) -> Result<Self, BoxDynError> {
-        Ok(Cow::Borrowed(value.as_str()?))
+        Ok(Cow::Borrowed(as_str(&value)?))
     }
 }
 
 impl Decode<'_, Postgres> for String {
     fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
-        Ok(value.as_str()?.to_owned())
+        Ok(as_str(&value)?.to_owned())
+    }
+}
+
+// `REGCLASS` and `REGTYPE` are sent as a name in text format, but as an OID in binary format
+fn as_str<'r>(value: &PgValueRef<'r>) -> Result<&'r str, BoxDynError> {
+    if value.format() == PgValueFormat::Binary
+        && (value.type_info == PgTypeInfo::REGCLASS || value.type_info == PgTypeInfo::REGTYPE)
+    {
+        return Err(format!(
+            "{} is sent as an OID in binary format; decode it as `PgOid` or cast it to TEXT",
+            value.type_info
+        )
+        .into());
     }
+
+    value.as_str()
 }
@@ -0,0 +1,116 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::postgres::{PgArgumentBuffer, PgTypeInfo, PgValueFormat, PgValueRef, Postgres};
+use crate::types::Type;
+
+/// A PostgreSQL [`TSVECTOR`], the document of a full text search, in its text representation,
+/// e.g. `'fat':2 'rat':3`.
+///
+/// SQLx does not implement the binary format of `TSVECTOR`, so values of this type are sent and
+/// received in text format, even along with values in binary format. Wrapped in a composite or
+/// an array, a `TSVECTOR` is sent in binary format and cannot be decoded.
+///
+/// [`TSVECTOR`]: https://www.postgresql.org/docs/current/datatype-textsearch.html#DATATYPE-TSVECTOR
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PgTsVector(String);
+
+impl PgTsVector {
+    /// A `TSVECTOR` given in its text representation, which Postgres parses when it is bound.
+    ///
+    /// The text is taken as is, not normalized into lexemes; use `to_tsvector` in the query for
+    /// that.
+    pub fn new(text: impl Into<String>) -> Self {
+        PgTsVector(text.into())
+    }
+
+    /// The text representation of the `TSVECTOR`.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+/// A PostgreSQL [`TSQUERY`], the query of a full text search, in its text representation, e.g.
+/// `'fat' & ( 'rat' | 'cat' )`.
+///
+/// As with [`PgTsVector`], values of this type are sent and received in text format only.
+///
+/// [`TSQUERY`]: https://www.postgresql.org/docs/current/datatype-textsearch.html#DATATYPE-TSQUERY
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PgTsQuery(String);
+
+impl PgTsQuery {
+    /// A `TSQUERY` given in its text representation, which Postgres parses when it is bound.
+    ///
+    /// The text is taken as is, not normalized into lexemes; use `to_tsquery` or
+    /// `plainto_tsquery` in the query for that.
+    pub fn new(text: impl Into<String>) -> Self {
+        PgTsQuery(text.into())
+    }
+
+    /// The text representation of the `TSQUERY`.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+// implements the traits of a type that wraps the text representation of a value, which is sent
+// and received in text format; `binary_is_text` says whether the binary format, in which a value
+// within a composite or an array is sent, is the same text and can be decoded as well
+macro_rules! impl_text_type {
+    ($ty:ident, $type_info:ident, binary_is_text = $binary_is_text:tt) => {
+        impl Type<Postgres> for $ty {
+            fn type_info() -> PgTypeInfo {
+                PgTypeInfo::$type_info
+            }
+        }
+
+        impl Encode<'_, Postgres> for $ty {
+            fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+                buf.extend(self.0.as_bytes());
+
+                IsNull::No
+            }
+        }
+
+        impl Decode<'_, Postgres> for $ty {
+            fn decode(value: PgValueRef<'_>) -> Result<Self, BoxDynError> {
+                impl_text_type!(@check_format value, $binary_is_text);
+
+                Ok($ty(value.as_str()?.to_owned()))
+            }
+        }
+
+        impl Display for $ty {
+            fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+    };
+
+    (@check_format $value:ident, false) => {
+        if $value.format() == PgValueFormat::Binary {
+            return Err(format!(
+                "{} is only decoded in text format, which it is not sent in within a composite \
+                 or an array",
+                $value.type_info
+            )
+            .into());
+        }
+    };
+
+    (@check_format $value:ident, true) => {};
+}
+
+impl_text_type!(PgTsVector, TS_VECTOR, binary_is_text = false);
+impl_text_type!(PgTsQuery, TS_QUERY, binary_is_text = false);
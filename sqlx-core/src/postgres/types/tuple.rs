@@ -1,9 +1,20 @@
 use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
 use crate::error::BoxDynError;
-use crate::postgres::types::PgRecordDecoder;
-use crate::postgres::{PgHasArrayType, PgTypeInfo, PgValueRef, Postgres};
+use crate::postgres::type_info::{PgType, PgTypeKind};
+use crate::postgres::types::{PgRecordDecoder, PgRecordEncoder};
+use crate::postgres::{PgArgumentBuffer, PgHasArrayType, PgTypeInfo, PgValueRef, Postgres};
 use crate::types::Type;
 
+// a tuple decodes an anonymous record as well as any composite type, field by field
+fn compatible(ty: &PgTypeInfo) -> bool {
+    match &ty.0 {
+        PgType::Record => true,
+        PgType::Custom(ty) => matches!(ty.kind, PgTypeKind::Composite(_)),
+        _ => false,
+    }
+}
+
 macro_rules! impl_type_for_tuple {
     ($( $idx:ident : $T:ident ),*) => {
         impl<$($T,)*> Type<Postgres> for ($($T,)*) {
@@ -11,6 +22,10 @@ macro_rules! impl_type_for_tuple {
             fn type_info() -> PgTypeInfo {
                 PgTypeInfo::RECORD
             }
+
+            fn compatible(ty: &PgTypeInfo) -> bool {
+                compatible(ty)
+            }
         }
 
         impl<$($T,)*> PgHasArrayType for ($($T,)*) {
@@ -35,6 +50,29 @@ macro_rules! impl_type_for_tuple {
                 Ok(($($idx,)*))
             }
         }
+
+        impl<'q, $($T,)*> Encode<'q, Postgres> for ($($T,)*)
+        where
+            $($T: for<'a> Encode<'a, Postgres> + Type<Postgres>,)*
+        {
+            fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+                let ($($idx,)*) = self;
+                let mut encoder = PgRecordEncoder::new(buf);
+
+                $(encoder.encode($idx);)*
+
+                encoder.finish();
+
+                IsNull::No
+            }
+
+            fn size_hint(&self) -> usize {
+                let ($($idx,)*) = self;
+
+                // the field count, then the OID and length of each field
+                4 $(+ 8 + $idx.size_hint())*
+            }
+        }
     };
 }
 
@@ -0,0 +1,39 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::decode::Decode;
+use crate::encode::{Encode, IsNull};
+use crate::error::BoxDynError;
+use crate::postgres::{PgArgumentBuffer, PgTypeInfo, PgValueRef, Postgres};
+use crate::types::Type;
+
+/// A PostgreSQL [`XML`] value, a document or content fragment in its text representation.
+///
+/// Values of this type are sent and received in text format. Postgres checks that the text is
+/// well-formed XML when it is bound; SQLx does not parse it.
+///
+/// A value is read whole into its row, however large it is; it cannot be streamed from the
+/// connection.
+///
+/// [`XML`]: https://www.postgresql.org/docs/current/datatype-xml.html
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PgXml(String);
+
+impl PgXml {
+    /// An `XML` value given in its text representation.
+    pub fn new(text: impl Into<String>) -> Self {
+        PgXml(text.into())
+    }
+
+    /// The text representation of the `XML` value.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+// the binary format of `XML`, in which it is sent within a composite or an array, is its text
+// representation as well
+impl_text_type!(PgXml, XML, binary_is_text = true);
@@ -4,11 +4,16 @@ use either::Either;
 use futures_core::stream::BoxStream;
 use futures_util::{future, StreamExt, TryFutureExt, TryStreamExt};
 
+use crate::acquire::Acquire;
 use crate::arguments::{Arguments, IntoArguments};
+use crate::column::ColumnIndex;
 use crate::database::{Database, HasArguments, HasStatement, HasStatementCache};
-use crate::encode::Encode;
+use crate::decode::Decode;
+use crate::encode::{Encode, RawValue};
 use crate::error::Error;
 use crate::executor::{Execute, Executor};
+use crate::fetch_progress::{FetchProgress, ProgressOptions, ProgressTracker};
+use crate::row::Row;
 use crate::statement::Statement;
 use crate::types::Type;
 
@@ -19,6 +24,10 @@ pub struct Query<'q, DB: Database, A> {
     pub(crate) arguments: Option<A>,
     pub(crate) database: PhantomData<DB>,
     pub(crate) persistent: bool,
+    pub(crate) read_only: Option<bool>,
+    pub(crate) text_results: bool,
+    // see `Query::retry_transient`
+    pub(crate) retry: Option<fn(&A) -> A>,
 }
 
 /// SQL query that will map its results to owned Rust types.
@@ -36,6 +45,17 @@ pub struct Map<'q, DB: Database, F, A> {
     mapper: F,
 }
 
+/// SQL query that does not return any rows.
+///
+/// Returned by `query!()` for statements without a result set, such as an `INSERT`, `UPDATE` or
+/// `DELETE` without a `RETURNING` clause. As there are no rows to fetch, only
+/// [`execute`](ExecuteOnly::execute) and [`execute_many`](ExecuteOnly::execute_many) are
+/// available.
+#[must_use = "query must be executed to affect database"]
+pub struct ExecuteOnly<'q, DB: Database, A> {
+    inner: Query<'q, DB, A>,
+}
+
 impl<'q, DB, A> Execute<'q, DB> for Query<'q, DB, A>
 where
     DB: Database,
@@ -65,6 +85,30 @@ where
     fn persistent(&self) -> bool {
         self.persistent
     }
+
+    #[inline]
+    fn read_only(&self) -> Option<bool> {
+        self.read_only
+    }
+
+    #[inline]
+    fn text_results(&self) -> bool {
+        self.text_results
+    }
+
+    fn retry(&self) -> Option<Self> {
+        let clone = self.retry?;
+
+        Some(Query {
+            statement: self.statement,
+            arguments: self.arguments.as_ref().map(clone),
+            database: PhantomData,
+            persistent: self.persistent,
+            read_only: self.read_only,
+            text_results: self.text_results,
+            retry: None,
+        })
+    }
 }
 
 impl<'q, DB: Database> Query<'q, DB, <DB as HasArguments<'q>>::Arguments> {
@@ -83,6 +127,22 @@ impl<'q, DB: Database> Query<'q, DB, <DB as HasArguments<'q>>::Arguments> {
 
         self
     }
+
+    /// Bind an already encoded value, or `NULL` for `None`, with the given type information.
+    ///
+    /// This is an escape hatch for types the driver does not support, such as a type of an
+    /// extension. The bytes must be in the binary wire format of the type and are sent as is;
+    /// neither they nor the type are checked. See [`RawValue`].
+    pub fn bind_raw(mut self, type_info: DB::TypeInfo, value: Option<&'q [u8]>) -> Self
+    where
+        RawValue<'q, DB>: Encode<'q, DB> + Type<DB>,
+    {
+        if let Some(arguments) = &mut self.arguments {
+            arguments.add_raw(type_info, value);
+        }
+
+        self
+    }
 }
 
 impl<'q, DB, A> Query<'q, DB, A>
@@ -103,6 +163,60 @@ where
     }
 }
 
+impl<'q, DB, A> Query<'q, DB, A>
+where
+    DB: Database,
+{
+    /// Mark this query as read-only, or as a write with `false`.
+    ///
+    /// A [`ReadWriteSplit`](crate::pool::ReadWriteSplit) runs a read-only query on one of its
+    /// replicas and any other query on its primary. This has no effect when the query is
+    /// executed anywhere else; in particular, the database does not enforce that the query only
+    /// reads.
+    pub fn read_only(mut self, value: bool) -> Self {
+        self.read_only = Some(value);
+        self
+    }
+
+    /// If `true`, the rows of the query are requested in the text format of the database, as
+    /// it renders them for display, so that every column can be read as a `String` whatever its
+    /// type, including the types SQLx cannot decode; e.g. for an interactive SQL console.
+    ///
+    /// As every value is decoded from its text, [`Row::try_get`](crate::row::Row::try_get)
+    /// does not check the type of the column against the Rust type it is decoded as.
+    ///
+    /// Only PostgreSQL and MySQL support this; other databases ignore it. MySQL only sends rows
+    /// in text format through its text protocol, which does not take bound values, so the rows
+    /// of a query with bound values are still in binary format there.
+    ///
+    /// Default: `false`.
+    pub fn text_results(mut self, value: bool) -> Self {
+        self.text_results = value;
+        self
+    }
+}
+
+impl<'q, DB, A> Query<'q, DB, A>
+where
+    DB: Database,
+    A: Clone,
+{
+    /// If `true`, the query is run once more on a new connection if the connection it was
+    /// sent on is lost before it returned any result.
+    ///
+    /// This only applies to `fetch_one`, `fetch_optional` and `fetch_all` on a
+    /// [`Pool`](crate::pool::Pool), and only to a `SELECT` that was not marked with
+    /// [`read_only(false)`](Query::read_only); a query is never retried once it returned a row,
+    /// nor by `execute`. The query must be safe to run twice, as the database may have run it
+    /// before the connection was lost.
+    ///
+    /// Default: `false`.
+    pub fn retry_transient(mut self, value: bool) -> Self {
+        self.retry = if value { Some(A::clone) } else { None };
+        self
+    }
+}
+
 impl<'q, DB, A: Send> Query<'q, DB, A>
 where
     DB: Database,
@@ -225,6 +339,227 @@ where
     {
         executor.fetch_optional(self).await
     }
+
+    /// Execute the query and return the generated results as a stream, like
+    /// [`fetch`](Query::fetch), reporting the progress of the fetch to `on_progress`.
+    ///
+    /// `on_progress` is called between rows as often as `options` asks, and once more when the
+    /// query completed. It only observes the fetch, which continues once it returns.
+    ///
+    /// The number of rows the query returns is not known ahead; give an estimate of it with
+    /// [`ProgressOptions::estimated_rows`], or count the rows first with
+    /// [`fetch_with_progress_counted`](Query::fetch_with_progress_counted), to be told the
+    /// [`fraction`](FetchProgress::fraction) fetched.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use sqlx_core::error::Error;
+    /// # #[cfg(feature = "postgres")]
+    /// # fn main() -> Result<(), Error> {
+    /// #     sqlx_rt::block_on(async move {
+    /// # use sqlx_core::connection::Connection;
+    /// use futures_util::TryStreamExt;
+    /// use sqlx_core::fetch_progress::ProgressOptions;
+    ///
+    /// let mut conn = sqlx_core::postgres::PgConnection::connect("postgres://").await?;
+    /// let options = ProgressOptions::new().every_rows(10_000).estimated_rows(1_000_000);
+    ///
+    /// let mut rows = sqlx_core::query::query("SELECT * FROM events")
+    ///     .fetch_with_progress(&mut conn, options, |p| {
+    ///         println!("{} rows, {} bytes, {:?}", p.rows, p.bytes, p.fraction());
+    ///     });
+    ///
+    /// while let Some(row) = rows.try_next().await? {
+    ///     // export `row`
+    /// }
+    /// #         Ok(())
+    /// #     })
+    /// # }
+    /// ```
+    pub fn fetch_with_progress<'e, 'c: 'e, E, F>(
+        self,
+        executor: E,
+        options: ProgressOptions,
+        on_progress: F,
+    ) -> BoxStream<'e, Result<DB::Row, Error>>
+    where
+        'q: 'e,
+        A: 'e,
+        E: 'e + Executor<'c, Database = DB>,
+        F: 'e + FnMut(&FetchProgress) + Send,
+    {
+        let mut steps = executor.fetch_many(self);
+
+        Box::pin(try_stream! {
+            let mut tracker = ProgressTracker::new(options, on_progress);
+
+            while let Some(step) = steps.try_next().await? {
+                if let Either::Right(row) = step {
+                    tracker.row(row.received_len());
+
+                    r#yield!(row);
+                }
+            }
+
+            tracker.finish();
+
+            Ok(())
+        })
+    }
+}
+
+impl<'q, DB, A> Query<'q, DB, A>
+where
+    DB: Database,
+    A: Send + Clone + for<'x> IntoArguments<'x, DB>,
+{
+    /// Count the rows the query returns with a `SELECT COUNT(*)` of it, then execute it like
+    /// [`fetch_with_progress`](Query::fetch_with_progress) with the count as the estimated
+    /// number of rows.
+    ///
+    /// The query is run twice, so the count is only exact if the data does not change in
+    /// between, e.g. in a transaction of the `REPEATABLE READ` isolation level. The query must
+    /// be valid as a subquery of `SELECT COUNT(*) FROM (...)`, and the `COUNT(*)` of the
+    /// database must be a `BIGINT`, as it is in PostgreSQL, MySQL and SQLite.
+    pub fn fetch_with_progress_counted<'a, 'e, C, F>(
+        self,
+        conn: C,
+        options: ProgressOptions,
+        on_progress: F,
+    ) -> BoxStream<'e, Result<DB::Row, Error>>
+    where
+        'q: 'e,
+        'a: 'e,
+        A: 'e,
+        C: 'e + Acquire<'a, Database = DB> + Send,
+        F: 'e + FnMut(&FetchProgress) + Send,
+        for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+        for<'r> i64: Decode<'r, DB> + Type<DB>,
+        usize: ColumnIndex<DB::Row>,
+    {
+        Box::pin(try_stream! {
+            let mut conn = conn.acquire().await?;
+
+            let sql = format!(
+                "SELECT COUNT(*) FROM ({}) AS _sqlx_count",
+                self.sql().trim_end().trim_end_matches(';')
+            );
+
+            let count = Query {
+                statement: Either::Left(&sql),
+                arguments: self.arguments.clone(),
+                database: PhantomData,
+                persistent: false,
+                read_only: self.read_only,
+                text_results: false,
+                retry: None,
+            };
+
+            let rows: i64 = (&mut *conn).fetch_one(count).await?.try_get(0)?;
+            let options = options.estimated_rows(rows.max(0) as u64);
+
+            let mut rows = self.fetch_with_progress(&mut *conn, options, on_progress);
+
+            while let Some(row) = rows.try_next().await? {
+                r#yield!(row);
+            }
+
+            Ok(())
+        })
+    }
+}
+
+impl<'q, DB, A> Query<'q, DB, A>
+where
+    DB: Database,
+{
+    // used by `query!()` once it knows the statement does not return rows
+    #[doc(hidden)]
+    pub fn __execute_only(self) -> ExecuteOnly<'q, DB, A> {
+        ExecuteOnly { inner: self }
+    }
+}
+
+impl<'q, DB, A> Execute<'q, DB> for ExecuteOnly<'q, DB, A>
+where
+    DB: Database,
+    A: Send + IntoArguments<'q, DB>,
+{
+    #[inline]
+    fn sql(&self) -> &'q str {
+        self.inner.sql()
+    }
+
+    #[inline]
+    fn statement(&self) -> Option<&<DB as HasStatement<'q>>::Statement> {
+        self.inner.statement()
+    }
+
+    #[inline]
+    fn take_arguments(&mut self) -> Option<<DB as HasArguments<'q>>::Arguments> {
+        self.inner.take_arguments()
+    }
+
+    #[inline]
+    fn persistent(&self) -> bool {
+        self.inner.persistent()
+    }
+
+    #[inline]
+    fn read_only(&self) -> Option<bool> {
+        Execute::read_only(&self.inner)
+    }
+
+    #[inline]
+    fn text_results(&self) -> bool {
+        Execute::text_results(&self.inner)
+    }
+}
+
+impl<'q, DB, A> ExecuteOnly<'q, DB, A>
+where
+    DB: Database + HasStatementCache,
+{
+    /// If `true`, the statement will get prepared once and cached to the
+    /// connection's statement cache.
+    ///
+    /// See [`Query::persistent`].
+    pub fn persistent(mut self, value: bool) -> Self {
+        self.inner = self.inner.persistent(value);
+        self
+    }
+}
+
+impl<'q, DB, A: Send> ExecuteOnly<'q, DB, A>
+where
+    DB: Database,
+    A: 'q + IntoArguments<'q, DB>,
+{
+    /// Execute the query and return the total number of rows affected.
+    #[inline]
+    pub async fn execute<'e, 'c: 'e, E>(self, executor: E) -> Result<DB::QueryResult, Error>
+    where
+        'q: 'e,
+        A: 'e,
+        E: Executor<'c, Database = DB>,
+    {
+        executor.execute(self).await
+    }
+
+    /// Execute multiple queries and return the rows affected from each query, in a stream.
+    #[inline]
+    pub async fn execute_many<'e, 'c: 'e, E>(
+        self,
+        executor: E,
+    ) -> BoxStream<'e, Result<DB::QueryResult, Error>>
+    where
+        'q: 'e,
+        A: 'e,
+        E: Executor<'c, Database = DB>,
+    {
+        executor.execute_many(self)
+    }
 }
 
 impl<'q, DB, F: Send, A: Send> Execute<'q, DB> for Map<'q, DB, F, A>
@@ -251,6 +586,16 @@ where
     fn persistent(&self) -> bool {
         self.inner.arguments.is_some()
     }
+
+    #[inline]
+    fn read_only(&self) -> Option<bool> {
+        Execute::read_only(&self.inner)
+    }
+
+    #[inline]
+    fn text_results(&self) -> bool {
+        Execute::text_results(&self.inner)
+    }
 }
 
 impl<'q, DB, F, O, A> Map<'q, DB, F, A>
@@ -406,6 +751,9 @@ where
         arguments: Some(Default::default()),
         statement: Either::Right(statement),
         persistent: true,
+        read_only: None,
+        text_results: false,
+        retry: None,
     }
 }
 
@@ -423,6 +771,9 @@ where
         arguments: Some(arguments),
         statement: Either::Right(statement),
         persistent: true,
+        read_only: None,
+        text_results: false,
+        retry: None,
     }
 }
 
@@ -436,6 +787,60 @@ where
         arguments: Some(Default::default()),
         statement: Either::Left(sql),
         persistent: true,
+        read_only: None,
+        text_results: false,
+        retry: None,
+    }
+}
+
+/// Make a SQL query with named parameters.
+///
+/// Each `:name` of the query is a parameter, and values are bound to them by name with
+/// `bind_named`. A name can appear any number of times and is bound once. Before the query is
+/// sent, the names are replaced by the placeholders of the database: Postgres uses one `$N` for
+/// every appearance of a name, and MySQL a `?` for each appearance, with the value repeated.
+///
+/// A `:` inside a string literal, a quoted identifier or a comment, or that follows a name or
+/// another `:` (as in the cast `$1::text` or the slice `a[1:n]`), is not a parameter.
+///
+/// Executing the query returns [`Error::NamedParameterMismatch`] when a name of the query was
+/// not bound, or when a name was bound that the query does not use or was bound twice. Values
+/// cannot be bound by position (`bind`) to the same query; executing it then returns
+/// [`Error::InvalidArguments`].
+///
+/// Named parameters are supported for Postgres and MySQL.
+///
+/// ```rust,no_run
+/// # async fn f(conn: &mut sqlx_core::postgres::PgConnection) -> Result<(), sqlx_core::error::Error> {
+/// let rows = sqlx_core::query::query_named(
+///     "SELECT name FROM users WHERE (owner_id = :user OR editor_id = :user) AND active = :active",
+/// )
+/// .bind_named("user", 42_i64)
+/// .bind_named("active", true)
+/// .fetch_all(conn)
+/// .await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`Error::NamedParameterMismatch`]: crate::error::Error::NamedParameterMismatch
+/// [`Error::InvalidArguments`]: crate::error::Error::InvalidArguments
+pub fn query_named<DB>(sql: &str) -> Query<'_, DB, <DB as HasArguments<'_>>::Arguments>
+where
+    DB: Database,
+{
+    let mut arguments = <DB as HasArguments<'_>>::Arguments::default();
+
+    arguments.expect_named();
+
+    Query {
+        database: PhantomData,
+        arguments: Some(arguments),
+        statement: Either::Left(sql),
+        persistent: true,
+        read_only: None,
+        text_results: false,
+        retry: None,
     }
 }
 
@@ -450,5 +855,8 @@ where
         arguments: Some(arguments),
         statement: Either::Left(sql),
         persistent: true,
+        read_only: None,
+        text_results: false,
+        retry: None,
     }
 }
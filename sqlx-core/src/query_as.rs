@@ -6,7 +6,7 @@ use futures_util::{StreamExt, TryStreamExt};
 
 use crate::arguments::IntoArguments;
 use crate::database::{Database, HasArguments, HasStatement, HasStatementCache};
-use crate::encode::Encode;
+use crate::encode::{Encode, RawValue};
 use crate::error::Error;
 use crate::executor::{Execute, Executor};
 use crate::from_row::FromRow;
@@ -45,6 +45,23 @@ where
     fn persistent(&self) -> bool {
         self.inner.persistent()
     }
+
+    #[inline]
+    fn read_only(&self) -> Option<bool> {
+        Execute::read_only(&self.inner)
+    }
+
+    #[inline]
+    fn text_results(&self) -> bool {
+        Execute::text_results(&self.inner)
+    }
+
+    fn retry(&self) -> Option<Self> {
+        Some(QueryAs {
+            inner: self.inner.retry()?,
+            output: PhantomData,
+        })
+    }
 }
 
 impl<'q, DB: Database, O> QueryAs<'q, DB, O, <DB as HasArguments<'q>>::Arguments> {
@@ -55,6 +72,17 @@ impl<'q, DB: Database, O> QueryAs<'q, DB, O, <DB as HasArguments<'q>>::Arguments
         self.inner = self.inner.bind(value);
         self
     }
+
+    /// Bind an already encoded value, or `NULL` for `None`, with the given type information.
+    ///
+    /// See [`Query::bind_raw`](Query::bind_raw).
+    pub fn bind_raw(mut self, type_info: DB::TypeInfo, value: Option<&'q [u8]>) -> Self
+    where
+        RawValue<'q, DB>: Encode<'q, DB> + Type<DB>,
+    {
+        self.inner = self.inner.bind_raw(type_info, value);
+        self
+    }
 }
 
 impl<'q, DB, O, A> QueryAs<'q, DB, O, A>
@@ -75,6 +103,42 @@ where
     }
 }
 
+impl<'q, DB, O, A> QueryAs<'q, DB, O, A>
+where
+    DB: Database,
+{
+    /// Mark this query as read-only, or as a write with `false`.
+    ///
+    /// See [`Query::read_only`](crate::query::Query::read_only).
+    pub fn read_only(mut self, value: bool) -> Self {
+        self.inner = self.inner.read_only(value);
+        self
+    }
+
+    /// Request the rows of the query in the text format of the database.
+    ///
+    /// See [`Query::text_results`](crate::query::Query::text_results).
+    pub fn text_results(mut self, value: bool) -> Self {
+        self.inner = self.inner.text_results(value);
+        self
+    }
+}
+
+impl<'q, DB, O, A> QueryAs<'q, DB, O, A>
+where
+    DB: Database,
+    A: Clone,
+{
+    /// If `true`, the query is run once more on a new connection if the connection it was
+    /// sent on is lost before it returned any result.
+    ///
+    /// See [`Query::retry_transient`](crate::query::Query::retry_transient).
+    pub fn retry_transient(mut self, value: bool) -> Self {
+        self.inner = self.inner.retry_transient(value);
+        self
+    }
+}
+
 // FIXME: This is very close, nearly 1:1 with `Map`
 // noinspection DuplicatedCode
 impl<'q, DB, O, A> QueryAs<'q, DB, O, A>
@@ -134,7 +198,12 @@ where
         O: 'e,
         A: 'e,
     {
-        self.fetch(executor).try_collect().await
+        executor
+            .fetch_all(self.inner)
+            .await?
+            .iter()
+            .map(O::from_row)
+            .collect()
     }
 
     /// Execute the query and returns exactly one row.
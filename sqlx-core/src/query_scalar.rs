@@ -4,20 +4,53 @@ use futures_util::{StreamExt, TryFutureExt, TryStreamExt};
 
 use crate::arguments::IntoArguments;
 use crate::database::{Database, HasArguments, HasStatement, HasStatementCache};
-use crate::encode::Encode;
+use crate::encode::{Encode, RawValue};
 use crate::error::Error;
 use crate::executor::{Execute, Executor};
 use crate::from_row::FromRow;
 use crate::query_as::{
     query_as, query_as_with, query_statement_as, query_statement_as_with, QueryAs,
 };
+use crate::row::Row;
 use crate::types::Type;
 
 /// Raw SQL query with bind parameters, mapped to a concrete type using [`FromRow`] on `(O,)`.
 /// Returned from [`query_scalar`].
+///
+/// Each row must have exactly one column; a row with more columns returns an error rather
+/// than have its other columns silently ignored.
 #[must_use = "query must be executed to affect database"]
 pub struct QueryScalar<'q, DB: Database, O, A> {
-    inner: QueryAs<'q, DB, (O,), A>,
+    pub(crate) inner: QueryAs<'q, DB, Scalar<O>, A>,
+}
+
+// the value of a row with a single column
+pub(crate) struct Scalar<O>(O);
+
+impl<'r, R, O> FromRow<'r, R> for Scalar<O>
+where
+    R: Row,
+    (O,): FromRow<'r, R>,
+{
+    #[inline]
+    fn from_row(row: &'r R) -> Result<Self, Error> {
+        check_columns(row)?;
+
+        let (value,) = <(O,)>::from_row(row)?;
+
+        Ok(Scalar(value))
+    }
+}
+
+// a row with more than one column is an error rather than have its other columns ignored
+fn check_columns<R: Row>(row: &R) -> Result<(), Error> {
+    if row.len() > 1 {
+        return Err(Error::Decode(
+            format!("expected 1 column but the query returned {}", row.len()).into(),
+        ));
+    }
+
+    Ok(())
 }
 
 impl<'q, DB: Database, O: Send, A: Send> Execute<'q, DB> for QueryScalar<'q, DB, O, A>
@@ -42,6 +75,22 @@ where
     fn persistent(&self) -> bool {
         self.inner.persistent()
     }
+
+    #[inline]
+    fn read_only(&self) -> Option<bool> {
+        Execute::read_only(&self.inner)
+    }
+
+    #[inline]
+    fn text_results(&self) -> bool {
+        Execute::text_results(&self.inner)
+    }
+
+    fn retry(&self) -> Option<Self> {
+        Some(QueryScalar {
+            inner: self.inner.retry()?,
+        })
+    }
 }
 
 impl<'q, DB: Database, O> QueryScalar<'q, DB, O, <DB as HasArguments<'q>>::Arguments> {
@@ -52,6 +101,17 @@ impl<'q, DB: Database, O> QueryScalar<'q, DB, O, <DB as HasArguments<'q>>::Argum
         self.inner = self.inner.bind(value);
         self
     }
+
+    /// Bind an already encoded value, or `NULL` for `None`, with the given type information.
+    ///
+    /// See [`Query::bind_raw`](crate::query::Query::bind_raw).
+    pub fn bind_raw(mut self, type_info: DB::TypeInfo, value: Option<&'q [u8]>) -> Self
+    where
+        RawValue<'q, DB>: Encode<'q, DB> + Type<DB>,
+    {
+        self.inner = self.inner.bind_raw(type_info, value);
+        self
+    }
 }
 
 impl<'q, DB, O, A> QueryScalar<'q, DB, O, A>
@@ -72,6 +132,42 @@ where
     }
 }
 
+impl<'q, DB, O, A> QueryScalar<'q, DB, O, A>
+where
+    DB: Database,
+{
+    /// Mark this query as read-only, or as a write with `false`.
+    ///
+    /// See [`Query::read_only`](crate::query::Query::read_only).
+    pub fn read_only(mut self, value: bool) -> Self {
+        self.inner = self.inner.read_only(value);
+        self
+    }
+
+    /// Request the rows of the query in the text format of the database.
+    ///
+    /// See [`Query::text_results`](crate::query::Query::text_results).
+    pub fn text_results(mut self, value: bool) -> Self {
+        self.inner = self.inner.text_results(value);
+        self
+    }
+}
+
+impl<'q, DB, O, A> QueryScalar<'q, DB, O, A>
+where
+    DB: Database,
+    A: Clone,
+{
+    /// If `true`, the query is run once more on a new connection if the connection it was
+    /// sent on is lost before it returned any result.
+    ///
+    /// See [`Query::retry_transient`](crate::query::Query::retry_transient).
+    pub fn retry_transient(mut self, value: bool) -> Self {
+        self.inner = self.inner.retry_transient(value);
+        self
+    }
+}
+
 // FIXME: This is very close, nearly 1:1 with `Map`
 // noinspection DuplicatedCode
 impl<'q, DB, O, A> QueryScalar<'q, DB, O, A>
@@ -82,7 +178,9 @@ where
     (O,): Send + Unpin + for<'r> FromRow<'r, DB::Row>,
 {
     /// Execute the query and return the generated results as a stream.
-    #[inline]
+    ///
+    /// The value of each row is decoded from its only column. As every row of a result has the
+    /// same columns, they are checked once on its first row rather than for every row.
     pub fn fetch<'e, 'c: 'e, E>(self, executor: E) -> BoxStream<'e, Result<O, Error>>
     where
         'q: 'e,
@@ -91,12 +189,13 @@ where
         A: 'e,
         O: 'e,
     {
-        self.inner.fetch(executor).map_ok(|it| it.0).boxed()
+        self.fetch_many(executor)
+            .try_filter_map(|step| async move { Ok(step.right()) })
+            .boxed()
     }
 
     /// Execute multiple queries and return the generated results as a stream
     /// from each query, in a stream.
-    #[inline]
     pub fn fetch_many<'e, 'c: 'e, E>(
         self,
         executor: E,
@@ -108,10 +207,35 @@ where
         A: 'e,
         O: 'e,
     {
-        self.inner
-            .fetch_many(executor)
-            .map_ok(|v| v.map_right(|it| it.0))
-            .boxed()
+        Box::pin(try_stream! {
+            let mut s = executor.fetch_many(self.inner.inner);
+
+            // whether the columns of the current result were checked
+            let mut checked = false;
+
+            while let Some(v) = s.try_next().await? {
+                r#yield!(match v {
+                    Either::Left(v) => {
+                        checked = false;
+
+                        Either::Left(v)
+                    }
+
+                    Either::Right(row) => {
+                        if !checked {
+                            check_columns(&row)?;
+                            checked = true;
+                        }
+
+                        let (value,) = <(O,)>::from_row(&row)?;
+
+                        Either::Right(value)
+                    }
+                });
+            }
+
+            Ok(())
+        })
     }
 
     /// Execute the query and return all the generated results, collected into a [`Vec`].
@@ -124,11 +248,13 @@ where
         (O,): 'e,
         A: 'e,
     {
-        self.inner
-            .fetch(executor)
-            .map_ok(|it| it.0)
-            .try_collect()
-            .await
+        Ok(self
+            .inner
+            .fetch_all(executor)
+            .await?
+            .into_iter()
+            .map(|it| it.0)
+            .collect())
     }
 
     /// Execute the query and returns exactly one row.
@@ -160,6 +286,21 @@ where
 
 /// Make a SQL query that is mapped to a single concrete type
 /// using [`FromRow`].
+///
+/// Each row must have a single column, which is decoded as `O`; a row with more columns
+/// returns an error.
+///
+/// ```rust,ignore
+/// let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users")
+///     .fetch_one(&mut conn)
+///     .await?;
+///
+/// // on Postgres, the id of an inserted row is returned by a `RETURNING` clause
+/// let id: i64 = sqlx::query_scalar("INSERT INTO users (name) VALUES ($1) RETURNING id")
+///     .bind("Herp Derpinson")
+///     .fetch_one(&mut conn)
+///     .await?;
+/// ```
 #[inline]
 pub fn query_scalar<'q, DB, O>(
     sql: &'q str,
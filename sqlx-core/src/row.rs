@@ -1,4 +1,4 @@
-use crate::column::ColumnIndex;
+use crate::column::{Column, ColumnIndex};
 use crate::database::{Database, HasValueRef};
 use crate::decode::Decode;
 use crate::error::{mismatched_types, Error};
@@ -8,6 +8,11 @@ use crate::value::ValueRef;
 
 /// Represents a single row from the database.
 ///
+/// A row keeps the values of its columns as they were received and decodes one only when it is
+/// read. A row with a column of a type that no Rust type decodes, e.g. of a type from a database
+/// extension, can be fetched and its other columns read; reading that column returns
+/// [`ColumnDecode`](Error::ColumnDecode) naming its SQL type.
+///
 /// This trait is sealed and cannot be implemented for types outside of SQLx.
 ///
 /// [`FromRow`]: crate::row::FromRow
@@ -96,7 +101,9 @@ pub trait Row: private_row::Sealed + Unpin + Send + Sync + 'static {
     /// Index into the database row and decode a single value.
     ///
     /// A string index can be used to access a column by name and a `usize` index
-    /// can be used to access a column by position.
+    /// can be used to access a column by position. When several columns have the same name,
+    /// as in `SELECT a.id, b.id FROM a JOIN b ..`, the name refers to the first of them; all of
+    /// them are listed by [`columns`](Self::columns) and can be accessed by position.
     ///
     /// # Errors
     ///
@@ -118,7 +125,8 @@ pub trait Row: private_row::Sealed + Unpin + Send + Sync + 'static {
         if !value.is_null() {
             let ty = value.type_info();
 
-            if !ty.is_null() && !T::compatible(&ty) {
+            // every value of a row in text format is decoded from its text, whatever its type
+            if !ty.is_null() && !self.text_results() && !T::compatible(&ty) {
                 return Err(Error::ColumnDecode {
                     index: format!("{:?}", index),
                     source: mismatched_types::<Self::Database, T>(&ty),
@@ -162,6 +170,21 @@ pub trait Row: private_row::Sealed + Unpin + Send + Sync + 'static {
         })
     }
 
+    // decodes the column named `name`, which must be the only column with that name;
+    // used by `#[derive(FromRow)]`
+    #[doc(hidden)]
+    fn try_get_unambiguous<'r, T, I>(&'r self, name: I) -> Result<T, Error>
+    where
+        I: ColumnIndex<Self> + AsRef<str>,
+        T: Decode<'r, Self::Database> + Type<Self::Database>,
+    {
+        if self.is_ambiguous(name.as_ref()) {
+            return Err(Error::AmbiguousColumn(name.as_ref().to_owned()));
+        }
+
+        self.try_get(name)
+    }
+
     /// Index into the database row and decode a single value.
     ///
     /// # Errors
@@ -178,6 +201,29 @@ pub trait Row: private_row::Sealed + Unpin + Send + Sync + 'static {
     ) -> Result<<Self::Database as HasValueRef<'_>>::ValueRef, Error>
     where
         I: ColumnIndex<Self>;
+
+    #[doc(hidden)]
+    // the number of bytes of the values of this row as received from the database, counted by
+    // `Query::fetch_with_progress`; 0 for the drivers that do not keep rows as received
+    fn received_len(&self) -> usize {
+        0
+    }
+
+    #[doc(hidden)]
+    // whether the values of this row are in the text format of the database, as requested with
+    // `Query::text_results`
+    fn text_results(&self) -> bool {
+        false
+    }
+
+    #[doc(hidden)]
+    // whether more than one column of this row is named `name`; the drivers look it up in the
+    // names of the columns they share between the rows of a result set
+    fn is_ambiguous(&self, name: &str) -> bool {
+        let mut columns = self.columns().iter().filter(|column| column.name() == name);
+
+        columns.nth(1).is_some()
+    }
 }
 
 // Prevent users from implementing the `Row` trait.
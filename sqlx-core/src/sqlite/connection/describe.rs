@@ -87,6 +87,8 @@ pub(super) fn describe(conn: &mut ConnectionState, query: &str) -> Result<Descri
     }
 
     Ok(Describe {
+        // SQLite does not allow a SELECT without columns
+        returns_rows: !columns.is_empty(),
         columns,
         parameters: Some(Either::Right(num_params)),
         nullable,
@@ -23,6 +23,8 @@ impl Database for Sqlite {
     type TypeInfo = SqliteTypeInfo;
 
     type Value = SqliteValue;
+
+    const DB_SYSTEM: &'static str = "sqlite";
 }
 
 impl<'r> HasValueRef<'r> for Sqlite {
@@ -1,8 +1,9 @@
 use crate::connection::{ConnectOptions, Connection};
 use crate::error::Error;
 use crate::executor::Executor;
+use crate::migrate::split::{split_statements, Dialect};
 use crate::migrate::MigrateError;
-use crate::migrate::{AppliedMigration, Migration};
+use crate::migrate::{AppliedMigration, Migration, MigrationEvent};
 use crate::migrate::{Migrate, MigrateDatabase};
 use crate::query::query;
 use crate::query_as::query_as;
@@ -158,32 +159,15 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
         &'e mut self,
         migration: &'m Migration,
     ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
-        Box::pin(async move {
-            let mut tx = self.begin().await?;
-            let start = Instant::now();
-
-            let _ = tx.execute(&*migration.sql).await?;
-
-            tx.commit().await?;
-
-            let elapsed = start.elapsed();
-
-            // language=SQL
-            let _ = query(
-                r#"
-    INSERT INTO _sqlx_migrations ( version, description, success, checksum, execution_time )
-    VALUES ( ?1, ?2, TRUE, ?3, ?4 )
-                "#,
-            )
-            .bind(migration.version)
-            .bind(&*migration.description)
-            .bind(&*migration.checksum)
-            .bind(elapsed.as_nanos() as i64)
-            .execute(self)
-            .await?;
+        Box::pin(apply(self, migration, None))
+    }
 
-            Ok(elapsed)
-        })
+    fn apply_with_progress<'e: 'm, 'm>(
+        &'e mut self,
+        migration: &'m Migration,
+        progress: &'m mut (dyn FnMut(MigrationEvent) + Send),
+    ) -> BoxFuture<'m, Result<Duration, MigrateError>> {
+        Box::pin(apply(self, migration, Some(progress)))
     }
 
     fn revert<'e: 'm, 'm>(
@@ -210,3 +194,61 @@ CREATE TABLE IF NOT EXISTS _sqlx_migrations (
         })
     }
 }
+
+async fn apply(
+    conn: &mut SqliteConnection,
+    migration: &Migration,
+    progress: Option<&mut (dyn FnMut(MigrationEvent) + Send)>,
+) -> Result<Duration, MigrateError> {
+    let mut tx = conn.begin().await?;
+    let start = Instant::now();
+
+    match progress {
+        Some(progress) => execute_statements(&mut tx, &migration.sql, progress).await?,
+        None => {
+            let _ = tx.execute(&*migration.sql).await?;
+        }
+    }
+
+    tx.commit().await?;
+
+    let elapsed = start.elapsed();
+
+    // language=SQL
+    let _ = query(
+        r#"
+    INSERT INTO _sqlx_migrations ( version, description, success, checksum, execution_time )
+    VALUES ( ?1, ?2, TRUE, ?3, ?4 )
+        "#,
+    )
+    .bind(migration.version)
+    .bind(&*migration.description)
+    .bind(&*migration.checksum)
+    .bind(elapsed.as_nanos() as i64)
+    .execute(conn)
+    .await?;
+
+    Ok(elapsed)
+}
+
+// executes the statements of a migration one at a time, or all at once if it cannot be split
+async fn execute_statements(
+    conn: &mut SqliteConnection,
+    sql: &str,
+    progress: &mut (dyn FnMut(MigrationEvent) + Send),
+) -> Result<(), Error> {
+    let statements = split_statements(sql, Dialect::Sqlite).unwrap_or_else(|| vec![sql]);
+
+    for (index, statement) in statements.into_iter().enumerate() {
+        let start = Instant::now();
+        let done = conn.execute(statement).await?;
+
+        progress(MigrationEvent::StatementCompleted {
+            index,
+            elapsed: start.elapsed(),
+            rows_affected: done.rows_affected(),
+        });
+    }
+
+    Ok(())
+}
@@ -2,11 +2,8 @@
 
 use std::sync::Arc;
 
-use crate::HashMap;
-
-use crate::column::ColumnIndex;
+use crate::column::{ColumnIndex, ColumnNames};
 use crate::error::Error;
-use crate::ext::ustr::UStr;
 use crate::row::Row;
 use crate::sqlite::statement::StatementHandle;
 use crate::sqlite::{Sqlite, SqliteColumn, SqliteValue, SqliteValueRef};
@@ -15,7 +12,7 @@ use crate::sqlite::{Sqlite, SqliteColumn, SqliteValue, SqliteValueRef};
 pub struct SqliteRow {
     pub(crate) values: Box<[SqliteValue]>,
     pub(crate) columns: Arc<Vec<SqliteColumn>>,
-    pub(crate) column_names: Arc<HashMap<UStr, usize>>,
+    pub(crate) column_names: Arc<ColumnNames>,
 }
 
 impl crate::row::private_row::Sealed for SqliteRow {}
@@ -33,7 +30,7 @@ impl SqliteRow {
     pub(crate) fn current(
         statement: &StatementHandle,
         columns: &Arc<Vec<SqliteColumn>>,
-        column_names: &Arc<HashMap<UStr, usize>>,
+        column_names: &Arc<ColumnNames>,
     ) -> Self {
         let size = statement.column_count();
         let mut values = Vec::with_capacity(size);
@@ -68,6 +65,10 @@ impl Row for SqliteRow {
         let index = index.index(self)?;
         Ok(SqliteValueRef::value(&self.values[index]))
     }
+
+    fn is_ambiguous(&self, name: &str) -> bool {
+        self.column_names.is_ambiguous(name)
+    }
 }
 
 impl ColumnIndex<SqliteRow> for &'_ str {
@@ -1,9 +1,7 @@
-use crate::column::ColumnIndex;
+use crate::column::{ColumnIndex, ColumnNames};
 use crate::error::Error;
-use crate::ext::ustr::UStr;
 use crate::sqlite::{Sqlite, SqliteArguments, SqliteColumn, SqliteTypeInfo};
 use crate::statement::Statement;
-use crate::HashMap;
 use either::Either;
 use std::borrow::Cow;
 use std::sync::Arc;
@@ -20,7 +18,7 @@ pub struct SqliteStatement<'q> {
     pub(crate) sql: Cow<'q, str>,
     pub(crate) parameters: usize,
     pub(crate) columns: Arc<Vec<SqliteColumn>>,
-    pub(crate) column_names: Arc<HashMap<UStr, usize>>,
+    pub(crate) column_names: Arc<ColumnNames>,
 }
 
 impl<'q> Statement<'q> for SqliteStatement<'q> {
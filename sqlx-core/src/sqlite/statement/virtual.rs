@@ -1,11 +1,11 @@
 #![allow(clippy::rc_buffer)]
 
+use crate::column::ColumnNames;
 use crate::error::Error;
 use crate::ext::ustr::UStr;
 use crate::sqlite::connection::ConnectionHandle;
 use crate::sqlite::statement::StatementHandle;
 use crate::sqlite::{SqliteColumn, SqliteError};
-use crate::HashMap;
 use bytes::{Buf, Bytes};
 use libsqlite3_sys::{
     sqlite3, sqlite3_prepare_v3, sqlite3_stmt, SQLITE_OK, SQLITE_PREPARE_PERSISTENT,
@@ -41,13 +41,13 @@ pub struct VirtualStatement {
     pub(crate) columns: SmallVec<[Arc<Vec<SqliteColumn>>; 1]>,
 
     // each set of column names
-    pub(crate) column_names: SmallVec<[Arc<HashMap<UStr, usize>>; 1]>,
+    pub(crate) column_names: SmallVec<[Arc<ColumnNames>; 1]>,
 }
 
 pub struct PreparedStatement<'a> {
     pub(crate) handle: &'a mut StatementHandle,
     pub(crate) columns: &'a Arc<Vec<SqliteColumn>>,
-    pub(crate) column_names: &'a Arc<HashMap<UStr, usize>>,
+    pub(crate) column_names: &'a Arc<ColumnNames>,
 }
 
 impl VirtualStatement {
@@ -90,7 +90,7 @@ impl VirtualStatement {
                 let num = statement.column_count();
 
                 let mut columns = Vec::with_capacity(num);
-                let mut column_names = HashMap::with_capacity(num);
+                let mut column_names = ColumnNames::with_capacity(num);
 
                 for i in 0..num {
                     let name: UStr = statement.column_name(i).to_owned().into();
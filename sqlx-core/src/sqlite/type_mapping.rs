@@ -0,0 +1,20 @@
+use crate as sqlx;
+
+impl_type_mapping! {
+    sqlx::sqlite::Sqlite {
+        bool,
+        i32,
+        i64,
+        f32,
+        f64,
+        String,
+        Vec<u8>,
+
+        #[cfg(feature = "chrono")]
+        sqlx::types::chrono::NaiveDateTime,
+
+        #[cfg(feature = "chrono")]
+        sqlx::types::chrono::DateTime<sqlx::types::chrono::Utc> | sqlx::types::chrono::DateTime<_>,
+    },
+    feature-types: _info => None
+}
@@ -55,6 +55,20 @@ impl<'q> Encode<'q, Sqlite> for Vec<u8> {
     }
 }
 
+impl<'q> Encode<'q, Sqlite> for Cow<'q, [u8]> {
+    fn encode(self, args: &mut Vec<SqliteArgumentValue<'q>>) -> IsNull {
+        args.push(SqliteArgumentValue::Blob(self));
+
+        IsNull::No
+    }
+
+    fn encode_by_ref(&self, args: &mut Vec<SqliteArgumentValue<'q>>) -> IsNull {
+        args.push(SqliteArgumentValue::Blob(self.clone()));
+
+        IsNull::No
+    }
+}
+
 impl<'r> Decode<'r, Sqlite> for Vec<u8> {
     fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
         Ok(value.blob().to_owned())
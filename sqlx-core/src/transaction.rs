@@ -55,7 +55,7 @@ where
     DB: Database,
 {
     connection: MaybePoolConnection<'c, DB>,
-    open: bool,
+    pub(crate) open: bool,
 }
 
 impl<'c, DB> Transaction<'c, DB>
@@ -94,6 +94,46 @@ where
     }
 }
 
+/// Whether a connection is in a transaction, as reported by the server after the last
+/// statement executed on it.
+///
+/// Returned by `transaction_status` on the connections of the databases that report it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionStatus {
+    /// Not in a transaction.
+    Idle,
+
+    /// In a transaction, or a savepoint within one.
+    InTransaction,
+
+    /// In a transaction in which a statement failed, so that every further statement fails
+    /// until it is rolled back. Only PostgreSQL aborts transactions this way.
+    InFailedTransaction,
+}
+
+/// The isolation level of a transaction.
+///
+/// See `set_isolation_level` on a [`Transaction`] of the databases that support it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IsolationLevel {
+    ReadUncommitted,
+    ReadCommitted,
+    RepeatableRead,
+    Serializable,
+}
+
+impl IsolationLevel {
+    #[allow(dead_code)]
+    pub(crate) fn as_sql(self) -> &'static str {
+        match self {
+            IsolationLevel::ReadUncommitted => "READ UNCOMMITTED",
+            IsolationLevel::ReadCommitted => "READ COMMITTED",
+            IsolationLevel::RepeatableRead => "REPEATABLE READ",
+            IsolationLevel::Serializable => "SERIALIZABLE",
+        }
+    }
+}
+
 // NOTE: required due to lack of lazy normalization
 #[allow(unused_macros)]
 macro_rules! impl_executor_for_transaction {
@@ -131,6 +171,17 @@ macro_rules! impl_executor_for_transaction {
                 (&mut **self).fetch_optional(query)
             }
 
+            fn fetch_all<'e, 'q: 'e, E: 'q>(
+                self,
+                query: E,
+            ) -> futures_core::future::BoxFuture<'e, Result<Vec<$Row>, crate::error::Error>>
+            where
+                't: 'e,
+                E: crate::executor::Execute<'q, Self::Database>,
+            {
+                (&mut **self).fetch_all(query)
+            }
+
             fn prepare_with<'e, 'q: 'e>(
                 self,
                 sql: &'q str,
@@ -243,3 +294,29 @@ pub(crate) fn rollback_ansi_transaction_sql(depth: usize) -> Cow<'static, str> {
         ))
     }
 }
+
+// the characteristics of a transaction can only be set right after it began, given its depth and
+// the number of queries sent on its connection before and since it began
+#[allow(dead_code)]
+pub(crate) fn check_characteristics_settable(
+    depth: usize,
+    queries_at_begin: u64,
+    queries: u64,
+) -> Result<(), Error> {
+    if depth != 1 {
+        return Err(Error::Configuration(
+            "the characteristics of a transaction can only be set on the outermost transaction"
+                .into(),
+        ));
+    }
+
+    if queries != queries_at_begin {
+        return Err(Error::Configuration(
+            "the characteristics of a transaction must be set before any other statement is \
+             executed in it"
+                .into(),
+        ));
+    }
+
+    Ok(())
+}
@@ -13,4 +13,18 @@ pub trait TypeInfo: Debug + Display + Clone + PartialEq<Self> + Send + Sync {
     fn is_void(&self) -> bool {
         false
     }
+
+    /// Returns `true` if the database could not infer this type, as for a parameter that is
+    /// only compared to other parameters.
+    #[doc(hidden)]
+    fn is_unknown(&self) -> bool {
+        false
+    }
+
+    /// Returns how the protocol of the database identifies this type, e.g. `oid 1043`, to
+    /// be shown next to its name where the name alone is not enough to look it up.
+    #[doc(hidden)]
+    fn protocol_id(&self) -> Option<String> {
+        None
+    }
 }
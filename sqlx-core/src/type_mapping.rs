@@ -0,0 +1,71 @@
+//! The Rust types that the types of each database map to, as used by the query macros and by
+//! [`codegen`](crate::codegen) to choose the type of a parameter or of a column.
+
+use crate::database::Database;
+
+/// Maps the types of a database to the Rust types that encode and decode them.
+///
+/// Types are named by their path from the `sqlx` crate, e.g.
+/// `sqlx::types::chrono::NaiveDate`, as they are written into generated code.
+pub trait TypeMapping: Database {
+    /// The type to bind to a parameter of type `info`.
+    fn param_type_for_id(info: &Self::TypeInfo) -> Option<&'static str>;
+
+    /// The type to decode a column of type `info` into.
+    fn return_type_for_id(info: &Self::TypeInfo) -> Option<&'static str>;
+
+    /// The feature that enables a type for `info`, if it is not enabled.
+    fn get_feature_gate(info: &Self::TypeInfo) -> Option<&'static str>;
+}
+
+macro_rules! impl_type_mapping {
+    (
+        $database:path {
+            $($(#[$meta:meta])? $ty:ty $(| $input:ty)?),*$(,)?
+        },
+        feature-types: $ty_info:ident => $get_gate:expr $(,)?
+    ) => {
+        impl $crate::type_mapping::TypeMapping for $database {
+            fn param_type_for_id(info: &Self::TypeInfo) -> Option<&'static str> {
+                match () {
+                    $(
+                        $(#[$meta])?
+                        _ if <$ty as $crate::types::Type<$database>>::type_info() == *info => Some(input_ty!($ty $(, $input)?)),
+                    )*
+                    $(
+                        $(#[$meta])?
+                        _ if <$ty as $crate::types::Type<$database>>::compatible(info) => Some(input_ty!($ty $(, $input)?)),
+                    )*
+                    _ => None
+                }
+            }
+
+            fn return_type_for_id(info: &Self::TypeInfo) -> Option<&'static str> {
+                match () {
+                    $(
+                        $(#[$meta])?
+                        _ if <$ty as $crate::types::Type<$database>>::type_info() == *info => return Some(stringify!($ty)),
+                    )*
+                    $(
+                        $(#[$meta])?
+                        _ if <$ty as $crate::types::Type<$database>>::compatible(info) => return Some(stringify!($ty)),
+                    )*
+                    _ => None
+                }
+            }
+
+            fn get_feature_gate($ty_info: &Self::TypeInfo) -> Option<&'static str> {
+                $get_gate
+            }
+        }
+    }
+}
+
+macro_rules! input_ty {
+    ($ty:ty, $input:ty) => {
+        stringify!($input)
+    };
+    ($ty:ty) => {
+        stringify!($ty)
+    };
+}
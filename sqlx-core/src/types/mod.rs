@@ -17,6 +17,16 @@
 //! To represent nullable SQL types, `Option<T>` is supported where `T` implements `Type`.
 //! An `Option<T>` represents a potentially `NULL` value from SQL.
 //!
+//! # Smart Pointers
+//!
+//! `&T`, `Box<T>`, `Arc<T>`, `Rc<T>` and `Cow<'_, T>` have the same SQL type as `T`, so a value
+//! can be bound without cloning it out of its container first. Note that `Rc<T>` is not `Send`
+//! and so cannot be passed to [`Query::bind`](crate::query::Query::bind).
+//!
+
+use std::borrow::Cow;
+use std::rc::Rc;
+use std::sync::Arc;
 
 use crate::database::Database;
 
@@ -197,6 +207,33 @@ impl<T: ?Sized + Type<DB>, DB: Database> Type<DB> for &'_ T {
     }
 }
 
+// for smart pointers, the underlying SQL type is identical
+macro_rules! impl_type_for_smart_pointer {
+    ($($ty:ty),*) => {$(
+        impl<T: ?Sized + Type<DB>, DB: Database> Type<DB> for $ty {
+            fn type_info() -> DB::TypeInfo {
+                <T as Type<DB>>::type_info()
+            }
+
+            fn compatible(ty: &DB::TypeInfo) -> bool {
+                <T as Type<DB>>::compatible(ty)
+            }
+        }
+    )*};
+}
+
+impl_type_for_smart_pointer!(Box<T>, Arc<T>, Rc<T>);
+
+impl<T: ?Sized + ToOwned + Type<DB>, DB: Database> Type<DB> for Cow<'_, T> {
+    fn type_info() -> DB::TypeInfo {
+        <T as Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <T as Type<DB>>::compatible(ty)
+    }
+}
+
 // for optionals, the underlying SQL type is identical
 impl<T: Type<DB>, DB: Database> Type<DB> for Option<T> {
     fn type_info() -> DB::TypeInfo {
@@ -0,0 +1,199 @@
+//! The schemes of database URLs, recognized the same way by `AnyKind` and the query macros.
+
+use crate::error::Error;
+
+/// The database a URL is for, by its scheme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UrlScheme {
+    Postgres,
+    MySql,
+    Sqlite,
+    Mssql,
+}
+
+const ALL: [UrlScheme; 4] = [
+    UrlScheme::Postgres,
+    UrlScheme::MySql,
+    UrlScheme::Sqlite,
+    UrlScheme::Mssql,
+];
+
+impl UrlScheme {
+    /// Recognizes the scheme of `url`, e.g. `postgres://localhost/db`.
+    ///
+    /// Fails if the scheme is unknown, or is for a database whose driver is not enabled.
+    pub fn from_url(url: &str) -> Result<Self, Error> {
+        let scheme = url.split(':').next().unwrap_or_default();
+
+        Self::from_scheme(scheme)
+    }
+
+    /// Recognizes a scheme such as `postgresql` or `MariaDB`, ignoring case.
+    ///
+    /// Fails if the scheme is unknown, or is for a database whose driver is not enabled.
+    pub fn from_scheme(scheme: &str) -> Result<Self, Error> {
+        let recognized = ALL.iter().copied().find(|known| {
+            known
+                .aliases()
+                .iter()
+                .any(|alias| alias.eq_ignore_ascii_case(scheme))
+        });
+
+        match recognized {
+            Some(known) if known.is_enabled() => Ok(known),
+            Some(known) => Err(known.not_enabled()),
+            None => Err(unknown(scheme)),
+        }
+    }
+
+    /// The schemes of the URLs of this database.
+    pub fn aliases(self) -> &'static [&'static str] {
+        match self {
+            UrlScheme::Postgres => &["postgres", "postgresql"],
+            UrlScheme::MySql => &["mysql", "mariadb"],
+            UrlScheme::Sqlite => &["sqlite"],
+            UrlScheme::Mssql => &["mssql", "sqlserver"],
+        }
+    }
+
+    /// The cargo feature that enables the driver of this database.
+    pub fn feature(self) -> &'static str {
+        match self {
+            UrlScheme::Postgres => "postgres",
+            UrlScheme::MySql => "mysql",
+            UrlScheme::Sqlite => "sqlite",
+            UrlScheme::Mssql => "mssql",
+        }
+    }
+
+    pub fn is_enabled(self) -> bool {
+        match self {
+            UrlScheme::Postgres => cfg!(feature = "postgres"),
+            UrlScheme::MySql => cfg!(feature = "mysql"),
+            UrlScheme::Sqlite => cfg!(feature = "sqlite"),
+            UrlScheme::Mssql => cfg!(feature = "mssql"),
+        }
+    }
+
+    /// The error for a URL of this database when its driver is not enabled.
+    pub fn not_enabled(self) -> Error {
+        Error::Configuration(
+            format!(
+                "database URL has the scheme of a {} database but the `{}` feature is not enabled",
+                self.name(),
+                self.feature()
+            )
+            .into(),
+        )
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            UrlScheme::Postgres => "PostgreSQL",
+            UrlScheme::MySql => "MySQL/MariaDB",
+            UrlScheme::Sqlite => "SQLite",
+            UrlScheme::Mssql => "MSSQL",
+        }
+    }
+}
+
+// e.g. `unknown database URL scheme "pg"; expected postgres or postgresql (PostgreSQL), ...`
+fn unknown(scheme: &str) -> Error {
+    let expected = ALL
+        .iter()
+        .map(|known| {
+            let aliases = known.aliases().join(" or ");
+
+            if known.is_enabled() {
+                format!("{} ({})", aliases, known.name())
+            } else {
+                format!(
+                    "{} ({}, requires the `{}` feature)",
+                    aliases,
+                    known.name(),
+                    known.feature()
+                )
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    Error::Configuration(
+        format!(
+            "unknown database URL scheme {:?}; expected {}",
+            scheme, expected
+        )
+        .into(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(result: Result<UrlScheme, Error>) -> String {
+        result.unwrap_err().to_string()
+    }
+
+    #[test]
+    fn it_recognizes_every_alias_ignoring_case() {
+        for (url, scheme) in [
+            ("postgres://localhost/db", UrlScheme::Postgres),
+            ("postgresql://localhost/db", UrlScheme::Postgres),
+            ("PostgreSQL://localhost/db", UrlScheme::Postgres),
+            ("mysql://localhost/db", UrlScheme::MySql),
+            ("mariadb://localhost/db", UrlScheme::MySql),
+            ("MARIADB://localhost/db", UrlScheme::MySql),
+            ("sqlite::memory:", UrlScheme::Sqlite),
+            ("mssql://localhost/db", UrlScheme::Mssql),
+            ("sqlserver://localhost/db", UrlScheme::Mssql),
+        ] {
+            match UrlScheme::from_url(url) {
+                Ok(recognized) => assert_eq!(recognized, scheme, "{}", url),
+                Err(e) => {
+                    assert!(!scheme.is_enabled(), "{}: {}", url, e);
+                    assert_eq!(e.to_string(), scheme.not_enabled().to_string());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn it_lists_the_supported_schemes_for_an_unknown_one() {
+        let message = message(UrlScheme::from_url("pg://localhost/db"));
+
+        assert!(
+            message.contains(
+                "unknown database URL scheme \"pg\"; expected postgres or postgresql (PostgreSQL"
+            ),
+            "{}",
+            message
+        );
+        assert!(
+            message.contains("mysql or mariadb (MySQL/MariaDB"),
+            "{}",
+            message
+        );
+        assert!(message.contains("mssql or sqlserver (MSSQL"), "{}", message);
+
+        for scheme in ALL.iter().filter(|scheme| !scheme.is_enabled()) {
+            let requires = format!("requires the `{}` feature", scheme.feature());
+            assert!(message.contains(&requires), "{}", message);
+        }
+    }
+
+    #[test]
+    fn it_says_which_feature_is_not_enabled() {
+        assert_eq!(
+            UrlScheme::Postgres.not_enabled().to_string(),
+            "error with configuration: database URL has the scheme of a PostgreSQL database but \
+             the `postgres` feature is not enabled"
+        );
+
+        #[cfg(not(feature = "mssql"))]
+        assert_eq!(
+            message(UrlScheme::from_url("sqlserver://localhost/db")),
+            UrlScheme::Mssql.not_enabled().to_string()
+        );
+    }
+}
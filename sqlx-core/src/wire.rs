@@ -0,0 +1,153 @@
+//! Observing the frames sent to and received from the database server.
+//!
+//! This is meant for debugging protocol issues. An observer is installed on the connect options
+//! of a database (e.g. [`PgConnectOptions::packet_observer`]) and is passed every frame, with its
+//! header, as it is written to or read from the connection.
+//!
+//! Frames that carry credentials are redacted down to their header.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! # #[cfg(feature = "postgres")]
+//! # fn f() {
+//! use sqlx_core::postgres::PgConnectOptions;
+//! use sqlx_core::wire::{Direction, HexDump};
+//!
+//! fn trace(direction: Direction, frame: &[u8]) {
+//!     eprintln!("{:?}:\n{}", direction, HexDump(frame));
+//! }
+//!
+//! let options = PgConnectOptions::new().packet_observer(trace, 256);
+//! # }
+//! ```
+//!
+//! [`PgConnectOptions::packet_observer`]: crate::postgres::PgConnectOptions::packet_observer
+//!
+//! The frames of the result sets of a query can also be read as they were received, without
+//! decoding them, to forward them to a client that speaks the same protocol; see [`RawFrame`].
+
+use std::fmt::{self, Debug, Display, Formatter};
+
+use bytes::Bytes;
+
+use crate::database::Database;
+
+/// The direction of a frame passed to a packet observer.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Direction {
+    /// The frame was sent to the server.
+    Sent,
+
+    /// The frame was received from the server.
+    Received,
+}
+
+/// Formats bytes as lines of 16 hexadecimal bytes, followed by their printable ASCII characters.
+pub struct HexDump<'a>(pub &'a [u8]);
+
+impl Display for HexDump<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (i, line) in self.0.chunks(16).enumerate() {
+            if i > 0 {
+                f.write_str("\n")?;
+            }
+
+            write!(f, "{:08x} ", i * 16)?;
+
+            for byte in line {
+                write!(f, " {:02x}", byte)?;
+            }
+
+            for _ in line.len()..16 {
+                f.write_str("   ")?;
+            }
+
+            f.write_str("  |")?;
+
+            for &byte in line {
+                let c = if byte.is_ascii_graphic() || byte == b' ' {
+                    char::from(byte)
+                } else {
+                    '.'
+                };
+
+                write!(f, "{}", c)?;
+            }
+
+            f.write_str("|")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A frame of the response to a query, as returned by `fetch_raw_frames` on a connection (see
+/// [`PgConnection::fetch_raw_frames`] and [`MySqlConnection::fetch_raw_frames`]).
+///
+/// The frames of the column metadata and rows of a result set are passed with their header,
+/// exactly as they were received from the server, after their length was checked. They are in
+/// the format of the protocol of the database and of the version of the server, and values in
+/// rows are in the text or binary format that the query was executed with.
+///
+/// [`PgConnection::fetch_raw_frames`]: crate::postgres::PgConnection::fetch_raw_frames
+/// [`MySqlConnection::fetch_raw_frames`]: crate::mysql::MySqlConnection::fetch_raw_frames
+#[derive(Debug)]
+pub enum RawFrame<DB: Database> {
+    /// A frame describing the columns of the rows that follow, sent once at the start of each
+    /// result set.
+    Metadata(Bytes),
+
+    /// A row of a result set.
+    Row(Bytes),
+
+    /// The end of a result set, or of a statement that returns none.
+    Done(DB::QueryResult),
+}
+
+#[derive(Copy, Clone)]
+pub(crate) struct PacketObserver {
+    observe: fn(Direction, &[u8]),
+
+    // the maximum number of bytes of a frame passed to the observer
+    limit: usize,
+}
+
+impl PacketObserver {
+    pub(crate) fn new(observe: fn(Direction, &[u8]), limit: usize) -> Self {
+        Self { observe, limit }
+    }
+
+    pub(crate) fn observe(&self, direction: Direction, frame: &[u8]) {
+        (self.observe)(direction, &frame[..frame.len().min(self.limit)]);
+    }
+
+    // observe a frame that was read as a separate header and payload
+    pub(crate) fn observe_parts(&self, direction: Direction, header: &[u8], payload: &[u8]) {
+        let mut frame = Vec::with_capacity((header.len() + payload.len()).min(self.limit));
+
+        frame.extend_from_slice(header);
+        frame.extend_from_slice(
+            &payload[..payload.len().min(self.limit.saturating_sub(header.len()))],
+        );
+
+        self.observe(direction, &frame);
+    }
+}
+
+impl Debug for PacketObserver {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PacketObserver")
+            .field("limit", &self.limit)
+            .finish()
+    }
+}
+
+#[test]
+fn test_hex_dump() {
+    assert_eq!(
+        HexDump(b"\x00\x00\x00\x01\x0eSELECT 1;\xff\xfe\x20").to_string(),
+        "00000000  00 00 00 01 0e 53 45 4c 45 43 54 20 31 3b ff fe  |.....SELECT 1;..|\n\
+         00000010  20                                               | |"
+    );
+}
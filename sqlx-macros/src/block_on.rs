@@ -0,0 +1,90 @@
+//! Runs the future of a macro that talks to the database on the thread expanding the macro.
+//!
+//! A macro only ever drives the one connection it opened, so this is all the executor it needs:
+//! the future is polled in place, and the thread sleeps until it is woken. The sockets and
+//! timers of the connection are driven by the reactor of the runtime that `sqlx-rt` was built
+//! with, which for Tokio (and actix) has to be entered while the future runs.
+
+use std::future::Future;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+use std::thread::{self, Thread};
+
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+    sqlx_rt::enter_runtime(|| {
+        let mut future = Box::pin(future);
+
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+
+        loop {
+            match future.as_mut().poll(&mut cx) {
+                Poll::Ready(output) => return output,
+
+                // a wakeup that came before the thread parks makes `park` return at once
+                Poll::Pending => thread::park(),
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::block_on;
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::task::{Context, Poll};
+    use std::thread;
+    use std::time::Duration;
+
+    // ready once another thread has woken it
+    #[derive(Default)]
+    struct WokenLater {
+        woken: Arc<AtomicBool>,
+        waiting: bool,
+    }
+
+    impl Future for WokenLater {
+        type Output = u32;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<u32> {
+            if self.woken.load(Ordering::Acquire) {
+                return Poll::Ready(42);
+            }
+
+            if !self.waiting {
+                self.waiting = true;
+
+                let woken = Arc::clone(&self.woken);
+                let waker = cx.waker().clone();
+
+                thread::spawn(move || {
+                    thread::sleep(Duration::from_millis(10));
+                    woken.store(true, Ordering::Release);
+                    waker.wake();
+                });
+            }
+
+            Poll::Pending
+        }
+    }
+
+    #[test]
+    fn it_blocks_until_the_future_is_woken() {
+        assert_eq!(block_on(WokenLater::default()), 42);
+    }
+}
@@ -70,6 +70,7 @@ pub struct SqlxContainerAttributes {
 pub struct SqlxChildAttributes {
     pub rename: Option<String>,
     pub default: bool,
+    pub ordinal: Option<usize>,
 }
 
 pub fn parse_container_attributes(input: &[Attribute]) -> syn::Result<SqlxContainerAttributes> {
@@ -177,6 +178,7 @@ pub fn parse_container_attributes(input: &[Attribute]) -> syn::Result<SqlxContai
 pub fn parse_child_attributes(input: &[Attribute]) -> syn::Result<SqlxChildAttributes> {
     let mut rename = None;
     let mut default = false;
+    let mut ordinal = None;
 
     for attr in input.iter().filter(|a| a.path.is_ident("sqlx")) {
         let meta = attr
@@ -193,6 +195,13 @@ pub fn parse_child_attributes(input: &[Attribute]) -> syn::Result<SqlxChildAttri
                             ..
                         }) if path.is_ident("rename") => try_set!(rename, val.value(), value),
                         Meta::Path(path) if path.is_ident("default") => default = true,
+                        Meta::NameValue(MetaNameValue {
+                            path,
+                            lit: Lit::Int(val),
+                            ..
+                        }) if path.is_ident("ordinal") => {
+                            try_set!(ordinal, val.base10_parse::<usize>()?, value)
+                        }
                         u => fail!(u, "unexpected attribute"),
                     },
                     u => fail!(u, "unexpected attribute"),
@@ -201,7 +210,18 @@ pub fn parse_child_attributes(input: &[Attribute]) -> syn::Result<SqlxChildAttri
         }
     }
 
-    Ok(SqlxChildAttributes { rename, default })
+    if let (Some(_), Some(_), Some(attr)) = (&rename, ordinal, input.last()) {
+        fail!(
+            attr,
+            "a field cannot be both renamed and mapped to a column by its ordinal"
+        );
+    }
+
+    Ok(SqlxChildAttributes {
+        rename,
+        default,
+        ordinal,
+    })
 }
 
 pub fn check_transparent_attributes(
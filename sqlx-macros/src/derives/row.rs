@@ -63,6 +63,16 @@ fn expand_derive_from_row_struct(
 
     predicates.push(parse_quote!(&#lifetime ::std::primitive::str: ::sqlx::ColumnIndex<R>));
 
+    let by_ordinal = fields.iter().any(|field| {
+        parse_child_attributes(&field.attrs)
+            .map(|attributes| attributes.ordinal.is_some())
+            .unwrap_or(false)
+    });
+
+    if by_ordinal {
+        predicates.push(parse_quote!(::std::primitive::usize: ::sqlx::ColumnIndex<R>));
+    }
+
     for field in fields {
         let ty = &field.ty;
 
@@ -88,18 +98,22 @@ fn expand_derive_from_row_struct(
 
         let ty = &field.ty;
 
+        // a column mapped by its name must be the only one of that name
+        let get = match attributes.ordinal {
+            Some(ordinal) => quote!(row.try_get(#ordinal)),
+            None => quote!(row.try_get_unambiguous(#id_s)),
+        };
+
         if attributes.default {
-            Some(
-                parse_quote!(let #id: #ty = row.try_get(#id_s).or_else(|e| match e {
+            Some(parse_quote!(let #id: #ty = #get.or_else(|e| match e {
                 ::sqlx::Error::ColumnNotFound(_) => {
                     ::std::result::Result::Ok(Default::default())
                 },
                 e => ::std::result::Result::Err(e)
-            })?;),
-            )
+            })?;))
         } else {
             Some(parse_quote!(
-                let #id: #ty = row.try_get(#id_s)?;
+                let #id: #ty = #get?;
             ))
         }
     });
@@ -16,6 +16,7 @@ type Error = Box<dyn std::error::Error>;
 
 type Result<T> = std::result::Result<T, Error>;
 
+mod block_on;
 mod common;
 mod database;
 mod derives;
@@ -41,6 +42,23 @@ pub fn expand_query(input: TokenStream) -> TokenStream {
     }
 }
 
+#[proc_macro]
+pub fn expand_assert_schema(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as query::SchemaMacroInput);
+
+    match query::expand_schema_input(input) {
+        Ok(ts) => ts.into(),
+        Err(e) => {
+            if let Some(parse_err) = e.downcast_ref::<syn::Error>() {
+                parse_err.to_compile_error().into()
+            } else {
+                let msg = e.to_string();
+                quote!(::std::compile_error! { #msg }).into()
+            }
+        }
+    }
+}
+
 #[proc_macro_derive(Encode, attributes(sqlx))]
 pub fn derive_encode(tokenstream: TokenStream) -> TokenStream {
     let input = syn::parse_macro_input!(tokenstream as syn::DeriveInput);
@@ -1,9 +1,11 @@
 use crate::database::DatabaseExt;
+use crate::query::output::DisplayType;
 use crate::query::QueryMacroInput;
 use either::Either;
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote, quote_spanned};
 use sqlx_core::describe::Describe;
+use sqlx_core::type_info::TypeInfo;
 use syn::spanned::Spanned;
 use syn::{Expr, ExprCast, ExprGroup, ExprType, Type};
 
@@ -53,6 +55,16 @@ pub fn quote_args<DB: DatabaseExt>(
                         // cast or type ascription will fail to compile if the type does not match
                         // and we strip casts to wildcard
                         Some(_) => return Ok(quote!()),
+                        None if param_ty.is_unknown() => {
+                            return Err(format!(
+                                "the database could not infer the type of param #{}; add a cast \
+                                 to the parameter in the query or to the argument \
+                                 (`{} as Type`)",
+                                i + 1,
+                                quote!(#expr),
+                            )
+                            .into());
+                        }
                         None => {
                             DB::param_type_for_id(&param_ty)
                                 .ok_or_else(|| {
@@ -64,7 +76,11 @@ pub fn quote_args<DB: DatabaseExt>(
                                             i + 1,
                                         )
                                     } else {
-                                        format!("unsupported type {} for param #{}", param_ty, i + 1)
+                                        format!(
+                                            "unsupported type {} for param #{}",
+                                            DisplayType(param_ty),
+                                            i + 1
+                                        )
                                     }
                                 })?
                                 .parse::<TokenStream>()
@@ -100,17 +116,39 @@ pub fn quote_args<DB: DatabaseExt>(
 
     let args_count = input.arg_exprs.len();
 
+    // encode and bind each argument through a binding with the span of its expression, so that
+    // an argument of a type that cannot be used as a parameter is reported at the argument
+    // rather than at the macro invocation
+    let query_args = format_ident!("query_args");
+
+    let (args_size_hint, args_add): (Vec<_>, Vec<_>) = arg_names
+        .iter()
+        .zip(&input.arg_exprs)
+        .map(|(name, expr)| {
+            (
+                quote_spanned!(expr.span() => {
+                    let expr = #name;
+                    ::sqlx::encode::Encode::<#db_path>::size_hint(expr)
+                }),
+                quote_spanned!(expr.span() => {
+                    let expr = #name;
+                    ::sqlx::Arguments::add(&mut #query_args, expr);
+                }),
+            )
+        })
+        .unzip();
+
     Ok(quote! {
         #arg_bindings
 
         #args_check
 
-        let mut query_args = <#db_path as ::sqlx::database::HasArguments>::Arguments::default();
-        query_args.reserve(
+        let mut #query_args = <#db_path as ::sqlx::database::HasArguments>::Arguments::default();
+        #query_args.reserve(
             #args_count,
-            0 #(+ ::sqlx::encode::Encode::<#db_path>::size_hint(#arg_name))*
+            0 #(+ #args_size_hint)*
         );
-        #(query_args.add(#arg_name);)*
+        #(#args_add)*
     })
 }
 
@@ -1,6 +1,5 @@
 use sqlx_core::database::Database;
 use sqlx_core::describe::Describe;
-use sqlx_core::executor::Executor;
 
 #[cfg_attr(feature = "offline", derive(serde::Deserialize, serde::Serialize))]
 #[cfg_attr(
@@ -20,19 +19,31 @@ pub struct QueryData<DB: Database> {
 }
 
 impl<DB: Database> QueryData<DB> {
-    pub async fn from_db(
-        conn: impl Executor<'_, Database = DB>,
+    pub fn from_describe(
         query: &str,
+        describe: Result<Describe<DB>, sqlx_core::error::Error>,
     ) -> crate::Result<Self> {
         Ok(QueryData {
             query: query.into(),
-            describe: conn.describe(query).await?,
+            describe: describe.map_err(with_cast_hint)?,
             #[cfg(feature = "offline")]
             hash: offline::hash_string(query),
         })
     }
 }
 
+fn with_cast_hint(error: sqlx_core::error::Error) -> crate::Error {
+    match error.as_database_error().and_then(|e| e.code()) {
+        Some(code) if code == "42P18" => format!(
+            "{}\nhint: add a cast to the parameter in the query, e.g. `$1::text`",
+            error
+        )
+        .into(),
+
+        _ => error.into(),
+    }
+}
+
 #[cfg(feature = "offline")]
 pub mod offline {
     use super::QueryData;
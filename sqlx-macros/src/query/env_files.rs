@@ -0,0 +1,229 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Display, Formatter};
+use std::path::{Path, PathBuf};
+
+// the files that are read in a directory, in order of precedence
+const FILE_NAMES: &[&str] = &[".env.local", ".env"];
+
+/// The variables of the `.env` files of the crate being compiled, which are used for the
+/// variables that are not set in the environment.
+#[derive(Default)]
+pub(super) struct EnvFiles {
+    // in order of precedence
+    files: Vec<(PathBuf, HashMap<String, String>)>,
+    // the variables set in the environment from the files
+    exported: HashSet<String>,
+}
+
+/// Where the value of a variable was read from.
+#[derive(Debug, PartialEq)]
+pub(super) enum Source {
+    Env,
+    File(PathBuf),
+}
+
+impl EnvFiles {
+    /// Reads `.env.local` and `.env` from the manifest directory of the crate, if it has them.
+    ///
+    /// No other directory is looked in, so that a `.env` file that happens to be in a directory
+    /// above the crate is never used.
+    pub(super) fn read(manifest_dir: &Path) -> Result<Self, String> {
+        let files = FILE_NAMES
+            .iter()
+            .map(|name| manifest_dir.join(name))
+            .filter(|path| path.is_file())
+            .map(|path| {
+                let vars = dotenv::from_path_iter(&path)
+                    .and_then(|vars| vars.collect::<Result<_, _>>())
+                    .map_err(|e| format!("failed to load environment from {:?}, {}", path, e))?;
+
+                Ok((path, vars))
+            })
+            .collect::<Result<_, String>>()?;
+
+        Ok(Self {
+            files,
+            exported: HashSet::new(),
+        })
+    }
+
+    /// Sets the variables of the files that are not set in the environment, as `dotenv` does,
+    /// so that they apply to everything the macros read from the environment.
+    pub(super) fn export(&mut self) {
+        for (_, vars) in &self.files {
+            for (name, value) in vars {
+                if std::env::var_os(name).is_none() {
+                    std::env::set_var(name, value);
+                    self.exported.insert(name.clone());
+                }
+            }
+        }
+    }
+
+    /// The paths of the files that were read.
+    #[cfg_attr(not(procmacro2_semver_exempt), allow(dead_code))]
+    pub(super) fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.files.iter().map(|(path, _)| &**path)
+    }
+
+    /// Gets the value of a variable from `env`, or else from the first file that sets it.
+    pub(super) fn var(
+        &self,
+        name: &str,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Option<(String, Source)> {
+        // a variable exported from a file is in the environment too, but comes from the file
+        if !self.exported.contains(name) {
+            if let Some(value) = env(name) {
+                return Some((value, Source::Env));
+            }
+        }
+
+        self.files.iter().find_map(|(path, vars)| {
+            let value = vars.get(name)?;
+
+            Some((value.clone(), Source::File(path.clone())))
+        })
+    }
+}
+
+/// Whether `.env` files are read, given the value of `SQLX_DOTENV`.
+///
+/// They are only read when `SQLX_DOTENV` is `1` or `true`, or with the `dotenv` feature unless
+/// `SQLX_DOTENV` is `0` or `false`.
+pub(super) fn enabled(setting: Option<&str>) -> bool {
+    match setting {
+        Some(s) if s == "1" || s.eq_ignore_ascii_case("true") => true,
+        Some(s) if s == "0" || s.eq_ignore_ascii_case("false") => false,
+        _ => cfg!(feature = "dotenv"),
+    }
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Env => f.write_str("the environment"),
+            Source::File(path) => write!(f, "`{}`", path.display()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    // a new empty directory for a test
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "sqlx-macros-env-files-{}-{}",
+            name,
+            std::process::id()
+        ));
+
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        dir
+    }
+
+    fn no_env(_: &str) -> Option<String> {
+        None
+    }
+
+    #[test]
+    fn it_prefers_the_environment_then_env_local_then_env() {
+        let dir = temp_dir("precedence");
+
+        fs::write(
+            dir.join(".env"),
+            "DATABASE_URL=postgres://env\nSQLX_OFFLINE=true\n",
+        )
+        .unwrap();
+        fs::write(dir.join(".env.local"), "DATABASE_URL=postgres://local\n").unwrap();
+
+        let files = EnvFiles::read(&dir).unwrap();
+
+        assert_eq!(
+            files.var("DATABASE_URL", |_| Some("postgres://var".into())),
+            Some(("postgres://var".into(), Source::Env))
+        );
+
+        assert_eq!(
+            files.var("DATABASE_URL", no_env),
+            Some((
+                "postgres://local".into(),
+                Source::File(dir.join(".env.local"))
+            ))
+        );
+
+        assert_eq!(
+            files.var("SQLX_OFFLINE", no_env),
+            Some(("true".into(), Source::File(dir.join(".env"))))
+        );
+
+        assert_eq!(files.var("OTHER", no_env), None);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn it_only_reads_the_manifest_directory() {
+        let dir = temp_dir("manifest");
+        let manifest_dir = dir.join("crate");
+
+        fs::create_dir_all(&manifest_dir).unwrap();
+        fs::write(dir.join(".env"), "DATABASE_URL=postgres://stray\n").unwrap();
+
+        // a `.env` file above the crate is not used
+        let files = EnvFiles::read(&manifest_dir).unwrap();
+
+        assert_eq!(files.var("DATABASE_URL", no_env), None);
+        assert_eq!(files.paths().count(), 0);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn it_exports_the_variables_not_set_in_the_environment() {
+        let dir = temp_dir("export");
+        let set = "SQLX_MACROS_ENV_FILES_TEST_SET";
+        let unset = "SQLX_MACROS_ENV_FILES_TEST_UNSET";
+
+        fs::write(
+            dir.join(".env"),
+            format!("{}=from-file\n{}=from-file\n", set, unset),
+        )
+        .unwrap();
+
+        std::env::set_var(set, "from-env");
+        std::env::remove_var(unset);
+
+        let mut files = EnvFiles::read(&dir).unwrap();
+        files.export();
+
+        let env = |name: &str| std::env::var(name).ok();
+
+        assert_eq!(env(set).as_deref(), Some("from-env"));
+        assert_eq!(files.var(set, env), Some(("from-env".into(), Source::Env)));
+
+        assert_eq!(env(unset).as_deref(), Some("from-file"));
+        assert_eq!(
+            files.var(unset, env),
+            Some(("from-file".into(), Source::File(dir.join(".env"))))
+        );
+
+        std::env::remove_var(set);
+        std::env::remove_var(unset);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn it_reads_env_files_only_when_enabled() {
+        assert!(enabled(Some("1")));
+        assert!(enabled(Some("TRUE")));
+        assert!(!enabled(Some("0")));
+        assert!(!enabled(Some("false")));
+        assert_eq!(enabled(None), cfg!(feature = "dotenv"));
+    }
+}
@@ -10,7 +10,6 @@ use syn::{ExprArray, Type};
 pub struct QueryMacroInput {
     pub(super) sql: String,
 
-    #[cfg_attr(not(feature = "offline"), allow(dead_code))]
     pub(super) src_span: Span,
 
     pub(super) record_type: RecordType,
@@ -20,6 +19,9 @@ pub struct QueryMacroInput {
     pub(super) checked: bool,
 
     pub(super) file_path: Option<String>,
+
+    // the environment variable to read the database URL from, instead of the default ones
+    pub(super) url_env: Option<String>,
 }
 
 enum QuerySrc {
@@ -39,6 +41,7 @@ impl Parse for QueryMacroInput {
         let mut args: Option<Vec<Expr>> = None;
         let mut record_type = RecordType::Generated;
         let mut checked = true;
+        let mut url_env = None;
 
         let mut expect_comma = false;
 
@@ -83,6 +86,8 @@ impl Parse for QueryMacroInput {
             } else if key == "checked" {
                 let lit_bool = input.parse::<LitBool>()?;
                 checked = lit_bool.value;
+            } else if key == "url_env" {
+                url_env = Some(input.parse::<LitStr>()?.value());
             } else {
                 let message = format!("unexpected input key: {}", key);
                 return Err(syn::Error::new_spanned(key, message));
@@ -105,6 +110,7 @@ impl Parse for QueryMacroInput {
             arg_exprs,
             checked,
             file_path,
+            url_env,
         })
     }
 }
@@ -0,0 +1,69 @@
+use proc_macro2::{Span, TokenStream};
+use quote::quote_spanned;
+
+/// Queries selecting more columns than this are reported by the `unused-columns` lint.
+const MAX_COLUMNS: usize = 8;
+
+/// Whether the `unused-columns` lint is enabled, given the value of `SQLX_LINT`.
+///
+/// `SQLX_LINT` is a comma-separated list of lints; none are enabled by default.
+pub(super) fn unused_columns_enabled(setting: Option<&str>) -> bool {
+    setting.map_or(false, |s| {
+        s.split(',')
+            .any(|lint| lint.trim().eq_ignore_ascii_case("unused-columns"))
+    })
+}
+
+/// Warns that the query selects `columns` if the lint is enabled and there are more than
+/// [`MAX_COLUMNS`] of them.
+///
+/// Proc macros cannot emit warnings on stable Rust, so this expands to the use of a deprecated
+/// constant carrying the note, at the span of the query.
+pub(super) fn quote_unused_columns(enabled: bool, columns: &[&str], span: Span) -> TokenStream {
+    if !enabled || columns.len() <= MAX_COLUMNS {
+        return TokenStream::new();
+    }
+
+    let note = format!(
+        "this query selects {} columns ({}); if not all of them are used, select only the ones \
+         that are (`SQLX_LINT=unused-columns`)",
+        columns.len(),
+        columns.join(", ")
+    );
+
+    quote_spanned! {span=>
+        #[allow(non_upper_case_globals)]
+        #[deprecated(note = #note)]
+        const sqlx_query_selects_many_columns: () = ();
+        let () = sqlx_query_selects_many_columns;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COLUMNS: [&str; 9] = ["id", "a", "b", "c", "d", "e", "f", "g", "h"];
+
+    #[test]
+    fn it_reads_the_lint_setting() {
+        assert!(!unused_columns_enabled(None));
+        assert!(!unused_columns_enabled(Some("")));
+        assert!(unused_columns_enabled(Some("unused-columns")));
+        assert!(unused_columns_enabled(Some("other, UNUSED-COLUMNS")));
+    }
+
+    #[test]
+    fn it_notes_wide_queries_when_enabled() {
+        let tokens = quote_unused_columns(true, &COLUMNS, Span::call_site()).to_string();
+
+        assert!(tokens.contains("deprecated"));
+        assert!(tokens.contains("this query selects 9 columns (id, a, b, c, d, e, f, g, h)"));
+    }
+
+    #[test]
+    fn it_does_not_note_queries_when_disabled_or_narrow() {
+        assert!(quote_unused_columns(false, &COLUMNS, Span::call_site()).is_empty());
+        assert!(quote_unused_columns(true, &COLUMNS[..8], Span::call_site()).is_empty());
+    }
+}
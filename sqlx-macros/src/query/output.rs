@@ -4,6 +4,7 @@ use syn::Type;
 
 use sqlx_core::column::Column;
 use sqlx_core::describe::Describe;
+use sqlx_core::type_info::TypeInfo;
 
 use crate::database::DatabaseExt;
 
@@ -46,6 +47,10 @@ struct DisplayColumn<'a> {
     name: &'a str,
 }
 
+// the name of a type followed by how the database identifies it, e.g. `VARCHAR (oid 1043)`,
+// for the types that have no mapping to a Rust type
+pub(super) struct DisplayType<'a, T>(pub(super) &'a T);
+
 struct ColumnDecl {
     ident: Ident,
     r#override: ColumnOverride,
@@ -75,10 +80,32 @@ impl Display for DisplayColumn<'_> {
     }
 }
 
+impl<T: TypeInfo> Display for DisplayType<'_, T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self.0.protocol_id() {
+            Some(id) => write!(f, "{} ({})", self.0, id),
+            None => write!(f, "{}", self.0),
+        }
+    }
+}
+
 pub fn columns_to_rust<DB: DatabaseExt>(describe: &Describe<DB>) -> crate::Result<Vec<RustColumn>> {
-    (0..describe.columns().len())
+    let columns = (0..describe.columns().len())
         .map(|i| column_to_rust(describe, i))
-        .collect::<crate::Result<Vec<_>>>()
+        .collect::<crate::Result<Vec<_>>>()?;
+
+    // each column is assigned to the field of its name, so two columns cannot share one
+    for (i, column) in columns.iter().enumerate() {
+        if columns[..i].iter().any(|prev| prev.ident == column.ident) {
+            return Err(format!(
+                "more than one column is named {:?}; alias them in the query",
+                column.ident.to_string().trim_start_matches("r#")
+            )
+            .into());
+        }
+    }
+
+    Ok(columns)
 }
 
 fn column_to_rust<DB: DatabaseExt>(describe: &Describe<DB>, i: usize) -> crate::Result<RustColumn> {
@@ -238,7 +265,7 @@ fn get_column_type<DB: DatabaseExt>(i: usize, column: &DB::Column) -> TokenStrea
                 } else {
                     format!(
                         "unsupported type {ty} of {col}",
-                        ty = type_info,
+                        ty = DisplayType(type_info),
                         col = DisplayColumn {
                             idx: i,
                             name: &*column.name()
@@ -0,0 +1,388 @@
+use std::borrow::Cow;
+
+use proc_macro2::{Ident, TokenStream};
+use quote::ToTokens;
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{GenericArgument, LitStr, PathArguments, Token, Type};
+
+use sqlx_core::describe::Describe;
+use sqlx_core::url_scheme::UrlScheme;
+use sqlx_core::{column::Column, type_info::TypeInfo};
+
+use crate::database::DatabaseExt;
+use crate::query::output::DisplayType;
+use crate::query::{describe_from_db, DatabaseUrl, DescribeExt, WithDescribe, METADATA};
+
+/// Macro input for `assert_schema!()`
+pub struct SchemaMacroInput {
+    table: LitStr,
+    columns: Vec<SchemaColumn>,
+}
+
+struct SchemaColumn {
+    ident: Ident,
+    type_: Type,
+}
+
+impl Parse for SchemaMacroInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let table = input.parse()?;
+        let _ = input.parse::<Token![,]>()?;
+
+        let content;
+        let _ = syn::braced!(content in input);
+
+        let columns = Punctuated::<SchemaColumn, Token![,]>::parse_terminated(&content)?
+            .into_iter()
+            .collect();
+
+        let _ = input.parse::<Option<Token![,]>>()?;
+
+        Ok(Self { table, columns })
+    }
+}
+
+impl Parse for SchemaColumn {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        // allow keywords such as `type` as column names
+        let ident = Ident::parse_any(input)?;
+        let _ = input.parse::<Token![:]>()?;
+        let type_ = input.parse()?;
+
+        Ok(Self { ident, type_ })
+    }
+}
+
+// a column of the table, as described by the database
+struct TableColumn {
+    name: String,
+    type_name: String,
+    rust_type: Result<&'static str, String>,
+    nullable: Option<bool>,
+}
+
+pub fn expand_schema_input(input: SchemaMacroInput) -> crate::Result<TokenStream> {
+    let database_url = DatabaseUrl::lookup(None);
+
+    match (METADATA.offline, &database_url.url) {
+        (false, Some(db_url)) => {
+            expand_schema_from_db(input, db_url).map_err(|e| database_url.annotate(e))
+        }
+
+        // there is no database to check the schema against
+        (true, _) => Ok(TokenStream::new()),
+
+        (false, None) => Err(format!(
+            "{} must be set to use `assert_schema!()`",
+            database_url.names()
+        )
+        .into()),
+    }
+}
+
+fn expand_schema_from_db(input: SchemaMacroInput, db_url: &str) -> crate::Result<TokenStream> {
+    let table = input.table.value();
+    let columns = describe_from_db(db_url, DescribeTable { table: &table })?;
+
+    // point at the table name, as that is most likely what is wrong (e.g. it does not exist)
+    let columns = columns.map_err(|e| syn::Error::new(input.table.span(), e))?;
+
+    check_columns(&input, &columns)?;
+
+    Ok(TokenStream::new())
+}
+
+// describes the columns of a table, by describing a query of all of them that returns no rows
+struct DescribeTable<'a> {
+    table: &'a str,
+}
+
+impl WithDescribe for DescribeTable<'_> {
+    type Output = Result<Vec<TableColumn>, sqlx_core::error::Error>;
+
+    fn sql(&self, scheme: UrlScheme) -> Cow<'_, str> {
+        match scheme {
+            UrlScheme::Mssql => format!("SELECT TOP 0 * FROM {}", self.table),
+            _ => format!("SELECT * FROM {} LIMIT 0", self.table),
+        }
+        .into()
+    }
+
+    fn with_describe<DB: DatabaseExt>(
+        self,
+        describe: Result<Describe<DB>, sqlx_core::error::Error>,
+    ) -> crate::Result<Self::Output>
+    where
+        Describe<DB>: DescribeExt,
+    {
+        Ok(describe.map(|describe| table_columns(&describe)))
+    }
+}
+
+fn table_columns<DB: DatabaseExt>(describe: &Describe<DB>) -> Vec<TableColumn> {
+    describe
+        .columns()
+        .iter()
+        .enumerate()
+        .map(|(i, column)| {
+            let type_info = column.type_info();
+
+            let rust_type = DB::return_type_for_id(type_info).ok_or_else(|| {
+                if let Some(feature_gate) = DB::get_feature_gate(type_info) {
+                    format!(
+                        "optional feature `{}` required for type {}",
+                        feature_gate,
+                        type_info.name()
+                    )
+                } else {
+                    format!("unsupported type {}", DisplayType(type_info))
+                }
+            });
+
+            TableColumn {
+                name: column.name().to_owned(),
+                type_name: type_info.name().to_owned(),
+                rust_type,
+                nullable: describe.nullable(i),
+            }
+        })
+        .collect()
+}
+
+// compare the declared columns against the columns of the table, collecting every difference
+fn check_columns(input: &SchemaMacroInput, columns: &[TableColumn]) -> syn::Result<()> {
+    let mut errors: Vec<syn::Error> = Vec::new();
+
+    for declared in &input.columns {
+        let name = declared.ident.unraw().to_string();
+
+        let column = match columns.iter().find(|column| column.name == name) {
+            Some(column) => column,
+
+            None => {
+                errors.push(syn::Error::new(
+                    declared.ident.span(),
+                    format!(
+                        "column `{}` does not exist in table `{}`",
+                        name,
+                        input.table.value()
+                    ),
+                ));
+
+                continue;
+            }
+        };
+
+        let rust_type = match &column.rust_type {
+            Ok(rust_type) => rust_type,
+
+            Err(message) => {
+                errors.push(syn::Error::new_spanned(
+                    &declared.type_,
+                    format!("column `{}`: {}", name, message),
+                ));
+
+                continue;
+            }
+        };
+
+        let (is_option, type_) = match option_inner(&declared.type_) {
+            Some(inner) => (true, inner),
+            None => (false, &declared.type_),
+        };
+
+        // the type returned by `return_type_for_id` always parses
+        let expected = normalize(&syn::parse_str(rust_type).unwrap());
+
+        // reading a non-null column into an `Option` is allowed, the reverse is not
+        if expected != normalize(type_) || (column.nullable == Some(true) && !is_option) {
+            let expected = if column.nullable == Some(true) {
+                format!("Option<{}>", expected)
+            } else {
+                expected
+            };
+
+            errors.push(syn::Error::new_spanned(
+                &declared.type_,
+                format!(
+                    "column `{}` has type {} and is expected to be declared as `{}`",
+                    name, column.type_name, expected
+                ),
+            ));
+        }
+    }
+
+    for column in columns {
+        let declared = input
+            .columns
+            .iter()
+            .any(|declared| declared.ident.unraw() == column.name);
+
+        if !declared {
+            errors.push(syn::Error::new(
+                input.table.span(),
+                format!(
+                    "column `{}` of table `{}` is not declared",
+                    column.name,
+                    input.table.value()
+                ),
+            ));
+        }
+    }
+
+    let mut errors = errors.into_iter();
+
+    match errors.next() {
+        Some(mut error) => {
+            error.extend(errors);
+
+            Err(error)
+        }
+
+        None => Ok(()),
+    }
+}
+
+// returns `T` of `Option<T>`
+fn option_inner(type_: &Type) -> Option<&Type> {
+    match type_ {
+        Type::Group(group) => option_inner(&group.elem),
+        Type::Paren(paren) => option_inner(&paren.elem),
+
+        Type::Path(path) if path.qself.is_none() => {
+            let segment = path.path.segments.last()?;
+
+            match &segment.arguments {
+                PathArguments::AngleBracketed(args) if segment.ident == "Option" => {
+                    match args.args.first() {
+                        Some(GenericArgument::Type(inner)) if args.args.len() == 1 => Some(inner),
+                        _ => None,
+                    }
+                }
+
+                _ => None,
+            }
+        }
+
+        _ => None,
+    }
+}
+
+// formats a type with only the last segment of each path, so that the path a type is named by
+// does not matter (e.g. `DateTime<Utc>` and `sqlx::types::chrono::DateTime<sqlx::types::chrono::Utc>`)
+fn normalize(type_: &Type) -> String {
+    match type_ {
+        Type::Group(group) => normalize(&group.elem),
+        Type::Paren(paren) => normalize(&paren.elem),
+
+        Type::Reference(reference) => format!("&{}", normalize(&reference.elem)),
+        Type::Slice(slice) => format!("[{}]", normalize(&slice.elem)),
+
+        Type::Tuple(tuple) => format!(
+            "({})",
+            tuple
+                .elems
+                .iter()
+                .map(normalize)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+
+        Type::Path(path) if path.qself.is_none() && !path.path.segments.is_empty() => {
+            let segment = path.path.segments.last().unwrap();
+            let mut normalized = segment.ident.to_string();
+
+            if let PathArguments::AngleBracketed(args) = &segment.arguments {
+                let args: Vec<_> = args
+                    .args
+                    .iter()
+                    .map(|arg| match arg {
+                        GenericArgument::Type(type_) => normalize(type_),
+                        arg => arg.to_token_stream().to_string(),
+                    })
+                    .collect();
+
+                normalized.push('<');
+                normalized.push_str(&args.join(", "));
+                normalized.push('>');
+            }
+
+            normalized
+        }
+
+        type_ => type_.to_token_stream().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tweet() -> Vec<TableColumn> {
+        vec![
+            TableColumn {
+                name: "id".into(),
+                type_name: "INT8".into(),
+                rust_type: Ok("i64"),
+                nullable: Some(false),
+            },
+            TableColumn {
+                name: "created_at".into(),
+                type_name: "TIMESTAMPTZ".into(),
+                rust_type: Ok("sqlx::types::chrono::DateTime<sqlx::types::chrono::Utc>"),
+                nullable: Some(false),
+            },
+            TableColumn {
+                name: "owner_id".into(),
+                type_name: "INT8".into(),
+                rust_type: Ok("i64"),
+                nullable: Some(true),
+            },
+        ]
+    }
+
+    fn check(input: &str) -> Vec<String> {
+        let input: SchemaMacroInput = syn::parse_str(input).unwrap();
+
+        match check_columns(&input, &tweet()) {
+            Ok(()) => Vec::new(),
+            Err(errors) => errors.into_iter().map(|e| e.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn it_accepts_matching_columns() {
+        assert!(
+            check(r#""tweet", { id: i64, created_at: DateTime<Utc>, owner_id: Option<i64> }"#)
+                .is_empty()
+        );
+
+        assert!(check(
+            r#""tweet", {
+                r#id: Option<i64>,
+                created_at: sqlx::types::chrono::DateTime<chrono::Utc>,
+                owner_id: std::option::Option<i64>,
+            },"#
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn it_names_missing_extra_and_mismatched_columns() {
+        assert_eq!(
+            check(r#""tweet", { id: i32, author_id: Option<i64>, created_at: DateTime<Utc> }"#),
+            vec![
+                "column `id` has type INT8 and is expected to be declared as `i64`",
+                "column `author_id` does not exist in table `tweet`",
+                "column `owner_id` of table `tweet` is not declared",
+            ]
+        );
+
+        assert_eq!(
+            check(r#""tweet", { id: i64, created_at: DateTime<Utc>, owner_id: i64 }"#),
+            vec!["column `owner_id` has type INT8 and is expected to be declared as `Option<i64>`"]
+        );
+    }
+}
@@ -2,6 +2,10 @@ use sqlx::pool::PoolOptions;
 use sqlx::{Connection, Database, Pool};
 use std::env;
 
+mod test_db;
+
+pub use test_db::TestDb;
+
 pub fn setup_if_needed() {
     let _ = dotenv::dotenv();
     let _ = env_logger::builder().is_test(true).try_init();
@@ -0,0 +1,204 @@
+use std::env;
+use std::marker::PhantomData;
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use sqlx::pool::PoolOptions;
+use sqlx::{Connection, Database, Executor, Pool};
+
+use crate::setup_if_needed;
+
+// numbers the scratch databases of this process
+static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Clone, Copy)]
+enum Server {
+    Postgres,
+    MySql,
+}
+
+impl Server {
+    fn of(url: &str) -> anyhow::Result<Self> {
+        match url.split(':').next() {
+            Some("postgres") | Some("postgresql") => Ok(Server::Postgres),
+            Some("mysql") | Some("mariadb") => Ok(Server::MySql),
+            _ => anyhow::bail!("scratch databases are only supported on Postgres and MySQL"),
+        }
+    }
+
+    // the database to connect to in order to create and drop others; MySQL and MariaDB accept
+    // a connection without one
+    fn admin_database(self) -> &'static str {
+        match self {
+            Server::Postgres => "postgres",
+            Server::MySql => "",
+        }
+    }
+
+    fn quote(self, name: &str) -> String {
+        match self {
+            Server::Postgres => format!("\"{}\"", name),
+            Server::MySql => format!("`{}`", name),
+        }
+    }
+}
+
+/// A scratch database, created on the server of a database URL for a single test and dropped
+/// again when this is dropped.
+///
+/// The name of the database is made of the id of the process and a counter, so tests running
+/// in parallel, in one process or in several, never share a database. Nothing but the server
+/// of the URL is assumed to exist: the database is created through the `postgres` database on
+/// Postgres, and without selecting a database on MySQL and MariaDB, where it is a schema.
+///
+/// ```rust,ignore
+/// let db = TestDb::<Postgres>::from_env().await?;
+/// let pool = db.pool().await?;
+/// ```
+pub struct TestDb<DB>
+where
+    DB: Database,
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+{
+    name: String,
+    url: String,
+    admin_url: String,
+    server: Server,
+    database: PhantomData<DB>,
+}
+
+impl<DB> TestDb<DB>
+where
+    DB: Database,
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+{
+    /// Create a scratch database on the server of `base_url`.
+    pub async fn new(base_url: &str) -> anyhow::Result<Self> {
+        let server = Server::of(base_url)?;
+
+        let name = format!(
+            "_sqlx_test_{}_{}",
+            process::id(),
+            COUNTER.fetch_add(1, Ordering::SeqCst)
+        );
+
+        let admin_url = with_database(base_url, server.admin_database());
+        let url = with_database(base_url, &name);
+
+        let mut conn = DB::Connection::connect(&admin_url).await?;
+
+        conn.execute(&*format!("CREATE DATABASE {}", server.quote(&name)))
+            .await?;
+
+        conn.close().await?;
+
+        Ok(Self {
+            name,
+            url,
+            admin_url,
+            server,
+            database: PhantomData,
+        })
+    }
+
+    /// Create a scratch database on the server of `DATABASE_URL`.
+    pub async fn from_env() -> anyhow::Result<Self> {
+        setup_if_needed();
+
+        Self::new(&env::var("DATABASE_URL")?).await
+    }
+
+    /// The URL of the scratch database.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Make a new connection to the scratch database.
+    pub async fn connect(&self) -> anyhow::Result<DB::Connection> {
+        Ok(DB::Connection::connect(&self.url).await?)
+    }
+
+    /// Make a new pool of connections to the scratch database, configured as
+    /// [`pool`](crate::pool).
+    pub async fn pool(&self) -> anyhow::Result<Pool<DB>> {
+        let pool = PoolOptions::<DB>::new()
+            .min_connections(0)
+            .max_connections(5)
+            .test_before_acquire(true)
+            .connect(&self.url)
+            .await?;
+
+        Ok(pool)
+    }
+}
+
+impl<DB> Drop for TestDb<DB>
+where
+    DB: Database,
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+{
+    fn drop(&mut self) {
+        let admin_url = self.admin_url.clone();
+        let name = self.name.clone();
+        let server = self.server;
+
+        // this may run while the test panics or its runtime shuts down, so the database is
+        // dropped from a thread and a runtime of its own
+        let dropped = thread::spawn(move || {
+            sqlx_rt::block_on(drop_database::<DB>(&admin_url, &name, server))
+        })
+        .join();
+
+        match dropped {
+            Ok(Ok(())) => {}
+            Ok(Err(error)) => {
+                eprintln!("failed to drop scratch database {}: {}", self.name, error)
+            }
+            Err(_) => eprintln!("failed to drop scratch database {}", self.name),
+        }
+    }
+}
+
+async fn drop_database<DB>(admin_url: &str, name: &str, server: Server) -> anyhow::Result<()>
+where
+    DB: Database,
+    for<'c> &'c mut DB::Connection: Executor<'c, Database = DB>,
+{
+    let mut conn = DB::Connection::connect(admin_url).await?;
+
+    // Postgres does not drop a database that has connections, such as those of a pool that
+    // has not been closed
+    if let Server::Postgres = server {
+        conn.execute(&*format!(
+            "SELECT pg_terminate_backend(pid) FROM pg_stat_activity \
+             WHERE datname = '{}' AND pid <> pg_backend_pid()",
+            name
+        ))
+        .await?;
+    }
+
+    conn.execute(&*format!("DROP DATABASE IF EXISTS {}", server.quote(name)))
+        .await?;
+
+    conn.close().await?;
+
+    Ok(())
+}
+
+// replaces the database of `url`, the path after its authority, by `database`
+fn with_database(url: &str, database: &str) -> String {
+    let authority = url.find("://").map_or(0, |i| i + 3);
+    let query = url[authority..]
+        .find('?')
+        .map_or(url.len(), |i| authority + i);
+    let path = url[authority..query]
+        .find('/')
+        .map_or(query, |i| authority + i);
+
+    if database.is_empty() {
+        format!("{}{}", &url[..path], &url[query..])
+    } else {
+        format!("{}/{}{}", &url[..path], database, &url[query..])
+    }
+}
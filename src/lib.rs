@@ -15,21 +15,27 @@ pub use sqlx_core::acquire::Acquire;
 pub use sqlx_core::arguments::{Arguments, IntoArguments};
 pub use sqlx_core::column::Column;
 pub use sqlx_core::column::ColumnIndex;
-pub use sqlx_core::connection::{ConnectOptions, Connection};
+pub use sqlx_core::connection::{
+    ConnectOptions, Connection, ConnectionStats, ServerVendor, ServerVersion,
+};
 pub use sqlx_core::database::{self, Database};
 pub use sqlx_core::describe::Describe;
 pub use sqlx_core::executor::{Execute, Executor};
 pub use sqlx_core::from_row::FromRow;
+pub use sqlx_core::owned_row::OwnedRow;
 pub use sqlx_core::pool::{self, Pool};
-pub use sqlx_core::query::{query, query_with};
+pub use sqlx_core::query::{query, query_named, query_with};
 pub use sqlx_core::query_as::{query_as, query_as_with};
 pub use sqlx_core::query_scalar::{query_scalar, query_scalar_with};
 pub use sqlx_core::row::Row;
 pub use sqlx_core::statement::Statement;
-pub use sqlx_core::transaction::{Transaction, TransactionManager};
+pub use sqlx_core::transaction::{
+    IsolationLevel, Transaction, TransactionManager, TransactionStatus,
+};
 pub use sqlx_core::type_info::TypeInfo;
 pub use sqlx_core::types::Type;
 pub use sqlx_core::value::{Value, ValueRef};
+pub use sqlx_core::wire;
 pub use sqlx_core::Either;
 
 #[doc(inline)]
@@ -38,6 +44,18 @@ pub use sqlx_core::error::{self, Error, Result};
 #[cfg(feature = "migrate")]
 pub use sqlx_core::migrate;
 
+#[cfg(feature = "export")]
+#[cfg_attr(docsrs, doc(cfg(feature = "export")))]
+pub use sqlx_core::export;
+
+#[cfg(feature = "introspect")]
+#[cfg_attr(docsrs, doc(cfg(feature = "introspect")))]
+pub use sqlx_core::introspect;
+
+#[cfg(feature = "introspect")]
+#[cfg_attr(docsrs, doc(cfg(feature = "introspect")))]
+pub use sqlx_core::codegen;
+
 #[cfg(all(
     any(
         feature = "mysql",
@@ -129,7 +147,8 @@ pub use self::decode::Decode;
 
 /// Types and traits for the `query` family of functions and macros.
 pub mod query {
-    pub use sqlx_core::query::{Map, Query};
+    pub use sqlx_core::fetch_progress::{FetchProgress, ProgressOptions};
+    pub use sqlx_core::query::{ExecuteOnly, Map, Query};
     pub use sqlx_core::query_as::QueryAs;
     pub use sqlx_core::query_scalar::QueryScalar;
 }
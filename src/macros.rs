@@ -39,7 +39,10 @@
 /// | Multiple   | `.fetch_all(...)`               | `sqlx::Result<Vec<{adhoc struct}>>`  | |
 ///
 /// \* All methods accept one of `&mut {connection type}`, `&mut Transaction` or `&Pool`.
-/// † Only callable if the query returns no columns; otherwise it's assumed the query *may* return at least one row.
+/// † If the statement does not return a result set (e.g. `INSERT`/`UPDATE`/`DELETE` without
+/// `RETURNING`), `.execute()` is the *only* method available, as a
+/// [`query::ExecuteOnly`][crate::query::ExecuteOnly] is returned. With `RETURNING`, the returned
+/// columns are typed as for a `SELECT`.
 /// ## Requirements
 /// * The `DATABASE_URL` environment variable must be set at build-time to point to a database
 /// server with the schema that the query string will be checked against. All variants of `query!()`
@@ -273,6 +276,31 @@
 /// | `foo!: T` | Forced not-null | Overridden |
 /// | `foo?: T` | Forced nullable | Overridden |
 ///
+/// ## Database URL
+/// The URL of the database that queries are checked against is read from the first of these
+/// environment variables (or entries of a `.env` file) that is set:
+///
+/// * `SQLX_DATABASE_URL_<CRATE_NAME>`, where `<CRATE_NAME>` is the name of the crate being
+///   compiled in upper case, e.g. `SQLX_DATABASE_URL_ANALYTICS_SERVICE` for `analytics-service`;
+/// * `DATABASE_URL`.
+///
+/// This lets the crates of one workspace use different databases. A query can also name the
+/// variable that its database URL is read from instead, with `url_env` ahead of the other
+/// arguments of any of the query macros:
+///
+/// ```rust,ignore
+/// let events = sqlx::query!(url_env = "ANALYTICS_DATABASE_URL", "SELECT id FROM events")
+///     .fetch_all(&analytics_pool)
+///     .await?;
+/// ```
+///
+/// With `SQLX_DOTENV=1` set, or the `dotenv` feature enabled, a variable that is not set in the
+/// environment is read from `.env.local`, then `.env`, in the directory of the crate being
+/// compiled; no directory above it is looked in. The variables of these files are also set in
+/// the environment of the macros, as `dotenv` does, and `SQLX_DOTENV=0` turns this off even with
+/// the feature enabled. When checking a query fails, the error names the variable and file the
+/// URL was read from and the database it points to, without credentials.
+///
 /// ## Offline Mode (requires the `offline` feature)
 /// The macros can be configured to not require a live database connection for compilation,
 /// but it requires a couple extra steps:
@@ -293,6 +321,12 @@
 ///
 /// See [the README for `sqlx-cli`](https://crates.io/crates/sqlx-cli) for more information.
 ///
+/// ## Lints
+/// Building with `SQLX_LINT=unused-columns` (read like `DATABASE_URL`) makes the macros warn about
+/// queries that select more than eight columns, listing them, as a reminder to select only the
+/// columns that are used. The warning is reported as the use of a deprecated item at the query.
+/// No lints are enabled by default.
+///
 /// ## See Also
 /// * [query_as!] if you want to use a struct you can name,
 /// * [query_file!] if you want to define the SQL query out-of-line,
@@ -300,6 +334,13 @@
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query (
+    // `url_env = ..` is matched first, as it would also parse as an assignment expression
+    (url_env = $url_env:literal, $query:expr) => ({
+        $crate::sqlx_macros::expand_query!(url_env = $url_env, source = $query)
+    });
+    (url_env = $url_env:literal, $query:expr, $($args:tt)*) => ({
+        $crate::sqlx_macros::expand_query!(url_env = $url_env, source = $query, args = [$($args)*])
+    });
     // in Rust 1.45 we can now invoke proc macros in expression position
     ($query:expr) => ({
         $crate::sqlx_macros::expand_query!(source = $query)
@@ -322,6 +363,12 @@ macro_rules! query (
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query_unchecked (
+    (url_env = $url_env:literal, $query:expr) => ({
+        $crate::sqlx_macros::expand_query!(url_env = $url_env, source = $query, checked = false)
+    });
+    (url_env = $url_env:literal, $query:expr, $($args:tt)*) => ({
+        $crate::sqlx_macros::expand_query!(url_env = $url_env, source = $query, args = [$($args)*], checked = false)
+    });
     ($query:expr) => ({
         $crate::sqlx_macros::expand_query!(source = $query, checked = false)
     });
@@ -375,6 +422,12 @@ macro_rules! query_unchecked (
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query_file (
+    (url_env = $url_env:literal, $path:literal) => ({
+        $crate::sqlx_macros::expand_query!(url_env = $url_env, source_file = $path)
+    });
+    (url_env = $url_env:literal, $path:literal, $($args:tt)*) => ({
+        $crate::sqlx_macros::expand_query!(url_env = $url_env, source_file = $path, args = [$($args)*])
+    });
     ($path:literal) => ({
         $crate::sqlx_macros::expand_query!(source_file = $path)
     });
@@ -388,6 +441,12 @@ macro_rules! query_file (
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query_file_unchecked (
+    (url_env = $url_env:literal, $path:literal) => ({
+        $crate::sqlx_macros::expand_query!(url_env = $url_env, source_file = $path, checked = false)
+    });
+    (url_env = $url_env:literal, $path:literal, $($args:tt)*) => ({
+        $crate::sqlx_macros::expand_query!(url_env = $url_env, source_file = $path, args = [$($args)*], checked = false)
+    });
     ($path:literal) => ({
         $crate::sqlx_macros::expand_query!(source_file = $path, checked = false)
     });
@@ -463,7 +522,9 @@ macro_rules! query_file_unchecked (
 /// | Multiple       | `.fetch_all(...)`           | `sqlx::Result<Vec<T>>`  | |
 ///
 /// \* All methods accept one of `&mut {connection type}`, `&mut Transaction` or `&Pool`.
-/// (`.execute()` is omitted as this macro requires at least one column to be returned.)
+/// (`.execute()` is omitted as this macro requires at least one column to be returned.
+/// If the statement does not return a result set, the struct is ignored and only `.execute()`
+/// is available, as with [query!].)
 ///
 /// ### Column Type Override: Infer from Struct Field
 /// In addition to the column type overrides supported by [query!], `query_as!()` supports an
@@ -548,6 +609,12 @@ macro_rules! query_file_unchecked (
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query_as (
+    (url_env = $url_env:literal, $out_struct:path, $query:expr) => ( {
+        $crate::sqlx_macros::expand_query!(url_env = $url_env, record = $out_struct, source = $query)
+    });
+    (url_env = $url_env:literal, $out_struct:path, $query:expr, $($args:tt)*) => ( {
+        $crate::sqlx_macros::expand_query!(url_env = $url_env, record = $out_struct, source = $query, args = [$($args)*])
+    });
     ($out_struct:path, $query:expr) => ( {
         $crate::sqlx_macros::expand_query!(record = $out_struct, source = $query)
     });
@@ -592,6 +659,12 @@ macro_rules! query_as (
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query_file_as (
+    (url_env = $url_env:literal, $out_struct:path, $path:literal) => ( {
+        $crate::sqlx_macros::expand_query!(url_env = $url_env, record = $out_struct, source_file = $path)
+    });
+    (url_env = $url_env:literal, $out_struct:path, $path:literal, $($args:tt)*) => ( {
+        $crate::sqlx_macros::expand_query!(url_env = $url_env, record = $out_struct, source_file = $path, args = [$($args)*])
+    });
     ($out_struct:path, $path:literal) => ( {
         $crate::sqlx_macros::expand_query!(record = $out_struct, source_file = $path)
     });
@@ -605,6 +678,12 @@ macro_rules! query_file_as (
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query_as_unchecked (
+    (url_env = $url_env:literal, $out_struct:path, $query:expr) => ( {
+        $crate::sqlx_macros::expand_query!(url_env = $url_env, record = $out_struct, source = $query, checked = false)
+    });
+    (url_env = $url_env:literal, $out_struct:path, $query:expr, $($args:tt)*) => ( {
+        $crate::sqlx_macros::expand_query!(url_env = $url_env, record = $out_struct, source = $query, args = [$($args)*], checked = false)
+    });
     ($out_struct:path, $query:expr) => ( {
         $crate::sqlx_macros::expand_query!(record = $out_struct, source = $query, checked = false)
     });
@@ -620,6 +699,12 @@ macro_rules! query_as_unchecked (
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query_file_as_unchecked (
+    (url_env = $url_env:literal, $out_struct:path, $path:literal) => ( {
+        $crate::sqlx_macros::expand_query!(url_env = $url_env, record = $out_struct, source_file = $path, checked = false)
+    });
+    (url_env = $url_env:literal, $out_struct:path, $path:literal, $($args:tt)*) => ( {
+        $crate::sqlx_macros::expand_query!(url_env = $url_env, record = $out_struct, source_file = $path, args = [$($args)*], checked = false)
+    });
     ($out_struct:path, $path:literal) => ( {
         $crate::sqlx_macros::expand_query!(record = $out_struct, source_file = $path, checked = false)
     });
@@ -646,6 +731,12 @@ macro_rules! query_file_as_unchecked (
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query_scalar (
+    (url_env = $url_env:literal, $query:expr) => (
+        $crate::sqlx_macros::expand_query!(url_env = $url_env, scalar = _, source = $query)
+    );
+    (url_env = $url_env:literal, $query:expr, $($args:tt)*) => (
+        $crate::sqlx_macros::expand_query!(url_env = $url_env, scalar = _, source = $query, args = [$($args)*])
+    );
     ($query:expr) => (
         $crate::sqlx_macros::expand_query!(scalar = _, source = $query)
     );
@@ -658,6 +749,12 @@ macro_rules! query_scalar (
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query_file_scalar (
+    (url_env = $url_env:literal, $path:literal) => (
+        $crate::sqlx_macros::expand_query!(url_env = $url_env, scalar = _, source_file = $path)
+    );
+    (url_env = $url_env:literal, $path:literal, $($args:tt)*) => (
+        $crate::sqlx_macros::expand_query!(url_env = $url_env, scalar = _, source_file = $path, args = [$($args)*])
+    );
     ($path:literal) => (
         $crate::sqlx_macros::expand_query!(scalar = _, source_file = $path)
     );
@@ -675,6 +772,12 @@ macro_rules! query_file_scalar (
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query_scalar_unchecked (
+    (url_env = $url_env:literal, $query:expr) => (
+        $crate::sqlx_macros::expand_query!(url_env = $url_env, scalar = _, source = $query, checked = false)
+    );
+    (url_env = $url_env:literal, $query:expr, $($args:tt)*) => (
+        $crate::sqlx_macros::expand_query!(url_env = $url_env, scalar = _, source = $query, args = [$($args)*], checked = false)
+    );
     ($query:expr) => (
         $crate::sqlx_macros::expand_query!(scalar = _, source = $query, checked = false)
     );
@@ -692,6 +795,12 @@ macro_rules! query_scalar_unchecked (
 #[macro_export]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
 macro_rules! query_file_scalar_unchecked (
+    (url_env = $url_env:literal, $path:literal) => (
+        $crate::sqlx_macros::expand_query!(url_env = $url_env, scalar = _, source_file = $path, checked = false)
+    );
+    (url_env = $url_env:literal, $path:literal, $($args:tt)*) => (
+        $crate::sqlx_macros::expand_query!(url_env = $url_env, scalar = _, source_file = $path, args = [$($args)*], checked = false)
+    );
     ($path:literal) => (
         $crate::sqlx_macros::expand_query!(scalar = _, source_file = $path, checked = false)
     );
@@ -700,6 +809,37 @@ macro_rules! query_file_scalar_unchecked (
     )
 );
 
+/// Checks at compile time that the columns of a table have the given names and types.
+///
+/// This describes `SELECT * FROM <table>` against the database at `DATABASE_URL`, the same way
+/// as [query!], and fails the build with an error for each column that is missing, not declared
+/// or declared with a type other than the one [query!] would infer for it. Nullable columns must
+/// be declared as `Option<T>`.
+///
+/// ```rust,ignore
+/// use sqlx::types::chrono::{DateTime, Utc};
+///
+/// sqlx::assert_schema!("tweet", {
+///     id: i64,
+///     created_at: DateTime<Utc>,
+///     text: String,
+///     owner_id: Option<i64>,
+/// });
+/// ```
+///
+/// Types are compared by name, ignoring the path they are named by, so `DateTime<Utc>` and
+/// `chrono::DateTime<chrono::Utc>` are the same type.
+///
+/// This can be used anywhere an item or a statement can. It expands to nothing, and the check is
+/// skipped when building with `SQLX_OFFLINE=true`.
+#[macro_export]
+#[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
+macro_rules! assert_schema (
+    ($table:literal, { $($columns:tt)* }) => (
+        $crate::sqlx_macros::expand_assert_schema!($table, { $($columns)* });
+    )
+);
+
 /// Embeds migrations into the binary by expanding to a static instance of [Migrator][crate::migrate::Migrator].
 ///
 /// ```rust,ignore
@@ -1,4 +1,6 @@
+use std::borrow::Cow;
 use std::marker::PhantomData;
+use std::sync::Arc;
 
 // These types allow the `query!()` and friends to compare a given parameter's type to
 // an expected parameter type even if the former is behind a reference or in `Option`.
@@ -62,24 +64,42 @@ impl<'a> MatchBorrowExt for MatchBorrow<Option<&'a str>, Option<String>> {
     type Matched = Option<&'a str>;
 }
 
-impl<'a> MatchBorrowExt for MatchBorrow<Option<&'a [u8]>, Option<Vec<u8>>> {
-    type Matched = Option<&'a [u8]>;
+impl<'a, T> MatchBorrowExt for MatchBorrow<Option<&'a [T]>, Option<Vec<T>>> {
+    type Matched = Option<&'a [T]>;
 }
 
 impl<'a> MatchBorrowExt for MatchBorrow<Option<&'a str>, Option<&'a String>> {
     type Matched = Option<&'a str>;
 }
 
-impl<'a> MatchBorrowExt for MatchBorrow<Option<&'a [u8]>, Option<&'a Vec<u8>>> {
-    type Matched = Option<&'a [u8]>;
+impl<'a, T> MatchBorrowExt for MatchBorrow<Option<&'a [T]>, Option<&'a Vec<T>>> {
+    type Matched = Option<&'a [T]>;
 }
 
 impl<'a> MatchBorrowExt for MatchBorrow<&'a str, String> {
     type Matched = &'a str;
 }
 
-impl<'a> MatchBorrowExt for MatchBorrow<&'a [u8], Vec<u8>> {
-    type Matched = &'a [u8];
+impl<'a, T> MatchBorrowExt for MatchBorrow<&'a [T], Vec<T>> {
+    type Matched = &'a [T];
+}
+
+// fixed-size arrays are accepted where a slice is expected, e.g. `[u8; 16]` for `BYTEA` or
+// `[i64; 3]` for `INT8[]`
+impl<'a, T, const N: usize> MatchBorrowExt for MatchBorrow<&'a [T], [T; N]> {
+    type Matched = &'a [T];
+}
+
+impl<'a, T, const N: usize> MatchBorrowExt for MatchBorrow<&'a [T], &'_ [T; N]> {
+    type Matched = &'a [T];
+}
+
+impl<'a, T, const N: usize> MatchBorrowExt for MatchBorrow<Option<&'a [T]>, Option<[T; N]>> {
+    type Matched = Option<&'a [T]>;
+}
+
+impl<'a, T, const N: usize> MatchBorrowExt for MatchBorrow<Option<&'a [T]>, Option<&'_ [T; N]>> {
+    type Matched = Option<&'a [T]>;
 }
 
 impl<T> MatchBorrowExt for MatchBorrow<&'_ T, T> {
@@ -114,6 +134,54 @@ impl<T> MatchBorrowExt for MatchBorrow<Option<T>, Option<&'_ &'_ T>> {
     type Matched = Option<T>;
 }
 
+// smart pointers are matched the same way as references; `Rc` is left out as it is not `Send`
+// and so cannot be bound
+macro_rules! impl_match_borrow_for_smart_pointer {
+    ($($ptr:ident),*) => {$(
+        impl<T> MatchBorrowExt for MatchBorrow<T, $ptr<T>> {
+            type Matched = T;
+        }
+
+        impl<T> MatchBorrowExt for MatchBorrow<Option<T>, Option<$ptr<T>>> {
+            type Matched = Option<T>;
+        }
+
+        impl MatchBorrowExt for MatchBorrow<String, $ptr<str>> {
+            type Matched = String;
+        }
+
+        impl MatchBorrowExt for MatchBorrow<Option<String>, Option<$ptr<str>>> {
+            type Matched = Option<String>;
+        }
+
+        impl MatchBorrowExt for MatchBorrow<Vec<u8>, $ptr<[u8]>> {
+            type Matched = Vec<u8>;
+        }
+
+        impl MatchBorrowExt for MatchBorrow<Option<Vec<u8>>, Option<$ptr<[u8]>>> {
+            type Matched = Option<Vec<u8>>;
+        }
+    )*};
+}
+
+impl_match_borrow_for_smart_pointer!(Box, Arc);
+
+impl MatchBorrowExt for MatchBorrow<String, Cow<'_, str>> {
+    type Matched = String;
+}
+
+impl MatchBorrowExt for MatchBorrow<Option<String>, Option<Cow<'_, str>>> {
+    type Matched = Option<String>;
+}
+
+impl MatchBorrowExt for MatchBorrow<Vec<u8>, Cow<'_, [u8]>> {
+    type Matched = Vec<u8>;
+}
+
+impl MatchBorrowExt for MatchBorrow<Option<Vec<u8>>, Option<Cow<'_, [u8]>>> {
+    type Matched = Option<Vec<u8>>;
+}
+
 impl<T, U> MatchBorrowExt for &'_ MatchBorrow<T, U> {
     type Matched = U;
 }
@@ -160,5 +228,32 @@ fn test_match_borrow() {
 
         let (_, match_borrow) = MatchBorrow::new(0i64, &0i64);
         let _: i64 = match_borrow.match_borrow();
+
+        let (_, match_borrow) = MatchBorrow::new(0i64, &Box::new(0i64));
+        let _: i64 = match_borrow.match_borrow();
+
+        let (_, match_borrow) = MatchBorrow::new(String::new(), &Arc::<str>::from(""));
+        let _: String = match_borrow.match_borrow();
+
+        let (_, match_borrow) = MatchBorrow::new(Vec::<u8>::new(), &Arc::<[u8]>::from(&[][..]));
+        let _: Vec<u8> = match_borrow.match_borrow();
+
+        let (_, match_borrow) = MatchBorrow::new(String::new(), &Cow::Borrowed(""));
+        let _: String = match_borrow.match_borrow();
+
+        let (_, match_borrow) = MatchBorrow::new(Some(String::new()), &Some(Box::<str>::from("")));
+        let _: Option<String> = match_borrow.match_borrow();
+
+        let (_, match_borrow) = MatchBorrow::new(&[][..], &vec![0i64]);
+        let _: &[i64] = match_borrow.match_borrow();
+
+        let (_, match_borrow) = MatchBorrow::new(&[][..], &[0u8; 16]);
+        let _: &[u8] = match_borrow.match_borrow();
+
+        let (_, match_borrow) = MatchBorrow::new(&[][..], &&[0i64; 3]);
+        let _: &[i64] = match_borrow.match_borrow();
+
+        let (_, match_borrow) = MatchBorrow::new(Some(&[][..]), &Some([0i64; 3]));
+        let _: Option<&[i64]> = match_borrow.match_borrow();
     }
 }
@@ -3,7 +3,7 @@ use std::sync::{
     atomic::{AtomicUsize, Ordering},
     Arc,
 };
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 #[sqlx_macros::test]
 async fn pool_should_invoke_after_connect() -> anyhow::Result<()> {
@@ -64,3 +64,148 @@ async fn pool_should_be_returned_failed_transactions() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn pool_try_acquire_returns_none_when_exhausted() -> anyhow::Result<()> {
+    let pool = AnyPoolOptions::new()
+        .max_connections(1)
+        .connect(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let conn = pool.acquire().await?;
+
+    assert!(pool.try_acquire().is_none());
+
+    drop(conn);
+
+    // connections are released asynchronously
+    let mut conn = None;
+
+    for _ in 0..100 {
+        conn = pool.try_acquire();
+
+        if conn.is_some() {
+            break;
+        }
+
+        sqlx_rt::sleep(Duration::from_millis(10)).await;
+    }
+
+    assert!(conn.is_some());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_acquire_timeout_overrides_connect_timeout() -> anyhow::Result<()> {
+    let pool = AnyPoolOptions::new()
+        .max_connections(1)
+        .connect_timeout(Duration::from_secs(30))
+        .connect(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let _conn = pool.acquire().await?;
+
+    let start = Instant::now();
+    let res = pool.acquire_timeout(Duration::from_millis(100)).await;
+
+    assert!(matches!(res, Err(sqlx::Error::PoolTimedOut)));
+    assert!(start.elapsed() < Duration::from_secs(5));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_discards_connections_dropped_on_panic() -> anyhow::Result<()> {
+    let connects = Arc::new(AtomicUsize::new(0));
+
+    let pool = AnyPoolOptions::new()
+        .max_connections(1)
+        .after_connect({
+            let connects = connects.clone();
+            move |_conn| {
+                let connects = connects.clone();
+                Box::pin(async move {
+                    connects.fetch_add(1, Ordering::SeqCst);
+
+                    Ok(())
+                })
+            }
+        })
+        .connect(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let conn = pool.acquire().await?;
+    assert_eq!(connects.load(Ordering::SeqCst), 1);
+
+    let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(move || {
+        let _conn = conn;
+
+        panic!("panicked while holding a connection");
+    }));
+
+    assert!(res.is_err());
+    assert_eq!(pool.connections_dropped_on_panic(), 1);
+
+    // the connection was closed instead of being returned to the pool
+    assert_eq!(pool.size(), 0);
+
+    let mut conn = pool.acquire().await?;
+    sqlx::query("SELECT 1").execute(&mut conn).await?;
+
+    assert_eq!(connects.load(Ordering::SeqCst), 2);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_acquire_is_cancellation_safe() -> anyhow::Result<()> {
+    const MAX_CONNECTIONS: u32 = 5;
+
+    let pool = AnyPoolOptions::new()
+        .max_connections(MAX_CONNECTIONS)
+        .connect(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    // time out while waiting for a permit, opening or testing a connection, or using it
+    let acquires = (0..2000_u64).map(|i| {
+        let pool = pool.clone();
+
+        async move {
+            let timeout = Duration::from_micros((i % 50) * 40);
+
+            let _ = sqlx_rt::timeout(timeout, async {
+                let mut conn = pool.acquire().await?;
+
+                sqlx::query("SELECT 1").execute(&mut conn).await
+            })
+            .await;
+        }
+    });
+
+    futures::future::join_all(acquires).await;
+
+    // connections are released asynchronously
+    for _ in 0..100 {
+        if pool.num_idle() == pool.size() as usize {
+            break;
+        }
+
+        sqlx_rt::sleep(Duration::from_millis(10)).await;
+    }
+
+    assert!(pool.size() <= MAX_CONNECTIONS);
+    assert_eq!(pool.num_idle(), pool.size() as usize);
+
+    // no permit was lost: the pool can still hand out all of its connections at once
+    let mut conns = Vec::new();
+
+    for _ in 0..MAX_CONNECTIONS {
+        conns.push(pool.acquire_timeout(Duration::from_secs(10)).await?);
+    }
+
+    assert_eq!(pool.size(), MAX_CONNECTIONS);
+    assert_eq!(pool.num_idle(), 0);
+
+    Ok(())
+}
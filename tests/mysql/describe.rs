@@ -69,3 +69,20 @@ async fn uses_alias_name() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn it_describes_statements_without_result_set() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let d = conn.describe("UPDATE tweet SET text = text").await?;
+
+    assert!(!d.returns_rows());
+    assert!(d.columns().is_empty());
+
+    let d = conn.describe("SELECT id FROM tweet").await?;
+
+    assert!(d.returns_rows());
+    assert_eq!(d.columns()[0].name(), "id");
+
+    Ok(())
+}
@@ -33,6 +33,28 @@ async fn macro_select_from_cte_bind() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn test_no_result() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+    let mut tx = conn.begin().await?;
+
+    let result = sqlx::query!("INSERT INTO tweet (text) VALUES (?)", "Hello, World")
+        .execute(&mut tx)
+        .await?;
+
+    assert_eq!(result.rows_affected(), 1);
+
+    let result = sqlx::query!("DELETE FROM tweet WHERE id = ?", result.last_insert_id())
+        .execute(&mut tx)
+        .await?;
+
+    assert_eq!(result.rows_affected(), 1);
+
+    // let the transaction rollback so we don't actually change the tweets
+
+    Ok(())
+}
+
 #[derive(Debug)]
 struct RawAccount {
     r#type: i32,
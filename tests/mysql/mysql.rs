@@ -1,8 +1,15 @@
 use futures::TryStreamExt;
-use sqlx::mysql::{MySql, MySqlConnection, MySqlPool, MySqlPoolOptions, MySqlRow};
-use sqlx::{Column, Connection, Executor, Row, Statement, TypeInfo};
-use sqlx_test::{new, setup_if_needed};
+use sqlx::mysql::{
+    MySql, MySqlCapabilities, MySqlConnectOptions, MySqlConnection, MySqlDatabaseError, MySqlPool,
+    MySqlPoolOptions, MySqlRow, MySqlTypeInfo,
+};
+use sqlx::{
+    Column, Connection, Executor, IsolationLevel, Row, ServerVendor, Statement, TransactionStatus,
+    TypeInfo,
+};
+use sqlx_test::{new, setup_if_needed, TestDb};
 use std::env;
+use std::time::Duration;
 
 #[sqlx_macros::test]
 async fn it_connects() -> anyhow::Result<()> {
@@ -14,6 +21,99 @@ async fn it_connects() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_closes_with_pending_work() -> anyhow::Result<()> {
+    // a result that was not read to the end is read for a bounded time before closing
+    let mut conn = new::<MySql>().await?;
+
+    {
+        let mut s = conn.fetch(
+            "SELECT a.column_name FROM information_schema.columns a, information_schema.columns b",
+        );
+        assert!(s.try_next().await?.is_some());
+    }
+
+    let start = std::time::Instant::now();
+    sqlx_rt::timeout(Duration::from_secs(10), conn.close()).await??;
+    assert!(start.elapsed() < Duration::from_secs(5));
+
+    // the `ROLLBACK` of a dropped transaction is not sent; the server rolls back on its own
+    let mut conn = new::<MySql>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE dropped_transaction_rows (id INTEGER PRIMARY KEY)")
+        .await?;
+
+    let mut tx = conn.begin().await?;
+    sqlx::query("INSERT INTO dropped_transaction_rows (id) VALUES (1)")
+        .execute(&mut tx)
+        .await?;
+    drop(tx);
+
+    sqlx_rt::timeout(Duration::from_secs(10), conn.close()).await??;
+
+    // an idle connection
+    let conn = new::<MySql>().await?;
+
+    sqlx_rt::timeout(Duration::from_secs(10), conn.close()).await??;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_connects_with_a_password_set_on_the_options() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let database: String = sqlx::query_scalar("SELECT DATABASE()")
+        .fetch_one(&mut conn)
+        .await?;
+
+    conn.execute("DROP USER IF EXISTS 'sqlx_special_password'@'%'")
+        .await?;
+    conn.execute("CREATE USER 'sqlx_special_password'@'%' IDENTIFIED BY 'p@ss:w/rd%'")
+        .await?;
+    conn.execute(&*format!(
+        "GRANT SELECT ON `{}`.* TO 'sqlx_special_password'@'%'",
+        database
+    ))
+    .await?;
+
+    let options = env::var("DATABASE_URL")?
+        .parse::<MySqlConnectOptions>()?
+        .username("sqlx_special_password")
+        .password("p@ss:w/rd%");
+
+    let debug = format!("{:?}", options);
+
+    assert!(debug.contains("REDACTED"), "{}", debug);
+    assert!(!debug.contains("p@ss"), "{}", debug);
+
+    let mut user_conn = MySqlConnection::connect_with(&options).await?;
+
+    let user: String = sqlx::query_scalar("SELECT CURRENT_USER()")
+        .fetch_one(&mut user_conn)
+        .await?;
+    assert_eq!(user, "sqlx_special_password@%");
+
+    user_conn.close().await?;
+
+    let pool = MySqlPoolOptions::new()
+        .max_connections(1)
+        .connect_with(options)
+        .await?;
+
+    let user: String = sqlx::query_scalar("SELECT CURRENT_USER()")
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(user, "sqlx_special_password@%");
+
+    pool.close().await;
+
+    conn.execute("DROP USER 'sqlx_special_password'@'%'")
+        .await?;
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_maths() -> anyhow::Result<()> {
     let mut conn = new::<MySql>().await?;
@@ -43,6 +143,113 @@ async fn it_can_fail_at_querying() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_fails_to_decode_more_than_one_character_as_char() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let res: Result<char, sqlx::Error> =
+        sqlx::query_scalar("SELECT 'ab'").fetch_one(&mut conn).await;
+
+    assert!(matches!(res, Err(sqlx::Error::ColumnDecode { .. })));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_connects_with_a_time_zone_and_sql_mode() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let options: MySqlConnectOptions = env::var("DATABASE_URL")?.parse()?;
+
+    let mut conn = MySqlConnection::connect_with(
+        &options
+            .clone()
+            .time_zone("+02:00")
+            .sql_mode("STRICT_TRANS_TABLES"),
+    )
+    .await?;
+
+    let (time_zone, sql_mode): (String, String) =
+        sqlx::query_as("SELECT @@session.time_zone, @@session.sql_mode")
+            .fetch_one(&mut conn)
+            .await?;
+
+    assert_eq!("+02:00", time_zone);
+
+    let mut sql_mode: Vec<&str> = sql_mode.split(',').collect();
+    sql_mode.sort_unstable();
+
+    assert_eq!(
+        vec![
+            "NO_ENGINE_SUBSTITUTION",
+            "PIPES_AS_CONCAT",
+            "STRICT_TRANS_TABLES"
+        ],
+        sql_mode
+    );
+
+    // an unknown time zone fails the connection
+    let res = MySqlConnection::connect_with(&options.time_zone("Nowhere/Special")).await;
+
+    assert!(res.is_err());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_observes_packets() -> anyhow::Result<()> {
+    use sqlx::wire::Direction;
+    use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+    // the last packet sent and the header and first byte of the last packet received
+    static SENT: AtomicU64 = AtomicU64::new(0);
+    static RECEIVED: AtomicU64 = AtomicU64::new(0);
+    static AUTHENTICATING: AtomicBool = AtomicBool::new(true);
+    static LEAKED: AtomicBool = AtomicBool::new(false);
+
+    fn pack(bytes: &[u8]) -> u64 {
+        bytes
+            .iter()
+            .fold(0, |packed, &byte| packed << 8 | u64::from(byte))
+    }
+
+    fn observe(direction: Direction, packet: &[u8]) {
+        match direction {
+            Direction::Sent => {
+                if AUTHENTICATING.load(Ordering::SeqCst) && packet.len() != 4 {
+                    LEAKED.store(true, Ordering::SeqCst);
+                }
+
+                SENT.store(pack(&packet[..packet.len().min(8)]), Ordering::SeqCst);
+            }
+
+            Direction::Received => {
+                RECEIVED.store(pack(&packet[..packet.len().min(5)]), Ordering::SeqCst);
+            }
+        }
+    }
+
+    setup_if_needed();
+
+    let options: MySqlConnectOptions = env::var("DATABASE_URL")?.parse()?;
+
+    let mut conn = MySqlConnection::connect_with(&options.packet_observer(observe, 64)).await?;
+
+    // packets sent during authentication are observed without their payload
+    AUTHENTICATING.store(false, Ordering::SeqCst);
+    assert!(!LEAKED.load(Ordering::SeqCst));
+
+    conn.ping().await?;
+
+    // COM_PING
+    assert_eq!(SENT.load(Ordering::SeqCst), pack(&[1, 0, 0, 0, 0x0e]));
+
+    // OK_Packet, with a sequence id of 1
+    assert_eq!(RECEIVED.load(Ordering::SeqCst) & 0xffff, pack(&[1, 0x00]));
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_executes() -> anyhow::Result<()> {
     let mut conn = new::<MySql>().await?;
@@ -75,6 +282,41 @@ CREATE TEMPORARY TABLE users (id INTEGER PRIMARY KEY);
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_keeps_the_query_result_of_fetched_statements() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let _ = conn
+        .execute(
+            r#"
+CREATE TEMPORARY TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL);
+INSERT INTO users (id, name) VALUES (1, 'a'), (2, 'b'), (3, 'c');
+            "#,
+        )
+        .await?;
+
+    // the rows and the number of rows affected come from separate results of one statement
+    let rows = conn
+        .fetch_all(
+            "UPDATE users SET name = 'd' WHERE id < 3; SELECT id FROM users WHERE name = 'd'",
+        )
+        .await?;
+
+    assert_eq!(rows.len(), 2);
+    assert_eq!(conn.last_query_result().rows_affected(), 2);
+
+    let row = sqlx::query("UPDATE users SET name = ? WHERE id = ?")
+        .bind("e")
+        .bind(3_i32)
+        .fetch_optional(&mut conn)
+        .await?;
+
+    assert!(row.is_none());
+    assert_eq!(conn.last_query_result().rows_affected(), 1);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_executes_with_pool() -> anyhow::Result<()> {
     let pool: MySqlPool = MySqlPoolOptions::new()
@@ -98,6 +340,39 @@ async fn it_executes_with_pool() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn pool_shrinks_read_buffers_on_release() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let options: MySqlConnectOptions = env::var("DATABASE_URL")?.parse()?;
+
+    let pool = MySqlPoolOptions::new()
+        .max_connections(1)
+        .connect_with(options.read_buffer_shrink_threshold(64 * 1024))
+        .await?;
+
+    let mut conn = pool.acquire().await?;
+
+    let rows = sqlx::query("SELECT REPEAT('x', 1024 * 1024)")
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(rows.len(), 1);
+    drop(rows);
+
+    // the buffer is kept while the connection keeps receiving large messages
+    assert!(conn.read_buffer_capacity() >= 1024 * 1024);
+
+    drop(conn);
+
+    // with a single connection, this waits for it to be returned to the pool
+    let conn = pool.acquire().await?;
+
+    assert!(conn.read_buffer_capacity() <= 64 * 1024);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_works_with_cache_disabled() -> anyhow::Result<()> {
     setup_if_needed();
@@ -446,3 +721,1605 @@ async fn it_can_work_with_transactions() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn it_reports_the_server_version() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let version = conn.server_version();
+    let reported: String = sqlx::query_scalar("SELECT VERSION()")
+        .fetch_one(&mut conn)
+        .await?;
+
+    // `VERSION()` has no `5.5.5-` prefix on MariaDB
+    let expected_vendor = if reported.contains("MariaDB") {
+        ServerVendor::MariaDb
+    } else {
+        ServerVendor::MySql
+    };
+
+    assert_eq!(version.vendor(), expected_vendor);
+    assert!(
+        reported.starts_with(&format!("{}.{}.", version.major(), version.minor())),
+        "{} does not start with {}",
+        reported,
+        version
+    );
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_reports_the_transaction_status() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    assert_eq!(conn.transaction_status(), TransactionStatus::Idle);
+
+    let mut tx = conn.begin().await?;
+    assert_eq!(tx.transaction_status(), TransactionStatus::InTransaction);
+
+    // a failed statement does not abort the transaction
+    assert!(tx.execute("SELECT * FROM _sqlx_missing").await.is_err());
+    assert_eq!(tx.transaction_status(), TransactionStatus::InTransaction);
+
+    let savepoint = tx.begin().await?;
+    savepoint.rollback().await?;
+    assert_eq!(tx.transaction_status(), TransactionStatus::InTransaction);
+
+    tx.commit().await?;
+    assert_eq!(conn.transaction_status(), TransactionStatus::Idle);
+
+    let tx = conn.begin().await?;
+    tx.rollback().await?;
+    assert_eq!(conn.transaction_status(), TransactionStatus::Idle);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_set_the_characteristics_of_a_transaction() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let mut tx = conn.begin().await?;
+    tx.set_isolation_level(IsolationLevel::Serializable).await?;
+    tx.set_read_only(true).await?;
+    assert_eq!(tx.transaction_status(), TransactionStatus::InTransaction);
+
+    // the transaction is only listed once it accessed a table
+    let _: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM tweet")
+        .fetch_one(&mut tx)
+        .await?;
+
+    let isolation: String = sqlx::query_scalar(
+        "SELECT trx_isolation_level FROM information_schema.innodb_trx \
+         WHERE trx_mysql_thread_id = CONNECTION_ID()",
+    )
+    .fetch_one(&mut tx)
+    .await?;
+
+    assert_eq!(isolation, "SERIALIZABLE");
+
+    let err = sqlx::query("INSERT INTO tweet (text) VALUES ('read-only')")
+        .execute(&mut tx)
+        .await
+        .unwrap_err()
+        .into_database_error()
+        .unwrap();
+
+    // ER_CANT_EXECUTE_IN_READ_ONLY_TRANSACTION
+    assert_eq!(err.downcast_ref::<MySqlDatabaseError>().number(), 1792);
+
+    // too late, now that statements were executed in the transaction
+    let error = tx.set_read_only(false).await.unwrap_err();
+    assert!(matches!(error, sqlx::Error::Configuration(_)), "{}", error);
+
+    tx.rollback().await?;
+
+    // neither can the characteristics of a savepoint be set
+    let mut tx = conn.begin().await?;
+    let mut savepoint = tx.begin().await?;
+
+    let error = savepoint.set_read_only(true).await.unwrap_err();
+    assert!(matches!(error, sqlx::Error::Configuration(_)), "{}", error);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_fetches_in_batches_with_a_cursor() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    // 10,000 rows, numbered 0 to 9999
+    let digits = "(SELECT 0 AS n UNION ALL SELECT 1 UNION ALL SELECT 2 UNION ALL SELECT 3 \
+                  UNION ALL SELECT 4 UNION ALL SELECT 5 UNION ALL SELECT 6 UNION ALL SELECT 7 \
+                  UNION ALL SELECT 8 UNION ALL SELECT 9)";
+
+    let sql = format!(
+        "SELECT a.n + b.n * 10 + c.n * 100 + d.n * 1000 AS n \
+         FROM {0} a, {0} b, {0} c, {0} d \
+         ORDER BY n",
+        digits
+    );
+
+    let mut cursor = sqlx::query(&sql).cursor(&mut conn).await?;
+    let mut expected = 0_i64;
+
+    loop {
+        let batch = cursor.next_batch(500).await?;
+
+        if batch.is_empty() {
+            break;
+        }
+
+        assert!(batch.len() <= 500);
+
+        for row in batch {
+            assert_eq!(row.try_get::<i64, _>("n")?, expected);
+            expected += 1;
+        }
+    }
+
+    assert!(cursor.is_done());
+    assert_eq!(expected, 10_000);
+
+    drop(cursor);
+
+    // drop the cursor partway through
+    let mut cursor = sqlx::query(&sql).cursor(&mut conn).await?;
+
+    let batch = cursor.next_batch(500).await?;
+    assert_eq!(batch.len(), 500);
+    assert!(!cursor.is_done());
+
+    drop(cursor);
+
+    // the connection is usable again
+    let value: i32 = sqlx::query_scalar("SELECT 1 + CAST(? AS SIGNED)")
+        .bind(41_i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(value, 42);
+
+    // and so is the statement
+    let mut cursor = sqlx::query(&sql).cursor(&mut conn).await?;
+
+    let batch = cursor.next_batch(10).await?;
+    assert_eq!(batch[0].try_get::<i64, _>(0)?, 0);
+
+    Ok(())
+}
+
+#[cfg(feature = "introspect")]
+#[sqlx_macros::test]
+async fn it_introspects_tables_and_columns() -> anyhow::Result<()> {
+    use sqlx::introspect;
+
+    let db = TestDb::<MySql>::from_env().await?;
+    let mut conn = db.connect().await?;
+
+    conn.execute(
+        r#"
+CREATE TABLE _sqlx_introspect (
+    id         BIGINT PRIMARY KEY AUTO_INCREMENT,
+    name       VARCHAR(255) NOT NULL DEFAULT 'nobody',
+    score      DOUBLE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+        "#,
+    )
+    .await?;
+
+    let tables = introspect::tables(&mut conn, None).await?;
+
+    assert!(tables.iter().any(|table| table.name == "_sqlx_introspect"));
+
+    let columns = introspect::columns(&mut conn, "`_sqlx_introspect`").await?;
+
+    let names: Vec<_> = columns.iter().map(|c| &*c.name).collect();
+    assert_eq!(names, ["id", "name", "score", "created_at"]);
+
+    assert_eq!(columns[0].type_info.name(), "BIGINT");
+    assert_eq!(columns[1].type_info.name(), "VARCHAR");
+    assert_eq!(columns[2].type_info.name(), "DOUBLE");
+    assert_eq!(columns[3].type_info.name(), "TIMESTAMP");
+
+    assert!(!columns[0].nullable);
+    assert!(!columns[1].nullable);
+    assert!(columns[2].nullable);
+
+    assert!(columns[0].is_primary_key);
+    assert!(!columns[1].is_primary_key);
+
+    // MariaDB returns the default as an expression, MySQL as the value
+    assert!(matches!(
+        columns[1].default.as_deref(),
+        Some("nobody") | Some("'nobody'")
+    ));
+
+    assert!(introspect::columns(&mut conn, "_sqlx_no_such_table")
+        .await?
+        .is_empty());
+
+    Ok(())
+}
+
+#[cfg(feature = "introspect")]
+#[sqlx_macros::test]
+async fn it_generates_structs_for_a_schema() -> anyhow::Result<()> {
+    use sqlx::codegen::{self, CodegenOptions};
+
+    let mut conn = new::<MySql>().await?;
+
+    conn.execute("DROP DATABASE IF EXISTS _sqlx_codegen")
+        .await?;
+    conn.execute(
+        r#"
+CREATE DATABASE _sqlx_codegen;
+
+CREATE TABLE _sqlx_codegen.users (
+    id            BIGINT PRIMARY KEY,
+    `displayName` VARCHAR(255),
+    type          VARCHAR(255) NOT NULL,
+    score         FLOAT
+);
+
+CREATE TABLE _sqlx_codegen.accounts (
+    id      INT PRIMARY KEY,
+    balance BIGINT NOT NULL
+);
+        "#,
+    )
+    .await?;
+
+    let source =
+        codegen::structs_for_schema(&mut conn, Some("_sqlx_codegen"), &CodegenOptions::new())
+            .await?;
+
+    conn.execute("DROP DATABASE _sqlx_codegen").await?;
+
+    assert_eq!(
+        source,
+        r#"// Generated by `sqlx::codegen::structs_for_schema`; do not edit by hand.
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct Accounts {
+    pub balance: i64,
+    pub id: i32,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct Users {
+    #[sqlx(rename = "displayName")]
+    pub display_name: Option<String>,
+    pub id: i64,
+    pub score: Option<f32>,
+    pub r#type: String,
+}
+"#
+    );
+
+    // the generated code compiles
+    let path = std::env::temp_dir().join("sqlx-codegen-mysql.rs");
+    std::fs::write(&path, source + "\nfn main() {}\n")?;
+
+    trybuild::TestCases::new().pass(&path);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_returns_rows_affected_from_execute() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE numbers (n INT NOT NULL)")
+        .await?;
+
+    let inserted = conn
+        .execute(
+            "INSERT INTO numbers (n) \
+             WITH RECURSIVE t (n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM t WHERE n < 1000) \
+             SELECT n FROM t",
+        )
+        .await?;
+
+    assert_eq!(inserted.rows_affected(), 1000);
+
+    let updated = conn.execute("UPDATE numbers SET n = n + 1").await?;
+    assert_eq!(updated.rows_affected(), 1000);
+
+    let updated = sqlx::query("UPDATE numbers SET n = n + 1 WHERE n > ?")
+        .bind(501_i32)
+        .execute(&mut conn)
+        .await?;
+    assert_eq!(updated.rows_affected(), 500);
+
+    // the rows a SELECT returns are not rows it affected
+    let selected = conn.execute("SELECT n FROM numbers").await?;
+    assert_eq!(selected.rows_affected(), 0);
+
+    let selected = sqlx::query("SELECT n FROM numbers WHERE n > ?")
+        .bind(0_i32)
+        .execute(&mut conn)
+        .await?;
+    assert_eq!(selected.rows_affected(), 0);
+
+    // the connection is left ready for the next query
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM numbers")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(count, 1000);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_binds_lists() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let numbers = "SELECT 1 AS n UNION ALL SELECT 2 UNION ALL SELECT 3";
+
+    let found: Vec<i64> = sqlx::query_scalar(&format!(
+        "SELECT n FROM ({}) t WHERE n IN (?) ORDER BY n",
+        numbers
+    ))
+    .bind_list(Vec::<i64>::new())
+    .fetch_all(&mut conn)
+    .await?;
+
+    assert!(found.is_empty());
+
+    let found: Vec<i64> = sqlx::query_scalar(&format!(
+        "SELECT n FROM ({}) t WHERE n NOT IN (?) ORDER BY n",
+        numbers
+    ))
+    .bind_list(Vec::<i64>::new())
+    .fetch_all(&mut conn)
+    .await?;
+
+    assert_eq!(found, [1, 2, 3]);
+
+    let found: Vec<i64> = sqlx::query_scalar(&format!(
+        "SELECT n FROM ({}) t WHERE n IN (?) ORDER BY n",
+        numbers
+    ))
+    .bind_list(&[2_i64])
+    .fetch_all(&mut conn)
+    .await?;
+
+    assert_eq!(found, [2]);
+
+    let values: Vec<i64> = (0..1000).collect();
+
+    let (literal, count, found): (String, i64, bool) =
+        sqlx::query_as("SELECT '?', ? /* ? */, ? IN (?)")
+            .bind(values.len() as i64)
+            .bind(999_i64)
+            .bind_list(&values)
+            .fetch_one(&mut conn)
+            .await?;
+
+    assert_eq!(literal, "?");
+    assert_eq!(count, 1000);
+    assert!(found);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_binds_limits_and_offsets() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let numbers = "SELECT 1 AS n UNION ALL SELECT 2 UNION ALL SELECT 3";
+    let sql = format!(
+        "SELECT n FROM ({}) t WHERE n > ? ORDER BY n LIMIT ? OFFSET ?",
+        numbers
+    );
+
+    for (limit, offset, expected) in [
+        (0, 0, &[][..]),
+        (1, 0, &[1][..]),
+        (1, 1, &[2][..]),
+        (u64::from(u32::MAX), 1, &[2, 3][..]),
+        (u64::MAX, 0, &[1, 2, 3][..]),
+        (1, u64::from(u32::MAX), &[][..]),
+    ] {
+        let found: Vec<i64> = sqlx::query_scalar(&sql)
+            .bind(0_i64)
+            .bind_limit(limit)
+            .bind_offset(offset)
+            .fetch_all(&mut conn)
+            .await?;
+
+        assert_eq!(found, expected, "LIMIT {} OFFSET {}", limit, offset);
+    }
+
+    // every page size is run with the same prepared statement
+    assert_eq!(conn.cached_statements_size(), 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_stream_scalars() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let digits = "(SELECT 0 AS d UNION ALL SELECT 1 UNION ALL SELECT 2 UNION ALL SELECT 3 \
+                  UNION ALL SELECT 4 UNION ALL SELECT 5 UNION ALL SELECT 6 UNION ALL SELECT 7 \
+                  UNION ALL SELECT 8 UNION ALL SELECT 9)";
+
+    let sql = format!(
+        "SELECT a.d + b.d * 10 + c.d * 100 + d.d * 1000 + e.d * 10000 + 1 AS n \
+         FROM {0} a, {0} b, {0} c, {0} d, {0} e ORDER BY n",
+        digits
+    );
+
+    let mut s = sqlx::query_scalar::<_, i64>(&sql).fetch(&mut conn);
+    let mut expected = 0;
+
+    while let Some(value) = s.try_next().await? {
+        expected += 1;
+        assert_eq!(value, expected);
+    }
+
+    assert_eq!(expected, 100_000);
+
+    drop(s);
+
+    // the columns are checked on the first row, before any value is returned
+    let mut s = sqlx::query_scalar::<_, i64>("SELECT 1, 2 UNION ALL SELECT 3, 4").fetch(&mut conn);
+
+    let err = s.try_next().await.unwrap_err();
+    assert!(matches!(err, sqlx::Error::Decode(_)), "{:?}", err);
+
+    drop(s);
+
+    // the rest of the rows were not read, so the query did not finish
+    assert!(conn.is_busy());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_rejects_use_after_abandoned_operation() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    // the stream is dropped after the first row, before the query finished
+    let mut s = conn.fetch("SELECT 1 UNION ALL SELECT 2 UNION ALL SELECT 3");
+    assert!(s.try_next().await?.is_some());
+    drop(s);
+
+    assert!(conn.is_busy());
+
+    // the next operation must not interleave with what is left of the abandoned one
+    let res = conn.execute("SELECT 1").await;
+
+    assert!(
+        matches!(res, Err(sqlx::Error::ConnectionBusy)),
+        "expected ConnectionBusy, got {:?}",
+        res
+    );
+
+    // `fetch_one` finishes without reading the rest of the rows
+    let mut conn = new::<MySql>().await?;
+
+    let value: i64 = sqlx::query_scalar("SELECT 1 UNION ALL SELECT 2")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(value, 1);
+    assert!(!conn.is_busy());
+
+    conn.execute("SELECT 1").await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_binds_raw_values() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    // 0x03 is the field type of INT
+    let int = MySqlTypeInfo::with_type_id(0x03, false)?;
+
+    let (value, null): (i64, Option<i64>) =
+        sqlx::query_as("SELECT CAST(? AS SIGNED), CAST(? AS SIGNED)")
+            .bind_raw(int.clone(), Some(&42_i32.to_le_bytes()))
+            .bind_raw(int, None)
+            .fetch_one(&mut conn)
+            .await?;
+
+    assert_eq!(value, 42);
+    assert_eq!(null, None);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_discards_connections_closed_by_the_server() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let pool = MySqlPoolOptions::new()
+        .max_connections(1)
+        .test_before_acquire(false)
+        .connect(&env::var("DATABASE_URL")?)
+        .await?;
+
+    let mut conn = pool.acquire().await?;
+
+    let id: u64 = sqlx::query_scalar("SELECT CONNECTION_ID()")
+        .fetch_one(&mut conn)
+        .await?;
+
+    let mut other = new::<MySql>().await?;
+    other.execute(&*format!("KILL CONNECTION {}", id)).await?;
+
+    let error = conn.execute("SELECT 1").await.unwrap_err();
+    assert!(error.is_connection_closed(), "{}", error);
+
+    // the next query fails right away, without writing into the closed connection
+    assert!(!conn.is_alive());
+
+    let written = conn.stats().bytes_written();
+    let error = conn.execute("SELECT 1").await.unwrap_err();
+
+    assert!(error.is_connection_closed(), "{}", error);
+    assert_eq!(conn.stats().bytes_written(), written);
+
+    drop(conn);
+
+    // with a single connection, this waits for the closed one to be discarded
+    let new_id: u64 = sqlx::query_scalar("SELECT CONNECTION_ID()")
+        .fetch_one(&pool)
+        .await?;
+
+    assert_ne!(id, new_id);
+
+    Ok(())
+}
+
+// with async-std, a socket that the server sent an error on before closing it is not detected
+// as closed until the error is read
+#[cfg(not(feature = "_rt-async-std"))]
+#[sqlx_macros::test]
+async fn pool_replaces_connections_closed_while_idle() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let pool = MySqlPoolOptions::new()
+        .max_connections(1)
+        .test_before_acquire(false)
+        .connect(&env::var("DATABASE_URL")?)
+        .await?;
+
+    let id: u64 = sqlx::query_scalar("SELECT CONNECTION_ID()")
+        .fetch_one(&pool)
+        .await?;
+
+    let mut other = new::<MySql>().await?;
+    other.execute(&*format!("KILL CONNECTION {}", id)).await?;
+
+    // give the server a moment to close the socket of the idle connection
+    sqlx_rt::sleep(Duration::from_millis(200)).await;
+
+    // the closed connection is not handed out, even without pinging it first
+    let new_id: u64 = sqlx::query_scalar("SELECT CONNECTION_ID()")
+        .fetch_one(&pool)
+        .await?;
+
+    assert_ne!(id, new_id);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_tracks_connection_stats() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let pool = MySqlPoolOptions::new()
+        .max_connections(1)
+        .connect(&env::var("DATABASE_URL")?)
+        .await?;
+
+    let mut conn = pool.acquire().await?;
+    let before = conn.stats();
+
+    let id: u64 = sqlx::query_scalar("SELECT CONNECTION_ID()")
+        .fetch_one(&mut conn)
+        .await?;
+
+    let rows = sqlx::query("SELECT ? UNION ALL SELECT ? UNION ALL SELECT ?")
+        .bind(1_i32)
+        .bind(2_i32)
+        .bind(3_i32)
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(rows.len(), 3);
+
+    conn.execute("SET @stats = 1").await?;
+
+    let after = conn.stats();
+
+    assert_eq!(after.backend_id(), Some(id as u32));
+    assert_eq!(after.queries(), before.queries() + 3);
+    assert_eq!(after.rows(), before.rows() + 4);
+    assert!(after.bytes_read() > before.bytes_read());
+    assert!(after.bytes_written() > before.bytes_written());
+    assert!(after.busy_time() > before.busy_time());
+    assert!(after.last_used() > before.last_used());
+
+    drop(conn);
+
+    // with a single connection, this waits for it to be returned to the pool
+    let conn = pool.acquire().await?;
+
+    // the counters are kept from one checkout to the next
+    assert_eq!(conn.stats().queries(), after.queries());
+
+    let stats = pool.connection_stats();
+
+    assert_eq!(stats.len(), 1);
+    assert_eq!(stats[0].backend_id(), Some(id as u32));
+    assert_eq!(stats[0].queries(), after.queries());
+    assert_eq!(stats[0].rows(), after.rows());
+
+    // including the ping that tested the connection as it was released
+    assert!(stats[0].bytes_read() > after.bytes_read());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_aborts_queries_over_their_server_timeout() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let version: String = sqlx::query_scalar("SELECT VERSION()")
+        .fetch_one(&mut conn)
+        .await?;
+
+    // `SLEEP` is not always interrupted, so keep the server busy instead
+    let query = || {
+        sqlx::query(
+            "WITH RECURSIVE t (n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM t WHERE n < 1000000000) \
+             SELECT COUNT(*) FROM t",
+        )
+        .server_timeout(Duration::from_millis(100))
+    };
+
+    if !version.contains("MariaDB") {
+        assert!(matches!(
+            query().execute(&mut conn).await,
+            Err(sqlx::Error::Configuration(_))
+        ));
+
+        return Ok(());
+    }
+
+    let err = query().execute(&mut conn).await.unwrap_err();
+    let err = err.into_database_error().unwrap();
+
+    assert_eq!(err.downcast_ref::<MySqlDatabaseError>().number(), 1969);
+
+    // the connection is usable afterwards
+    let value: i32 = sqlx::query_scalar("SELECT ?")
+        .bind(5_i32)
+        .server_timeout(Duration::from_secs(10))
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(value, 5);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_fetches_all_rows_before_decoding_them() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let digits = "(SELECT 0 AS n UNION ALL SELECT 1 UNION ALL SELECT 2 UNION ALL SELECT 3 \
+                  UNION ALL SELECT 4 UNION ALL SELECT 5 UNION ALL SELECT 6 UNION ALL SELECT 7 \
+                  UNION ALL SELECT 8 UNION ALL SELECT 9)";
+
+    let sql = format!(
+        "SELECT a.n + b.n * 10 + c.n * 100 AS i, CONCAT('row ', a.n + b.n * 10 + c.n * 100) AS t \
+         FROM {0} a, {0} b, {0} c ORDER BY i",
+        digits
+    );
+
+    let streamed: Vec<MySqlRow> = conn.fetch(&*sql).try_collect().await?;
+    let buffered = conn.fetch_all_concrete(&*sql).await?;
+
+    assert_eq!(buffered.len(), 1000);
+    assert_eq!(streamed.len(), buffered.len());
+
+    for (i, row) in buffered.iter().enumerate() {
+        assert_eq!(row.get::<i64, _>(0), i as i64);
+        assert_eq!(row.get::<String, _>("t"), format!("row {}", i));
+    }
+
+    // every result set keeps its own columns
+    let rows = conn
+        .fetch_all("SELECT 1 AS a; SELECT 'b' AS b, 2 AS c")
+        .await?;
+
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].get::<i32, _>("a"), 1);
+    assert_eq!(rows[1].get::<String, _>("b"), "b");
+    assert_eq!(rows[1].get::<i32, _>("c"), 2);
+
+    let rows = sqlx::query("SELECT ? + n FROM (SELECT 1 AS n UNION ALL SELECT 2) t ORDER BY n")
+        .bind(10_i64)
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[1].get::<i64, _>(0), 12);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_returns_errors_in_the_middle_of_fetch_all() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    // the first result set is read before the second one fails
+    let err = conn
+        .fetch_all("SELECT 1; SELECT * FROM sqlx_missing_table")
+        .await
+        .unwrap_err()
+        .into_database_error()
+        .unwrap();
+
+    assert_eq!(err.downcast_ref::<MySqlDatabaseError>().number(), 1146);
+
+    let value: i32 = sqlx::query_scalar("SELECT 5").fetch_one(&mut conn).await?;
+    assert_eq!(value, 5);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_keeps_owned_rows_after_the_connection_is_gone() -> anyhow::Result<()> {
+    use sqlx::{FromRow, OwnedRow};
+
+    #[derive(sqlx::FromRow)]
+    struct Record {
+        id: i64,
+        name: String,
+        note: Option<String>,
+    }
+
+    let sql = "SELECT CAST(7 AS SIGNED) AS id, 'seven' AS name, CAST(NULL AS CHAR) AS note";
+
+    let mut conn = new::<MySql>().await?;
+
+    // prepared, so the values are in the binary format
+    let row = sqlx::query(sql).fetch_one(&mut conn).await?;
+    let binary = row.to_owned();
+    drop(row);
+
+    // fetched without arguments, so the values are in the text format
+    let text: Vec<OwnedRow<MySql>> = conn
+        .fetch_all(sql)
+        .await?
+        .into_iter()
+        .map(OwnedRow::from)
+        .collect();
+
+    // or fetched as owned rows directly
+    let fetched: OwnedRow<MySql> = sqlx::query_as(sql).fetch_one(&mut conn).await?;
+
+    conn.close().await?;
+
+    for row in vec![binary, text[0].clone(), fetched] {
+        let row = std::thread::spawn(move || row).join().unwrap();
+
+        assert_eq!(row.len(), 3);
+        assert_eq!(row.column(1).name(), "name");
+        assert_eq!(row.try_get::<i64, _>("id")?, 7);
+        assert_eq!(row.try_get::<String, _>(1)?, "seven");
+        assert_eq!(row.try_get::<Option<String>, _>("note")?, None);
+
+        let record = Record::from_row(&row)?;
+
+        assert_eq!(record.id, 7);
+        assert_eq!(record.name, "seven");
+        assert_eq!(record.note, None);
+    }
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_falls_back_to_the_next_host() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let mut url = url::Url::parse(&env::var("DATABASE_URL")?)?;
+    let host = format!(
+        "{}:{}",
+        url.host_str().unwrap_or("localhost"),
+        url.port().unwrap_or(3306)
+    );
+
+    // nothing listens on port 1, so the connection to the first host is refused
+    url.set_host(Some("127.0.0.1"))?;
+    url.set_port(Some(1)).unwrap();
+    let url = url
+        .as_str()
+        .replacen("127.0.0.1:1", &format!("127.0.0.1:1,{}", host), 1);
+
+    let mut conn = MySqlConnection::connect(&url).await?;
+    let value: i64 = conn.fetch_one("SELECT 1").await?.try_get(0)?;
+
+    assert_eq!(value, 1);
+
+    // without a host to fall back to, the error lists why each host failed
+    let url = url.replacen(&host, "127.0.0.1:2", 1);
+    let error = MySqlConnection::connect(&url)
+        .await
+        .unwrap_err()
+        .to_string();
+
+    assert!(error.contains("failed to connect to any host (127.0.0.1:1: "));
+    assert!(error.contains("; 127.0.0.1:2: "));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_checks_the_number_of_bound_values() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let error = sqlx::query("SELECT ?, ?")
+        .bind(1_i32)
+        .execute(&mut conn)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        sqlx::Error::ParameterCountMismatch {
+            expected: 2,
+            got: 1
+        }
+    ));
+
+    let error = sqlx::query("SELECT ?")
+        .bind(1_i32)
+        .bind(2_i32)
+        .execute(&mut conn)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        sqlx::Error::ParameterCountMismatch {
+            expected: 1,
+            got: 2
+        }
+    ));
+
+    let (a, b): (i32, i32) = sqlx::query_as("SELECT ?, ?")
+        .bind(1_i32)
+        .bind(2_i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!((a, b), (1, 2));
+
+    // the values of a list count as one parameter each
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM (SELECT 1 AS n) t WHERE n IN (?)")
+        .bind_list(vec![1_i32, 2, 3])
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_runs_queries_after_a_parameter_count_mismatch() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let error = sqlx::query("SELECT ?, ?")
+        .bind(1_i32)
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, sqlx::Error::ParameterCountMismatch { .. }));
+
+    // the query was never sent, so the connection does not wait for its results
+    let value: i32 = sqlx_rt::timeout(
+        Duration::from_secs(10),
+        sqlx::query_scalar("SELECT ?")
+            .bind(7_i32)
+            .fetch_one(&mut conn),
+    )
+    .await??;
+
+    assert_eq!(value, 7);
+
+    sqlx_rt::timeout(Duration::from_secs(10), conn.execute("SELECT 1")).await??;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_returns_the_id_of_an_inserted_row() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    conn.execute(
+        "CREATE TEMPORARY TABLE people (id BIGINT UNSIGNED AUTO_INCREMENT PRIMARY KEY, name TEXT)",
+    )
+    .await?;
+
+    let first = sqlx::query("INSERT INTO people (name) VALUES (?)")
+        .bind("Herp Derpinson")
+        .insert_returning_id(&mut conn)
+        .await?;
+
+    let second = sqlx::query("INSERT INTO people (name) VALUES (?)")
+        .bind("Derp Herpinson")
+        .insert_returning_id(&mut conn)
+        .await?;
+
+    assert_eq!(second, first + 1);
+
+    let name: String = sqlx::query_scalar("SELECT name FROM people WHERE id = ?")
+        .bind(second)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(name, "Derp Herpinson");
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM people")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 2);
+
+    // an INSERT that inserts nothing has no id to return
+    let error = sqlx::query("INSERT IGNORE INTO people (id, name) VALUES (?, ?)")
+        .bind(first)
+        .bind("Herp Derpinson")
+        .insert_returning_id(&mut conn)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, sqlx::Error::RowNotFound));
+
+    // a scalar is decoded from a row with a single column
+    let error = sqlx::query_scalar::<_, u64>("SELECT id, name FROM people")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, sqlx::Error::Decode(_)));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_rejects_ambiguous_columns() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let sql = "SELECT * FROM (SELECT 1 AS id, 'Herp Derpinson' AS name) accounts \
+               JOIN (SELECT 2 AS id, 1 AS account_id) posts ON posts.account_id = accounts.id";
+
+    #[derive(Debug, sqlx::FromRow)]
+    struct Account {
+        id: i64,
+        name: String,
+    }
+
+    // both tables have an `id` column, so the field could be decoded from either
+    let error = sqlx::query_as::<_, Account>(sql)
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, sqlx::Error::AmbiguousColumn(ref name) if name == "id"));
+
+    #[derive(Debug, sqlx::FromRow)]
+    struct AccountPost {
+        #[sqlx(ordinal = 0)]
+        account_id: i64,
+        name: String,
+        #[sqlx(ordinal = 2)]
+        post_id: i64,
+    }
+
+    let row: AccountPost = sqlx::query_as(sql).fetch_one(&mut conn).await?;
+
+    assert_eq!(row.account_id, 1);
+    assert_eq!(row.name, "Herp Derpinson");
+    assert_eq!(row.post_id, 2);
+
+    // the dynamic API decodes the first column of the name, and lists both
+    let row = sqlx::query(sql).fetch_one(&mut conn).await?;
+    let id: i64 = row.try_get("id")?;
+
+    assert_eq!(id, 1);
+    assert_eq!(row.columns().iter().filter(|c| c.name() == "id").count(), 2);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_only_negotiates_capabilities_it_implements() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    let capabilities = conn.capabilities();
+
+    assert!(capabilities.contains(MySqlCapabilities::PROTOCOL_41));
+    assert!(capabilities.contains(MySqlCapabilities::PLUGIN_AUTH));
+
+    // offered by servers, but not implemented by the driver
+    assert!(!capabilities.contains(MySqlCapabilities::COMPRESS));
+    assert!(!capabilities.contains(MySqlCapabilities::LOCAL_FILES));
+    assert!(!capabilities.contains(MySqlCapabilities::SESSION_TRACK));
+
+    // multiple result sets work either way
+    let rows = conn.fetch_all("SELECT 1; SELECT 2").await?;
+    assert_eq!(rows.len(), 2);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_decodes_rows_in_either_format_the_same() -> anyhow::Result<()> {
+    use sqlx::mysql::MySqlValueFormat;
+    use sqlx::FromRow;
+
+    #[derive(Debug, PartialEq, sqlx::FromRow)]
+    struct Record {
+        signed: i64,
+        big: u64,
+        real: f64,
+        flag: bool,
+        name: String,
+        data: Vec<u8>,
+        note: Option<String>,
+    }
+
+    let sql =
+        "SELECT CAST(-7 AS SIGNED) AS signed, CAST(18446744073709551615 AS UNSIGNED) AS big, \
+               CAST(1.5 AS DOUBLE) AS `real`, TRUE AS flag, 'seven' AS name, \
+               CAST(X'00FF' AS BINARY) AS data, CAST(NULL AS CHAR) AS note";
+
+    let mut conn = new::<MySql>().await?;
+
+    let binary = sqlx::query(sql).fetch_one(&mut conn).await?;
+    let text = conn.fetch_one(sql).await?;
+
+    assert_eq!(binary.format(), MySqlValueFormat::Binary);
+    assert_eq!(text.format(), MySqlValueFormat::Text);
+
+    let expected = Record {
+        signed: -7,
+        big: u64::MAX,
+        real: 1.5,
+        flag: true,
+        name: "seven".to_owned(),
+        data: vec![0x00, 0xff],
+        note: None,
+    };
+
+    assert_eq!(Record::from_row(&binary)?, expected);
+    assert_eq!(Record::from_row(&text)?, expected);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_reads_the_other_columns_of_a_row_with_an_unsupported_type() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    #[derive(sqlx::FromRow)]
+    struct Place {
+        id: i64,
+        name: String,
+    }
+
+    // no Rust type decodes `GEOMETRY`
+    let sql = "SELECT CAST(1 AS SIGNED) AS id, ST_GeomFromText('POINT(0 0)') AS location, \
+               'origin' AS name";
+
+    for row in vec![
+        sqlx::query(sql).fetch_one(&mut conn).await?,
+        conn.fetch_one(sql).await?,
+    ] {
+        assert_eq!(row.try_get::<i64, _>("id")?, 1);
+        assert_eq!(row.try_get::<String, _>("name")?, "origin");
+
+        let error = row.try_get::<String, _>("location").unwrap_err();
+
+        assert!(
+            matches!(error, sqlx::Error::ColumnDecode { .. }),
+            "{:?}",
+            error
+        );
+        assert!(error.to_string().contains("GEOMETRY"), "{}", error);
+    }
+
+    let place: Place = sqlx::query_as(sql).fetch_one(&mut conn).await?;
+
+    assert_eq!(place.id, 1);
+    assert_eq!(place.name, "origin");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_binds_named_parameters() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    // the value of a repeated name is bound to each of its `?`; literals and comments are left
+    // alone
+    let row = sqlx::query_named(
+        "SELECT :a, CONCAT(:b, ':a'), :a + 1, `:c` FROM (SELECT 'c' AS `:c`) t # :c",
+    )
+    .bind_named("a", 10_i64)
+    .bind_named("b", "x")
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(row.try_get::<i64, _>(0)?, 10);
+    assert_eq!(row.try_get::<String, _>(1)?, "x:a");
+    assert_eq!(row.try_get::<i64, _>(2)?, 11);
+    assert_eq!(row.try_get::<String, _>(3)?, "c");
+
+    // the names are bound in any order, through any of the query types
+    let (a, b): (i64, Option<i64>) = sqlx::query_as("SELECT :a, :b")
+        .bind_named("b", Option::<i64>::None)
+        .bind_named("a", 1_i64)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!((a, b), (1, None));
+
+    let error = sqlx::query_named("SELECT :a, :b")
+        .bind_named("a", 1_i64)
+        .bind_named("c", 3_i64)
+        .execute(&mut conn)
+        .await
+        .unwrap_err();
+
+    match error {
+        sqlx::Error::NamedParameterMismatch { missing, extra } => {
+            assert_eq!(missing, vec!["b"]);
+            assert_eq!(extra, vec!["c"]);
+        }
+
+        error => panic!("unexpected error: {}", error),
+    }
+
+    let error = sqlx::query_named("SELECT :a")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, sqlx::Error::NamedParameterMismatch { .. }));
+
+    // nothing was sent for the queries that failed
+    let value: i64 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+
+    assert_eq!(value, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_loads_local_files() -> anyhow::Result<()> {
+    let db = TestDb::<MySql>::from_env().await?;
+    let mut conn = db.connect().await?;
+
+    // the server has to allow `LOAD DATA LOCAL INFILE` for it to ask for files at all
+    let allowed: i64 = sqlx::query_scalar("SELECT @@GLOBAL.local_infile + 0")
+        .fetch_one(&mut conn)
+        .await?;
+
+    if allowed == 0 {
+        return Ok(());
+    }
+
+    conn.execute("CREATE TABLE pets (id INTEGER PRIMARY KEY, name TEXT NOT NULL)")
+        .await?;
+
+    let sql = "LOAD DATA LOCAL INFILE 'pets.csv' INTO TABLE pets \
+               FIELDS TERMINATED BY ',' LINES TERMINATED BY '\\n'";
+
+    // without a handler, the file is refused and the connection is left usable
+    let error = conn.execute(sql).await.unwrap_err();
+    assert!(matches!(error, sqlx::Error::Configuration(_)), "{}", error);
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM pets")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(count, 0);
+
+    conn.close().await?;
+
+    let options = db
+        .url()
+        .parse::<MySqlConnectOptions>()?
+        .local_infile_handler(|filename| {
+            let allowed = filename == "pets.csv";
+
+            async move {
+                if !allowed {
+                    return Err(std::io::ErrorKind::PermissionDenied.into());
+                }
+
+                Ok(&b"1,Garfield\n2,Snoopy\n3,Tom\n"[..])
+            }
+        });
+
+    let mut conn = MySqlConnection::connect_with(&options).await?;
+
+    let done = conn.execute(sql).await?;
+    assert_eq!(done.rows_affected(), 3);
+
+    let names: Vec<String> = sqlx::query_scalar("SELECT name FROM pets ORDER BY id")
+        .fetch_all(&mut conn)
+        .await?;
+    assert_eq!(names, ["Garfield", "Snoopy", "Tom"]);
+
+    conn.close().await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_fetches_raw_frames() -> anyhow::Result<()> {
+    use sqlx::wire::RawFrame;
+
+    let mut conn = new::<MySql>().await?;
+
+    let frames: Vec<RawFrame<MySql>> = conn
+        .fetch_raw_frames(sqlx::query("SELECT ? AS a UNION SELECT 2").bind(1_i32))
+        .try_collect()
+        .await?;
+
+    let mut rows = 0;
+
+    for frame in &frames[..frames.len() - 1] {
+        let bytes = match frame {
+            RawFrame::Metadata(bytes) => bytes,
+            RawFrame::Row(bytes) => {
+                rows += 1;
+                bytes
+            }
+            RawFrame::Done(_) => panic!("unexpected end of the result set"),
+        };
+
+        assert_eq!(
+            u32::from_le_bytes([bytes[0], bytes[1], bytes[2], 0]) as usize,
+            bytes.len() - 4
+        );
+    }
+
+    assert_eq!(rows, 2);
+    assert!(matches!(frames.last(), Some(RawFrame::Done(_))));
+
+    // the connection is left ready after a stream of frames that was not read to the end
+    let mut frames = conn.fetch_raw_frames(
+        "SELECT a.column_name FROM information_schema.columns a, information_schema.columns b",
+    );
+    assert!(frames.try_next().await?.is_some());
+    drop(frames);
+
+    let value: i64 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(value, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_executes_prepared_statements_without_parameters() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE nothing_bound (id INTEGER)")
+        .await?;
+
+    // `query` prepares the statements even though no values are bound to them
+    for _ in 0..2 {
+        let done = sqlx::query("INSERT INTO nothing_bound (id) VALUES (1), (2)")
+            .execute(&mut conn)
+            .await?;
+        assert_eq!(done.rows_affected(), 2);
+
+        let value: i64 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+        assert_eq!(value, 1);
+
+        let done = sqlx::query("DELETE FROM nothing_bound")
+            .execute(&mut conn)
+            .await?;
+        assert_eq!(done.rows_affected(), 2);
+    }
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_prepares_statements_again_after_the_schema_changed() -> anyhow::Result<()> {
+    const SELECT: &str = "SELECT * FROM reprepared_rows WHERE id = ?";
+
+    let mut conn = new::<MySql>().await?;
+    let mut other = new::<MySql>().await?;
+
+    conn.execute("DROP TABLE IF EXISTS reprepared_rows").await?;
+    conn.execute("CREATE TABLE reprepared_rows (id INTEGER PRIMARY KEY)")
+        .await?;
+    conn.execute("INSERT INTO reprepared_rows (id) VALUES (1)")
+        .await?;
+
+    let row = sqlx::query(SELECT).bind(1_i32).fetch_one(&mut conn).await?;
+    assert_eq!(row.len(), 1);
+
+    // depending on the server, the cached statement is prepared again by the server or by us;
+    // either way, its rows have the columns the table has now
+    other
+        .execute("ALTER TABLE reprepared_rows ADD COLUMN a VARCHAR(8) NOT NULL DEFAULT 'a'")
+        .await?;
+
+    let row = sqlx::query(SELECT).bind(1_i32).fetch_one(&mut conn).await?;
+    assert_eq!(row.len(), 2);
+    assert_eq!(row.try_get::<String, _>("a")?, "a");
+
+    other
+        .execute("ALTER TABLE reprepared_rows ADD COLUMN b VARCHAR(8) NOT NULL DEFAULT 'b'")
+        .await?;
+
+    let rows = sqlx::query(SELECT).bind(1_i32).fetch_all(&mut conn).await?;
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].try_get::<String, _>("b")?, "b");
+
+    conn.execute("DROP TABLE reprepared_rows").await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_reports_the_progress_of_a_fetch() -> anyhow::Result<()> {
+    use sqlx::query::{FetchProgress, ProgressOptions};
+
+    let mut conn = new::<MySql>().await?;
+
+    // a temporary table cannot be referred to more than once in a query
+    let digits = "(SELECT 0 AS d UNION ALL SELECT 1 UNION ALL SELECT 2 UNION ALL SELECT 3 \
+                  UNION ALL SELECT 4 UNION ALL SELECT 5 UNION ALL SELECT 6 UNION ALL SELECT 7 \
+                  UNION ALL SELECT 8 UNION ALL SELECT 9)";
+
+    let sql = format!(
+        r#"
+SELECT n, MD5(n) AS hash
+FROM (
+    SELECT CAST(a.d + 10 * b.d + 100 * c.d + 1000 * e.d + 10000 * f.d + 1 AS SIGNED) AS n
+    FROM {0} a, {0} b, {0} c, {0} e, {0} f
+) numbers
+WHERE n <= ?
+ORDER BY n
+        "#,
+        digits
+    );
+
+    let expected: Vec<(i64, String)> = sqlx::query_as(&sql)
+        .bind(50_000_i64)
+        .fetch_all(&mut conn)
+        .await?;
+
+    let mut reports: Vec<FetchProgress> = Vec::new();
+    let rows: Vec<MySqlRow> = sqlx::query(&sql)
+        .bind(50_000_i64)
+        .fetch_with_progress(
+            &mut conn,
+            ProgressOptions::new()
+                .every_rows(5000)
+                .every(Duration::from_secs(3600))
+                .estimated_rows(50_000),
+            |p| reports.push(p.clone()),
+        )
+        .try_collect()
+        .await?;
+
+    let fetched: Vec<(i64, String)> = rows
+        .iter()
+        .map(|row| Ok((row.try_get(0)?, row.try_get(1)?)))
+        .collect::<sqlx::Result<_>>()?;
+
+    assert_eq!(fetched, expected);
+
+    // every 5000 rows, then once more when done
+    let progress: Vec<_> = reports.iter().map(|p| (p.rows, p.fraction())).collect();
+    let mut reported: Vec<_> = (1..=10)
+        .map(|i| (i * 5000, Some(i as f64 / 10.0)))
+        .collect();
+    reported.push((50_000, Some(1.0)));
+
+    assert_eq!(progress, reported);
+    assert!(reports.last().unwrap().done);
+    assert!(reports.last().unwrap().bytes > 50_000 * 32);
+
+    let mut estimates = Vec::new();
+    let rows: Vec<MySqlRow> = sqlx::query(&sql)
+        .bind(1234_i64)
+        .fetch_with_progress_counted(&mut conn, ProgressOptions::new(), |p| {
+            estimates.push(p.estimated_rows)
+        })
+        .try_collect()
+        .await?;
+
+    assert_eq!(rows.len(), 1234);
+    assert_eq!(estimates.last(), Some(&Some(1234)));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_decodes_the_columns_of_a_cached_statement_after_their_type_changed(
+) -> anyhow::Result<()> {
+    const SELECT: &str = "SELECT id, value FROM retyped_values WHERE id = ?";
+
+    let mut conn = new::<MySql>().await?;
+    let mut other = new::<MySql>().await?;
+
+    conn.execute("DROP TABLE IF EXISTS retyped_values").await?;
+    conn.execute("CREATE TABLE retyped_values (id INTEGER PRIMARY KEY, value INTEGER NOT NULL)")
+        .await?;
+    conn.execute("INSERT INTO retyped_values (id, value) VALUES (1, 10)")
+        .await?;
+
+    let row = sqlx::query(SELECT).bind(1_i32).fetch_one(&mut conn).await?;
+    assert_eq!(row.try_get::<i32, _>("value")?, 10);
+
+    other
+        .execute("ALTER TABLE retyped_values MODIFY value DOUBLE NOT NULL")
+        .await?;
+    other
+        .execute("UPDATE retyped_values SET value = 2.5")
+        .await?;
+
+    // the rows are decoded with the columns of the statement as executed, not as cached
+    let row = sqlx::query(SELECT).bind(1_i32).fetch_one(&mut conn).await?;
+    assert_eq!(row.column("value").type_info().name(), "DOUBLE");
+    assert_eq!(row.try_get::<f64, _>("value")?, 2.5);
+
+    let rows = sqlx::query(SELECT).bind(1_i32).fetch_all(&mut conn).await?;
+    assert_eq!(rows[0].try_get::<f64, _>(1)?, 2.5);
+
+    // which are then those of the cached statement
+    let statement = conn.prepare(SELECT).await?;
+    assert_eq!(statement.column("value").type_info().name(), "DOUBLE");
+
+    conn.execute("DROP TABLE retyped_values").await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_reads_every_column_as_text_with_text_results() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    conn.execute(
+        r#"
+CREATE TEMPORARY TABLE payments (
+    at DATETIME NOT NULL,
+    amount DECIMAL(10, 2) NOT NULL,
+    mood ENUM('ok', 'happy') NOT NULL
+);
+
+INSERT INTO payments (at, amount, mood) VALUES ('2022-05-04 12:30:00', 12.50, 'happy');
+        "#,
+    )
+    .await?;
+
+    const SELECT: &str = "SELECT at, amount, mood, 7 AS id FROM payments";
+
+    let rows = sqlx::query(SELECT)
+        .text_results(true)
+        .fetch_all(&mut conn)
+        .await?;
+
+    let values = (0..rows[0].len())
+        .map(|index| rows[0].try_get::<String, _>(index))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    assert_eq!(values, ["2022-05-04 12:30:00", "12.50", "happy", "7"]);
+
+    // the columns keep their types, and values can still be decoded from their text
+    let types: Vec<_> = rows[0]
+        .columns()
+        .iter()
+        .map(|column| column.type_info().name())
+        .collect();
+
+    assert_eq!(types, ["DATETIME", "DECIMAL", "ENUM", "BIGINT"]);
+    assert_eq!(rows[0].try_get::<i32, _>("id")?, 7);
+
+    let rows: Vec<MySqlRow> = sqlx::query(SELECT)
+        .text_results(true)
+        .fetch(&mut conn)
+        .try_collect()
+        .await?;
+
+    assert_eq!(rows[0].try_get::<String, _>("amount")?, "12.50");
+
+    // as can those of a copy of the row
+    let row = sqlx::OwnedRow::from(&rows[0]);
+
+    assert_eq!(row.try_get::<String, _>("amount")?, "12.50");
+    assert_eq!(row.try_get::<i32, _>("id")?, 7);
+
+    // a query with bound values is prepared, and its rows are in binary format regardless
+    let row = sqlx::query("SELECT at FROM payments WHERE mood = ?")
+        .bind("happy")
+        .text_results(true)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert!(row.try_get::<String, _>("at").is_err());
+
+    Ok(())
+}
+
+#[cfg(feature = "export")]
+#[sqlx_macros::test]
+async fn it_exports_csv() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+    let mut out = Vec::new();
+
+    sqlx::export::csv(
+        sqlx::query(
+            "SELECT 1 AS id, 'a, \"quoted\" name' AS name, X'DEADBEEF' AS data \
+             UNION ALL SELECT 2, NULL, NULL",
+        ),
+        &mut conn,
+        &mut out,
+    )
+    .await?;
+
+    assert_eq!(
+        String::from_utf8(out)?,
+        "id,name,data\r\n1,\"a, \"\"quoted\"\" name\",3q2+7w==\r\n2,,\r\n"
+    );
+
+    Ok(())
+}
+
+#[cfg(all(feature = "export", feature = "chrono"))]
+#[sqlx_macros::test]
+async fn it_exports_timestamps_as_csv() -> anyhow::Result<()> {
+    let mut conn = new::<MySql>().await?;
+
+    // the time zone of the session is UTC, in which `at_utc` is read back
+    conn.execute(
+        "CREATE TEMPORARY TABLE exported_times (at_utc TIMESTAMP(3) NOT NULL, \
+         at_local DATETIME(3) NOT NULL, on_day DATE NOT NULL, at_time TIME NOT NULL)",
+    )
+    .await?;
+
+    conn.execute(
+        "INSERT INTO exported_times VALUES \
+         ('2022-05-04 10:30:00.5', '2022-05-04 12:30:00.5', '2022-05-04', '12:30:00')",
+    )
+    .await?;
+
+    let mut out = Vec::new();
+
+    sqlx::export::csv(
+        sqlx::query("SELECT * FROM exported_times"),
+        &mut conn,
+        &mut out,
+    )
+    .await?;
+
+    assert_eq!(
+        String::from_utf8(out)?,
+        "at_utc,at_local,on_day,at_time\r\n\
+         2022-05-04T10:30:00.500+00:00,2022-05-04T12:30:00.500,2022-05-04,12:30:00\r\n"
+    );
+
+    Ok(())
+}
@@ -33,6 +33,13 @@ test_type!(string<String>(MySql,
     "''" == ""
 ));
 
+test_type!(char(
+    MySql,
+    "'a'" == 'a',
+    "CAST('\u{e9}' AS CHAR(1))" == '\u{e9}',
+    "'\u{1f980}'" == '\u{1f980}',
+));
+
 test_type!(bytes<Vec<u8>>(MySql,
     "X'DEADBEEF'"
         == vec![0xDE_u8, 0xAD, 0xBE, 0xEF],
@@ -61,11 +68,12 @@ test_type!(uuid_hyphenated<sqlx::types::uuid::adapter::Hyphenated>(MySql,
 #[cfg(feature = "chrono")]
 mod chrono {
     use super::*;
-    use sqlx::types::chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+    use sqlx::types::chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 
     test_type!(chrono_date<NaiveDate>(MySql,
         "DATE '2001-01-05'" == NaiveDate::from_ymd(2001, 1, 5),
-        "DATE '2050-11-23'" == NaiveDate::from_ymd(2050, 11, 23)
+        "DATE '2050-11-23'" == NaiveDate::from_ymd(2050, 11, 23),
+        "DATE '1969-07-20'" == NaiveDate::from_ymd(1969, 7, 20)
     ));
 
     test_type!(chrono_time_zero<NaiveTime>(MySql,
@@ -73,11 +81,20 @@ mod chrono {
     ));
 
     test_type!(chrono_time<NaiveTime>(MySql,
-        "TIME '05:10:20.115100'" == NaiveTime::from_hms_micro(5, 10, 20, 115100)
+        "TIME '05:10:20.115100'" == NaiveTime::from_hms_micro(5, 10, 20, 115100),
+        "TIME '23:59:59.999999'" == NaiveTime::from_hms_micro(23, 59, 59, 999999)
+    ));
+
+    test_type!(chrono_duration<Duration>(MySql,
+        "TIME '00:00:00'" == Duration::zero(),
+        "TIME '-838:59:59'" == -(Duration::hours(838) + Duration::minutes(59) + Duration::seconds(59)),
+        "TIME '100:00:00.000001'" == Duration::hours(100) + Duration::microseconds(1)
     ));
 
     test_type!(chrono_date_time<NaiveDateTime>(MySql,
-        "TIMESTAMP '2019-01-02 05:10:20'" == NaiveDate::from_ymd(2019, 1, 2).and_hms(5, 10, 20)
+        "TIMESTAMP '2019-01-02 05:10:20'" == NaiveDate::from_ymd(2019, 1, 2).and_hms(5, 10, 20),
+        "TIMESTAMP '1969-07-20 20:17:40.999999'"
+            == NaiveDate::from_ymd(1969, 7, 20).and_hms_micro(20, 17, 40, 999999)
     ));
 
     test_type!(chrono_timestamp<DateTime::<Utc>>(MySql,
@@ -136,7 +153,8 @@ mod time_tests {
     test_type!(time_date<Date>(
         MySql,
         "DATE '2001-01-05'" == date!(2001 - 1 - 5),
-        "DATE '2050-11-23'" == date!(2050 - 11 - 23)
+        "DATE '2050-11-23'" == date!(2050 - 11 - 23),
+        "DATE '1969-07-20'" == date!(1969 - 7 - 20)
     ));
 
     test_type!(time_time_zero<Time>(
@@ -146,14 +164,17 @@ mod time_tests {
 
     test_type!(time_time<Time>(
         MySql,
-        "TIME '05:10:20.115100'" == time!(5:10:20.115100)
+        "TIME '05:10:20.115100'" == time!(5:10:20.115100),
+        "TIME '23:59:59.999999'" == time!(23:59:59.999999)
     ));
 
     test_type!(time_date_time<PrimitiveDateTime>(
         MySql,
         "TIMESTAMP '2019-01-02 05:10:20'" == date!(2019 - 1 - 2).with_time(time!(5:10:20)),
         "TIMESTAMP '2019-01-02 05:10:20.115100'"
-            == date!(2019 - 1 - 2).with_time(time!(5:10:20.115100))
+            == date!(2019 - 1 - 2).with_time(time!(5:10:20.115100)),
+        "TIMESTAMP '1969-07-20 20:17:40.999999'"
+            == date!(1969 - 7 - 20).with_time(time!(20:17:40.999999))
     ));
 
     test_type!(time_timestamp<OffsetDateTime>(
@@ -1,5 +1,5 @@
 use futures::TryStreamExt;
-use sqlx::{Connection, Executor, FromRow, Postgres};
+use sqlx::{Column, Connection, Executor, FromRow, Postgres, Row};
 use sqlx_core::postgres::types::PgRange;
 use sqlx_test::{new, test_type};
 use std::fmt::Debug;
@@ -110,6 +110,13 @@ struct InventoryItem {
     price: Option<i64>,
 }
 
+#[derive(PartialEq, Debug, sqlx::Type)]
+#[sqlx(type_name = "inventory_line")]
+struct InventoryLine {
+    item: InventoryItem,
+    quantity: Option<i32>,
+}
+
 // Custom range type
 #[derive(sqlx::Type, Debug, PartialEq)]
 #[sqlx(type_name = "float_range")]
@@ -232,6 +239,7 @@ SELECT id, mood FROM people WHERE id = $1
     assert_eq!(rec.id, people_id);
     assert_eq!(rec.mood, Mood::Sad);
 
+    assert!(cursor.try_next().await?.is_none());
     drop(cursor);
 
     // Normal type equivalency test
@@ -360,6 +368,100 @@ SELECT $1 = ROW('fuzzy dice', 42, 199)::inventory_item, $1
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn test_nested_record_type() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let value = InventoryLine {
+        item: InventoryItem {
+            name: "fuzzy dice".to_owned(),
+            supplier_id: None,
+            price: Some(199),
+        },
+        quantity: None,
+    };
+
+    let rec: (bool, InventoryLine) = sqlx::query_as(
+        "
+SELECT $1 IS NOT DISTINCT FROM inventory_line('fuzzy dice', NULL), $1
+        ",
+    )
+    .bind(&value)
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert!(rec.0);
+    assert_eq!(rec.1, value);
+
+    // a composite returned by a function
+    let line: InventoryLine = sqlx::query_scalar("SELECT inventory_line('fuzzy dice', 3)")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(line.item, value.item);
+    assert_eq!(line.quantity, Some(3));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_record_type_as_tuple() -> anyhow::Result<()> {
+    use sqlx::postgres::PgTypeInfo;
+
+    let mut conn = new::<Postgres>().await?;
+
+    // a composite decodes into a tuple of its fields
+    let (item, quantity): ((String, Option<i32>, Option<i64>), Option<i32>) =
+        sqlx::query_scalar("SELECT inventory_line('fuzzy dice', 3)")
+            .fetch_one(&mut conn)
+            .await?;
+
+    assert_eq!(item, ("fuzzy dice".to_owned(), None, Some(199)));
+    assert_eq!(quantity, Some(3));
+
+    // a composite nested in an anonymous record is only known by its OID
+    let (id, item): (i32, InventoryItem) =
+        sqlx::query_scalar("SELECT ROW(1, ROW('fuzzy dice', 42, NULL)::inventory_item)")
+            .fetch_one(&mut conn)
+            .await?;
+
+    assert_eq!(id, 1);
+    assert_eq!(item.supplier_id, Some(42));
+    assert_eq!(item.price, None);
+
+    // a tuple is sent as a composite when it is bound with the type of the composite
+    let item = InventoryItem {
+        name: "fuzzy dice".to_owned(),
+        supplier_id: None,
+        price: Some(199),
+    };
+
+    let line: InventoryLine = sqlx::query_scalar("SELECT $1")
+        .bind_typed(
+            (&item, Some(3_i32)),
+            PgTypeInfo::with_name("inventory_line"),
+        )
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(line.item, item);
+    assert_eq!(line.quantity, Some(3));
+
+    let matches: bool = sqlx::query_scalar(
+        "SELECT $1 IS NOT DISTINCT FROM ROW('fuzzy dice', NULL, 199)::inventory_item",
+    )
+    .bind_typed(
+        ("fuzzy dice", None::<i32>, Some(199_i64)),
+        PgTypeInfo::with_name("inventory_item"),
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert!(matches);
+
+    Ok(())
+}
+
 #[cfg(feature = "macros")]
 #[sqlx_macros::test]
 async fn test_from_row() -> anyhow::Result<()> {
@@ -559,3 +661,50 @@ async fn test_default() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[cfg(feature = "macros")]
+#[sqlx_macros::test]
+async fn test_from_row_ambiguous_column() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let sql = "SELECT * FROM (VALUES (1, 'Herp Derpinson')) accounts(id, name) \
+               JOIN (VALUES (2, 1)) posts(id, account_id) ON posts.account_id = accounts.id";
+
+    #[derive(Debug, sqlx::FromRow)]
+    struct Account {
+        id: i32,
+        name: String,
+    }
+
+    // both tables have an `id` column, so the field could be decoded from either
+    let error = sqlx::query_as::<_, Account>(sql)
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, sqlx::Error::AmbiguousColumn(ref name) if name == "id"));
+
+    #[derive(Debug, sqlx::FromRow)]
+    struct AccountPost {
+        #[sqlx(ordinal = 0)]
+        account_id: i32,
+        name: String,
+        #[sqlx(ordinal = 2)]
+        post_id: i32,
+    }
+
+    let row: AccountPost = sqlx::query_as(sql).fetch_one(&mut conn).await?;
+
+    assert_eq!(row.account_id, 1);
+    assert_eq!(row.name, "Herp Derpinson");
+    assert_eq!(row.post_id, 2);
+
+    // the dynamic API decodes the first column of the name, and lists both
+    let row = sqlx::query(sql).fetch_one(&mut conn).await?;
+    let id: i32 = row.try_get("id")?;
+
+    assert_eq!(id, 1);
+    assert_eq!(row.columns().iter().filter(|c| c.name() == "id").count(), 2);
+
+    Ok(())
+}
@@ -93,3 +93,31 @@ async fn it_describes_composite() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn it_describes_statements_without_result_set() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    // the server responds with `NoData`
+    let d = conn.describe("UPDATE tweet SET text = text").await?;
+
+    assert!(!d.returns_rows());
+    assert!(d.columns().is_empty());
+
+    let d = conn
+        .describe("INSERT INTO tweet (text) VALUES ('Hello') RETURNING id")
+        .await?;
+
+    assert!(d.returns_rows());
+    assert_eq!(d.columns()[0].name(), "id");
+    assert_eq!(d.columns()[0].type_info().name(), "INT8");
+    assert_eq!(d.nullable(0), Some(false));
+
+    // a result set of zero columns is still a result set
+    let d = conn.describe("SELECT FROM tweet").await?;
+
+    assert!(d.returns_rows());
+    assert!(d.columns().is_empty());
+
+    Ok(())
+}
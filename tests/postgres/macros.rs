@@ -42,6 +42,110 @@ async fn test_non_null() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn test_query_domains() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    // the parameters and columns of domains have the types the domains are defined over,
+    // following `work_email` to `email` to `TEXT`
+    let mailbox = sqlx::query!(
+        "SELECT id, email FROM mailboxes WHERE id = $1::account_id AND email = $2::work_email",
+        1_i64,
+        "herp@example.com"
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    let id: i64 = mailbox.id;
+    let email: String = mailbox.email;
+
+    assert_eq!(id, 1);
+    assert_eq!(email, "herp@example.com");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_query_with_url_env() -> anyhow::Result<()> {
+    // the same database, with the `analytics` schema on the search path instead of `public`
+    let mut analytics = PgConnection::connect(&std::env::var("ANALYTICS_DATABASE_URL")?).await?;
+    let mut conn = new::<Postgres>().await?;
+
+    // `events` only exists in the `analytics` schema
+    let event = sqlx::query!(
+        url_env = "ANALYTICS_DATABASE_URL",
+        "SELECT id, name FROM events WHERE id = $1",
+        1_i32
+    )
+    .fetch_one(&mut analytics)
+    .await?;
+
+    assert_eq!(event.id, 1);
+    assert_eq!(event.name, "signup");
+
+    let name: String = sqlx::query_scalar!(
+        url_env = "ANALYTICS_DATABASE_URL",
+        "SELECT name FROM events LIMIT 1"
+    )
+    .fetch_one(&mut analytics)
+    .await?;
+
+    assert_eq!(name, "signup");
+
+    // and `tweet` only in the `public` one
+    let tweet = sqlx::query!(
+        url_env = "DATABASE_URL",
+        "SELECT id, text FROM tweet WHERE id = $1",
+        -1_i64
+    )
+    .fetch_optional(&mut conn)
+    .await?;
+
+    assert!(tweet.is_none());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_query_array_params() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let row = sqlx::query!(
+        "SELECT $1::int8[] as ids, $2::bytea as bytes",
+        [1_i64, 2, 3],
+        &[0xaa_u8; 4]
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(row.ids, Some(vec![1, 2, 3]));
+    assert_eq!(row.bytes, Some(vec![0xaa; 4]));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_query_catalog_types() -> anyhow::Result<()> {
+    use sqlx::postgres::types::PgOid;
+
+    let mut conn = new::<Postgres>().await?;
+
+    let row = sqlx::query!(
+        r#"SELECT oid, oid::regclass AS "rel!", relname FROM pg_class WHERE relname = 'pg_class'"#
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    let oid: u32 = row.oid;
+    let rel: PgOid = row.rel;
+
+    assert_eq!(oid, 1259);
+    assert_eq!(rel, PgOid(1259));
+    assert_eq!(row.relname, "pg_class");
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn test_no_result() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
@@ -54,6 +158,39 @@ async fn test_no_result() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn test_insert_update_returning() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+    let mut tx = conn.begin().await?;
+
+    let tweet = sqlx::query!(
+        "INSERT INTO tweet (text) VALUES ($1) RETURNING id, text",
+        "Hello, World"
+    )
+    .fetch_one(&mut tx)
+    .await?;
+
+    let id: i64 = tweet.id;
+    assert_eq!(tweet.text, "Hello, World");
+
+    let result = sqlx::query!("UPDATE tweet SET text = $1 WHERE id = $2", "Goodbye", id)
+        .execute(&mut tx)
+        .await?;
+
+    assert_eq!(result.rows_affected(), 1);
+
+    // the struct is ignored for a statement without a result set
+    let result = sqlx::query_as!(Account, "DELETE FROM tweet WHERE id = $1", id)
+        .execute(&mut tx)
+        .await?;
+
+    assert_eq!(result.rows_affected(), 1);
+
+    // let the transaction rollback so we don't actually insert the tweet
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn test_text_var_char_char_n() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
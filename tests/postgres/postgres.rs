@@ -2,9 +2,12 @@ use futures::{StreamExt, TryStreamExt};
 use sqlx::postgres::{
     PgConnectOptions, PgConnection, PgDatabaseError, PgErrorPosition, PgSeverity,
 };
-use sqlx::postgres::{PgConnectionInfo, PgPoolOptions, PgRow, Postgres};
-use sqlx::{Column, Connection, Executor, Row, Statement, TypeInfo};
-use sqlx_test::{new, setup_if_needed};
+use sqlx::postgres::{PgConnectionInfo, PgPoolOptions, PgRow, PgTypeInfo, Postgres};
+use sqlx::{
+    Column, Connection, Executor, IsolationLevel, Row, ServerVendor, Statement, TransactionStatus,
+    TypeInfo,
+};
+use sqlx_test::{new, setup_if_needed, TestDb};
 use std::env;
 use std::time::Duration;
 
@@ -22,6 +25,87 @@ async fn it_connects() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_closes_with_pending_work() -> anyhow::Result<()> {
+    // a result that was not read to the end is read for a bounded time before closing
+    let mut conn = new::<Postgres>().await?;
+
+    {
+        let mut s = conn.fetch("SELECT generate_series(1, 1000000)");
+        assert!(s.try_next().await?.is_some());
+    }
+
+    let start = std::time::Instant::now();
+    sqlx_rt::timeout(Duration::from_secs(10), conn.close()).await??;
+    assert!(start.elapsed() < Duration::from_secs(5));
+
+    // the `ROLLBACK` of a dropped transaction is not sent; the server rolls back on its own
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE dropped_transaction_rows (id INTEGER PRIMARY KEY)")
+        .await?;
+
+    let mut tx = conn.begin().await?;
+    sqlx::query("INSERT INTO dropped_transaction_rows (id) VALUES (1)")
+        .execute(&mut tx)
+        .await?;
+    drop(tx);
+
+    sqlx_rt::timeout(Duration::from_secs(10), conn.close()).await??;
+
+    // an idle connection
+    let conn = new::<Postgres>().await?;
+
+    sqlx_rt::timeout(Duration::from_secs(10), conn.close()).await??;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_connects_with_a_password_set_on_the_options() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("DROP ROLE IF EXISTS sqlx_special_password")
+        .await?;
+    conn.execute("CREATE ROLE sqlx_special_password LOGIN PASSWORD 'p@ss:w/rd%'")
+        .await?;
+
+    let options = env::var("DATABASE_URL")?
+        .parse::<PgConnectOptions>()?
+        .username("sqlx_special_password")
+        .password("p@ss:w/rd%");
+
+    let debug = format!("{:?}", options);
+
+    assert!(debug.contains("REDACTED"), "{}", debug);
+    assert!(!debug.contains("p@ss"), "{}", debug);
+
+    let mut user_conn = PgConnection::connect_with(&options).await?;
+
+    let user: String = sqlx::query_scalar("SELECT current_user::text")
+        .fetch_one(&mut user_conn)
+        .await?;
+    assert_eq!(user, "sqlx_special_password");
+
+    user_conn.close().await?;
+
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .connect_with(options)
+        .await?;
+
+    let user: String = sqlx::query_scalar("SELECT current_user::text")
+        .fetch_one(&pool)
+        .await?;
+    assert_eq!(user, "sqlx_special_password");
+
+    pool.close().await;
+
+    conn.execute("DROP ROLE sqlx_special_password").await?;
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_can_select_void() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
@@ -84,6 +168,45 @@ async fn it_can_inspect_errors() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_shows_the_position_of_errors() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let err = sqlx::query("SELECT 1\nFROM pg_type\nWHER typname = $1")
+        .bind("bool")
+        .fetch_optional(&mut conn)
+        .await
+        .unwrap_err();
+
+    assert_eq!(
+        err.to_string(),
+        concat!(
+            "error returned from database: syntax error at or near \"WHER\"\n",
+            "LINE 3: WHER typname = $1\n",
+            "        ^",
+        )
+    );
+
+    // the message itself is untouched
+    let err = err.into_database_error().unwrap();
+    assert_eq!(err.message(), "syntax error at or near \"WHER\"");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_fails_to_decode_more_than_one_character_as_char() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let res: Result<char, sqlx::Error> = sqlx::query_scalar("SELECT 'ab'::text")
+        .fetch_one(&mut conn)
+        .await;
+
+    assert!(matches!(res, Err(sqlx::Error::ColumnDecode { .. })));
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_can_inspect_constraint_errors() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
@@ -151,6 +274,39 @@ CREATE TEMPORARY TABLE users (id INTEGER PRIMARY KEY);
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_keeps_the_query_result_of_fetched_statements() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let _ = conn
+        .execute(
+            r#"
+CREATE TEMPORARY TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL);
+INSERT INTO users (id, name) VALUES (1, 'a'), (2, 'b'), (3, 'c');
+            "#,
+        )
+        .await?;
+
+    let rows = sqlx::query("UPDATE users SET name = $1 WHERE id < $2 RETURNING id")
+        .bind("d")
+        .bind(3_i32)
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(rows.len(), 2);
+    assert_eq!(conn.last_query_result().rows_affected(), 2);
+
+    // each query starts over, and a `SELECT` counts the rows it returned
+    let rows = conn
+        .fetch_all("SELECT 1; UPDATE users SET name = 'e'")
+        .await?;
+
+    assert_eq!(rows.len(), 1);
+    assert_eq!(conn.last_query_result().rows_affected(), 1 + 3);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_can_nest_map() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
@@ -352,6 +508,39 @@ async fn it_can_query_all_scalar() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_can_stream_scalars() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let mut s =
+        sqlx::query_scalar::<_, i64>("SELECT generate_series(1, 100000)::int8").fetch(&mut conn);
+
+    let mut expected = 0;
+
+    while let Some(value) = s.try_next().await? {
+        expected += 1;
+        assert_eq!(value, expected);
+    }
+
+    assert_eq!(expected, 100_000);
+
+    drop(s);
+
+    // the columns are checked on the first row, before any value is returned
+    let mut s = sqlx::query_scalar::<_, i64>("SELECT n::int8, n FROM generate_series(1, 10) n")
+        .fetch(&mut conn);
+
+    let err = s.try_next().await.unwrap_err();
+    assert!(matches!(err, sqlx::Error::Decode(_)), "{:?}", err);
+
+    drop(s);
+
+    // the rest of the rows were not read, so the query did not finish
+    assert!(conn.is_busy());
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_can_work_with_transactions() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
@@ -500,6 +689,231 @@ async fn it_can_drop_multiple_transactions() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn pool_backs_off_when_the_database_has_too_many_connections() -> anyhow::Result<()> {
+    let mut admin = new::<Postgres>().await?;
+
+    admin.execute("DROP ROLE IF EXISTS _sqlx_limited").await?;
+    admin
+        .execute("CREATE ROLE _sqlx_limited LOGIN PASSWORD 'limited' CONNECTION LIMIT 2")
+        .await?;
+
+    let options = dotenv::var("DATABASE_URL")?
+        .parse::<PgConnectOptions>()?
+        .username("_sqlx_limited")
+        .password("limited");
+
+    let pool = PgPoolOptions::new()
+        .max_connections(10)
+        .connect_timeout(Duration::from_secs(1))
+        .connect_with(options)
+        .await?;
+
+    let result = async {
+        let first = pool.acquire().await?;
+        let _second = pool.acquire().await?;
+
+        // the database refuses a third connection; rather than trying again right away, the
+        // pool stops growing and waits for one of its connections to be released
+        let started = std::time::Instant::now();
+        let error = pool.acquire().await.unwrap_err();
+
+        assert!(
+            matches!(error, sqlx::Error::ServerConnectionLimit { .. }),
+            "{}",
+            error
+        );
+        assert!(started.elapsed() >= Duration::from_secs(1));
+        assert_eq!(pool.size(), 2);
+
+        drop(first);
+        let _first = pool.acquire().await?;
+
+        // once the database allows more connections, the pool grows again after its backoff
+        admin
+            .execute("ALTER ROLE _sqlx_limited CONNECTION LIMIT 4")
+            .await?;
+
+        let _third = pool.acquire_timeout(Duration::from_secs(10)).await?;
+        assert_eq!(pool.size(), 3);
+
+        Ok::<_, anyhow::Error>(())
+    }
+    .await;
+
+    // the role can only be dropped once none of its connections are left
+    pool.close().await;
+    admin.execute("DROP ROLE _sqlx_limited").await?;
+
+    result
+}
+
+// waits for the backend of a connection to exit, i.e. for the connection to be closed
+async fn backend_exited(admin: &mut PgConnection, pid: i32) -> anyhow::Result<bool> {
+    for _ in 0..50 {
+        let exists: bool =
+            sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM pg_stat_activity WHERE pid = $1)")
+                .bind(pid)
+                .fetch_one(&mut *admin)
+                .await?;
+
+        if !exists {
+            return Ok(true);
+        }
+
+        sqlx_rt::sleep(Duration::from_millis(100)).await;
+    }
+
+    Ok(false)
+}
+
+#[sqlx_macros::test]
+async fn pool_connection_outlives_the_pool() -> anyhow::Result<()> {
+    let mut admin = new::<Postgres>().await?;
+
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .connect(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let mut conn = pool.acquire().await?;
+    let pid: i32 = sqlx::query_scalar("SELECT pg_backend_pid()")
+        .fetch_one(&mut conn)
+        .await?;
+
+    // the last handle to the pool is dropped while the connection is checked out
+    let acquire = pool.acquire();
+    drop(pool.clone());
+    drop(pool);
+
+    assert!(matches!(acquire.await, Err(sqlx::Error::PoolClosed)));
+
+    let value: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(value, 1);
+
+    // the connection is closed rather than returned to the pool
+    drop(conn);
+
+    assert!(backend_exited(&mut admin, pid).await?);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_closes_connections_returned_after_close() -> anyhow::Result<()> {
+    let mut admin = new::<Postgres>().await?;
+
+    let pool = PgPoolOptions::new()
+        .max_connections(2)
+        .connect(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let mut conn = pool.acquire().await?;
+    let pid: i32 = sqlx::query_scalar("SELECT pg_backend_pid()")
+        .fetch_one(&mut conn)
+        .await?;
+
+    // the pool is closed as soon as `close()` is called, before it is awaited
+    let close = pool.close();
+
+    assert!(pool.is_closed());
+    assert!(matches!(pool.acquire().await, Err(sqlx::Error::PoolClosed)));
+    assert!(matches!(
+        pool.try_begin().await,
+        Err(sqlx::Error::PoolClosed)
+    ));
+    assert!(pool.try_acquire().is_none());
+
+    let value: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(value, 1);
+
+    drop(conn);
+
+    sqlx_rt::timeout(Duration::from_secs(5), close).await?;
+    assert_eq!(pool.size(), 0);
+    assert!(backend_exited(&mut admin, pid).await?);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_resizes_under_load() -> anyhow::Result<()> {
+    use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+    use std::sync::Arc;
+    use std::time::Instant;
+
+    let pool = PgPoolOptions::new()
+        .max_connections(10)
+        .connect(&dotenv::var("DATABASE_URL")?)
+        .await?;
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let queries = Arc::new(AtomicU64::new(0));
+    let errors = Arc::new(AtomicU64::new(0));
+
+    for _ in 0..20 {
+        let (pool, stop) = (pool.clone(), Arc::clone(&stop));
+        let (queries, errors) = (Arc::clone(&queries), Arc::clone(&errors));
+
+        sqlx_rt::spawn(async move {
+            while !stop.load(Ordering::Acquire) {
+                match sqlx::query("SELECT pg_sleep(0.02)").execute(&pool).await {
+                    Ok(_) => queries.fetch_add(1, Ordering::AcqRel),
+                    Err(_) => errors.fetch_add(1, Ordering::AcqRel),
+                };
+            }
+        });
+    }
+
+    // the number of queries run in a second at the current size of the pool
+    async fn throughput(queries: &AtomicU64) -> u64 {
+        let before = queries.load(Ordering::Acquire);
+        sqlx_rt::sleep(Duration::from_secs(1)).await;
+        queries.load(Ordering::Acquire) - before
+    }
+
+    let full = throughput(&queries).await;
+    assert_eq!(pool.size(), 10);
+
+    pool.resize(2).await;
+    assert_eq!(pool.max_connections(), 2);
+
+    // the connections in use are closed as they are returned
+    let deadline = Instant::now() + Duration::from_secs(10);
+
+    while pool.size() > 2 {
+        assert!(
+            Instant::now() < deadline,
+            "{} connections left",
+            pool.size()
+        );
+        sqlx_rt::sleep(Duration::from_millis(10)).await;
+    }
+
+    let shrunk = throughput(&queries).await;
+    assert!(pool.size() <= 2);
+
+    pool.resize(10).await;
+
+    let recovered = throughput(&queries).await;
+    assert_eq!(pool.size(), 10);
+
+    stop.store(true, Ordering::Release);
+
+    assert_eq!(errors.load(Ordering::Acquire), 0);
+    assert!(shrunk * 2 < full, "{} queries/s down from {}", shrunk, full);
+    assert!(
+        recovered > shrunk * 2,
+        "{} queries/s after {}",
+        recovered,
+        shrunk
+    );
+
+    sqlx_rt::timeout(Duration::from_secs(5), pool.close()).await?;
+
+    Ok(())
+}
+
 // run with `cargo test --features postgres -- --ignored --nocapture pool_smoke_test`
 #[ignore]
 #[sqlx_macros::test]
@@ -691,6 +1105,76 @@ async fn it_caches_statements() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_connects_with_a_timezone() -> anyhow::Result<()> {
+    sqlx_test::setup_if_needed();
+
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse()?;
+
+    let mut conn = PgConnection::connect_with(&options.clone().timezone("Europe/Berlin")).await?;
+
+    let timezone: String = sqlx::query_scalar("SHOW timezone")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!("Europe/Berlin", timezone);
+
+    // an unknown time zone fails the connection
+    let res = PgConnection::connect_with(&options.timezone("Nowhere/Special")).await;
+
+    assert!(res.is_err());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_observes_messages() -> anyhow::Result<()> {
+    use sqlx::wire::Direction;
+    use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+    // the tags of the observed messages, packed in order
+    static SENT: AtomicU64 = AtomicU64::new(0);
+    static RECEIVED: AtomicU64 = AtomicU64::new(0);
+    static LEAKED: AtomicBool = AtomicBool::new(false);
+
+    fn observe(direction: Direction, message: &[u8]) {
+        if message.len() > 64 || (message[0] == b'p' && message.len() != 5) {
+            LEAKED.store(true, Ordering::SeqCst);
+        }
+
+        let tags = match direction {
+            Direction::Sent => &SENT,
+            Direction::Received => &RECEIVED,
+        };
+
+        let _ = tags.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |tags| {
+            Some(tags << 8 | u64::from(message[0]))
+        });
+    }
+
+    sqlx_test::setup_if_needed();
+
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse()?;
+
+    let mut conn = PgConnection::connect_with(&options.packet_observer(observe, 64)).await?;
+
+    // password messages are observed without their contents
+    assert!(!LEAKED.load(Ordering::SeqCst));
+
+    SENT.store(0, Ordering::SeqCst);
+    RECEIVED.store(0, Ordering::SeqCst);
+
+    conn.ping().await?;
+
+    assert_eq!(SENT.load(Ordering::SeqCst), u64::from(b'Q'));
+    assert_eq!(
+        RECEIVED.load(Ordering::SeqCst),
+        u64::from(b'I') << 8 | u64::from(b'Z')
+    );
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_closes_statement_from_cache_issue_470() -> anyhow::Result<()> {
     sqlx_test::setup_if_needed();
@@ -774,28 +1258,170 @@ async fn it_can_prepare_then_execute() -> anyhow::Result<()> {
     Ok(())
 }
 
-// repro is more reliable with the basic scheduler used by `#[tokio::test]`
-#[cfg(feature = "_rt-tokio")]
-#[tokio::test]
-async fn test_issue_622() -> anyhow::Result<()> {
-    use std::time::Instant;
-
+#[sqlx_macros::test]
+async fn pool_shrinks_read_buffers_on_release() -> anyhow::Result<()> {
     setup_if_needed();
 
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse()?;
+
     let pool = PgPoolOptions::new()
-        .max_connections(1) // also fails with higher counts, e.g. 5
-        .connect(&std::env::var("DATABASE_URL").unwrap())
+        .max_connections(1)
+        .connect_with(options.read_buffer_shrink_threshold(64 * 1024))
         .await?;
 
-    println!("pool state: {:?}", pool);
+    let mut conn = pool.acquire().await?;
 
-    let mut handles = vec![];
+    let rows = sqlx::query("SELECT repeat('x', 4 * 1024 * 1024)")
+        .fetch_all(&mut conn)
+        .await?;
 
-    // given repro spawned 100 tasks but I found it reliably reproduced with 3
-    for i in 0..3 {
-        let pool = pool.clone();
+    assert_eq!(rows.len(), 1);
+    drop(rows);
 
-        handles.push(sqlx_rt::spawn(async move {
+    // the buffer is kept while the connection keeps receiving large messages
+    assert!(conn.read_buffer_capacity() >= 4 * 1024 * 1024);
+
+    drop(conn);
+
+    // with a single connection, this waits for it to be returned to the pool
+    let conn = pool.acquire().await?;
+
+    assert!(conn.read_buffer_capacity() <= 64 * 1024);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_tracks_connection_stats() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .connect(&env::var("DATABASE_URL")?)
+        .await?;
+
+    let mut conn = pool.acquire().await?;
+    let before = conn.stats();
+
+    let pid: i32 = sqlx::query_scalar("SELECT pg_backend_pid()")
+        .fetch_one(&mut conn)
+        .await?;
+
+    let rows = sqlx::query("SELECT generate_series(1, $1)")
+        .bind(10_i32)
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(rows.len(), 10);
+
+    conn.execute("SET application_name = 'stats'").await?;
+
+    let after = conn.stats();
+
+    assert_eq!(after.backend_id(), Some(pid as u32));
+    assert_eq!(after.queries(), before.queries() + 3);
+    assert_eq!(after.rows(), before.rows() + 11);
+    assert!(after.bytes_read() > before.bytes_read());
+    assert!(after.bytes_written() > before.bytes_written());
+    assert!(after.busy_time() > before.busy_time());
+    assert!(after.last_used() > before.last_used());
+
+    drop(conn);
+
+    // with a single connection, this waits for it to be returned to the pool
+    let conn = pool.acquire().await?;
+
+    // the counters are kept from one checkout to the next
+    assert_eq!(conn.stats().queries(), after.queries());
+
+    let stats = pool.connection_stats();
+
+    assert_eq!(stats.len(), 1);
+    assert_eq!(stats[0].backend_id(), Some(pid as u32));
+    assert_eq!(stats[0].queries(), after.queries());
+    assert_eq!(stats[0].rows(), after.rows());
+
+    // including the ping that tested the connection as it was released
+    assert!(stats[0].bytes_read() > after.bytes_read());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_warms_statements_on_new_connections() -> anyhow::Result<()> {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    setup_if_needed();
+
+    let counter = Arc::new(AtomicUsize::new(0));
+
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .after_connect({
+            let counter = counter.clone();
+            move |_conn| {
+                let counter = counter.clone();
+                Box::pin(async move {
+                    counter.fetch_add(1, Ordering::SeqCst);
+
+                    Ok(())
+                })
+            }
+        })
+        .warm_statements(&[
+            "SELECT 1",
+            "SELECT $1::int4 + 1",
+            // logged and skipped
+            "SELECT * FROM this_table_does_not_exist",
+        ])
+        .connect(&env::var("DATABASE_URL")?)
+        .await?;
+
+    for _ in 0..4 {
+        let conn = pool.acquire().await?;
+        assert_eq!(2, conn.cached_statements_size());
+    }
+
+    // the warmed statement is used rather than prepared again
+    let mut conn = pool.acquire().await?;
+
+    let value: i32 = sqlx::query_scalar("SELECT $1::int4 + 1")
+        .bind(1_i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(2, value);
+    assert_eq!(2, conn.cached_statements_size());
+
+    // there is only ever one physical connection
+    assert_eq!(1, counter.load(Ordering::SeqCst));
+
+    Ok(())
+}
+
+// repro is more reliable with the basic scheduler used by `#[tokio::test]`
+#[cfg(feature = "_rt-tokio")]
+#[tokio::test]
+async fn test_issue_622() -> anyhow::Result<()> {
+    use std::time::Instant;
+
+    setup_if_needed();
+
+    let pool = PgPoolOptions::new()
+        .max_connections(1) // also fails with higher counts, e.g. 5
+        .connect(&std::env::var("DATABASE_URL").unwrap())
+        .await?;
+
+    println!("pool state: {:?}", pool);
+
+    let mut handles = vec![];
+
+    // given repro spawned 100 tasks but I found it reliably reproduced with 3
+    for i in 0..3 {
+        let pool = pool.clone();
+
+        handles.push(sqlx_rt::spawn(async move {
             {
                 let mut conn = pool.acquire().await.unwrap();
 
@@ -966,21 +1592,62 @@ async fn test_listener_cleanup() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_binds_and_decodes_domains_as_their_base_types() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let (id, email): (i64, String) = sqlx::query_as("SELECT $1::account_id, $2::work_email")
+        .bind(2_i64)
+        .bind("derp@example.com")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(id, 2);
+    assert_eq!(email, "derp@example.com");
+
+    // the statement reports the domains of its parameters
+    let statement = conn
+        .prepare("SELECT $1::account_id, $2::work_email")
+        .await?;
+
+    let params = statement.parameters().unwrap().unwrap_left();
+    assert_eq!(params[0].name(), "account_id");
+    assert_eq!(params[1].name(), "work_email");
+
+    // the domains are treated as the types they are defined over
+    assert_eq!(params[0], PgTypeInfo::with_name("int8"));
+    assert!(<String as sqlx::Type<Postgres>>::compatible(&params[1]));
+
+    let id: i64 = sqlx::query_scalar("SELECT id FROM mailboxes WHERE id = $1 AND email = $2")
+        .bind(1_i64)
+        .bind("herp@example.com")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(id, 1);
+
+    // the checks of the domains still apply
+    let res = sqlx::query("SELECT $1::email")
+        .bind("not an email")
+        .execute(&mut conn)
+        .await;
+
+    assert!(res.unwrap_err().into_database_error().is_some());
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_supports_domain_types_in_composite_domain_types() -> anyhow::Result<()> {
     // Only supported in Postgres 11+
-    let mut conn = new::<Postgres>().await?;
+    let db = TestDb::<Postgres>::from_env().await?;
+    let mut conn = db.connect().await?;
     if matches!(conn.server_version_num(), Some(version) if version < 110000) {
         return Ok(());
     }
 
     conn.execute(
         r#"
-DROP TABLE IF EXISTS heating_bills;
-DROP DOMAIN IF EXISTS winter_year_month;
-DROP TYPE IF EXISTS year_month;
-DROP DOMAIN IF EXISTS month_id;
-
 CREATE DOMAIN month_id AS INT2 CHECK (1 <= value AND value <= 12);
 CREATE TYPE year_month AS (year INT4, month month_id);
 CREATE DOMAIN winter_year_month AS year_month CHECK ((value).month <= 3);
@@ -1063,7 +1730,7 @@ CREATE TABLE heating_bills (
             sqlx::encode::IsNull::No
         }
     }
-    let mut conn = new::<Postgres>().await?;
+    let mut conn = db.connect().await?;
 
     let result = sqlx::query("DELETE FROM heating_bills;")
         .execute(&mut conn)
@@ -1097,7 +1764,8 @@ CREATE TABLE heating_bills (
 #[sqlx_macros::test]
 async fn it_resolves_custom_type_in_array() -> anyhow::Result<()> {
     // Only supported in Postgres 11+
-    let mut conn = new::<Postgres>().await?;
+    let db = TestDb::<Postgres>::from_env().await?;
+    let mut conn = db.connect().await?;
     if matches!(conn.server_version_num(), Some(version) if version < 110000) {
         return Ok(());
     }
@@ -1105,9 +1773,6 @@ async fn it_resolves_custom_type_in_array() -> anyhow::Result<()> {
     // language=PostgreSQL
     conn.execute(
         r#"
-DROP TABLE IF EXISTS pets;
-DROP TYPE IF EXISTS pet_name_and_race;
-
 CREATE TYPE pet_name_and_race AS (
   name TEXT,
   race TEXT
@@ -1169,7 +1834,7 @@ VALUES
         }
     }
 
-    let mut conn = new::<Postgres>().await?;
+    let mut conn = db.connect().await?;
 
     let row = sqlx::query("select owner, array_agg(row(name, race)::pet_name_and_race) as pets from pets group by owner")
         .fetch_one(&mut conn)
@@ -1256,6 +1921,107 @@ async fn it_can_abort_copy_in() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[sqlx_macros::test]
+async fn it_can_copy_in_binary() -> anyhow::Result<()> {
+    const ROWS: i64 = 100_000;
+
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute(
+        r#"
+        CREATE TEMPORARY TABLE measurements (id INT8 NOT NULL, name TEXT NOT NULL, value FLOAT8, valid BOOL NOT NULL);
+    "#,
+    )
+    .await?;
+
+    let mut copy = conn
+        .copy_in_binary(
+            "COPY measurements (id, name, value, valid) FROM STDIN WITH (FORMAT binary)",
+        )
+        .await?;
+
+    assert_eq!(copy.num_columns(), 4);
+
+    for id in 0..ROWS {
+        let value = if id % 10 == 0 {
+            None
+        } else {
+            Some(id as f64 / 2.0)
+        };
+
+        copy.write_row((id, format!("measurement {}", id), value, id % 2 == 0))
+            .await?;
+    }
+
+    assert_eq!(copy.finish().await?, ROWS as u64);
+
+    let (count, sum, nulls, valid): (i64, f64, i64, i64) = sqlx::query_as(
+        "SELECT COUNT(*), SUM(value), COUNT(*) - COUNT(value), COUNT(*) FILTER (WHERE valid) FROM measurements",
+    )
+    .fetch_one(&mut conn)
+    .await?;
+
+    let expected_sum: f64 = (0..ROWS)
+        .filter(|id| id % 10 != 0)
+        .map(|id| id as f64 / 2.0)
+        .sum();
+
+    assert_eq!(count, ROWS);
+    assert_eq!(sum, expected_sum);
+    assert_eq!(nulls, ROWS / 10);
+    assert_eq!(valid, ROWS / 2);
+
+    let name: String = sqlx::query_scalar("SELECT name FROM measurements WHERE id = 12345")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(name, "measurement 12345");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_aborts_a_binary_copy_of_a_mismatched_row() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE users (id INT8 NOT NULL, name TEXT NOT NULL)")
+        .await?;
+
+    let mut copy = conn
+        .copy_in_binary("COPY users (id, name) FROM STDIN WITH (FORMAT binary)")
+        .await?;
+
+    copy.write_row((1_i64, "alice")).await?;
+
+    let error = copy.write_row((2_i64,)).await.unwrap_err();
+    assert!(matches!(error, sqlx::Error::Encode(_)), "{}", error);
+
+    // the COPY is over; neither more rows nor finishing it are accepted
+    assert!(copy.write_row((3_i64, "carol")).await.is_err());
+    assert!(copy.finish().await.is_err());
+
+    // nothing was copied and the connection is safe for reuse
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 0);
+
+    // a COPY in text format is rejected
+    assert!(conn
+        .copy_in_binary("COPY users (id, name) FROM STDIN")
+        .await
+        .is_err());
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 0);
+
+    Ok(())
+}
+
 #[sqlx_macros::test]
 async fn it_can_copy_out() -> anyhow::Result<()> {
     let mut conn = new::<Postgres>().await?;
@@ -1289,83 +2055,314 @@ async fn it_can_copy_out() -> anyhow::Result<()> {
 }
 
 #[sqlx_macros::test]
-async fn it_encodes_custom_array_issue_1504() -> anyhow::Result<()> {
-    use sqlx::encode::IsNull;
-    use sqlx::postgres::{PgArgumentBuffer, PgTypeInfo};
-    use sqlx::{Decode, Encode, Type, ValueRef};
+async fn it_discards_the_rest_of_an_unfinished_copy_out() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
 
-    #[derive(Debug, PartialEq)]
-    enum Value {
-        String(String),
-        Number(i32),
-        Array(Vec<Value>),
+    {
+        let mut copy = conn
+            .copy_out_raw("COPY (SELECT generate_series(1, 100000) AS id) TO STDOUT")
+            .await?;
+
+        assert_eq!(copy.next().await.unwrap()?, "1\n");
     }
 
-    impl<'r> Decode<'r, Postgres> for Value {
-        fn decode(
-            value: sqlx::postgres::PgValueRef<'r>,
-        ) -> std::result::Result<Self, Box<dyn std::error::Error + 'static + Send + Sync>> {
-            let typ = value.type_info().into_owned();
+    let value: i32 = sqlx::query_scalar("SELECT 1 + 1")
+        .fetch_one(&mut conn)
+        .await?;
 
-            if typ == PgTypeInfo::with_name("text") {
-                let s = <String as Decode<'_, Postgres>>::decode(value)?;
+    assert_eq!(value, 2);
 
-                Ok(Self::String(s))
-            } else if typ == PgTypeInfo::with_name("int4") {
-                let n = <i32 as Decode<'_, Postgres>>::decode(value)?;
+    Ok(())
+}
 
-                Ok(Self::Number(n))
-            } else if typ == PgTypeInfo::with_name("_text") {
-                let arr = Vec::<String>::decode(value)?;
-                let v = arr.into_iter().map(|s| Value::String(s)).collect();
+#[sqlx_macros::test]
+async fn it_recovers_from_a_copy_of_the_wrong_statement() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
 
-                Ok(Self::Array(v))
-            } else if typ == PgTypeInfo::with_name("_int4") {
-                let arr = Vec::<i32>::decode(value)?;
-                let v = arr.into_iter().map(|n| Value::Number(n)).collect();
+    assert!(conn.copy_in_raw("SELECT 1").await.is_err());
+    assert!(conn.copy_out_raw("SELECT * FROM missing").await.is_err());
 
-                Ok(Self::Array(v))
-            } else {
-                Err("unknown type".into())
-            }
-        }
-    }
+    let value: i32 = sqlx::query_scalar("SELECT 1 + 1")
+        .fetch_one(&mut conn)
+        .await?;
 
-    impl Encode<'_, Postgres> for Value {
-        fn produces(&self) -> Option<PgTypeInfo> {
-            match self {
-                Self::Array(a) => {
-                    if a.len() < 1 {
-                        return Some(PgTypeInfo::with_name("_text"));
-                    }
+    assert_eq!(value, 2);
 
-                    match a[0] {
-                        Self::String(_) => Some(PgTypeInfo::with_name("_text")),
-                        Self::Number(_) => Some(PgTypeInfo::with_name("_int4")),
-                        Self::Array(_) => None,
-                    }
-                }
-                Self::String(_) => Some(PgTypeInfo::with_name("text")),
-                Self::Number(_) => Some(PgTypeInfo::with_name("int4")),
-            }
-        }
+    Ok(())
+}
 
-        fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
-            match self {
-                Value::String(s) => <String as Encode<'_, Postgres>>::encode_by_ref(s, buf),
-                Value::Number(n) => <i32 as Encode<'_, Postgres>>::encode_by_ref(n, buf),
-                Value::Array(arr) => arr.encode(buf),
-            }
-        }
+#[sqlx_macros::test]
+async fn it_returns_an_error_for_a_dropped_copy_in_once() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE users (id INTEGER NOT NULL)")
+        .await?;
+
+    {
+        let mut copy = conn.copy_in_raw("COPY users (id) FROM STDIN").await?;
+
+        copy.send("1\n".as_bytes()).await?;
     }
 
-    impl Type<Postgres> for Value {
-        fn type_info() -> PgTypeInfo {
-            PgTypeInfo::with_name("unknown")
-        }
+    let error = conn.execute("SELECT 1").await.unwrap_err();
+    assert_eq!(
+        error.as_database_error().and_then(|e| e.code()).as_deref(),
+        Some("57014")
+    );
 
-        fn compatible(ty: &PgTypeInfo) -> bool {
-            [
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM users")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 0);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_rejects_queries_on_a_connection_closed_by_the_server() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+    let mut other = new::<Postgres>().await?;
+
+    let pid: i32 = sqlx::query_scalar("SELECT pg_backend_pid()")
+        .fetch_one(&mut conn)
+        .await?;
+
+    sqlx::query("SELECT pg_terminate_backend($1)")
+        .bind(pid)
+        .execute(&mut other)
+        .await?;
+
+    let error = conn.execute("SELECT 1").await.unwrap_err();
+    assert!(error.is_connection_closed(), "{}", error);
+
+    // nothing is written into the closed connection anymore
+    assert!(!conn.is_alive());
+
+    let error = conn.execute("SELECT 1").await.unwrap_err();
+    assert!(error.is_connection_closed(), "{}", error);
+
+    assert!(conn
+        .copy_in_raw("COPY users (id) FROM STDIN")
+        .await
+        .unwrap_err()
+        .is_connection_closed());
+
+    Ok(())
+}
+
+// with async-std, a socket that the server sent an error on before closing it is not detected
+// as closed until the error is read
+#[cfg(not(feature = "_rt-async-std"))]
+#[sqlx_macros::test]
+async fn pool_replaces_connections_closed_while_idle() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .test_before_acquire(false)
+        .connect(&env::var("DATABASE_URL")?)
+        .await?;
+
+    let pid: i32 = sqlx::query_scalar("SELECT pg_backend_pid()")
+        .fetch_one(&pool)
+        .await?;
+
+    let mut other = new::<Postgres>().await?;
+
+    sqlx::query("SELECT pg_terminate_backend($1)")
+        .bind(pid)
+        .execute(&mut other)
+        .await?;
+
+    // give the server a moment to close the socket of the idle connection
+    sqlx_rt::sleep(Duration::from_millis(200)).await;
+
+    // the closed connection is not handed out, even without pinging it first
+    let new_pid: i32 = sqlx::query_scalar("SELECT pg_backend_pid()")
+        .fetch_one(&pool)
+        .await?;
+
+    assert_ne!(pid, new_pid);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_retries_reads_on_a_connection_lost_before_they_returned() -> anyhow::Result<()> {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    setup_if_needed();
+
+    // once set, the backend of the next connection is terminated as it is acquired
+    let kill = Arc::new(AtomicBool::new(false));
+    let kill_next = Arc::clone(&kill);
+
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .test_before_acquire(false)
+        .before_acquire(move |conn| {
+            let kill = kill_next.swap(false, Ordering::SeqCst);
+            let pid = conn.stats().backend_id();
+
+            Box::pin(async move {
+                if kill {
+                    let mut other =
+                        PgConnection::connect(&env::var("DATABASE_URL").unwrap()).await?;
+
+                    sqlx::query("SELECT pg_terminate_backend($1)")
+                        .bind(pid.map(|pid| pid as i32))
+                        .execute(&mut other)
+                        .await?;
+
+                    // give the server a moment to close the socket
+                    sqlx_rt::sleep(Duration::from_millis(200)).await;
+                }
+
+                Ok(true)
+            })
+        })
+        .connect(&env::var("DATABASE_URL")?)
+        .await?;
+
+    let pid: i32 = sqlx::query_scalar("SELECT pg_backend_pid()")
+        .fetch_one(&pool)
+        .await?;
+
+    kill.store(true, Ordering::SeqCst);
+
+    let new_pid: i32 = sqlx::query_scalar("SELECT pg_backend_pid()")
+        .retry_transient(true)
+        .fetch_one(&pool)
+        .await?;
+
+    assert_ne!(pid, new_pid);
+
+    // writes are never retried
+    kill.store(true, Ordering::SeqCst);
+
+    let error = sqlx::query("UPDATE tweet SET text = text WHERE id = 0")
+        .retry_transient(true)
+        .execute(&pool)
+        .await
+        .unwrap_err();
+
+    assert!(error.is_connection_closed(), "{}", error);
+
+    kill.store(true, Ordering::SeqCst);
+
+    let error = sqlx::query("UPDATE tweet SET text = text WHERE id = 0 RETURNING id")
+        .retry_transient(true)
+        .fetch_optional(&pool)
+        .await
+        .unwrap_err();
+
+    assert!(error.is_connection_closed(), "{}", error);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_decodes_catalog_types() -> anyhow::Result<()> {
+    use sqlx::postgres::types::PgOid;
+
+    let mut conn = new::<Postgres>().await?;
+
+    let row =
+        sqlx::query("SELECT oid, oid::regclass, relname FROM pg_class WHERE relname = 'pg_class'")
+            .fetch_one(&mut conn)
+            .await?;
+
+    assert_eq!(row.try_get::<u32, _>(0)?, 1259);
+    assert_eq!(row.try_get::<PgOid, _>(0)?, PgOid(1259));
+    assert_eq!(row.try_get::<PgOid, _>(1)?, PgOid(1259));
+    assert_eq!(row.try_get::<String, _>(2)?, "pg_class");
+
+    // prepared statements return `REGCLASS` as an OID, not as the name of the table
+    assert!(row.try_get::<String, _>(1).is_err());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_encodes_custom_array_issue_1504() -> anyhow::Result<()> {
+    use sqlx::encode::IsNull;
+    use sqlx::postgres::{PgArgumentBuffer, PgTypeInfo};
+    use sqlx::{Decode, Encode, Type, ValueRef};
+
+    #[derive(Debug, PartialEq)]
+    enum Value {
+        String(String),
+        Number(i32),
+        Array(Vec<Value>),
+    }
+
+    impl<'r> Decode<'r, Postgres> for Value {
+        fn decode(
+            value: sqlx::postgres::PgValueRef<'r>,
+        ) -> std::result::Result<Self, Box<dyn std::error::Error + 'static + Send + Sync>> {
+            let typ = value.type_info().into_owned();
+
+            if typ == PgTypeInfo::with_name("text") {
+                let s = <String as Decode<'_, Postgres>>::decode(value)?;
+
+                Ok(Self::String(s))
+            } else if typ == PgTypeInfo::with_name("int4") {
+                let n = <i32 as Decode<'_, Postgres>>::decode(value)?;
+
+                Ok(Self::Number(n))
+            } else if typ == PgTypeInfo::with_name("_text") {
+                let arr = Vec::<String>::decode(value)?;
+                let v = arr.into_iter().map(|s| Value::String(s)).collect();
+
+                Ok(Self::Array(v))
+            } else if typ == PgTypeInfo::with_name("_int4") {
+                let arr = Vec::<i32>::decode(value)?;
+                let v = arr.into_iter().map(|n| Value::Number(n)).collect();
+
+                Ok(Self::Array(v))
+            } else {
+                Err("unknown type".into())
+            }
+        }
+    }
+
+    impl Encode<'_, Postgres> for Value {
+        fn produces(&self) -> Option<PgTypeInfo> {
+            match self {
+                Self::Array(a) => {
+                    if a.len() < 1 {
+                        return Some(PgTypeInfo::with_name("_text"));
+                    }
+
+                    match a[0] {
+                        Self::String(_) => Some(PgTypeInfo::with_name("_text")),
+                        Self::Number(_) => Some(PgTypeInfo::with_name("_int4")),
+                        Self::Array(_) => None,
+                    }
+                }
+                Self::String(_) => Some(PgTypeInfo::with_name("text")),
+                Self::Number(_) => Some(PgTypeInfo::with_name("int4")),
+            }
+        }
+
+        fn encode_by_ref(&self, buf: &mut PgArgumentBuffer) -> IsNull {
+            match self {
+                Value::String(s) => <String as Encode<'_, Postgres>>::encode_by_ref(s, buf),
+                Value::Number(n) => <i32 as Encode<'_, Postgres>>::encode_by_ref(n, buf),
+                Value::Array(arr) => arr.encode(buf),
+            }
+        }
+    }
+
+    impl Type<Postgres> for Value {
+        fn type_info() -> PgTypeInfo {
+            PgTypeInfo::with_name("unknown")
+        }
+
+        fn compatible(ty: &PgTypeInfo) -> bool {
+            [
                 PgTypeInfo::with_name("text"),
                 PgTypeInfo::with_name("_text"),
                 PgTypeInfo::with_name("int4"),
@@ -1445,3 +2442,1691 @@ CREATE TABLE issue_1254 (id INT4 PRIMARY KEY, pairs PAIR[]);
 
     Ok(())
 }
+
+#[sqlx_macros::test]
+async fn it_rejects_use_after_abandoned_operation() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    // the stream is dropped after the first row, before the query finished
+    let mut s = conn.fetch("SELECT generate_series(1, 10)");
+    assert!(s.try_next().await?.is_some());
+    drop(s);
+
+    assert!(conn.is_busy());
+
+    // the next operation must not interleave with what is left of the abandoned one
+    let res = conn.execute("SELECT 1").await;
+
+    assert!(
+        matches!(res, Err(sqlx::Error::ConnectionBusy)),
+        "expected ConnectionBusy, got {:?}",
+        res
+    );
+
+    // an operation that finished leaves the connection usable
+    let mut conn = new::<Postgres>().await?;
+
+    let rows: Vec<PgRow> = conn
+        .fetch("SELECT generate_series(1, 10)")
+        .try_collect()
+        .await?;
+    assert_eq!(rows.len(), 10);
+    assert!(!conn.is_busy());
+
+    conn.execute("SELECT 1").await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_reports_the_server_version() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let version = conn.server_version().unwrap();
+    let version_num: String = sqlx::query_scalar("SHOW server_version_num")
+        .fetch_one(&mut conn)
+        .await?;
+    let version_num: u32 = version_num.parse()?;
+
+    assert_eq!(version.vendor(), ServerVendor::Postgres);
+    assert_eq!(u32::from(version.major()), version_num / 10000);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_reports_the_transaction_status() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    assert_eq!(conn.transaction_status(), TransactionStatus::Idle);
+
+    let mut tx = conn.begin().await?;
+    assert_eq!(tx.transaction_status(), TransactionStatus::InTransaction);
+
+    let savepoint = tx.begin().await?;
+    savepoint.commit().await?;
+    assert_eq!(tx.transaction_status(), TransactionStatus::InTransaction);
+
+    tx.commit().await?;
+    assert_eq!(conn.transaction_status(), TransactionStatus::Idle);
+
+    // a failed statement aborts the transaction until it is rolled back
+    let mut tx = conn.begin().await?;
+    assert!(tx.execute("SELECT 1 / 0").await.is_err());
+    assert_eq!(
+        tx.transaction_status(),
+        TransactionStatus::InFailedTransaction
+    );
+
+    tx.rollback().await?;
+    assert_eq!(conn.transaction_status(), TransactionStatus::Idle);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_set_the_characteristics_of_a_transaction() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let mut tx = conn.begin().await?;
+    tx.set_isolation_level(IsolationLevel::Serializable).await?;
+    tx.set_read_only(true).await?;
+
+    let isolation: String = sqlx::query_scalar("SELECT current_setting('transaction_isolation')")
+        .fetch_one(&mut tx)
+        .await?;
+
+    let read_only: String = sqlx::query_scalar("SELECT current_setting('transaction_read_only')")
+        .fetch_one(&mut tx)
+        .await?;
+
+    assert_eq!(isolation, "serializable");
+    assert_eq!(read_only, "on");
+
+    // too late, now that statements were executed in the transaction
+    let error = tx
+        .set_isolation_level(IsolationLevel::ReadCommitted)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, sqlx::Error::Configuration(_)), "{}", error);
+
+    tx.rollback().await?;
+
+    // the next transaction starts out with the defaults again
+    let mut tx = conn.begin().await?;
+
+    let isolation: String = sqlx::query_scalar("SELECT current_setting('transaction_isolation')")
+        .fetch_one(&mut tx)
+        .await?;
+
+    assert_eq!(isolation, "read committed");
+
+    // neither can the characteristics of a savepoint be set
+    let mut savepoint = tx.begin().await?;
+
+    let error = savepoint.set_read_only(true).await.unwrap_err();
+    assert!(matches!(error, sqlx::Error::Configuration(_)), "{}", error);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_can_commit_prepared_transactions() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+    let mut other = new::<Postgres>().await?;
+
+    conn.execute("CREATE TABLE IF NOT EXISTS _sqlx_2pc (id INTEGER)")
+        .await?;
+    conn.execute("DELETE FROM _sqlx_2pc WHERE id = 2001")
+        .await?;
+
+    let mut tx = conn.begin().await?;
+
+    sqlx::query("INSERT INTO _sqlx_2pc (id) VALUES (2001)")
+        .execute(&mut tx)
+        .await?;
+
+    tx.prepare_two_phase("sqlx-it's-prepared").await?;
+
+    // the row is not visible until the prepared transaction is committed
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM _sqlx_2pc WHERE id = 2001")
+        .fetch_one(&mut other)
+        .await?;
+
+    assert_eq!(count, 0);
+
+    other.commit_prepared("sqlx-it's-prepared").await?;
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM _sqlx_2pc WHERE id = 2001")
+        .fetch_one(&mut other)
+        .await?;
+
+    assert_eq!(count, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_does_not_rollback_prepared_transactions_on_drop() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    {
+        let tx = conn.begin().await?;
+        tx.prepare_two_phase("sqlx-dropped").await?;
+    }
+
+    // no ROLLBACK was queued; the connection is no longer in a transaction
+    // and the prepared transaction is still there to be finished
+    let count: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM pg_prepared_xacts WHERE gid = 'sqlx-dropped'")
+            .fetch_one(&mut conn)
+            .await?;
+
+    assert_eq!(count, 1);
+
+    conn.rollback_prepared("sqlx-dropped").await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn test_listener_unlisten_and_reconnect() -> anyhow::Result<()> {
+    #[cfg(any(feature = "_rt-tokio", feature = "_rt-actix"))]
+    use tokio::time::timeout;
+
+    #[cfg(feature = "_rt-async-std")]
+    use async_std::future::timeout;
+
+    use sqlx::postgres::{PgListener, MAX_NOTIFY_PAYLOAD_LEN};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let mut notify_conn = new::<Postgres>().await?;
+
+    let mut listener = PgListener::connect(&env::var("DATABASE_URL")?).await?;
+
+    let reconnects = Arc::new(AtomicUsize::new(0));
+    listener.on_reconnect({
+        let reconnects = reconnects.clone();
+        move || {
+            reconnects.fetch_add(1, Ordering::SeqCst);
+        }
+    });
+
+    // requires identifier quoting
+    listener.listen("Mixed \"Case\" Chan").await?;
+    listener.listen("other_chan").await?;
+
+    async fn try_recv(listener: &mut PgListener) -> anyhow::Result<Option<String>> {
+        match timeout(Duration::from_millis(250), listener.recv()).await {
+            Ok(res) => Ok(Some(res?.payload().to_owned())),
+            Err(_) => Ok(None),
+        }
+    }
+
+    // a payload right below the limit is delivered intact
+    let payload = "x".repeat(MAX_NOTIFY_PAYLOAD_LEN - 1);
+    notify_conn.notify("Mixed \"Case\" Chan", &payload).await?;
+    assert_eq!(try_recv(&mut listener).await?.as_deref(), Some(&*payload));
+
+    // and one at the limit is rejected before it is sent
+    assert!(notify_conn
+        .notify("Mixed \"Case\" Chan", &"x".repeat(MAX_NOTIFY_PAYLOAD_LEN))
+        .await
+        .is_err());
+
+    // unlisten stops delivery
+    listener.unlisten("other_chan").await?;
+    notify_conn.notify("other_chan", "dropped").await?;
+    assert_eq!(try_recv(&mut listener).await?, None);
+
+    // kill the listener connection out from under it
+    let pid: i32 = sqlx::query_scalar("SELECT pg_backend_pid()")
+        .fetch_one(&mut listener)
+        .await?;
+
+    sqlx::query("SELECT pg_terminate_backend($1)")
+        .bind(pid)
+        .execute(&mut notify_conn)
+        .await?;
+
+    // the lost connection is reported once
+    assert!(listener.try_recv().await?.is_none());
+
+    // the next receive reconnects and re-subscribes only the remaining channel
+    assert_eq!(try_recv(&mut listener).await?, None);
+
+    notify_conn.notify("other_chan", "still dropped").await?;
+    notify_conn.notify("Mixed \"Case\" Chan", "after").await?;
+    assert_eq!(try_recv(&mut listener).await?.as_deref(), Some("after"));
+
+    assert_eq!(reconnects.load(Ordering::SeqCst), 1);
+    assert_eq!(
+        listener.channels().collect::<Vec<_>>(),
+        ["Mixed \"Case\" Chan"]
+    );
+
+    Ok(())
+}
+
+#[cfg(feature = "export")]
+#[sqlx_macros::test]
+async fn it_exports_csv() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+    let mut out = Vec::new();
+
+    sqlx::export::csv(
+        sqlx::query(
+            "SELECT * FROM (VALUES \
+             (1, 'a, \"quoted\" name', '\\xdeadbeef'::bytea), \
+             (2, NULL, NULL)) t(id, name, data)",
+        ),
+        &mut conn,
+        &mut out,
+    )
+    .await?;
+
+    assert_eq!(
+        String::from_utf8(out)?,
+        "id,name,data\r\n1,\"a, \"\"quoted\"\" name\",3q2+7w==\r\n2,,\r\n"
+    );
+
+    Ok(())
+}
+
+#[cfg(all(feature = "export", feature = "chrono"))]
+#[sqlx_macros::test]
+async fn it_exports_timestamps_as_csv() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+    let mut out = Vec::new();
+
+    sqlx::export::csv(
+        sqlx::query(
+            "SELECT TIMESTAMPTZ '2022-05-04 12:30:00.5+02' AS at_utc, \
+             TIMESTAMP '2022-05-04 12:30:00.5' AS at_local, DATE '2022-05-04' AS on_day, \
+             TIME '12:30:00' AS at_time",
+        ),
+        &mut conn,
+        &mut out,
+    )
+    .await?;
+
+    assert_eq!(
+        String::from_utf8(out)?,
+        "at_utc,at_local,on_day,at_time\r\n\
+         2022-05-04T10:30:00.500+00:00,2022-05-04T12:30:00.500,2022-05-04,12:30:00\r\n"
+    );
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_leaves_no_prepared_statements_with_simple_protocol() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let options = env::var("DATABASE_URL")?
+        .parse::<PgConnectOptions>()?
+        .prefer_simple_protocol(true);
+
+    let mut conn = PgConnection::connect_with(&options).await?;
+
+    for i in 0..3_i32 {
+        let value: i32 = sqlx::query_scalar("SELECT $1 + 1")
+            .bind(i)
+            .fetch_one(&mut conn)
+            .await?;
+
+        assert_eq!(value, i + 1);
+
+        let value: i32 = sqlx::query_scalar("SELECT 10").fetch_one(&mut conn).await?;
+
+        assert_eq!(value, 10);
+    }
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM pg_prepared_statements")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 0);
+
+    Ok(())
+}
+
+#[cfg(feature = "introspect")]
+#[sqlx_macros::test]
+async fn it_introspects_tables_and_columns() -> anyhow::Result<()> {
+    use sqlx::introspect;
+
+    let mut conn = new::<Postgres>().await?;
+    let mut tx = conn.begin().await?;
+
+    tx.execute(
+        r#"
+CREATE TABLE "Introspect_Me" (
+    id         BIGSERIAL PRIMARY KEY,
+    name       TEXT NOT NULL DEFAULT 'nobody',
+    score      REAL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+        "#,
+    )
+    .await?;
+
+    let tables = introspect::tables(&mut *tx, None).await?;
+
+    assert!(tables
+        .iter()
+        .any(|table| table.schema == "public" && table.name == "Introspect_Me"));
+
+    // an unquoted name is folded to lowercase
+    assert!(introspect::columns(&mut *tx, "Introspect_Me")
+        .await?
+        .is_empty());
+
+    let columns = introspect::columns(&mut *tx, r#"public."Introspect_Me""#).await?;
+
+    let names: Vec<_> = columns.iter().map(|c| &*c.name).collect();
+    assert_eq!(names, ["id", "name", "score", "created_at"]);
+
+    assert_eq!(columns[0].type_info.name(), "INT8");
+    assert_eq!(columns[1].type_info.name(), "TEXT");
+    assert_eq!(columns[2].type_info.name(), "FLOAT4");
+    assert_eq!(columns[3].type_info.name(), "TIMESTAMPTZ");
+
+    assert!(!columns[0].nullable);
+    assert!(!columns[1].nullable);
+    assert!(columns[2].nullable);
+
+    assert!(columns[0].is_primary_key);
+    assert!(!columns[1].is_primary_key);
+
+    assert_eq!(columns[1].default.as_deref(), Some("'nobody'::text"));
+    assert_eq!(columns[2].default, None);
+
+    // let the transaction rollback so we don't actually create the table
+
+    Ok(())
+}
+
+#[cfg(feature = "introspect")]
+#[sqlx_macros::test]
+async fn it_generates_structs_for_a_schema() -> anyhow::Result<()> {
+    use sqlx::codegen::{self, CodegenOptions};
+
+    let mut conn = new::<Postgres>().await?;
+    let mut tx = conn.begin().await?;
+
+    tx.execute(
+        r#"
+CREATE SCHEMA codegen_me;
+
+CREATE TABLE codegen_me.users (
+    id            BIGINT PRIMARY KEY,
+    "displayName" TEXT,
+    type          TEXT NOT NULL,
+    score         REAL
+);
+
+CREATE TABLE codegen_me.accounts (
+    id      INT4 PRIMARY KEY,
+    balance INT8 NOT NULL
+);
+        "#,
+    )
+    .await?;
+
+    let source =
+        codegen::structs_for_schema(&mut *tx, Some("codegen_me"), &CodegenOptions::new()).await?;
+
+    assert_eq!(
+        source,
+        r#"// Generated by `sqlx::codegen::structs_for_schema`; do not edit by hand.
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct Accounts {
+    pub balance: i64,
+    pub id: i32,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct Users {
+    #[sqlx(rename = "displayName")]
+    pub display_name: Option<String>,
+    pub id: i64,
+    pub score: Option<f32>,
+    pub r#type: String,
+}
+"#
+    );
+
+    // the generated code compiles
+    let path = std::env::temp_dir().join("sqlx-codegen-postgres.rs");
+    std::fs::write(&path, source + "\nfn main() {}\n")?;
+
+    trybuild::TestCases::new().pass(&path);
+
+    // let the transaction rollback so we don't actually create the schema
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_binds_raw_values() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    // 23 is the OID of INT4
+    let int4 = PgTypeInfo::with_oid(23);
+
+    let (value, null): (i32, Option<i32>) = sqlx::query_as("SELECT $1, $2")
+        .bind_raw(int4.clone(), Some(&42_i32.to_be_bytes()))
+        .bind_raw(int4, None)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(value, 42);
+    assert_eq!(null, None);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn pool_discards_connections_closed_by_the_server() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let pool = PgPoolOptions::new()
+        .max_connections(1)
+        .test_before_acquire(false)
+        .connect(&env::var("DATABASE_URL")?)
+        .await?;
+
+    let mut conn = pool.acquire().await?;
+
+    let pid: i32 = sqlx::query_scalar("SELECT pg_backend_pid()")
+        .fetch_one(&mut conn)
+        .await?;
+
+    let mut other = new::<Postgres>().await?;
+
+    let terminated: bool = sqlx::query_scalar("SELECT pg_terminate_backend($1)")
+        .bind(pid)
+        .fetch_one(&mut other)
+        .await?;
+
+    assert!(terminated);
+
+    let error = conn.execute("SELECT 1").await.unwrap_err();
+    assert!(error.is_connection_closed(), "{}", error);
+
+    drop(conn);
+
+    // with a single connection, this waits for the closed one to be discarded
+    let new_pid: i32 = sqlx::query_scalar("SELECT pg_backend_pid()")
+        .fetch_one(&pool)
+        .await?;
+
+    assert_ne!(pid, new_pid);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_cancels_queries_over_their_server_timeout() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let default: String = sqlx::query_scalar("SHOW statement_timeout")
+        .fetch_one(&mut conn)
+        .await?;
+
+    let err = sqlx::query("SELECT pg_sleep(10)")
+        .server_timeout(Duration::from_millis(100))
+        .execute(&mut conn)
+        .await
+        .unwrap_err();
+
+    let err = err.into_database_error().unwrap();
+    assert_eq!(err.code().as_deref(), Some("57014"));
+
+    // the connection is usable and the timeout did not outlive the query
+    let timeout: String = sqlx::query_scalar("SHOW statement_timeout")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(timeout, default);
+
+    let done = sqlx::query("SELECT 1")
+        .server_timeout(Duration::from_secs(10))
+        .execute(&mut conn)
+        .await?;
+
+    assert_eq!(done.rows_affected(), 1);
+
+    let value: i32 = sqlx::query_scalar("SELECT $1::int4")
+        .bind(5_i32)
+        .server_timeout(Duration::from_secs(10))
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(value, 5);
+
+    // in a transaction, the timeout lasts until its end
+    let mut tx = conn.begin().await?;
+
+    sqlx::query("SELECT 1")
+        .server_timeout(Duration::from_secs(5))
+        .execute(&mut tx)
+        .await?;
+
+    let timeout: String = sqlx::query_scalar("SHOW statement_timeout")
+        .fetch_one(&mut tx)
+        .await?;
+
+    assert_eq!(timeout, "5s");
+
+    tx.rollback().await?;
+
+    let timeout: String = sqlx::query_scalar("SHOW statement_timeout")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(timeout, default);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_fetches_all_rows_before_decoding_them() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let sql = "SELECT i, i::text AS t FROM generate_series(1, 5000) i";
+
+    let streamed: Vec<PgRow> = conn.fetch(sql).try_collect().await?;
+    let buffered = conn.fetch_all_concrete(sql).await?;
+
+    assert_eq!(buffered.len(), 5000);
+    assert_eq!(streamed.len(), buffered.len());
+
+    for (i, row) in buffered.iter().enumerate() {
+        assert_eq!(row.get::<i32, _>(0), i as i32 + 1);
+        assert_eq!(row.get::<String, _>("t"), (i + 1).to_string());
+    }
+
+    // every result set keeps its own columns
+    let rows = conn
+        .fetch_all("SELECT 1::int4 AS a; SELECT 'b'::text AS b, 2::int8 AS c")
+        .await?;
+
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].get::<i32, _>("a"), 1);
+    assert_eq!(rows[1].get::<String, _>("b"), "b");
+    assert_eq!(rows[1].get::<i64, _>("c"), 2);
+
+    let rows = sqlx::query("SELECT $1::int4 + i FROM generate_series(1, 3) i")
+        .bind(10_i32)
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(rows.len(), 3);
+    assert_eq!(rows[2].get::<i32, _>(0), 13);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_returns_errors_in_the_middle_of_fetch_all() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    // the division fails after some rows have been sent
+    let err = sqlx::query("SELECT 1 / (100 - i) FROM generate_series(1, 200) i")
+        .fetch_all(&mut conn)
+        .await;
+
+    let err = err.unwrap_err().into_database_error().unwrap();
+    assert_eq!(err.code().as_deref(), Some("22012"));
+
+    let err = conn
+        .fetch_all("SELECT 1; SELECT 1 / 0")
+        .await
+        .unwrap_err()
+        .into_database_error()
+        .unwrap();
+
+    assert_eq!(err.code().as_deref(), Some("22012"));
+
+    // the rest of the response is discarded
+    let value: i32 = sqlx::query_scalar("SELECT 5").fetch_one(&mut conn).await?;
+    assert_eq!(value, 5);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_binds_values_with_an_explicit_type() -> anyhow::Result<()> {
+    use sqlx::postgres::types::PgOid;
+
+    let mut conn = new::<Postgres>().await?;
+
+    // the server cannot infer the type of `$1` from the query alone
+    let is_null: bool = sqlx::query_scalar("SELECT $1 IS NULL")
+        .bind_typed(Option::<i64>::None, PgTypeInfo::with_name("int8"))
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert!(is_null);
+
+    let (ty, name): (String, String) = sqlx::query_as("SELECT pg_typeof($1)::text, $1::text")
+        .bind_typed(PgOid(23), PgTypeInfo::with_name("regtype"))
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(ty, "regtype");
+    assert_eq!(name, "integer");
+
+    // a type the value cannot be decoded as is reported by the server
+    let res = sqlx::query("SELECT $1")
+        .bind_typed(5_i32, PgTypeInfo::with_name("int8"))
+        .execute(&mut conn)
+        .await;
+
+    assert!(res.unwrap_err().into_database_error().is_some());
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_binds_limits_and_offsets() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let sql = "SELECT n FROM generate_series(1, 3) n WHERE n > $1 ORDER BY n LIMIT $2 OFFSET $3";
+
+    for (limit, offset, expected) in [
+        (0, 0, &[][..]),
+        (1, 0, &[1][..]),
+        (1, 1, &[2][..]),
+        (u64::from(u32::MAX), 1, &[2, 3][..]),
+        (u64::MAX, 0, &[1, 2, 3][..]),
+        (1, u64::from(u32::MAX), &[][..]),
+    ] {
+        let found: Vec<i32> = sqlx::query_scalar(sql)
+            .bind(0_i32)
+            .bind_limit(limit)
+            .bind_offset(offset)
+            .fetch_all(&mut conn)
+            .await?;
+
+        assert_eq!(found, expected, "LIMIT {} OFFSET {}", limit, offset);
+    }
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_keeps_owned_rows_after_the_connection_is_gone() -> anyhow::Result<()> {
+    use sqlx::{FromRow, OwnedRow};
+
+    #[derive(sqlx::FromRow)]
+    struct Record {
+        id: i64,
+        name: String,
+        tags: Vec<String>,
+        note: Option<String>,
+    }
+
+    let sql = "SELECT 7::int8 AS id, 'seven' AS name, ARRAY['a', 'b'] AS tags, NULL::text AS note";
+
+    let mut conn = new::<Postgres>().await?;
+
+    // prepared, so the values are in the binary format
+    let row = sqlx::query(sql).fetch_one(&mut conn).await?;
+    let binary = row.to_owned();
+    drop(row);
+
+    // fetched without arguments, so the values are in the text format
+    let text: Vec<OwnedRow<Postgres>> = conn
+        .fetch_all(sql)
+        .await?
+        .into_iter()
+        .map(OwnedRow::from)
+        .collect();
+
+    // or fetched as owned rows directly
+    let fetched: OwnedRow<Postgres> = sqlx::query_as(sql).fetch_one(&mut conn).await?;
+
+    conn.close().await?;
+
+    for row in vec![binary, text[0].clone(), fetched] {
+        let row = std::thread::spawn(move || row).join().unwrap();
+
+        assert_eq!(row.len(), 4);
+        assert_eq!(row.column(1).name(), "name");
+        assert_eq!(row.try_get::<i64, _>("id")?, 7);
+        assert_eq!(row.try_get::<String, _>(1)?, "seven");
+        assert_eq!(row.try_get::<Option<String>, _>("note")?, None);
+        assert!(matches!(
+            row.try_get::<i64, _>("missing"),
+            Err(sqlx::Error::ColumnNotFound(_))
+        ));
+
+        let record = Record::from_row(&row)?;
+
+        assert_eq!(record.id, 7);
+        assert_eq!(record.name, "seven");
+        assert_eq!(record.tags, vec!["a", "b"]);
+        assert_eq!(record.note, None);
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "offline")]
+#[sqlx_macros::test]
+async fn it_serializes_owned_rows() -> anyhow::Result<()> {
+    use sqlx::OwnedRow;
+
+    let mut conn = new::<Postgres>().await?;
+
+    let row: OwnedRow<Postgres> = sqlx::query_as("SELECT 7::int8 AS id, 'seven'::text AS name")
+        .fetch_one(&mut conn)
+        .await?;
+
+    let json = serde_json::to_string(&row)?;
+    let row: OwnedRow<Postgres> = serde_json::from_str(&json)?;
+
+    assert_eq!(row.try_get::<i64, _>("id")?, 7);
+    assert_eq!(row.try_get::<String, _>("name")?, "seven");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_falls_back_to_the_next_host() -> anyhow::Result<()> {
+    setup_if_needed();
+
+    let mut url = url::Url::parse(&env::var("DATABASE_URL")?)?;
+    let host = format!(
+        "{}:{}",
+        url.host_str().unwrap_or("localhost"),
+        url.port().unwrap_or(5432)
+    );
+
+    // nothing listens on port 1, so the connection to the first host is refused
+    url.set_host(Some("127.0.0.1"))?;
+    url.set_port(Some(1)).unwrap();
+    let url = url
+        .as_str()
+        .replacen("127.0.0.1:1", &format!("127.0.0.1:1,{}", host), 1);
+
+    let mut conn = PgConnection::connect(&url).await?;
+    let value: i32 = conn.fetch_one("SELECT 1").await?.try_get(0)?;
+
+    assert_eq!(value, 1);
+
+    // without a host to fall back to, the error lists why each host failed
+    let url = url.replacen(&host, "127.0.0.1:2", 1);
+    let error = PgConnection::connect(&url).await.unwrap_err().to_string();
+
+    assert!(error.contains("failed to connect to any host (127.0.0.1:1: "));
+    assert!(error.contains("; 127.0.0.1:2: "));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_checks_the_number_of_bound_values() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    let error = sqlx::query("SELECT $1::int4, $2::int4")
+        .bind(1_i32)
+        .execute(&mut conn)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        sqlx::Error::ParameterCountMismatch {
+            expected: 2,
+            got: 1
+        }
+    ));
+
+    let error = sqlx::query("SELECT $1::int4")
+        .bind(1_i32)
+        .bind(2_i32)
+        .execute(&mut conn)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        sqlx::Error::ParameterCountMismatch {
+            expected: 1,
+            got: 2
+        }
+    ));
+
+    // the statement is not prepared with the types of the extra values, so binding the right
+    // number of values afterwards still works
+    let value: i32 = sqlx::query_scalar("SELECT $1::int4")
+        .bind(1_i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(value, 1);
+
+    // a prepared statement is checked against its own parameters
+    let statement = conn.prepare("SELECT $1::int4 + $2::int4").await?;
+
+    let error = statement
+        .query()
+        .bind(1_i32)
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        sqlx::Error::ParameterCountMismatch {
+            expected: 2,
+            got: 1
+        }
+    ));
+
+    let value: i32 = statement
+        .query_scalar()
+        .bind(1_i32)
+        .bind(2_i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(value, 3);
+
+    // placeholders in string literals are not parameters
+    let value: String = sqlx::query_scalar("SELECT '$2' || $1")
+        .bind("!")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(value, "$2!");
+
+    // a placeholder used twice is one parameter, and one skipped is a parameter still
+    let value: i32 = sqlx::query_scalar("SELECT $1::int4 + $1::int4")
+        .bind(2_i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(value, 4);
+
+    let error = sqlx::query("SELECT $2::int4")
+        .bind(1_i32)
+        .execute(&mut conn)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        sqlx::Error::ParameterCountMismatch {
+            expected: 2,
+            got: 1
+        }
+    ));
+
+    // the connection is still usable after the errors
+    let value: i32 = conn.fetch_one("SELECT 1").await?.try_get(0)?;
+    assert_eq!(value, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_returns_the_id_of_an_inserted_row() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE people (id BIGSERIAL PRIMARY KEY, name TEXT)")
+        .await?;
+
+    let first: i64 = sqlx::query_scalar("INSERT INTO people (name) VALUES ($1) RETURNING id")
+        .bind("Herp Derpinson")
+        .fetch_one(&mut conn)
+        .await?;
+
+    let second: i64 = sqlx::query_scalar("INSERT INTO people (name) VALUES ($1) RETURNING id")
+        .bind("Derp Herpinson")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(second, first + 1);
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM people")
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!(count, 2);
+
+    // a scalar is decoded from a row with a single column
+    let error =
+        sqlx::query_scalar::<_, i64>("INSERT INTO people (name) VALUES ($1) RETURNING id, name")
+            .bind("Herp Derpinson")
+            .fetch_one(&mut conn)
+            .await
+            .unwrap_err();
+
+    assert!(matches!(error, sqlx::Error::Decode(_)));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_opens_new_connections_with_replaced_options() -> anyhow::Result<()> {
+    let options: PgConnectOptions = env::var("DATABASE_URL")?.parse()?;
+
+    let pool = PgPoolOptions::new()
+        .max_connections(2)
+        .connect_timeout(Duration::from_secs(5))
+        .connect_with(options.clone())
+        .await?;
+
+    let mut conn = pool.acquire().await?;
+
+    // a rotated password is only used by connections opened afterwards
+    pool.set_connect_options(options.clone().password("not the password"));
+
+    let error = pool.acquire().await.unwrap_err();
+
+    assert!(error.as_database_error().is_some(), "{:?}", error);
+
+    let value: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+
+    assert_eq!(value, 1);
+
+    pool.set_connect_options(options);
+
+    let mut other = pool.acquire().await?;
+
+    let value: i32 = sqlx::query_scalar("SELECT 2").fetch_one(&mut other).await?;
+
+    assert_eq!(value, 2);
+    assert_eq!(pool.size(), 2);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_reads_the_other_columns_of_a_row_with_an_unsupported_type() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    #[derive(sqlx::FromRow)]
+    struct Place {
+        id: i32,
+        name: String,
+    }
+
+    // no Rust type decodes `point` or `tsvector`
+    let sql = "SELECT 1 AS id, '(0,0)'::point AS location, 'origin' AS name, \
+               'a fat cat'::tsvector AS words";
+
+    let row = sqlx::query(sql).fetch_one(&mut conn).await?;
+
+    assert_eq!(row.try_get::<i32, _>("id")?, 1);
+    assert_eq!(row.try_get::<String, _>("name")?, "origin");
+
+    let error = row.try_get::<String, _>("location").unwrap_err();
+
+    assert!(
+        matches!(error, sqlx::Error::ColumnDecode { .. }),
+        "{:?}",
+        error
+    );
+    assert!(error.to_string().contains("POINT"), "{}", error);
+
+    let place: Place = sqlx::query_as(sql).fetch_one(&mut conn).await?;
+
+    assert_eq!(place.id, 1);
+    assert_eq!(place.name, "origin");
+
+    // the columns of a query that is not prepared have only the oid of a type that is not
+    // builtin, as the type of `state` was not looked up by this connection
+    let sql = "SELECT 1 AS id, 'open'::status AS state, 'origin' AS name";
+    let row = conn.fetch_one(sql).await?;
+
+    assert_eq!(row.try_get::<i32, _>("id")?, 1);
+    assert_eq!(row.try_get::<String, _>("name")?, "origin");
+
+    for error in [
+        row.try_get::<String, _>("state").unwrap_err(),
+        row.try_get::<Vec<String>, _>("state").unwrap_err(),
+    ] {
+        assert!(
+            matches!(error, sqlx::Error::ColumnDecode { .. }),
+            "{:?}",
+            error
+        );
+    }
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_shares_statement_metadata_between_connections() -> anyhow::Result<()> {
+    let db = TestDb::<Postgres>::from_env().await?;
+    let pool = PgPoolOptions::new()
+        .max_connections(2)
+        .connect(db.url())
+        .await?;
+
+    let mut a = pool.acquire().await?;
+    let mut b = pool.acquire().await?;
+
+    a.execute(
+        r#"
+CREATE TABLE shared_statements (id INT4);
+INSERT INTO shared_statements (id) VALUES (1);
+    "#,
+    )
+    .await?;
+
+    let sql = "SELECT * FROM shared_statements";
+
+    let (a_before, b_before) = (a.stats(), b.stats());
+
+    sqlx::query(sql).fetch_all(&mut a).await?;
+    sqlx::query(sql).fetch_all(&mut a).await?;
+    sqlx::query(sql).fetch_all(&mut b).await?;
+
+    let (a_after, b_after) = (a.stats(), b.stats());
+
+    // prepared once on each connection, the second time described the same way as the first
+    assert_eq!(
+        a_after.statement_cache_misses() - a_before.statement_cache_misses(),
+        1
+    );
+    assert_eq!(
+        a_after.statement_cache_hits() - a_before.statement_cache_hits(),
+        1
+    );
+    assert_eq!(
+        a_after.shared_statement_misses() - a_before.shared_statement_misses(),
+        1
+    );
+    assert_eq!(
+        b_after.statement_cache_misses() - b_before.statement_cache_misses(),
+        1
+    );
+    assert_eq!(
+        b_after.shared_statement_hits() - b_before.shared_statement_hits(),
+        1
+    );
+
+    a.execute("ALTER TABLE shared_statements ADD COLUMN name TEXT DEFAULT 'one'")
+        .await?;
+
+    for conn in vec![&mut a, &mut b] {
+        // the statements prepared before the change can no longer be executed
+        let error = sqlx::query(sql).fetch_all(&mut *conn).await.unwrap_err();
+
+        assert_eq!(
+            error.as_database_error().and_then(|e| e.code()).as_deref(),
+            Some("0A000")
+        );
+
+        // but are prepared again, and not with the metadata from before the change
+        let row = sqlx::query(sql).fetch_one(&mut *conn).await?;
+
+        assert_eq!(row.len(), 2);
+        assert_eq!(row.try_get::<String, _>("name")?, "one");
+    }
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_binds_named_parameters() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    // a repeated name is one parameter; casts, literals and comments are left alone
+    let row = sqlx::query_named(
+        "SELECT :a::int4, :b || ':a', :a::int4 + 1, $$ :c $$, E'\\':c' /* :c */ -- :c",
+    )
+    .bind_named("a", 10_i32)
+    .bind_named("b", "x")
+    .fetch_one(&mut conn)
+    .await?;
+
+    assert_eq!(row.try_get::<i32, _>(0)?, 10);
+    assert_eq!(row.try_get::<String, _>(1)?, "x:a");
+    assert_eq!(row.try_get::<i32, _>(2)?, 11);
+    assert_eq!(row.try_get::<String, _>(3)?, " :c ");
+    assert_eq!(row.try_get::<String, _>(4)?, "':c");
+
+    // the names are bound in any order, through any of the query types
+    let (a, b): (i32, i32) = sqlx::query_as("SELECT :a::int4, :b::int4")
+        .bind_named("b", 2_i32)
+        .bind_named("a", 1_i32)
+        .fetch_one(&mut conn)
+        .await?;
+
+    assert_eq!((a, b), (1, 2));
+
+    let error = sqlx::query_named("SELECT :a::int4, :b::int4")
+        .bind_named("a", 1_i32)
+        .bind_named("c", 3_i32)
+        .execute(&mut conn)
+        .await
+        .unwrap_err();
+
+    match error {
+        sqlx::Error::NamedParameterMismatch { missing, extra } => {
+            assert_eq!(missing, vec!["b"]);
+            assert_eq!(extra, vec!["c"]);
+        }
+
+        error => panic!("unexpected error: {}", error),
+    }
+
+    let error = sqlx::query_named("SELECT :a::int4")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, sqlx::Error::NamedParameterMismatch { .. }));
+
+    // nothing was sent for the queries that failed
+    let value: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+
+    assert_eq!(value, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_fetches_raw_frames() -> anyhow::Result<()> {
+    use sqlx::wire::RawFrame;
+
+    let mut conn = new::<Postgres>().await?;
+
+    for _ in 0..2 {
+        // the second time around, the statement is prepared and cached
+        let frames: Vec<RawFrame<Postgres>> = conn
+            .fetch_raw_frames(sqlx::query("SELECT generate_series(1, $1)").bind(3_i32))
+            .try_collect()
+            .await?;
+
+        assert_eq!(frames.len(), 5);
+
+        for frame in &frames[..4] {
+            let (tag, bytes) = match frame {
+                RawFrame::Metadata(bytes) => (b'T', bytes),
+                RawFrame::Row(bytes) => (b'D', bytes),
+                RawFrame::Done(_) => panic!("unexpected end of the result set"),
+            };
+
+            assert_eq!(bytes[0], tag);
+            assert_eq!(
+                u32::from_be_bytes([bytes[1], bytes[2], bytes[3], bytes[4]]) as usize,
+                bytes.len() - 1
+            );
+        }
+
+        match &frames[4] {
+            RawFrame::Done(done) => assert_eq!(done.rows_affected(), 3),
+            frame => panic!("unexpected frame: {:?}", frame),
+        }
+    }
+
+    // the connection is left ready after a stream of frames that was not read to the end
+    let mut frames = conn.fetch_raw_frames("SELECT generate_series(1, 1000)");
+    assert!(frames.try_next().await?.is_some());
+    drop(frames);
+
+    let value: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(value, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_executes_prepared_statements_without_parameters() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE nothing_bound (id INTEGER)")
+        .await?;
+
+    // `query` prepares the statements even though no values are bound to them
+    for _ in 0..2 {
+        let done = sqlx::query("INSERT INTO nothing_bound (id) VALUES (1), (2)")
+            .execute(&mut conn)
+            .await?;
+        assert_eq!(done.rows_affected(), 2);
+
+        let value: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+        assert_eq!(value, 1);
+
+        let done = sqlx::query("DELETE FROM nothing_bound")
+            .execute(&mut conn)
+            .await?;
+        assert_eq!(done.rows_affected(), 2);
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "migrate")]
+#[sqlx_macros::test]
+async fn it_reports_the_progress_of_migrations() -> anyhow::Result<()> {
+    use sqlx::migrate::{Migrate, Migration, MigrationEvent, MigrationType, Migrator};
+    use std::borrow::Cow;
+
+    const VERSION: i64 = 19_640_000_000_001;
+
+    let mut conn = new::<Postgres>().await?;
+
+    conn.ensure_migrations_table().await?;
+    sqlx::query("DELETE FROM _sqlx_migrations WHERE version = $1")
+        .bind(VERSION)
+        .execute(&mut conn)
+        .await?;
+
+    // the semicolons of the function body are not the end of a statement
+    let sql = r#"
+CREATE TEMPORARY TABLE progress (n INTEGER);
+
+CREATE FUNCTION pg_temp.add_one(n INTEGER) RETURNS INTEGER AS $$
+BEGIN
+    RETURN n + 1;
+END;
+$$ LANGUAGE plpgsql;
+
+-- two rows
+INSERT INTO progress (n) VALUES (pg_temp.add_one(1)), (pg_temp.add_one(2));
+"#;
+
+    // other migrations were applied to the test database
+    let migrator = Migrator {
+        migrations: Cow::Owned(vec![Migration::new(
+            VERSION,
+            "progress".into(),
+            MigrationType::Simple,
+            sql.into(),
+        )]),
+        ignore_missing: true,
+    };
+
+    let mut events = Vec::new();
+    migrator
+        .run_with_progress(&mut conn, |event| events.push(event))
+        .await?;
+
+    assert_eq!(events.len(), 5);
+    assert!(matches!(
+        events[0],
+        MigrationEvent::MigrationStarted { version: VERSION }
+    ));
+
+    for (i, event) in events[1..4].iter().enumerate() {
+        match event {
+            MigrationEvent::StatementCompleted {
+                index,
+                rows_affected,
+                ..
+            } => {
+                assert_eq!(*index, i);
+                assert_eq!(*rows_affected, if i == 2 { 2 } else { 0 });
+            }
+
+            event => panic!("unexpected event: {:?}", event),
+        }
+    }
+
+    assert!(matches!(
+        events[4],
+        MigrationEvent::MigrationCompleted {
+            version: VERSION,
+            ..
+        }
+    ));
+
+    let sum: i64 = sqlx::query_scalar("SELECT SUM(n) FROM progress")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(sum, 5);
+
+    // an applied migration is not reported again
+    events.clear();
+    migrator
+        .run_with_progress(&mut conn, |event| events.push(event))
+        .await?;
+    assert!(events.is_empty());
+
+    sqlx::query("DELETE FROM _sqlx_migrations WHERE version = $1")
+        .bind(VERSION)
+        .execute(&mut conn)
+        .await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_prepares_statements_again_after_the_schema_changed() -> anyhow::Result<()> {
+    const SELECT: &str = "SELECT * FROM reprepared_rows WHERE id = $1";
+
+    let mut conn = new::<Postgres>().await?;
+    let mut other = new::<Postgres>().await?;
+
+    conn.execute("DROP TABLE IF EXISTS reprepared_rows").await?;
+    conn.execute("CREATE TABLE reprepared_rows (id INTEGER PRIMARY KEY)")
+        .await?;
+    conn.execute("INSERT INTO reprepared_rows (id) VALUES (1)")
+        .await?;
+
+    let row = sqlx::query(SELECT).bind(1_i32).fetch_one(&mut conn).await?;
+    assert_eq!(row.len(), 1);
+
+    // the cached statement can no longer be executed as it was prepared, so it is prepared
+    // again and executed with the same value bound
+    other
+        .execute("ALTER TABLE reprepared_rows ADD COLUMN a TEXT NOT NULL DEFAULT 'a'")
+        .await?;
+
+    let row = sqlx::query(SELECT).bind(1_i32).fetch_one(&mut conn).await?;
+    assert_eq!(row.len(), 2);
+    assert_eq!(row.try_get::<String, _>("a")?, "a");
+
+    other
+        .execute("ALTER TABLE reprepared_rows ADD COLUMN b TEXT NOT NULL DEFAULT 'b'")
+        .await?;
+
+    let rows = sqlx::query(SELECT).bind(1_i32).fetch_all(&mut conn).await?;
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].try_get::<String, _>("b")?, "b");
+
+    other
+        .execute("ALTER TABLE reprepared_rows ADD COLUMN c TEXT NOT NULL DEFAULT 'c'")
+        .await?;
+
+    let rows: Vec<PgRow> = sqlx::query(SELECT)
+        .bind(1_i32)
+        .fetch(&mut conn)
+        .try_collect()
+        .await?;
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].try_get::<String, _>("c")?, "c");
+
+    // a transaction is aborted by the error, so it is returned; the statement is prepared again
+    // the next time
+    other
+        .execute("ALTER TABLE reprepared_rows ADD COLUMN d TEXT NOT NULL DEFAULT 'd'")
+        .await?;
+
+    let mut tx = conn.begin().await?;
+    let error = sqlx::query(SELECT)
+        .bind(1_i32)
+        .fetch_one(&mut tx)
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error.as_database_error().unwrap().code().as_deref(),
+        Some("0A000")
+    );
+    tx.rollback().await?;
+
+    let row = sqlx::query(SELECT).bind(1_i32).fetch_one(&mut conn).await?;
+    assert_eq!(row.try_get::<String, _>("d")?, "d");
+
+    conn.execute("DROP TABLE reprepared_rows").await?;
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_reads_and_binds_text_search_types() -> anyhow::Result<()> {
+    use sqlx::postgres::types::{PgTsQuery, PgTsVector};
+
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute(
+        r#"
+CREATE TEMPORARY TABLE documents (id INTEGER PRIMARY KEY, body TSVECTOR NOT NULL);
+CREATE INDEX documents_body ON documents USING GIN (body);
+        "#,
+    )
+    .await?;
+
+    sqlx::query("INSERT INTO documents (id, body) VALUES (1, to_tsvector('simple', $1))")
+        .bind("a fat cat sat on a mat")
+        .execute(&mut conn)
+        .await?;
+
+    sqlx::query("INSERT INTO documents (id, body) VALUES ($1, $2)")
+        .bind(2_i32)
+        .bind(PgTsVector::new("'fat':2 'rat':3"))
+        .execute(&mut conn)
+        .await?;
+
+    // `SELECT *` reads the TSVECTOR column in text format, the other in binary format
+    let rows = sqlx::query("SELECT * FROM documents ORDER BY id")
+        .fetch_all(&mut conn)
+        .await?;
+
+    assert_eq!(rows[0].try_get::<i32, _>("id")?, 1);
+    assert_eq!(
+        rows[0].try_get::<PgTsVector, _>("body")?.as_str(),
+        "'a':1,6 'cat':3 'fat':2 'mat':7 'on':5 'sat':4"
+    );
+    assert_eq!(rows[1].try_get::<i32, _>("id")?, 2);
+    assert_eq!(
+        rows[1].try_get::<PgTsVector, _>("body")?,
+        PgTsVector::new("'fat':2 'rat':3")
+    );
+
+    let ids: Vec<i32> = sqlx::query_scalar("SELECT id FROM documents WHERE body @@ $1 ORDER BY id")
+        .bind(PgTsQuery::new("fat & (rat | cat)"))
+        .fetch_all(&mut conn)
+        .await?;
+    assert_eq!(ids, [1, 2]);
+
+    let (query, id): (PgTsQuery, i32) = sqlx::query_as("SELECT to_tsquery('simple', $1), $2::int4")
+        .bind("fat & !rat")
+        .bind(1_i32)
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(query.as_str(), "'fat' & !'rat'");
+    assert_eq!(id, 1);
+
+    // the values round-trip, and are read the same without bound values, all in text format
+    let query: PgTsQuery = sqlx::query_scalar("SELECT $1::tsquery")
+        .bind(PgTsQuery::new("'fat' & !'rat'"))
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(query.as_str(), "'fat' & !'rat'");
+
+    let row = conn
+        .fetch_one("SELECT body FROM documents WHERE id = 2")
+        .await?;
+    assert_eq!(row.try_get::<PgTsVector, _>(0)?.as_str(), "'fat':2 'rat':3");
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_reads_and_binds_xml() -> anyhow::Result<()> {
+    use sqlx::postgres::types::PgXml;
+
+    let mut conn = new::<Postgres>().await?;
+
+    conn.execute("CREATE TEMPORARY TABLE invoices (id INTEGER PRIMARY KEY, body XML NOT NULL)")
+        .await?;
+
+    sqlx::query("INSERT INTO invoices (id, body) VALUES ($1, $2)")
+        .bind(1_i32)
+        .bind(PgXml::new(
+            r#"<invoice number="1"><total>9.99</total></invoice>"#,
+        ))
+        .execute(&mut conn)
+        .await?;
+
+    // `SELECT *` reads the XML column in text format, the other in binary format
+    let (id, body): (i32, PgXml) = sqlx::query_as("SELECT * FROM invoices")
+        .fetch_one(&mut conn)
+        .await?;
+    assert_eq!(id, 1);
+    assert_eq!(
+        body.as_str(),
+        r#"<invoice number="1"><total>9.99</total></invoice>"#
+    );
+
+    // Postgres checks that a bound value is well-formed
+    let error = sqlx::query("INSERT INTO invoices (id, body) VALUES ($1, $2)")
+        .bind(2_i32)
+        .bind(PgXml::new("<invoice>"))
+        .execute(&mut conn)
+        .await
+        .unwrap_err();
+    assert!(error.as_database_error().is_some(), "{}", error);
+
+    // a value of several megabytes, as is read without bound values
+    let body: PgXml = conn
+        .fetch_one("SELECT xmlelement(name doc, repeat('x', 20 * 1024 * 1024))")
+        .await?
+        .try_get(0)?;
+    assert_eq!(body.as_str().len(), 20 * 1024 * 1024 + "<doc></doc>".len());
+    assert!(body.as_str().starts_with("<doc>xxx"));
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_reports_the_progress_of_a_fetch() -> anyhow::Result<()> {
+    use sqlx::query::{FetchProgress, ProgressOptions};
+
+    let mut conn = new::<Postgres>().await?;
+
+    let sql = "SELECT n, md5(n::text) AS hash FROM generate_series(1, $1) AS n ORDER BY n";
+
+    let expected: Vec<(i32, String)> = sqlx::query_as(sql)
+        .bind(50_000_i32)
+        .fetch_all(&mut conn)
+        .await?;
+
+    let mut reports: Vec<FetchProgress> = Vec::new();
+    let rows: Vec<PgRow> = sqlx::query(sql)
+        .bind(50_000_i32)
+        .fetch_with_progress(
+            &mut conn,
+            ProgressOptions::new()
+                .every_rows(1000)
+                .every(Duration::from_secs(3600))
+                .estimated_rows(50_000),
+            |p| reports.push(p.clone()),
+        )
+        .try_collect()
+        .await?;
+
+    let fetched: Vec<(i32, String)> = rows
+        .iter()
+        .map(|row| Ok((row.try_get(0)?, row.try_get(1)?)))
+        .collect::<sqlx::Result<_>>()?;
+
+    assert_eq!(fetched, expected);
+
+    // every 1000 rows, then once more when done
+    assert_eq!(reports.len(), 51);
+
+    for (i, p) in reports[..50].iter().enumerate() {
+        assert_eq!(p.rows, (i as u64 + 1) * 1000);
+        assert!(!p.done);
+    }
+
+    for w in reports.windows(2) {
+        assert!(w[0].bytes <= w[1].bytes && w[0].elapsed <= w[1].elapsed);
+    }
+
+    let last = reports.last().unwrap();
+    assert!(last.done);
+    assert_eq!(last.rows, 50_000);
+    assert!(last.bytes > 50_000 * 32);
+    assert_eq!(last.fraction(), Some(1.0));
+
+    let middle = reports.iter().find(|p| p.rows == 25_000).unwrap();
+    assert_eq!(middle.fraction(), Some(0.5));
+
+    // the rows are counted first in the same snapshot
+    let mut tx = conn.begin().await?;
+    tx.execute("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ")
+        .await?;
+
+    let mut estimates = Vec::new();
+    let rows: Vec<PgRow> = sqlx::query(sql)
+        .bind(1234_i32)
+        .fetch_with_progress_counted(
+            &mut tx,
+            ProgressOptions::new()
+                .every_rows(100)
+                .every(Duration::from_secs(3600)),
+            |p| estimates.push((p.estimated_rows, p.rows)),
+        )
+        .try_collect()
+        .await?;
+
+    tx.commit().await?;
+
+    assert_eq!(rows.len(), 1234);
+    assert_eq!(estimates.len(), 13);
+    assert!(estimates
+        .iter()
+        .all(|(estimate, _)| *estimate == Some(1234)));
+    assert_eq!(estimates.last(), Some(&(Some(1234), 1234)));
+
+    // the connection is usable after both
+    let one: i32 = sqlx::query_scalar("SELECT 1").fetch_one(&mut conn).await?;
+    assert_eq!(one, 1);
+
+    Ok(())
+}
+
+#[sqlx_macros::test]
+async fn it_reads_every_column_as_text_with_text_results() -> anyhow::Result<()> {
+    let mut conn = new::<Postgres>().await?;
+
+    // `mood` is an enum of setup.sql; a `POINT` has no `Decode` implementation
+    const SELECT: &str = "SELECT TIMESTAMP '2022-05-04 12:30:00' AS at, 12.50::NUMERIC AS amount, \
+                          'happy'::mood AS mood, point(1, 2) AS location, $1 AS id";
+
+    let rows = sqlx::query(SELECT)
+        .bind(7_i32)
+        .text_results(true)
+        .fetch_all(&mut conn)
+        .await?;
+
+    let values = (0..rows[0].len())
+        .map(|index| rows[0].try_get::<String, _>(index))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    assert_eq!(
+        values,
+        ["2022-05-04 12:30:00", "12.50", "happy", "(1,2)", "7"]
+    );
+
+    // the columns keep their types, and values can still be decoded from their text
+    assert_eq!(rows[0].column("at").type_info().name(), "TIMESTAMP");
+    assert_eq!(rows[0].try_get::<i32, _>("id")?, 7);
+
+    let rows: Vec<PgRow> = sqlx::query(SELECT)
+        .bind(7_i32)
+        .text_results(true)
+        .fetch(&mut conn)
+        .try_collect()
+        .await?;
+
+    assert_eq!(rows[0].try_get::<String, _>("amount")?, "12.50");
+
+    // as can those of a copy of the row
+    let row = sqlx::OwnedRow::from(&rows[0]);
+
+    assert_eq!(row.try_get::<String, _>("amount")?, "12.50");
+    assert_eq!(row.try_get::<i32, _>("id")?, 7);
+
+    // otherwise the rows of a query with bound values are in binary format
+    let row = sqlx::query(SELECT).bind(7_i32).fetch_one(&mut conn).await?;
+    assert!(row.try_get::<String, _>("at").is_err());
+
+    Ok(())
+}
@@ -1,3 +1,6 @@
+-- https://www.postgresql.org/docs/current/hstore.html
+CREATE EXTENSION IF NOT EXISTS hstore;
+
 -- https://www.postgresql.org/docs/current/sql-createtype.html
 CREATE TYPE status AS ENUM ('new', 'open', 'closed');
 
@@ -9,6 +12,20 @@ CREATE TYPE inventory_item AS
     price       BIGINT
 );
 
+-- a composite type with a field of another composite type
+CREATE TYPE inventory_line AS
+(
+    item     inventory_item,
+    quantity INT
+);
+
+CREATE FUNCTION inventory_line(name TEXT, quantity INT) RETURNS inventory_line
+    LANGUAGE SQL
+AS
+$$
+SELECT ROW (ROW (name, NULL, 199)::inventory_item, quantity)::inventory_line
+$$;
+
 -- https://github.com/prisma/database-schema-examples/tree/master/postgres/basic-twitter#basic-twitter
 CREATE TABLE tweet
 (
@@ -29,3 +46,26 @@ CREATE TABLE products (
     name TEXT,
     price NUMERIC CHECK (price > 0)
 );
+
+-- domains, which are bound and decoded as the types they are defined over
+CREATE DOMAIN email AS TEXT CHECK (VALUE LIKE '%@%');
+CREATE DOMAIN work_email AS email;
+CREATE DOMAIN account_id AS BIGINT CHECK (VALUE > 0);
+
+CREATE TABLE mailboxes (
+    id    account_id PRIMARY KEY,
+    email work_email NOT NULL
+);
+
+INSERT INTO mailboxes (id, email) VALUES (1, 'herp@example.com');
+
+-- a second schema, which the macros check queries against through `ANALYTICS_DATABASE_URL`
+CREATE SCHEMA analytics;
+
+CREATE TABLE analytics.events
+(
+    id   INT PRIMARY KEY,
+    name TEXT NOT NULL
+);
+
+INSERT INTO analytics.events (id, name) VALUES (1, 'signup');
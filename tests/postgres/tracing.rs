@@ -0,0 +1,165 @@
+use sqlx::Postgres;
+use sqlx_test::pool;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use std::thread::{self, ThreadId};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Instrument, Metadata, Subscriber};
+
+#[derive(Debug, Clone)]
+struct RecordedSpan {
+    name: &'static str,
+    parent: Option<usize>,
+    fields: HashMap<&'static str, String>,
+}
+
+// records every span with its parent and fields; the ID of a span is its index plus one
+#[derive(Clone, Default)]
+struct Recorder {
+    spans: Arc<Mutex<Vec<RecordedSpan>>>,
+
+    // the spans entered on each thread, innermost last
+    entered: Arc<Mutex<HashMap<ThreadId, Vec<usize>>>>,
+}
+
+impl Recorder {
+    fn current(&self) -> Option<usize> {
+        let entered = self.entered.lock().unwrap();
+
+        entered
+            .get(&thread::current().id())
+            .and_then(|stack| stack.last().copied())
+    }
+
+    fn spans(&self) -> Vec<RecordedSpan> {
+        self.spans.lock().unwrap().clone()
+    }
+}
+
+struct Fields<'a>(&'a mut HashMap<&'static str, String>);
+
+impl Visit for Fields<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name(), value.to_owned());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0.insert(field.name(), format!("{:?}", value));
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, attrs: &Attributes<'_>) -> Id {
+        let parent = match attrs.parent() {
+            Some(parent) => Some(parent.into_u64() as usize - 1),
+            None if attrs.is_contextual() => self.current(),
+            None => None,
+        };
+
+        let mut fields = HashMap::new();
+        attrs.record(&mut Fields(&mut fields));
+
+        let mut spans = self.spans.lock().unwrap();
+
+        spans.push(RecordedSpan {
+            name: attrs.metadata().name(),
+            parent,
+            fields,
+        });
+
+        Id::from_u64(spans.len() as u64)
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        let mut spans = self.spans.lock().unwrap();
+        let span = &mut spans[span.into_u64() as usize - 1];
+
+        values.record(&mut Fields(&mut span.fields));
+    }
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, _event: &Event<'_>) {}
+
+    fn enter(&self, span: &Id) {
+        self.entered
+            .lock()
+            .unwrap()
+            .entry(thread::current().id())
+            .or_default()
+            .push(span.into_u64() as usize - 1);
+    }
+
+    fn exit(&self, _span: &Id) {
+        if let Some(stack) = self
+            .entered
+            .lock()
+            .unwrap()
+            .get_mut(&thread::current().id())
+        {
+            stack.pop();
+        }
+    }
+}
+
+#[sqlx_macros::test]
+async fn it_records_spans_for_a_pooled_query() -> anyhow::Result<()> {
+    let recorder = Recorder::default();
+    tracing::subscriber::set_global_default(recorder.clone())?;
+
+    let pool = pool::<Postgres>().await?;
+
+    let value: i32 = sqlx::query_scalar("SELECT $1::int4 + 1")
+        .bind(41_i32)
+        .fetch_one(&pool)
+        .instrument(tracing::info_span!("request"))
+        .await?;
+
+    assert_eq!(value, 42);
+
+    let spans = recorder.spans();
+    let position = |name: &str, parent: Option<usize>| {
+        spans
+            .iter()
+            .position(|span| span.name == name && span.parent == parent)
+            .unwrap_or_else(|| panic!("no span {} under {:?} in {:#?}", name, parent, spans))
+    };
+
+    let request = position("request", None);
+
+    // the connection is acquired and the query is run in the span of the caller
+    let acquire = &spans[position("sqlx.acquire", Some(request))];
+
+    assert_eq!(acquire.fields["db.system"], "postgresql");
+    assert!(acquire
+        .fields
+        .contains_key("db.client.connections.wait_time"));
+
+    let fetch = position("sqlx.fetch", Some(request));
+
+    assert_eq!(spans[fetch].fields["db.system"], "postgresql");
+    assert_eq!(spans[fetch].fields["db.statement"], "SELECT $1::int4 + 1");
+
+    // the statement is prepared as part of running it the first time
+    let prepare = &spans[position("sqlx.prepare", Some(fetch))];
+
+    assert_eq!(prepare.fields["db.statement"], "SELECT $1::int4 + 1");
+
+    // the connection was opened when the pool was created, before the request
+    let connect = spans
+        .iter()
+        .find(|span| span.name == "sqlx.connect")
+        .expect("no connect span");
+
+    assert_eq!(connect.fields["db.system"], "postgresql");
+    assert!(connect.fields.contains_key("net.peer.name"));
+    assert_ne!(connect.parent, Some(request));
+
+    Ok(())
+}
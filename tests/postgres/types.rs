@@ -4,9 +4,9 @@ use std::ops::Bound;
 #[cfg(feature = "decimal")]
 use std::str::FromStr;
 
-use sqlx::postgres::types::{PgInterval, PgMoney, PgRange};
+use sqlx::postgres::types::{PgInterval, PgMoney, PgOid, PgRange};
 use sqlx::postgres::Postgres;
-use sqlx_test::{test_decode_type, test_prepared_type, test_type};
+use sqlx_test::{test_decode_type, test_prepared_type, test_type, test_unprepared_type};
 
 test_type!(null<Option<i16>>(Postgres,
     "NULL::int2" == None::<i16>
@@ -71,8 +71,40 @@ test_type!(i8(
     "120::\"char\"" == 120_i8,
 ));
 
+test_type!(char(
+    Postgres,
+    "'a'::\"char\"" == 'a',
+    "'~'::\"char\"" == '~',
+    "'\u{e9}'::text" == '\u{e9}',
+    "'\u{1f980}'::varchar" == '\u{1f980}',
+));
+
+test_decode_type!(char_bpchar<char>(
+    Postgres,
+    "'a'::char(1)" == 'a',
+    "'\u{e9}'::char(1)" == '\u{e9}',
+));
+
 test_type!(u32(Postgres, "325235::oid" == 325235_u32,));
 
+test_type!(oid<PgOid>(Postgres, "325235::oid" == PgOid(325235)));
+
+test_type!(oid_vec<Vec<PgOid>>(Postgres,
+    "'{1,2,3}'::oid[]" == vec![PgOid(1), PgOid(2), PgOid(3)],
+));
+
+test_prepared_type!(regclass<PgOid>(Postgres,
+    "'pg_class'::regclass" == PgOid(1259),
+    "'int4'::regtype" == PgOid(23),
+));
+
+test_unprepared_type!(regclass_name<String>(Postgres,
+    "'pg_class'::regclass" == "pg_class",
+    "'int4'::regtype" == "integer",
+));
+
+test_decode_type!(name<String>(Postgres, "'pg_class'::name" == "pg_class"));
+
 test_type!(i16(
     Postgres,
     "-2144::smallint" == -2144_i16,
@@ -175,6 +207,24 @@ test_type!(mac_address<sqlx::types::mac_address::MacAddress>(Postgres,
             .unwrap()
 ));
 
+#[cfg(feature = "hstore")]
+test_prepared_type!(hstore<std::collections::HashMap<String, Option<String>>>(Postgres,
+    "''::hstore" == std::collections::HashMap::<String, Option<String>>::new(),
+    "'a=>1, b=>NULL'::hstore" == vec![
+        ("a".to_owned(), Some("1".to_owned())),
+        ("b".to_owned(), None),
+    ].into_iter().collect::<std::collections::HashMap<_, _>>(),
+    r#"'"ключ"=>"значение", "🦀"=>""'::hstore"# == vec![
+        ("ключ".to_owned(), Some("значение".to_owned())),
+        ("🦀".to_owned(), Some("".to_owned())),
+    ].into_iter().collect::<std::collections::HashMap<_, _>>(),
+    // sent and read in the binary format, none of this is escaped
+    r#"'"a=>b"=>"\"quoted\"", "x, y"=>"NULL"'::hstore"# == vec![
+        ("a=>b".to_owned(), Some("\"quoted\"".to_owned())),
+        ("x, y".to_owned(), Some("NULL".to_owned())),
+    ].into_iter().collect::<std::collections::HashMap<_, _>>(),
+));
+
 #[cfg(feature = "bit-vec")]
 test_type!(bitvec<sqlx::types::BitVec>(
     Postgres,
@@ -229,15 +279,19 @@ mod chrono {
 
     test_type!(chrono_date<NaiveDate>(Postgres,
         "DATE '2001-01-05'" == NaiveDate::from_ymd(2001, 1, 5),
-        "DATE '2050-11-23'" == NaiveDate::from_ymd(2050, 11, 23)
+        "DATE '2050-11-23'" == NaiveDate::from_ymd(2050, 11, 23),
+        "DATE '1969-07-20'" == NaiveDate::from_ymd(1969, 7, 20)
     ));
 
     test_type!(chrono_time<NaiveTime>(Postgres,
-        "TIME '05:10:20.115100'" == NaiveTime::from_hms_micro(5, 10, 20, 115100)
+        "TIME '05:10:20.115100'" == NaiveTime::from_hms_micro(5, 10, 20, 115100),
+        "TIME '23:59:59.999999'" == NaiveTime::from_hms_micro(23, 59, 59, 999999)
     ));
 
     test_type!(chrono_date_time<NaiveDateTime>(Postgres,
-        "'2019-01-02 05:10:20'::timestamp" == NaiveDate::from_ymd(2019, 1, 2).and_hms(5, 10, 20)
+        "'2019-01-02 05:10:20'::timestamp" == NaiveDate::from_ymd(2019, 1, 2).and_hms(5, 10, 20),
+        "'1969-07-20 20:17:40.999999'::timestamp"
+            == NaiveDate::from_ymd(1969, 7, 20).and_hms_micro(20, 17, 40, 999999)
     ));
 
     test_type!(chrono_date_time_vec<Vec<NaiveDateTime>>(Postgres,
@@ -287,19 +341,23 @@ mod time_tests {
     test_type!(time_date<Date>(
         Postgres,
         "DATE '2001-01-05'" == date!(2001 - 1 - 5),
-        "DATE '2050-11-23'" == date!(2050 - 11 - 23)
+        "DATE '2050-11-23'" == date!(2050 - 11 - 23),
+        "DATE '1969-07-20'" == date!(1969 - 7 - 20)
     ));
 
     test_type!(time_time<Time>(
         Postgres,
-        "TIME '05:10:20.115100'" == time!(5:10:20.115100)
+        "TIME '05:10:20.115100'" == time!(5:10:20.115100),
+        "TIME '23:59:59.999999'" == time!(23:59:59.999999)
     ));
 
     test_type!(time_date_time<PrimitiveDateTime>(
         Postgres,
         "TIMESTAMP '2019-01-02 05:10:20'" == date!(2019 - 1 - 2).with_time(time!(5:10:20)),
         "TIMESTAMP '2019-01-02 05:10:20.115100'"
-            == date!(2019 - 1 - 2).with_time(time!(5:10:20.115100))
+            == date!(2019 - 1 - 2).with_time(time!(5:10:20.115100)),
+        "TIMESTAMP '1969-07-20 20:17:40.999999'"
+            == date!(1969 - 7 - 20).with_time(time!(20:17:40.999999))
     ));
 
     test_type!(time_timestamp<OffsetDateTime>(
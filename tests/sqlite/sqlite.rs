@@ -1,6 +1,7 @@
 use futures::TryStreamExt;
 use rand::{Rng, SeedableRng};
 use rand_xoshiro::Xoshiro256PlusPlus;
+use sqlx::pool::ReadWriteSplit;
 use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
 use sqlx::{
     query, sqlite::Sqlite, sqlite::SqliteRow, Column, ConnectOptions, Connection, Executor, Row,
@@ -662,3 +663,131 @@ async fn issue_1467() -> anyhow::Result<()> {
     }
     Ok(())
 }
+
+#[cfg(feature = "export")]
+#[sqlx_macros::test]
+async fn it_exports_csv() -> anyhow::Result<()> {
+    let mut conn = new::<Sqlite>().await?;
+    let mut out = Vec::new();
+
+    sqlx::export::csv(
+        query(
+            "SELECT 1 AS id, 'a, \"quoted\" name' AS name, x'DEADBEEF' AS data \
+             UNION ALL SELECT 2, NULL, NULL",
+        ),
+        &mut conn,
+        &mut out,
+    )
+    .await?;
+
+    assert_eq!(
+        String::from_utf8(out)?,
+        "id,name,data\r\n1,\"a, \"\"quoted\"\" name\",3q2+7w==\r\n2,,\r\n"
+    );
+
+    Ok(())
+}
+
+// an in-memory database that tells which node of a `ReadWriteSplit` a query ran on
+async fn node(name: &str) -> anyhow::Result<SqlitePool> {
+    // the only connection of the pool holds the database
+    let pool = SqlitePoolOptions::new()
+        .min_connections(1)
+        .max_connections(1)
+        .connect(":memory:")
+        .await?;
+
+    sqlx::query("CREATE TABLE node (name TEXT NOT NULL)")
+        .execute(&pool)
+        .await?;
+
+    sqlx::query("INSERT INTO node (name) VALUES (?)")
+        .bind(name)
+        .execute(&pool)
+        .await?;
+
+    Ok(pool)
+}
+
+#[sqlx_macros::test]
+async fn it_routes_queries_between_the_primary_and_replicas() -> anyhow::Result<()> {
+    let primary = node("primary").await?;
+    let replica_1 = node("replica 1").await?;
+    let replica_2 = node("replica 2").await?;
+
+    let db = ReadWriteSplit::new(primary.clone(), vec![replica_1.clone(), replica_2]);
+    let select = "SELECT name FROM node";
+
+    // without a hint, nothing runs on a replica
+    let name: String = sqlx::query_scalar(select).fetch_one(&db).await?;
+    assert_eq!(name, "primary");
+
+    let mut names = Vec::new();
+
+    for _ in 0..4 {
+        let name: String = sqlx::query_scalar(select)
+            .read_only(true)
+            .fetch_one(&db)
+            .await?;
+
+        names.push(name);
+    }
+
+    assert_eq!(names, ["replica 1", "replica 2", "replica 1", "replica 2"]);
+
+    // everything in a transaction runs on the primary
+    let mut tx = db.begin().await?;
+
+    let name: String = sqlx::query_scalar(select)
+        .read_only(true)
+        .fetch_one(&mut tx)
+        .await?;
+
+    assert_eq!(name, "primary");
+
+    tx.rollback().await?;
+
+    let auto = db.clone().auto_detect(true);
+
+    let name: String = sqlx::query_scalar(select).fetch_one(&auto).await?;
+    assert_ne!(name, "primary");
+
+    let name: String = sqlx::query_scalar(select)
+        .read_only(false)
+        .fetch_one(&auto)
+        .await?;
+
+    assert_eq!(name, "primary");
+
+    let name: String = sqlx::query_scalar("WITH n AS (SELECT name FROM node) SELECT name FROM n")
+        .fetch_one(&auto)
+        .await?;
+
+    assert_eq!(name, "primary");
+
+    sqlx::query("INSERT INTO node (name) VALUES ('written')")
+        .execute(&auto)
+        .await?;
+
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM node")
+        .fetch_one(&primary)
+        .await?;
+
+    assert_eq!(count, 2);
+
+    // a replica that is down is skipped
+    replica_1.close().await;
+
+    for _ in 0..3 {
+        let name: String = sqlx::query_scalar(select)
+            .read_only(true)
+            .fetch_one(&db)
+            .await?;
+
+        assert_eq!(name, "replica 2");
+    }
+
+    assert_eq!(db.check_replicas().await, 1);
+
+    Ok(())
+}
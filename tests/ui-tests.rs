@@ -7,6 +7,7 @@ fn ui_tests() {
 
     if cfg!(feature = "postgres") {
         t.compile_fail("tests/ui/postgres/*.rs");
+        t.pass("tests/ui/postgres/pass/*.rs");
 
         // UI tests for column types that require gated features
         if cfg!(not(feature = "chrono")) && cfg!(not(feature = "time")) {
@@ -24,6 +25,7 @@ fn ui_tests() {
 
     if cfg!(feature = "mysql") {
         t.compile_fail("tests/ui/mysql/*.rs");
+        t.pass("tests/ui/mysql/pass/*.rs");
 
         // UI tests for column types that require gated features
         if cfg!(not(feature = "chrono")) && cfg!(not(feature = "time")) {
@@ -0,0 +1,17 @@
+use std::borrow::Cow;
+use std::sync::Arc;
+
+fn main() {
+    let _query = sqlx::query!("select CAST(? AS CHAR)", &String::new());
+    let _query = sqlx::query!("select CAST(? AS CHAR)", Box::<str>::from(""));
+    let _query = sqlx::query!("select CAST(? AS CHAR)", Arc::<str>::from(""));
+    let _query = sqlx::query!("select CAST(? AS CHAR)", Cow::Borrowed(""));
+
+    let _query = sqlx::query!("select CAST(? AS BINARY)", &Vec::<u8>::new());
+    let _query = sqlx::query!("select CAST(? AS BINARY)", Box::<[u8]>::from(&[][..]));
+    let _query = sqlx::query!("select CAST(? AS BINARY)", Arc::<[u8]>::from(&[][..]));
+    let _query = sqlx::query!("select CAST(? AS BINARY)", Cow::Borrowed(&[][..]));
+
+    let _query = sqlx::query!("select CAST(? AS SIGNED)", Box::new(0i64));
+    let _query = sqlx::query!("select CAST(? AS SIGNED)", Arc::new(0i64));
+}
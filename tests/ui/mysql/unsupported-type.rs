@@ -0,0 +1,4 @@
+fn main() {
+    // the spatial types are not supported
+    let _ = sqlx::query!("select ST_GeomFromText('POINT(1 1)') point");
+}
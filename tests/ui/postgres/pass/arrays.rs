@@ -0,0 +1,13 @@
+fn main() {
+    let uuid = [0u8; 16];
+
+    let _query = sqlx::query!("select $1::bytea", uuid);
+    let _query = sqlx::query!("select $1::bytea", &uuid);
+    let _query = sqlx::query!("select $1::bytea", Some(uuid));
+
+    let _query = sqlx::query!("select $1::int8[]", [1i64, 2, 3]);
+    let _query = sqlx::query!("select $1::int8[]", &[1i64, 2, 3]);
+    let _query = sqlx::query!("select $1::int8[]", &[1i64, 2, 3][..]);
+    let _query = sqlx::query!("select $1::int8[]", vec![1i64, 2, 3]);
+    let _query = sqlx::query!("select $1::int8[]", Some([1i64, 2, 3]));
+}
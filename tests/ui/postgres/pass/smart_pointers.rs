@@ -0,0 +1,19 @@
+use std::borrow::Cow;
+use std::sync::Arc;
+
+fn main() {
+    let _query = sqlx::query!("select $1::text", &String::new());
+    let _query = sqlx::query!("select $1::text", Box::<str>::from(""));
+    let _query = sqlx::query!("select $1::text", Arc::<str>::from(""));
+    let _query = sqlx::query!("select $1::text", Cow::Borrowed(""));
+    let _query = sqlx::query!("select $1::text", Some(Arc::<str>::from("")));
+
+    let _query = sqlx::query!("select $1::bytea", &Vec::<u8>::new());
+    let _query = sqlx::query!("select $1::bytea", Box::<[u8]>::from(&[][..]));
+    let _query = sqlx::query!("select $1::bytea", Arc::<[u8]>::from(&[][..]));
+    let _query = sqlx::query!("select $1::bytea", Cow::Borrowed(&[][..]));
+
+    let _query = sqlx::query!("select $1::int4", Box::new(0i32));
+    let _query = sqlx::query!("select $1::int4", Arc::new(0i32));
+    let _query = sqlx::query!("select $1::int4", Some(Box::new(0i32)));
+}
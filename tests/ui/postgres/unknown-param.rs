@@ -0,0 +1,4 @@
+fn main() {
+    // the type of `$1` cannot be inferred from the query
+    let _ = sqlx::query!("SELECT $1 IS NULL", 1i32);
+}
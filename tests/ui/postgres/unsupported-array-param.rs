@@ -0,0 +1,4 @@
+fn main() {
+    // multi-dimensional arrays are not supported
+    let _ = sqlx::query_unchecked!("select $1::int8[]", [[0i64; 2]; 2]);
+}
@@ -0,0 +1,5 @@
+struct Foo;
+
+fn main() {
+    let _ = sqlx::query_unchecked!("select $1::text", Foo);
+}
@@ -49,6 +49,11 @@ def run(command, comment=None, env=None, service=None, tag=None, args=None, data
 
         environ["DATABASE_URL"] = database_url
 
+        if service.startswith("postgres"):
+            # the same database with the schema of the `url_env` macro tests on the search path
+            separator = "&" if "?" in database_url else "?"
+            environ["ANALYTICS_DATABASE_URL"] = database_url + separator + "options[search_path]=analytics"
+
         # show the database url
         print(f"\x1b[94m @ {database_url}\x1b[0m")
 